@@ -18,6 +18,36 @@ pub struct Cli {
     /// 安静模式 (只输出错误)
     #[arg(short, long, global = true)]
     pub quiet: bool,
+
+    /// lency_runtime 库文件或所在目录 (覆盖 LENCY_RUNTIME 和默认的 target 目录搜索)
+    #[arg(long, global = true, value_name = "PATH")]
+    pub runtime_path: Option<String>,
+
+    /// LLVM 工具版本后缀，如 `18` 表示使用 lli-18/llc-18 (覆盖 LENCY_LLVM_SUFFIX)
+    #[arg(long, global = true, value_name = "SUFFIX")]
+    pub llvm_suffix: Option<String>,
+
+    /// 调试失控递归：调用深度超过 DEPTH (默认 10000) 时 panic 并报告函数名，而不是段错误
+    #[arg(
+        long,
+        global = true,
+        value_name = "DEPTH",
+        num_args = 0..=1,
+        default_missing_value = "10000"
+    )]
+    pub stack_guard: Option<u64>,
+
+    /// 编译后打印代码生成统计：函数、基本块、指令数量与模块大小
+    #[arg(long, global = true)]
+    pub stats: bool,
+
+    /// 打印词法分析得到的 token 流 (调试用)
+    #[arg(long, global = true, hide = true)]
+    pub dump_tokens: bool,
+
+    /// 解析后打印语法树 (调试用)
+    #[arg(long, global = true, hide = true)]
+    pub dump_ast: bool,
 }
 
 #[derive(Subcommand)]
@@ -72,8 +102,25 @@ pub enum Commands {
         /// 仅做语法/语义检查，不产出可执行文件
         #[arg(long)]
         check_only: bool,
+
+        /// 静态链接 lency_runtime (.a)，产出不依赖 runtime 动态库的可执行文件
+        #[arg(long)]
+        static_runtime: bool,
     },
 
     /// 交互式 REPL (实验性)
     Repl,
 }
+
+impl Commands {
+    /// 子命令的输入文件（REPL 没有输入文件）
+    pub fn input(&self) -> Option<&str> {
+        match self {
+            Commands::Compile { input, .. }
+            | Commands::Run { input, .. }
+            | Commands::Check { input }
+            | Commands::Build { input, .. } => Some(input),
+            Commands::Repl => None,
+        }
+    }
+}
@@ -1,11 +1,14 @@
 use anyhow::{bail, Result};
+use lency_driver::CodegenOptions;
 use std::fs;
+use std::path::Path;
+use std::process::Command;
 
 use crate::path_utils::resolve_output_path;
 
 use super::{
     check::cmd_check,
-    common::{compile_to_llvm_ir, find_runtime_library, require_tool, temp_artifact_path},
+    common::{compile_to_llvm_ir, require_tool, temp_artifact_path, RuntimeUse, Toolchain},
 };
 
 /// 构建命令 - 生成可执行文件
@@ -15,6 +18,9 @@ pub fn cmd_build(
     out_dir: Option<&str>,
     release: bool,
     check_only: bool,
+    static_runtime: bool,
+    toolchain: &Toolchain,
+    options: &CodegenOptions,
 ) -> Result<()> {
     if check_only {
         println!("Building {} (check-only=true) ...", input);
@@ -23,13 +29,18 @@ pub fn cmd_build(
 
     println!("Building {} (release={}) ...", input, release);
 
-    let ir = compile_to_llvm_ir(input)?;
+    let ir = compile_to_llvm_ir(input, options)?;
+    let runtime_lib = toolchain.runtime(if static_runtime {
+        RuntimeUse::StaticLink
+    } else {
+        RuntimeUse::Link
+    })?;
     let temp_ll = temp_artifact_path("ll")?;
     fs::write(&temp_ll, ir)?;
 
     println!("  Generating object file...");
     let temp_obj = temp_artifact_path("o")?;
-    let llc = require_tool(&["llc-15", "llc"], "LLVM static compiler (llc)")?;
+    let llc = toolchain.llvm_tool("llc", "LLVM static compiler (llc)")?;
     let mut llc_cmd = std::process::Command::new(llc);
     llc_cmd.args(["-filetype=obj"]);
     if release {
@@ -46,37 +57,17 @@ pub fn cmd_build(
         bail!("llc compilation failed");
     }
 
-    let runtime_lib = find_runtime_library();
-    if runtime_lib.is_none() {
-        eprintln!("Warning: lency_runtime library not found in target dir. Linking might fail.");
-    }
-
     println!("  Linking executable...");
     let output_path = resolve_output_path(output, out_dir)?;
-    let output_str = output_path.to_string_lossy().into_owned();
 
     let linker = require_tool(&["gcc", "clang"], "linker (gcc/clang)")?;
-    let mut gcc_cmd = std::process::Command::new(linker);
-    gcc_cmd.args([
-        temp_obj.to_string_lossy().as_ref(),
-        "-o",
-        output_str.as_str(),
-    ]);
-    if !cfg!(windows) {
-        gcc_cmd.arg("-no-pie");
-    }
-
-    if let Some(lib_path) = runtime_lib {
-        if cfg!(windows) {
-            // On Windows (MSVC/clang), link directly with the import/static library file.
-            gcc_cmd.arg(&lib_path);
-        } else if let Some(path) = lib_path.parent() {
-            gcc_cmd.arg(format!("-L{}", path.display()));
-            gcc_cmd.arg("-llency_runtime");
-            gcc_cmd.arg(format!("-Wl,-rpath,{}", path.display()));
-        }
-    }
-
+    let mut gcc_cmd = link_command(
+        &linker,
+        &temp_obj,
+        &output_path,
+        &runtime_lib,
+        static_runtime,
+    );
     let gcc_status = gcc_cmd.status()?;
     if !gcc_status.success() {
         bail!("Linking failed - please ensure lency_runtime is built");
@@ -88,3 +79,101 @@ pub fn cmd_build(
     println!("Successfully built: {}", output_path.display());
     Ok(())
 }
+
+/// 静态链接 Rust runtime 时需要补上的系统库（见 `rustc --print native-static-libs`）
+const STATIC_RUNTIME_SYSTEM_LIBS: &[&str] = if cfg!(windows) {
+    &[
+        "-lkernel32",
+        "-ladvapi32",
+        "-lntdll",
+        "-luserenv",
+        "-lws2_32",
+        "-lbcrypt",
+    ]
+} else if cfg!(target_os = "macos") {
+    &["-lSystem", "-lc", "-lm"]
+} else {
+    &[
+        "-lgcc_s",
+        "-lutil",
+        "-lrt",
+        "-lpthread",
+        "-lm",
+        "-ldl",
+        "-lc",
+    ]
+};
+
+/// 构造链接命令
+///
+/// 始终直接链接库文件本身，这样 --runtime-path 指向任意文件名时也能工作；
+/// 动态链接时写入 rpath，静态链接时改为补齐系统库且不写 rpath。
+fn link_command(
+    linker: &Path,
+    object: &Path,
+    output: &Path,
+    runtime_lib: &Path,
+    static_runtime: bool,
+) -> Command {
+    let mut cmd = Command::new(linker);
+    cmd.arg(object).arg("-o").arg(output);
+    if !cfg!(windows) {
+        cmd.arg("-no-pie");
+    }
+
+    cmd.arg(runtime_lib);
+    if static_runtime {
+        cmd.args(STATIC_RUNTIME_SYSTEM_LIBS);
+    } else if !cfg!(windows) {
+        if let Some(path) = runtime_lib.parent() {
+            cmd.arg(format!("-Wl,-rpath,{}", path.display()));
+        }
+    }
+    cmd
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args_of(cmd: &Command) -> Vec<String> {
+        cmd.get_args()
+            .map(|a| a.to_string_lossy().into_owned())
+            .collect()
+    }
+
+    #[test]
+    fn test_static_link_uses_archive_without_rpath() {
+        let cmd = link_command(
+            Path::new("gcc"),
+            Path::new("main.o"),
+            Path::new("app"),
+            Path::new("/opt/lency/liblency_runtime.a"),
+            true,
+        );
+        let args = args_of(&cmd);
+        assert!(args.contains(&"/opt/lency/liblency_runtime.a".to_string()));
+        assert!(!args.iter().any(|a| a.contains("rpath")));
+        assert!(!args.iter().any(|a| a == "-llency_runtime"));
+        for lib in STATIC_RUNTIME_SYSTEM_LIBS {
+            assert!(args.contains(&lib.to_string()));
+        }
+    }
+
+    #[test]
+    fn test_dynamic_link_sets_rpath() {
+        let cmd = link_command(
+            Path::new("gcc"),
+            Path::new("main.o"),
+            Path::new("app"),
+            Path::new("/opt/lency/liblency_runtime.so"),
+            false,
+        );
+        let args = args_of(&cmd);
+        assert!(args.contains(&"/opt/lency/liblency_runtime.so".to_string()));
+        if !cfg!(windows) {
+            assert!(args.contains(&"-Wl,-rpath,/opt/lency".to_string()));
+        }
+        assert!(!args.contains(&"-lpthread".to_string()));
+    }
+}
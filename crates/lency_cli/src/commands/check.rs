@@ -15,7 +15,10 @@ pub fn cmd_check(input: &str) -> Result<()> {
 
     let source = fs::read_to_string(input)?;
     match lency_driver::compile(&source) {
-        Ok(_) => {
+        Ok(output) => {
+            for warning in &output.warnings {
+                eprintln!("warning: {}", warning);
+            }
             println!("No errors found");
             Ok(())
         }
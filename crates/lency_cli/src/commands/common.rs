@@ -1,38 +1,21 @@
 use crate::lir_backend;
 use anyhow::{bail, Context, Result};
-use lency_driver::compile_file;
+use lency_driver::{compile_file_with_options, CodegenOptions};
+use std::fs;
+use std::path::{Path, PathBuf};
 use std::time::{SystemTime, UNIX_EPOCH};
-use std::{fs, path::PathBuf};
 
-pub fn compile_to_llvm_ir(input: &str) -> Result<String> {
+pub fn compile_to_llvm_ir(input: &str, options: &CodegenOptions) -> Result<String> {
     if input.ends_with(".lir") {
         let source = fs::read_to_string(input)?;
         return lir_backend::compile_lir_to_llvm_ir(&source);
     }
-    Ok(compile_file(input)?.ir)
-}
-
-pub fn find_runtime_dir() -> Option<PathBuf> {
-    let cwd = std::env::current_dir().ok()?;
-    let dirs = ["target/release", "target/debug"];
-    let libs = [
-        "liblency_runtime.so",
-        "liblency_runtime.dylib",
-        "liblency_runtime.a",
-        "lency_runtime.dll.lib",
-        "lency_runtime.lib",
-        "lency_runtime.dll",
-    ];
-
-    for dir in dirs {
-        for lib in libs {
-            let path = cwd.join(dir).join(lib);
-            if path.exists() {
-                return Some(cwd.join(dir));
-            }
-        }
+    let output = compile_file_with_options(input, options)?;
+    // 统计输出到 stderr，不与 run 的程序输出混在一起
+    if let Some(stats) = output.stats {
+        eprintln!("{}", stats);
     }
-    None
+    Ok(output.ir)
 }
 
 pub fn temp_artifact_path(ext: &str) -> Result<PathBuf> {
@@ -65,6 +48,52 @@ pub fn require_tool(candidates: &[&str], tool_desc: &str) -> Result<PathBuf> {
     )
 }
 
+/// 覆盖 LLVM 工具版本后缀的环境变量
+pub const LLVM_SUFFIX_ENV: &str = "LENCY_LLVM_SUFFIX";
+
+/// 未指定版本后缀时依次探测的 LLVM 大版本（从新到旧）
+const LLVM_VERSIONS: [u32; 4] = [18, 17, 16, 15];
+
+/// 命令行上与外部工具链相关的选项
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Toolchain<'a> {
+    /// `--runtime-path`
+    pub runtime_path: Option<&'a str>,
+    /// `--llvm-suffix`
+    pub llvm_suffix: Option<&'a str>,
+}
+
+impl Toolchain<'_> {
+    /// 查找 LLVM 工具（如 `lli`、`llc`）
+    ///
+    /// 优先级：`--llvm-suffix` > `LENCY_LLVM_SUFFIX` > 依次探测 `tool`、`tool-18` … `tool-15`。
+    pub fn llvm_tool(&self, tool: &str, tool_desc: &str) -> Result<PathBuf> {
+        let env = std::env::var(LLVM_SUFFIX_ENV).ok();
+        let suffix = self.llvm_suffix.or(env.as_deref());
+        let candidates = llvm_tool_candidates(tool, suffix);
+        let candidates: Vec<&str> = candidates.iter().map(String::as_str).collect();
+        require_tool(&candidates, tool_desc)
+    }
+
+    /// 查找 lency_runtime 库文件，见 [`find_runtime`]
+    pub fn runtime(&self, usage: RuntimeUse) -> Result<PathBuf> {
+        find_runtime(self.runtime_path, usage)
+    }
+}
+
+/// LLVM 工具的候选文件名，按探测顺序排列
+///
+/// 指定后缀时只探测该版本；后缀可写成 `18` 或 `-18`，空后缀表示不带版本号的工具名。
+fn llvm_tool_candidates(tool: &str, suffix: Option<&str>) -> Vec<String> {
+    match suffix.map(|s| s.trim().trim_start_matches('-')) {
+        Some("") => vec![tool.to_string()],
+        Some(suffix) => vec![format!("{tool}-{suffix}")],
+        None => std::iter::once(tool.to_string())
+            .chain(LLVM_VERSIONS.iter().map(|v| format!("{tool}-{v}")))
+            .collect(),
+    }
+}
+
 fn find_tool(candidates: &[&str]) -> Option<PathBuf> {
     if let Ok(prefix) = std::env::var("LLVM_SYS_150_PREFIX") {
         let bin = PathBuf::from(prefix).join("bin");
@@ -88,30 +117,109 @@ fn find_tool(candidates: &[&str]) -> Option<PathBuf> {
     None
 }
 
-pub fn find_runtime_library() -> Option<PathBuf> {
-    let cwd = std::env::current_dir().ok()?;
-    let dirs = ["target/release", "target/debug"];
-    let libs: &[&str] = if cfg!(windows) {
-        &[
-            "lency_runtime.dll.lib",
-            "lency_runtime.lib",
-            "liblency_runtime.a",
-        ]
-    } else if cfg!(target_os = "macos") {
-        &["liblency_runtime.dylib", "liblency_runtime.a"]
-    } else {
-        &["liblency_runtime.so", "liblency_runtime.a"]
-    };
-
-    for dir in dirs {
-        for lib in libs {
-            let path = cwd.join(dir).join(lib);
-            if path.exists() {
-                return Some(path);
+/// 覆盖 runtime 库搜索路径的环境变量
+pub const RUNTIME_ENV: &str = "LENCY_RUNTIME";
+
+/// runtime 库的用途：`run` 需要交给 lli 装载的动态库，`build` 需要可链接的库
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuntimeUse {
+    Load,
+    Link,
+    /// 只接受静态库，产出不依赖 runtime 动态库的可执行文件
+    StaticLink,
+}
+
+impl RuntimeUse {
+    fn library_names(self) -> &'static [&'static str] {
+        match self {
+            RuntimeUse::Load => &["liblency_runtime.so", "liblency_runtime.dylib"],
+            RuntimeUse::Link if cfg!(windows) => &[
+                "lency_runtime.dll.lib",
+                "lency_runtime.lib",
+                "liblency_runtime.a",
+            ],
+            RuntimeUse::Link if cfg!(target_os = "macos") => {
+                &["liblency_runtime.dylib", "liblency_runtime.a"]
             }
+            RuntimeUse::Link => &["liblency_runtime.so", "liblency_runtime.a"],
+            RuntimeUse::StaticLink if cfg!(windows) => &["lency_runtime.lib"],
+            RuntimeUse::StaticLink => &["liblency_runtime.a"],
         }
     }
-    None
+}
+
+/// 查找 lency_runtime 库文件
+///
+/// 优先级：`--runtime-path` > `LENCY_RUNTIME` > 当前目录下的 `target/release`、`target/debug`。
+/// 找不到时直接报错，而不是带着警告继续走到链接失败。
+pub fn find_runtime(explicit: Option<&str>, usage: RuntimeUse) -> Result<PathBuf> {
+    let env = std::env::var_os(RUNTIME_ENV).map(PathBuf::from);
+    let cwd = std::env::current_dir().context("failed to get current directory")?;
+    let search_dirs = [cwd.join("target/release"), cwd.join("target/debug")];
+    resolve_runtime(
+        explicit.map(Path::new),
+        env.as_deref(),
+        &search_dirs,
+        usage.library_names(),
+    )
+}
+
+/// 按优先级解析 runtime 库路径（不读取进程环境，便于测试）
+///
+/// 显式给出的路径（命令行或环境变量）可以是库文件或其所在目录；
+/// 一旦给出就必须有效，不会退回到默认搜索。
+fn resolve_runtime(
+    flag: Option<&Path>,
+    env: Option<&Path>,
+    search_dirs: &[PathBuf],
+    names: &[&str],
+) -> Result<PathBuf> {
+    let overrides = [(flag, "--runtime-path"), (env, RUNTIME_ENV)];
+    if let Some((path, source)) = overrides
+        .into_iter()
+        .find_map(|(path, source)| path.map(|p| (p, source)))
+    {
+        if path.is_file() {
+            return Ok(path.to_path_buf());
+        }
+        if path.is_dir() {
+            return find_library_in(path, names).with_context(|| {
+                format!(
+                    "lency_runtime library not found in {} (from {source}); expected one of [{}]",
+                    path.display(),
+                    names.join(", ")
+                )
+            });
+        }
+        bail!(
+            "lency_runtime path does not exist: {} (from {source})",
+            path.display()
+        );
+    }
+
+    if let Some(path) = search_dirs
+        .iter()
+        .find_map(|dir| find_library_in(dir, names))
+    {
+        return Ok(path);
+    }
+
+    let searched: Vec<String> = search_dirs
+        .iter()
+        .map(|d| d.display().to_string())
+        .collect();
+    bail!(
+        "lency_runtime library not found: searched [{}] for [{}]; build it with `cargo build -p lency_runtime`, or pass --runtime-path / set {RUNTIME_ENV}",
+        searched.join(", "),
+        names.join(", ")
+    )
+}
+
+fn find_library_in(dir: &Path, names: &[&str]) -> Option<PathBuf> {
+    names
+        .iter()
+        .map(|name| dir.join(name))
+        .find(|path| path.is_file())
 }
 
 fn with_platform_names(candidates: &[&str]) -> Vec<String> {
@@ -124,3 +232,6 @@ fn with_platform_names(candidates: &[&str]) -> Vec<String> {
     }
     out
 }
+
+#[cfg(test)]
+mod tests;
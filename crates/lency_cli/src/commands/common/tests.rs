@@ -0,0 +1,135 @@
+use super::*;
+
+const NAMES: &[&str] = &["liblency_runtime.so", "liblency_runtime.a"];
+
+/// 在 tmp 下建立互不干扰的测试目录
+fn scratch_dir(name: &str) -> Result<PathBuf> {
+    let dir = std::env::temp_dir().join(format!(
+        "lency_runtime_lookup_{}_{}",
+        std::process::id(),
+        name
+    ));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+fn touch(dir: &Path, name: &str) -> Result<PathBuf> {
+    let path = dir.join(name);
+    fs::write(&path, b"")?;
+    Ok(path)
+}
+
+/// 期望查找失败，返回错误信息
+fn lookup_error(
+    flag: Option<&Path>,
+    env: Option<&Path>,
+    search_dirs: &[PathBuf],
+) -> Result<String> {
+    match resolve_runtime(flag, env, search_dirs, NAMES) {
+        Ok(path) => bail!("expected lookup to fail, found {}", path.display()),
+        Err(err) => Ok(err.to_string()),
+    }
+}
+
+#[test]
+fn test_flag_takes_precedence_over_env_and_search() -> Result<()> {
+    let root = scratch_dir("flag")?;
+    let flag_dir = root.join("flag");
+    let env_dir = root.join("env");
+    let search_dir = root.join("search");
+    for dir in [&flag_dir, &env_dir, &search_dir] {
+        fs::create_dir_all(dir)?;
+    }
+    let expected = touch(&flag_dir, "liblency_runtime.a")?;
+    touch(&env_dir, "liblency_runtime.so")?;
+    touch(&search_dir, "liblency_runtime.so")?;
+
+    let found = resolve_runtime(
+        Some(&flag_dir),
+        Some(&env_dir),
+        std::slice::from_ref(&search_dir),
+        NAMES,
+    )?;
+    assert_eq!(found, expected);
+    let _ = fs::remove_dir_all(&root);
+    Ok(())
+}
+
+#[test]
+fn test_env_used_when_no_flag() -> Result<()> {
+    let root = scratch_dir("env")?;
+    let search_dir = root.join("search");
+    fs::create_dir_all(&search_dir)?;
+    touch(&search_dir, "liblency_runtime.so")?;
+    // 环境变量直接指向库文件
+    let lib = touch(&root, "custom_runtime.so")?;
+
+    let found = resolve_runtime(None, Some(&lib), std::slice::from_ref(&search_dir), NAMES)?;
+    assert_eq!(found, lib);
+    let _ = fs::remove_dir_all(&root);
+    Ok(())
+}
+
+#[test]
+fn test_search_dirs_in_order() -> Result<()> {
+    let root = scratch_dir("search")?;
+    let release = root.join("release");
+    let debug = root.join("debug");
+    fs::create_dir_all(&release)?;
+    fs::create_dir_all(&debug)?;
+    let debug_lib = touch(&debug, "liblency_runtime.so")?;
+
+    let dirs = [release.clone(), debug.clone()];
+    assert_eq!(resolve_runtime(None, None, &dirs, NAMES)?, debug_lib);
+
+    let release_lib = touch(&release, "liblency_runtime.a")?;
+    assert_eq!(resolve_runtime(None, None, &dirs, NAMES)?, release_lib);
+    let _ = fs::remove_dir_all(&root);
+    Ok(())
+}
+
+#[test]
+fn test_invalid_override_does_not_fall_back() -> Result<()> {
+    let root = scratch_dir("invalid")?;
+    let search_dir = root.join("search");
+    fs::create_dir_all(&search_dir)?;
+    touch(&search_dir, "liblency_runtime.so")?;
+    let missing = root.join("missing");
+
+    let message = lookup_error(Some(&missing), None, std::slice::from_ref(&search_dir))?;
+    assert!(message.contains("--runtime-path"));
+
+    // 目录存在但没有库文件
+    let empty = root.join("empty");
+    fs::create_dir_all(&empty)?;
+    let message = lookup_error(None, Some(&empty), std::slice::from_ref(&search_dir))?;
+    assert!(message.contains(RUNTIME_ENV));
+    let _ = fs::remove_dir_all(&root);
+    Ok(())
+}
+
+#[test]
+fn test_llvm_tool_default_probing_order() {
+    assert_eq!(
+        llvm_tool_candidates("lli", None),
+        ["lli", "lli-18", "lli-17", "lli-16", "lli-15"]
+    );
+}
+
+#[test]
+fn test_llvm_tool_explicit_suffix() {
+    assert_eq!(llvm_tool_candidates("llc", Some("17")), ["llc-17"]);
+    assert_eq!(llvm_tool_candidates("llc", Some("-16")), ["llc-16"]);
+    assert_eq!(llvm_tool_candidates("llc", Some("")), ["llc"]);
+}
+
+#[test]
+fn test_not_found_is_an_error() -> Result<()> {
+    let root = scratch_dir("none")?;
+    let message = lookup_error(None, None, std::slice::from_ref(&root))?;
+    assert!(message.contains("lency_runtime library not found"));
+    assert!(message.contains("--runtime-path"));
+    let _ = fs::remove_dir_all(&root);
+    Ok(())
+}
@@ -0,0 +1,23 @@
+use anyhow::Result;
+use std::fs;
+
+/// 打印输入文件的语法树 (`--dump-ast`)
+///
+/// 只打印 `.lcy` 源文件；`.lir` 没有 Lency 语法树，直接跳过。
+pub fn cmd_dump_ast(input: &str) -> Result<()> {
+    if input.ends_with(".lir") {
+        return Ok(());
+    }
+
+    let source = fs::read_to_string(input)?;
+    match lency_driver::parse(&source) {
+        Ok(program) => {
+            print!("{}", program);
+            Ok(())
+        }
+        Err(e) => {
+            e.emit(Some(input), Some(&source));
+            std::process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,15 @@
+use anyhow::Result;
+use std::fs;
+
+/// 打印输入文件的 token 流 (`--dump-tokens`)
+///
+/// 词法错误按 span 就地打印，不会中断输出。
+pub fn cmd_dump_tokens(input: &str) -> Result<()> {
+    if input.ends_with(".lir") {
+        return Ok(());
+    }
+
+    let source = fs::read_to_string(input)?;
+    print!("{}", lency_syntax::lexer::dump_tokens(&source));
+    Ok(())
+}
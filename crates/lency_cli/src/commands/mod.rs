@@ -2,11 +2,16 @@ mod build;
 mod check;
 mod common;
 mod compile;
+mod dump_ast;
+mod dump_tokens;
 mod repl;
 mod run;
 
 pub use build::cmd_build;
 pub use check::cmd_check;
+pub use common::Toolchain;
 pub use compile::cmd_compile;
+pub use dump_ast::cmd_dump_ast;
+pub use dump_tokens::cmd_dump_tokens;
 pub use repl::cmd_repl;
 pub use run::cmd_run;
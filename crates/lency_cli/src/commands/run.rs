@@ -1,31 +1,28 @@
 use anyhow::Result;
+use lency_driver::CodegenOptions;
 use std::fs;
 
-use super::common::{find_runtime_dir, require_tool, temp_artifact_path};
+use super::common::{temp_artifact_path, RuntimeUse, Toolchain};
 
 /// 运行命令
-pub fn cmd_run(input: &str) -> Result<()> {
+pub fn cmd_run(input: &str, toolchain: &Toolchain, options: &CodegenOptions) -> Result<()> {
     println!("Running {} ...", input);
 
-    let result = lency_driver::compile_file(input)?;
+    let result = lency_driver::compile_file_with_options(input, options)?;
+    for warning in &result.warnings {
+        eprintln!("warning: {}", warning);
+    }
+    if let Some(stats) = &result.stats {
+        eprintln!("{}", stats);
+    }
 
     let temp_ir = temp_artifact_path("ll")?;
     fs::write(&temp_ir, result.ir)?;
 
-    let lli = require_tool(&["lli-15", "lli"], "LLVM IR interpreter (lli)")?;
+    let runtime = toolchain.runtime(RuntimeUse::Load)?;
+    let lli = toolchain.llvm_tool("lli", "LLVM IR interpreter (lli)")?;
     let mut cmd = std::process::Command::new(lli);
-    if let Some(runtime_dir) = find_runtime_dir() {
-        let libs = ["liblency_runtime.so", "liblency_runtime.dylib"];
-        for lib in libs {
-            let lib_path = runtime_dir.join(lib);
-            if lib_path.exists() {
-                cmd.arg(format!("-load={}", lib_path.display()));
-                break;
-            }
-        }
-    } else {
-        eprintln!("Warning: lency_runtime library not found. I/O operations may fail.");
-    }
+    cmd.arg(format!("-load={}", runtime.display()));
 
     let output = cmd.arg(&temp_ir).output()?;
 
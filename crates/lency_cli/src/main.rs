@@ -7,7 +7,10 @@ mod lir_backend;
 mod path_utils;
 
 use cli::{Cli, Commands};
-use commands::{cmd_build, cmd_check, cmd_compile, cmd_repl, cmd_run};
+use commands::{
+    cmd_build, cmd_check, cmd_compile, cmd_dump_ast, cmd_dump_tokens, cmd_repl, cmd_run, Toolchain,
+};
+use lency_driver::CodegenOptions;
 
 fn main() -> Result<()> {
     let cli = Cli::parse();
@@ -18,13 +21,35 @@ fn main() -> Result<()> {
         println!("Verbose mode enabled");
     }
 
+    if cli.dump_tokens {
+        if let Some(input) = cli.command.input() {
+            cmd_dump_tokens(input)?;
+        }
+    }
+
+    if cli.dump_ast {
+        if let Some(input) = cli.command.input() {
+            cmd_dump_ast(input)?;
+        }
+    }
+
+    let toolchain = Toolchain {
+        runtime_path: cli.runtime_path.as_deref(),
+        llvm_suffix: cli.llvm_suffix.as_deref(),
+    };
+    let codegen_options = CodegenOptions {
+        stack_guard: cli.stack_guard,
+        stats: cli.stats,
+        ..CodegenOptions::default()
+    };
+
     match cli.command {
         Commands::Compile {
             input,
             output,
             out_dir,
-        } => cmd_compile(&input, &output, out_dir.as_deref())?,
-        Commands::Run { input, args: _ } => cmd_run(&input)?,
+        } => cmd_compile(&input, &output, out_dir.as_deref(), &codegen_options)?,
+        Commands::Run { input, args: _ } => cmd_run(&input, &toolchain, &codegen_options)?,
         Commands::Check { input } => cmd_check(&input)?,
         Commands::Build {
             input,
@@ -32,7 +57,17 @@ fn main() -> Result<()> {
             out_dir,
             release,
             check_only,
-        } => cmd_build(&input, &output, out_dir.as_deref(), release, check_only)?,
+            static_runtime,
+        } => cmd_build(
+            &input,
+            &output,
+            out_dir.as_deref(),
+            release,
+            check_only,
+            static_runtime,
+            &toolchain,
+            &codegen_options,
+        )?,
         Commands::Repl => cmd_repl()?,
     }
 
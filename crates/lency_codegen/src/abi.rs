@@ -0,0 +1,351 @@
+//! C ABI Lowering
+//!
+//! extern "C" 函数中按值传递的 extern struct 按 x86-64 System V 调用约定降级：
+//! - 不超过 16 字节：按 8 字节拆分，全是浮点字段的部分用 `double`，其余用整数，逐个放入寄存器
+//! - 超过 16 字节或寄存器不足：经内存传递，参数带 `byval`，返回值改为首参数 `sret`
+//!
+//! Lency 内部的结构体始终是堆上对象的指针，只在调用边界按上述规则拆开/重组
+
+use crate::context::CodegenContext;
+use crate::error::{CodegenError, CodegenResult};
+use crate::expr::CodegenValue;
+use crate::types::ToLLVMType;
+use inkwell::attributes::{Attribute, AttributeLoc};
+use inkwell::types::{AnyType, BasicMetadataTypeEnum, BasicType, BasicTypeEnum, StructType};
+use inkwell::values::{BasicMetadataValueEnum, BasicValueEnum, FunctionValue, PointerValue};
+use inkwell::AddressSpace;
+use lency_syntax::ast::{Param, Type};
+
+/// 参数寄存器数量（System V：rdi, rsi, rdx, rcx, r8, r9 / xmm0-xmm7）
+const INT_REGISTERS: usize = 6;
+const SSE_REGISTERS: usize = 8;
+
+/// 单个参数或返回值的传递方式
+#[derive(Debug, Clone)]
+pub enum CArg<'ctx> {
+    /// 非结构体：按 Lency 的 LLVM 类型原样传递
+    Direct,
+    /// 拆成一到两个寄存器大小的标量，第 i 个标量对应结构体的第 i 个 8 字节
+    Coerced {
+        layout: StructType<'ctx>,
+        parts: Vec<BasicTypeEnum<'ctx>>,
+    },
+    /// 经内存传递：参数为 byval 指针，返回值为 sret 指针
+    Memory(StructType<'ctx>),
+}
+
+/// extern "C" 函数降级后的签名
+#[derive(Debug, Clone)]
+pub struct CSignature<'ctx> {
+    pub params: Vec<CArg<'ctx>>,
+    pub ret: CArg<'ctx>,
+}
+
+/// 声明 extern "C" 函数，记录降级后的签名供调用点使用
+pub fn declare_c_function<'ctx>(
+    ctx: &mut CodegenContext<'ctx>,
+    name: &str,
+    params: &[Param],
+    return_type: &Type,
+    is_variadic: bool,
+) -> CodegenResult<FunctionValue<'ctx>> {
+    let signature = lower_signature(ctx, params, return_type)?;
+
+    let ptr = |layout: StructType<'ctx>| layout.ptr_type(AddressSpace::default());
+    let mut llvm_params: Vec<BasicMetadataTypeEnum> = Vec::new();
+    if let CArg::Memory(layout) = signature.ret {
+        llvm_params.push(ptr(layout).into());
+    }
+    for (param, arg) in params.iter().zip(&signature.params) {
+        match arg {
+            CArg::Direct => llvm_params.push(param.ty.to_llvm_type(ctx)?.into()),
+            CArg::Coerced { parts, .. } => llvm_params.extend(parts.iter().map(|&t| t.into())),
+            CArg::Memory(layout) => llvm_params.push(ptr(*layout).into()),
+        }
+    }
+
+    let fn_type = match &signature.ret {
+        CArg::Direct if *return_type == Type::Void => {
+            ctx.context.void_type().fn_type(&llvm_params, is_variadic)
+        }
+        CArg::Direct => return_type
+            .to_llvm_type(ctx)?
+            .fn_type(&llvm_params, is_variadic),
+        CArg::Coerced { parts, .. } => {
+            coerced_return_type(ctx, parts).fn_type(&llvm_params, is_variadic)
+        }
+        CArg::Memory(_) => ctx.context.void_type().fn_type(&llvm_params, is_variadic),
+    };
+    let function = ctx.module.add_function(name, fn_type, None);
+    for (index, attribute) in memory_attributes(ctx, &signature) {
+        function.add_attribute(AttributeLoc::Param(index), attribute);
+    }
+
+    ctx.c_signatures.insert(name.to_string(), signature);
+    Ok(function)
+}
+
+/// 按降级后的签名调用 extern "C" 函数，结构体返回值重新装入堆上的 Lency 结构体
+pub fn gen_c_call<'ctx>(
+    ctx: &CodegenContext<'ctx>,
+    function: FunctionValue<'ctx>,
+    signature: &CSignature<'ctx>,
+    args: Vec<BasicValueEnum<'ctx>>,
+    param_types: &[Type],
+    return_type: &Type,
+) -> CodegenResult<CodegenValue<'ctx>> {
+    let mut call_args: Vec<BasicMetadataValueEnum> = Vec::new();
+    let mut variadic: Vec<BasicMetadataValueEnum> = Vec::new();
+    let sret = match signature.ret {
+        CArg::Memory(layout) => {
+            let ptr = crate::expr::struct_init::malloc_struct(ctx, layout, "sret")?;
+            call_args.push(ptr.into());
+            Some(ptr)
+        }
+        _ => None,
+    };
+    let fixed = signature.params.len();
+    for arg in &args[fixed.min(args.len())..] {
+        // 可变参数部分不拆分结构体（语义分析只允许基础类型），按默认实参提升传递
+        variadic.push(crate::expr::promote_variadic_arg(ctx, *arg)?.into());
+    }
+    for ((arg, lowering), ty) in args.into_iter().zip(&signature.params).zip(param_types) {
+        match lowering {
+            CArg::Direct => call_args.push(crate::expr::widen_int(ctx, arg, ty)?.into()),
+            CArg::Coerced { parts, .. } => {
+                let base = arg.into_pointer_value();
+                for (i, part) in parts.iter().enumerate() {
+                    let slot = part_pointer(ctx, base, i, *part)?;
+                    let value = ctx
+                        .builder
+                        .build_load(*part, slot, "c_arg_part")
+                        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+                    call_args.push(value.into());
+                }
+            }
+            CArg::Memory(_) => call_args.push(arg.into()),
+        }
+    }
+
+    call_args.extend(variadic);
+
+    let call_site = ctx
+        .builder
+        .build_call(function, &call_args, "c_call")
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+    for (index, attribute) in memory_attributes(ctx, signature) {
+        call_site.add_attribute(AttributeLoc::Param(index), attribute);
+    }
+    let result = call_site.try_as_basic_value().left();
+
+    let value: Option<BasicValueEnum> = match (&signature.ret, result) {
+        (CArg::Memory(_), _) => sret.map(Into::into),
+        (CArg::Coerced { layout, parts }, Some(result)) => {
+            let ptr = crate::expr::struct_init::malloc_struct(ctx, *layout, "c_ret")?;
+            for (i, part) in parts.iter().enumerate() {
+                let value = if parts.len() == 1 {
+                    result
+                } else {
+                    ctx.builder
+                        .build_extract_value(result.into_struct_value(), i as u32, "c_ret_part")
+                        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?
+                };
+                let slot = part_pointer(ctx, ptr, i, *part)?;
+                ctx.builder
+                    .build_store(slot, value)
+                    .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+            }
+            Some(ptr.into())
+        }
+        (_, result) => result,
+    };
+
+    Ok(match value {
+        Some(value) => CodegenValue {
+            value,
+            ty: return_type.clone(),
+        },
+        None => CodegenValue {
+            value: ctx.context.bool_type().const_int(0, false).into(),
+            ty: Type::Void,
+        },
+    })
+}
+
+/// 按调用约定为每个参数选择传递方式（返回值优先，sret 指针占用一个整数寄存器）
+fn lower_signature<'ctx>(
+    ctx: &CodegenContext<'ctx>,
+    params: &[Param],
+    return_type: &Type,
+) -> CodegenResult<CSignature<'ctx>> {
+    let mut int_left = INT_REGISTERS;
+    let mut sse_left = SSE_REGISTERS;
+
+    let ret = classify(ctx, return_type)?;
+    if let CArg::Memory(_) = ret {
+        int_left -= 1;
+    }
+
+    let mut lowered = Vec::with_capacity(params.len());
+    for param in params {
+        let arg = match classify(ctx, &param.ty)? {
+            CArg::Direct => {
+                if matches!(param.ty, Type::Float) {
+                    sse_left = sse_left.saturating_sub(1);
+                } else {
+                    int_left = int_left.saturating_sub(1);
+                }
+                CArg::Direct
+            }
+            CArg::Coerced { layout, parts } => {
+                let sse = parts.iter().filter(|p| p.is_float_type()).count();
+                let int = parts.len() - sse;
+                // 结构体要么整体进入寄存器，要么整体经栈传递
+                if int <= int_left && sse <= sse_left {
+                    int_left -= int;
+                    sse_left -= sse;
+                    CArg::Coerced { layout, parts }
+                } else {
+                    CArg::Memory(layout)
+                }
+            }
+            memory => memory,
+        };
+        lowered.push(arg);
+    }
+
+    Ok(CSignature {
+        params: lowered,
+        ret,
+    })
+}
+
+/// 按字段布局把结构体分类为寄存器传递或内存传递
+fn classify<'ctx>(ctx: &CodegenContext<'ctx>, ty: &Type) -> CodegenResult<CArg<'ctx>> {
+    let Type::Struct(name) = ty else {
+        return Ok(CArg::Direct);
+    };
+    let layout = *ctx
+        .struct_types
+        .get(name)
+        .ok_or_else(|| CodegenError::UndefinedStructType(name.clone()))?;
+    let field_types = ctx
+        .struct_field_types
+        .get(name)
+        .ok_or_else(|| CodegenError::UndefinedStructType(name.clone()))?;
+
+    // 字段按自然对齐排列（与 LLVM 非 packed 结构体、C 结构体一致）
+    let mut fields = Vec::with_capacity(field_types.len());
+    let mut offset = 0;
+    let mut align = 1;
+    for field in field_types {
+        let (size, is_float) = scalar_layout(field);
+        offset = offset.next_multiple_of(size);
+        fields.push((offset, is_float));
+        offset += size;
+        align = align.max(size);
+    }
+    let size = offset.next_multiple_of(align);
+    if size > 16 {
+        return Ok(CArg::Memory(layout));
+    }
+
+    let parts = (0..size.div_ceil(8))
+        .map(|chunk| {
+            let all_float = fields
+                .iter()
+                .filter(|(offset, _)| offset / 8 == chunk)
+                .all(|(_, is_float)| *is_float);
+            let bytes = (size - chunk * 8).min(8);
+            if all_float {
+                ctx.context.f64_type().as_basic_type_enum()
+            } else {
+                ctx.context
+                    .custom_width_int_type(bytes as u32 * 8)
+                    .as_basic_type_enum()
+            }
+        })
+        .collect();
+    Ok(CArg::Coerced { layout, parts })
+}
+
+/// 字段的大小（也是对齐）与是否为浮点；extern struct 的字段已由语义分析限制为标量
+fn scalar_layout(ty: &Type) -> (usize, bool) {
+    match ty {
+        Type::I8 | Type::Bool => (1, false),
+        Type::I16 => (2, false),
+        Type::I32 => (4, false),
+        Type::Float => (8, true),
+        Type::Newtype(_, inner) => scalar_layout(inner),
+        _ => (8, false),
+    }
+}
+
+/// 返回值的寄存器类型：单个标量直接返回，两个标量组成匿名结构体（rax:rdx / xmm0:xmm1）
+fn coerced_return_type<'ctx>(
+    ctx: &CodegenContext<'ctx>,
+    parts: &[BasicTypeEnum<'ctx>],
+) -> BasicTypeEnum<'ctx> {
+    match parts {
+        [single] => *single,
+        _ => ctx.context.struct_type(parts, false).as_basic_type_enum(),
+    }
+}
+
+/// 结构体第 `chunk` 个 8 字节处、按 `part` 类型访问的指针
+fn part_pointer<'ctx>(
+    ctx: &CodegenContext<'ctx>,
+    base: PointerValue<'ctx>,
+    chunk: usize,
+    part: BasicTypeEnum<'ctx>,
+) -> CodegenResult<PointerValue<'ctx>> {
+    let i8_type = ctx.context.i8_type();
+    let bytes = ctx
+        .builder
+        .build_pointer_cast(base, i8_type.ptr_type(AddressSpace::default()), "c_bytes")
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+    let offset = ctx.context.i64_type().const_int(chunk as u64 * 8, false);
+    let slot = unsafe {
+        ctx.builder
+            .build_gep(i8_type, bytes, &[offset], "c_part_slot")
+            .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?
+    };
+    ctx.builder
+        .build_pointer_cast(slot, part.ptr_type(AddressSpace::default()), "c_part_ptr")
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))
+}
+
+/// 经内存传递的参数与返回值需要的属性（byval / sret 带上被指向的结构体类型）
+fn memory_attributes<'ctx>(
+    ctx: &CodegenContext<'ctx>,
+    signature: &CSignature<'ctx>,
+) -> Vec<(u32, Attribute)> {
+    let type_attribute = |kind: &str, layout: StructType<'ctx>| {
+        ctx.context.create_type_attribute(
+            Attribute::get_named_enum_kind_id(kind),
+            layout.as_any_type_enum(),
+        )
+    };
+    let align = ctx
+        .context
+        .create_enum_attribute(Attribute::get_named_enum_kind_id("align"), 8);
+
+    let mut attributes = Vec::new();
+    let mut index = 0;
+    if let CArg::Memory(layout) = signature.ret {
+        attributes.push((0, type_attribute("sret", layout)));
+        index = 1;
+    }
+    for arg in &signature.params {
+        match arg {
+            CArg::Direct => index += 1,
+            CArg::Coerced { parts, .. } => index += parts.len() as u32,
+            CArg::Memory(layout) => {
+                attributes.push((index, type_attribute("byval", *layout)));
+                attributes.push((index, align));
+                index += 1;
+            }
+        }
+    }
+    attributes
+}
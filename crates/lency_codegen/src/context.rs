@@ -6,6 +6,7 @@ use crate::error::CodegenError;
 use inkwell::builder::Builder;
 use inkwell::context::Context;
 use inkwell::module::Module;
+use inkwell::values::IntValue;
 
 /// LLVM 代码生成上下文
 ///
@@ -23,6 +24,8 @@ pub struct CodegenContext<'ctx> {
     pub struct_fields: std::collections::HashMap<String, Vec<String>>,
     /// Function Return Types - used for type propagation
     pub function_signatures: std::collections::HashMap<String, lency_syntax::ast::Type>,
+    /// Function Parameter Types - 函数名作为值（函数指针）使用时恢复其函数类型
+    pub function_param_types: std::collections::HashMap<String, Vec<lency_syntax::ast::Type>>,
     /// Struct Field Types (ordered) - used to recover Lency Type from field access
     /// Struct Field Types (ordered) - used to recover Lency Type from field access
     pub struct_field_types: std::collections::HashMap<String, Vec<lency_syntax::ast::Type>>,
@@ -33,14 +36,38 @@ pub struct CodegenContext<'ctx> {
     /// Map EnumName -> Ordered Variants [(Name, Types)]
     pub enum_variants:
         std::collections::HashMap<String, Vec<(String, Vec<lency_syntax::ast::Type>)>>,
+    /// Map EnumName -> 变体判别值（与 enum_variants 顺序一致）
+    pub enum_discriminants: std::collections::HashMap<String, Vec<i64>>,
+
+    /// Trait 方法（声明顺序）：虚表的槽位顺序与动态分发时的签名
+    pub trait_methods: std::collections::HashMap<String, Vec<lency_syntax::ast::TraitMethod>>,
+
+    /// extern "C" 函数降级后的签名：按值传递的 extern struct 在调用点拆开/重组
+    pub c_signatures: std::collections::HashMap<String, crate::abi::CSignature<'ctx>>,
 
     /// Global Variable Types
     pub global_var_types: std::collections::HashMap<String, lency_syntax::ast::Type>,
 
     /// Runtime Panic Function
     pub panic_func: Option<inkwell::values::FunctionValue<'ctx>>,
-    /// Line starts for source mapping
-    line_starts: Vec<usize>,
+    /// 调用深度上限：设置后每个函数入口检查递归深度，超出时 panic（`--stack-guard`）
+    pub stack_guard: Option<u64>,
+    /// 源文件路径，写入 panic 消息
+    pub source_path: Option<String>,
+    /// 源码位置映射 (字节偏移 -> 行列)
+    source_map: Option<lency_diagnostics::SourceMap>,
+    /// 导入模块的源文件：路径 -> (位置映射, 报告该文件的 panic 函数)，首次用到时创建
+    imported_files: std::collections::HashMap<
+        String,
+        (
+            Option<lency_diagnostics::SourceMap>,
+            inkwell::values::FunctionValue<'ctx>,
+        ),
+    >,
+    /// 当前生成的函数所在的导入模块源文件；主文件中为 None
+    current_file: Option<String>,
+    /// 主文件的 panic 函数，切换回主文件时恢复
+    main_panic_func: Option<inkwell::values::FunctionValue<'ctx>>,
 }
 
 impl<'ctx> CodegenContext<'ctx> {
@@ -51,14 +78,6 @@ impl<'ctx> CodegenContext<'ctx> {
     /// * `module_name` - 模块名称
     /// * `source` - 源代码 (可选, 用于调试信息)
     pub fn new(context: &'ctx Context, module_name: &str, source: Option<&str>) -> Self {
-        let line_starts = if let Some(src) = source {
-            std::iter::once(0)
-                .chain(src.match_indices('\n').map(|(i, _)| i + 1))
-                .collect()
-        } else {
-            Vec::new()
-        };
-
         Self {
             context,
             module: context.create_module(module_name),
@@ -66,26 +85,96 @@ impl<'ctx> CodegenContext<'ctx> {
             struct_types: std::collections::HashMap::new(),
             struct_fields: std::collections::HashMap::new(),
             function_signatures: std::collections::HashMap::new(),
+            function_param_types: std::collections::HashMap::new(),
             struct_field_types: std::collections::HashMap::new(),
             enum_types: std::collections::HashSet::new(),
             enum_variants: std::collections::HashMap::new(),
+            enum_discriminants: std::collections::HashMap::new(),
+            trait_methods: std::collections::HashMap::new(),
+            c_signatures: std::collections::HashMap::new(),
             global_var_types: std::collections::HashMap::new(),
             panic_func: None,
-            line_starts,
+            stack_guard: None,
+            source_path: None,
+            source_map: source.map(lency_diagnostics::SourceMap::new),
+            imported_files: std::collections::HashMap::new(),
+            current_file: None,
+            main_panic_func: None,
         }
     }
 
-    /// 获取字节偏移对应的行号 (1-based)
-    pub fn get_line(&self, byte_offset: usize) -> u32 {
-        if self.line_starts.is_empty() {
-            return 0;
+    /// 切换到函数所在的源文件：之后的行号按该文件计算，panic 消息报告该文件路径
+    ///
+    /// `None` 表示主文件；导入模块的源码在首次切换时读取，读取失败时行号为 0
+    pub fn set_source_file(&mut self, file: Option<&str>) {
+        if self.current_file.is_none() {
+            self.main_panic_func = self.panic_func;
+        }
+        let Some(file) = file else {
+            self.current_file = None;
+            self.panic_func = self.main_panic_func;
+            return;
+        };
+        if !self.imported_files.contains_key(file) {
+            let source_map = std::fs::read_to_string(file)
+                .ok()
+                .map(|source| lency_diagnostics::SourceMap::new(&source));
+            let name = format!("__lency_panic.{}", self.imported_files.len() + 1);
+            let panic_func = crate::runtime::define_panic_function(
+                self.context,
+                &self.module,
+                &name,
+                Some(file),
+            );
+            self.imported_files
+                .insert(file.to_string(), (source_map, panic_func));
         }
-        match self.line_starts.binary_search(&byte_offset) {
-            Ok(line) => (line + 1) as u32,
-            Err(next_line_idx) => next_line_idx as u32,
+        self.panic_func = self.imported_files.get(file).map(|(_, func)| *func);
+        self.current_file = Some(file.to_string());
+    }
+
+    /// 当前源文件的位置映射
+    fn current_source_map(&self) -> Option<&lency_diagnostics::SourceMap> {
+        match &self.current_file {
+            Some(file) => self
+                .imported_files
+                .get(file)
+                .and_then(|(map, _)| map.as_ref()),
+            None => self.source_map.as_ref(),
         }
     }
 
+    /// 获取字节偏移对应的行号 (1-based)；没有源码时返回 0
+    pub fn get_line(&self, byte_offset: usize) -> u32 {
+        self.current_source_map()
+            .map_or(0, |map| map.line(byte_offset))
+    }
+
+    /// 获取字节偏移对应的 (行, 列)，列按字符计 (均为 1-based)；没有源码时返回 (0, 0)
+    pub fn get_line_col(&self, byte_offset: usize) -> (u32, u32) {
+        self.current_source_map()
+            .map_or((0, 0), |map| map.line_col(byte_offset))
+    }
+
+    /// 枚举是否声明了名为 `variant` 的变体
+    pub fn enum_has_variant(&self, enum_name: &str, variant: &str) -> bool {
+        self.enum_variants
+            .get(enum_name)
+            .is_some_and(|variants| variants.iter().any(|(name, _)| name == variant))
+    }
+
+    /// 获取枚举第 `variant_index` 个变体的 tag 常量
+    ///
+    /// 未登记判别值的枚举（如内置 Result）按变体序号编号
+    pub fn enum_tag(&self, enum_name: &str, variant_index: usize) -> IntValue<'ctx> {
+        let value = self
+            .enum_discriminants
+            .get(enum_name)
+            .and_then(|values| values.get(variant_index).copied())
+            .unwrap_or(variant_index as i64);
+        self.context.i64_type().const_int(value as u64, true)
+    }
+
     /// 获取模块的 LLVM IR 字符串表示
     pub fn print_to_string(&self) -> String {
         self.module.print_to_string().to_string()
@@ -1,7 +1,10 @@
 use crate::context::CodegenContext;
 use crate::error::{CodegenError, CodegenResult};
-use crate::expr::{generate_expr, CodegenValue};
+use crate::expr::{generate_expr, generate_lvalue_addr, CodegenValue};
+use crate::types::ToLLVMType;
 use inkwell::types::BasicType;
+use inkwell::values::{BasicValueEnum, IntValue, PointerValue};
+use inkwell::AddressSpace;
 use lency_syntax::ast::{Expr, Type};
 use std::collections::HashMap;
 
@@ -25,7 +28,25 @@ pub fn gen_array_literal<'ctx>(
         element_codegens.push(generate_expr(ctx, locals, elem)?);
     }
 
-    // 元素类型（假设所有元素类型相同，Sema 已验证）
+    // int 与 float 混合时 Sema 已将元素类型统一为 float，这里补上整数到浮点的转换
+    if element_codegens.iter().any(|v| v.ty == Type::Float) {
+        for elem in element_codegens.iter_mut() {
+            if elem.ty == Type::Int {
+                let promoted = ctx
+                    .builder
+                    .build_signed_int_to_float(
+                        elem.value.into_int_value(),
+                        ctx.context.f64_type(),
+                        "itof",
+                    )
+                    .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+                elem.value = promoted.into();
+                elem.ty = Type::Float;
+            }
+        }
+    }
+
+    // 元素类型（Sema 已验证所有元素类型一致）
     // element_codegens[0] is CodegenValue
     let first_elem = &element_codegens[0];
     let elem_llvm_type = first_elem.value.get_type();
@@ -79,6 +100,170 @@ pub fn gen_array_literal<'ctx>(
     })
 }
 
+/// 生成重复初始化的数组 [value; N]
+///
+/// value 只求值一次；为常量时直接生成常量聚合，否则在栈上分配后循环存储
+pub fn gen_array_repeat<'ctx>(
+    ctx: &CodegenContext<'ctx>,
+    locals: &HashMap<String, (inkwell::values::PointerValue<'ctx>, lency_syntax::ast::Type)>,
+    value: &Expr,
+    size: u32,
+) -> CodegenResult<CodegenValue<'ctx>> {
+    let elem = generate_expr(ctx, locals, value)?;
+    let array_type = elem.value.get_type().array_type(size);
+    let array_lency_type = Type::Array {
+        element_type: Box::new(elem.ty.clone()),
+        size: size as usize,
+    };
+
+    let constant: Option<BasicValueEnum> = match elem.value {
+        BasicValueEnum::IntValue(v) if v.is_const() => {
+            Some(v.get_type().const_array(&vec![v; size as usize]).into())
+        }
+        BasicValueEnum::FloatValue(v) if v.is_const() => {
+            Some(v.get_type().const_array(&vec![v; size as usize]).into())
+        }
+        _ => None,
+    };
+    if let Some(value) = constant {
+        return Ok(CodegenValue {
+            value,
+            ty: array_lency_type,
+        });
+    }
+
+    let array_alloca = ctx
+        .builder
+        .build_alloca(array_type, "array_repeat")
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+    let count = ctx.context.i64_type().const_int(size as u64, false);
+    build_counted_loop(ctx, count, "repeat", |index| {
+        let indices = [ctx.context.i64_type().const_zero(), index];
+        let elem_ptr = unsafe {
+            ctx.builder
+                .build_gep(array_type, array_alloca, &indices, "repeat_elem_ptr")
+                .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?
+        };
+        ctx.builder
+            .build_store(elem_ptr, elem.value)
+            .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+        Ok(())
+    })?;
+
+    let val = ctx
+        .builder
+        .build_load(array_type, array_alloca, "array_value")
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+    Ok(CodegenValue {
+        value: val,
+        ty: array_lency_type,
+    })
+}
+
+/// 生成执行 count 次的计数循环（count <= 0 时不执行），body 接收当前下标 (i64)
+pub(crate) fn build_counted_loop<'ctx>(
+    ctx: &CodegenContext<'ctx>,
+    count: IntValue<'ctx>,
+    name: &str,
+    mut body: impl FnMut(IntValue<'ctx>) -> CodegenResult<()>,
+) -> CodegenResult<()> {
+    let entry_bb = ctx
+        .builder
+        .get_insert_block()
+        .ok_or_else(|| CodegenError::LLVMBuildError("no insert block".to_string()))?;
+    let function = entry_bb
+        .get_parent()
+        .ok_or_else(|| CodegenError::LLVMBuildError("block has no parent".to_string()))?;
+    let cond_bb = ctx
+        .context
+        .append_basic_block(function, &format!("{}.cond", name));
+    let body_bb = ctx
+        .context
+        .append_basic_block(function, &format!("{}.body", name));
+    let end_bb = ctx
+        .context
+        .append_basic_block(function, &format!("{}.end", name));
+
+    let i64_type = ctx.context.i64_type();
+    ctx.builder
+        .build_unconditional_branch(cond_bb)
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+
+    ctx.builder.position_at_end(cond_bb);
+    let index = ctx
+        .builder
+        .build_phi(i64_type, &format!("{}_index", name))
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+    let index_val = index.as_basic_value().into_int_value();
+    let in_range = ctx
+        .builder
+        .build_int_compare(
+            inkwell::IntPredicate::SLT,
+            index_val,
+            count,
+            &format!("{}_in_range", name),
+        )
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+    ctx.builder
+        .build_conditional_branch(in_range, body_bb, end_bb)
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+
+    ctx.builder.position_at_end(body_bb);
+    body(index_val)?;
+    let next = ctx
+        .builder
+        .build_int_add(
+            index_val,
+            i64_type.const_int(1, false),
+            &format!("{}_next", name),
+        )
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+    let body_end_bb = ctx
+        .builder
+        .get_insert_block()
+        .ok_or_else(|| CodegenError::LLVMBuildError("no insert block".to_string()))?;
+    ctx.builder
+        .build_unconditional_branch(cond_bb)
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+
+    index.add_incoming(&[(&i64_type.const_zero(), entry_bb), (&next, body_end_bb)]);
+    ctx.builder.position_at_end(end_bb);
+    Ok(())
+}
+
+/// 调用用户类型的 index 方法（`{Struct}_index(this, key)`）
+fn gen_index_method_call<'ctx>(
+    ctx: &CodegenContext<'ctx>,
+    struct_name: &str,
+    this_val: BasicValueEnum<'ctx>,
+    key_val: BasicValueEnum<'ctx>,
+) -> CodegenResult<CodegenValue<'ctx>> {
+    let mangled_name = lency_monomorph::mangling::method_symbol(struct_name, "index");
+    let function = ctx
+        .module
+        .get_function(&mangled_name)
+        .ok_or_else(|| CodegenError::FunctionNotFound(mangled_name.clone()))?;
+    let return_type = ctx
+        .function_signatures
+        .get(&mangled_name)
+        .cloned()
+        .ok_or_else(|| CodegenError::FunctionNotFound(mangled_name.clone()))?;
+
+    let call = ctx
+        .builder
+        .build_call(function, &[this_val.into(), key_val.into()], "index_call")
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+    let value = call
+        .try_as_basic_value()
+        .left()
+        .ok_or_else(|| CodegenError::LLVMBuildError("index method returned void".into()))?;
+
+    Ok(CodegenValue {
+        value,
+        ty: return_type,
+    })
+}
+
 /// 生成数组索引访问
 /// arr[i] -> GEP + load (带边界检查)
 pub fn gen_index_access<'ctx>(
@@ -95,6 +280,11 @@ pub fn gen_index_access<'ctx>(
     let array_val = array_wrapper.value;
     let index_val = index_wrapper.value;
 
+    // 用户类型实现了 Index trait：obj[i] 调用 obj.index(i)
+    if let Type::Struct(name) = &array_wrapper.ty {
+        return gen_index_method_call(ctx, name, array_val, index_val);
+    }
+
     // 确保索引是整数
     let index_int = index_val.into_int_value();
 
@@ -119,37 +309,55 @@ pub fn gen_index_access<'ctx>(
     }
 
     if array_wrapper.ty == Type::String {
-        // String Indexing: s[i] -> int (byte)
-        // Assume s is i8*
+        // String Indexing: s[i] -> int (第 i 个字符的码点，按字符而不是字节计数)
+        // 没有独立的 char 类型，码点以 int 表示，可交给 char_to_string 还原
         let str_ptr = array_val.into_pointer_value();
-
-        // TODO: Bounds check? Need strlen. Skip for now.
-
-        // GEP i8* s, index
-        let char_ptr = unsafe {
-            ctx.builder
-                .build_gep(ctx.context.i8_type(), str_ptr, &[index_int], "char_ptr")
-                .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?
-        };
-
-        // Load i8
-        let char_val = ctx
+        let char_at_fn = crate::expr::string_ops::get_or_declare_string_char_at(ctx);
+        let code_point = ctx
             .builder
-            .build_load(ctx.context.i8_type(), char_ptr, "char_val")
-            .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+            .build_call(char_at_fn, &[str_ptr.into(), index_int.into()], "char_at")
+            .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?
+            .try_as_basic_value()
+            .left()
+            .ok_or(CodegenError::LLVMBuildError(
+                "lency_string_char_at returned void".to_string(),
+            ))?;
 
-        // Cast i8 to i64 (int)
-        let int_val = ctx
-            .builder
-            .build_int_z_extend(
-                char_val.into_int_value(),
-                ctx.context.i64_type(),
-                "char_to_int",
-            )
-            .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+        // 目标字符不是合法 UTF-8 时 char_at 返回 -2，单独报错；
+        // 越界时返回 -1，合法码点都小于 0x110000，按无符号比较复用数组的越界检查
+        if let Some(panic_func) = ctx.panic_func {
+            let i64_type = ctx.context.i64_type();
+            let is_invalid_utf8 = ctx
+                .builder
+                .build_int_compare(
+                    inkwell::IntPredicate::EQ,
+                    code_point.into_int_value(),
+                    i64_type.const_int(-2i64 as u64, true),
+                    "is_invalid_utf8",
+                )
+                .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+            crate::runtime::gen_panic_if(
+                ctx.context,
+                &ctx.builder,
+                panic_func,
+                is_invalid_utf8,
+                "Invalid UTF-8 in string",
+                line,
+            );
+
+            let code_point_limit = i64_type.const_int(0x11_0000, false);
+            crate::runtime::gen_bounds_check(
+                ctx.context,
+                &ctx.builder,
+                panic_func,
+                code_point.into_int_value(),
+                code_point_limit,
+                line,
+            );
+        }
 
         return Ok(CodegenValue {
-            value: int_val.into(),
+            value: code_point,
             ty: Type::Int,
         });
     }
@@ -221,3 +429,110 @@ pub fn gen_index_access<'ctx>(
         ty: elem_lency_type,
     })
 }
+
+/// 生成固定数组元素的地址（左值），例如 `arr[i] = v` 或 `m[i][j] = v`
+///
+/// 数组本身必须是左值，元素地址直接指向其存储位置，带边界检查。
+/// Vec 元素存放在运行时缓冲区中，没有可用的 LLVM 指针，需走 `gen_index_assignment`。
+pub fn gen_index_lvalue<'ctx>(
+    ctx: &CodegenContext<'ctx>,
+    locals: &HashMap<String, (inkwell::values::PointerValue<'ctx>, lency_syntax::ast::Type)>,
+    array_expr: &Expr,
+    index_expr: &Expr,
+    line: u32,
+) -> CodegenResult<(PointerValue<'ctx>, Type)> {
+    let (array_ptr, array_ty) = generate_lvalue_addr(ctx, locals, array_expr)?;
+    let index_int = generate_expr(ctx, locals, index_expr)?
+        .value
+        .into_int_value();
+    gen_array_element_ptr(ctx, array_ptr, &array_ty, index_int, line)
+}
+
+/// 生成索引赋值 `container[index] = value`
+///
+/// - 固定数组：计算元素地址后直接 store
+/// - Vec：调用 `lency_vec_set`（运行时负责边界检查）
+pub fn gen_index_assignment<'ctx>(
+    ctx: &CodegenContext<'ctx>,
+    locals: &HashMap<String, (inkwell::values::PointerValue<'ctx>, lency_syntax::ast::Type)>,
+    array_expr: &Expr,
+    index_expr: &Expr,
+    value_expr: &Expr,
+    line: u32,
+) -> CodegenResult<()> {
+    let (container_ptr, container_ty) = generate_lvalue_addr(ctx, locals, array_expr)?;
+    let index_int = generate_expr(ctx, locals, index_expr)?
+        .value
+        .into_int_value();
+
+    if let Type::Vec(_) = container_ty {
+        // 变量中保存的是 %LencyVec*
+        let vec_ptr = ctx
+            .builder
+            .build_load(
+                ctx.context.i8_type().ptr_type(AddressSpace::default()),
+                container_ptr,
+                "vec_ptr",
+            )
+            .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+        let value = generate_expr(ctx, locals, value_expr)?;
+        let value_i64 = crate::expr::vec::cast_to_i64(ctx, value.value)?;
+
+        let func = crate::expr::vec::get_or_declare_vec_set(ctx)?;
+        ctx.builder
+            .build_call(
+                func,
+                &[vec_ptr.into(), index_int.into(), value_i64.into()],
+                "",
+            )
+            .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+        return Ok(());
+    }
+
+    let (elem_ptr, _) = gen_array_element_ptr(ctx, container_ptr, &container_ty, index_int, line)?;
+    let value = generate_expr(ctx, locals, value_expr)?;
+    ctx.builder
+        .build_store(elem_ptr, value.value)
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+    Ok(())
+}
+
+/// 计算数组 `array_ptr` 中第 `index` 个元素的地址，并插入边界检查
+fn gen_array_element_ptr<'ctx>(
+    ctx: &CodegenContext<'ctx>,
+    array_ptr: PointerValue<'ctx>,
+    array_ty: &Type,
+    index: IntValue<'ctx>,
+    line: u32,
+) -> CodegenResult<(PointerValue<'ctx>, Type)> {
+    let (elem_lency_type, size) = match array_ty {
+        Type::Array { element_type, size } => (*element_type.clone(), *size),
+        _ => {
+            return Err(CodegenError::UnsupportedType(
+                "Index assignment on non-array type".into(),
+            ))
+        }
+    };
+
+    if let Some(panic_func) = ctx.panic_func {
+        let len_val = ctx.context.i64_type().const_int(size as u64, false);
+        crate::runtime::gen_bounds_check(
+            ctx.context,
+            &ctx.builder,
+            panic_func,
+            index,
+            len_val,
+            line,
+        );
+    }
+
+    let arr_llvm_ty = array_ty.to_llvm_type(ctx)?;
+    let indices = [ctx.context.i64_type().const_int(0, false), index];
+    let elem_ptr = unsafe {
+        ctx.builder
+            .build_gep(arr_llvm_ty, array_ptr, &indices, "elem_ptr")
+            .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?
+    };
+
+    Ok((elem_ptr, elem_lency_type))
+}
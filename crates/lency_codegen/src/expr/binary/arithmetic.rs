@@ -131,12 +131,19 @@ pub fn gen_mul<'ctx>(
     }
 }
 
+/// 整数除法向零截断；`signed` 为 false 时（uint）使用无符号除法 udiv
 pub fn gen_div<'ctx>(
     ctx: &CodegenContext<'ctx>,
     lhs: BasicValueEnum<'ctx>,
     rhs: BasicValueEnum<'ctx>,
+    signed: bool,
 ) -> CodegenResult<BasicValueEnum<'ctx>> {
     match (lhs, rhs) {
+        (BasicValueEnum::IntValue(l), BasicValueEnum::IntValue(r)) if !signed => ctx
+            .builder
+            .build_int_unsigned_div(l, r, "divtmp")
+            .map(Into::into)
+            .map_err(|e| CodegenError::LLVMBuildError(e.to_string())),
         (BasicValueEnum::IntValue(l), BasicValueEnum::IntValue(r)) => ctx
             .builder
             .build_int_signed_div(l, r, "divtmp")
@@ -172,17 +179,53 @@ pub fn gen_div<'ctx>(
     }
 }
 
+/// 取余采用截断语义（与 C/Rust 一致）：结果符号跟随被除数，
+/// 与 `/` 的向零截断配套，保证 `a == (a / b) * b + a % b`。
+/// 例如 `-7 % 3 == -1`，`7 % -3 == 1`。uint 使用无符号取余 urem。
 pub fn gen_mod<'ctx>(
     ctx: &CodegenContext<'ctx>,
     lhs: BasicValueEnum<'ctx>,
     rhs: BasicValueEnum<'ctx>,
+    signed: bool,
 ) -> CodegenResult<BasicValueEnum<'ctx>> {
     match (lhs, rhs) {
+        (BasicValueEnum::IntValue(l), BasicValueEnum::IntValue(r)) if !signed => ctx
+            .builder
+            .build_int_unsigned_rem(l, r, "modtmp")
+            .map(Into::into)
+            .map_err(|e| CodegenError::LLVMBuildError(e.to_string())),
         (BasicValueEnum::IntValue(l), BasicValueEnum::IntValue(r)) => ctx
             .builder
             .build_int_signed_rem(l, r, "modtmp")
             .map(Into::into)
             .map_err(|e| CodegenError::LLVMBuildError(e.to_string())),
+        // frem 同样是截断语义（等价于 C 的 fmod）
+        (BasicValueEnum::FloatValue(l), BasicValueEnum::FloatValue(r)) => ctx
+            .builder
+            .build_float_rem(l, r, "modtmp")
+            .map(Into::into)
+            .map_err(|e| CodegenError::LLVMBuildError(e.to_string())),
+        // 类型提升
+        (BasicValueEnum::IntValue(l), BasicValueEnum::FloatValue(r)) => {
+            let l_float = ctx
+                .builder
+                .build_signed_int_to_float(l, ctx.context.f64_type(), "itof")
+                .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+            ctx.builder
+                .build_float_rem(l_float, r, "modtmp")
+                .map(Into::into)
+                .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))
+        }
+        (BasicValueEnum::FloatValue(l), BasicValueEnum::IntValue(r)) => {
+            let r_float = ctx
+                .builder
+                .build_signed_int_to_float(r, ctx.context.f64_type(), "itof")
+                .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+            ctx.builder
+                .build_float_rem(l, r_float, "modtmp")
+                .map(Into::into)
+                .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))
+        }
         _ => Err(CodegenError::TypeMismatch),
     }
 }
@@ -1,7 +1,10 @@
+use super::struct_eq::{gen_struct_eq, struct_name_of};
 use crate::context::CodegenContext;
 use crate::error::{CodegenError, CodegenResult};
-use inkwell::values::BasicValueEnum;
-use inkwell::IntPredicate;
+use crate::types::ToLLVMType;
+use inkwell::basic_block::BasicBlock;
+use inkwell::values::{BasicValueEnum, IntValue, PointerValue};
+use inkwell::{AddressSpace, IntPredicate};
 use lency_syntax::ast::Type;
 
 pub fn gen_eq<'ctx>(
@@ -22,6 +25,16 @@ pub fn gen_eq<'ctx>(
             .map(Into::into)
             .map_err(|e| CodegenError::LLVMBuildError(e.to_string())),
         (BasicValueEnum::PointerValue(l), BasicValueEnum::PointerValue(r)) => {
+            // 枚举：按 tag 与 payload 比较
+            if let Some(enum_name) = enum_name_of(ctx, lhs_ty) {
+                return gen_enum_eq(ctx, l, r, enum_name).map(Into::into);
+            }
+
+            // 结构体：逐字段比较
+            if let Some(struct_name) = struct_name_of(ctx, lhs_ty) {
+                return gen_struct_eq(ctx, l, r, struct_name).map(Into::into);
+            }
+
             // 检查是否为字符串类型，使用 strcmp 进行内容比较
             if matches!(lhs_ty, Type::String) {
                 // 获取或声明 strcmp 函数
@@ -92,6 +105,24 @@ pub fn gen_neq<'ctx>(
             .map(Into::into)
             .map_err(|e| CodegenError::LLVMBuildError(e.to_string())),
         (BasicValueEnum::PointerValue(l), BasicValueEnum::PointerValue(r)) => {
+            if let Some(enum_name) = enum_name_of(ctx, lhs_ty) {
+                let eq = gen_enum_eq(ctx, l, r, enum_name)?;
+                return ctx
+                    .builder
+                    .build_not(eq, "enum_netmp")
+                    .map(Into::into)
+                    .map_err(|e| CodegenError::LLVMBuildError(e.to_string()));
+            }
+
+            if let Some(struct_name) = struct_name_of(ctx, lhs_ty) {
+                let eq = gen_struct_eq(ctx, l, r, struct_name)?;
+                return ctx
+                    .builder
+                    .build_not(eq, "struct_netmp")
+                    .map(Into::into)
+                    .map_err(|e| CodegenError::LLVMBuildError(e.to_string()));
+            }
+
             // 检查是否为字符串类型
             if matches!(lhs_ty, Type::String) {
                 // 获取或声明 strcmp 函数
@@ -142,15 +173,175 @@ pub fn gen_neq<'ctx>(
     }
 }
 
+/// 若类型是已注册的（单态化后的）枚举，返回其名称
+fn enum_name_of<'a>(ctx: &CodegenContext<'_>, ty: &'a Type) -> Option<&'a str> {
+    match ty {
+        Type::Struct(name) if ctx.enum_types.contains(name) => Some(name),
+        _ => None,
+    }
+}
+
+/// 比较两个枚举值是否相等
+///
+/// 枚举布局为 `{ i64 tag, [N x i8] payload }`。先比较 tag；tag 相同且该变体
+/// 带有 payload 时，再按字段类型逐个比较（字段比较复用 `gen_eq`，因此嵌套枚举、
+/// 字符串等按值比较）。
+fn gen_enum_eq<'ctx>(
+    ctx: &CodegenContext<'ctx>,
+    lhs: PointerValue<'ctx>,
+    rhs: PointerValue<'ctx>,
+    enum_name: &str,
+) -> CodegenResult<IntValue<'ctx>> {
+    let enum_struct_type = *ctx
+        .struct_types
+        .get(enum_name)
+        .ok_or_else(|| CodegenError::UndefinedStructType(enum_name.to_string()))?;
+    let variants = ctx
+        .enum_variants
+        .get(enum_name)
+        .ok_or_else(|| CodegenError::UndefinedStructType(enum_name.to_string()))?
+        .clone();
+
+    let load_tag = |ptr: PointerValue<'ctx>, name: &str| -> CodegenResult<IntValue<'ctx>> {
+        let tag_ptr = ctx
+            .builder
+            .build_struct_gep(enum_struct_type, ptr, 0, &format!("{}_tag_ptr", name))
+            .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+        ctx.builder
+            .build_load(ctx.context.i64_type(), tag_ptr, &format!("{}_tag", name))
+            .map(|v| v.into_int_value())
+            .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))
+    };
+    let lhs_tag = load_tag(lhs, "lhs")?;
+    let rhs_tag = load_tag(rhs, "rhs")?;
+    let tag_eq = ctx
+        .builder
+        .build_int_compare(IntPredicate::EQ, lhs_tag, rhs_tag, "tag_eq")
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+
+    // 只有 unit 变体时，tag 相等即相等
+    if variants.iter().all(|(_, fields)| fields.is_empty()) {
+        return Ok(tag_eq);
+    }
+
+    let entry_bb = ctx
+        .builder
+        .get_insert_block()
+        .ok_or_else(|| CodegenError::LLVMBuildError("no insert block".into()))?;
+    let function = entry_bb
+        .get_parent()
+        .ok_or_else(|| CodegenError::LLVMBuildError("no parent function".into()))?;
+    let payload_bb = ctx.context.append_basic_block(function, "enum_eq_payload");
+    let merge_bb = ctx.context.append_basic_block(function, "enum_eq_merge");
+
+    ctx.builder
+        .build_conditional_branch(tag_eq, payload_bb, merge_bb)
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+
+    // tag 相同：按变体分派比较 payload；unit 变体走 default 直接视为相等
+    let mut variant_blocks = Vec::new();
+    for (tag_idx, (_, fields)) in variants.iter().enumerate() {
+        if !fields.is_empty() {
+            let bb = ctx.context.append_basic_block(function, "enum_eq_variant");
+            variant_blocks.push((tag_idx, fields, bb));
+        }
+    }
+
+    ctx.builder.position_at_end(payload_bb);
+    let cases: Vec<_> = variant_blocks
+        .iter()
+        .map(|(tag_idx, _, bb)| (ctx.enum_tag(enum_name, *tag_idx), *bb))
+        .collect();
+    ctx.builder
+        .build_switch(lhs_tag, merge_bb, &cases)
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+
+    let bool_type = ctx.context.bool_type();
+    let mut incoming: Vec<(IntValue<'ctx>, BasicBlock<'ctx>)> = vec![
+        (bool_type.const_zero(), entry_bb),
+        (bool_type.const_all_ones(), payload_bb),
+    ];
+
+    for (_, fields, bb) in variant_blocks {
+        ctx.builder.position_at_end(bb);
+
+        let mut field_llvm_types = Vec::new();
+        for ty in fields {
+            field_llvm_types.push(ty.to_llvm_type(ctx)?);
+        }
+        let variant_struct_type = ctx.context.struct_type(&field_llvm_types, false);
+        let variant_ptr_type = variant_struct_type.ptr_type(AddressSpace::default());
+
+        let payload_ptr = |ptr: PointerValue<'ctx>| -> CodegenResult<PointerValue<'ctx>> {
+            let arr_ptr = ctx
+                .builder
+                .build_struct_gep(enum_struct_type, ptr, 1, "payload_arr")
+                .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+            ctx.builder
+                .build_bitcast(arr_ptr, variant_ptr_type, "payload_typed")
+                .map(|v| v.into_pointer_value())
+                .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))
+        };
+        let lhs_payload = payload_ptr(lhs)?;
+        let rhs_payload = payload_ptr(rhs)?;
+
+        let mut all_eq = bool_type.const_all_ones();
+        for (i, (field_ty, field_llvm_ty)) in fields.iter().zip(&field_llvm_types).enumerate() {
+            let load_field = |ptr: PointerValue<'ctx>| -> CodegenResult<BasicValueEnum<'ctx>> {
+                let field_ptr = ctx
+                    .builder
+                    .build_struct_gep(variant_struct_type, ptr, i as u32, "field_ptr")
+                    .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+                ctx.builder
+                    .build_load(*field_llvm_ty, field_ptr, "field_val")
+                    .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))
+            };
+            let l = load_field(lhs_payload)?;
+            let r = load_field(rhs_payload)?;
+            let field_eq = gen_eq(ctx, l, r, field_ty)?.into_int_value();
+            all_eq = ctx
+                .builder
+                .build_and(all_eq, field_eq, "payload_eq")
+                .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+        }
+
+        // 字段比较可能产生新的基本块（嵌套枚举），以当前块作为 phi 来源
+        let end_bb = ctx
+            .builder
+            .get_insert_block()
+            .ok_or_else(|| CodegenError::LLVMBuildError("no insert block".into()))?;
+        ctx.builder
+            .build_unconditional_branch(merge_bb)
+            .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+        incoming.push((all_eq, end_bb));
+    }
+
+    ctx.builder.position_at_end(merge_bb);
+    let phi = ctx
+        .builder
+        .build_phi(bool_type, "enum_eq")
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+    for (value, bb) in &incoming {
+        phi.add_incoming(&[(value, *bb)]);
+    }
+    Ok(phi.as_basic_value().into_int_value())
+}
+
 pub fn gen_lt<'ctx>(
     ctx: &CodegenContext<'ctx>,
     lhs: BasicValueEnum<'ctx>,
     rhs: BasicValueEnum<'ctx>,
+    signed: bool,
 ) -> CodegenResult<BasicValueEnum<'ctx>> {
+    let predicate = if signed {
+        IntPredicate::SLT
+    } else {
+        IntPredicate::ULT
+    };
     match (lhs, rhs) {
         (BasicValueEnum::IntValue(l), BasicValueEnum::IntValue(r)) => ctx
             .builder
-            .build_int_compare(IntPredicate::SLT, l, r, "lttmp")
+            .build_int_compare(predicate, l, r, "lttmp")
             .map(Into::into)
             .map_err(|e| CodegenError::LLVMBuildError(e.to_string())),
         (BasicValueEnum::FloatValue(l), BasicValueEnum::FloatValue(r)) => ctx
@@ -166,11 +357,17 @@ pub fn gen_gt<'ctx>(
     ctx: &CodegenContext<'ctx>,
     lhs: BasicValueEnum<'ctx>,
     rhs: BasicValueEnum<'ctx>,
+    signed: bool,
 ) -> CodegenResult<BasicValueEnum<'ctx>> {
+    let predicate = if signed {
+        IntPredicate::SGT
+    } else {
+        IntPredicate::UGT
+    };
     match (lhs, rhs) {
         (BasicValueEnum::IntValue(l), BasicValueEnum::IntValue(r)) => ctx
             .builder
-            .build_int_compare(IntPredicate::SGT, l, r, "gttmp")
+            .build_int_compare(predicate, l, r, "gttmp")
             .map(Into::into)
             .map_err(|e| CodegenError::LLVMBuildError(e.to_string())),
         (BasicValueEnum::FloatValue(l), BasicValueEnum::FloatValue(r)) => ctx
@@ -186,11 +383,17 @@ pub fn gen_leq<'ctx>(
     ctx: &CodegenContext<'ctx>,
     lhs: BasicValueEnum<'ctx>,
     rhs: BasicValueEnum<'ctx>,
+    signed: bool,
 ) -> CodegenResult<BasicValueEnum<'ctx>> {
+    let predicate = if signed {
+        IntPredicate::SLE
+    } else {
+        IntPredicate::ULE
+    };
     match (lhs, rhs) {
         (BasicValueEnum::IntValue(l), BasicValueEnum::IntValue(r)) => ctx
             .builder
-            .build_int_compare(IntPredicate::SLE, l, r, "letmp")
+            .build_int_compare(predicate, l, r, "letmp")
             .map(Into::into)
             .map_err(|e| CodegenError::LLVMBuildError(e.to_string())),
         (BasicValueEnum::FloatValue(l), BasicValueEnum::FloatValue(r)) => ctx
@@ -206,11 +409,17 @@ pub fn gen_geq<'ctx>(
     ctx: &CodegenContext<'ctx>,
     lhs: BasicValueEnum<'ctx>,
     rhs: BasicValueEnum<'ctx>,
+    signed: bool,
 ) -> CodegenResult<BasicValueEnum<'ctx>> {
+    let predicate = if signed {
+        IntPredicate::SGE
+    } else {
+        IntPredicate::UGE
+    };
     match (lhs, rhs) {
         (BasicValueEnum::IntValue(l), BasicValueEnum::IntValue(r)) => ctx
             .builder
-            .build_int_compare(IntPredicate::SGE, l, r, "getmp")
+            .build_int_compare(predicate, l, r, "getmp")
             .map(Into::into)
             .map_err(|e| CodegenError::LLVMBuildError(e.to_string())),
         (BasicValueEnum::FloatValue(l), BasicValueEnum::FloatValue(r)) => ctx
@@ -8,12 +8,14 @@ use std::collections::HashMap;
 use crate::context::CodegenContext;
 use crate::error::CodegenResult;
 
-use crate::expr::{generate_expr, CodegenValue};
+use crate::expr::{generate_expr, widen_int_to, CodegenValue};
+use inkwell::values::BasicValueEnum;
 use lency_syntax::ast::Type;
 
 pub mod arithmetic;
 pub mod comparison;
 pub mod logical;
+mod struct_eq;
 
 pub mod elvis;
 
@@ -38,21 +40,34 @@ pub(super) fn gen_binary<'ctx>(
     let lhs_wrapper = generate_expr(ctx, locals, left)?;
     let rhs_wrapper = generate_expr(ctx, locals, right)?;
 
-    let lhs_val = lhs_wrapper.value;
-    let rhs_val = rhs_wrapper.value;
+    // 不同宽度的整数运算前先拓宽到较宽的一侧
+    let (lhs_val, rhs_val) = match (lhs_wrapper.value, rhs_wrapper.value) {
+        (BasicValueEnum::IntValue(l), BasicValueEnum::IntValue(r)) => (
+            widen_int_to(ctx, l.into(), r.get_type().into())?,
+            widen_int_to(ctx, r.into(), l.get_type().into())?,
+        ),
+        (l, r) => (l, r),
+    };
+    // newtype 之间的比较按底层类型进行
+    let cmp_ty = match &lhs_wrapper.ty {
+        Type::Newtype(_, inner) => inner.as_ref(),
+        ty => ty,
+    };
+    // uint 的除法、取余与大小比较使用无符号指令
+    let signed = !cmp_ty.is_unsigned();
 
     let result_val = match op {
         BinaryOp::Add => gen_add(ctx, lhs_val, rhs_val),
         BinaryOp::Sub => gen_sub(ctx, lhs_val, rhs_val),
         BinaryOp::Mul => gen_mul(ctx, lhs_val, rhs_val),
-        BinaryOp::Div => gen_div(ctx, lhs_val, rhs_val),
-        BinaryOp::Mod => gen_mod(ctx, lhs_val, rhs_val),
-        BinaryOp::Eq => gen_eq(ctx, lhs_val, rhs_val, &lhs_wrapper.ty),
-        BinaryOp::Neq => gen_neq(ctx, lhs_val, rhs_val, &lhs_wrapper.ty),
-        BinaryOp::Lt => gen_lt(ctx, lhs_val, rhs_val),
-        BinaryOp::Gt => gen_gt(ctx, lhs_val, rhs_val),
-        BinaryOp::Leq => gen_leq(ctx, lhs_val, rhs_val),
-        BinaryOp::Geq => gen_geq(ctx, lhs_val, rhs_val),
+        BinaryOp::Div => gen_div(ctx, lhs_val, rhs_val, signed),
+        BinaryOp::Mod => gen_mod(ctx, lhs_val, rhs_val, signed),
+        BinaryOp::Eq => gen_eq(ctx, lhs_val, rhs_val, cmp_ty),
+        BinaryOp::Neq => gen_neq(ctx, lhs_val, rhs_val, cmp_ty),
+        BinaryOp::Lt => gen_lt(ctx, lhs_val, rhs_val, signed),
+        BinaryOp::Gt => gen_gt(ctx, lhs_val, rhs_val, signed),
+        BinaryOp::Leq => gen_leq(ctx, lhs_val, rhs_val, signed),
+        BinaryOp::Geq => gen_geq(ctx, lhs_val, rhs_val, signed),
         BinaryOp::And => gen_and(ctx, lhs_val, rhs_val),
         BinaryOp::Or => gen_or(ctx, lhs_val, rhs_val),
         BinaryOp::Elvis => unreachable!("Elvis operator handled by short-circuit logic"),
@@ -60,7 +75,10 @@ pub(super) fn gen_binary<'ctx>(
 
     let result_ty = match op {
         BinaryOp::Add | BinaryOp::Sub | BinaryOp::Mul | BinaryOp::Div | BinaryOp::Mod => {
-            lhs_wrapper.ty
+            match (lhs_wrapper.ty.int_bits(), rhs_wrapper.ty.int_bits()) {
+                (Some(l), Some(r)) if r > l => rhs_wrapper.ty,
+                _ => lhs_wrapper.ty,
+            }
         }
         _ => Type::Bool,
     };
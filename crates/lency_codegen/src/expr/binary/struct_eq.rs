@@ -0,0 +1,189 @@
+//! Struct Equality Code Generation
+//!
+//! 结构体的 `==`：按定义顺序逐字段比较，任一字段不等即短路返回 false。
+//! 字段比较复用 `gen_eq`，因此嵌套结构体、枚举、字符串都按值比较。
+
+use super::comparison::gen_eq;
+use crate::context::CodegenContext;
+use crate::error::{CodegenError, CodegenResult};
+use crate::types::ToLLVMType;
+use inkwell::basic_block::BasicBlock;
+use inkwell::values::{BasicValueEnum, IntValue, PointerValue};
+use lency_syntax::ast::Type;
+
+/// 若类型是已注册的结构体（不含枚举），返回其名称
+pub(super) fn struct_name_of<'a>(ctx: &CodegenContext<'_>, ty: &'a Type) -> Option<&'a str> {
+    match ty {
+        Type::Struct(name)
+            if ctx.struct_field_types.contains_key(name) && !ctx.enum_types.contains(name) =>
+        {
+            Some(name)
+        }
+        _ => None,
+    }
+}
+
+/// 比较两个结构体值是否相等
+pub(super) fn gen_struct_eq<'ctx>(
+    ctx: &CodegenContext<'ctx>,
+    lhs: PointerValue<'ctx>,
+    rhs: PointerValue<'ctx>,
+    struct_name: &str,
+) -> CodegenResult<IntValue<'ctx>> {
+    let struct_type = *ctx
+        .struct_types
+        .get(struct_name)
+        .ok_or_else(|| CodegenError::UndefinedStructType(struct_name.to_string()))?;
+    let field_types = ctx
+        .struct_field_types
+        .get(struct_name)
+        .ok_or_else(|| CodegenError::UndefinedStructType(struct_name.to_string()))?
+        .clone();
+
+    let bool_type = ctx.context.bool_type();
+    if field_types.is_empty() {
+        return Ok(bool_type.const_all_ones());
+    }
+
+    let function = current_function(ctx)?;
+    let merge_bb = ctx.context.append_basic_block(function, "struct_eq_merge");
+    let mut incoming: Vec<(IntValue<'ctx>, BasicBlock<'ctx>)> = Vec::new();
+
+    for (i, field_ty) in field_types.iter().enumerate() {
+        let field_llvm_ty = field_ty.to_llvm_type(ctx)?;
+        let load_field = |ptr: PointerValue<'ctx>, side: &str| {
+            let field_ptr = ctx
+                .builder
+                .build_struct_gep(struct_type, ptr, i as u32, &format!("{}_field_ptr", side))
+                .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+            ctx.builder
+                .build_load(field_llvm_ty, field_ptr, &format!("{}_field", side))
+                .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))
+        };
+        let l = load_field(lhs, "lhs")?;
+        let r = load_field(rhs, "rhs")?;
+        let field_eq = gen_field_eq(ctx, l, r, field_ty)?;
+
+        // 字段比较可能产生新的基本块（嵌套结构体、枚举），以当前块作为 phi 来源
+        let end_bb = insert_block(ctx)?;
+        incoming.push((bool_type.const_zero(), end_bb));
+        let next_bb = ctx.context.append_basic_block(function, "struct_eq_next");
+        ctx.builder
+            .build_conditional_branch(field_eq, next_bb, merge_bb)
+            .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+        ctx.builder.position_at_end(next_bb);
+    }
+
+    // 所有字段都相等
+    let last_bb = insert_block(ctx)?;
+    ctx.builder
+        .build_unconditional_branch(merge_bb)
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+    incoming.push((bool_type.const_all_ones(), last_bb));
+
+    ctx.builder.position_at_end(merge_bb);
+    let phi = ctx
+        .builder
+        .build_phi(bool_type, "struct_eq")
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+    for (value, bb) in &incoming {
+        phi.add_incoming(&[(value, *bb)]);
+    }
+    Ok(phi.as_basic_value().into_int_value())
+}
+
+/// 比较单个字段：可空字段按值比较，其余交给 `gen_eq`
+fn gen_field_eq<'ctx>(
+    ctx: &CodegenContext<'ctx>,
+    lhs: BasicValueEnum<'ctx>,
+    rhs: BasicValueEnum<'ctx>,
+    ty: &Type,
+) -> CodegenResult<IntValue<'ctx>> {
+    match (ty, lhs, rhs) {
+        (
+            Type::Nullable(inner),
+            BasicValueEnum::PointerValue(l),
+            BasicValueEnum::PointerValue(r),
+        ) => gen_nullable_eq(ctx, l, r, inner),
+        _ => Ok(gen_eq(ctx, lhs, rhs, ty)?.into_int_value()),
+    }
+}
+
+/// 比较两个可空值：都为 null 时相等，只有一个为 null 时不等，否则比较内部值
+///
+/// 可空值是指针：内部类型本身是指针（string、结构体）时直接使用，否则指向装箱的值
+fn gen_nullable_eq<'ctx>(
+    ctx: &CodegenContext<'ctx>,
+    lhs: PointerValue<'ctx>,
+    rhs: PointerValue<'ctx>,
+    inner: &Type,
+) -> CodegenResult<IntValue<'ctx>> {
+    let lhs_null = ctx
+        .builder
+        .build_is_null(lhs, "lhs_is_null")
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+    let rhs_null = ctx
+        .builder
+        .build_is_null(rhs, "rhs_is_null")
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+    let both_null = ctx
+        .builder
+        .build_and(lhs_null, rhs_null, "both_null")
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+    let any_null = ctx
+        .builder
+        .build_or(lhs_null, rhs_null, "any_null")
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+
+    let entry_bb = insert_block(ctx)?;
+    let function = current_function(ctx)?;
+    let value_bb = ctx
+        .context
+        .append_basic_block(function, "nullable_eq_value");
+    let merge_bb = ctx
+        .context
+        .append_basic_block(function, "nullable_eq_merge");
+    ctx.builder
+        .build_conditional_branch(any_null, merge_bb, value_bb)
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+
+    ctx.builder.position_at_end(value_bb);
+    let inner_llvm_ty = inner.to_llvm_type(ctx)?;
+    let (l, r): (BasicValueEnum<'ctx>, BasicValueEnum<'ctx>) = if inner_llvm_ty.is_pointer_type() {
+        (lhs.into(), rhs.into())
+    } else {
+        let load = |ptr: PointerValue<'ctx>, name: &str| {
+            ctx.builder
+                .build_load(inner_llvm_ty, ptr, name)
+                .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))
+        };
+        (load(lhs, "lhs_unboxed")?, load(rhs, "rhs_unboxed")?)
+    };
+    let value_eq = gen_eq(ctx, l, r, inner)?.into_int_value();
+    let value_end_bb = insert_block(ctx)?;
+    ctx.builder
+        .build_unconditional_branch(merge_bb)
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+
+    ctx.builder.position_at_end(merge_bb);
+    let phi = ctx
+        .builder
+        .build_phi(ctx.context.bool_type(), "nullable_eq")
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+    phi.add_incoming(&[(&both_null, entry_bb), (&value_eq, value_end_bb)]);
+    Ok(phi.as_basic_value().into_int_value())
+}
+
+fn insert_block<'ctx>(ctx: &CodegenContext<'ctx>) -> CodegenResult<BasicBlock<'ctx>> {
+    ctx.builder
+        .get_insert_block()
+        .ok_or_else(|| CodegenError::LLVMBuildError("no insert block".into()))
+}
+
+fn current_function<'ctx>(
+    ctx: &CodegenContext<'ctx>,
+) -> CodegenResult<inkwell::values::FunctionValue<'ctx>> {
+    insert_block(ctx)?
+        .get_parent()
+        .ok_or_else(|| CodegenError::LLVMBuildError("no parent function".into()))
+}
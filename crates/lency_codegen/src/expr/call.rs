@@ -11,6 +11,7 @@ use crate::error::{CodegenError, CodegenResult};
 use crate::expr::{generate_expr, CodegenValue};
 use crate::types::ToLLVMType;
 use inkwell::types::BasicType;
+use inkwell::values::BasicMetadataValueEnum;
 use lency_syntax::ast::Type;
 
 /// 生成函数调用代码
@@ -40,7 +41,7 @@ pub(super) fn gen_call<'ctx>(
     let mut arg_values = Vec::new();
     for arg in args {
         let val_wrapper = generate_expr(ctx, locals, arg)?;
-        arg_values.push(val_wrapper.value.into());
+        arg_values.push(val_wrapper.value);
     }
 
     // 检查是否为函数指针变量 (闭包)
@@ -74,6 +75,12 @@ pub(super) fn gen_call<'ctx>(
                 ret.fn_type(&param_llvm_types, false)
             };
 
+            let arg_values = arg_values
+                .into_iter()
+                .zip(param_types)
+                .map(|(value, ty)| super::widen_int(ctx, value, ty).map(Into::into))
+                .collect::<CodegenResult<Vec<BasicMetadataValueEnum>>>()?;
+
             // 间接调用
             let call_site = ctx
                 .builder
@@ -110,6 +117,34 @@ pub(super) fn gen_call<'ctx>(
         .cloned()
         .ok_or_else(|| CodegenError::FunctionNotFound(func_name.clone()))?;
 
+    // extern "C" 函数按 C ABI 传递结构体参数与返回值
+    if let Some(signature) = ctx.c_signatures.get(func_name) {
+        let param_types = ctx
+            .function_param_types
+            .get(func_name)
+            .cloned()
+            .unwrap_or_default();
+        return crate::abi::gen_c_call(
+            ctx,
+            function,
+            signature,
+            arg_values,
+            &param_types,
+            &return_type,
+        );
+    }
+
+    // 窄整数实参拓宽到形参的整数宽度；可变参数部分没有形参类型，按 C 的默认实参提升传递
+    let param_types = function.get_type().get_param_types();
+    let arg_values = arg_values
+        .into_iter()
+        .enumerate()
+        .map(|(i, value)| match param_types.get(i) {
+            Some(ty) => super::widen_int_to(ctx, value, *ty).map(Into::into),
+            None => super::promote_variadic_arg(ctx, value).map(Into::into),
+        })
+        .collect::<CodegenResult<Vec<BasicMetadataValueEnum>>>()?;
+
     // 调用函数
     let call_site = ctx
         .builder
@@ -119,8 +154,9 @@ pub(super) fn gen_call<'ctx>(
     let val = call_site.try_as_basic_value().left();
 
     if let Some(v) = val {
+        // 沿用运行时声明的 C 函数（如返回 i32 的 printf）按 Lency 声明的返回类型拓宽
         Ok(CodegenValue {
-            value: v,
+            value: super::widen_int(ctx, v, &return_type)?,
             ty: return_type,
         })
     } else {
@@ -131,3 +167,55 @@ pub(super) fn gen_call<'ctx>(
         })
     }
 }
+
+/// 生成关联函数调用代码: Type::method(args)
+///
+/// 关联函数与方法共用 `Type_method` 符号，方法体不引用 this，因此以空指针占位
+pub(super) fn gen_associated_call<'ctx>(
+    ctx: &CodegenContext<'ctx>,
+    locals: &HashMap<String, (inkwell::values::PointerValue<'ctx>, lency_syntax::ast::Type)>,
+    type_name: &str,
+    member: &str,
+    args: &[Expr],
+) -> CodegenResult<CodegenValue<'ctx>> {
+    let mangled_name = lency_monomorph::mangling::method_symbol(type_name, member);
+    let function = ctx
+        .module
+        .get_function(&mangled_name)
+        .ok_or_else(|| CodegenError::FunctionNotFound(mangled_name.clone()))?;
+
+    let param_types = function.get_type().get_param_types();
+    let this_type = param_types
+        .first()
+        .copied()
+        .ok_or(CodegenError::TypeMismatch)?;
+    let mut arg_values = Vec::with_capacity(args.len() + 1);
+    arg_values.push(this_type.const_zero().into());
+    for (arg, param_ty) in args.iter().zip(&param_types[1..]) {
+        let val_wrapper = generate_expr(ctx, locals, arg)?;
+        let value = super::widen_int_to(ctx, val_wrapper.value, *param_ty)?;
+        arg_values.push(value.into());
+    }
+
+    let return_type = ctx
+        .function_signatures
+        .get(&mangled_name)
+        .cloned()
+        .ok_or_else(|| CodegenError::FunctionNotFound(mangled_name.clone()))?;
+
+    let call_site = ctx
+        .builder
+        .build_call(function, &arg_values, "call_assoc")
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+
+    match call_site.try_as_basic_value().left() {
+        Some(v) => Ok(CodegenValue {
+            value: v,
+            ty: return_type,
+        }),
+        None => Ok(CodegenValue {
+            value: ctx.context.bool_type().const_int(0, false).into(),
+            ty: Type::Void,
+        }),
+    }
+}
@@ -4,7 +4,7 @@
 
 use crate::context::CodegenContext;
 use crate::error::{CodegenError, CodegenResult};
-use crate::expr::{generate_expr, CodegenValue};
+use crate::expr::{generate_expr, string_ops, CodegenValue};
 use inkwell::AddressSpace;
 use lency_syntax::ast::{Expr, Type};
 use std::collections::HashMap;
@@ -21,6 +21,9 @@ pub fn is_type_conversion_fn(name: &str) -> bool {
             | "is_dir"
             | "arg_count"
             | "arg_at"
+            | "byte_len"
+            | "string_bytes"
+            | "bytes_to_string"
     )
 }
 
@@ -34,163 +37,213 @@ pub fn gen_type_conversion_call<'ctx>(
     match func_name {
         "int_to_string" => gen_int_to_string(ctx, locals, args),
         "float_to_string" => gen_float_to_string(ctx, locals, args),
-        "parse_int" => gen_parse_int(ctx, locals, args),
-        "parse_float" => gen_parse_float(ctx, locals, args),
+        "parse_int" => super::parse::gen_parse_int(ctx, locals, args),
+        "parse_float" => super::parse::gen_parse_float(ctx, locals, args),
         "file_exists" => gen_file_exists(ctx, locals, args),
         "is_dir" => gen_is_dir(ctx, locals, args),
         "arg_count" => gen_arg_count(ctx, args),
         "arg_at" => gen_arg_at(ctx, locals, args),
+        "byte_len" => gen_byte_len(ctx, locals, args),
+        "string_bytes" => super::string_bytes::gen_string_bytes(ctx, locals, args),
+        "bytes_to_string" => super::string_bytes::gen_bytes_to_string(ctx, locals, args),
         _ => Err(CodegenError::FunctionNotFound(func_name.to_string())),
     }
 }
 
-/// Generate code for arg_count() -> int
-fn gen_arg_count<'ctx>(
+/// 生成 x as T / cast<T>(x)
+///
+/// 整数与 float 之间生成转换指令（float 转整数向零截断），不同宽度的整数之间
+/// 按源类型的符号扩展或截断（int 与 uint 同宽，按位重新解释）；字符本身就是 int，
+/// newtype 与底层类型的 LLVM 表示也相同，这些转换只替换值携带的类型
+///
+/// 转为 `dyn T` / `dyn T?` 时构造 Trait 对象胖指针，见 [`super::trait_object`]
+pub fn gen_cast<'ctx>(
     ctx: &CodegenContext<'ctx>,
-    args: &[Expr],
+    locals: &HashMap<String, (inkwell::values::PointerValue<'ctx>, Type)>,
+    value: &Expr,
+    ty: &Type,
 ) -> CodegenResult<CodegenValue<'ctx>> {
-    if !args.is_empty() {
-        return Err(CodegenError::UnsupportedFeature(
-            "arg_count expects 0 argument".to_string(),
-        ));
+    let val = generate_expr(ctx, locals, value)?;
+    match ty {
+        Type::TraitObject(trait_name) => {
+            return super::trait_object::gen_trait_object(ctx, val, trait_name);
+        }
+        Type::Nullable(inner) => {
+            if let Type::TraitObject(trait_name) = inner.as_ref() {
+                return super::trait_object::gen_nullable_trait_object(ctx, val, trait_name);
+            }
+        }
+        _ => {}
     }
-
-    let func = if let Some(f) = ctx.module.get_function("lency_arg_count") {
-        f
-    } else {
-        let i64_type = ctx.context.i64_type();
-        let fn_type = i64_type.fn_type(&[], false);
-        ctx.module.add_function("lency_arg_count", fn_type, None)
+    let converted = match (val.ty.int_bits(), ty.int_bits()) {
+        (Some(from_bits), Some(to_bits)) => {
+            let int_val = val.value.into_int_value();
+            let target = ctx.context.custom_width_int_type(to_bits);
+            if from_bits < to_bits {
+                ctx.builder
+                    .build_int_s_extend(int_val, target, "int_widen")
+                    .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?
+                    .into()
+            } else if from_bits > to_bits {
+                ctx.builder
+                    .build_int_truncate(int_val, target, "int_narrow")
+                    .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?
+                    .into()
+            } else {
+                val.value
+            }
+        }
+        (Some(_), None) if *ty == Type::Float && val.ty.is_unsigned() => ctx
+            .builder
+            .build_unsigned_int_to_float(
+                val.value.into_int_value(),
+                ctx.context.f64_type(),
+                "uint_to_float",
+            )
+            .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?
+            .into(),
+        (Some(_), None) if *ty == Type::Float => ctx
+            .builder
+            .build_signed_int_to_float(
+                val.value.into_int_value(),
+                ctx.context.f64_type(),
+                "int_to_float",
+            )
+            .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?
+            .into(),
+        (None, Some(to_bits)) if val.ty == Type::Float && ty.is_unsigned() => ctx
+            .builder
+            .build_float_to_unsigned_int(
+                val.value.into_float_value(),
+                ctx.context.custom_width_int_type(to_bits),
+                "float_to_uint",
+            )
+            .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?
+            .into(),
+        (None, Some(to_bits)) if val.ty == Type::Float => ctx
+            .builder
+            .build_float_to_signed_int(
+                val.value.into_float_value(),
+                ctx.context.custom_width_int_type(to_bits),
+                "float_to_int",
+            )
+            .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?
+            .into(),
+        _ => val.value,
     };
-
-    let call = ctx
-        .builder
-        .build_call(func, &[], "arg_count")
-        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
-    let result = call.try_as_basic_value().left().unwrap();
-
     Ok(CodegenValue {
-        value: result,
-        ty: Type::Int,
+        value: converted,
+        ty: ty.clone(),
     })
 }
 
-/// Generate code for arg_at(int index) -> string
-fn gen_arg_at<'ctx>(
+/// Generate code for byte_len(string) -> int
+fn gen_byte_len<'ctx>(
     ctx: &CodegenContext<'ctx>,
     locals: &HashMap<String, (inkwell::values::PointerValue<'ctx>, Type)>,
     args: &[Expr],
 ) -> CodegenResult<CodegenValue<'ctx>> {
     if args.len() != 1 {
         return Err(CodegenError::UnsupportedFeature(
-            "arg_at expects 1 argument".to_string(),
+            "byte_len expects 1 argument".to_string(),
         ));
     }
+    if let Some(s) = string_ops::string_literal(&args[0]) {
+        return Ok(string_ops::const_int_value(ctx, s.len()));
+    }
 
-    let func = if let Some(f) = ctx.module.get_function("lency_arg_at") {
+    let func = if let Some(f) = ctx.module.get_function("lency_string_byte_len") {
         f
     } else {
         let i64_type = ctx.context.i64_type();
         let ptr_type = ctx.context.i8_type().ptr_type(AddressSpace::default());
-        let fn_type = ptr_type.fn_type(&[i64_type.into()], false);
-        ctx.module.add_function("lency_arg_at", fn_type, None)
+        let fn_type = i64_type.fn_type(&[ptr_type.into()], false);
+        ctx.module
+            .add_function("lency_string_byte_len", fn_type, None)
     };
 
     let arg_val = generate_expr(ctx, locals, &args[0])?;
-    if arg_val.ty != Type::Int {
+    if arg_val.ty != Type::String {
         return Err(CodegenError::TypeMismatch);
     }
 
-    let call = ctx
+    let result = ctx
         .builder
-        .build_call(func, &[arg_val.value.into()], "arg_at")
-        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
-    let result = call.try_as_basic_value().left().unwrap();
+        .build_call(func, &[arg_val.value.into()], "byte_len")
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?
+        .try_as_basic_value()
+        .left()
+        .ok_or(CodegenError::LLVMBuildError(
+            "lency_string_byte_len returned void".to_string(),
+        ))?;
 
     Ok(CodegenValue {
         value: result,
-        ty: Type::String,
+        ty: Type::Int,
     })
 }
 
-/// Generate code for int_to_string(int) -> string
-fn gen_int_to_string<'ctx>(
+/// Generate code for arg_count() -> int
+fn gen_arg_count<'ctx>(
     ctx: &CodegenContext<'ctx>,
-    locals: &HashMap<String, (inkwell::values::PointerValue<'ctx>, Type)>,
     args: &[Expr],
 ) -> CodegenResult<CodegenValue<'ctx>> {
-    if args.len() != 1 {
+    if !args.is_empty() {
         return Err(CodegenError::UnsupportedFeature(
-            "int_to_string expects 1 argument".to_string(),
+            "arg_count expects 0 argument".to_string(),
         ));
     }
 
-    // Declare or get lency_int_to_string FFI function
-    let func = if let Some(f) = ctx.module.get_function("lency_int_to_string") {
+    let func = if let Some(f) = ctx.module.get_function("lency_arg_count") {
         f
     } else {
         let i64_type = ctx.context.i64_type();
-        let ptr_type = ctx.context.i8_type().ptr_type(AddressSpace::default());
-        let fn_type = ptr_type.fn_type(&[i64_type.into()], false);
-        ctx.module
-            .add_function("lency_int_to_string", fn_type, None)
+        let fn_type = i64_type.fn_type(&[], false);
+        ctx.module.add_function("lency_arg_count", fn_type, None)
     };
 
-    // Evaluate argument
-    let arg_val = generate_expr(ctx, locals, &args[0])?;
-    if arg_val.ty != Type::Int {
-        return Err(CodegenError::TypeMismatch);
-    }
-
-    // Call FFI
     let call = ctx
         .builder
-        .build_call(func, &[arg_val.value.into()], "int_to_str")
+        .build_call(func, &[], "arg_count")
         .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
-
     let result = call.try_as_basic_value().left().unwrap();
 
     Ok(CodegenValue {
         value: result,
-        ty: Type::String,
+        ty: Type::Int,
     })
 }
 
-/// Generate code for float_to_string(float) -> string
-fn gen_float_to_string<'ctx>(
+/// Generate code for arg_at(int index) -> string
+fn gen_arg_at<'ctx>(
     ctx: &CodegenContext<'ctx>,
     locals: &HashMap<String, (inkwell::values::PointerValue<'ctx>, Type)>,
     args: &[Expr],
 ) -> CodegenResult<CodegenValue<'ctx>> {
     if args.len() != 1 {
         return Err(CodegenError::UnsupportedFeature(
-            "float_to_string expects 1 argument".to_string(),
+            "arg_at expects 1 argument".to_string(),
         ));
     }
 
-    // Declare or get lency_float_to_string FFI function
-    let func = if let Some(f) = ctx.module.get_function("lency_float_to_string") {
+    let func = if let Some(f) = ctx.module.get_function("lency_arg_at") {
         f
     } else {
-        let f64_type = ctx.context.f64_type();
+        let i64_type = ctx.context.i64_type();
         let ptr_type = ctx.context.i8_type().ptr_type(AddressSpace::default());
-        let fn_type = ptr_type.fn_type(&[f64_type.into()], false);
-        ctx.module
-            .add_function("lency_float_to_string", fn_type, None)
+        let fn_type = ptr_type.fn_type(&[i64_type.into()], false);
+        ctx.module.add_function("lency_arg_at", fn_type, None)
     };
 
-    // Evaluate argument
     let arg_val = generate_expr(ctx, locals, &args[0])?;
-    if arg_val.ty != Type::Float {
+    if arg_val.ty != Type::Int {
         return Err(CodegenError::TypeMismatch);
     }
 
-    // Call FFI
     let call = ctx
         .builder
-        .build_call(func, &[arg_val.value.into()], "float_to_str")
+        .build_call(func, &[arg_val.value.into()], "arg_at")
         .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
-
     let result = call.try_as_basic_value().left().unwrap();
 
     Ok(CodegenValue {
@@ -199,109 +252,98 @@ fn gen_float_to_string<'ctx>(
     })
 }
 
-/// Generate code for parse_int(string) -> int
-fn gen_parse_int<'ctx>(
+/// Generate code for int_to_string(int) -> string
+fn gen_int_to_string<'ctx>(
     ctx: &CodegenContext<'ctx>,
     locals: &HashMap<String, (inkwell::values::PointerValue<'ctx>, Type)>,
     args: &[Expr],
 ) -> CodegenResult<CodegenValue<'ctx>> {
     if args.len() != 1 {
         return Err(CodegenError::UnsupportedFeature(
-            "parse_int expects 1 argument".to_string(),
+            "int_to_string expects 1 argument".to_string(),
         ));
     }
 
-    // Declare or get lency_parse_int FFI function
-    let func = if let Some(f) = ctx.module.get_function("lency_parse_int") {
-        f
-    } else {
-        let i64_type = ctx.context.i64_type();
-        let ptr_type = ctx.context.i8_type().ptr_type(AddressSpace::default());
-        let fn_type = i64_type.fn_type(&[ptr_type.into(), ptr_type.into()], false);
-        ctx.module.add_function("lency_parse_int", fn_type, None)
-    };
-
-    // Evaluate argument
     let arg_val = generate_expr(ctx, locals, &args[0])?;
-    if arg_val.ty != Type::String {
+    if arg_val.ty != Type::Int {
         return Err(CodegenError::TypeMismatch);
     }
-
-    // Allocate is_ok flag (we ignore it for now, assume success)
-    let _i32_type = ctx.context.i32_type();
-    let is_ok_ptr = ctx
-        .builder
-        .build_alloca(_i32_type, "is_ok")
-        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
-
-    // Call FFI
-    let call = ctx
-        .builder
-        .build_call(
-            func,
-            &[arg_val.value.into(), is_ok_ptr.into()],
-            "parse_int_res",
-        )
-        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
-
-    let result = call.try_as_basic_value().left().unwrap();
-
-    Ok(CodegenValue {
-        value: result,
-        ty: Type::Int,
-    })
+    gen_primitive_to_string(ctx, arg_val)
 }
 
-/// Generate code for parse_float(string) -> float
-fn gen_parse_float<'ctx>(
+/// Generate code for float_to_string(float) -> string
+fn gen_float_to_string<'ctx>(
     ctx: &CodegenContext<'ctx>,
     locals: &HashMap<String, (inkwell::values::PointerValue<'ctx>, Type)>,
     args: &[Expr],
 ) -> CodegenResult<CodegenValue<'ctx>> {
     if args.len() != 1 {
         return Err(CodegenError::UnsupportedFeature(
-            "parse_float expects 1 argument".to_string(),
+            "float_to_string expects 1 argument".to_string(),
         ));
     }
 
-    // Declare or get lency_parse_float FFI function
-    let func = if let Some(f) = ctx.module.get_function("lency_parse_float") {
-        f
-    } else {
-        let f64_type = ctx.context.f64_type();
-        let ptr_type = ctx.context.i8_type().ptr_type(AddressSpace::default());
-        let fn_type = f64_type.fn_type(&[ptr_type.into(), ptr_type.into()], false);
-        ctx.module.add_function("lency_parse_float", fn_type, None)
-    };
-
-    // Evaluate argument
     let arg_val = generate_expr(ctx, locals, &args[0])?;
-    if arg_val.ty != Type::String {
+    if arg_val.ty != Type::Float {
         return Err(CodegenError::TypeMismatch);
     }
+    gen_primitive_to_string(ctx, arg_val)
+}
 
-    // Allocate is_ok flag (we ignore it for now, assume success)
-    let _i32_type = ctx.context.i32_type();
-    let is_ok_ptr = ctx
-        .builder
-        .build_alloca(_i32_type, "is_ok")
-        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+/// Generate code for x.to_string() on int / float / bool
+pub fn gen_primitive_to_string<'ctx>(
+    ctx: &CodegenContext<'ctx>,
+    value: CodegenValue<'ctx>,
+) -> CodegenResult<CodegenValue<'ctx>> {
+    let ptr_type = ctx.context.i8_type().ptr_type(AddressSpace::default());
+    let (ffi_name, param_type) = match value.ty {
+        Type::Int => ("lency_int_to_string", ctx.context.i64_type().into()),
+        Type::Float => ("lency_float_to_string", ctx.context.f64_type().into()),
+        Type::Bool => {
+            // bool 只有两个取值，直接选择常量字符串
+            let global = |text: &str, name: &str| {
+                ctx.builder
+                    .build_global_string_ptr(text, name)
+                    .map(|g| g.as_pointer_value())
+                    .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))
+            };
+            let result = ctx
+                .builder
+                .build_select(
+                    value.value.into_int_value(),
+                    global("true", "bool_true")?,
+                    global("false", "bool_false")?,
+                    "bool_to_str",
+                )
+                .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+            return Ok(CodegenValue {
+                value: result,
+                ty: Type::String,
+            });
+        }
+        _ => return Err(CodegenError::TypeMismatch),
+    };
+
+    // Declare or get lency_{int,float}_to_string FFI function
+    let func = ctx.module.get_function(ffi_name).unwrap_or_else(|| {
+        let fn_type = ptr_type.fn_type(&[param_type], false);
+        ctx.module.add_function(ffi_name, fn_type, None)
+    });
 
-    // Call FFI
     let call = ctx
         .builder
-        .build_call(
-            func,
-            &[arg_val.value.into(), is_ok_ptr.into()],
-            "parse_float_res",
-        )
+        .build_call(func, &[value.value.into()], "to_str")
         .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
-
-    let result = call.try_as_basic_value().left().unwrap();
+    let result = call
+        .try_as_basic_value()
+        .left()
+        .ok_or(CodegenError::LLVMBuildError(format!(
+            "{ffi_name} returned void"
+        )))?;
 
     Ok(CodegenValue {
         value: result,
-        ty: Type::Float,
+        ty: Type::String,
     })
 }
 
@@ -1,12 +1,12 @@
 //! Intrinsic Functions Code Generation
 //!
 //! 内置函数代码生成：print
-//! 文件 I/O 函数已移至 file_io 模块
+//! 文件 I/O 函数已移至 file_io 模块，结构体 / 枚举等复合类型的输出见 print_composite
 
 use crate::context::CodegenContext;
 use crate::error::{CodegenError, CodegenResult};
 use crate::expr::vec::{cast_from_i64, get_or_declare_vec_get, get_or_declare_vec_len};
-use crate::expr::{generate_expr, CodegenValue};
+use crate::expr::{generate_expr, print_composite, CodegenValue};
 use inkwell::AddressSpace;
 use lency_syntax::ast::{Expr, Type};
 use std::collections::HashMap;
@@ -32,11 +32,22 @@ pub fn gen_print<'ctx>(
 }
 
 /// 打印值内容（不换行）
-fn gen_print_value_impl<'ctx>(
+pub(super) fn gen_print_value_impl<'ctx>(
     ctx: &CodegenContext<'ctx>,
     value: inkwell::values::BasicValueEnum<'ctx>,
     ty: &Type,
 ) -> CodegenResult<()> {
+    // newtype 按底层类型打印
+    if let Type::Newtype(_, inner) = ty {
+        return gen_print_value_impl(ctx, value, inner);
+    }
+
+    // 窄整数符号扩展为 int 后打印
+    if matches!(ty, Type::I8 | Type::I16 | Type::I32) {
+        let widened = super::widen_int(ctx, value, &Type::Int)?;
+        return gen_print_value_impl(ctx, widened, &Type::Int);
+    }
+
     let i8_ptr_type = ctx.context.i8_type().ptr_type(AddressSpace::default());
     let i64_type = ctx.context.i64_type();
 
@@ -64,6 +75,21 @@ fn gen_print_value_impl<'ctx>(
                 )
                 .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
         }
+        Type::UInt => {
+            let printf_fn = get_printf();
+            let format_str = ctx
+                .builder
+                .build_global_string_ptr("%llu", "uint_fmt")
+                .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+
+            ctx.builder
+                .build_call(
+                    printf_fn,
+                    &[format_str.as_pointer_value().into(), value.into()],
+                    "print_uint",
+                )
+                .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+        }
         Type::Float => {
             let printf_fn = get_printf();
             let format_str = ctx
@@ -233,6 +259,15 @@ fn gen_print_value_impl<'ctx>(
             ctx.builder.position_at_end(end_bb);
             gen_print_str_literal(ctx, "]")?;
         }
+        Type::Struct(name) if ctx.struct_types.contains_key(name) => {
+            print_composite::gen_print_named(ctx, value.into_pointer_value(), name)?;
+        }
+        Type::Record(layout) => {
+            print_composite::gen_print_record(ctx, value.into_pointer_value(), layout)?;
+        }
+        Type::Nullable(inner) => {
+            print_composite::gen_print_nullable(ctx, value, inner)?;
+        }
         _ => {
             // 其他类型打印其类型名称或占位符
             gen_print_str_literal(ctx, "<unknown>")?;
@@ -241,7 +276,10 @@ fn gen_print_value_impl<'ctx>(
     Ok(())
 }
 
-fn gen_print_str_literal<'ctx>(ctx: &CodegenContext<'ctx>, s: &str) -> CodegenResult<()> {
+pub(super) fn gen_print_str_literal<'ctx>(
+    ctx: &CodegenContext<'ctx>,
+    s: &str,
+) -> CodegenResult<()> {
     let i8_ptr_type = ctx.context.i8_type().ptr_type(AddressSpace::default());
     let i64_type = ctx.context.i64_type();
 
@@ -275,27 +313,25 @@ fn gen_print_str_literal<'ctx>(ctx: &CodegenContext<'ctx>, s: &str) -> CodegenRe
 
 /// 生成 panic 内建函数调用
 /// panic(string message) -> void (never returns)
+///
+/// 调用 `__lency_panic(msg, line)` 打印带行号的消息并退出。之后的代码不可达，
+/// 后续语句生成到一个新的死代码块中，保证当前块只有一个终止指令。
 pub fn gen_panic<'ctx>(
     ctx: &CodegenContext<'ctx>,
     locals: &HashMap<String, (inkwell::values::PointerValue<'ctx>, lency_syntax::ast::Type)>,
     arg: &Expr,
+    line: u32,
 ) -> CodegenResult<CodegenValue<'ctx>> {
     let arg_val = generate_expr(ctx, locals, arg)?;
     let msg_ptr = arg_val.value.into_pointer_value();
 
-    let i8_ptr_type = ctx.context.i8_type().ptr_type(AddressSpace::default());
-
-    // 声明 C runtime 函数: void lency_panic(char* message)
-    let panic_fn = ctx.module.get_function("lency_panic").unwrap_or_else(|| {
-        let fn_type = ctx
-            .context
-            .void_type()
-            .fn_type(&[i8_ptr_type.into()], false);
-        ctx.module.add_function("lency_panic", fn_type, None)
-    });
+    let panic_fn = ctx
+        .panic_func
+        .ok_or_else(|| CodegenError::FunctionNotFound("__lency_panic".to_string()))?;
+    let line_val = ctx.context.i32_type().const_int(line as u64, false);
 
     ctx.builder
-        .build_call(panic_fn, &[msg_ptr.into()], "panic_call")
+        .build_call(panic_fn, &[msg_ptr.into(), line_val.into()], "")
         .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
 
     // panic 永远不返回，添加 unreachable 指令
@@ -303,9 +339,72 @@ pub fn gen_panic<'ctx>(
         .build_unreachable()
         .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
 
+    // 后续语句放入不可达的新块
+    let function = ctx
+        .builder
+        .get_insert_block()
+        .and_then(|bb| bb.get_parent())
+        .ok_or_else(|| CodegenError::LLVMBuildError("panic outside function".into()))?;
+    let dead_bb = ctx.context.append_basic_block(function, "after_panic");
+    ctx.builder.position_at_end(dead_bb);
+
     // 返回 void（实际上不会执行到）
     Ok(CodegenValue {
         value: ctx.context.i64_type().const_int(0, false).into(),
         ty: Type::Void,
     })
 }
+
+/// 生成 assert 内建函数调用
+/// assert(bool cond, string message) -> void
+///
+/// cond 为 false 时以 "Assertion failed: <message>" 调用 `__lency_panic`，否则什么也不做。
+/// message 只在失败分支中求值。
+pub fn gen_assert<'ctx>(
+    ctx: &CodegenContext<'ctx>,
+    locals: &HashMap<String, (inkwell::values::PointerValue<'ctx>, lency_syntax::ast::Type)>,
+    cond: &Expr,
+    message: &Expr,
+    line: u32,
+) -> CodegenResult<CodegenValue<'ctx>> {
+    let cond_val = generate_expr(ctx, locals, cond)?.value.into_int_value();
+
+    let panic_fn = ctx
+        .panic_func
+        .ok_or_else(|| CodegenError::FunctionNotFound("__lency_panic".to_string()))?;
+    let function = ctx
+        .builder
+        .get_insert_block()
+        .and_then(|bb| bb.get_parent())
+        .ok_or_else(|| CodegenError::LLVMBuildError("assert outside function".into()))?;
+    let fail_bb = ctx.context.append_basic_block(function, "assert_fail");
+    let cont_bb = ctx.context.append_basic_block(function, "assert_cont");
+
+    ctx.builder
+        .build_conditional_branch(cond_val, cont_bb, fail_bb)
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+
+    // 失败分支：拼接消息后 panic
+    ctx.builder.position_at_end(fail_bb);
+    let msg_val = generate_expr(ctx, locals, message)?
+        .value
+        .into_pointer_value();
+    let prefix = ctx
+        .builder
+        .build_global_string_ptr("Assertion failed: ", "assert_prefix")
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+    let full_msg = super::string_ops::concat(ctx, prefix.as_pointer_value(), msg_val)?;
+    let line_val = ctx.context.i32_type().const_int(line as u64, false);
+    ctx.builder
+        .build_call(panic_fn, &[full_msg.into(), line_val.into()], "")
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+    ctx.builder
+        .build_unreachable()
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+
+    ctx.builder.position_at_end(cont_bb);
+    Ok(CodegenValue {
+        value: ctx.context.i64_type().const_int(0, false).into(),
+        ty: Type::Void,
+    })
+}
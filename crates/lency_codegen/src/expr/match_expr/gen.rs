@@ -74,9 +74,8 @@ pub fn gen_match<'ctx>(
 
     let merge_bb = ctx.context.append_basic_block(current_func, "match_merge");
 
+    // 每个分支的 (值, Lency 类型, 跳转到 merge 的块)
     let mut incoming_values = Vec::new();
-    let mut result_type = Type::Void;
-    let mut result_llvm_type = None;
 
     // We will chain checks: Case 0 Test -> Case 0 Body -> Merge
     //                       |
@@ -140,39 +139,37 @@ pub fn gen_match<'ctx>(
 
         // Note: gen_pattern_check might have multiple checks.
         // e.g. case Some(1): check tag -> next_bb, check val -> next_bb.
-        // Success: Branch to body
-        ctx.builder.build_unconditional_branch(body_bb).unwrap();
 
-        // -----------------------------------------------------
-        // Generate Body in body_bb
-        // -----------------------------------------------------
-        ctx.builder.position_at_end(body_bb);
-
-        // Construct new locals for body
+        // Construct new locals for guard and body
         let mut body_locals = locals.clone();
         for (name, ptr, ty) in bindings {
             body_locals.insert(name, (ptr, ty));
         }
 
-        let body_val = generate_expr(ctx, &body_locals, &case.body)?;
-
-        // Record result type from first arm
-        if result_llvm_type.is_none() {
-            result_type = body_val.ty.clone();
-            result_llvm_type = Some(body_val.value.get_type());
+        // Guard: evaluated with pattern bindings in scope, falls through to next arm on false
+        if let Some(guard) = &case.guard {
+            let guard_val = generate_expr(ctx, &body_locals, guard)?;
+            ctx.builder
+                .build_conditional_branch(guard_val.value.into_int_value(), body_bb, next_bb)
+                .unwrap();
+        } else {
+            // Success: Branch to body
+            ctx.builder.build_unconditional_branch(body_bb).unwrap();
         }
 
-        // Capture result
-        incoming_values.push((body_val.value, body_bb));
+        // -----------------------------------------------------
+        // Generate Body in body_bb
+        // -----------------------------------------------------
+        ctx.builder.position_at_end(body_bb);
+
+        let body_val = generate_expr(ctx, &body_locals, &case.body)?;
+
+        // Capture result（分支体可能新建了基本块，以当前块作为 phi 的来源）
+        let body_end = ctx.builder.get_insert_block().unwrap();
+        incoming_values.push((body_val.value, body_val.ty, body_end));
 
         // Jump to merge
-        if ctx
-            .builder
-            .get_insert_block()
-            .unwrap()
-            .get_terminator()
-            .is_none()
-        {
+        if body_end.get_terminator().is_none() {
             ctx.builder.build_unconditional_branch(merge_bb).unwrap();
         }
     }
@@ -188,11 +185,8 @@ pub fn gen_match<'ctx>(
 
     if let Some(def) = default {
         let def_val = generate_expr(ctx, locals, def)?;
-        if result_llvm_type.is_none() {
-            result_type = def_val.ty.clone();
-            result_llvm_type = Some(def_val.value.get_type());
-        }
-        incoming_values.push((def_val.value, next_check_bb));
+        let def_end = ctx.builder.get_insert_block().unwrap();
+        incoming_values.push((def_val.value, def_val.ty, def_end));
         ctx.builder.build_unconditional_branch(merge_bb).unwrap();
     } else {
         // No default. If we reach here, it's a runtime mismatch error (or undefined).
@@ -203,19 +197,52 @@ pub fn gen_match<'ctx>(
         ctx.builder.build_unreachable().unwrap();
     }
 
-    // -----------------------------------------------------
-    // Merge Block
-    // -----------------------------------------------------
-    ctx.builder.position_at_end(merge_bb);
-
     if incoming_values.is_empty() {
         // Should not happen if cases > 0 or default exists
+        ctx.builder.position_at_end(merge_bb);
         return Err(CodegenError::LLVMBuildError("Empty match".to_string()));
     }
 
+    // 分支类型不一致时（null 与 T、int 与 float），在各分支跳转前转换为统一的结果类型
+    let result_type = unify_arm_types(incoming_values.iter().map(|(_, ty, _)| ty));
+    let mut converted = Vec::with_capacity(incoming_values.len());
+    for (value, ty, bb) in incoming_values {
+        if ty == result_type {
+            converted.push((value, bb));
+            continue;
+        }
+        match bb.get_terminator() {
+            Some(terminator) => ctx.builder.position_before(&terminator),
+            None => ctx.builder.position_at_end(bb),
+        }
+        let value: inkwell::values::BasicValueEnum<'ctx> = match (&ty, &result_type) {
+            (Type::Int, Type::Float) => ctx
+                .builder
+                .build_signed_int_to_float(
+                    value.into_int_value(),
+                    ctx.context.f64_type(),
+                    "match_arm_float",
+                )
+                .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?
+                .into(),
+            // 与可空字段相同的表示：非指针值装箱，null 转换指针类型
+            (_, Type::Nullable(_)) => {
+                crate::expr::struct_init::coerce_field_value(ctx, value, &result_type)?
+            }
+            _ => value,
+        };
+        converted.push((value, bb));
+    }
+
+    // -----------------------------------------------------
+    // Merge Block
+    // -----------------------------------------------------
+    ctx.builder.position_at_end(merge_bb);
+
+    let result_llvm_type = converted.first().map(|(v, _)| v.get_type());
     if let Some(phi_ty) = result_llvm_type {
         let phi = ctx.builder.build_phi(phi_ty, "match_result").unwrap();
-        for (v, bb) in incoming_values {
+        for (v, bb) in converted {
             phi.add_incoming(&[(&v, bb)]);
         }
 
@@ -233,3 +260,33 @@ pub fn gen_match<'ctx>(
         })
     }
 }
+
+/// 各分支的统一结果类型：null 分支与 `T` 分支合并为 `T?`，int 与 float 合并为 float，
+/// 其余情况以第一个分支为准（类型检查已保证兼容）
+fn unify_arm_types<'a>(mut types: impl Iterator<Item = &'a Type>) -> Type {
+    let is_null = |ty: &Type| matches!(ty, Type::Nullable(inner) if **inner == Type::Void);
+    let nullable = |ty: &Type| match ty {
+        Type::Nullable(_) => ty.clone(),
+        _ => Type::Nullable(Box::new(ty.clone())),
+    };
+    let Some(first) = types.next() else {
+        return Type::Void;
+    };
+    types.fold(first.clone(), |acc, ty| {
+        if is_null(&acc) {
+            if is_null(ty) {
+                acc
+            } else {
+                nullable(ty)
+            }
+        } else if is_null(ty) {
+            nullable(&acc)
+        } else if matches!(ty, Type::Nullable(_)) && !matches!(acc, Type::Nullable(_)) {
+            ty.clone()
+        } else if acc == Type::Int && *ty == Type::Float {
+            Type::Float
+        } else {
+            acc
+        }
+    })
+}
@@ -2,3 +2,4 @@ mod gen;
 mod pattern;
 
 pub use gen::gen_match;
+pub(crate) use pattern::gen_pattern_check;
@@ -191,6 +191,100 @@ pub(crate) fn gen_pattern_check<'ctx>(
             ctx.builder.position_at_end(success_bb);
             Ok(())
         }
+        MatchPattern::Range { start, end } => {
+            // Integer range: start <= subject && subject <= end
+            let (lency_syntax::ast::Literal::Int(lo), lency_syntax::ast::Literal::Int(hi)) =
+                (start, end)
+            else {
+                return Err(CodegenError::UnsupportedFeature(
+                    "Non-integer range in match".into(),
+                ));
+            };
+            if *subject_type != Type::Int {
+                return Err(CodegenError::TypeMismatch);
+            }
+
+            let i64_type = ctx.context.i64_type();
+            let load_val = ctx
+                .builder
+                .build_load(i64_type, subject_ptr, "range_chk_load")
+                .unwrap()
+                .into_int_value();
+            let ge_start = ctx
+                .builder
+                .build_int_compare(
+                    inkwell::IntPredicate::SGE,
+                    load_val,
+                    i64_type.const_int(*lo as u64, true),
+                    "range_ge",
+                )
+                .unwrap();
+            let le_end = ctx
+                .builder
+                .build_int_compare(
+                    inkwell::IntPredicate::SLE,
+                    load_val,
+                    i64_type.const_int(*hi as u64, true),
+                    "range_le",
+                )
+                .unwrap();
+            let in_range = ctx.builder.build_and(ge_start, le_end, "in_range").unwrap();
+
+            let success_bb = ctx.context.append_basic_block(
+                ctx.builder
+                    .get_insert_block()
+                    .unwrap()
+                    .get_parent()
+                    .unwrap(),
+                "range_match_success",
+            );
+
+            ctx.builder
+                .build_conditional_branch(in_range, success_bb, mismatch_bb)
+                .unwrap();
+            ctx.builder.position_at_end(success_bb);
+            Ok(())
+        }
+        MatchPattern::Or(alternatives) => {
+            // Try each alternative in turn: a failed check jumps to the next alternative,
+            // any success jumps to the shared success block. Or-patterns bind nothing (Sema).
+            let current_func = ctx
+                .builder
+                .get_insert_block()
+                .unwrap()
+                .get_parent()
+                .unwrap();
+            let success_bb = ctx
+                .context
+                .append_basic_block(current_func, "or_match_success");
+
+            for (i, alt) in alternatives.iter().enumerate() {
+                let is_last = i == alternatives.len() - 1;
+                let alt_mismatch_bb = if is_last {
+                    mismatch_bb
+                } else {
+                    ctx.context.append_basic_block(current_func, "or_alt_next")
+                };
+
+                gen_pattern_check(
+                    ctx,
+                    locals,
+                    alt,
+                    subject_ptr,
+                    subject_type,
+                    bindings,
+                    alt_mismatch_bb,
+                )?;
+                ctx.builder.build_unconditional_branch(success_bb).unwrap();
+
+                if !is_last {
+                    ctx.builder.position_at_end(alt_mismatch_bb);
+                }
+            }
+
+            ctx.builder.position_at_end(success_bb);
+            Ok(())
+        }
         MatchPattern::Variant {
             name: variant_name,
             sub_patterns,
@@ -202,12 +296,14 @@ pub(crate) fn gen_pattern_check<'ctx>(
             // We need to know the tag index for `variant_name`.
             // The Enum Name is in `subject_type`.
             let enum_name = match subject_type {
-                Type::Struct(n) => n,
-                Type::Generic(n, _) => n, // Generic Enum
+                Type::Struct(n) => n.clone(),
+                // 泛型枚举按单态化后的实例名查找（Maybe<int> -> Maybe__int），各实例有独立布局
+                Type::Generic(..) => lency_monomorph::mangling::mangle_type(subject_type),
                 // Sprint 15: Treat Result<T, E> as enum "Result"
-                Type::Result { .. } => "Result",
+                Type::Result { .. } => "Result".to_string(),
                 _ => return Err(CodegenError::TypeMismatch),
             };
+            let enum_name = enum_name.as_str();
 
             // Look up variants info to find index
             // Sprint 15: Special handling for Result<T,E>
@@ -264,7 +360,7 @@ pub(crate) fn gen_pattern_check<'ctx>(
                 .build_load(ctx.context.i64_type(), tag_ptr, "tag_val")
                 .unwrap()
                 .into_int_value();
-            let expected_tag = ctx.context.i64_type().const_int(tag_idx as u64, false);
+            let expected_tag = ctx.enum_tag(enum_name, tag_idx);
 
             let tag_cmp = ctx
                 .builder
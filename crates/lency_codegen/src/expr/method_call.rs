@@ -41,7 +41,7 @@ pub fn gen_method_call<'ctx>(
                 name.clone()
             };
 
-            let ctor_name = format!("{}_{}", struct_name, method_name);
+            let ctor_name = lency_monomorph::mangling::method_symbol(&struct_name, method_name);
             let function = ctx
                 .module
                 .get_function(&ctor_name)
@@ -65,10 +65,14 @@ pub fn gen_method_call<'ctx>(
                     "Constructor returned void".into(),
                 ))?;
 
-            return Ok(CodegenValue {
-                value: val,
-                ty: Type::Struct(struct_name),
-            });
+            // from_int 返回 Enum?（未知判别值得到 null）
+            let ty = if method_name == "from_int" && !ctx.enum_has_variant(&struct_name, "from_int")
+            {
+                Type::Nullable(Box::new(Type::Struct(struct_name)))
+            } else {
+                Type::Struct(struct_name)
+            };
+            return Ok(CodegenValue { value: val, ty });
         }
     }
 
@@ -79,6 +83,14 @@ pub fn gen_method_call<'ctx>(
     // Clone type to avoid borrowing object_val, so we can move it
     let object_type = object_val.ty.clone();
 
+    // int / float / bool 的内置 to_string()
+    if method_name == "to_string"
+        && args.is_empty()
+        && matches!(object_type, Type::Int | Type::Float | Type::Bool)
+    {
+        return crate::expr::conversion::gen_primitive_to_string(ctx, object_val);
+    }
+
     match object_type {
         Type::Vec(inner) => crate::expr::vec::gen_vec_method_call(
             ctx,
@@ -88,18 +100,11 @@ pub fn gen_method_call<'ctx>(
             args,
             &inner,
         ),
-        // Primitive types: int, string, bool
-        Type::Int | Type::String | Type::Bool => {
-            // 获取类型名称用于 mangling
-            let type_name = match &object_val.ty {
-                Type::Int => "int",
-                Type::String => "string",
-                Type::Bool => "bool",
-                _ => unreachable!(),
-            };
-
-            // 构建 mangled name: int_hash, string_eq 等
-            let mangled_name = format!("{}_{}", type_name, method_name);
+        // Primitive types: impl int / float / string / bool 中定义的方法
+        Type::Int | Type::Float | Type::String | Type::Bool => {
+            // 构建 mangled name: int_hash, string_eq 等，与声明 impl 方法时一致
+            let type_name = lency_monomorph::mangling::mangle_receiver(&object_val.ty);
+            let mangled_name = lency_monomorph::mangling::method_symbol(&type_name, method_name);
 
             // 查找函数
             let function = ctx
@@ -180,6 +185,20 @@ pub fn gen_method_call<'ctx>(
                 );
             }
 
+            // 枚举内置方法 discriminant() / as_int()：读取 tag（用户同名方法优先）
+            if matches!(method_name, "discriminant" | "as_int")
+                && ctx.enum_types.contains(&struct_name)
+                && ctx
+                    .module
+                    .get_function(&lency_monomorph::mangling::method_symbol(
+                        &struct_name,
+                        method_name,
+                    ))
+                    .is_none()
+            {
+                return gen_enum_discriminant(ctx, this_ptr, &struct_name);
+            }
+
             // Sprint 15: Option 内置方法支持
             if struct_name.starts_with("Option__") {
                 if let Some(res) = crate::expr::option::gen_option_builtin_method(
@@ -242,7 +261,7 @@ pub fn gen_method_call<'ctx>(
             }
 
             // 构建 mangled name
-            let mangled_name = format!("{}_{}", struct_name, method_name);
+            let mangled_name = lency_monomorph::mangling::method_symbol(&struct_name, method_name);
 
             // 查找函数
             let function = ctx
@@ -256,10 +275,12 @@ pub fn gen_method_call<'ctx>(
             // 将 this_ptr 作为第一个参数
             compiled_args.push(this_ptr.into());
 
-            // 添加其他参数
-            for arg in args {
+            // 添加其他参数（窄整数实参拓宽到形参的宽度）
+            let param_types = function.get_type().get_param_types();
+            for (arg, param_ty) in args.iter().zip(param_types.iter().skip(1)) {
                 let arg_val = generate_expr(ctx, locals, arg)?;
-                compiled_args.push(arg_val.value.into());
+                let value = super::widen_int_to(ctx, arg_val.value, *param_ty)?;
+                compiled_args.push(value.into());
             }
 
             // 获取返回类型
@@ -292,6 +313,24 @@ pub fn gen_method_call<'ctx>(
                 })
             }
         }
+        Type::TraitObject(trait_name) => crate::expr::trait_object::gen_trait_object_call(
+            ctx,
+            locals,
+            object_val,
+            &trait_name,
+            method_name,
+            args,
+        ),
+        Type::Nullable(inner) if matches!(method_name, "value_or" | "map") => {
+            crate::expr::nullable::gen_nullable_builtin_method(
+                ctx,
+                locals,
+                object_val,
+                method_name,
+                args,
+                &inner,
+            )
+        }
         Type::Result { ok_type, err_type } => {
             // Result 类型方法调用
             // Result 使用指针语义，类似 Struct
@@ -317,11 +356,12 @@ pub fn gen_method_call<'ctx>(
 
             // Fallback: 查找编译的方法函数
             // 构建 mangled 方法名：Result__int_Error_unwrap_or
-            let result_type_mangled = lency_monomorph::mangling::mangle_type(&Type::Result {
+            let result_type_mangled = lency_monomorph::mangling::mangle_receiver(&Type::Result {
                 ok_type: ok_type.clone(),
                 err_type: err_type.clone(),
             });
-            let mangled_name = format!("{}_{}", result_type_mangled, method_name);
+            let mangled_name =
+                lency_monomorph::mangling::method_symbol(&result_type_mangled, method_name);
 
             // 查找函数
             let function = ctx
@@ -374,3 +414,27 @@ pub fn gen_method_call<'ctx>(
         _ => Err(CodegenError::TypeMismatch),
     }
 }
+
+/// 读取枚举值的 tag（布局 `{ i64 tag, payload }` 的第 0 个字段）
+fn gen_enum_discriminant<'ctx>(
+    ctx: &CodegenContext<'ctx>,
+    this_ptr: PointerValue<'ctx>,
+    enum_name: &str,
+) -> CodegenResult<CodegenValue<'ctx>> {
+    let enum_type = *ctx
+        .struct_types
+        .get(enum_name)
+        .ok_or_else(|| CodegenError::UndefinedStructType(enum_name.to_string()))?;
+    let tag_ptr = ctx
+        .builder
+        .build_struct_gep(enum_type, this_ptr, 0, "tag_ptr")
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+    let tag = ctx
+        .builder
+        .build_load(ctx.context.i64_type(), tag_ptr, "discriminant")
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+    Ok(CodegenValue {
+        value: tag,
+        ty: Type::Int,
+    })
+}
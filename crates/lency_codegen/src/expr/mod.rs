@@ -13,15 +13,22 @@ mod intrinsic;
 mod literal;
 mod match_expr;
 mod method_call;
+mod nullable;
 pub mod option;
+mod parse;
+mod print_composite;
 mod result;
+mod string_bytes;
 mod string_ops;
 mod struct_access;
-mod struct_init;
+pub(crate) mod struct_init;
+mod trait_object;
 mod unary;
 mod variable;
 mod vec;
 
+pub(crate) use match_expr::gen_pattern_check;
+
 use inkwell::values::{BasicValueEnum, PointerValue};
 use lency_syntax::ast::{Expr, ExprKind};
 use std::collections::HashMap;
@@ -63,6 +70,104 @@ impl<'ctx, 'a> ExprGenerator<'ctx, 'a> {
         // Return Type too for verification if needed
         generate_lvalue_addr(self.ctx, self.locals, expr)
     }
+
+    /// 生成索引赋值 `array[index] = value`（支持固定数组与 Vec）
+    pub fn generate_index_assignment(
+        &self,
+        array: &Expr,
+        index: &Expr,
+        value: &Expr,
+    ) -> CodegenResult<()> {
+        let line = self.ctx.get_line(array.span.start);
+        array::gen_index_assignment(self.ctx, self.locals, array, index, value, line)
+    }
+}
+
+/// 将指针值转换为目标类型对应的指针类型
+///
+/// 用于 null 字面量（i8*）赋给 `T?` 等只有指针类型不同的场景；
+/// 非指针值或类型已一致时原样返回
+pub(crate) fn coerce_pointer<'ctx>(
+    ctx: &CodegenContext<'ctx>,
+    value: BasicValueEnum<'ctx>,
+    target: &lency_syntax::ast::Type,
+) -> CodegenResult<BasicValueEnum<'ctx>> {
+    use crate::types::ToLLVMType;
+
+    let BasicValueEnum::PointerValue(ptr) = value else {
+        return Ok(value);
+    };
+    let Ok(inkwell::types::BasicTypeEnum::PointerType(target_ty)) = target.to_llvm_type(ctx) else {
+        return Ok(value);
+    };
+    if ptr.get_type() == target_ty {
+        return Ok(value);
+    }
+    ctx.builder
+        .build_pointer_cast(ptr, target_ty, "ptr_coerce")
+        .map(Into::into)
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))
+}
+
+/// 整数隐式拓宽：窄整数值符号扩展到目标整数类型的位宽（如 i32 值赋给 int）
+///
+/// 类型检查只允许拓宽，目标不是更宽的整数类型时原样返回
+pub(crate) fn widen_int<'ctx>(
+    ctx: &CodegenContext<'ctx>,
+    value: BasicValueEnum<'ctx>,
+    target: &lency_syntax::ast::Type,
+) -> CodegenResult<BasicValueEnum<'ctx>> {
+    use crate::types::ToLLVMType;
+
+    match target.to_llvm_type(ctx) {
+        Ok(target_ty) => widen_int_to(ctx, value, target_ty),
+        Err(_) => Ok(value),
+    }
+}
+
+/// 同 [`widen_int`]，目标以 LLVM 类型给出（用于只知道形参 LLVM 类型的调用）
+pub(crate) fn widen_int_to<'ctx>(
+    ctx: &CodegenContext<'ctx>,
+    value: BasicValueEnum<'ctx>,
+    target: inkwell::types::BasicTypeEnum<'ctx>,
+) -> CodegenResult<BasicValueEnum<'ctx>> {
+    let (BasicValueEnum::IntValue(int_val), inkwell::types::BasicTypeEnum::IntType(target_ty)) =
+        (value, target)
+    else {
+        return Ok(value);
+    };
+    // i1 是 bool，不参与整数拓宽
+    let from_bits = int_val.get_type().get_bit_width();
+    if from_bits == 1 || from_bits >= target_ty.get_bit_width() {
+        return Ok(value);
+    }
+    ctx.builder
+        .build_int_s_extend(int_val, target_ty, "int_widen")
+        .map(Into::into)
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))
+}
+
+/// C 的默认实参提升：bool 零扩展、i8/i16 符号扩展为 i32，其余原样传递
+pub(crate) fn promote_variadic_arg<'ctx>(
+    ctx: &CodegenContext<'ctx>,
+    value: BasicValueEnum<'ctx>,
+) -> CodegenResult<BasicValueEnum<'ctx>> {
+    let BasicValueEnum::IntValue(int_val) = value else {
+        return Ok(value);
+    };
+    let i32_type = ctx.context.i32_type();
+    let promoted = match int_val.get_type().get_bit_width() {
+        1 => ctx
+            .builder
+            .build_int_z_extend(int_val, i32_type, "vararg_bool"),
+        8 | 16 => ctx
+            .builder
+            .build_int_s_extend(int_val, i32_type, "vararg_int"),
+        _ => return Ok(value),
+    };
+    promoted
+        .map(Into::into)
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))
 }
 
 /// 内部辅助函数：生成表达式代码
@@ -87,10 +192,14 @@ fn generate_expr<'ctx>(
                 // gen_method_call 内部会验证 object 是否为 Struct
                 let line = ctx.get_line(callee.span.start);
                 method_call::gen_method_call(ctx, locals, object, name, args, line)
+            } else if let ExprKind::Path { type_name, member } = &callee.kind {
+                call::gen_associated_call(ctx, locals, type_name, member, args)
             } else {
                 call::gen_call(ctx, locals, callee, args)
             }
         }
+        // 语义分析保证关联函数路径只作为被调用者出现
+        ExprKind::Path { .. } => Err(CodegenError::UnsupportedExpression),
         ExprKind::Match {
             value,
             cases,
@@ -123,7 +232,20 @@ fn generate_expr<'ctx>(
             };
             struct_init::gen_struct_literal(ctx, locals, type_name, fields)
         }
+        ExprKind::RecordLiteral(fields) => struct_init::gen_record_literal(ctx, locals, fields),
         ExprKind::VecLiteral(elements) => vec::gen_vec_literal(ctx, locals, elements),
+        ExprKind::Repeat {
+            value,
+            count,
+            is_vec: true,
+        } => vec::gen_vec_repeat(ctx, locals, value, count),
+        ExprKind::Repeat { value, count, .. } => match &count.kind {
+            // Sema 保证数组长度是非负整数字面量
+            ExprKind::Literal(lency_syntax::ast::Literal::Int(n)) => {
+                array::gen_array_repeat(ctx, locals, value, *n as u32)
+            }
+            _ => Err(CodegenError::UnsupportedExpression),
+        },
         ExprKind::GenericInstantiation { .. } => {
             unreachable!("GenericInstantiation (turbo-fish) should be monomorphized before codegen")
         }
@@ -144,7 +266,16 @@ fn generate_expr<'ctx>(
         }
         ExprKind::CharToString(arg) => string_ops::gen_char_to_string(ctx, locals, arg),
         ExprKind::Format(template, args) => string_ops::gen_format(ctx, locals, template, args),
-        ExprKind::Panic(arg) => intrinsic::gen_panic(ctx, locals, arg),
+        ExprKind::Fmt { template, args } => string_ops::gen_fmt(ctx, locals, template, args),
+        ExprKind::Cast { ty, value } => conversion::gen_cast(ctx, locals, value, ty),
+        ExprKind::Panic(arg) => {
+            let line = ctx.get_line(expr.span.start);
+            intrinsic::gen_panic(ctx, locals, arg, line)
+        }
+        ExprKind::Assert(cond, message) => {
+            let line = ctx.get_line(expr.span.start);
+            intrinsic::gen_assert(ctx, locals, cond, message, line)
+        }
     }
 }
 
@@ -159,10 +290,14 @@ fn generate_lvalue_addr<'ctx>(
             unreachable!("GenericInstantiation (turbo-fish) should be monomorphized before codegen")
         }
         ExprKind::Variable(name) => {
-            let (ptr, ty) = locals
-                .get(name)
-                .ok_or_else(|| CodegenError::UndefinedVariable(name.clone()))?;
-            Ok((*ptr, ty.clone()))
+            if let Some((ptr, ty)) = locals.get(name) {
+                return Ok((*ptr, ty.clone()));
+            }
+            // 全局变量（由 __lency_init 初始化）同样可以赋值
+            match (ctx.module.get_global(name), ctx.global_var_types.get(name)) {
+                (Some(global), Some(ty)) => Ok((global.as_pointer_value(), ty.clone())),
+                _ => Err(CodegenError::UndefinedVariable(name.clone())),
+            }
         }
         ExprKind::Get { object, name } => {
             let line = ctx.get_line(expr.span.start);
@@ -213,6 +348,10 @@ fn generate_lvalue_addr<'ctx>(
 
             Ok((ptr, field_ty))
         }
+        ExprKind::Index { array, index } => {
+            let line = ctx.get_line(expr.span.start);
+            array::gen_index_lvalue(ctx, locals, array, index, line)
+        }
         _ => Err(CodegenError::UnsupportedExpression),
     }
 }
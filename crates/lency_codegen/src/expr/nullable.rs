@@ -0,0 +1,198 @@
+//! Nullable Intrinsic Methods
+//!
+//! 内置实现 T? 的方法：value_or, map
+//!
+//! 可空值的表示与 types.rs 一致：指针类型 (string、结构体等) 直接以空指针表示 null，
+//! 其余类型使用指向值的指针
+
+use crate::context::CodegenContext;
+use crate::error::{CodegenError, CodegenResult};
+use crate::expr::{coerce_pointer, generate_expr, CodegenValue};
+use crate::types::ToLLVMType;
+use inkwell::types::BasicType;
+use inkwell::values::{BasicValueEnum, PointerValue};
+use lency_syntax::ast::{Expr, Type};
+use std::collections::HashMap;
+
+/// T? 内置方法实现
+pub fn gen_nullable_builtin_method<'ctx>(
+    ctx: &CodegenContext<'ctx>,
+    locals: &HashMap<String, (PointerValue<'ctx>, Type)>,
+    object_val: CodegenValue<'ctx>,
+    method_name: &str,
+    args: &[Expr],
+    inner: &Type,
+) -> CodegenResult<CodegenValue<'ctx>> {
+    if args.len() != 1 {
+        return Err(CodegenError::TypeMismatch);
+    }
+    // 未装箱的值（如字面量初始化的 int?）一定非空
+    let BasicValueEnum::PointerValue(ptr) = object_val.value else {
+        return match method_name {
+            "value_or" => Ok(CodegenValue {
+                value: object_val.value,
+                ty: inner.clone(),
+            }),
+            _ => gen_map_call(ctx, locals, object_val.value, &args[0]),
+        };
+    };
+
+    let function = ctx
+        .builder
+        .get_insert_block()
+        .and_then(|bb| bb.get_parent())
+        .ok_or(CodegenError::LLVMBuildError(
+            "No parent function".to_string(),
+        ))?;
+    let some_bb = ctx.context.append_basic_block(function, "nullable_some");
+    let none_bb = ctx.context.append_basic_block(function, "nullable_none");
+    let merge_bb = ctx.context.append_basic_block(function, "nullable_merge");
+
+    let is_null = ctx
+        .builder
+        .build_is_null(ptr, "nullable_is_null")
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+    ctx.builder
+        .build_conditional_branch(is_null, none_bb, some_bb)
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+
+    // 非空分支：取出内部值
+    ctx.builder.position_at_end(some_bb);
+    let inner_val = unwrap_nullable(ctx, ptr, inner)?;
+    let (some_val, result_ty) = match method_name {
+        "value_or" => (inner_val, inner.clone()),
+        _ => {
+            let mapped = gen_map_call(ctx, locals, inner_val, &args[0])?;
+            (mapped.value, mapped.ty)
+        }
+    };
+    let some_end_bb = ctx
+        .builder
+        .get_insert_block()
+        .ok_or(CodegenError::LLVMBuildError("No insert block".to_string()))?;
+    ctx.builder
+        .build_unconditional_branch(merge_bb)
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+
+    // 为空分支：value_or 求值默认值，map 结果仍为 null
+    ctx.builder.position_at_end(none_bb);
+    let none_val = match method_name {
+        "value_or" => generate_expr(ctx, locals, &args[0])?.value,
+        _ => some_val.get_type().into_pointer_type().const_null().into(),
+    };
+    let none_end_bb = ctx
+        .builder
+        .get_insert_block()
+        .ok_or(CodegenError::LLVMBuildError("No insert block".to_string()))?;
+    ctx.builder
+        .build_unconditional_branch(merge_bb)
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+
+    ctx.builder.position_at_end(merge_bb);
+    let phi = ctx
+        .builder
+        .build_phi(some_val.get_type(), "nullable_result")
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+    phi.add_incoming(&[(&some_val, some_end_bb), (&none_val, none_end_bb)]);
+
+    Ok(CodegenValue {
+        value: phi.as_basic_value(),
+        ty: result_ty,
+    })
+}
+
+/// 从非空的可空值中取出 T
+fn unwrap_nullable<'ctx>(
+    ctx: &CodegenContext<'ctx>,
+    ptr: PointerValue<'ctx>,
+    inner: &Type,
+) -> CodegenResult<BasicValueEnum<'ctx>> {
+    let inner_llvm = inner.to_llvm_type(ctx)?;
+    if inner_llvm.is_pointer_type() {
+        return coerce_pointer(ctx, ptr.into(), inner);
+    }
+    ctx.builder
+        .build_load(inner_llvm, ptr, "nullable_value")
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))
+}
+
+/// 调用 map 的函数参数，并把结果包装为可空值 (非指针类型需装箱)
+fn gen_map_call<'ctx>(
+    ctx: &CodegenContext<'ctx>,
+    locals: &HashMap<String, (PointerValue<'ctx>, Type)>,
+    value: BasicValueEnum<'ctx>,
+    func_expr: &Expr,
+) -> CodegenResult<CodegenValue<'ctx>> {
+    let func_val = generate_expr(ctx, locals, func_expr)?;
+    let (param_types, return_type) = match &func_val.ty {
+        Type::Function {
+            param_types,
+            return_type,
+        } => (param_types.clone(), *return_type.clone()),
+        _ => return Err(CodegenError::TypeMismatch),
+    };
+
+    let param_llvm_types = param_types
+        .iter()
+        .map(|t| t.to_llvm_type(ctx).map(|ty| ty.into()))
+        .collect::<CodegenResult<Vec<_>>>()?;
+    let ret_llvm = return_type.to_llvm_type(ctx)?;
+    let fn_type = ret_llvm.fn_type(&param_llvm_types, false);
+    let mapped = ctx
+        .builder
+        .build_indirect_call(
+            fn_type,
+            func_val.value.into_pointer_value(),
+            &[value.into()],
+            "nullable_map",
+        )
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?
+        .try_as_basic_value()
+        .left()
+        .ok_or(CodegenError::TypeMismatch)?;
+
+    if let Type::Nullable(_) = return_type {
+        return Ok(CodegenValue {
+            value: mapped,
+            ty: return_type,
+        });
+    }
+    let ty = Type::Nullable(Box::new(return_type));
+    if ret_llvm.is_pointer_type() {
+        return Ok(CodegenValue { value: mapped, ty });
+    }
+
+    // 装箱：malloc 一块空间存放结果
+    let size = ret_llvm.size_of().ok_or(CodegenError::LLVMBuildError(
+        "Failed to get size of mapped value".to_string(),
+    ))?;
+    let malloc = ctx
+        .module
+        .get_function("malloc")
+        .ok_or(CodegenError::LLVMBuildError("malloc not found".to_string()))?;
+    let raw_ptr = ctx
+        .builder
+        .build_call(malloc, &[size.into()], "malloc_nullable")
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?
+        .try_as_basic_value()
+        .left()
+        .ok_or(CodegenError::LLVMBuildError(
+            "malloc returned void".to_string(),
+        ))?
+        .into_pointer_value();
+    let boxed = ctx
+        .builder
+        .build_pointer_cast(
+            raw_ptr,
+            ret_llvm.ptr_type(inkwell::AddressSpace::default()),
+            "boxed_nullable",
+        )
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+    ctx.builder
+        .build_store(boxed, mapped)
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+    Ok(CodegenValue {
+        value: boxed.into(),
+        ty,
+    })
+}
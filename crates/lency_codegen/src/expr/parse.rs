@@ -0,0 +1,252 @@
+//! Parse Intrinsics Code Generation
+//!
+//! parse_int / parse_float：调用带 is_ok 出参的运行时函数，
+//! 再把结果包装为 `int!` / `float!`，解析失败时携带 Error { message }
+
+use inkwell::types::{BasicType, BasicTypeEnum};
+use inkwell::values::{BasicValueEnum, PointerValue};
+use inkwell::AddressSpace;
+use lency_syntax::ast::{Expr, Type};
+use std::collections::HashMap;
+
+use super::string_ops::concat;
+use crate::context::CodegenContext;
+use crate::error::{CodegenError, CodegenResult};
+use crate::expr::{generate_expr, CodegenValue};
+
+/// Generate code for parse_int(string) -> int!
+pub(super) fn gen_parse_int<'ctx>(
+    ctx: &CodegenContext<'ctx>,
+    locals: &HashMap<String, (PointerValue<'ctx>, Type)>,
+    args: &[Expr],
+) -> CodegenResult<CodegenValue<'ctx>> {
+    let i64_type = ctx.context.i64_type().into();
+    gen_parse(ctx, locals, args, "parse_int", Type::Int, i64_type)
+}
+
+/// Generate code for parse_float(string) -> float!
+pub(super) fn gen_parse_float<'ctx>(
+    ctx: &CodegenContext<'ctx>,
+    locals: &HashMap<String, (PointerValue<'ctx>, Type)>,
+    args: &[Expr],
+) -> CodegenResult<CodegenValue<'ctx>> {
+    let f64_type = ctx.context.f64_type().into();
+    gen_parse(ctx, locals, args, "parse_float", Type::Float, f64_type)
+}
+
+fn gen_parse<'ctx>(
+    ctx: &CodegenContext<'ctx>,
+    locals: &HashMap<String, (PointerValue<'ctx>, Type)>,
+    args: &[Expr],
+    name: &str,
+    ok_ty: Type,
+    llvm_ok_ty: BasicTypeEnum<'ctx>,
+) -> CodegenResult<CodegenValue<'ctx>> {
+    if args.len() != 1 {
+        return Err(CodegenError::UnsupportedFeature(format!(
+            "{name} expects 1 argument"
+        )));
+    }
+
+    // 1. 声明 FFI: T lency_parse_xxx(i8* s, i32* is_ok)
+    let ffi_name = format!("lency_{name}");
+    let ptr_type = ctx.context.i8_type().ptr_type(AddressSpace::default());
+    let func = ctx.module.get_function(&ffi_name).unwrap_or_else(|| {
+        let fn_type = llvm_ok_ty.fn_type(&[ptr_type.into(), ptr_type.into()], false);
+        ctx.module.add_function(&ffi_name, fn_type, None)
+    });
+
+    let arg_val = generate_expr(ctx, locals, &args[0])?;
+    if arg_val.ty != Type::String {
+        return Err(CodegenError::TypeMismatch);
+    }
+    let input = arg_val.value.into_pointer_value();
+
+    // 2. 调用 FFI，读取 is_ok 出参
+    let is_ok_slot = ctx
+        .builder
+        .build_alloca(ctx.context.i32_type(), "is_ok")
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+    let is_ok_arg = ctx
+        .builder
+        .build_pointer_cast(is_ok_slot, ptr_type, "is_ok_arg")
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+    let parsed = ctx
+        .builder
+        .build_call(
+            func,
+            &[input.into(), is_ok_arg.into()],
+            &format!("{name}_res"),
+        )
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?
+        .try_as_basic_value()
+        .left()
+        .ok_or(CodegenError::LLVMBuildError(format!(
+            "{ffi_name} returned void"
+        )))?;
+    let is_ok_raw = ctx
+        .builder
+        .build_load(ctx.context.i32_type(), is_ok_slot, "is_ok_raw")
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?
+        .into_int_value();
+    let is_ok = ctx
+        .builder
+        .build_int_compare(
+            inkwell::IntPredicate::NE,
+            is_ok_raw,
+            ctx.context.i32_type().const_zero(),
+            "is_ok_flag",
+        )
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+
+    // 3. 分配 Result<T, Error>，写入 is_ok 与解析结果
+    let result_ty = Type::Result {
+        ok_type: Box::new(ok_ty),
+        err_type: Box::new(Type::Struct("Error".to_string())),
+    };
+    let mangled_name = lency_monomorph::mangling::mangle_type(&result_ty);
+    let struct_type = *ctx
+        .struct_types
+        .get(&mangled_name)
+        .ok_or_else(|| CodegenError::UndefinedStructType(mangled_name.clone()))?;
+    let size = struct_type.size_of().ok_or(CodegenError::LLVMBuildError(
+        "Failed to get size of Result type".to_string(),
+    ))?;
+    let raw_ptr = call_malloc(ctx, size.into(), "parse_result")?;
+    let result_ptr = ctx
+        .builder
+        .build_pointer_cast(
+            raw_ptr,
+            struct_type.ptr_type(AddressSpace::default()),
+            "parse_result_ptr",
+        )
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+
+    let is_ok_ptr = ctx
+        .builder
+        .build_struct_gep(struct_type, result_ptr, 0, "is_ok_ptr")
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+    ctx.builder
+        .build_store(is_ok_ptr, is_ok)
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+    let ok_val_ptr = ctx
+        .builder
+        .build_struct_gep(struct_type, result_ptr, 1, "ok_val_ptr")
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+    ctx.builder
+        .build_store(ok_val_ptr, parsed)
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+
+    // 4. 解析失败时构造 Error { message }
+    let parent_func = ctx
+        .builder
+        .get_insert_block()
+        .and_then(|b| b.get_parent())
+        .ok_or(CodegenError::LLVMBuildError(
+            "No parent function".to_string(),
+        ))?;
+    let err_block = ctx
+        .context
+        .append_basic_block(parent_func, &format!("{name}_err"));
+    let merge_block = ctx
+        .context
+        .append_basic_block(parent_func, &format!("{name}_merge"));
+    ctx.builder
+        .build_conditional_branch(is_ok, merge_block, err_block)
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+
+    ctx.builder.position_at_end(err_block);
+    let target = if name == "parse_int" { "int" } else { "float" };
+    let error = build_parse_error(ctx, input, target)?;
+    let err_val_ptr = ctx
+        .builder
+        .build_struct_gep(struct_type, result_ptr, 2, "err_val_ptr")
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+    ctx.builder
+        .build_store(err_val_ptr, error)
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+    ctx.builder
+        .build_unconditional_branch(merge_block)
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+
+    ctx.builder.position_at_end(merge_block);
+    Ok(CodegenValue {
+        value: result_ptr.into(),
+        ty: result_ty,
+    })
+}
+
+/// 构造 Error { message: "cannot parse '<input>' as <target>" }
+fn build_parse_error<'ctx>(
+    ctx: &CodegenContext<'ctx>,
+    input: PointerValue<'ctx>,
+    target: &str,
+) -> CodegenResult<BasicValueEnum<'ctx>> {
+    let error_struct = *ctx
+        .struct_types
+        .get("Error")
+        .ok_or_else(|| CodegenError::UndefinedStructType("Error".to_string()))?;
+    let size = error_struct.size_of().ok_or(CodegenError::LLVMBuildError(
+        "Failed to get size of Error".to_string(),
+    ))?;
+    let raw_ptr = call_malloc(ctx, size.into(), "parse_error")?;
+    let error_ptr = ctx
+        .builder
+        .build_pointer_cast(
+            raw_ptr,
+            error_struct.ptr_type(AddressSpace::default()),
+            "parse_error_ptr",
+        )
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+
+    let message_index = ctx
+        .struct_fields
+        .get("Error")
+        .and_then(|fields| fields.iter().position(|f| f == "message"));
+    if let Some(index) = message_index {
+        let prefix = global_str(ctx, "cannot parse '", "parse_err_prefix")?;
+        let suffix = global_str(ctx, &format!("' as {target}"), "parse_err_suffix")?;
+        let head = concat(ctx, prefix, input)?.into_pointer_value();
+        let message = concat(ctx, head, suffix)?;
+        let message_ptr = ctx
+            .builder
+            .build_struct_gep(error_struct, error_ptr, index as u32, "message_ptr")
+            .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+        ctx.builder
+            .build_store(message_ptr, message)
+            .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+    }
+
+    Ok(error_ptr.into())
+}
+
+fn global_str<'ctx>(
+    ctx: &CodegenContext<'ctx>,
+    text: &str,
+    name: &str,
+) -> CodegenResult<PointerValue<'ctx>> {
+    ctx.builder
+        .build_global_string_ptr(text, name)
+        .map(|g| g.as_pointer_value())
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))
+}
+
+fn call_malloc<'ctx>(
+    ctx: &CodegenContext<'ctx>,
+    size: BasicValueEnum<'ctx>,
+    name: &str,
+) -> CodegenResult<PointerValue<'ctx>> {
+    let malloc = ctx
+        .module
+        .get_function("malloc")
+        .ok_or(CodegenError::LLVMBuildError("malloc not found".to_string()))?;
+    ctx.builder
+        .build_call(malloc, &[size.into()], name)
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?
+        .try_as_basic_value()
+        .left()
+        .ok_or(CodegenError::LLVMBuildError(
+            "malloc returned void".to_string(),
+        ))
+        .map(|v| v.into_pointer_value())
+}
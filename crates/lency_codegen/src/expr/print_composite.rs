@@ -0,0 +1,320 @@
+//! Composite Print Code Generation
+//!
+//! print 的复合类型输出：
+//! - 结构体：`Point { x: 1, y: 2 }`
+//! - 枚举：`Circle(3)`、`Empty`
+//! - 匿名记录：`{ x: 1, y: 2 }`
+//! - 可空值：`null` 或内部值
+//!
+//! 结构体和枚举为每个类型生成一个 `__lency_print_<Name>` 函数，
+//! 递归类型（如链表节点）在运行时递归，而不是在编译期无限展开。
+
+use super::intrinsic::{gen_print_str_literal, gen_print_value_impl};
+use crate::context::CodegenContext;
+use crate::error::{CodegenError, CodegenResult};
+use crate::types::{record_struct_type, ToLLVMType};
+use inkwell::module::Linkage;
+use inkwell::types::StructType;
+use inkwell::values::{BasicValueEnum, FunctionValue, PointerValue};
+use inkwell::AddressSpace;
+use lency_syntax::ast::Type;
+
+/// 打印结构体或枚举值（调用该类型的打印函数）
+pub(super) fn gen_print_named<'ctx>(
+    ctx: &CodegenContext<'ctx>,
+    value: PointerValue<'ctx>,
+    name: &str,
+) -> CodegenResult<()> {
+    let print_fn = get_or_build_print_fn(ctx, name)?;
+    let struct_type = lookup_struct_type(ctx, name)?;
+    let arg = ctx
+        .builder
+        .build_pointer_cast(
+            value,
+            struct_type.ptr_type(AddressSpace::default()),
+            "print_arg",
+        )
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+    ctx.builder
+        .build_call(print_fn, &[arg.into()], "")
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+    Ok(())
+}
+
+/// 打印匿名记录：`{ x: 1, y: 2 }`
+pub(super) fn gen_print_record<'ctx>(
+    ctx: &CodegenContext<'ctx>,
+    value: PointerValue<'ctx>,
+    layout: &[(String, Type)],
+) -> CodegenResult<()> {
+    let struct_type = record_struct_type(ctx, layout)?;
+    gen_print_fields(ctx, value, struct_type, layout, "{ ", " }", "{}")
+}
+
+/// 打印可空值：空指针打印 `null`，否则打印内部值
+///
+/// 可空值的表示与 types.rs 一致：内部类型本身是指针时直接使用，否则指向装箱的值；
+/// 未装箱的值（如字面量初始化的局部 `int?`）一定非空；`null` 字面量没有内部类型，直接打印 `null`
+pub(super) fn gen_print_nullable<'ctx>(
+    ctx: &CodegenContext<'ctx>,
+    value: BasicValueEnum<'ctx>,
+    inner: &Type,
+) -> CodegenResult<()> {
+    if matches!(inner, Type::Void | Type::Error) {
+        return gen_print_str_literal(ctx, "null");
+    }
+    let BasicValueEnum::PointerValue(ptr) = value else {
+        return gen_print_value_impl(ctx, value, inner);
+    };
+
+    let function = current_function(ctx)?;
+    let null_bb = ctx.context.append_basic_block(function, "print_null");
+    let some_bb = ctx.context.append_basic_block(function, "print_some");
+    let end_bb = ctx
+        .context
+        .append_basic_block(function, "print_nullable_end");
+
+    let is_null = ctx
+        .builder
+        .build_is_null(ptr, "print_is_null")
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+    ctx.builder
+        .build_conditional_branch(is_null, null_bb, some_bb)
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+
+    ctx.builder.position_at_end(null_bb);
+    gen_print_str_literal(ctx, "null")?;
+    branch_to(ctx, end_bb)?;
+
+    ctx.builder.position_at_end(some_bb);
+    let inner_llvm_ty = inner.to_llvm_type(ctx)?;
+    let inner_val = if inner_llvm_ty.is_pointer_type() {
+        ptr.into()
+    } else {
+        ctx.builder
+            .build_load(inner_llvm_ty, ptr, "print_unboxed")
+            .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?
+    };
+    gen_print_value_impl(ctx, inner_val, inner)?;
+    branch_to(ctx, end_bb)?;
+
+    ctx.builder.position_at_end(end_bb);
+    Ok(())
+}
+
+/// 获取（必要时生成）类型的打印函数 `void __lency_print_<Name>(T*)`
+fn get_or_build_print_fn<'ctx>(
+    ctx: &CodegenContext<'ctx>,
+    name: &str,
+) -> CodegenResult<FunctionValue<'ctx>> {
+    let fn_name = format!("__lency_print_{}", name);
+    if let Some(function) = ctx.module.get_function(&fn_name) {
+        return Ok(function);
+    }
+
+    let struct_type = lookup_struct_type(ctx, name)?;
+    let fn_type = ctx.context.void_type().fn_type(
+        &[struct_type.ptr_type(AddressSpace::default()).into()],
+        false,
+    );
+    // 先加入模块再生成函数体，递归类型的字段打印会找到这个函数
+    let function = ctx
+        .module
+        .add_function(&fn_name, fn_type, Some(Linkage::Private));
+
+    let saved_bb = ctx.builder.get_insert_block();
+    let entry_bb = ctx.context.append_basic_block(function, "entry");
+    ctx.builder.position_at_end(entry_bb);
+
+    let value = function
+        .get_nth_param(0)
+        .ok_or_else(|| CodegenError::LLVMBuildError("print function has no param".into()))?
+        .into_pointer_value();
+    if ctx.enum_types.contains(name) {
+        gen_enum_body(ctx, value, struct_type, name)?;
+    } else {
+        gen_struct_body(ctx, value, struct_type, name)?;
+    }
+    ctx.builder
+        .build_return(None)
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+
+    if let Some(bb) = saved_bb {
+        ctx.builder.position_at_end(bb);
+    }
+    Ok(function)
+}
+
+/// 结构体：`Name { field: value, ... }`
+fn gen_struct_body<'ctx>(
+    ctx: &CodegenContext<'ctx>,
+    value: PointerValue<'ctx>,
+    struct_type: StructType<'ctx>,
+    name: &str,
+) -> CodegenResult<()> {
+    let names = ctx
+        .struct_fields
+        .get(name)
+        .ok_or_else(|| CodegenError::UndefinedStructType(name.to_string()))?;
+    let types = ctx
+        .struct_field_types
+        .get(name)
+        .ok_or_else(|| CodegenError::UndefinedStructType(name.to_string()))?;
+    let fields: Vec<_> = names.iter().cloned().zip(types.iter().cloned()).collect();
+
+    // 泛型实例的名称经过改写（Box__int），只显示原始名称
+    let display_name = name.split("__").next().unwrap_or(name);
+    gen_print_fields(
+        ctx,
+        value,
+        struct_type,
+        &fields,
+        &format!("{} {{ ", display_name),
+        " }",
+        &format!("{} {{}}", display_name),
+    )
+}
+
+/// 依次打印 `name: value` 字段，以 open / close 包围；没有字段时打印 empty
+fn gen_print_fields<'ctx>(
+    ctx: &CodegenContext<'ctx>,
+    value: PointerValue<'ctx>,
+    struct_type: StructType<'ctx>,
+    fields: &[(String, Type)],
+    open: &str,
+    close: &str,
+    empty: &str,
+) -> CodegenResult<()> {
+    if fields.is_empty() {
+        return gen_print_str_literal(ctx, empty);
+    }
+
+    gen_print_str_literal(ctx, open)?;
+    for (i, (field_name, field_ty)) in fields.iter().enumerate() {
+        let sep = if i == 0 { "" } else { ", " };
+        gen_print_str_literal(ctx, &format!("{}{}: ", sep, field_name))?;
+        let field_val = load_field(ctx, struct_type, value, i as u32, field_ty)?;
+        gen_print_value_impl(ctx, field_val, field_ty)?;
+    }
+    gen_print_str_literal(ctx, close)
+}
+
+/// 枚举：按 tag 分派，打印 `Variant` 或 `Variant(a, b)`
+fn gen_enum_body<'ctx>(
+    ctx: &CodegenContext<'ctx>,
+    value: PointerValue<'ctx>,
+    enum_type: StructType<'ctx>,
+    name: &str,
+) -> CodegenResult<()> {
+    let variants = ctx
+        .enum_variants
+        .get(name)
+        .ok_or_else(|| CodegenError::UndefinedStructType(name.to_string()))?
+        .clone();
+
+    let tag_ptr = ctx
+        .builder
+        .build_struct_gep(enum_type, value, 0, "tag_ptr")
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+    let tag = ctx
+        .builder
+        .build_load(ctx.context.i64_type(), tag_ptr, "tag")
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?
+        .into_int_value();
+
+    let function = current_function(ctx)?;
+    let end_bb = ctx.context.append_basic_block(function, "print_enum_end");
+    let variant_blocks: Vec<_> = (0..variants.len())
+        .map(|_| ctx.context.append_basic_block(function, "print_variant"))
+        .collect();
+    let cases: Vec<_> = variant_blocks
+        .iter()
+        .enumerate()
+        .map(|(idx, bb)| (ctx.enum_tag(name, idx), *bb))
+        .collect();
+    ctx.builder
+        .build_switch(tag, end_bb, &cases)
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+
+    for ((variant_name, fields), bb) in variants.iter().zip(variant_blocks) {
+        ctx.builder.position_at_end(bb);
+        gen_print_str_literal(ctx, variant_name)?;
+        if !fields.is_empty() {
+            let mut field_llvm_types = Vec::with_capacity(fields.len());
+            for ty in fields {
+                field_llvm_types.push(ty.to_llvm_type(ctx)?);
+            }
+            let payload_type = ctx.context.struct_type(&field_llvm_types, false);
+            let payload_arr = ctx
+                .builder
+                .build_struct_gep(enum_type, value, 1, "payload_arr")
+                .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+            let payload = ctx
+                .builder
+                .build_bitcast(
+                    payload_arr,
+                    payload_type.ptr_type(AddressSpace::default()),
+                    "payload_typed",
+                )
+                .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?
+                .into_pointer_value();
+
+            gen_print_str_literal(ctx, "(")?;
+            for (i, field_ty) in fields.iter().enumerate() {
+                if i > 0 {
+                    gen_print_str_literal(ctx, ", ")?;
+                }
+                let field_val = load_field(ctx, payload_type, payload, i as u32, field_ty)?;
+                gen_print_value_impl(ctx, field_val, field_ty)?;
+            }
+            gen_print_str_literal(ctx, ")")?;
+        }
+        branch_to(ctx, end_bb)?;
+    }
+
+    ctx.builder.position_at_end(end_bb);
+    Ok(())
+}
+
+fn load_field<'ctx>(
+    ctx: &CodegenContext<'ctx>,
+    struct_type: StructType<'ctx>,
+    ptr: PointerValue<'ctx>,
+    index: u32,
+    ty: &Type,
+) -> CodegenResult<BasicValueEnum<'ctx>> {
+    let field_ptr = ctx
+        .builder
+        .build_struct_gep(struct_type, ptr, index, "print_field_ptr")
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+    ctx.builder
+        .build_load(ty.to_llvm_type(ctx)?, field_ptr, "print_field")
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))
+}
+
+fn lookup_struct_type<'ctx>(
+    ctx: &CodegenContext<'ctx>,
+    name: &str,
+) -> CodegenResult<StructType<'ctx>> {
+    ctx.struct_types
+        .get(name)
+        .copied()
+        .ok_or_else(|| CodegenError::UndefinedStructType(name.to_string()))
+}
+
+fn branch_to<'ctx>(
+    ctx: &CodegenContext<'ctx>,
+    bb: inkwell::basic_block::BasicBlock<'ctx>,
+) -> CodegenResult<()> {
+    ctx.builder
+        .build_unconditional_branch(bb)
+        .map(|_| ())
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))
+}
+
+fn current_function<'ctx>(ctx: &CodegenContext<'ctx>) -> CodegenResult<FunctionValue<'ctx>> {
+    ctx.builder
+        .get_insert_block()
+        .and_then(|bb| bb.get_parent())
+        .ok_or_else(|| CodegenError::LLVMBuildError("print outside function".into()))
+}
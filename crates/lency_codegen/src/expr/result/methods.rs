@@ -1,6 +1,8 @@
 use crate::context::CodegenContext;
 use crate::error::{CodegenError, CodegenResult};
 use crate::expr::{generate_expr, CodegenValue};
+use crate::types::ToLLVMType;
+use inkwell::types::BasicType;
 
 use lency_syntax::ast::{Expr, Type};
 use std::collections::HashMap;
@@ -20,13 +22,13 @@ pub fn gen_result_builtin_method<'ctx>(
     method_name: &str,
     args: &[Expr],
     ok_type: &Type,
-    _err_type: &Type,
+    err_type: &Type,
     line: u32,
 ) -> CodegenResult<Option<CodegenValue<'ctx>>> {
     // 获取 Result struct type
     let result_ty = Type::Result {
         ok_type: Box::new(ok_type.clone()),
-        err_type: Box::new(Type::Struct("Error".to_string())),
+        err_type: Box::new(err_type.clone()),
     };
     let mangled_name = lency_monomorph::mangling::mangle_type(&result_ty);
 
@@ -209,6 +211,190 @@ pub fn gen_result_builtin_method<'ctx>(
                 ty: ok_type.clone(),
             }))
         }
+        "map" => {
+            if args.len() != 1 {
+                return Ok(None);
+            }
+            gen_result_map(
+                ctx,
+                locals,
+                result_ptr,
+                struct_type,
+                &args[0],
+                ok_type,
+                err_type,
+            )
+            .map(Some)
+        }
         _ => Ok(None), // 未知方法，fallback 到编译方法
     }
 }
+
+/// map(f): Ok(v) -> Ok(f(v))，Err(e) 原样保留
+///
+/// 总是分配新的 Result<U, E>，tag 与 err 从原值复制
+fn gen_result_map<'ctx>(
+    ctx: &CodegenContext<'ctx>,
+    locals: &HashMap<String, (inkwell::values::PointerValue<'ctx>, Type)>,
+    result_ptr: inkwell::values::PointerValue<'ctx>,
+    struct_type: inkwell::types::StructType<'ctx>,
+    func_expr: &Expr,
+    ok_type: &Type,
+    err_type: &Type,
+) -> CodegenResult<CodegenValue<'ctx>> {
+    let func_val = generate_expr(ctx, locals, func_expr)?;
+    let (param_types, mapped_ok) = match &func_val.ty {
+        Type::Function {
+            param_types,
+            return_type,
+        } => (param_types.clone(), *return_type.clone()),
+        _ => return Err(CodegenError::TypeMismatch),
+    };
+
+    // 1. 分配 Result<U, E>
+    let mapped_ty = Type::Result {
+        ok_type: Box::new(mapped_ok.clone()),
+        err_type: Box::new(err_type.clone()),
+    };
+    let mapped_ptr_type = mapped_ty.to_llvm_type(ctx)?.into_pointer_type();
+    let mapped_name = lency_monomorph::mangling::mangle_type(&mapped_ty);
+    let mapped_struct = match ctx.struct_types.get(&mapped_name) {
+        Some(st) => *st,
+        // to_llvm_type 已按 mangled 名称创建了命名结构体
+        None => ctx
+            .module
+            .get_struct_type(&mapped_name)
+            .ok_or_else(|| CodegenError::UndefinedStructType(mapped_name.clone()))?,
+    };
+    let size = mapped_struct.size_of().ok_or(CodegenError::LLVMBuildError(
+        "Failed to get size of Result type".to_string(),
+    ))?;
+    let malloc = ctx
+        .module
+        .get_function("malloc")
+        .ok_or(CodegenError::LLVMBuildError("malloc not found".to_string()))?;
+    let raw_ptr = ctx
+        .builder
+        .build_call(malloc, &[size.into()], "malloc_result_map")
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?
+        .try_as_basic_value()
+        .left()
+        .ok_or(CodegenError::LLVMBuildError(
+            "malloc returned void".to_string(),
+        ))?
+        .into_pointer_value();
+    let mapped_ptr = ctx
+        .builder
+        .build_pointer_cast(raw_ptr, mapped_ptr_type, "mapped_ptr")
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+
+    // 2. 复制 is_ok
+    let is_ok_ptr = ctx
+        .builder
+        .build_struct_gep(struct_type, result_ptr, 0, "is_ok_ptr")
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+    let is_ok = ctx
+        .builder
+        .build_load(ctx.context.bool_type(), is_ok_ptr, "is_ok")
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?
+        .into_int_value();
+    let mapped_is_ok_ptr = ctx
+        .builder
+        .build_struct_gep(mapped_struct, mapped_ptr, 0, "mapped_is_ok_ptr")
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+    ctx.builder
+        .build_store(mapped_is_ok_ptr, is_ok)
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+
+    let func = ctx
+        .builder
+        .get_insert_block()
+        .and_then(|bb| bb.get_parent())
+        .ok_or(CodegenError::LLVMBuildError(
+            "No parent function".to_string(),
+        ))?;
+    let ok_bb = ctx.context.append_basic_block(func, "result_map_ok");
+    let err_bb = ctx.context.append_basic_block(func, "result_map_err");
+    let merge_bb = ctx.context.append_basic_block(func, "result_map_merge");
+    ctx.builder
+        .build_conditional_branch(is_ok, ok_bb, err_bb)
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+
+    // 3. Ok 分支：调用 f(ok_val)
+    ctx.builder.position_at_end(ok_bb);
+    let ok_val_ptr = ctx
+        .builder
+        .build_struct_gep(struct_type, result_ptr, 1, "ok_val_ptr")
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+    let ok_val = ctx
+        .builder
+        .build_load(ok_type.to_llvm_type(ctx)?, ok_val_ptr, "ok_val")
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+    let param_llvm_types = param_types
+        .iter()
+        .map(|t| t.to_llvm_type(ctx).map(|ty| ty.into()))
+        .collect::<CodegenResult<Vec<_>>>()?;
+    let fn_type = if matches!(mapped_ok, Type::Void) {
+        ctx.context.void_type().fn_type(&param_llvm_types, false)
+    } else {
+        mapped_ok
+            .to_llvm_type(ctx)?
+            .fn_type(&param_llvm_types, false)
+    };
+    let call_site = ctx
+        .builder
+        .build_indirect_call(
+            fn_type,
+            func_val.value.into_pointer_value(),
+            &[ok_val.into()],
+            "map_call",
+        )
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+    if let Some(mapped_val) = call_site.try_as_basic_value().left() {
+        let mapped_val_ptr = ctx
+            .builder
+            .build_struct_gep(mapped_struct, mapped_ptr, 1, "mapped_val_ptr")
+            .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+        ctx.builder
+            .build_store(mapped_val_ptr, mapped_val)
+            .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+    }
+    ctx.builder
+        .build_unconditional_branch(merge_bb)
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+
+    // 4. Err 分支：原样复制错误值
+    ctx.builder.position_at_end(err_bb);
+    if !matches!(err_type, Type::Void) {
+        let err_idx = if matches!(ok_type, Type::Void) { 1 } else { 2 };
+        let mapped_err_idx = if matches!(mapped_ok, Type::Void) {
+            1
+        } else {
+            2
+        };
+        let err_val_ptr = ctx
+            .builder
+            .build_struct_gep(struct_type, result_ptr, err_idx, "err_val_ptr")
+            .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+        let err_val = ctx
+            .builder
+            .build_load(err_type.to_llvm_type(ctx)?, err_val_ptr, "err_val")
+            .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+        let mapped_err_ptr = ctx
+            .builder
+            .build_struct_gep(mapped_struct, mapped_ptr, mapped_err_idx, "mapped_err_ptr")
+            .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+        ctx.builder
+            .build_store(mapped_err_ptr, err_val)
+            .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+    }
+    ctx.builder
+        .build_unconditional_branch(merge_bb)
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+
+    ctx.builder.position_at_end(merge_bb);
+    Ok(CodegenValue {
+        value: mapped_ptr.into(),
+        ty: mapped_ty,
+    })
+}
@@ -17,8 +17,8 @@ pub fn gen_try<'ctx>(
     let result_ptr = val_wrapper.value.into_pointer_value();
 
     // 解析 Result 类型以获取 ok_type
-    let ok_type = match &val_wrapper.ty {
-        Type::Result { ok_type, .. } => *ok_type.clone(),
+    let (ok_type, err_type) = match &val_wrapper.ty {
+        Type::Result { ok_type, err_type } => (*ok_type.clone(), *err_type.clone()),
         _ => return Err(CodegenError::TypeMismatch),
     };
 
@@ -71,11 +71,7 @@ pub fn gen_try<'ctx>(
         .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
     let err_val = ctx
         .builder
-        .build_load(
-            Type::Struct("Error".to_string()).to_llvm_type(ctx)?,
-            err_val_ptr,
-            "err_val",
-        )
+        .build_load(err_type.to_llvm_type(ctx)?, err_val_ptr, "err_val")
         .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
 
     // 构造返回值并 return
@@ -89,9 +85,16 @@ pub fn gen_try<'ctx>(
     // 如果函数返回 Result<T, Error>，我们需要构造 Err(err_val)
     if let Type::Result {
         ok_type: ret_ok_type,
-        ..
+        err_type: ret_err_type,
     } = func_ret_type
     {
+        // 错误类型不同时，经由 Target::from(err) 转换 (语义分析已保证其存在)
+        let err_val = if **ret_err_type == err_type {
+            err_val
+        } else {
+            convert_error(ctx, ret_err_type, err_val)?
+        };
+
         // 获取返回类型的 Result struct type
         let ret_mangled_name = lency_monomorph::mangling::mangle_type(func_ret_type);
         let ret_struct_type = *ctx.struct_types.get(&ret_mangled_name).ok_or_else(|| {
@@ -191,3 +194,37 @@ pub fn gen_try<'ctx>(
         })
     }
 }
+
+/// 调用目标错误类型的关联函数 `Target::from(err)` 完成错误类型转换
+fn convert_error<'ctx>(
+    ctx: &CodegenContext<'ctx>,
+    target: &Type,
+    err_val: inkwell::values::BasicValueEnum<'ctx>,
+) -> CodegenResult<inkwell::values::BasicValueEnum<'ctx>> {
+    let target_name = match target {
+        Type::Struct(name) => name,
+        _ => return Err(CodegenError::TypeMismatch),
+    };
+    let mangled_name = lency_monomorph::mangling::method_symbol(target_name, "from");
+    let function = ctx
+        .module
+        .get_function(&mangled_name)
+        .ok_or_else(|| CodegenError::FunctionNotFound(mangled_name.clone()))?;
+    let this_type = function
+        .get_type()
+        .get_param_types()
+        .first()
+        .copied()
+        .ok_or(CodegenError::TypeMismatch)?;
+
+    ctx.builder
+        .build_call(
+            function,
+            &[this_type.const_zero().into(), err_val.into()],
+            "err_converted",
+        )
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?
+        .try_as_basic_value()
+        .left()
+        .ok_or(CodegenError::TypeMismatch)
+}
@@ -0,0 +1,110 @@
+//! String Byte Intrinsics Code Generation
+//!
+//! string_bytes / bytes_to_string：在字符串与 `Vec<int>` 字节数组之间转换，
+//! 扫描器取一次字节数组后按下标访问，每次访问都是 O(1)
+
+use inkwell::values::PointerValue;
+use inkwell::AddressSpace;
+use lency_syntax::ast::{Expr, Type};
+use std::collections::HashMap;
+
+use crate::context::CodegenContext;
+use crate::error::{CodegenError, CodegenResult};
+use crate::expr::{generate_expr, CodegenValue};
+
+/// Generate code for string_bytes(string) -> Vec<int>
+pub(super) fn gen_string_bytes<'ctx>(
+    ctx: &CodegenContext<'ctx>,
+    locals: &HashMap<String, (PointerValue<'ctx>, Type)>,
+    args: &[Expr],
+) -> CodegenResult<CodegenValue<'ctx>> {
+    if args.len() != 1 {
+        return Err(CodegenError::UnsupportedFeature(
+            "string_bytes expects 1 argument".to_string(),
+        ));
+    }
+
+    let ptr_type = ctx.context.i8_type().ptr_type(AddressSpace::default());
+    let func = ctx
+        .module
+        .get_function("lency_string_bytes")
+        .unwrap_or_else(|| {
+            let fn_type = ptr_type.fn_type(&[ptr_type.into()], false);
+            ctx.module.add_function("lency_string_bytes", fn_type, None)
+        });
+
+    let arg_val = generate_expr(ctx, locals, &args[0])?;
+    if arg_val.ty != Type::String {
+        return Err(CodegenError::TypeMismatch);
+    }
+
+    let result = ctx
+        .builder
+        .build_call(func, &[arg_val.value.into()], "str_bytes")
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?
+        .try_as_basic_value()
+        .left()
+        .ok_or(CodegenError::LLVMBuildError(
+            "lency_string_bytes returned void".to_string(),
+        ))?;
+
+    Ok(CodegenValue {
+        value: result,
+        ty: Type::Vec(Box::new(Type::Int)),
+    })
+}
+
+/// Generate code for bytes_to_string(Vec<int>, int, int) -> string
+pub(super) fn gen_bytes_to_string<'ctx>(
+    ctx: &CodegenContext<'ctx>,
+    locals: &HashMap<String, (PointerValue<'ctx>, Type)>,
+    args: &[Expr],
+) -> CodegenResult<CodegenValue<'ctx>> {
+    if args.len() != 3 {
+        return Err(CodegenError::UnsupportedFeature(
+            "bytes_to_string expects 3 arguments".to_string(),
+        ));
+    }
+
+    let ptr_type = ctx.context.i8_type().ptr_type(AddressSpace::default());
+    let i64_type = ctx.context.i64_type();
+    let func = ctx
+        .module
+        .get_function("lency_string_from_bytes")
+        .unwrap_or_else(|| {
+            let fn_type =
+                ptr_type.fn_type(&[ptr_type.into(), i64_type.into(), i64_type.into()], false);
+            ctx.module
+                .add_function("lency_string_from_bytes", fn_type, None)
+        });
+
+    let vec_val = generate_expr(ctx, locals, &args[0])?;
+    if !matches!(vec_val.ty, Type::Vec(_)) {
+        return Err(CodegenError::TypeMismatch);
+    }
+    let start_val = generate_expr(ctx, locals, &args[1])?;
+    let len_val = generate_expr(ctx, locals, &args[2])?;
+
+    let result = ctx
+        .builder
+        .build_call(
+            func,
+            &[
+                vec_val.value.into(),
+                start_val.value.into(),
+                len_val.value.into(),
+            ],
+            "bytes_str",
+        )
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?
+        .try_as_basic_value()
+        .left()
+        .ok_or(CodegenError::LLVMBuildError(
+            "lency_string_from_bytes returned void".to_string(),
+        ))?;
+
+    Ok(CodegenValue {
+        value: result,
+        ty: Type::String,
+    })
+}
@@ -80,6 +80,20 @@ fn get_or_declare_strlen<'ctx>(ctx: &CodegenContext<'ctx>) -> FunctionValue<'ctx
     ctx.module.add_function("strlen", fn_type, None)
 }
 
+/// 声明 `i64 lency_string_char_at(i8*, i64)`
+pub(crate) fn get_or_declare_string_char_at<'ctx>(
+    ctx: &CodegenContext<'ctx>,
+) -> FunctionValue<'ctx> {
+    if let Some(func) = ctx.module.get_function("lency_string_char_at") {
+        return func;
+    }
+    let i8_ptr_type = ctx.context.i8_type().ptr_type(AddressSpace::default());
+    let i64_type = ctx.context.i64_type();
+    let fn_type = i64_type.fn_type(&[i8_ptr_type.into(), i64_type.into()], false);
+    ctx.module
+        .add_function("lency_string_char_at", fn_type, None)
+}
+
 fn get_or_declare_malloc<'ctx>(ctx: &CodegenContext<'ctx>) -> FunctionValue<'ctx> {
     if let Some(func) = ctx.module.get_function("malloc") {
         return func;
@@ -113,7 +127,9 @@ use super::CodegenValue;
 use lency_syntax::ast::{Expr, Type};
 use std::collections::HashMap;
 
-/// 生成 len(string) -> int
+/// 生成 len(string) -> int（字符数）
+///
+/// 参数是字符串字面量时在编译期求值，不调用运行时
 pub fn gen_len<'ctx>(
     ctx: &CodegenContext<'ctx>,
     locals: &HashMap<String, (inkwell::values::PointerValue<'ctx>, Type)>,
@@ -121,6 +137,10 @@ pub fn gen_len<'ctx>(
 ) -> CodegenResult<CodegenValue<'ctx>> {
     use super::generate_expr;
 
+    if let Some(s) = string_literal(arg) {
+        return Ok(const_int_value(ctx, s.chars().count()));
+    }
+
     let arg_val = generate_expr(ctx, locals, arg)?;
     let str_ptr = arg_val.value.into_pointer_value();
 
@@ -152,6 +172,22 @@ pub fn gen_len<'ctx>(
     })
 }
 
+/// 表达式是字符串字面量时返回其内容
+pub(super) fn string_literal(expr: &Expr) -> Option<&str> {
+    match &expr.kind {
+        lency_syntax::ast::ExprKind::Literal(lency_syntax::ast::Literal::String(s)) => Some(s),
+        _ => None,
+    }
+}
+
+/// 编译期已知的长度
+pub(super) fn const_int_value<'ctx>(ctx: &CodegenContext<'ctx>, n: usize) -> CodegenValue<'ctx> {
+    CodegenValue {
+        value: ctx.context.i64_type().const_int(n as u64, false).into(),
+        ty: Type::Int,
+    }
+}
+
 /// 生成 trim(string) -> string
 pub fn gen_trim<'ctx>(
     ctx: &CodegenContext<'ctx>,
@@ -419,3 +455,41 @@ pub fn gen_format<'ctx>(
         ty: Type::String,
     })
 }
+
+/// 生成 fmt!("{} + {}", a, b) -> string：按占位符切分模板，实参转为字符串后依次拼接
+pub fn gen_fmt<'ctx>(
+    ctx: &CodegenContext<'ctx>,
+    locals: &HashMap<String, (inkwell::values::PointerValue<'ctx>, Type)>,
+    template: &str,
+    args: &[Expr],
+) -> CodegenResult<CodegenValue<'ctx>> {
+    use super::generate_expr;
+
+    let piece = |text: &str| {
+        ctx.builder
+            .build_global_string_ptr(text, "fmt_piece")
+            .map(|g| g.as_pointer_value())
+            .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))
+    };
+
+    // 占位符数量已由语义分析保证与实参一致
+    let mut pieces = template.split("{}");
+    let mut result = piece(pieces.next().unwrap_or_default())?;
+    for (arg, text) in args.iter().zip(pieces) {
+        let value = generate_expr(ctx, locals, arg)?;
+        let value = if value.ty == Type::String {
+            value
+        } else {
+            super::conversion::gen_primitive_to_string(ctx, value)?
+        };
+        result = concat(ctx, result, value.value.into_pointer_value())?.into_pointer_value();
+        if !text.is_empty() {
+            result = concat(ctx, result, piece(text)?)?.into_pointer_value();
+        }
+    }
+
+    Ok(CodegenValue {
+        value: result.into(),
+        ty: Type::String,
+    })
+}
@@ -24,7 +24,7 @@ pub fn gen_member_access<'ctx>(
                     // Check if Unit or Tuple
                     if fields.is_empty() {
                         // Unit Variant: Generate Call to Enum_Variant()
-                        let ctor_name = format!("{}_{}", name, field_name);
+                        let ctor_name = lency_monomorph::mangling::method_symbol(name, field_name);
                         let function = ctx
                             .module
                             .get_function(&ctor_name)
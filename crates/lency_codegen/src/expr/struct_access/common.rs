@@ -1,7 +1,7 @@
 use crate::context::CodegenContext;
 use crate::error::{CodegenError, CodegenResult};
 use crate::expr::CodegenValue;
-use crate::types::ToLLVMType;
+use crate::types::{record_struct_type, ToLLVMType};
 use inkwell::values::PointerValue;
 use lency_syntax::ast::Type;
 
@@ -12,6 +12,26 @@ pub fn load_field<'ctx>(
     field_name: &str,
     field_ptr: PointerValue<'ctx>,
 ) -> CodegenResult<CodegenValue<'ctx>> {
+    // 匿名记录：字段类型直接来自 Type::Record
+    if let Some(fields) = record_fields(&object_val.ty) {
+        let (_, ret_type) = fields
+            .iter()
+            .find(|(name, _)| name == field_name)
+            .ok_or(CodegenError::TypeMismatch)?;
+        let load = ctx
+            .builder
+            .build_load(
+                ret_type.to_llvm_type(ctx)?,
+                field_ptr,
+                &format!("field_{}_val", field_name),
+            )
+            .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+        return Ok(CodegenValue {
+            value: load,
+            ty: ret_type.clone(),
+        });
+    }
+
     // Get return type logic (duplicated from before, can extract)
     let struct_name_str = match &object_val.ty {
         Type::Struct(name) => name,
@@ -47,3 +67,40 @@ pub fn load_field<'ctx>(
         ty: ret_type,
     })
 }
+
+/// 如果类型是（可空的）匿名记录，返回其字段列表
+pub fn record_fields(ty: &Type) -> Option<&[(String, Type)]> {
+    match ty {
+        Type::Record(fields) => Some(fields),
+        Type::Nullable(inner) => match &**inner {
+            Type::Record(fields) => Some(fields),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// 辅助：生成匿名记录字段的 GEP
+pub fn gen_record_field_ptr<'ctx>(
+    ctx: &CodegenContext<'ctx>,
+    fields: &[(String, Type)],
+    ptr_val: PointerValue<'ctx>,
+    field_name: &str,
+) -> CodegenResult<PointerValue<'ctx>> {
+    let index = fields
+        .iter()
+        .position(|(name, _)| name == field_name)
+        .ok_or_else(|| {
+            CodegenError::UnsupportedType(format!("Record has no field '{}'", field_name))
+        })?;
+    let record_type = record_struct_type(ctx, fields)?;
+
+    ctx.builder
+        .build_struct_gep(
+            record_type,
+            ptr_val,
+            index as u32,
+            &format!("field_{}_ptr", field_name),
+        )
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))
+}
@@ -1,3 +1,4 @@
+use super::common::{gen_record_field_ptr, record_fields};
 use crate::context::CodegenContext;
 use crate::error::{CodegenError, CodegenResult};
 use crate::expr::{generate_expr, CodegenValue};
@@ -30,6 +31,11 @@ pub fn gen_struct_member_ptr<'ctx>(
         crate::runtime::gen_null_check(ctx.context, &ctx.builder, panic_func, ptr_val, line);
     }
 
+    // 匿名记录
+    if let Some(fields) = record_fields(&object_val.ty) {
+        return gen_record_field_ptr(ctx, fields, ptr_val, field_name);
+    }
+
     // 3. 获取结构体名称和 LLVM 类型
     let struct_name = match &object_val.ty {
         Type::Struct(name) => name,
@@ -100,6 +106,11 @@ pub fn gen_struct_member_ptr_val<'ctx>(
     // Standard access needs check. Safe access needs check (but branches).
     // So raw GEP generation should not check.
 
+    // 匿名记录
+    if let Some(fields) = record_fields(&object_val.ty) {
+        return gen_record_field_ptr(ctx, fields, ptr_val, field_name);
+    }
+
     // 3. 获取结构体名称和 LLVM 类型
     let struct_name = match &object_val.ty {
         Type::Struct(name) => name,
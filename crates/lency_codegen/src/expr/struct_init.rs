@@ -1,10 +1,14 @@
 //! Struct Initialization Code Generation
 //!
 //! 处理结构体字面量：Point { x: 1, y: 2 }
+//! 以及匿名记录字面量：{ x: 1, y: 2 }
 
 use crate::context::CodegenContext;
 use crate::error::{CodegenError, CodegenResult};
-use crate::expr::{generate_expr, CodegenValue};
+use crate::expr::{coerce_pointer, generate_expr, widen_int, CodegenValue};
+use crate::types::{record_struct_type, ToLLVMType};
+use inkwell::types::{BasicType, StructType};
+use inkwell::values::{BasicValueEnum, PointerValue};
 use lency_syntax::ast::{Expr, Type};
 use std::collections::HashMap;
 
@@ -25,10 +29,166 @@ pub fn gen_struct_literal<'ctx>(
         CodegenError::UnsupportedType(format!("Struct '{}' fields not found", type_name))
     })?;
 
-    // 3. 计算大小并调用 malloc
+    // 3. 在堆上分配结构体
+    let struct_ptr = malloc_struct(ctx, *struct_type, type_name)?;
+
+    // 4. 初始化字段
+    // 将提供的字段值映射到它们在结构体中的位置
+    let mut provided_values = HashMap::new();
+    for (name, expr) in fields {
+        let val_wrapper = generate_expr(ctx, locals, expr)?;
+        provided_values.insert(name, val_wrapper.value);
+    }
+
+    let field_types = ctx
+        .struct_field_types
+        .get(type_name)
+        .cloned()
+        .unwrap_or_default();
+
+    // 按定义顺序遍历字段并 store
+    // 省略的字段已在语义分析阶段用默认值补全，这里仍缺失说明前端有遗漏
+    for (i, field_name) in field_names.iter().enumerate() {
+        let val = provided_values.get(field_name).ok_or_else(|| {
+            CodegenError::UnsupportedFeature(format!(
+                "struct literal '{}' is missing field '{}'",
+                type_name, field_name
+            ))
+        })?;
+        let field_ptr = ctx
+            .builder
+            .build_struct_gep(
+                *struct_type,
+                struct_ptr,
+                i as u32,
+                &format!("field_{}", field_name),
+            )
+            .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+
+        let val = match field_types.get(i) {
+            Some(field_ty) => coerce_field_value(ctx, *val, field_ty)?,
+            None => *val,
+        };
+        ctx.builder
+            .build_store(field_ptr, val)
+            .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+    }
+
+    Ok(CodegenValue {
+        value: struct_ptr.into(),
+        ty: Type::Struct(type_name.to_string()),
+    })
+}
+
+/// 生成匿名记录字面量
+///
+/// 字段按源码顺序求值，再按 Type::Record 的（排序后）布局写入匿名结构体。
+pub fn gen_record_literal<'ctx>(
+    ctx: &CodegenContext<'ctx>,
+    locals: &HashMap<String, (inkwell::values::PointerValue<'ctx>, lency_syntax::ast::Type)>,
+    fields: &[(String, Expr)],
+) -> CodegenResult<CodegenValue<'ctx>> {
+    let mut values = Vec::with_capacity(fields.len());
+    for (name, expr) in fields {
+        values.push((name.clone(), generate_expr(ctx, locals, expr)?));
+    }
+
+    let record_ty = Type::record(
+        values
+            .iter()
+            .map(|(name, val)| (name.clone(), val.ty.clone()))
+            .collect(),
+    );
+    let Type::Record(layout) = &record_ty else {
+        unreachable!("Type::record always builds a record type");
+    };
+
+    let struct_type = record_struct_type(ctx, layout)?;
+    let struct_ptr = malloc_struct(ctx, struct_type, "record")?;
+
+    for (i, (field_name, _)) in layout.iter().enumerate() {
+        let (_, val) = values
+            .iter()
+            .find(|(name, _)| name == field_name)
+            .ok_or(CodegenError::TypeMismatch)?;
+        let field_ptr = ctx
+            .builder
+            .build_struct_gep(
+                struct_type,
+                struct_ptr,
+                i as u32,
+                &format!("field_{}", field_name),
+            )
+            .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+        ctx.builder
+            .build_store(field_ptr, val.value)
+            .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+    }
+
+    Ok(CodegenValue {
+        value: struct_ptr.into(),
+        ty: record_ty,
+    })
+}
+
+/// 将字段值转换为字段的存储表示
+///
+/// 非指针内部类型的 `T?` 字段存放指向值的指针：非空值需要装箱到堆上，
+/// null 字面量只需转换指针类型（match 分支合并为 `T?` 时同样使用）
+pub(crate) fn coerce_field_value<'ctx>(
+    ctx: &CodegenContext<'ctx>,
+    value: BasicValueEnum<'ctx>,
+    field_ty: &Type,
+) -> CodegenResult<BasicValueEnum<'ctx>> {
+    let Type::Nullable(inner) = field_ty else {
+        let value = widen_int(ctx, value, field_ty)?;
+        return coerce_pointer(ctx, value, field_ty);
+    };
+    if value.is_pointer_value() {
+        return coerce_pointer(ctx, value, field_ty);
+    }
+
+    let inner_llvm_ty = inner.to_llvm_type(ctx)?;
+    let size = inner_llvm_ty
+        .size_of()
+        .ok_or_else(|| CodegenError::LLVMBuildError(format!("type '{}' has no size", inner)))?;
+    let malloc = ctx
+        .module
+        .get_function("malloc")
+        .ok_or_else(|| CodegenError::LLVMBuildError("malloc function not found".to_string()))?;
+    let raw_ptr = ctx
+        .builder
+        .build_call(malloc, &[size.into()], "nullable_box")
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?
+        .try_as_basic_value()
+        .left()
+        .ok_or(CodegenError::LLVMBuildError(
+            "malloc returned void".to_string(),
+        ))?
+        .into_pointer_value();
+    let box_ptr = ctx
+        .builder
+        .build_pointer_cast(
+            raw_ptr,
+            inner_llvm_ty.ptr_type(inkwell::AddressSpace::default()),
+            "nullable_box_typed",
+        )
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+    ctx.builder
+        .build_store(box_ptr, value)
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+    Ok(box_ptr.into())
+}
+
+/// 调用 malloc 为结构体分配内存，返回结构体指针
+pub(crate) fn malloc_struct<'ctx>(
+    ctx: &CodegenContext<'ctx>,
+    struct_type: StructType<'ctx>,
+    name: &str,
+) -> CodegenResult<PointerValue<'ctx>> {
     // size_of 返回的是 Option<IntValue>，因为如果是 opaque 可能没有 size。但我们已经定义了 body。
     let size = struct_type.size_of().ok_or_else(|| {
-        CodegenError::LLVMBuildError(format!("Struct '{}' has no size (opaque?)", type_name))
+        CodegenError::LLVMBuildError(format!("Struct '{}' has no size (opaque?)", name))
     })?;
 
     let malloc = ctx
@@ -49,65 +209,12 @@ pub fn gen_struct_literal<'ctx>(
         ))?
         .into_pointer_value();
 
-    // 4. Bitcast i8* to StructType*
+    // Bitcast i8* to StructType*
     let struct_ptr_type = struct_type.ptr_type(inkwell::AddressSpace::default());
-    // Inkwell's build_bitcast takes BasicValueEnum, returns BasicValueEnum
-    // Wait, build_bitcast takes (val, type, name).
-    // raw_ptr is PointerValue.
-
-    // In LLVM 15+ typed pointers are gone, so raw_ptr (i8*) can often just be used if we specify type in GEP/Load/Store.
-    // However, for strictness and if using older LLVM binding behavior:
-    // If opaque pointers are enabled (LLVM 15 default), bitcast might be no-op or unnecessary,
-    // but inkwell might explicit types.
-    // Let's assume typed pointers for safety with inkwell API.
-
-    // Actually, inkwell wrapping implies typed pointers API usually.
     let struct_ptr = ctx
         .builder
         .build_bitcast(raw_ptr, struct_ptr_type, "struct_ptr")
         .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?
         .into_pointer_value();
-
-    // 5. 初始化字段
-    // 将提供的字段值映射到它们在结构体中的位置
-    let mut provided_values = HashMap::new();
-    for (name, expr) in fields {
-        let val_wrapper = generate_expr(ctx, locals, expr)?;
-        provided_values.insert(name, val_wrapper.value);
-    }
-
-    // 按定义顺序遍历字段并 store
-    for (i, field_name) in field_names.iter().enumerate() {
-        if let Some(val) = provided_values.get(field_name) {
-            // GEP to field address
-            // struct_ptr is Pointer to Struct.
-            // GEP needs to dereference that pointer?
-            // build_struct_gep takes the pointer to the struct.
-            let field_ptr = ctx
-                .builder
-                .build_struct_gep(
-                    *struct_type,
-                    struct_ptr,
-                    i as u32,
-                    &format!("field_{}", field_name),
-                )
-                .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
-
-            ctx.builder
-                .build_store(field_ptr, *val)
-                .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
-        } else {
-            // 如果字段没有在字面量中提供，这里会有默认值逻辑吗？
-            // Lency 语法目前似乎要求提供所有字段，或者 Sema 阶段会检查。
-            // 这里假设 Sema 已经保证完整性，或者是 Option/Default 处理。
-            // 如果缺失，暂不处理（或者是 garbage/zero initialized if malloc was calloc? malloc is raw）
-            // 应该报错或者 fill zero。
-            // 为了简化，假设必填。
-        }
-    }
-
-    Ok(CodegenValue {
-        value: struct_ptr.into(),
-        ty: Type::Struct(type_name.to_string()),
-    })
+    Ok(struct_ptr)
 }
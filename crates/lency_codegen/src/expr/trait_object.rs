@@ -0,0 +1,327 @@
+//! Trait Object Code Generation
+//!
+//! `dyn T` 的构造与动态分发：
+//! - 值转换为 `dyn T` 时在堆上分配 `{ 数据指针, 虚表指针 }` 胖指针；转为 `dyn T?` 时 null 仍是空指针
+//! - 虚表是按 Trait 方法声明顺序排列的函数指针数组，每个 (Trait, 类型) 组合一张，首次使用时生成
+//! - 方法调用从虚表取出实现，以数据指针作为 this 间接调用
+
+use crate::context::CodegenContext;
+use crate::error::{CodegenError, CodegenResult};
+use crate::expr::{generate_expr, CodegenValue};
+use crate::types::{trait_object_struct_type, ToLLVMType};
+use inkwell::module::Linkage;
+use inkwell::types::BasicMetadataTypeEnum;
+use inkwell::values::{BasicMetadataValueEnum, GlobalValue, PointerValue};
+use inkwell::AddressSpace;
+use lency_syntax::ast::{Expr, Type};
+use std::collections::HashMap;
+
+/// 生成 `value as dyn T`：已经是 `dyn T` 的值直接复用，结构体值包装为新的胖指针
+pub(crate) fn gen_trait_object<'ctx>(
+    ctx: &CodegenContext<'ctx>,
+    val: CodegenValue<'ctx>,
+    trait_name: &str,
+) -> CodegenResult<CodegenValue<'ctx>> {
+    let target = Type::TraitObject(trait_name.to_string());
+    if val.ty == target {
+        return Ok(CodegenValue {
+            value: val.value,
+            ty: target,
+        });
+    }
+    let Type::Struct(type_name) = &val.ty else {
+        return Err(CodegenError::UnsupportedType(format!(
+            "cannot convert '{}' to 'dyn {}'",
+            val.ty, trait_name
+        )));
+    };
+
+    let i8_ptr = ctx.context.i8_type().ptr_type(AddressSpace::default());
+    let vtable = get_or_create_vtable(ctx, trait_name, type_name)?;
+    let data_ptr = ctx
+        .builder
+        .build_pointer_cast(val.value.into_pointer_value(), i8_ptr, "dyn_data")
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+    let vtable_ptr = ctx
+        .builder
+        .build_pointer_cast(vtable.as_pointer_value(), i8_ptr, "dyn_vtable")
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+
+    let fat_type = trait_object_struct_type(ctx);
+    let size = fat_type
+        .size_of()
+        .ok_or_else(|| CodegenError::LLVMBuildError("trait object has no size".to_string()))?;
+    let malloc = ctx
+        .module
+        .get_function("malloc")
+        .ok_or_else(|| CodegenError::LLVMBuildError("malloc function not found".to_string()))?;
+    let raw_ptr = ctx
+        .builder
+        .build_call(malloc, &[size.into()], "dyn_alloc")
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?
+        .try_as_basic_value()
+        .left()
+        .ok_or(CodegenError::LLVMBuildError(
+            "malloc returned void".to_string(),
+        ))?
+        .into_pointer_value();
+    let fat_ptr = ctx
+        .builder
+        .build_pointer_cast(
+            raw_ptr,
+            fat_type.ptr_type(AddressSpace::default()),
+            "dyn_ptr",
+        )
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+
+    for (index, value, name) in [
+        (0, data_ptr, "dyn_data_slot"),
+        (1, vtable_ptr, "dyn_vtable_slot"),
+    ] {
+        let slot = ctx
+            .builder
+            .build_struct_gep(fat_type, fat_ptr, index, name)
+            .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+        ctx.builder
+            .build_store(slot, value)
+            .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+    }
+
+    Ok(CodegenValue {
+        value: fat_ptr.into(),
+        ty: target,
+    })
+}
+
+/// 生成 `value as dyn T?`：非空值同 `as dyn T`，可空结构体为 null 时结果仍为空指针
+pub(crate) fn gen_nullable_trait_object<'ctx>(
+    ctx: &CodegenContext<'ctx>,
+    val: CodegenValue<'ctx>,
+    trait_name: &str,
+) -> CodegenResult<CodegenValue<'ctx>> {
+    let target = Type::Nullable(Box::new(Type::TraitObject(trait_name.to_string())));
+    let inner = match &val.ty {
+        Type::Nullable(inner) => inner.as_ref().clone(),
+        _ => {
+            let object = gen_trait_object(ctx, val, trait_name)?;
+            return Ok(CodegenValue {
+                value: object.value,
+                ty: target,
+            });
+        }
+    };
+    if matches!(&inner, Type::TraitObject(name) if name == trait_name) {
+        return Ok(CodegenValue {
+            value: val.value,
+            ty: target,
+        });
+    }
+
+    // 可空结构体：非空时才构造胖指针，两条路径在 phi 处汇合
+    let ptr = val.value.into_pointer_value();
+    let entry_bb = ctx
+        .builder
+        .get_insert_block()
+        .ok_or(CodegenError::LLVMBuildError("No insert block".to_string()))?;
+    let function = entry_bb.get_parent().ok_or(CodegenError::LLVMBuildError(
+        "No parent function".to_string(),
+    ))?;
+    let some_bb = ctx.context.append_basic_block(function, "dyn_some");
+    let merge_bb = ctx.context.append_basic_block(function, "dyn_merge");
+    let is_null = ctx
+        .builder
+        .build_is_null(ptr, "dyn_is_null")
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+    ctx.builder
+        .build_conditional_branch(is_null, merge_bb, some_bb)
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+
+    ctx.builder.position_at_end(some_bb);
+    let object = gen_trait_object(
+        ctx,
+        CodegenValue {
+            value: val.value,
+            ty: inner,
+        },
+        trait_name,
+    )?;
+    let object_ptr = object.value.into_pointer_value();
+    let some_end_bb = ctx
+        .builder
+        .get_insert_block()
+        .ok_or(CodegenError::LLVMBuildError("No insert block".to_string()))?;
+    ctx.builder
+        .build_unconditional_branch(merge_bb)
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+
+    ctx.builder.position_at_end(merge_bb);
+    let phi = ctx
+        .builder
+        .build_phi(object_ptr.get_type(), "dyn_nullable")
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+    phi.add_incoming(&[
+        (&object_ptr.get_type().const_null(), entry_bb),
+        (&object_ptr, some_end_bb),
+    ]);
+
+    Ok(CodegenValue {
+        value: phi.as_basic_value(),
+        ty: target,
+    })
+}
+
+/// 生成 Trait 对象上的方法调用：按方法在 Trait 中的位置取出虚表槽位并间接调用
+pub(crate) fn gen_trait_object_call<'ctx>(
+    ctx: &CodegenContext<'ctx>,
+    locals: &HashMap<String, (PointerValue<'ctx>, Type)>,
+    object_val: CodegenValue<'ctx>,
+    trait_name: &str,
+    method_name: &str,
+    args: &[Expr],
+) -> CodegenResult<CodegenValue<'ctx>> {
+    let methods = trait_methods(ctx, trait_name)?;
+    let (slot_index, method) = methods
+        .iter()
+        .enumerate()
+        .find(|(_, m)| m.name == method_name)
+        .ok_or_else(|| {
+            CodegenError::FunctionNotFound(format!("dyn {}.{}", trait_name, method_name))
+        })?;
+
+    let i8_ptr = ctx.context.i8_type().ptr_type(AddressSpace::default());
+    let fat_type = trait_object_struct_type(ctx);
+    // 从 Vec 等容器取出的 Trait 对象是 i8*，统一转换为胖指针类型
+    let fat_ptr = ctx
+        .builder
+        .build_pointer_cast(
+            object_val.value.into_pointer_value(),
+            fat_type.ptr_type(AddressSpace::default()),
+            "dyn_ptr",
+        )
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+    let load_slot = |index: u32, name: &str| -> CodegenResult<PointerValue<'ctx>> {
+        let slot = ctx
+            .builder
+            .build_struct_gep(fat_type, fat_ptr, index, &format!("{}_slot", name))
+            .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+        Ok(ctx
+            .builder
+            .build_load(i8_ptr, slot, name)
+            .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?
+            .into_pointer_value())
+    };
+    let data_ptr = load_slot(0, "dyn_data")?;
+    let vtable_raw = load_slot(1, "dyn_vtable")?;
+
+    let vtable_type = i8_ptr.array_type(methods.len() as u32);
+    let vtable_ptr = ctx
+        .builder
+        .build_pointer_cast(
+            vtable_raw,
+            vtable_type.ptr_type(AddressSpace::default()),
+            "vtable",
+        )
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+    let indices = [
+        ctx.context.i64_type().const_zero(),
+        ctx.context.i64_type().const_int(slot_index as u64, false),
+    ];
+    let method_slot = unsafe {
+        ctx.builder
+            .build_gep(vtable_type, vtable_ptr, &indices, "vtable_slot")
+            .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?
+    };
+    let method_raw = ctx
+        .builder
+        .build_load(i8_ptr, method_slot, "method_raw")
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?
+        .into_pointer_value();
+
+    // 实现方法的 this 是具体结构体的指针，经虚表调用时统一按 i8* 传递
+    let mut param_types: Vec<BasicMetadataTypeEnum> = vec![i8_ptr.into()];
+    for param in &method.params {
+        param_types.push(param.ty.to_llvm_type(ctx)?.into());
+    }
+    let fn_type = if matches!(method.return_type, Type::Void) {
+        ctx.context.void_type().fn_type(&param_types, false)
+    } else {
+        method
+            .return_type
+            .to_llvm_type(ctx)?
+            .fn_type(&param_types, false)
+    };
+    let fn_ptr = ctx
+        .builder
+        .build_pointer_cast(
+            method_raw,
+            fn_type.ptr_type(AddressSpace::default()),
+            "method_ptr",
+        )
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+
+    let mut compiled_args: Vec<BasicMetadataValueEnum> = vec![data_ptr.into()];
+    for (arg, param) in args.iter().zip(&method.params) {
+        let arg_val = generate_expr(ctx, locals, arg)?;
+        let value = super::widen_int(ctx, arg_val.value, &param.ty)?;
+        compiled_args.push(super::coerce_pointer(ctx, value, &param.ty)?.into());
+    }
+
+    let call_site = ctx
+        .builder
+        .build_indirect_call(fn_type, fn_ptr, &compiled_args, "dyn_call")
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+
+    match call_site.try_as_basic_value().left() {
+        Some(value) => Ok(CodegenValue {
+            value,
+            ty: method.return_type.clone(),
+        }),
+        None => Ok(CodegenValue {
+            value: ctx.context.bool_type().const_int(0, false).into(),
+            ty: Type::Void,
+        }),
+    }
+}
+
+/// 取得 (Trait, 类型) 对应的虚表，首次使用时生成
+fn get_or_create_vtable<'ctx>(
+    ctx: &CodegenContext<'ctx>,
+    trait_name: &str,
+    type_name: &str,
+) -> CodegenResult<GlobalValue<'ctx>> {
+    let vtable_name = format!("vtable.{}.{}", trait_name, type_name);
+    if let Some(vtable) = ctx.module.get_global(&vtable_name) {
+        return Ok(vtable);
+    }
+
+    let i8_ptr = ctx.context.i8_type().ptr_type(AddressSpace::default());
+    // Trait 的默认方法已在语义分析时复制进 impl 块，每个槽位都有对应的 Type_method
+    let slots = trait_methods(ctx, trait_name)?
+        .iter()
+        .map(|method| {
+            let fn_name = lency_monomorph::mangling::method_symbol(type_name, &method.name);
+            ctx.module
+                .get_function(&fn_name)
+                .map(|f| f.as_global_value().as_pointer_value().const_cast(i8_ptr))
+                .ok_or(CodegenError::FunctionNotFound(fn_name))
+        })
+        .collect::<CodegenResult<Vec<_>>>()?;
+
+    let vtable = ctx
+        .module
+        .add_global(i8_ptr.array_type(slots.len() as u32), None, &vtable_name);
+    vtable.set_initializer(&i8_ptr.const_array(&slots));
+    vtable.set_constant(true);
+    vtable.set_linkage(Linkage::Private);
+    Ok(vtable)
+}
+
+fn trait_methods<'a>(
+    ctx: &'a CodegenContext<'_>,
+    trait_name: &str,
+) -> CodegenResult<&'a [lency_syntax::ast::TraitMethod]> {
+    ctx.trait_methods
+        .get(trait_name)
+        .map(Vec::as_slice)
+        .ok_or_else(|| CodegenError::UnsupportedType(format!("undefined trait '{}'", trait_name)))
+}
@@ -8,7 +8,6 @@ use crate::context::CodegenContext;
 use crate::error::{CodegenError, CodegenResult};
 use crate::expr::CodegenValue;
 use crate::types::ToLLVMType;
-use inkwell::types::BasicType;
 
 /// 生成变量引用代码
 pub(super) fn gen_variable<'ctx>(
@@ -88,23 +87,29 @@ pub(super) fn gen_variable<'ctx>(
                 }
             }
 
+            // 函数名作为值：取函数地址，作为函数指针传递（如运行时回调）
+            if let Some(param_types) = ctx.function_param_types.get(name) {
+                if let (Some(function), Some(return_type)) = (
+                    ctx.module.get_function(name),
+                    ctx.function_signatures.get(name),
+                ) {
+                    return Ok(CodegenValue {
+                        value: function.as_global_value().as_pointer_value().into(),
+                        ty: lency_syntax::ast::Type::Function {
+                            param_types: param_types.clone(),
+                            return_type: Box::new(return_type.clone()),
+                        },
+                    });
+                }
+            }
+
             return Err(CodegenError::UndefinedVariable(name.to_string()));
         }
     };
 
     // 使用保存的类型信息进行加载
-    // 对于 this 变量（方法中），它是指向结构体的指针，需要使用指针类型加载
-    let llvm_type = if name == "this" {
-        if let lency_syntax::ast::Type::Struct(_) = ty {
-            ty.to_llvm_type(ctx)?
-                .ptr_type(inkwell::AddressSpace::default())
-                .into()
-        } else {
-            ty.to_llvm_type(ctx)?
-        }
-    } else {
-        ty.to_llvm_type(ctx)?
-    };
+    // 方法中的 this 与普通结构体变量一样，存储的是结构体指针
+    let llvm_type = ty.to_llvm_type(ctx)?;
     let val = ctx
         .builder
         .build_load(llvm_type, *ptr, name)
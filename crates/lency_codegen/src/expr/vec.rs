@@ -58,6 +58,43 @@ pub fn gen_vec_literal<'ctx>(
     })
 }
 
+/// Generate code for vec![value; count] (count may be computed at runtime)
+pub fn gen_vec_repeat<'ctx>(
+    ctx: &CodegenContext<'ctx>,
+    locals: &HashMap<String, (inkwell::values::PointerValue<'ctx>, Type)>,
+    value: &Expr,
+    count: &Expr,
+) -> CodegenResult<CodegenValue<'ctx>> {
+    let vec_new_fn = get_or_declare_vec_new(ctx)?;
+    let vec_push_fn = get_or_declare_vec_push(ctx)?;
+
+    // value 只求值一次，每个元素都是它的副本
+    let elem_val = generate_expr(ctx, locals, value)?;
+    let count_val = generate_expr(ctx, locals, count)?;
+    let count_i64 = super::widen_int(ctx, count_val.value, &Type::Int)?.into_int_value();
+
+    let vec_ptr = ctx
+        .builder
+        .build_call(vec_new_fn, &[count_i64.into()], "vec")
+        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?
+        .try_as_basic_value()
+        .left()
+        .ok_or_else(|| CodegenError::LLVMBuildError("vec_new returned void".to_string()))?;
+
+    let elem_i64 = cast_to_i64(ctx, elem_val.value)?;
+    super::array::build_counted_loop(ctx, count_i64, "vec_repeat", |_| {
+        ctx.builder
+            .build_call(vec_push_fn, &[vec_ptr.into(), elem_i64.into()], "")
+            .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+        Ok(())
+    })?;
+
+    Ok(CodegenValue {
+        value: vec_ptr,
+        ty: Type::Vec(Box::new(elem_val.ty)),
+    })
+}
+
 /// Get or declare lency_vec_new function
 fn get_or_declare_vec_new<'ctx>(
     ctx: &CodegenContext<'ctx>,
@@ -185,10 +222,60 @@ pub fn gen_vec_method_call<'ctx>(
                 ty: Type::Void,
             })
         }
+        "sort_by" => {
+            // 运行时以 i64 存储元素：只有 int 与指针类元素能与比较器的调用约定一致
+            if matches!(inner_type, Type::Float | Type::Bool) {
+                return Err(CodegenError::UnsupportedType(format!(
+                    "Vec<{}>.sort_by",
+                    inner_type
+                )));
+            }
+            let func = get_or_declare_vec_sort_by(ctx)?;
+            let cmp_val = generate_expr(ctx, locals, &args[0])?;
+            let cmp_ptr = ctx
+                .builder
+                .build_pointer_cast(
+                    cmp_val.value.into_pointer_value(),
+                    comparator_ptr_type(ctx),
+                    "cmp_fn",
+                )
+                .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+
+            ctx.builder
+                .build_call(func, &[vec_ptr.into(), cmp_ptr.into()], "")
+                .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+
+            Ok(CodegenValue {
+                value: ctx.context.i64_type().const_zero().into(),
+                ty: Type::Void,
+            })
+        }
         _ => Err(CodegenError::FunctionNotFound(method_name.to_string())),
     }
 }
 
+/// 运行时比较器的函数指针类型: i64 (i64, i64)*
+fn comparator_ptr_type<'ctx>(ctx: &CodegenContext<'ctx>) -> inkwell::types::PointerType<'ctx> {
+    let i64_type = ctx.context.i64_type();
+    i64_type
+        .fn_type(&[i64_type.into(), i64_type.into()], false)
+        .ptr_type(AddressSpace::default())
+}
+
+pub(crate) fn get_or_declare_vec_sort_by<'ctx>(
+    ctx: &CodegenContext<'ctx>,
+) -> CodegenResult<inkwell::values::FunctionValue<'ctx>> {
+    if let Some(func) = ctx.module.get_function("lency_vec_sort_by") {
+        return Ok(func);
+    }
+    let vec_ptr_type = ctx.context.i8_type().ptr_type(AddressSpace::default());
+    let fn_type = ctx.context.void_type().fn_type(
+        &[vec_ptr_type.into(), comparator_ptr_type(ctx).into()],
+        false,
+    );
+    Ok(ctx.module.add_function("lency_vec_sort_by", fn_type, None))
+}
+
 // Helpers for declaring missing functions
 pub(crate) fn get_or_declare_vec_pop<'ctx>(
     ctx: &CodegenContext<'ctx>,
@@ -285,7 +372,11 @@ pub(crate) fn cast_from_i64<'ctx>(
             .builder
             .build_bitcast(val, ctx.context.f64_type(), "i2f")
             .unwrap()),
-        Type::String | Type::Struct(_) | Type::Vec(_) | Type::Array { .. } => {
+        Type::String
+        | Type::Struct(_)
+        | Type::Vec(_)
+        | Type::Array { .. }
+        | Type::TraitObject(_) => {
             // Pointer
             let ptr_ty = ctx.context.i8_type().ptr_type(AddressSpace::default());
             Ok(ctx
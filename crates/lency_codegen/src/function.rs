@@ -52,6 +52,7 @@ impl<'ctx, 'a> FunctionGenerator<'ctx, 'a> {
             params,
             return_type,
             body,
+            span,
             ..
         } = decl
         else {
@@ -136,10 +137,8 @@ impl<'ctx, 'a> FunctionGenerator<'ctx, 'a> {
 
                 locals.insert("this".to_string(), (this_alloca, this_type));
             } else {
-                // Struct 类型：this 是指针类型
-                let this_ptr_llvm_type = this_type
-                    .to_llvm_type(self.ctx)?
-                    .ptr_type(inkwell::AddressSpace::default());
+                // Struct 类型：this 是结构体指针（Struct 的 LLVM 类型本身就是指针）
+                let this_ptr_llvm_type = this_type.to_llvm_type(self.ctx)?;
                 let this_alloca = self
                     .ctx
                     .builder
@@ -178,60 +177,168 @@ impl<'ctx, 'a> FunctionGenerator<'ctx, 'a> {
             locals.insert(param.name.clone(), (alloca, param.ty.clone()));
         }
 
+        if let Some(limit) = self.ctx.stack_guard {
+            self.gen_stack_guard_prologue(function, name, limit, self.ctx.get_line(span.start))?;
+        }
+
         // 生成函数体
         let mut stmt_gen = StmtGenerator::new(self.ctx, &mut locals, return_type);
         stmt_gen.generate_block(body)?;
 
         // 如果是 void 函数且没有显式 return，添加隐式 return
-        if *return_type == Type::Void {
-            let last_bb = self.ctx.builder.get_insert_block();
-            if let Some(bb) = last_bb {
-                if bb.get_terminator().is_none() {
+        // 非 void 函数的返回已由语义分析保证，末尾未终止的块只可能是不可达代码（如 panic 之后）
+        if let Some(bb) = self.ctx.builder.get_insert_block() {
+            if bb.get_terminator().is_none() {
+                if *return_type == Type::Void {
                     self.ctx
                         .builder
                         .build_return(None)
                         .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+                } else {
+                    self.ctx
+                        .builder
+                        .build_unreachable()
+                        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
                 }
             }
         }
 
+        if self.ctx.stack_guard.is_some() {
+            self.gen_stack_guard_epilogue(function)?;
+        }
+
         Ok(function)
     }
 
+    /// 全局调用深度计数器 `__lency_stack_depth`，首次使用时创建
+    fn stack_depth_counter(&self) -> inkwell::values::PointerValue<'ctx> {
+        const COUNTER: &str = "__lency_stack_depth";
+        let global = self.ctx.module.get_global(COUNTER).unwrap_or_else(|| {
+            let i64_type = self.ctx.context.i64_type();
+            let global = self.ctx.module.add_global(i64_type, None, COUNTER);
+            global.set_linkage(inkwell::module::Linkage::Internal);
+            global.set_initializer(&i64_type.const_zero());
+            global
+        });
+        global.as_pointer_value()
+    }
+
+    /// 调用深度加一，超过 `limit` 时以 "stack overflow" 调用 __lency_panic
+    fn gen_stack_guard_prologue(
+        &self,
+        function: FunctionValue<'ctx>,
+        name: &str,
+        limit: u64,
+        line: u32,
+    ) -> CodegenResult<()> {
+        let builder = &self.ctx.builder;
+        let i64_type = self.ctx.context.i64_type();
+        let counter = self.stack_depth_counter();
+        let depth = builder
+            .build_load(i64_type, counter, "stack_depth")
+            .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?
+            .into_int_value();
+        let depth = builder
+            .build_int_add(depth, i64_type.const_int(1, false), "stack_depth_inc")
+            .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+        builder
+            .build_store(counter, depth)
+            .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+        let overflow = builder
+            .build_int_compare(
+                inkwell::IntPredicate::UGT,
+                depth,
+                i64_type.const_int(limit, false),
+                "stack_overflow",
+            )
+            .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+
+        let panic_block = self
+            .ctx
+            .context
+            .append_basic_block(function, "stack_guard_panic");
+        let body_block = self.ctx.context.append_basic_block(function, "body");
+        builder
+            .build_conditional_branch(overflow, panic_block, body_block)
+            .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+
+        builder.position_at_end(panic_block);
+        let panic_func = self
+            .ctx
+            .panic_func
+            .ok_or_else(|| CodegenError::FunctionNotFound("__lency_panic".to_string()))?;
+        let message = format!(
+            "stack overflow: call depth exceeded {} in function '{}'",
+            limit, name
+        );
+        crate::runtime::gen_panic(self.ctx.context, builder, panic_func, &message, line);
+
+        builder.position_at_end(body_block);
+        Ok(())
+    }
+
+    /// 在函数的每条 ret 指令前把调用深度减一
+    fn gen_stack_guard_epilogue(&self, function: FunctionValue<'ctx>) -> CodegenResult<()> {
+        let builder = &self.ctx.builder;
+        let i64_type = self.ctx.context.i64_type();
+        let counter = self.stack_depth_counter();
+        for block in function.get_basic_blocks() {
+            let Some(ret) = block
+                .get_terminator()
+                .filter(|t| t.get_opcode() == inkwell::values::InstructionOpcode::Return)
+            else {
+                continue;
+            };
+            builder.position_before(&ret);
+            let depth = builder
+                .build_load(i64_type, counter, "stack_depth")
+                .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?
+                .into_int_value();
+            let depth = builder
+                .build_int_sub(depth, i64_type.const_int(1, false), "stack_depth_dec")
+                .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+            builder
+                .build_store(counter, depth)
+                .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+        }
+        Ok(())
+    }
+
     /// 声明函数（不生成函数体）
     pub fn declare(
         &self,
         name: &str,
         params: &[lency_syntax::ast::Param],
         return_type: &Type,
+    ) -> CodegenResult<FunctionValue<'ctx>> {
+        self.declare_extern(name, params, return_type, false)
+    }
+
+    /// 声明外部函数，`is_variadic` 时固定参数之后接受任意个实参（C 的 `...`）
+    pub fn declare_extern(
+        &self,
+        name: &str,
+        params: &[lency_syntax::ast::Param],
+        return_type: &Type,
+        is_variadic: bool,
     ) -> CodegenResult<FunctionValue<'ctx>> {
         // 构建参数类型列表
         let mut param_types = Vec::new();
         for param in params {
-            // 对于方法的 this 参数，使用指针类型（结构体通过指针传递）
-            let param_ty = if param.name == "this" {
-                if let Type::Struct(_) = &param.ty {
-                    // this 参数使用指针类型
-                    param
-                        .ty
-                        .to_llvm_type(self.ctx)?
-                        .ptr_type(inkwell::AddressSpace::default())
-                        .into()
-                } else {
-                    param.ty.to_llvm_type(self.ctx)?
-                }
-            } else {
-                param.ty.to_llvm_type(self.ctx)?
-            };
+            // 方法的 this 参数：Struct 的 LLVM 类型已是指针，结构体按指针传递
+            let param_ty = param.ty.to_llvm_type(self.ctx)?;
             param_types.push(param_ty.into());
         }
 
         // 构建函数类型
         let fn_type = if *return_type == Type::Void {
-            self.ctx.context.void_type().fn_type(&param_types, false)
+            self.ctx
+                .context
+                .void_type()
+                .fn_type(&param_types, is_variadic)
         } else {
             let ret_ty = return_type.to_llvm_type(self.ctx)?;
-            ret_ty.fn_type(&param_types, false)
+            ret_ty.fn_type(&param_types, is_variadic)
         };
 
         // 添加函数到模块
@@ -12,25 +12,43 @@
 //! - `stmt.rs` - 语句代码生成
 //! - `function.rs` - 函数代码生成
 //! - `module.rs` - 模块代码生成
+//! - `abi.rs` - extern "C" 调用的 C ABI 降级
+//! - `stats.rs` - 生成后模块的规模统计
 
+pub mod abi;
 pub mod context;
 pub mod error;
 pub mod expr;
 pub mod function;
 pub mod module;
 pub mod runtime;
+pub mod stats;
 pub mod stmt;
 pub mod types;
 
 // 重新导出核心类型
 pub use context::CodegenContext;
 pub use error::{CodegenError, CodegenResult};
+pub use stats::ModuleStats;
 
 use inkwell::context::Context;
 use inkwell::module::Module;
 use lency_syntax::ast::Program;
 use module::ModuleGenerator;
 
+/// 代码生成选项
+#[derive(Debug, Clone, Default)]
+pub struct CodegenOptions {
+    /// 调用深度上限；设置后在每个函数的入口/出口维护全局调用深度计数，
+    /// 超出上限时调用 `__lency_panic`，便于调试失控递归（默认关闭）
+    pub stack_guard: Option<u64>,
+    /// 生成后统计模块的函数、基本块与指令数量（`--stats`，默认关闭）
+    pub stats: bool,
+    /// 主源文件路径：运行时 panic 报告为 `panic at <path>:<line>: ...`，缺省时只报告行号；
+    /// 导入模块中的函数总是报告该模块自己的源文件
+    pub source_path: Option<String>,
+}
+
 /// 编译 Lency 程序为 LLVM IR
 ///
 /// # Arguments
@@ -47,8 +65,30 @@ pub fn compile_to_ir(
     module_name: &str,
     source: Option<&str>,
 ) -> CodegenResult<String> {
+    compile_to_ir_with_options(program, module_name, source, &CodegenOptions::default())
+}
+
+/// 按给定选项编译 Lency 程序为 LLVM IR，参数同 [`compile_to_ir`]
+pub fn compile_to_ir_with_options(
+    program: &Program,
+    module_name: &str,
+    source: Option<&str>,
+    options: &CodegenOptions,
+) -> CodegenResult<String> {
+    compile_to_ir_with_stats(program, module_name, source, options).map(|(ir, _)| ir)
+}
+
+/// 同 [`compile_to_ir_with_options`]，`options.stats` 开启时一并返回模块统计
+pub fn compile_to_ir_with_stats(
+    program: &Program,
+    module_name: &str,
+    source: Option<&str>,
+    options: &CodegenOptions,
+) -> CodegenResult<(String, Option<ModuleStats>)> {
     let context = Context::create();
     let mut ctx = CodegenContext::new(&context, module_name, source);
+    ctx.stack_guard = options.stack_guard;
+    ctx.source_path = options.source_path.clone();
 
     // 生成代码
     let mut module_gen = ModuleGenerator::new(&mut ctx);
@@ -58,7 +98,11 @@ pub fn compile_to_ir(
     ctx.verify()?;
 
     // 返回 IR 字符串
-    Ok(ctx.print_to_string())
+    let ir = ctx.print_to_string();
+    let stats = options
+        .stats
+        .then(|| ModuleStats::collect(&ctx.module, &ir));
+    Ok((ir, stats))
 }
 
 /// 编译 Lency 程序为 LLVM Module（用于进一步处理）
@@ -100,6 +144,8 @@ mod tests {
         Program {
             decls: vec![Decl::Function {
                 span: 0..30,
+                doc: None,
+                is_public: false,
                 name: "main".to_string(),
                 generic_params: vec![],
                 params: vec![],
@@ -111,6 +157,7 @@ mod tests {
                         span: 17..19,
                     }),
                 }],
+                source_file: None,
             }],
         }
     }
@@ -138,6 +185,8 @@ mod tests {
         let program = Program {
             decls: vec![Decl::Function {
                 span: 0..50,
+                doc: None,
+                is_public: false,
                 name: "add".to_string(),
                 generic_params: vec![],
                 params: vec![
@@ -168,6 +217,7 @@ mod tests {
                         span: 37..42,
                     }),
                 }],
+                source_file: None,
             }],
         };
 
@@ -186,6 +236,8 @@ mod tests {
         let program = Program {
             decls: vec![Decl::Function {
                 span: 0..60,
+                doc: None,
+                is_public: false,
                 name: "test".to_string(),
                 generic_params: vec![],
                 params: vec![],
@@ -194,6 +246,7 @@ mod tests {
                     Stmt::VarDecl {
                         span: 15..25,
                         name: "x".to_string(),
+                        mutable: true,
                         ty: Some(Type::Int),
                         value: Expr {
                             kind: ExprKind::Literal(Literal::Int(10)),
@@ -208,6 +261,7 @@ mod tests {
                         }),
                     },
                 ],
+                source_file: None,
             }],
         };
 
@@ -220,4 +274,39 @@ mod tests {
         assert!(ir.contains("store i64 10"));
         assert!(ir.contains("load i64"));
     }
+
+    #[test]
+    fn test_packed_struct_layout() {
+        let source = r#"
+            packed struct Header {
+                i8 tag
+                int len
+            }
+
+            struct Loose {
+                i8 tag
+                int len
+            }
+
+            int main() {
+                return 0
+            }
+        "#;
+        let mut program = lency_syntax::parser::parse(source).expect("parse failed");
+        lency_sema::analyze(&mut program).expect("analysis failed");
+
+        let context = Context::create();
+        let module = compile_to_module(&program, &context, "test_packed", Some(source))
+            .expect("compilation failed");
+        let header = module.get_struct_type("Header").unwrap();
+        let loose = module.get_struct_type("Loose").unwrap();
+        assert!(header.is_packed());
+        assert!(!loose.is_packed());
+
+        // x86-64 布局：默认对齐时 i8 后填充 7 字节，packed 则紧挨着排列
+        let target_data =
+            inkwell::targets::TargetData::create("e-m:e-i64:64-f80:128-n8:16:32:64-S128");
+        assert_eq!(target_data.get_abi_size(&header), 9);
+        assert_eq!(target_data.get_abi_size(&loose), 16);
+    }
 }
@@ -1,14 +1,21 @@
 use super::ModuleGenerator;
-use crate::error::CodegenResult;
+use crate::error::{CodegenError, CodegenResult};
+use crate::expr::ExprGenerator;
 use crate::function::FunctionGenerator;
 use crate::types::ToLLVMType;
-use lency_syntax::ast::{Decl, Program};
+use inkwell::module::Linkage;
+use inkwell::types::BasicTypeEnum;
+use inkwell::values::BasicValueEnum;
+use lency_syntax::ast::{Decl, Expr, ExprKind, Literal, Program, Type, UnaryOp};
 
 impl<'ctx, 'a> ModuleGenerator<'ctx, 'a> {
     /// 注入运行时函数 (__lency_panic, printf, exit, malloc)
     pub(crate) fn inject_runtime(&mut self) -> CodegenResult<()> {
-        let panic_func =
-            crate::runtime::inject_runtime_functions(self.ctx.context, &self.ctx.module);
+        let panic_func = crate::runtime::inject_runtime_functions(
+            self.ctx.context,
+            &self.ctx.module,
+            self.ctx.source_path.as_deref(),
+        );
         self.ctx.panic_func = Some(panic_func);
 
         // 预定义 malloc: declare i8* @malloc(i64)
@@ -49,6 +56,9 @@ impl<'ctx, 'a> ModuleGenerator<'ctx, 'a> {
                     self.ctx
                         .function_signatures
                         .insert(name.clone(), return_type.clone());
+                    self.ctx
+                        .function_param_types
+                        .insert(name.clone(), params.iter().map(|p| p.ty.clone()).collect());
 
                     if name == "main" {
                         let func_gen = FunctionGenerator::new(&*self.ctx);
@@ -61,16 +71,34 @@ impl<'ctx, 'a> ModuleGenerator<'ctx, 'a> {
                 }
 
                 Decl::ExternFunction {
+                    abi,
                     name,
                     params,
+                    is_variadic,
                     return_type,
                     ..
                 } => {
                     self.ctx
                         .function_signatures
                         .insert(name.clone(), return_type.clone());
-                    let func_gen = FunctionGenerator::new(&*self.ctx);
-                    func_gen.declare(name, params, return_type)?;
+                    self.ctx
+                        .function_param_types
+                        .insert(name.clone(), params.iter().map(|p| p.ty.clone()).collect());
+                    // 运行时已声明的 C 函数（如 printf）沿用已有声明，调用处按声明的返回类型拓宽
+                    if self.ctx.module.get_function(name).is_none() {
+                        if abi.as_deref() == Some("C") {
+                            crate::abi::declare_c_function(
+                                self.ctx,
+                                name,
+                                params,
+                                return_type,
+                                *is_variadic,
+                            )?;
+                        } else {
+                            let func_gen = FunctionGenerator::new(&*self.ctx);
+                            func_gen.declare_extern(name, params, return_type, *is_variadic)?;
+                        }
+                    }
                 }
                 Decl::Struct { .. } => {
                     // 已处理
@@ -99,8 +127,9 @@ impl<'ctx, 'a> ModuleGenerator<'ctx, 'a> {
                         } = method
                         {
                             // 生成 mangled 名称：StructName_methodName
-                            let type_str = lency_monomorph::mangling::mangle_type(type_name);
-                            let mangled_name = format!("{}_{}", type_str, name);
+                            let type_str = lency_monomorph::mangling::mangle_receiver(type_name);
+                            let mangled_name =
+                                lency_monomorph::mangling::method_symbol(&type_str, name);
 
                             // 构建带 this 指针的参数列表
                             let this_type = match type_name {
@@ -132,61 +161,162 @@ impl<'ctx, 'a> ModuleGenerator<'ctx, 'a> {
                         }
                     }
                 }
-                // Trait 定义：阶段1不生成代码，仅注册
-                Decl::Trait { .. } => {}
+                // Trait 定义：不生成代码，记录方法顺序供 Trait 对象的虚表使用
+                Decl::Trait { name, methods, .. } => {
+                    self.ctx.trait_methods.insert(name.clone(), methods.clone());
+                }
                 // Enum 定义：在 to_llvm_type 时按需生成布局，这里跳过
                 Decl::Enum { .. } => {}
+                // newtype 直接使用底层类型，不需要声明
+                Decl::Newtype { .. } => {}
                 Decl::Import { .. } => {}
+                Decl::Const {
+                    name, ty, value, ..
+                } => {
+                    // 常量生成为带初始值的只读全局变量
+                    let ty = ty.as_ref().ok_or_else(|| {
+                        CodegenError::UnsupportedFeature(format!("const {} missing type", name))
+                    })?;
+                    let global =
+                        self.ctx
+                            .module
+                            .add_global(ty.to_llvm_type(&*self.ctx)?, None, name);
+                    global.set_constant(true);
+                    global.set_initializer(&self.const_initializer(ty, value)?);
+                    self.ctx.global_var_types.insert(name.clone(), ty.clone());
+                }
                 Decl::Var {
                     name, ty, value, ..
                 } => {
                     // Declare Global
-                    let llvm_ty = if let Some(t) = ty {
-                        t.to_llvm_type(&*self.ctx)?
-                    } else {
-                        // Must have type
-                        return Err(crate::error::CodegenError::UnsupportedFeature(format!(
-                            "Global {} missing type",
-                            name
-                        )));
-                    };
-
+                    let ty = ty.as_ref().ok_or_else(|| {
+                        CodegenError::UnsupportedFeature(format!("Global {} missing type", name))
+                    })?;
+                    let llvm_ty = ty.to_llvm_type(&*self.ctx)?;
                     let global = self.ctx.module.add_global(llvm_ty, None, name);
-                    self.ctx
-                        .global_var_types
-                        .insert(name.clone(), ty.clone().unwrap()); // ty is Some here
+                    self.ctx.global_var_types.insert(name.clone(), ty.clone());
 
-                    // Set Initializer (Assume StructLiteral or Constant for now)
-                    if let lency_syntax::ast::ExprKind::StructLiteral { fields, .. } = &value.kind {
-                        if fields.is_empty() {
-                            // Initializer for empty struct
-                            if llvm_ty.is_struct_type() {
-                                let struct_ty = llvm_ty.into_struct_type();
-                                let const_val = struct_ty.const_named_struct(&[]);
-                                global.set_initializer(&const_val);
-                            }
+                    // 字面量直接作为静态初始值；其余先置零，由 __lency_init 在运行时赋值
+                    match self.const_initializer(ty, value) {
+                        Ok(init) => global.set_initializer(&init),
+                        Err(_) => {
+                            global.set_initializer(&llvm_ty.const_zero());
+                            self.runtime_globals.insert(name.clone());
                         }
                     }
-                    // TODO: Better global init handling
                 }
             }
         }
         Ok(())
     }
 
+    /// 常量初始值：整数 / 浮点 / 布尔字面量（可带负号），以及由它们组成的数组与结构体字面量
+    fn const_initializer(&self, ty: &Type, value: &Expr) -> CodegenResult<BasicValueEnum<'ctx>> {
+        match (&value.kind, ty) {
+            (ExprKind::Array(elements), Type::Array { element_type, size })
+                if elements.len() == *size =>
+            {
+                let values = elements
+                    .iter()
+                    .map(|element| self.const_initializer(element_type, element))
+                    .collect::<CodegenResult<Vec<_>>>()?;
+                return const_array(element_type.to_llvm_type(&*self.ctx)?, &values);
+            }
+            (ExprKind::StructLiteral { fields, .. }, Type::Struct(name)) => {
+                return self.const_struct(name, fields);
+            }
+            _ => {}
+        }
+        let (negate, lit) = match &value.kind {
+            ExprKind::Literal(lit) => (false, lit),
+            ExprKind::Unary(UnaryOp::Neg, inner) => match &inner.kind {
+                ExprKind::Literal(lit) => (true, lit),
+                _ => return Err(CodegenError::UnsupportedExpression),
+            },
+            _ => return Err(CodegenError::UnsupportedExpression),
+        };
+        let context = self.ctx.context;
+        match (ty.int_bits(), ty, lit) {
+            // 整数常量按声明的宽度生成（const i32 MASK = 255）
+            (Some(bits), _, Literal::Int(n)) => {
+                let n = if negate { n.wrapping_neg() } else { *n };
+                Ok(context
+                    .custom_width_int_type(bits)
+                    .const_int(n as u64, true)
+                    .into())
+            }
+            (_, Type::Float, Literal::Float(f)) => {
+                let f = if negate { -f } else { *f };
+                Ok(context.f64_type().const_float(f).into())
+            }
+            (_, Type::Bool, Literal::Bool(b)) if !negate => {
+                Ok(context.bool_type().const_int(*b as u64, false).into())
+            }
+            _ => Err(CodegenError::TypeMismatch),
+        }
+    }
+
+    /// 常量结构体：结构体按指针传递，字段值放进一个私有全局变量，返回其地址
+    fn const_struct(
+        &self,
+        name: &str,
+        fields: &[(String, Expr)],
+    ) -> CodegenResult<BasicValueEnum<'ctx>> {
+        let struct_type = *self
+            .ctx
+            .struct_types
+            .get(name)
+            .ok_or_else(|| CodegenError::UndefinedStructType(name.to_string()))?;
+        let field_names = self.ctx.struct_fields.get(name).ok_or_else(|| {
+            CodegenError::UnsupportedType(format!("Struct '{}' fields not found", name))
+        })?;
+        let field_types = self.ctx.struct_field_types.get(name).ok_or_else(|| {
+            CodegenError::UnsupportedType(format!("Struct '{}' fields not found", name))
+        })?;
+
+        // 按定义顺序排列字段值
+        let values = field_names
+            .iter()
+            .zip(field_types)
+            .map(|(field_name, field_ty)| {
+                let (_, expr) = fields
+                    .iter()
+                    .find(|(provided, _)| provided == field_name)
+                    .ok_or_else(|| {
+                        CodegenError::UnsupportedFeature(format!(
+                            "struct literal '{}' is missing field '{}'",
+                            name, field_name
+                        ))
+                    })?;
+                self.const_initializer(field_ty, expr)
+            })
+            .collect::<CodegenResult<Vec<_>>>()?;
+
+        let global = self
+            .ctx
+            .module
+            .add_global(struct_type, None, &format!("{}.const", name));
+        global.set_initializer(&struct_type.const_named_struct(&values));
+        global.set_linkage(Linkage::Private);
+        Ok(global.as_pointer_value().into())
+    }
+
     /// 第二遍：生成函数体
     pub(crate) fn generate_function_bodies(&mut self, program: &Program) -> CodegenResult<()> {
-        let mut func_gen = FunctionGenerator::new(&*self.ctx);
         for decl in &program.decls {
             match decl {
                 Decl::Function {
                     name,
                     generic_params,
+                    source_file,
                     ..
                 } => {
                     if !generic_params.is_empty() {
                         continue;
                     }
+                    // 导入模块中的函数按其源文件报告 panic 位置
+                    self.ctx.set_source_file(source_file.as_deref());
+                    let mut func_gen = FunctionGenerator::new(&*self.ctx);
                     if name == "main" {
                         func_gen.generate(decl, Some("__lency_main"), None)?;
                     } else {
@@ -201,19 +331,80 @@ impl<'ctx, 'a> ModuleGenerator<'ctx, 'a> {
                 } => {
                     // 生成所有方法的函数体
                     for method in methods {
-                        if let Decl::Function { name, .. } = method {
-                            let type_str = lency_monomorph::mangling::mangle_type(type_name);
-                            let mangled_name = format!("{}_{}", type_str, name);
+                        if let Decl::Function {
+                            name, source_file, ..
+                        } = method
+                        {
+                            let type_str = lency_monomorph::mangling::mangle_receiver(type_name);
+                            let mangled_name =
+                                lency_monomorph::mangling::method_symbol(&type_str, name);
+                            self.ctx.set_source_file(source_file.as_deref());
+                            let mut func_gen = FunctionGenerator::new(&*self.ctx);
                             func_gen.generate(method, Some(&mangled_name), Some(&type_str))?;
                         }
                     }
                 }
                 Decl::Trait { .. } => {}
-                Decl::Enum { .. } => {}
+                Decl::Enum { .. } | Decl::Newtype { .. } => {}
                 Decl::Import { .. } => {}
-                Decl::Var { .. } => {} // Globals generated in declarations pass
+                // Globals generated in declarations pass
+                Decl::Const { .. } | Decl::Var { .. } => {}
             }
         }
+        // 全局变量初始化与 main 包装函数回到主文件
+        self.ctx.set_source_file(None);
+        Ok(())
+    }
+
+    /// 生成 __lency_init：按声明顺序执行需要运行时计算的全局变量初始化
+    ///
+    /// 没有此类全局变量时不生成；main 包装函数在调用用户 main 之前调用它
+    pub(crate) fn generate_global_initializers(&mut self, program: &Program) -> CodegenResult<()> {
+        let runtime_inits: Vec<(&String, &Expr)> = program
+            .decls
+            .iter()
+            .filter_map(|decl| match decl {
+                Decl::Var { name, value, .. } if self.runtime_globals.contains(name) => {
+                    Some((name, value))
+                }
+                _ => None,
+            })
+            .collect();
+        if runtime_inits.is_empty() {
+            return Ok(());
+        }
+
+        let init_type = self.ctx.context.void_type().fn_type(&[], false);
+        let init_func = self.ctx.module.add_function(
+            "__lency_init",
+            init_type,
+            Some(inkwell::module::Linkage::Internal),
+        );
+        let entry = self.ctx.context.append_basic_block(init_func, "entry");
+        self.ctx.builder.position_at_end(entry);
+
+        let locals = std::collections::HashMap::new();
+        let expr_gen = ExprGenerator::new(&*self.ctx, &locals);
+        for (name, value) in runtime_inits {
+            let global = self
+                .ctx
+                .module
+                .get_global(name)
+                .ok_or_else(|| CodegenError::UndefinedVariable(name.clone()))?;
+            let ty = self.ctx.global_var_types[name].clone();
+            let val = expr_gen.generate(value)?;
+            let val = crate::expr::coerce_pointer(&*self.ctx, val.value, &ty)?;
+            let val = crate::expr::widen_int(&*self.ctx, val, &ty)?;
+            self.ctx
+                .builder
+                .build_store(global.as_pointer_value(), val)
+                .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+        }
+
+        self.ctx
+            .builder
+            .build_return(None)
+            .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
         Ok(())
     }
 
@@ -227,6 +418,14 @@ impl<'ctx, 'a> ModuleGenerator<'ctx, 'a> {
             let entry = self.ctx.context.append_basic_block(main_func, "entry");
             self.ctx.builder.position_at_end(entry);
 
+            // 全局变量的运行时初始化先于用户 main
+            if let Some(init_func) = self.ctx.module.get_function("__lency_init") {
+                self.ctx
+                    .builder
+                    .build_call(init_func, &[], "")
+                    .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+            }
+
             let call_inst = self
                 .ctx
                 .builder
@@ -263,3 +462,34 @@ impl<'ctx, 'a> ModuleGenerator<'ctx, 'a> {
         Ok(())
     }
 }
+
+/// 由同类型常量元素组成的 LLVM 常量数组
+fn const_array<'ctx>(
+    element_type: BasicTypeEnum<'ctx>,
+    values: &[BasicValueEnum<'ctx>],
+) -> CodegenResult<BasicValueEnum<'ctx>> {
+    let array = match element_type {
+        BasicTypeEnum::IntType(t) => {
+            let values: Vec<_> = values.iter().map(|v| v.into_int_value()).collect();
+            t.const_array(&values)
+        }
+        BasicTypeEnum::FloatType(t) => {
+            let values: Vec<_> = values.iter().map(|v| v.into_float_value()).collect();
+            t.const_array(&values)
+        }
+        BasicTypeEnum::PointerType(t) => {
+            let values: Vec<_> = values.iter().map(|v| v.into_pointer_value()).collect();
+            t.const_array(&values)
+        }
+        BasicTypeEnum::ArrayType(t) => {
+            let values: Vec<_> = values.iter().map(|v| v.into_array_value()).collect();
+            t.const_array(&values)
+        }
+        BasicTypeEnum::StructType(t) => {
+            let values: Vec<_> = values.iter().map(|v| v.into_struct_value()).collect();
+            t.const_array(&values)
+        }
+        BasicTypeEnum::VectorType(_) => return Err(CodegenError::TypeMismatch),
+    };
+    Ok(array.into())
+}
@@ -16,12 +16,17 @@ mod types;
 /// 模块代码生成器
 pub struct ModuleGenerator<'ctx, 'a> {
     pub(crate) ctx: &'a mut CodegenContext<'ctx>,
+    /// 初始值无法静态求值、需要由 __lency_init 在运行时赋值的全局变量
+    pub(crate) runtime_globals: std::collections::HashSet<String>,
 }
 
 impl<'ctx, 'a> ModuleGenerator<'ctx, 'a> {
     /// 创建模块生成器
     pub fn new(ctx: &'a mut CodegenContext<'ctx>) -> Self {
-        Self { ctx }
+        Self {
+            ctx,
+            runtime_globals: std::collections::HashSet::new(),
+        }
     }
 
     /// 生成整个程序
@@ -73,7 +78,10 @@ impl<'ctx, 'a> ModuleGenerator<'ctx, 'a> {
         // 7. 第二遍：生成函数体
         self.generate_function_bodies(program)?;
 
-        // 8. Generate main wrapper (entry point)
+        // 8. 全局变量运行时初始化 (__lency_init)
+        self.generate_global_initializers(program)?;
+
+        // 9. Generate main wrapper (entry point)
         self.generate_main_wrapper()?;
 
         Ok(())
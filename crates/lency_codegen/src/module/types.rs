@@ -125,13 +125,18 @@ impl<'ctx, 'a> ModuleGenerator<'ctx, 'a> {
                 let mut variants_info = Vec::new();
                 for variant in variants {
                     match variant {
-                        EnumVariant::Unit(v_name) => variants_info.push((v_name.clone(), vec![])),
+                        EnumVariant::Unit(v_name, _) => {
+                            variants_info.push((v_name.clone(), vec![]))
+                        }
                         EnumVariant::Tuple(v_name, types) => {
                             variants_info.push((v_name.clone(), types.clone()))
                         }
                     }
                 }
                 self.ctx.enum_variants.insert(name.clone(), variants_info);
+                self.ctx
+                    .enum_discriminants
+                    .insert(name.clone(), EnumVariant::discriminants(variants));
             }
         }
         Ok(())
@@ -144,6 +149,7 @@ impl<'ctx, 'a> ModuleGenerator<'ctx, 'a> {
                 name,
                 fields,
                 generic_params,
+                is_packed,
                 ..
             } = decl
             {
@@ -161,7 +167,8 @@ impl<'ctx, 'a> ModuleGenerator<'ctx, 'a> {
                     field_types.push(field.ty.to_llvm_type(&*self.ctx)?);
                 }
 
-                struct_type.set_body(&field_types, false);
+                // packed 结构体不插入对齐填充，其余按目标平台的自然对齐布局
+                struct_type.set_body(&field_types, *is_packed);
             }
         }
         Ok(())
@@ -187,7 +194,7 @@ impl<'ctx, 'a> ModuleGenerator<'ctx, 'a> {
 
                 for variant in variants {
                     match variant {
-                        EnumVariant::Unit(_) => {}
+                        EnumVariant::Unit(..) => {}
                         EnumVariant::Tuple(_, types) => {
                             // 计算 tuple struct 大小 (考虑对齐)
                             let mut field_types = Vec::new();
@@ -224,11 +231,11 @@ impl<'ctx, 'a> ModuleGenerator<'ctx, 'a> {
                 // fn Enum_Variant(fields...) -> Enum
                 for (tag_idx, variant) in variants.iter().enumerate() {
                     let (variant_name, field_types_ast) = match variant {
-                        EnumVariant::Unit(n) => (n, vec![]),
+                        EnumVariant::Unit(n, _) => (n, vec![]),
                         EnumVariant::Tuple(n, t) => (n, t.clone()),
                     };
 
-                    let ctor_name = format!("{}_{}", name, variant_name);
+                    let ctor_name = lency_monomorph::mangling::method_symbol(name, variant_name);
 
                     // Convert field types to LLVM
                     let mut llvm_param_types = Vec::new();
@@ -273,7 +280,7 @@ impl<'ctx, 'a> ModuleGenerator<'ctx, 'a> {
                         .builder
                         .build_struct_gep(enum_type, enum_ptr, 0, "tag_ptr")
                         .unwrap();
-                    let tag_val = self.ctx.context.i64_type().const_int(tag_idx as u64, false);
+                    let tag_val = self.ctx.enum_tag(name, tag_idx);
                     self.ctx.builder.build_store(tag_ptr, tag_val).unwrap();
 
                     // 3. Store Fields (if any)
@@ -324,8 +331,65 @@ impl<'ctx, 'a> ModuleGenerator<'ctx, 'a> {
                     // 4. Return Pointer (不再 load)
                     self.ctx.builder.build_return(Some(&enum_ptr)).unwrap();
                 }
+
+                // 纯 unit 枚举: 生成 Enum_from_int(i64) -> Enum?
+                let is_unit_enum = variants.iter().all(|v| matches!(v, EnumVariant::Unit(..)));
+                if is_unit_enum && variants.iter().all(|v| v.name() != "from_int") {
+                    self.gen_enum_from_int(name, variants)?;
+                }
             }
         }
         Ok(())
     }
+
+    /// 生成 `Enum_from_int`：判别值匹配某个变体时调用其构造函数，否则返回 null
+    fn gen_enum_from_int(&self, name: &str, variants: &[EnumVariant]) -> CodegenResult<()> {
+        let enum_type = *self.ctx.struct_types.get(name).unwrap();
+        let ret_ptr_type = enum_type.ptr_type(inkwell::AddressSpace::default());
+        let i64_type = self.ctx.context.i64_type();
+        let fn_type = ret_ptr_type.fn_type(&[i64_type.into()], false);
+        let function = self.ctx.module.add_function(
+            &lency_monomorph::mangling::method_symbol(name, "from_int"),
+            fn_type,
+            None,
+        );
+
+        let entry = self.ctx.context.append_basic_block(function, "entry");
+        let unknown = self.ctx.context.append_basic_block(function, "unknown");
+        let value = function.get_nth_param(0).unwrap().into_int_value();
+
+        let mut cases = Vec::new();
+        for (tag_idx, variant) in variants.iter().enumerate() {
+            let block = self
+                .ctx
+                .context
+                .append_basic_block(function, variant.name());
+            self.ctx.builder.position_at_end(block);
+            let ctor_name = lency_monomorph::mangling::method_symbol(name, variant.name());
+            let ctor = self.ctx.module.get_function(&ctor_name).unwrap();
+            let enum_val = self
+                .ctx
+                .builder
+                .build_call(ctor, &[], "variant")
+                .unwrap()
+                .try_as_basic_value()
+                .left()
+                .unwrap();
+            self.ctx.builder.build_return(Some(&enum_val)).unwrap();
+            cases.push((self.ctx.enum_tag(name, tag_idx), block));
+        }
+
+        self.ctx.builder.position_at_end(unknown);
+        self.ctx
+            .builder
+            .build_return(Some(&ret_ptr_type.const_null()))
+            .unwrap();
+
+        self.ctx.builder.position_at_end(entry);
+        self.ctx
+            .builder
+            .build_switch(value, unknown, &cases)
+            .unwrap();
+        Ok(())
+    }
 }
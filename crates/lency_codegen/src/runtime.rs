@@ -1,30 +1,50 @@
 use inkwell::context::Context;
 use inkwell::module::Module;
 
-use inkwell::values::{FunctionValue, PointerValue};
+use inkwell::values::{BasicMetadataValueEnum, FunctionValue, PointerValue};
 use inkwell::AddressSpace;
 use inkwell::IntPredicate;
 
 /// 注入运行时函数 (panic, printf, exit)
+///
+/// `source_path` 为源文件路径，panic 消息形如 `panic at file.lcy:12: <msg>`；
+/// 没有路径时退化为 `panic at line 12: <msg>`
 pub fn inject_runtime_functions<'ctx>(
     context: &'ctx Context,
     module: &Module<'ctx>,
+    source_path: Option<&str>,
+) -> FunctionValue<'ctx> {
+    define_panic_function(context, module, "__lency_panic", source_path)
+}
+
+/// 定义 panic 函数 `void <name>(i8* msg, i32 line)`，消息中报告 `source_path`
+///
+/// 导入模块中的函数使用各自的 panic 函数，行号与文件路径都按该模块的源文件报告
+pub fn define_panic_function<'ctx>(
+    context: &'ctx Context,
+    module: &Module<'ctx>,
+    name: &str,
+    source_path: Option<&str>,
 ) -> FunctionValue<'ctx> {
     // 1. Declare printf: i32 printf(i8*, ...)
     let i32_type = context.i32_type();
     let i8_ptr_type = context.i8_type().ptr_type(AddressSpace::default());
 
-    let printf_type = i32_type.fn_type(&[i8_ptr_type.into()], true);
-    let printf_func = module.add_function("printf", printf_type, None);
+    let printf_func = module.get_function("printf").unwrap_or_else(|| {
+        let printf_type = i32_type.fn_type(&[i8_ptr_type.into()], true);
+        module.add_function("printf", printf_type, None)
+    });
 
     // 2. Declare exit: void exit(i32)
     let void_type = context.void_type();
-    let exit_type = void_type.fn_type(&[i32_type.into()], false);
-    let exit_func = module.add_function("exit", exit_type, None);
+    let exit_func = module.get_function("exit").unwrap_or_else(|| {
+        let exit_type = void_type.fn_type(&[i32_type.into()], false);
+        module.add_function("exit", exit_type, None)
+    });
 
-    // 3. Define __lency_panic(msg: i8*, line: i32)
+    // 3. Define panic(msg: i8*, line: i32)
     let panic_type = void_type.fn_type(&[i8_ptr_type.into(), i32_type.into()], false);
-    let panic_func = module.add_function("__lency_panic", panic_type, None);
+    let panic_func = module.add_function(name, panic_type, None);
 
     // Create entry block for panic
     let builder = context.create_builder();
@@ -35,25 +55,25 @@ pub fn inject_runtime_functions<'ctx>(
     let msg = panic_func.get_nth_param(0).unwrap().into_pointer_value();
     let line = panic_func.get_nth_param(1).unwrap().into_int_value();
 
-    // Call printf("Panic at line %d: %s\n", line, msg)
-    // We need a format string global
-    let format_str = "Panic at line %d: %s\n";
+    // Call printf("panic at %s:%d: %s\n", file, line, msg)
+    // 路径作为 %s 参数传入，避免其中的 '%' 被当作格式符
+    let format_str = match source_path {
+        Some(_) => "panic at %s:%d: %s\n",
+        None => "panic at line %d: %s\n",
+    };
     let format_global = builder
         .build_global_string_ptr(format_str, "panic_fmt")
         .unwrap();
-
-    // printf args: format, line, msg
-    // Note: printf arg order in C is (fmt, ...). Here: fmt, line, msg.
+    let mut printf_args: Vec<BasicMetadataValueEnum> =
+        vec![format_global.as_pointer_value().into()];
+    if let Some(path) = source_path {
+        let file_global = builder.build_global_string_ptr(path, "panic_file").unwrap();
+        printf_args.push(file_global.as_pointer_value().into());
+    }
+    printf_args.push(line.into());
+    printf_args.push(msg.into());
     builder
-        .build_call(
-            printf_func,
-            &[
-                format_global.as_pointer_value().into(),
-                line.into(),
-                msg.into(),
-            ],
-            "call_printf",
-        )
+        .build_call(printf_func, &printf_args, "call_printf")
         .unwrap();
 
     // Call exit(1)
@@ -159,6 +179,30 @@ pub fn gen_bounds_check<'ctx>(
     builder.position_at_end(cont_block);
 }
 
+/// 条件成立时 panic，否则继续执行
+pub fn gen_panic_if<'ctx>(
+    context: &'ctx Context,
+    builder: &inkwell::builder::Builder<'ctx>,
+    panic_func: FunctionValue<'ctx>,
+    condition: inkwell::values::IntValue<'ctx>,
+    msg: &str,
+    line: u32,
+) {
+    let current_block = builder.get_insert_block().unwrap();
+    let function = current_block.get_parent().unwrap();
+    let panic_block = context.append_basic_block(function, "check_panic");
+    let cont_block = context.append_basic_block(function, "check_cont");
+
+    builder
+        .build_conditional_branch(condition, panic_block, cont_block)
+        .unwrap();
+
+    builder.position_at_end(panic_block);
+    gen_panic(context, builder, panic_func, msg, line);
+
+    builder.position_at_end(cont_block);
+}
+
 /// 生成 panic 调用
 pub fn gen_panic<'ctx>(
     context: &'ctx Context,
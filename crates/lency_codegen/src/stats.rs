@@ -0,0 +1,60 @@
+//! Codegen Statistics
+//!
+//! 代码生成统计：遍历生成后的 LLVM Module，统计函数、基本块、指令数量与 IR 大小（`--stats`）
+
+use std::fmt;
+
+use inkwell::module::Module;
+
+/// 生成后模块的规模统计，便于追踪代码膨胀
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ModuleStats {
+    /// 有函数体的函数（含运行时辅助函数与 main 包装）
+    pub functions: usize,
+    /// 只有声明的外部函数（printf、malloc 等）
+    pub declarations: usize,
+    pub basic_blocks: usize,
+    pub instructions: usize,
+    /// 文本 IR 的字节数
+    pub ir_bytes: usize,
+}
+
+impl ModuleStats {
+    /// 统计 `module`，`ir` 为其文本形式
+    pub fn collect(module: &Module<'_>, ir: &str) -> Self {
+        let mut stats = Self {
+            ir_bytes: ir.len(),
+            ..Self::default()
+        };
+        for function in module.get_functions() {
+            let blocks = function.get_basic_blocks();
+            if blocks.is_empty() {
+                stats.declarations += 1;
+                continue;
+            }
+            stats.functions += 1;
+            stats.basic_blocks += blocks.len();
+            for block in blocks {
+                let mut instruction = block.get_first_instruction();
+                while let Some(inst) = instruction {
+                    stats.instructions += 1;
+                    instruction = inst.get_next_instruction();
+                }
+            }
+        }
+        stats
+    }
+}
+
+impl fmt::Display for ModuleStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "functions:    {} defined, {} declared",
+            self.functions, self.declarations
+        )?;
+        writeln!(f, "basic blocks: {}", self.basic_blocks)?;
+        writeln!(f, "instructions: {}", self.instructions)?;
+        write!(f, "module size:  {} bytes", self.ir_bytes)
+    }
+}
@@ -1,16 +1,28 @@
 use crate::error::{CodegenError, CodegenResult};
 use crate::expr::ExprGenerator;
-use lency_syntax::ast::{Expr, Stmt};
+use crate::types::ToLLVMType;
+use lency_syntax::ast::{Expr, MatchPattern, Stmt, Type};
 
 use crate::stmt::StmtGenerator;
 
 /// 生成 if 语句
+///
+/// 条件在编译期恒为真或恒为假时只生成会执行的分支
 pub fn gen_if<'ctx, 'a>(
     gen: &mut StmtGenerator<'ctx, 'a>,
     condition: &Expr,
     then_block: &[Stmt],
     else_block: Option<&[Stmt]>,
 ) -> CodegenResult<()> {
+    if let Some(value) = condition.const_bool() {
+        let taken = if value {
+            then_block
+        } else {
+            else_block.unwrap_or_default()
+        };
+        return gen_taken_branch(gen, taken);
+    }
+
     // 生成条件
     let expr_gen = ExprGenerator::new(gen.ctx, gen.locals);
     let cond_wrapper = expr_gen.generate(condition)?;
@@ -66,3 +78,143 @@ pub fn gen_if<'ctx, 'a>(
 
     Ok(())
 }
+
+/// 在当前基本块中直接生成唯一会执行的分支
+///
+/// 分支以 return/break 等结束时，后续语句放进一个无前驱的新块
+fn gen_taken_branch<'ctx, 'a>(
+    gen: &mut StmtGenerator<'ctx, 'a>,
+    stmts: &[Stmt],
+) -> CodegenResult<()> {
+    gen.generate_block(stmts)?;
+
+    let current = gen
+        .ctx
+        .builder
+        .get_insert_block()
+        .ok_or_else(|| CodegenError::LLVMBuildError("not in a function".to_string()))?;
+    if gen.block_ends_with_terminator(current) {
+        let function = current
+            .get_parent()
+            .ok_or_else(|| CodegenError::LLVMBuildError("not in a function".to_string()))?;
+        let merge_bb = gen.ctx.context.append_basic_block(function, "ifcont");
+        gen.ctx.builder.position_at_end(merge_bb);
+    }
+
+    Ok(())
+}
+
+/// 生成 if let 语句
+///
+/// - `T?`：非空时把解包后的值绑定到模式变量
+/// - 枚举：复用 match 的模式检查，匹配成功时绑定变体字段
+pub fn gen_if_let<'ctx, 'a>(
+    gen: &mut StmtGenerator<'ctx, 'a>,
+    pattern: &MatchPattern,
+    value: &Expr,
+    then_block: &[Stmt],
+    else_block: Option<&[Stmt]>,
+) -> CodegenResult<()> {
+    let expr_gen = ExprGenerator::new(gen.ctx, gen.locals);
+    let subject = expr_gen.generate(value)?;
+
+    let function = gen
+        .ctx
+        .builder
+        .get_insert_block()
+        .and_then(|bb| bb.get_parent())
+        .ok_or_else(|| CodegenError::LLVMBuildError("not in a function".to_string()))?;
+
+    let then_bb = gen.ctx.context.append_basic_block(function, "iflet_then");
+    let else_bb = gen.ctx.context.append_basic_block(function, "iflet_else");
+    let merge_bb = gen.ctx.context.append_basic_block(function, "iflet_cont");
+
+    let mut bindings = Vec::new();
+    if let Type::Nullable(inner) = &subject.ty {
+        let ptr = subject.value.into_pointer_value();
+        let is_null = gen
+            .ctx
+            .builder
+            .build_is_null(ptr, "iflet_is_null")
+            .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+        gen.ctx
+            .builder
+            .build_conditional_branch(is_null, else_bb, then_bb)
+            .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+        gen.ctx.builder.position_at_end(then_bb);
+
+        if let MatchPattern::Variable(name) = pattern {
+            // 指针类型（string、结构体等）本身即值，存入新槽位；
+            // 其余类型的可空值就是指向值的指针，可直接作为变量地址
+            let binding_ptr = if inner.to_llvm_type(gen.ctx)?.is_pointer_type() {
+                let val = crate::expr::coerce_pointer(gen.ctx, subject.value, inner)?;
+                let alloca = gen
+                    .ctx
+                    .builder
+                    .build_alloca(val.get_type(), name)
+                    .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+                gen.ctx
+                    .builder
+                    .build_store(alloca, val)
+                    .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+                alloca
+            } else {
+                ptr
+            };
+            bindings.push((name.clone(), binding_ptr, (**inner).clone()));
+        }
+    } else {
+        let subject_ptr = subject.value.into_pointer_value();
+        crate::expr::gen_pattern_check(
+            gen.ctx,
+            gen.locals,
+            pattern,
+            subject_ptr,
+            &subject.ty,
+            &mut bindings,
+            else_bb,
+        )?;
+        gen.ctx
+            .builder
+            .build_unconditional_branch(then_bb)
+            .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+        gen.ctx.builder.position_at_end(then_bb);
+    }
+
+    // Then 分支：绑定只在块内可见，结束后恢复被遮蔽的同名变量
+    let mut shadowed = Vec::new();
+    for (name, ptr, ty) in bindings {
+        shadowed.push((name.clone(), gen.locals.insert(name, (ptr, ty))));
+    }
+    gen.generate_block(then_block)?;
+    for (name, prev) in shadowed {
+        match prev {
+            Some(entry) => gen.locals.insert(name, entry),
+            None => gen.locals.remove(&name),
+        };
+    }
+    let current_then = gen.ctx.builder.get_insert_block().unwrap();
+    if !gen.block_ends_with_terminator(current_then) {
+        gen.ctx
+            .builder
+            .build_unconditional_branch(merge_bb)
+            .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+    }
+
+    // Else 分支
+    gen.ctx.builder.position_at_end(else_bb);
+    if let Some(else_stmts) = else_block {
+        gen.generate_block(else_stmts)?;
+    }
+    let current_else = gen.ctx.builder.get_insert_block().unwrap();
+    if !gen.block_ends_with_terminator(current_else) {
+        gen.ctx
+            .builder
+            .build_unconditional_branch(merge_bb)
+            .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+    }
+
+    gen.ctx.builder.position_at_end(merge_bb);
+
+    Ok(())
+}
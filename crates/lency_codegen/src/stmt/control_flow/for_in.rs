@@ -1,16 +1,20 @@
 use crate::error::{CodegenError, CodegenResult};
 use crate::expr::ExprGenerator;
-use lency_syntax::ast::{Expr, Stmt};
+use lency_syntax::ast::{Expr, Stmt, Type};
 
-use crate::stmt::{LoopContext, StmtGenerator};
+use crate::stmt::{BreakValues, LoopContext, StmtGenerator};
+use inkwell::basic_block::BasicBlock;
+use inkwell::types::ArrayType;
+use inkwell::values::{BasicValueEnum, IntValue, PointerValue};
 
-/// 生成 for-in 循环
+/// 生成 for-in 循环（`value_exit` 同 [`super::gen_while`]）
 pub fn gen_for_in<'ctx, 'a>(
     gen: &mut StmtGenerator<'ctx, 'a>,
     iterator: &str,
     iterable: &Expr,
     body: &[Stmt],
-) -> CodegenResult<()> {
+    value_exit: Option<BasicBlock<'ctx>>,
+) -> CodegenResult<BreakValues<'ctx>> {
     // 获取当前函数
     let function = gen
         .ctx
@@ -21,30 +25,55 @@ pub fn gen_for_in<'ctx, 'a>(
 
     // 1. Evaluate iterable
     let expr_gen = ExprGenerator::new(gen.ctx, gen.locals);
-    let array_val = expr_gen.generate(iterable)?;
-
-    // Safety check: must be array type (Sema ensured this)
-    if !array_val.value.is_array_value() {
-        return Err(CodegenError::LLVMBuildError(
-            "For-in iterable must be an array".to_string(),
-        ));
-    }
-    let array_type = array_val.value.get_type().into_array_type();
-    let size = array_type.len() as u64;
+    let iterable_val = expr_gen.generate(iterable)?;
+    let i64_type = gen.ctx.context.i64_type();
 
-    // Store array temporary on stack (to allow GEP)
-    let array_alloca = gen
-        .ctx
-        .builder
-        .build_alloca(array_type, "for_arr_temp")
-        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
-    gen.ctx
-        .builder
-        .build_store(array_alloca, array_val.value)
-        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+    // 元素个数在进入循环前确定：数组是编译期长度，Vec 只调用一次 lency_vec_len
+    let (source, len, elem_ty) = match iterable_val.ty {
+        Type::Array { element_type, .. } if iterable_val.value.is_array_value() => {
+            let array_type = iterable_val.value.get_type().into_array_type();
+            // Store array temporary on stack (to allow GEP)
+            let array_alloca = gen
+                .ctx
+                .builder
+                .build_alloca(array_type, "for_arr_temp")
+                .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+            gen.ctx
+                .builder
+                .build_store(array_alloca, iterable_val.value)
+                .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+            let len = i64_type.const_int(array_type.len() as u64, false);
+            (
+                IterSource::Array(array_alloca, array_type),
+                len,
+                *element_type,
+            )
+        }
+        Type::Vec(element_type) => {
+            let vec_ptr = iterable_val.value.into_pointer_value();
+            let len_fn = crate::expr::vec::get_or_declare_vec_len(gen.ctx)?;
+            let len = gen
+                .ctx
+                .builder
+                .build_call(len_fn, &[vec_ptr.into()], "for_vec_len")
+                .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?
+                .try_as_basic_value()
+                .left()
+                .ok_or_else(|| {
+                    CodegenError::LLVMBuildError("lency_vec_len returned void".to_string())
+                })?
+                .into_int_value();
+            (IterSource::Vec(vec_ptr), len, *element_type)
+        }
+        // Sema ensured this
+        _ => {
+            return Err(CodegenError::LLVMBuildError(
+                "For-in iterable must be an array or Vec".to_string(),
+            ))
+        }
+    };
 
     // 2. Index variable (alloca)
-    let i64_type = gen.ctx.context.i64_type();
     let idx_alloca = gen
         .ctx
         .builder
@@ -77,12 +106,7 @@ pub fn gen_for_in<'ctx, 'a>(
     let cond = gen
         .ctx
         .builder
-        .build_int_compare(
-            inkwell::IntPredicate::SLT,
-            curr_idx,
-            i64_type.const_int(size, false),
-            "loop_cond",
-        )
+        .build_int_compare(inkwell::IntPredicate::SLT, curr_idx, len, "loop_cond")
         .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
     gen.ctx
         .builder
@@ -93,24 +117,13 @@ pub fn gen_for_in<'ctx, 'a>(
     gen.ctx.builder.position_at_end(body_bb);
 
     // Load element
-    let zero = i64_type.const_int(0, false);
-    let elem_ptr = unsafe {
-        gen.ctx
-            .builder
-            .build_gep(array_type, array_alloca, &[zero, curr_idx], "elem_ptr")
-            .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?
-    };
-    let elem_val = gen
-        .ctx
-        .builder
-        .build_load(array_type.get_element_type(), elem_ptr, "elem_val")
-        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+    let elem_val = source.load(gen, curr_idx, &elem_ty)?;
 
     // Create iterator variable local
     let iter_alloca = gen
         .ctx
         .builder
-        .build_alloca(array_type.get_element_type(), iterator)
+        .build_alloca(elem_val.get_type(), iterator)
         .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
     gen.ctx
         .builder
@@ -118,11 +131,6 @@ pub fn gen_for_in<'ctx, 'a>(
         .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
 
     // Add to locals (Handle shadowing)
-    let elem_ty = match array_val.ty {
-        lency_syntax::ast::Type::Array { element_type, .. } => *element_type,
-        _ => return Err(CodegenError::TypeMismatch),
-    };
-
     let old_local = gen
         .locals
         .insert(iterator.to_string(), (iter_alloca, elem_ty));
@@ -131,12 +139,14 @@ pub fn gen_for_in<'ctx, 'a>(
     gen.loop_stack.push(LoopContext {
         continue_block: inc_bb,
         break_block: after_bb,
+        value_exit,
+        break_values: Vec::new(),
     });
 
     // Generate Body
     gen.generate_block(body)?;
 
-    gen.loop_stack.pop();
+    let loop_ctx = gen.loop_stack.pop().unwrap();
 
     // Restore locals
     if let Some(old) = old_local {
@@ -172,5 +182,53 @@ pub fn gen_for_in<'ctx, 'a>(
     // 7. End
     gen.ctx.builder.position_at_end(after_bb);
 
-    Ok(())
+    Ok(loop_ctx.break_values)
+}
+
+/// for-in 遍历的对象
+enum IterSource<'ctx> {
+    /// 栈上的数组副本
+    Array(PointerValue<'ctx>, ArrayType<'ctx>),
+    /// 运行时 Vec，元素以 i64 存储
+    Vec(PointerValue<'ctx>),
+}
+
+impl<'ctx> IterSource<'ctx> {
+    /// 读取第 `idx` 个元素（按值）
+    fn load(
+        &self,
+        gen: &StmtGenerator<'ctx, '_>,
+        idx: IntValue<'ctx>,
+        elem_ty: &Type,
+    ) -> CodegenResult<BasicValueEnum<'ctx>> {
+        match self {
+            IterSource::Array(array_alloca, array_type) => {
+                let zero = gen.ctx.context.i64_type().const_int(0, false);
+                let elem_ptr = unsafe {
+                    gen.ctx
+                        .builder
+                        .build_gep(*array_type, *array_alloca, &[zero, idx], "elem_ptr")
+                        .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?
+                };
+                gen.ctx
+                    .builder
+                    .build_load(array_type.get_element_type(), elem_ptr, "elem_val")
+                    .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))
+            }
+            IterSource::Vec(vec_ptr) => {
+                let get_fn = crate::expr::vec::get_or_declare_vec_get(gen.ctx)?;
+                let raw = gen
+                    .ctx
+                    .builder
+                    .build_call(get_fn, &[(*vec_ptr).into(), idx.into()], "elem_raw")
+                    .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?
+                    .try_as_basic_value()
+                    .left()
+                    .ok_or_else(|| {
+                        CodegenError::LLVMBuildError("lency_vec_get returned void".to_string())
+                    })?;
+                crate::expr::vec::cast_from_i64(gen.ctx, raw.into_int_value(), elem_ty)
+            }
+        }
+    }
 }
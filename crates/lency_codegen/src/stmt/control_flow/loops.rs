@@ -2,14 +2,18 @@ use crate::error::{CodegenError, CodegenResult};
 use crate::expr::ExprGenerator;
 use lency_syntax::ast::{Expr, Stmt};
 
-use crate::stmt::{LoopContext, StmtGenerator};
+use crate::stmt::{BreakValues, LoopContext, StmtGenerator};
+use inkwell::basic_block::BasicBlock;
 
 /// 生成 while 循环
+///
+/// `value_exit` 为 Some 时循环作为值使用，返回各 break value 出口
 pub fn gen_while<'ctx, 'a>(
     gen: &mut StmtGenerator<'ctx, 'a>,
     condition: &Expr,
     body: &[Stmt],
-) -> CodegenResult<()> {
+    value_exit: Option<BasicBlock<'ctx>>,
+) -> CodegenResult<BreakValues<'ctx>> {
     // 获取当前函数
     let function = gen
         .ctx
@@ -46,12 +50,14 @@ pub fn gen_while<'ctx, 'a>(
     gen.loop_stack.push(LoopContext {
         continue_block: cond_bb,
         break_block: after_bb,
+        value_exit,
+        break_values: Vec::new(),
     });
 
     gen.generate_block(body)?;
 
     // Pop loop context
-    gen.loop_stack.pop();
+    let loop_ctx = gen.loop_stack.pop().unwrap();
 
     let current_body = gen.ctx.builder.get_insert_block().unwrap();
     if !gen.block_ends_with_terminator(current_body) {
@@ -64,17 +70,18 @@ pub fn gen_while<'ctx, 'a>(
     // 循环后
     gen.ctx.builder.position_at_end(after_bb);
 
-    Ok(())
+    Ok(loop_ctx.break_values)
 }
 
-/// 生成 for 循环
+/// 生成 for 循环（`value_exit` 同 [`gen_while`]）
 pub fn gen_for<'ctx, 'a>(
     gen: &mut StmtGenerator<'ctx, 'a>,
     init: Option<&Stmt>,
     condition: Option<&Expr>,
     update: Option<&Stmt>,
     body: &[Stmt],
-) -> CodegenResult<()> {
+    value_exit: Option<BasicBlock<'ctx>>,
+) -> CodegenResult<BreakValues<'ctx>> {
     // 获取当前函数
     let function = gen
         .ctx
@@ -125,12 +132,14 @@ pub fn gen_for<'ctx, 'a>(
     gen.loop_stack.push(LoopContext {
         continue_block: inc_bb,
         break_block: after_bb,
+        value_exit,
+        break_values: Vec::new(),
     });
 
     gen.generate_block(body)?;
 
     // Pop loop context
-    gen.loop_stack.pop();
+    let loop_ctx = gen.loop_stack.pop().unwrap();
 
     let current_body = gen.ctx.builder.get_insert_block().unwrap();
     if !gen.block_ends_with_terminator(current_body) {
@@ -153,20 +162,36 @@ pub fn gen_for<'ctx, 'a>(
     // 7. 循环结束块
     gen.ctx.builder.position_at_end(after_bb);
 
-    Ok(())
+    Ok(loop_ctx.break_values)
 }
 
 /// 生成 break 语句
-pub fn gen_break(gen: &mut StmtGenerator) -> CodegenResult<()> {
+///
+/// 作为值的循环中，先求 break 的值并记录来源块，再跳到循环的值汇合块
+pub fn gen_break(gen: &mut StmtGenerator, value: Option<&Expr>) -> CodegenResult<()> {
     let context = gen.loop_stack.last().ok_or_else(|| {
         CodegenError::LLVMBuildError(
             "break statement outside loop (should be caught by sema)".to_string(),
         )
     })?;
+    let (break_block, value_exit) = (context.break_block, context.value_exit);
+
+    let target = match (value, value_exit) {
+        (Some(expr), Some(value_exit)) => {
+            let expr_gen = ExprGenerator::new(gen.ctx, gen.locals);
+            let val = expr_gen.generate(expr)?;
+            let from_bb = gen.ctx.builder.get_insert_block().unwrap();
+            if let Some(context) = gen.loop_stack.last_mut() {
+                context.break_values.push((val.value, from_bb));
+            }
+            value_exit
+        }
+        _ => break_block,
+    };
 
     gen.ctx
         .builder
-        .build_unconditional_branch(context.break_block)
+        .build_unconditional_branch(target)
         .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
 
     Ok(())
@@ -2,6 +2,6 @@ pub mod conditional;
 pub mod for_in;
 pub mod loops;
 
-pub use conditional::gen_if;
+pub use conditional::{gen_if, gen_if_let};
 pub use for_in::gen_for_in;
 pub use loops::{gen_break, gen_continue, gen_for, gen_while};
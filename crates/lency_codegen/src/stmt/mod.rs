@@ -4,8 +4,8 @@
 
 mod control_flow;
 
-use inkwell::values::PointerValue;
-use lency_syntax::ast::Expr;
+use inkwell::values::{BasicValueEnum, PointerValue};
+use lency_syntax::ast::{Expr, ExprKind};
 use lency_syntax::ast::{Stmt, Type};
 use std::collections::HashMap;
 
@@ -17,10 +17,16 @@ use inkwell::types::BasicType;
 
 use inkwell::basic_block::BasicBlock;
 
+/// 作为值的循环中各 break 出口的 (值, 来源块)，用于结束处的 phi
+pub type BreakValues<'ctx> = Vec<(BasicValueEnum<'ctx>, BasicBlock<'ctx>)>;
+
 /// 循环上下文，用于 break/continue 跳转
 pub struct LoopContext<'ctx> {
     pub continue_block: BasicBlock<'ctx>,
     pub break_block: BasicBlock<'ctx>,
+    /// 作为值的循环：break value 跳到此块（而不是 break_block）
+    pub value_exit: Option<BasicBlock<'ctx>>,
+    pub break_values: BreakValues<'ctx>,
 }
 
 /// 语句代码生成器
@@ -55,6 +61,13 @@ impl<'ctx, 'a> StmtGenerator<'ctx, 'a> {
             Stmt::VarDecl {
                 name, ty, value, ..
             } => self.gen_var_decl(name, ty.as_ref(), value),
+            Stmt::LoopVarDecl {
+                name,
+                ty,
+                loop_stmt,
+                default,
+                ..
+            } => self.gen_loop_var_decl(name, ty.as_ref(), loop_stmt, default),
             Stmt::Assignment { target, value, .. } => self.gen_assignment(target, value),
             Stmt::Return { value, .. } => self.gen_return(value.as_ref()),
             Stmt::If {
@@ -63,9 +76,16 @@ impl<'ctx, 'a> StmtGenerator<'ctx, 'a> {
                 else_block,
                 ..
             } => control_flow::gen_if(self, condition, then_block, else_block.as_deref()),
+            Stmt::IfLet {
+                pattern,
+                value,
+                then_block,
+                else_block,
+                ..
+            } => control_flow::gen_if_let(self, pattern, value, then_block, else_block.as_deref()),
             Stmt::While {
                 condition, body, ..
-            } => control_flow::gen_while(self, condition, body),
+            } => control_flow::gen_while(self, condition, body, None).map(|_| ()),
             Stmt::For {
                 init,
                 condition,
@@ -78,8 +98,10 @@ impl<'ctx, 'a> StmtGenerator<'ctx, 'a> {
                 condition.as_ref(),
                 update.as_deref(),
                 body,
-            ),
-            Stmt::Break { .. } => control_flow::gen_break(self),
+                None,
+            )
+            .map(|_| ()),
+            Stmt::Break { value, .. } => control_flow::gen_break(self, value.as_ref()),
             Stmt::Continue { .. } => control_flow::gen_continue(self),
             Stmt::Expression(expr) => {
                 let expr_gen = ExprGenerator::new(self.ctx, self.locals);
@@ -91,16 +113,21 @@ impl<'ctx, 'a> StmtGenerator<'ctx, 'a> {
                 iterable,
                 body,
                 ..
-            } => control_flow::gen_for_in(self, iterator, iterable, body),
+            } => control_flow::gen_for_in(self, iterator, iterable, body, None).map(|_| ()),
             Stmt::Block(stmts) => self.generate_block(stmts),
         }
     }
 
     /// 生成代码块
+    ///
+    /// 块内声明的变量只在块内可见：结束时恢复进入前的局部变量表，
+    /// 使嵌套作用域中遮蔽的同名变量不会覆盖外层变量
     pub fn generate_block(&mut self, stmts: &[Stmt]) -> CodegenResult<()> {
+        let outer_locals = self.locals.clone();
         for stmt in stmts {
             self.generate(stmt)?;
         }
+        *self.locals = outer_locals;
         Ok(())
     }
 
@@ -113,7 +140,6 @@ impl<'ctx, 'a> StmtGenerator<'ctx, 'a> {
     ) -> CodegenResult<()> {
         let expr_gen = ExprGenerator::new(self.ctx, self.locals);
         let val_wrapper = expr_gen.generate(value)?;
-        let val = val_wrapper.value;
 
         // 若有显式类型声明，优先使用（这里假定类型检查已通过）
         // 若无，使用推导类型
@@ -122,6 +148,8 @@ impl<'ctx, 'a> StmtGenerator<'ctx, 'a> {
         } else {
             val_wrapper.ty.clone()
         };
+        let val = crate::expr::coerce_pointer(self.ctx, val_wrapper.value, &var_ty)?;
+        let val = crate::expr::widen_int(self.ctx, val, &var_ty)?;
 
         // 分配栈空间
         let alloca = self
@@ -142,15 +170,107 @@ impl<'ctx, 'a> StmtGenerator<'ctx, 'a> {
         Ok(())
     }
 
+    /// 生成以循环的值初始化的变量
+    ///
+    /// 各 `break value` 直接跳到 loop.value 块，循环正常结束时在结束块中求默认值，
+    /// 两类出口在 loop.value 中由 phi 汇合后存入变量
+    fn gen_loop_var_decl(
+        &mut self,
+        name: &str,
+        declared_ty: Option<&Type>,
+        loop_stmt: &Stmt,
+        default: &Expr,
+    ) -> CodegenResult<()> {
+        let function = self
+            .ctx
+            .builder
+            .get_insert_block()
+            .and_then(|bb| bb.get_parent())
+            .ok_or_else(|| CodegenError::LLVMBuildError("not in a function".to_string()))?;
+        let value_bb = self.ctx.context.append_basic_block(function, "loop.value");
+
+        let mut incoming = match loop_stmt {
+            Stmt::While {
+                condition, body, ..
+            } => control_flow::gen_while(self, condition, body, Some(value_bb))?,
+            Stmt::For {
+                init,
+                condition,
+                update,
+                body,
+                ..
+            } => control_flow::gen_for(
+                self,
+                init.as_deref(),
+                condition.as_ref(),
+                update.as_deref(),
+                body,
+                Some(value_bb),
+            )?,
+            Stmt::ForIn {
+                iterator,
+                iterable,
+                body,
+                ..
+            } => control_flow::gen_for_in(self, iterator, iterable, body, Some(value_bb))?,
+            _ => {
+                return Err(CodegenError::UnsupportedFeature(
+                    "loop value must come from while/for".to_string(),
+                ))
+            }
+        };
+
+        // 循环正常结束：求默认值
+        let expr_gen = ExprGenerator::new(self.ctx, self.locals);
+        let default_val = expr_gen.generate(default)?;
+        let default_bb = self.ctx.builder.get_insert_block().unwrap();
+        self.ctx
+            .builder
+            .build_unconditional_branch(value_bb)
+            .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+        incoming.push((default_val.value, default_bb));
+
+        self.ctx.builder.position_at_end(value_bb);
+        let phi = self
+            .ctx
+            .builder
+            .build_phi(default_val.value.get_type(), "loop_value")
+            .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+        for (val, bb) in &incoming {
+            phi.add_incoming(&[(val, *bb)]);
+        }
+
+        let var_ty = declared_ty.cloned().unwrap_or(default_val.ty);
+        let alloca = self
+            .ctx
+            .builder
+            .build_alloca(phi.as_basic_value().get_type(), name)
+            .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+        self.ctx
+            .builder
+            .build_store(alloca, phi.as_basic_value())
+            .map_err(|e| CodegenError::LLVMBuildError(e.to_string()))?;
+        self.locals.insert(name.to_string(), (alloca, var_ty));
+
+        Ok(())
+    }
+
     /// 生成赋值语句
     fn gen_assignment(&mut self, target: &Expr, value: &Expr) -> CodegenResult<()> {
-        // 生成目标地址（LValue）
         let expr_gen = ExprGenerator::new(self.ctx, self.locals);
-        let (ptr, _ty) = expr_gen.generate_lvalue_addr(target)?;
+
+        // 索引赋值：Vec 元素没有 LLVM 地址，需单独处理
+        if let ExprKind::Index { array, index } = &target.kind {
+            return expr_gen.generate_index_assignment(array, index, value);
+        }
+
+        // 生成目标地址（LValue）
+        let (ptr, target_ty) = expr_gen.generate_lvalue_addr(target)?;
 
         // 生成值
         let val_wrapper = expr_gen.generate(value)?;
-        let val = val_wrapper.value;
+        let val = crate::expr::coerce_pointer(self.ctx, val_wrapper.value, &target_ty)?;
+        let val = crate::expr::widen_int(self.ctx, val, &target_ty)?;
 
         // 存储
         self.ctx
@@ -278,6 +398,8 @@ impl<'ctx, 'a> StmtGenerator<'ctx, 'a> {
                 val_wrapper.value
             };
 
+            let final_val = crate::expr::coerce_pointer(self.ctx, final_val, self.return_type)?;
+            let final_val = crate::expr::widen_int(self.ctx, final_val, self.return_type)?;
             self.ctx
                 .builder
                 .build_return(Some(&final_val))
@@ -4,7 +4,7 @@
 
 use crate::context::CodegenContext;
 use crate::error::{CodegenError, CodegenResult};
-use inkwell::types::{BasicType, BasicTypeEnum};
+use inkwell::types::{BasicType, BasicTypeEnum, StructType};
 use inkwell::AddressSpace;
 use lency_syntax::ast::Type;
 
@@ -18,7 +18,10 @@ impl<'ctx> ToLLVMType<'ctx> for Type {
     fn to_llvm_type(&self, context: &CodegenContext<'ctx>) -> CodegenResult<BasicTypeEnum<'ctx>> {
         match self {
             // 基础类型映射
-            Type::Int => Ok(context.context.i64_type().as_basic_type_enum()),
+            Type::Int | Type::UInt => Ok(context.context.i64_type().as_basic_type_enum()),
+            Type::I8 => Ok(context.context.i8_type().as_basic_type_enum()),
+            Type::I16 => Ok(context.context.i16_type().as_basic_type_enum()),
+            Type::I32 => Ok(context.context.i32_type().as_basic_type_enum()),
             Type::Float => Ok(context.context.f64_type().as_basic_type_enum()),
             Type::Bool => Ok(context.context.bool_type().as_basic_type_enum()),
 
@@ -34,9 +37,13 @@ impl<'ctx> ToLLVMType<'ctx> for Type {
                 "void is not a basic type".to_string(),
             )),
 
-            // 可空类型用指针表示
+            // 可空类型用指针表示：本身就是指针的类型（string、结构体等）直接复用，
+            // null 即空指针；其余类型使用指向值的指针
             Type::Nullable(inner) => {
                 let inner_type = inner.to_llvm_type(context)?;
+                if inner_type.is_pointer_type() {
+                    return Ok(inner_type);
+                }
                 Ok(inner_type
                     .ptr_type(AddressSpace::default())
                     .as_basic_type_enum())
@@ -66,6 +73,12 @@ impl<'ctx> ToLLVMType<'ctx> for Type {
                 .ptr_type(AddressSpace::default())
                 .as_basic_type_enum()),
 
+            // [N]T：名称解析阶段已替换为具体长度
+            Type::ConstSizedArray { size_name, .. } => Err(CodegenError::UnsupportedType(format!(
+                "unresolved array size '{}'",
+                size_name
+            ))),
+
             // 泛型参数: T, U
             // 在单态化之前不应该遇到这种类型
             Type::GenericParam(_) => Err(CodegenError::UnsupportedType(
@@ -147,14 +160,49 @@ impl<'ctx> ToLLVMType<'ctx> for Type {
                     .as_basic_type_enum())
             }
 
+            // newtype 与底层类型共用同一 LLVM 表示
+            Type::Newtype(_, inner) => inner.to_llvm_type(context),
+
+            // 匿名记录: { int x, int y } -> { i64, i64 }* (匿名 LLVM 结构体，通过指针传递)
+            Type::Record(fields) => Ok(record_struct_type(context, fields)?
+                .ptr_type(AddressSpace::default())
+                .as_basic_type_enum()),
+
+            // Trait 对象: dyn T -> { i8*, i8* }* (数据指针 + 虚表指针的胖指针，堆上分配后按指针传递)
+            Type::TraitObject(_) => Ok(trait_object_struct_type(context)
+                .ptr_type(AddressSpace::default())
+                .as_basic_type_enum()),
+
+            // Resolver 已把 This 替换为具体类型，不会到达代码生成
+            Type::SelfType => Err(CodegenError::UnsupportedType("This".to_string())),
             Type::Error => Err(CodegenError::UnsupportedType("error type".to_string())),
         }
     }
 }
 
-/// 检查类型是否为整数类型
+/// 记录类型对应的匿名 LLVM 结构体（字段顺序即 Type::Record 中已排序的顺序）
+pub fn record_struct_type<'ctx>(
+    context: &CodegenContext<'ctx>,
+    fields: &[(String, Type)],
+) -> CodegenResult<StructType<'ctx>> {
+    let field_types = fields
+        .iter()
+        .map(|(_, ty)| ty.to_llvm_type(context))
+        .collect::<CodegenResult<Vec<_>>>()?;
+    Ok(context.context.struct_type(&field_types, false))
+}
+
+/// Trait 对象胖指针的布局：{ 数据指针, 虚表指针 }
+pub fn trait_object_struct_type<'ctx>(context: &CodegenContext<'ctx>) -> StructType<'ctx> {
+    let i8_ptr = context.context.i8_type().ptr_type(AddressSpace::default());
+    context
+        .context
+        .struct_type(&[i8_ptr.into(), i8_ptr.into()], false)
+}
+
+/// 检查类型是否为整数类型（任意宽度）
 pub fn is_int_type(ty: &Type) -> bool {
-    matches!(ty, Type::Int)
+    ty.int_bits().is_some()
 }
 
 /// 检查类型是否为浮点类型
@@ -190,6 +238,10 @@ mod tests {
 
         // 测试基础类型
         assert!(Type::Int.to_llvm_type(&codegen_ctx).is_ok());
+        assert_eq!(
+            Type::I32.to_llvm_type(&codegen_ctx).unwrap(),
+            context.i32_type().as_basic_type_enum()
+        );
         assert!(Type::Float.to_llvm_type(&codegen_ctx).is_ok());
         assert!(Type::Bool.to_llvm_type(&codegen_ctx).is_ok());
         assert!(Type::String.to_llvm_type(&codegen_ctx).is_ok());
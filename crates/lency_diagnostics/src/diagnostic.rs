@@ -10,8 +10,8 @@ use crate::span::Span;
 pub struct Suggestion {
     /// 建议消息
     pub message: String,
-    /// 建议的替换内容（如果有）
-    pub replacement: Option<String>,
+    /// 可直接应用的修改：用替换文本覆盖该范围（如果有）
+    pub replacement: Option<(Span, String)>,
 }
 
 impl Suggestion {
@@ -23,11 +23,19 @@ impl Suggestion {
         }
     }
 
-    /// 添加替换内容
-    pub fn with_replacement(mut self, replacement: impl Into<String>) -> Self {
-        self.replacement = Some(replacement.into());
+    /// 添加替换内容：把 `span` 范围内的源码替换为 `text`
+    pub fn with_replacement(mut self, span: Span, text: impl Into<String>) -> Self {
+        self.replacement = Some((span, text.into()));
         self
     }
+
+    /// 将修改应用到源码上；没有替换内容或范围越界时返回 None
+    pub fn apply(&self, source: &str) -> Option<String> {
+        let (span, text) = self.replacement.as_ref()?;
+        let before = source.get(..span.start)?;
+        let after = source.get(span.end..)?;
+        Some(format!("{}{}{}", before, text, after))
+    }
 }
 
 /// 诊断信息
@@ -155,4 +163,26 @@ mod tests {
         assert_eq!(diag.notes.len(), 2);
         assert_eq!(diag.suggestions.len(), 2);
     }
+
+    #[test]
+    fn test_suggestion_replacement_is_applicable() -> Result<(), &'static str> {
+        let source = "print(\"n = \" + n)";
+        let start = source.rfind('n').ok_or("missing 'n'")?;
+        let diag = Diagnostic::error("operator '+' cannot be applied").with_suggestion(
+            Suggestion::new("convert with int_to_string")
+                .with_replacement(start..start + 1, "int_to_string(n)"),
+        );
+
+        let fix = &diag.suggestions[0];
+        assert_eq!(
+            fix.replacement,
+            Some((start..start + 1, "int_to_string(n)".into()))
+        );
+        assert_eq!(
+            fix.apply(source).as_deref(),
+            Some("print(\"n = \" + int_to_string(n))")
+        );
+        assert!(Suggestion::new("text only").apply(source).is_none());
+        Ok(())
+    }
 }
@@ -3,7 +3,9 @@
 //! 负责将诊断信息格式化输出
 
 use crate::diagnostic::Diagnostic;
+use crate::source_map::SourceMap;
 use colored::*;
+use std::fmt::Write;
 
 /// 诊断输出器
 pub struct Emitter {
@@ -56,6 +58,7 @@ impl Emitter {
 
     /// 输出带颜色的诊断
     fn emit_colored(&self, diagnostic: &Diagnostic, source: Option<&str>) {
+        let source_map = source.map(SourceMap::new);
         // 级别和消息
         println!(
             "{}: {}",
@@ -65,12 +68,12 @@ impl Emitter {
 
         // 位置信息（如果有）
         if let Some(span) = &diagnostic.span {
-            if let (Some(src), Some(file)) = (source, &diagnostic.file_path) {
-                let (line, col) = resolve_line_col(src, span.start);
+            if let (Some(map), Some(file)) = (&source_map, &diagnostic.file_path) {
+                let (line, col) = map.line_col(span.start);
                 println!("  {} {}:{}:{}", "-->".blue().bold(), file, line, col);
 
                 // 显示源代码片段
-                self.emit_source_snippet_colored(src, span.start, span.len(), line);
+                self.emit_source_snippet_colored(map, span);
             } else {
                 println!("  {} {:?}", "-->".blue().bold(), span);
             }
@@ -92,7 +95,7 @@ impl Emitter {
                 "=".green().bold(),
                 format!("help: {}", suggestion.message).green()
             );
-            if let Some(replacement) = &suggestion.replacement {
+            if let Some((_, replacement)) = &suggestion.replacement {
                 println!("        try: {}", replacement.green().italic());
             }
         }
@@ -100,150 +103,146 @@ impl Emitter {
 
     /// 输出纯文本诊断
     fn emit_plain(&self, diagnostic: &Diagnostic, source: Option<&str>) {
+        print!("{}", self.render_plain(diagnostic, source));
+    }
+
+    /// 渲染纯文本诊断（不带颜色），包含源码行与 `^^^` 指示符
+    pub fn render_plain(&self, diagnostic: &Diagnostic, source: Option<&str>) -> String {
+        let mut out = String::new();
+        let source_map = source.map(SourceMap::new);
         // 标准格式: error: file:line:col: message
-        let pos = if let (Some(span), Some(src), Some(file)) =
-            (&diagnostic.span, source, &diagnostic.file_path)
+        let pos = if let (Some(span), Some(map), Some(file)) =
+            (&diagnostic.span, &source_map, &diagnostic.file_path)
         {
-            let (line, col) = resolve_line_col(src, span.start);
+            let (line, col) = map.line_col(span.start);
             format!(" {}:{}:{}:", file, line, col)
         } else {
             String::new()
         };
 
-        println!("{}:{} {}", diagnostic.level, pos, diagnostic.message);
+        let _ = writeln!(out, "{}:{} {}", diagnostic.level, pos, diagnostic.message);
 
         // 详细位置信息
         if let Some(span) = &diagnostic.span {
-            if let (Some(src), Some(_)) = (source, &diagnostic.file_path) {
-                let (line, _) = resolve_line_col(src, span.start);
+            if let (Some(map), Some(_)) = (&source_map, &diagnostic.file_path) {
                 // 显示源代码片段 (Plain)
-                self.emit_source_snippet_plain(src, span.start, span.len(), line);
+                if let Some(snippet) = Snippet::new(map, span) {
+                    let padding = " ".repeat(snippet.line_num.len());
+                    let _ = writeln!(out, "  {} |", padding);
+                    let _ = writeln!(out, "  {} | {}", snippet.line_num, snippet.content);
+                    let _ = writeln!(
+                        out,
+                        "  {} | {}{}",
+                        padding,
+                        " ".repeat(snippet.col_offset),
+                        "^".repeat(snippet.mark_len)
+                    );
+                }
             } else {
-                println!("  --> {:?}", span);
+                let _ = writeln!(out, "  --> {:?}", span);
             }
         }
 
         // 注释
         for note in &diagnostic.notes {
-            println!("  = note: {}", note);
+            let _ = writeln!(out, "  = note: {}", note);
         }
 
         // 建议
         for suggestion in &diagnostic.suggestions {
-            println!("  = help: {}", suggestion.message);
-            if let Some(replacement) = &suggestion.replacement {
-                println!("        try: {}", replacement);
+            let _ = writeln!(out, "  = help: {}", suggestion.message);
+            if let Some((_, replacement)) = &suggestion.replacement {
+                let _ = writeln!(out, "        try: {}", replacement);
             }
         }
+        out
     }
 
     // --- Snippet Helpers ---
 
-    fn emit_source_snippet_colored(&self, source: &str, start: usize, len: usize, line_num: usize) {
-        let (line_content, line_start_offset) = get_line_content(source, start);
-        if line_content.trim().is_empty() {
+    fn emit_source_snippet_colored(&self, map: &SourceMap, span: &std::ops::Range<usize>) {
+        let Some(snippet) = Snippet::new(map, span) else {
             return;
-        }
-
-        let line_num_str = line_num.to_string();
-        let gutter_width = line_num_str.len();
-        let padding = " ".repeat(gutter_width);
+        };
+        let padding = " ".repeat(snippet.line_num.len());
 
         // 1. Empty line before
         println!("  {} |", padding.blue().bold());
 
         // 2. Source line
-        println!("  {} | {}", line_num_str.blue().bold(), line_content);
+        println!("  {} | {}", snippet.line_num.blue().bold(), snippet.content);
 
         // 3. Pointer line
-        // Calculate offset within the line
-        let col_offset = start.saturating_sub(line_start_offset);
-        // Ensure len is at least 1, and doesn't overflow line
-        let mark_len = len
-            .max(1)
-            .min(line_content.len().saturating_sub(col_offset));
-
-        let pointer_padding = " ".repeat(col_offset);
-        let pointer = "^".repeat(mark_len);
-
         println!(
             "  {} | {}{}",
             padding.blue().bold(),
-            pointer_padding,
-            pointer.red().bold()
+            " ".repeat(snippet.col_offset),
+            "^".repeat(snippet.mark_len).red().bold()
         );
     }
+}
 
-    fn emit_source_snippet_plain(&self, source: &str, start: usize, len: usize, line_num: usize) {
-        let (line_content, line_start_offset) = get_line_content(source, start);
-        if line_content.trim().is_empty() {
-            return;
-        }
-
-        let line_num_str = line_num.to_string();
-        let gutter_width = line_num_str.len();
-        let padding = " ".repeat(gutter_width);
-
-        println!("  {} |", padding);
-        println!("  {} | {}", line_num_str, line_content);
-
-        let col_offset = start.saturating_sub(line_start_offset);
-        let mark_len = len
-            .max(1)
-            .min(line_content.len().saturating_sub(col_offset));
-
-        let pointer_padding = " ".repeat(col_offset);
-        let pointer = "^".repeat(mark_len);
+/// 制表符展开宽度
+const TAB_WIDTH: usize = 4;
 
-        println!("  {} | {}{}", padding, pointer_padding, pointer);
-    }
+/// 源码片段：所在行内容（制表符已展开）与指示符位置（按显示列计）
+struct Snippet {
+    line_num: String,
+    content: String,
+    col_offset: usize,
+    mark_len: usize,
 }
 
-/// 解析行列
-fn resolve_line_col(source: &str, offset: usize) -> (usize, usize) {
-    let mut line = 1;
-    let mut col = 1;
-    for (i, c) in source.char_indices() {
-        if i >= offset {
-            break;
+impl Snippet {
+    fn new(map: &SourceMap, span: &std::ops::Range<usize>) -> Option<Self> {
+        let (line, col) = map.line_col(span.start);
+        let text = map.line_text(line)?;
+        if text.trim().is_empty() {
+            return None;
         }
-        if c == '\n' {
-            line += 1;
-            col = 1;
-        } else {
-            col += 1;
-        }
-    }
-    (line, col)
-}
 
-/// 获取指定偏移量所在的行内容和该行的起始偏移量
-fn get_line_content(source: &str, offset: usize) -> (&str, usize) {
-    let mut line_start = 0;
-    let mut line_end = source.len();
-
-    // Find start of line
-    for i in (0..=offset.min(source.len().saturating_sub(1))).rev() {
-        if source.as_bytes()[i] == b'\n' {
-            line_start = i + 1;
-            break;
-        }
-    }
+        // 跨行的 span 只标到行尾
+        let (end_line, end_col) = map.line_col(span.end);
+        let start_char = col as usize - 1;
+        let end_char = if end_line == line {
+            end_col as usize - 1
+        } else {
+            text.chars().count()
+        };
 
-    // Find end of line
-    for i in offset..source.len() {
-        if source.as_bytes()[i] == b'\n' {
-            line_end = i;
-            break;
+        // 逐字符展开制表符，同时把字符下标换算为显示列
+        let mut content = String::with_capacity(text.len());
+        let mut col_offset = None;
+        let mut mark_end = None;
+        for (i, c) in text.chars().enumerate() {
+            if i == start_char {
+                col_offset = Some(content.len());
+            }
+            if i == end_char {
+                mark_end = Some(content.len());
+            }
+            if c == '\t' {
+                let width = TAB_WIDTH - content.chars().count() % TAB_WIDTH;
+                content.extend(std::iter::repeat_n(' ', width));
+            } else {
+                content.push(c);
+            }
         }
+        // content 里只有展开后的字符，用字符数作为显示列
+        let width = content.chars().count();
+        let to_col = |byte: usize| content[..byte].chars().count();
+        let col_offset = col_offset.map_or(width, to_col);
+        let mark_end = mark_end.map_or(width, to_col);
+        // 指示符至少一列
+        let mark_len = mark_end.saturating_sub(col_offset).max(1);
+
+        Some(Self {
+            line_num: line.to_string(),
+            content,
+            col_offset,
+            mark_len,
+        })
     }
-
-    // Handle case where offset is at EOF or somehow out of bounds gracefully
-    if line_start > source.len() || line_start > line_end {
-        return ("", line_start);
-    }
-
-    (&source[line_start..line_end], line_start)
 }
 
 #[cfg(test)]
@@ -278,4 +277,44 @@ mod tests {
 
         emitter.emit(&diag);
     }
+
+    #[test]
+    fn test_render_source_line_with_carets() -> Result<(), &'static str> {
+        let source = "int main() {\n    return x + 1\n}\n";
+        let start = source.find('x').ok_or("missing 'x'")?;
+        let diag = Diagnostic::error("undefined variable 'x'")
+            .with_file("main.lcy")
+            .span(start..start + 1)
+            .with_note("declare it with 'var x = ...'");
+
+        let rendered = Emitter::without_colors().render_plain(&diag, Some(source));
+        let expected = "\
+error: main.lcy:2:12: undefined variable 'x'
+    |
+  2 |     return x + 1
+    |            ^
+  = note: declare it with 'var x = ...'
+";
+        assert_eq!(rendered, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_render_expands_tabs_and_multibyte_chars() -> Result<(), &'static str> {
+        let source = "var s = 0\n\tprint(名字 + s)\n";
+        let start = source.find("名字").ok_or("missing identifier")?;
+        let diag = Diagnostic::error("type mismatch")
+            .with_file("a.lcy")
+            .span(start..start + "名字 + s".len());
+
+        let rendered = Emitter::without_colors().render_plain(&diag, Some(source));
+        let expected = "\
+error: a.lcy:2:8: type mismatch
+    |
+  2 |     print(名字 + s)
+    |           ^^^^^^
+";
+        assert_eq!(rendered, expected);
+        Ok(())
+    }
 }
@@ -8,6 +8,7 @@
 //! - [`DiagnosticLevel`] - 诊断级别（Error/Warning/Info/Note）
 //! - [`DiagnosticSink`] - 诊断收集器
 //! - [`Emitter`] - 诊断输出器
+//! - [`SourceMap`] - 字节偏移到行列的映射
 //! - [`Span`] - 源码位置信息
 //!
 //! # 示例
@@ -36,6 +37,7 @@ pub mod diagnostic;
 pub mod emitter;
 pub mod level;
 pub mod sink;
+pub mod source_map;
 pub mod span;
 
 // 重新导出核心类型
@@ -43,4 +45,5 @@ pub use diagnostic::{Diagnostic, Suggestion};
 pub use emitter::Emitter;
 pub use level::DiagnosticLevel;
 pub use sink::DiagnosticSink;
+pub use source_map::SourceMap;
 pub use span::{Span, SpanExt};
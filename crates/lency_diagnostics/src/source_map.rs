@@ -0,0 +1,133 @@
+//! SourceMap - 源码位置映射
+//!
+//! 预先计算每一行的起始字节偏移，把字节偏移换算为 (行, 列)。
+//! 列按字符计数，多字节 UTF-8 字符只算一列。
+
+/// 源码位置映射
+#[derive(Debug, Clone, Default)]
+pub struct SourceMap {
+    /// 源代码
+    source: String,
+    /// 每一行的起始字节偏移（第一项恒为 0）
+    line_starts: Vec<usize>,
+}
+
+impl SourceMap {
+    /// 为源代码建立映射
+    pub fn new(source: &str) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(source.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+        Self {
+            source: source.to_string(),
+            line_starts,
+        }
+    }
+
+    /// 源代码
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// 行数
+    pub fn line_count(&self) -> usize {
+        self.line_starts.len()
+    }
+
+    /// 字节偏移所在的行号 (1-based)；超出末尾时视为最后一行
+    pub fn line(&self, offset: usize) -> u32 {
+        self.line_index(offset) as u32 + 1
+    }
+
+    /// 字节偏移对应的 (行, 列)，均为 1-based
+    ///
+    /// 偏移落在多字节字符内部时按该字符所在列计算
+    pub fn line_col(&self, offset: usize) -> (u32, u32) {
+        let offset = offset.min(self.source.len());
+        let index = self.line_index(offset);
+        let line_start = self.line_starts[index];
+        let col = self.source[line_start..]
+            .char_indices()
+            .take_while(|(i, _)| line_start + i < offset)
+            .filter(|(i, c)| line_start + i + c.len_utf8() <= offset)
+            .count();
+        (index as u32 + 1, col as u32 + 1)
+    }
+
+    /// 第 `line` 行 (1-based) 的内容，不含换行符；行号越界时返回 None
+    pub fn line_text(&self, line: u32) -> Option<&str> {
+        let index = (line as usize).checked_sub(1)?;
+        let start = *self.line_starts.get(index)?;
+        let end = self
+            .line_starts
+            .get(index + 1)
+            .map_or(self.source.len(), |next| next - 1);
+        let text = &self.source[start..end];
+        Some(text.strip_suffix('\r').unwrap_or(text))
+    }
+
+    /// 第 `line` 行 (1-based) 的起始字节偏移
+    pub fn line_start(&self, line: u32) -> Option<usize> {
+        let index = (line as usize).checked_sub(1)?;
+        self.line_starts.get(index).copied()
+    }
+
+    fn line_index(&self, offset: usize) -> usize {
+        match self.line_starts.binary_search(&offset) {
+            Ok(index) => index,
+            Err(next) => next - 1,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_first_line() {
+        let map = SourceMap::new("let x = 1\nlet y = 2\n");
+        assert_eq!(map.line_col(0), (1, 1));
+        assert_eq!(map.line_col(4), (1, 5));
+        // 换行符本身仍属于第一行
+        assert_eq!(map.line_col(9), (1, 10));
+    }
+
+    #[test]
+    fn test_after_newline() {
+        let map = SourceMap::new("let x = 1\nlet y = 2\n");
+        assert_eq!(map.line_col(10), (2, 1));
+        assert_eq!(map.line_col(14), (2, 5));
+        assert_eq!(map.line(14), 2);
+        assert_eq!(map.line_text(2), Some("let y = 2"));
+        // 文件末尾的空行
+        assert_eq!(map.line_col(20), (3, 1));
+        assert_eq!(map.line_count(), 3);
+    }
+
+    #[test]
+    fn test_columns_count_chars_not_bytes() -> Result<(), &'static str> {
+        // "变量" 每个字符占 3 字节
+        let source = "var 变量 = x\nprint(变量)";
+        let map = SourceMap::new(source);
+        let eq = source.find('=').ok_or("missing '='")?;
+        assert_eq!(eq, 11);
+        assert_eq!(map.line_col(eq), (1, 8));
+
+        let second = source.find("print").ok_or("missing print")?;
+        let inner = second + "print(变量".len();
+        assert_eq!(map.line_col(inner), (2, 9));
+
+        // 偏移落在多字节字符内部时仍指向该字符
+        assert_eq!(map.line_col(5), (1, 5));
+        Ok(())
+    }
+
+    #[test]
+    fn test_out_of_range_offset_is_clamped() {
+        let map = SourceMap::new("ab\ncd");
+        assert_eq!(map.line_col(100), (2, 3));
+        assert_eq!(map.line_text(3), None);
+        assert_eq!(map.line_text(0), None);
+    }
+}
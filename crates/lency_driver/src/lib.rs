@@ -5,14 +5,11 @@
 pub mod error;
 
 pub use error::{CompileError, CompileResult};
+pub use lency_codegen::{CodegenOptions, ModuleStats};
 
-use chumsky::Parser;
-use lency_codegen::compile_to_ir;
+use lency_codegen::compile_to_ir_with_stats;
 use lency_sema::analyze;
 use lency_syntax::ast::Program;
-use lency_syntax::lexer::Token;
-use lency_syntax::parser::program_parser;
-use logos::Logos;
 
 /// 编译结果
 #[derive(Debug)]
@@ -21,30 +18,14 @@ pub struct CompilationOutput {
     pub ir: String,
     /// 警告信息
     pub warnings: Vec<String>,
+    /// 代码生成统计，仅在 `CodegenOptions::stats` 开启时收集
+    pub stats: Option<ModuleStats>,
 }
 
-/// 解析源代码
 /// 解析源代码
 fn parse_source(source: &str) -> CompileResult<Program> {
-    use chumsky::Stream;
-
-    // 1. 词法分析 (保留 Span)
-    let token_iter = Token::lexer(source).spanned().map(|(tok, span)| match tok {
-        Ok(t) => (t, span),
-        Err(_) => (Token::Error, span),
-    });
-
-    let token_vec: Vec<(Token, std::ops::Range<usize>)> = token_iter.collect();
-    let len = source.len();
-
-    // 2. 创建 Stream
-    // Stream 需要一个迭代器，其中元素是 (Token, Span)
-    // 还需要指定 EOF 的 Span (这里用 source.len()..source.len())
-    let stream = Stream::from_iter(len..len, token_vec.into_iter());
-
-    // 3. 语法分析
-    let parser = program_parser();
-    parser.parse(stream).map_err(|e| {
+    // 词法分析保留每个 token 的 Span，AST 节点与语法错误均为字节偏移
+    lency_syntax::parser::parse(source).map_err(|e| {
         let details = e
             .into_iter()
             .map(|err| {
@@ -113,6 +94,13 @@ fn parse_source(source: &str) -> CompileResult<Program> {
     })
 }
 
+/// 只做语法分析，返回单个文件的语法树（不展开 import）
+///
+/// 用于 `lencyc --dump-ast` 等调试场景
+pub fn parse(source: &str) -> CompileResult<Program> {
+    parse_source(source)
+}
+
 /// 编译 Lency 源代码
 ///
 /// # Arguments
@@ -131,22 +119,40 @@ fn parse_source(source: &str) -> CompileResult<Program> {
 /// println!("{}", output.ir);
 /// ```
 pub fn compile(source: &str) -> CompileResult<CompilationOutput> {
+    compile_with_options(source, &CodegenOptions::default())
+}
+
+/// 按给定的代码生成选项编译 Lency 源代码（如 `--stack-guard`、`--stats`）
+pub fn compile_with_options(
+    source: &str,
+    options: &CodegenOptions,
+) -> CompileResult<CompilationOutput> {
     // 1. 词法 + 语法分析
     let mut ast = parse_source(source)?;
 
     // 2. 语义分析
-    let _analysis_result = analyze(&mut ast).map_err(CompileError::SemanticErrors)?;
+    let analysis_result = analyze(&mut ast).map_err(CompileError::SemanticErrors)?;
+    let warnings = analysis_result
+        .warnings
+        .iter()
+        .map(|w| w.to_string())
+        .collect();
 
     // 3. 单态化 (Generic Monomorphization)
     let mut monomorphizer = lency_monomorph::Monomorphizer::new();
     let monomorphized_ast = monomorphizer.process(ast);
+    let mono_errors = monomorphizer.take_errors();
+    if !mono_errors.is_empty() {
+        return Err(CompileError::SemanticErrors(mono_errors));
+    }
 
     // 4. 代码生成
-    let ir = compile_to_ir(&monomorphized_ast, "main", Some(source))?;
+    let (ir, stats) = compile_to_ir_with_stats(&monomorphized_ast, "main", Some(source), options)?;
 
     Ok(CompilationOutput {
         ir,
-        warnings: Vec::new(),
+        warnings,
+        stats,
     })
 }
 
@@ -155,108 +161,19 @@ pub fn compile(source: &str) -> CompileResult<CompilationOutput> {
 /// # Arguments
 /// * `path` - 源文件路径
 pub fn compile_file(path: &str) -> CompileResult<CompilationOutput> {
-    let source = std::fs::read_to_string(path)?;
-    compile(&source)
+    compile_file_with_options(path, &CodegenOptions::default())
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_compile_simple_return() {
-        let source = r#"
-            int main() {
-                return 42;
-            }
-        "#;
-
-        let result = compile(source);
-        assert!(result.is_ok(), "Compilation failed: {:?}", result.err());
-
-        let output = result.unwrap();
-        // 检查 IR 中包含 main 函数
-        assert!(output.ir.contains("@main"));
-        // 检查返回 42
-        assert!(output.ir.contains("ret i64 42"));
-    }
-
-    #[test]
-    fn test_compile_with_arithmetic() {
-        let source = r#"
-            int add(int a, int b) {
-                return a + b;
-            }
-
-            int main() {
-                return add(10, 32);
-            }
-        "#;
-
-        let result = compile(source);
-        assert!(result.is_ok(), "Compilation failed: {:?}", result.err());
-
-        let output = result.unwrap();
-        assert!(output.ir.contains("@add"));
-        assert!(output.ir.contains("@main"));
-        assert!(output.ir.contains("call"));
-    }
-
-    #[test]
-    fn test_compile_with_variable() {
-        let source = r#"
-            int test() {
-                var x = 10;
-                return x;
-            }
-        "#;
-
-        let result = compile(source);
-        assert!(result.is_ok(), "Compilation failed: {:?}", result.err());
-
-        let output = result.unwrap();
-        assert!(output.ir.contains("alloca"));
-        assert!(output.ir.contains("store"));
-        assert!(output.ir.contains("load"));
-    }
-
-    #[test]
-    fn test_compile_undefined_variable_error() {
-        let source = r#"
-            int main() {
-                return x;
-            }
-        "#;
-
-        let result = compile(source);
-        assert!(result.is_err(), "Should fail with undefined variable");
-
-        match result.unwrap_err() {
-            CompileError::SemanticErrors(errors) => {
-                assert!(!errors.is_empty());
-                assert!(format!("{:?}", errors[0]).contains("x"));
-            }
-            _ => panic!("Expected SemanticErrors"),
-        }
-    }
-
-    #[test]
-    fn test_compile_type_mismatch_error() {
-        let source = r#"
-            int main() {
-                var x: int = "hello";
-                return x;
-            }
-        "#;
-
-        let result = compile(source);
-        assert!(result.is_err(), "Should fail with type mismatch");
-
-        match result.unwrap_err() {
-            CompileError::SemanticErrors(_) => {
-                // Expected
-            }
-            _ => panic!("Expected SemanticErrors"),
-        }
-    }
+/// 按给定的代码生成选项从文件编译，运行时 panic 消息报告该文件路径
+pub fn compile_file_with_options(
+    path: &str,
+    options: &CodegenOptions,
+) -> CompileResult<CompilationOutput> {
+    let source = std::fs::read_to_string(path)?;
+    // panic 消息带上源文件路径
+    let options = CodegenOptions {
+        source_path: Some(path.to_string()),
+        ..options.clone()
+    };
+    compile_with_options(&source, &options)
 }
@@ -0,0 +1,283 @@
+mod common;
+
+use common::run_with_lli;
+use lency_driver::{compile, compile_with_options, CodegenOptions};
+
+#[test]
+fn test_compile_simple_return() {
+    let source = r#"
+            int main() {
+                return 42;
+            }
+        "#;
+
+    let result = compile(source);
+    assert!(result.is_ok(), "Compilation failed: {:?}", result.err());
+
+    let output = result.unwrap();
+    // 检查 IR 中包含 main 函数
+    assert!(output.ir.contains("@main"));
+    // 检查返回 42
+    assert!(output.ir.contains("ret i64 42"));
+}
+
+#[test]
+fn test_compile_with_arithmetic() {
+    let source = r#"
+            int add(int a, int b) {
+                return a + b;
+            }
+
+            int main() {
+                return add(10, 32);
+            }
+        "#;
+
+    let result = compile(source);
+    assert!(result.is_ok(), "Compilation failed: {:?}", result.err());
+
+    let output = result.unwrap();
+    assert!(output.ir.contains("@add"));
+    assert!(output.ir.contains("@main"));
+    assert!(output.ir.contains("call"));
+}
+
+#[test]
+fn test_compile_with_variable() {
+    let source = r#"
+            int test() {
+                var x = 10;
+                return x;
+            }
+        "#;
+
+    let result = compile(source);
+    assert!(result.is_ok(), "Compilation failed: {:?}", result.err());
+
+    let output = result.unwrap();
+    assert!(output.ir.contains("alloca"));
+    assert!(output.ir.contains("store"));
+    assert!(output.ir.contains("load"));
+}
+
+#[test]
+fn test_modulo_uses_truncated_remainder() {
+    let source = r#"
+            int int_mod(int a, int b) { return a % b }
+            float float_mod(float a, float b) { return a % b }
+            float mixed_mod(int a, float b) { return a % b }
+        "#;
+
+    let output = compile(source).expect("compilation failed");
+    // 截断语义：符号跟随被除数，与 sdiv 配套
+    assert!(output.ir.contains("%modtmp = srem i64 %a"));
+    assert!(output.ir.contains("%modtmp = frem double %a"));
+    assert!(output.ir.contains("%modtmp = frem double %itof"));
+}
+
+#[test]
+fn test_as_cast_lowers_to_conversion_instructions() {
+    let source = r#"
+            int truncate(float x) { return x as int }
+            float widen(int n) { return n as float }
+        "#;
+
+    let output = compile(source).expect("compilation failed");
+    assert!(output.ir.contains("fptosi double %"));
+    assert!(output.ir.contains("sitofp i64 %"));
+}
+
+#[test]
+fn test_sized_int_lowers_to_narrow_llvm_types() {
+    let source = r#"
+            i64 widen(i32 step) {
+                var small: i32 = 40
+                var wide: i64 = small + step
+                return wide
+            }
+        "#;
+
+    let output = compile(source).expect("compilation failed");
+    assert!(output.ir.contains("define i64 @widen(i32"));
+    assert!(output.ir.contains("alloca i32"));
+    // i32 + i32 保持 32 位运算，赋给 i64 时才符号扩展
+    assert!(output.ir.contains("add i32"));
+    assert!(output.ir.contains("sext i32 %"));
+}
+
+#[test]
+fn test_uint_uses_unsigned_division_and_comparison() {
+    let source = r#"
+            uint half(uint a, uint b) {
+                if a < b {
+                    return a % b
+                }
+                return a / b
+            }
+        "#;
+
+    let output = compile(source).expect("compilation failed");
+    assert!(output.ir.contains("define i64 @half(i64"));
+    assert!(output.ir.contains("icmp ult"));
+    assert!(output.ir.contains("udiv i64"));
+    assert!(output.ir.contains("urem i64"));
+    assert!(!output.ir.contains("sdiv"));
+}
+
+#[test]
+fn test_newtype_uses_inner_llvm_type() {
+    let source = r#"
+            newtype UserId = int
+
+            UserId next(UserId id) {
+                return cast<UserId>(cast<int>(id) + 1)
+            }
+
+            bool same(UserId a, UserId b) {
+                return a == b
+            }
+        "#;
+
+    let output = compile(source).expect("compilation failed");
+    assert!(output.ir.contains("define i64 @next(i64"));
+    assert!(output.ir.contains("define i1 @same(i64"));
+    assert!(output.ir.contains("icmp eq i64"));
+}
+
+#[test]
+fn test_len_of_string_literal_is_folded() {
+    let source = r#"
+            int chars() { return len("abc") }
+            int wide() { return len("héllo") }
+            int bytes() { return byte_len("héllo") }
+        "#;
+
+    let output = compile(source).expect("compilation failed");
+    assert!(output.ir.contains("ret i64 3"));
+    assert!(output.ir.contains("ret i64 5"));
+    assert!(output.ir.contains("ret i64 6"));
+    assert!(!output.ir.contains("@lency_string_len"));
+    assert!(!output.ir.contains("@lency_string_byte_len"));
+}
+
+#[test]
+fn test_constant_if_condition_emits_only_taken_branch() {
+    let source = r#"
+            int main() {
+                if (false) { print(1) }
+                if (true) { return 2 } else { return 3 }
+            }
+
+            int first() {
+                if (true) { return 4 }
+            }
+        "#;
+
+    let output = compile(source).expect("compilation failed");
+    assert!(!output.ir.contains("call i32 (i8*, ...) @printf"));
+    assert!(!output.ir.contains("br i1"));
+    assert!(output.ir.contains("ret i64 2"));
+    assert!(!output.ir.contains("ret i64 3"));
+    assert!(output.ir.contains("ret i64 4"));
+    assert_eq!(output.warnings.len(), 2);
+}
+
+#[test]
+fn test_repeat_literals_fill_storage() {
+    let source = r#"
+            int main() {
+                var sevens = [7; 3]
+                var n = sevens[0]
+                var filled = vec![n; n - 4]
+                return filled.len()
+            }
+        "#;
+
+    let output = compile(source).expect("compilation failed");
+    // 常量值直接生成常量聚合；Vec 按运行时长度循环 push
+    assert!(output.ir.contains("[3 x i64] [i64 7, i64 7, i64 7]"));
+    assert!(output.ir.contains("vec_repeat.cond:"));
+    assert!(output.ir.contains("call i8* @lency_vec_new(i64 %"));
+    assert!(output.ir.contains("icmp slt i64 %vec_repeat_index, %"));
+}
+
+const GLOBAL_INITIALIZERS_RUN_BEFORE_MAIN_SOURCE: &str = r#"
+    int compute() {
+        return 6 * 7
+    }
+
+    var answer = compute()
+    var offset: int = 100
+    var total = answer + offset
+
+    int main() {
+        print(answer)
+        print(total)
+        return answer
+    }
+"#;
+
+#[test]
+fn test_global_initializers_run_before_main() {
+    let output = compile(GLOBAL_INITIALIZERS_RUN_BEFORE_MAIN_SOURCE).expect("compilation failed");
+    // 字面量初始值静态生成，其余按声明顺序在 __lency_init 中计算
+    assert!(output.ir.contains("@offset = global i64 100"));
+    assert!(output.ir.contains("@answer = global i64 0"));
+    assert!(output.ir.contains("define internal void @__lency_init()"));
+    let wrapper = &output.ir[output.ir.find("define i32 @main()").unwrap()..];
+    let init_call = wrapper.find("call void @__lency_init()").unwrap();
+    assert!(init_call < wrapper.find("%call_user_main").unwrap());
+}
+
+#[test]
+#[cfg_attr(not(feature = "lli-tests"), ignore = "requires lli")]
+fn test_global_initializers_run_before_main_executes() {
+    let output = compile(GLOBAL_INITIALIZERS_RUN_BEFORE_MAIN_SOURCE).expect("compilation failed");
+    let result = run_with_lli(&output.ir, "global_init");
+    let stdout = String::from_utf8_lossy(&result.stdout);
+    assert_eq!(result.status.code(), Some(42), "stdout: {}", stdout);
+    assert!(stdout.starts_with("42142"), "stdout: {}", stdout);
+}
+
+#[test]
+fn test_stats_count_functions_blocks_and_instructions() {
+    let base = r#"
+            int main() {
+                return 0
+            }
+        "#;
+    let with_add = r#"
+            int add(int a, int b) {
+                return a + b
+            }
+
+            int main() {
+                return 0
+            }
+        "#;
+
+    assert!(compile(base).unwrap().stats.is_none());
+
+    let options = CodegenOptions {
+        stats: true,
+        ..CodegenOptions::default()
+    };
+    let base = compile_with_options(base, &options).expect("compilation failed");
+    let output = compile_with_options(with_add, &options).expect("compilation failed");
+    let (base_stats, stats) = (base.stats.unwrap(), output.stats.unwrap());
+
+    // 函数数量与 IR 文本中的定义、声明一一对应
+    assert_eq!(stats.functions, output.ir.matches("\ndefine ").count());
+    assert_eq!(stats.declarations, output.ir.matches("\ndeclare ").count());
+    assert_eq!(stats.ir_bytes, output.ir.len());
+
+    // add 只有入口块：两个形参各一对 alloca/store，两次 load，add 与 ret
+    assert_eq!(stats.functions, base_stats.functions + 1);
+    assert_eq!(stats.declarations, base_stats.declarations);
+    assert_eq!(stats.basic_blocks, base_stats.basic_blocks + 1);
+    assert_eq!(stats.instructions, base_stats.instructions + 8);
+    assert!(stats.ir_bytes > base_stats.ir_bytes);
+
+    let summary = stats.to_string();
+    assert!(summary.contains(&format!("instructions: {}", stats.instructions)));
+}
@@ -0,0 +1,28 @@
+//! 驱动测试辅助：用 lli 执行生成的 IR
+//!
+//! 执行类测试默认标记为 ignored，启用 `lli-tests` feature 后运行：
+//! `cargo test -p lency_driver --features lli-tests`
+
+#![allow(dead_code)]
+
+/// 用 lli 执行 IR；环境中没有 lli 时测试失败
+pub fn run_with_lli(ir: &str, name: &str) -> std::process::Output {
+    run_with_lli_args(ir, name, &[])
+}
+
+/// 带额外参数运行 lli（如 `--extra-object=shim.o` 链接 C 目标文件）
+pub fn run_with_lli_args(ir: &str, name: &str, args: &[String]) -> std::process::Output {
+    let path = std::env::temp_dir().join(format!("lency_{}_{}.ll", name, std::process::id()));
+    std::fs::write(&path, ir).unwrap();
+    let output = ["lli", "lli-18", "lli-17", "lli-16", "lli-15"]
+        .iter()
+        .find_map(|lli| {
+            std::process::Command::new(lli)
+                .args(args)
+                .arg(&path)
+                .output()
+                .ok()
+        });
+    let _ = std::fs::remove_file(&path);
+    output.unwrap_or_else(|| panic!("execution test '{}' requires lli, but none was found", name))
+}
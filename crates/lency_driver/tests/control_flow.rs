@@ -0,0 +1,184 @@
+mod common;
+
+use common::run_with_lli;
+use lency_driver::compile;
+
+#[test]
+fn test_match_guard_falls_through_to_next_arm() {
+    let source = r#"
+            int classify(int x) {
+                return match x {
+                    case n if n > 100 => 2,
+                    case n if n > 0 => 1,
+                    case _ => 0
+                }
+            }
+        "#;
+
+    let output = compile(source).expect("compilation failed");
+    // 守卫为假时跳到下一分支的检查块
+    assert!(output
+        .ir
+        .contains("label %case_0_body, label %case_1_check"));
+    assert!(output
+        .ir
+        .contains("label %case_1_body, label %case_2_check"));
+}
+
+#[test]
+fn test_match_range_and_or_patterns_compare_subject() {
+    let source = r#"
+            int status(int code) {
+                return match code {
+                    case 200..=299 => 1,
+                    case 400 | 404 => 2,
+                    case _ => 0
+                }
+            }
+        "#;
+
+    let output = compile(source).expect("compilation failed");
+    // 区间：两端有符号比较后取与；或模式：逐个分支比较，失败跳到下一个分支
+    assert!(output
+        .ir
+        .contains("%range_ge = icmp sge i64 %range_chk_load, 200"));
+    assert!(output
+        .ir
+        .contains("%range_le = icmp sle i64 %range_chk_load, 299"));
+    assert!(output
+        .ir
+        .contains("br i1 %in_range, label %range_match_success"));
+    assert!(output
+        .ir
+        .contains("label %lit_match_success, label %or_alt_next"));
+    assert!(output.ir.contains("br label %or_match_success"));
+}
+
+#[test]
+fn test_loop_break_value_merges_with_phi() {
+    let source = r#"
+            int find(int limit) {
+                var i = 0
+                var found = while i < 100 {
+                    if i * i > limit {
+                        break i
+                    }
+                    i = i + 1
+                } else -1
+                return found
+            }
+        "#;
+
+    let output = compile(source).expect("compilation failed");
+    // break value 与循环正常结束的默认值在 loop.value 中汇合
+    assert!(output.ir.contains("%loop_value = phi i64"));
+    assert!(output.ir.contains("[ -1, %while.end ]"));
+    assert!(output.ir.contains("br label %loop.value"));
+}
+
+const MATCH_ARMS_UNIFY_NULL_AND_FLOAT_SOURCE: &str = r#"
+    int? pick(bool c) {
+        return match c {
+            case true => 5,
+            case false => null
+        }
+    }
+
+    float half(bool c) {
+        return match c {
+            case true => 1,
+            case false => 2.5
+        }
+    }
+
+    int main() {
+        print(pick(true))
+        print(" ")
+        print(pick(false))
+        var h = half(false) * 2.0
+        return h as int
+    }
+"#;
+
+#[test]
+fn test_match_arms_unify_null_and_float() {
+    let output = compile(MATCH_ARMS_UNIFY_NULL_AND_FLOAT_SOURCE).expect("compilation failed");
+    // int 分支在跳转前转为 float，非空分支装箱为 int?
+    assert!(output.ir.contains("%match_arm_float = sitofp i64"));
+    assert!(output.ir.contains("%nullable_box = call i8* @malloc"));
+}
+
+#[test]
+#[cfg_attr(not(feature = "lli-tests"), ignore = "requires lli")]
+fn test_match_arms_unify_null_and_float_executes() {
+    let output = compile(MATCH_ARMS_UNIFY_NULL_AND_FLOAT_SOURCE).expect("compilation failed");
+    let result = run_with_lli(&output.ir, "match_arm_unify");
+    assert_eq!(String::from_utf8_lossy(&result.stdout), "5 null");
+    assert_eq!(result.status.code(), Some(5));
+}
+
+const FOR_IN_SUMS_VEC_SOURCE: &str = r#"
+    int main() {
+        var v = vec![1, 2, 3, 4]
+        var total = 0
+        for x in v {
+            total = total + x
+        }
+        return total
+    }
+"#;
+
+#[test]
+fn test_for_in_sums_vec() {
+    let output = compile(FOR_IN_SUMS_VEC_SOURCE).expect("compilation failed");
+    // 长度只在进入循环前读取一次，元素逐个按值取出
+    assert_eq!(output.ir.matches("call i64 @lency_vec_len").count(), 1);
+    assert!(output.ir.contains("call i64 @lency_vec_get"));
+}
+
+#[test]
+#[cfg_attr(not(feature = "lli-tests"), ignore = "requires lli")]
+fn test_for_in_sums_vec_executes() {
+    let output = compile(FOR_IN_SUMS_VEC_SOURCE).expect("compilation failed");
+    let result = run_with_lli(&output.ir, "for_in_vec");
+    assert_eq!(result.status.code(), Some(10));
+}
+
+const FOR_IN_CONTINUE_AND_BREAK_SOURCE: &str = r#"
+    int main() {
+        var v = vec![1, 2, 3, 4, 5, 6]
+        var odd = 0
+        for x in v {
+            if x % 2 == 0 {
+                continue
+            }
+            odd = odd + x
+        }
+
+        var arr = [10, 20, 30, 40]
+        var prefix = 0
+        for y in arr {
+            if y > 20 {
+                break
+            }
+            prefix = prefix + y
+        }
+        return odd + prefix
+    }
+"#;
+
+#[test]
+fn test_for_in_continue_and_break() {
+    let output = compile(FOR_IN_CONTINUE_AND_BREAK_SOURCE).expect("compilation failed");
+    // continue 与循环体末尾一样跳到自增块，下标才会前进
+    assert!(output.ir.contains("br label %forin.inc"));
+}
+
+#[test]
+#[cfg_attr(not(feature = "lli-tests"), ignore = "requires lli")]
+fn test_for_in_continue_and_break_executes() {
+    let output = compile(FOR_IN_CONTINUE_AND_BREAK_SOURCE).expect("compilation failed");
+    let result = run_with_lli(&output.ir, "for_in_continue_break");
+    // 1 + 3 + 5 = 9，10 + 20 = 30
+    assert_eq!(result.status.code(), Some(39));
+}
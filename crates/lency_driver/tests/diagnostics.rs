@@ -0,0 +1,168 @@
+use lency_driver::{compile, CompileError};
+use lency_sema::SemanticError;
+
+#[test]
+fn test_compile_undefined_variable_error() {
+    let source = r#"
+            int main() {
+                return x;
+            }
+        "#;
+
+    let result = compile(source);
+    assert!(result.is_err(), "Should fail with undefined variable");
+
+    match result.unwrap_err() {
+        CompileError::SemanticErrors(errors) => {
+            assert!(!errors.is_empty());
+            assert!(format!("{:?}", errors[0]).contains("x"));
+        }
+        _ => panic!("Expected SemanticErrors"),
+    }
+}
+
+#[test]
+fn test_compile_type_mismatch_error() {
+    let source = r#"
+            int main() {
+                var x: int = "hello";
+                return x;
+            }
+        "#;
+
+    let result = compile(source);
+    assert!(result.is_err(), "Should fail with type mismatch");
+
+    match result.unwrap_err() {
+        CompileError::SemanticErrors(_) => {
+            // Expected
+        }
+        _ => panic!("Expected SemanticErrors"),
+    }
+}
+
+#[test]
+fn test_string_concat_with_int_suggests_applicable_fix() {
+    let source = r#"
+            void show(int count) {
+                print("count: " + count)
+            }
+        "#;
+
+    let errors = match compile(source) {
+        Err(CompileError::SemanticErrors(errors)) => errors,
+        _ => panic!("Expected SemanticErrors"),
+    };
+    let diag = errors
+        .iter()
+        .find(|e| matches!(e, lency_sema::SemanticError::InvalidBinaryOp { .. }))
+        .expect("expected InvalidBinaryOp")
+        .to_diagnostic();
+
+    // 修复覆盖非字符串一侧的操作数，应用后可以通过编译
+    let start = source.rfind("count)").unwrap();
+    let fix = &diag.suggestions[0];
+    assert_eq!(
+        fix.replacement,
+        Some((start..start + 5, "int_to_string(count)".to_string()))
+    );
+    let fixed = fix.apply(source).unwrap();
+    assert!(fixed.contains(r#"print("count: " + int_to_string(count))"#));
+    assert!(compile(&fixed).is_ok());
+}
+
+#[test]
+fn test_assert_requires_bool_condition() {
+    let source = r#"
+            int main() {
+                assert(1, "not a bool")
+                return 0
+            }
+        "#;
+
+    match compile(source) {
+        Err(CompileError::SemanticErrors(errors)) => {
+            assert!(errors
+                .iter()
+                .any(|e| e.to_string().contains("expected 'bool', found 'int'")));
+        }
+        _ => panic!("Expected SemanticErrors"),
+    }
+}
+
+#[test]
+fn test_compile_reports_shadowing_warning() {
+    let source = r#"
+            int main() {
+                var x = 1
+                if true {
+                    var x = 2
+                }
+                return x
+            }
+        "#;
+
+    let output = compile(source).expect("shadowing should compile");
+    assert_eq!(output.warnings.len(), 1);
+    assert!(output.warnings[0].contains("'x' shadows"));
+}
+
+#[test]
+fn test_monomorphization_output_is_deterministic() {
+    let source = r#"
+            struct Box<T> { T value }
+            struct Pair<A, B> { A first  B second }
+
+            T identity<T>(T x) { return x; }
+
+            int main() {
+                var a: Box<int>? = null;
+                var b: Box<string>? = null;
+                var c: Box<bool>? = null;
+                var d: Pair<int, string>? = null;
+                var e: Pair<string, bool>? = null;
+                var f: Box<float>? = null;
+                var x = identity::<int>(1);
+                var y = identity::<string>("s");
+                var z = identity::<bool>(true);
+                return x;
+            }
+        "#;
+
+    let first = compile(source).expect("compilation failed").ir;
+    for _ in 0..4 {
+        let again = compile(source).expect("compilation failed").ir;
+        assert_eq!(first, again, "IR differs between compilations");
+    }
+}
+
+#[test]
+fn test_infinitely_expanding_generic_reports_overflow() {
+    let source = r#"
+            struct S<T> {
+                S<Vec<T>>? next
+            }
+
+            int main() {
+                var s: S<int>? = null
+                return 0
+            }
+        "#;
+
+    let Err(CompileError::SemanticErrors(errors)) = compile(source) else {
+        panic!("expected monomorphization overflow");
+    };
+    let chain = errors
+        .iter()
+        .find_map(|e| match e {
+            SemanticError::MonomorphizationOverflow { chain, limit, .. } => {
+                assert_eq!(*limit, lency_monomorph::DEFAULT_MAX_DEPTH);
+                Some(chain)
+            }
+            _ => None,
+        })
+        .expect("expected MonomorphizationOverflow");
+    assert_eq!(chain.len(), lency_monomorph::DEFAULT_MAX_DEPTH + 1);
+    assert_eq!(chain[0], "S<int>");
+    assert_eq!(chain[1], "S<Vec<int>>");
+}
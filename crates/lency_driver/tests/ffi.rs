@@ -0,0 +1,121 @@
+mod common;
+
+use common::{run_with_lli, run_with_lli_args};
+use lency_driver::compile;
+
+const EXTERN_C_STRUCTS_SOURCE: &str = r#"
+    extern struct Rect {
+        int w
+        int h
+    }
+
+    extern struct Small {
+        i32 a
+        i32 b
+        i8 c
+    }
+
+    extern struct Big {
+        int a
+        int b
+        int c
+    }
+
+    extern "C" int rect_area(Rect r);
+    extern "C" Rect rect_scale(Rect r, int k);
+    extern "C" int small_sum(Small s);
+    extern "C" Big big_make(int a);
+    extern "C" int big_sum(Big b);
+
+    int main() {
+        var scaled = rect_scale(Rect { w: 2, h: 3 }, 10)
+        print(scaled.w + scaled.h)
+        var small = Small { a: 1, b: 2, c: 3 }
+        var big = big_make(10)
+        return rect_area(Rect { w: 6, h: 7 }) + small_sum(small) + big_sum(big) - 39
+    }
+"#;
+
+const EXTERN_C_STRUCTS_SHIM: &str = r#"
+    #include <stdint.h>
+    typedef struct { int64_t w, h; } Rect;
+    typedef struct { int32_t a, b; int8_t c; } Small;
+    typedef struct { int64_t a, b, c; } Big;
+    int64_t rect_area(Rect r) { return r.w * r.h; }
+    Rect rect_scale(Rect r, int64_t k) { Rect o = { r.w * k, r.h * k }; return o; }
+    int64_t small_sum(Small s) { return s.a + s.b + s.c; }
+    Big big_make(int64_t a) { Big b = { a, a + 1, a + 2 }; return b; }
+    int64_t big_sum(Big b) { return b.a + b.b + b.c; }
+"#;
+
+#[test]
+fn test_extern_c_structs_follow_c_abi() {
+    let output = compile(EXTERN_C_STRUCTS_SOURCE).expect("compilation failed");
+    // 不超过 16 字节的结构体拆成寄存器标量，更大的经 byval / sret 指针传递
+    for expected in [
+        "declare i64 @rect_area(i64, i64)",
+        "declare { i64, i64 } @rect_scale(i64, i64, i64)",
+        "declare i64 @small_sum(i64, i32)",
+        "declare void @big_make(%Big* sret(%Big), i64)",
+        "declare i64 @big_sum(%Big* byval(%Big) align 8)",
+    ] {
+        assert!(output.ir.contains(expected), "missing `{}`", expected);
+    }
+}
+
+#[test]
+#[cfg_attr(not(feature = "lli-tests"), ignore = "requires lli")]
+fn test_extern_c_structs_follow_c_abi_executes() {
+    let output = compile(EXTERN_C_STRUCTS_SOURCE).expect("compilation failed");
+
+    // 用 C 编译器生成的目标文件作为对照，检查双方对结构体的传递方式一致
+    let dir = std::env::temp_dir();
+    let shim_c = dir.join(format!("lency_c_abi_shim_{}.c", std::process::id()));
+    let shim_o = shim_c.with_extension("o");
+    std::fs::write(&shim_c, EXTERN_C_STRUCTS_SHIM).unwrap();
+    let compiled = std::process::Command::new("cc")
+        .args(["-c", "-fPIC", "-o"])
+        .arg(&shim_o)
+        .arg(&shim_c)
+        .status()
+        .is_ok_and(|status| status.success());
+    let _ = std::fs::remove_file(&shim_c);
+    assert!(compiled, "failed to compile the C shim with `cc`");
+
+    let extra_object = format!("--extra-object={}", shim_o.display());
+    let result = run_with_lli_args(&output.ir, "c_abi", &[extra_object]);
+    let _ = std::fs::remove_file(&shim_o);
+    let stdout = String::from_utf8_lossy(&result.stdout);
+    assert_eq!(result.status.code(), Some(42), "stdout: {}", stdout);
+    assert!(stdout.starts_with("50"), "stdout: {}", stdout);
+}
+
+const VARIADIC_EXTERN_PRINTF_SOURCE: &str = r#"
+    extern int printf(string, ...);
+
+    int main() {
+        var written = printf("%d %s %.1f %d\n", 42, "ok", 2.5, true)
+        return written
+    }
+"#;
+
+#[test]
+fn test_variadic_extern_printf() {
+    let output = compile(VARIADIC_EXTERN_PRINTF_SOURCE).expect("compilation failed");
+    // 沿用运行时的 printf 声明，可变参数部分 bool 提升为 i32
+    assert!(
+        output.ir.contains("call i32 (i8*, ...) @printf"),
+        "IR: {}",
+        output.ir
+    );
+}
+
+#[test]
+#[cfg_attr(not(feature = "lli-tests"), ignore = "requires lli")]
+fn test_variadic_extern_printf_executes() {
+    let output = compile(VARIADIC_EXTERN_PRINTF_SOURCE).expect("compilation failed");
+    let result = run_with_lli(&output.ir, "variadic_printf");
+    let stdout = String::from_utf8_lossy(&result.stdout);
+    assert_eq!(stdout, "42 ok 2.5 1\n");
+    assert_eq!(result.status.code(), Some(12));
+}
@@ -0,0 +1,270 @@
+mod common;
+
+use common::run_with_lli;
+use lency_driver::compile;
+
+const GENERIC_ENUM_INSTANCES_HAVE_OWN_LAYOUT_SOURCE: &str = r#"
+    enum Maybe<T> {
+        Some(T),
+        None
+    }
+
+    int unwrap_or(Maybe<int> m, int fallback) {
+        return match m {
+            case Maybe.Some(v) => v,
+            case Maybe.None => fallback
+        }
+    }
+
+    string describe(Maybe<string> m) {
+        return match m {
+            case Maybe.Some(s) => s,
+            case Maybe.None => "none"
+        }
+    }
+
+    int main() {
+        var a = Maybe::<int>.Some(40)
+        var b = Maybe::<string>.Some("hi")
+        print(describe(b))
+        print(describe(Maybe::<string>.None))
+        return unwrap_or(a, 0) + unwrap_or(Maybe::<int>.None, 2)
+    }
+"#;
+
+#[test]
+fn test_generic_enum_instances_have_own_layout() {
+    let output =
+        compile(GENERIC_ENUM_INSTANCES_HAVE_OWN_LAYOUT_SOURCE).expect("compilation failed");
+    // 每个实例各自生成布局和构造函数
+    for expected in [
+        "%Maybe__int = type { i64, [8 x i8] }",
+        "%Maybe__string = type { i64, [8 x i8] }",
+        "define %Maybe__int* @Maybe__int_Some(i64",
+        "define %Maybe__string* @Maybe__string_Some(i8*",
+    ] {
+        assert!(output.ir.contains(expected), "missing `{}`", expected);
+    }
+}
+
+#[test]
+#[cfg_attr(not(feature = "lli-tests"), ignore = "requires lli")]
+fn test_generic_enum_instances_have_own_layout_executes() {
+    let output =
+        compile(GENERIC_ENUM_INSTANCES_HAVE_OWN_LAYOUT_SOURCE).expect("compilation failed");
+    let result = run_with_lli(&output.ir, "generic_enum");
+    let stdout = String::from_utf8_lossy(&result.stdout);
+    assert_eq!(result.status.code(), Some(42), "stdout: {}", stdout);
+    assert!(stdout.starts_with("hinone"), "stdout: {}", stdout);
+}
+
+const TRAIT_OBJECTS_DISPATCH_THROUGH_VTABLE_SOURCE: &str = r#"
+    trait Printable {
+        string describe();
+
+        int weight() {
+            return 1
+        }
+    }
+
+    struct Dog {
+        string name
+    }
+
+    impl Printable for Dog {
+        string describe() {
+            return "dog " + this.name
+        }
+    }
+
+    struct Crate {
+        int size
+    }
+
+    impl Printable for Crate {
+        string describe() {
+            return "crate"
+        }
+
+        int weight() {
+            return this.size
+        }
+    }
+
+    int main() {
+        var items: Vec<dyn Printable> = vec![]
+        items.push(Dog { name: "rex" })
+        items.push(Crate { size: 41 })
+        print(items.get(0).describe())
+        return items.get(0).weight() + items.get(1).weight()
+    }
+"#;
+
+#[test]
+fn test_trait_objects_dispatch_through_vtable() {
+    let output = compile(TRAIT_OBJECTS_DISPATCH_THROUGH_VTABLE_SOURCE).expect("compilation failed");
+    // 每个 (trait, 类型) 组合各有一张虚表
+    for expected in [
+        "@vtable.Printable.Dog",
+        "@vtable.Printable.Crate",
+        "%dyn_call",
+    ] {
+        assert!(output.ir.contains(expected), "missing `{}`", expected);
+    }
+}
+
+#[test]
+#[cfg_attr(not(feature = "lli-tests"), ignore = "requires lli")]
+fn test_trait_objects_dispatch_through_vtable_executes() {
+    let output = compile(TRAIT_OBJECTS_DISPATCH_THROUGH_VTABLE_SOURCE).expect("compilation failed");
+    let result = run_with_lli(&output.ir, "trait_object");
+    let stdout = String::from_utf8_lossy(&result.stdout);
+    assert_eq!(result.status.code(), Some(42), "stdout: {}", stdout);
+    assert!(stdout.starts_with("dog rex"), "stdout: {}", stdout);
+}
+
+const NULLABLE_TRAIT_OBJECTS_SOURCE: &str = r#"
+    trait Shape { int area(); }
+    struct Square { int side }
+    impl Shape for Square { int area() { return this.side * this.side } }
+
+    int count(dyn Shape? s) {
+        if s == null {
+            return 0
+        }
+        return 1
+    }
+
+    int main() {
+        var missing: Square? = null
+        var present: Square? = Square { side: 3 }
+        var shapes: Vec<dyn Shape> = vec![Square { side: 2 }, Square { side: 4 }]
+        return count(missing) * 100 + count(present) * 10 + count(Square { side: 1 }) + shapes.get(1).area()
+    }
+"#;
+
+#[test]
+fn test_nullable_trait_objects_coerce() {
+    let output = compile(NULLABLE_TRAIT_OBJECTS_SOURCE).expect("compilation failed");
+    // 可空结构体只在非空时构造胖指针
+    assert!(output.ir.contains("%dyn_is_null = icmp eq %Square*"));
+    assert!(output.ir.contains("@vtable.Shape.Square"));
+}
+
+#[test]
+#[cfg_attr(not(feature = "lli-tests"), ignore = "requires lli")]
+fn test_nullable_trait_objects_coerce_executes() {
+    let output = compile(NULLABLE_TRAIT_OBJECTS_SOURCE).expect("compilation failed");
+    let result = run_with_lli(&output.ir, "nullable_trait_object");
+    // 0 * 100 + 1 * 10 + 1 + 4 * 4
+    assert_eq!(result.status.code(), Some(27));
+}
+
+const PRIMITIVE_IMPL_METHODS_DISPATCH_SOURCE: &str = r#"
+    impl int {
+        int double() {
+            return this * 2
+        }
+    }
+
+    impl float {
+        float half() {
+            return this / 2.0
+        }
+    }
+
+    int main() {
+        var x = 10
+        var f = 5.0
+        return x.double() + (5).double() + f.half() as int
+    }
+"#;
+
+#[test]
+fn test_primitive_impl_methods_dispatch() {
+    let output = compile(PRIMITIVE_IMPL_METHODS_DISPATCH_SOURCE).expect("compilation failed");
+    // 接收者按值作为第一个参数传给 int_double / float_half
+    assert!(
+        output.ir.contains("call i64 @int_double(i64"),
+        "IR: {}",
+        output.ir
+    );
+    assert!(
+        output.ir.contains("call double @float_half(double"),
+        "IR: {}",
+        output.ir
+    );
+}
+
+#[test]
+#[cfg_attr(not(feature = "lli-tests"), ignore = "requires lli")]
+fn test_primitive_impl_methods_dispatch_executes() {
+    let output = compile(PRIMITIVE_IMPL_METHODS_DISPATCH_SOURCE).expect("compilation failed");
+    let result = run_with_lli(&output.ir, "primitive_impl_methods");
+    assert_eq!(result.status.code(), Some(32));
+}
+
+// 外层实例名超过截断阈值，结构体名是哈希名；方法声明与调用必须得到同一个符号
+const METHOD_ON_LONG_MONOMORPHIZED_STRUCT_LINKS_SOURCE: &str = r#"
+    struct LongNamedContainer<T> { T value }
+
+    impl<T> LongNamedContainer<T> {
+        T get() { return this.value }
+    }
+
+    int main() {
+        var inner = LongNamedContainer<int> { value: 7 }
+        var outer = LongNamedContainer<LongNamedContainer<int>> { value: inner }
+        return outer.get().get()
+    }
+"#;
+
+#[test]
+fn test_method_on_long_monomorphized_struct_links() {
+    let output =
+        compile(METHOD_ON_LONG_MONOMORPHIZED_STRUCT_LINKS_SOURCE).expect("compilation failed");
+    assert!(output.ir.contains("@LongNamedContainer__int_get("));
+}
+
+#[test]
+#[cfg_attr(not(feature = "lli-tests"), ignore = "requires lli")]
+fn test_method_on_long_monomorphized_struct_links_executes() {
+    let output =
+        compile(METHOD_ON_LONG_MONOMORPHIZED_STRUCT_LINKS_SOURCE).expect("compilation failed");
+    let result = run_with_lli(&output.ir, "long_monomorphized_method");
+    assert_eq!(result.status.code(), Some(7));
+}
+
+const CONST_STRUCT_TABLE_IS_STATIC_SOURCE: &str = r#"
+    struct Point {
+        int x
+        int y
+    }
+
+    const [2]Point TABLE = [Point { x: 1, y: 2 }, Point { x: 3, y: 4 }]
+
+    int main() {
+        var i = 1
+        return TABLE[i].x
+    }
+"#;
+
+#[test]
+fn test_const_struct_table_is_static() {
+    let output = compile(CONST_STRUCT_TABLE_IS_STATIC_SOURCE).expect("compilation failed");
+    // 整张表是静态初始化的常量，不需要 __lency_init
+    assert!(
+        output.ir.contains("@TABLE = constant [2 x %Point*]"),
+        "IR: {}",
+        output.ir
+    );
+    assert!(!output.ir.contains("__lency_init"), "IR: {}", output.ir);
+}
+
+#[test]
+#[cfg_attr(not(feature = "lli-tests"), ignore = "requires lli")]
+fn test_const_struct_table_is_static_executes() {
+    let output = compile(CONST_STRUCT_TABLE_IS_STATIC_SOURCE).expect("compilation failed");
+    let result = run_with_lli(&output.ir, "const_struct_table");
+    assert_eq!(result.status.code(), Some(3));
+}
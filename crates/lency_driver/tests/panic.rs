@@ -0,0 +1,184 @@
+mod common;
+
+use common::run_with_lli;
+use lency_driver::{
+    compile, compile_file, compile_with_options, CodegenOptions, CompilationOutput,
+};
+
+#[test]
+fn test_compile_panic_aborts_with_message() {
+    let source = r#"
+            int pick(int x) {
+                if x > 0 {
+                    return x
+                }
+                panic("negative input")
+            }
+
+            int main() {
+                return pick(-1)
+            }
+        "#;
+
+    let output = compile(source).expect("compilation failed");
+    // panic 调用 __lency_panic(msg, line) 后以 unreachable 结束当前块
+    assert!(output.ir.contains("negative input"));
+    assert!(output.ir.contains("call void @__lency_panic("));
+    assert!(output.ir.contains("after_panic:"));
+}
+
+const PANIC_LOCATION_SOURCE: &str =
+    "int main() {\n    var arr = [1, 2, 3]\n    var i = 5\n    return arr[i]\n}\n";
+
+// 驱动测试的工作目录为 crates/lency_driver，导入路径相对于它解析
+const PANIC_IN_IMPORT_SOURCE: &str =
+    "import tests.fixtures.panicky\n\nint main() {\n    return item_at(7)\n}\n";
+
+/// 把源码写入临时文件后编译，返回生成结果与文件路径
+fn compile_temp_file(name: &str, source: &str) -> (CompilationOutput, String) {
+    let path = std::env::temp_dir().join(format!("lency_{}_{}.lcy", name, std::process::id()));
+    std::fs::write(&path, source).unwrap();
+    let path_str = path.to_str().unwrap().to_string();
+    let output = compile_file(&path_str);
+    let _ = std::fs::remove_file(&path);
+    (output.expect("compilation failed"), path_str)
+}
+
+#[test]
+fn test_runtime_panic_reports_file_and_line() {
+    let (output, _) = compile_temp_file("panic_loc", PANIC_LOCATION_SOURCE);
+    assert!(output.ir.contains("panic at %s:%d: %s"));
+}
+
+#[test]
+#[cfg_attr(not(feature = "lli-tests"), ignore = "requires lli")]
+fn test_runtime_panic_reports_file_and_line_executes() {
+    let (output, path) = compile_temp_file("panic_loc", PANIC_LOCATION_SOURCE);
+    let result = run_with_lli(&output.ir, "panic_location");
+    let stdout = String::from_utf8_lossy(&result.stdout);
+    assert_eq!(result.status.code(), Some(1));
+    assert_eq!(
+        stdout,
+        format!("panic at {}:4: Index Out of Bounds\n", path)
+    );
+}
+
+#[test]
+fn test_runtime_panic_in_imported_function_reports_its_file() {
+    let (output, _) = compile_temp_file("panic_import", PANIC_IN_IMPORT_SOURCE);
+    // 导入函数使用单独的 panic 函数，报告它自己的源文件
+    assert!(output.ir.contains("tests/fixtures/panicky.lcy"));
+    assert!(output.ir.contains("define void @__lency_panic.1("));
+}
+
+#[test]
+#[cfg_attr(not(feature = "lli-tests"), ignore = "requires lli")]
+fn test_runtime_panic_in_imported_function_reports_its_file_executes() {
+    let (output, _) = compile_temp_file("panic_import", PANIC_IN_IMPORT_SOURCE);
+    let result = run_with_lli(&output.ir, "panic_import_location");
+    let stdout = String::from_utf8_lossy(&result.stdout);
+    assert_eq!(result.status.code(), Some(1));
+    assert_eq!(
+        stdout,
+        "panic at tests/fixtures/panicky.lcy:3: Index Out of Bounds\n"
+    );
+}
+
+#[test]
+fn test_compile_assert_branches_to_panic() {
+    let source = r#"
+            int main() {
+                var x = 3
+                assert(x > 0, "x must be positive")
+                assert(x < 0, "x must be negative")
+                return 0
+            }
+        "#;
+
+    let output = compile(source).expect("compilation failed");
+    // 失败分支调用 __lency_panic，成功分支继续执行
+    assert!(output.ir.contains("x must be negative"));
+    assert!(output.ir.contains("Assertion failed: "));
+    assert!(output.ir.contains("assert_fail:"));
+    assert!(output.ir.contains("assert_cont:"));
+    assert!(output.ir.contains("call void @__lency_panic("));
+}
+
+#[test]
+fn test_stack_guard_counts_call_depth() {
+    let source = r#"
+            int depth(int n) {
+                if n == 0 {
+                    return 0
+                }
+                return depth(n - 1) + 1
+            }
+        "#;
+
+    let plain = compile(source).expect("compilation failed");
+    assert!(!plain.ir.contains("__lency_stack_depth"));
+
+    let options = CodegenOptions {
+        stack_guard: Some(500),
+        ..CodegenOptions::default()
+    };
+    let output = compile_with_options(source, &options).expect("compilation failed");
+    assert!(output
+        .ir
+        .contains("@__lency_stack_depth = internal global i64 0"));
+    assert!(output.ir.contains("icmp ugt i64 %stack_depth_inc, 500"));
+    assert!(output
+        .ir
+        .contains("stack overflow: call depth exceeded 500 in function 'depth'"));
+    // 两条 return 路径都要恢复深度计数
+    assert_eq!(output.ir.matches("store i64 %stack_depth_dec").count(), 2);
+}
+
+#[test]
+#[cfg_attr(not(feature = "lli-tests"), ignore = "requires lli")]
+fn test_stack_guard_aborts_runaway_recursion() {
+    let source = r#"
+            bool is_even(int n) {
+                if n == 0 {
+                    return true
+                }
+                return is_odd(n - 1)
+            }
+
+            bool is_odd(int n) {
+                if n == 0 {
+                    return false
+                }
+                return is_even(n - 1)
+            }
+
+            int forever(int n) {
+                return forever(n + 1) + 1
+            }
+
+            int main() {
+                // 互递归深度 600 < 1000；连续调用三次验证出口处正确递减
+                var i = 0
+                while i < 3 {
+                    print(is_even(600))
+                    i = i + 1
+                }
+                return forever(0)
+            }
+        "#;
+
+    let options = CodegenOptions {
+        stack_guard: Some(1000),
+        ..CodegenOptions::default()
+    };
+    let output = compile_with_options(source, &options).expect("compilation failed");
+    let result = run_with_lli(&output.ir, "stack_guard");
+    let stdout = String::from_utf8_lossy(&result.stdout);
+    assert_eq!(result.status.code(), Some(1), "stdout: {}", stdout);
+    assert!(stdout.starts_with("truetruetrue"), "stdout: {}", stdout);
+    assert!(
+        stdout.contains("stack overflow: call depth exceeded 1000 in function 'forever'"),
+        "stdout: {}",
+        stdout
+    );
+}
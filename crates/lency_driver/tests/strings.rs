@@ -0,0 +1,190 @@
+mod common;
+
+use common::run_with_lli;
+use lency_driver::compile;
+
+#[test]
+fn test_string_len_and_index_count_chars() {
+    let source = r#"
+            int main() {
+                var s = "héllo"
+                var first = s[1]
+                return len(s) + byte_len(s) + first
+            }
+        "#;
+
+    let output = compile(source).expect("compilation failed");
+    // s[i] 按字符取码点，不再直接按字节 GEP
+    assert!(output.ir.contains("call i64 @lency_string_char_at(i8* %s"));
+    assert!(output.ir.contains("call i64 @lency_string_len("));
+    assert!(output.ir.contains("call i64 @lency_string_byte_len("));
+}
+
+#[test]
+fn test_string_bytes_round_trip() {
+    let source = r#"
+            int main() {
+                var bytes = string_bytes("héllo")
+                var tail = bytes_to_string(bytes, 1, 2)
+                return bytes.len() + bytes[0] + len(tail)
+            }
+        "#;
+
+    let output = compile(source).expect("compilation failed");
+    // 字节数组是普通的 Vec<int>，下标访问走 Vec 的 O(1) 路径
+    assert!(output.ir.contains("call i8* @lency_string_bytes("));
+    assert!(output.ir.contains("call i8* @lency_string_from_bytes("));
+    assert!(output.ir.contains("@lency_vec_get"));
+}
+
+#[test]
+fn test_string_index_out_of_range_panics() {
+    let source = r#"
+            int main() {
+                var s = "héllo"
+                var i = 5
+                return s[i]
+            }
+        "#;
+
+    let output = compile(source).expect("compilation failed");
+    // "héllo" 有 5 个字符、6 个字节：s[5] 越界，char_at 返回 -1 后走越界 panic
+    assert!(output
+        .ir
+        .contains("%char_at = call i64 @lency_string_char_at("));
+    assert!(output.ir.contains("icmp uge i64 %char_at, 1114112"));
+    assert!(output.ir.contains("Index Out of Bounds"));
+    // 非法 UTF-8 返回 -2，单独报错而不是当作越界
+    assert!(output.ir.contains("icmp eq i64 %char_at, -2"));
+    assert!(output.ir.contains("Invalid UTF-8 in string"));
+}
+
+#[test]
+fn test_parse_int_builds_result_from_out_param() {
+    let source = r#"
+            struct Error { string message }
+            int! read(string s) {
+                var n = parse_int(s)?
+                return Ok(n * 2)
+            }
+        "#;
+
+    let output = compile(source).expect("compilation failed");
+    assert!(output.ir.contains("call i64 @lency_parse_int("));
+    // 失败分支构造 Error { message }
+    assert!(output.ir.contains("parse_int_err:"));
+    assert!(output.ir.contains("cannot parse '"));
+}
+
+#[test]
+fn test_primitive_to_string_dispatches_by_receiver() {
+    let source = r#"
+            string describe(int n, float f, bool b) {
+                return n.to_string() + f.to_string() + b.to_string()
+            }
+        "#;
+
+    let output = compile(source).expect("compilation failed");
+    assert!(output.ir.contains("call i8* @lency_int_to_string(i64 %n"));
+    assert!(output
+        .ir
+        .contains("call i8* @lency_float_to_string(double %f"));
+    // bool 直接在 "true"/"false" 常量间选择
+    assert!(output.ir.contains("%bool_to_str = select i1 %b"));
+}
+
+#[test]
+fn test_fmt_macro_concatenates_converted_args() {
+    let source = r#"
+            string describe(int n, float f, bool b, string s) {
+                return fmt!("n={}, f={}, b={}{}", n, f, b, s)
+            }
+        "#;
+
+    let output = compile(source).expect("compilation failed");
+    // 模板按占位符切成常量片段，非 string 实参先转换再拼接
+    for piece in ["c\"n=\\00\"", "c\", f=\\00\"", "c\", b=\\00\""] {
+        assert!(output.ir.contains(piece), "missing piece {}", piece);
+    }
+    assert!(output.ir.contains("call i8* @lency_int_to_string(i64 %n"));
+    assert!(output
+        .ir
+        .contains("call i8* @lency_float_to_string(double %f"));
+    assert!(output.ir.contains("%bool_to_str = select i1 %b"));
+    assert!(output.ir.contains("%concat_result = call i8* @malloc"));
+}
+
+#[test]
+fn test_print_composite_values() {
+    let source = r#"
+            struct Point {
+                int x
+                int y
+            }
+            struct Node {
+                int v
+                Node? next
+            }
+            enum Shape { Circle(int), Empty }
+            void show(Point p, Node n, Shape s, Vec<Point> ps) {
+                print(p)
+                print(n)
+                print(s)
+                print(ps)
+            }
+        "#;
+
+    let output = compile(source).expect("compilation failed");
+    // 结构体 / 枚举各生成一个打印函数，Vec 元素复用它
+    assert!(output
+        .ir
+        .contains("define private void @__lency_print_Point(%Point*"));
+    assert!(output.ir.contains("call void @__lency_print_Point("));
+    assert!(output.ir.contains(r#"c"Point { \00""#));
+    assert!(output.ir.contains(r#"c", y: \00""#));
+    assert!(output.ir.contains(r#"c" }\00""#));
+    // 递归类型调用自身，可空字段为 null 时打印 null
+    assert!(output.ir.contains("call void @__lency_print_Node(%Node*"));
+    assert!(output.ir.contains(r#"c"null\00""#));
+    assert!(output
+        .ir
+        .contains("define private void @__lency_print_Shape(%Shape*"));
+    assert!(output.ir.contains(r#"c"Circle\00""#));
+    assert!(output.ir.contains(r#"c"[\00""#));
+}
+
+const PRINT_NULLABLE_INT_SOURCE: &str = r#"
+    int? find(int x) {
+        if x > 0 {
+            return x
+        }
+        return null
+    }
+
+    int main() {
+        var hit = find(3)
+        var miss = find(-1)
+        print(hit)
+        print(" ")
+        print(miss)
+        print(" ")
+        print(null)
+        return 0
+    }
+"#;
+
+#[test]
+fn test_print_nullable_int() {
+    let output = compile(PRINT_NULLABLE_INT_SOURCE).expect("compilation failed");
+    // 装箱的 int? 先判空，非空时解引用后按 int 打印
+    assert!(output.ir.contains("%print_is_null = icmp eq i64*"));
+    assert!(output.ir.contains("%print_unboxed = load i64, i64*"));
+}
+
+#[test]
+#[cfg_attr(not(feature = "lli-tests"), ignore = "requires lli")]
+fn test_print_nullable_int_executes() {
+    let output = compile(PRINT_NULLABLE_INT_SOURCE).expect("compilation failed");
+    let result = run_with_lli(&output.ir, "print_nullable_int");
+    assert_eq!(String::from_utf8_lossy(&result.stdout), "3 null null");
+}
@@ -0,0 +1,216 @@
+use lency_driver::compile;
+
+#[test]
+fn test_struct_literal_stores_field_default() {
+    let source = r#"
+            struct Config {
+                int port
+                int retries = 7
+            }
+            int main() {
+                var c = Config { port: 80 }
+                return c.retries
+            }
+        "#;
+
+    let output = compile(source).expect("compilation failed");
+    assert!(output.ir.contains("%field_port ="));
+    assert!(output.ir.contains("store i64 7, i64* %field_retries"));
+}
+
+#[test]
+fn test_struct_equality_compares_fields() {
+    let source = r#"
+            struct Point {
+                int x
+                int y
+            }
+            struct Label {
+                string text
+                Point pos
+                int? weight
+            }
+            bool same(Label a, Label b) { return a == b }
+            bool differ(Point a, Point b) { return a != b }
+        "#;
+
+    let output = compile(source).expect("compilation failed");
+    // 逐字段短路比较，嵌套结构体与可空字段按值比较
+    assert!(output.ir.contains("%struct_eq = phi i1"));
+    assert!(output.ir.contains("br i1 %eqtmp, label %struct_eq_next"));
+    assert!(output.ir.contains("@strcmp"));
+    assert!(output.ir.contains("%nullable_eq = phi i1"));
+    assert!(output.ir.contains("%struct_netmp = xor i1"));
+}
+
+#[test]
+fn test_enum_constructor_stores_explicit_discriminant() {
+    let source = r#"
+            enum Color { Red = 1, Green = 2, Blue = 4, Alpha }
+
+            int main() {
+                var c = Color.Blue
+                return c.discriminant()
+            }
+        "#;
+
+    let output = compile(source).expect("compilation failed");
+    let ctor_body = |name: &str| -> String {
+        let start = output
+            .ir
+            .find(&format!("@{}(", name))
+            .unwrap_or_else(|| panic!("missing constructor {}", name));
+        let end = output.ir[start..].find("\n}").unwrap() + start;
+        output.ir[start..end].to_string()
+    };
+    assert!(ctor_body("Color_Red").contains("store i64 1,"));
+    assert!(ctor_body("Color_Blue").contains("store i64 4,"));
+    // 未指定的变体接着前一个值递增
+    assert!(ctor_body("Color_Alpha").contains("store i64 5,"));
+}
+
+#[test]
+fn test_chained_methods_thread_returned_receiver() {
+    let source = r#"
+            struct Point {
+                int x
+                int y
+            }
+
+            impl Point {
+                Point set_x(int v) {
+                    this.x = v
+                    return this
+                }
+                Point set_y(int v) {
+                    this.y = v
+                    return this
+                }
+            }
+
+            int main() {
+                var p = Point { x: 0, y: 0 }.set_x(1).set_y(2)
+                return p.x + p.y
+            }
+        "#;
+
+    let output = compile(source).expect("compilation failed");
+    // set_x 的返回值直接作为 set_y 的 this 参数，不经过额外拷贝
+    assert!(output
+        .ir
+        .contains("%call_method = call %Point* @Point_set_x(%Point*"));
+    assert!(output
+        .ir
+        .contains("call %Point* @Point_set_y(%Point* %call_method, i64 2)"));
+}
+
+#[test]
+fn test_result_helpers_read_tag_and_payload() {
+    let source = r#"
+            struct Error {
+                string message
+            }
+
+            int! half(int x) {
+                if x % 2 != 0 {
+                    return Err(Error { message: "odd" })
+                }
+                return Ok(x / 2)
+            }
+
+            int main() {
+                var r = half(10)
+                var doubled = r.map(|int v| => v * 2)
+                if doubled.is_err() {
+                    return 1
+                }
+                return r.unwrap_or(-1)
+            }
+        "#;
+
+    let output = compile(source).expect("compilation failed");
+    // map 在 Ok 分支调用闭包，Err 分支原样复制错误值
+    assert!(output.ir.contains("call i64 @__closure_"));
+    assert!(output.ir.contains("result_map_err:"));
+    assert!(output.ir.contains("%is_err = xor i1"));
+    assert!(output.ir.contains("select i1 %is_ok"));
+}
+
+#[test]
+fn test_nullable_value_or_branches_on_null() {
+    let source = r#"
+            string? find(int k) {
+                if k > 0 {
+                    return "hit"
+                }
+                return null
+            }
+
+            int main() {
+                print(find(0).value_or("none"))
+                return 0
+            }
+        "#;
+
+    let output = compile(source).expect("compilation failed");
+    // null 时走默认值分支，两路结果在 phi 汇合
+    assert!(output
+        .ir
+        .contains("%nullable_is_null = icmp eq i8* %calltmp, null"));
+    assert!(output.ir.contains("%nullable_result = phi i8*"));
+}
+
+#[test]
+fn test_associated_function_returns_struct_value() {
+    let source = r#"
+            struct Point {
+                int x
+                int y
+            }
+
+            impl Point {
+                Point origin() {
+                    return Point { x: 0, y: 0 }
+                }
+            }
+
+            int main() {
+                var p = Point::origin()
+                return p.x
+            }
+        "#;
+
+    let output = compile(source).expect("compilation failed");
+    // 关联函数复用方法符号，this 位置传入空指针
+    assert!(output
+        .ir
+        .contains("call %Point* @Point_origin(%Point* null)"));
+}
+
+#[test]
+fn test_function_name_passed_as_runtime_comparator() {
+    let source = r#"
+            int descending(int a, int b) {
+                return b - a
+            }
+
+            int main() {
+                var v = vec![3, 1, 2]
+                v.sort_by(descending)
+                var cmp: int(int, int) = descending
+                return cmp(1, 2)
+            }
+        "#;
+
+    let output = compile(source).expect("compilation failed");
+    // 函数名取地址后以函数指针类型跨越 FFI 边界
+    assert!(output
+        .ir
+        .contains("declare void @lency_vec_sort_by(i8*, i64 (i64, i64)*)"));
+    assert!(output
+        .ir
+        .contains("@lency_vec_sort_by(i8* %vec, i64 (i64, i64)* @descending)"));
+    assert!(output
+        .ir
+        .contains("store i64 (i64, i64)* @descending, i64 (i64, i64)** %cmp"));
+}
@@ -0,0 +1,36 @@
+//! 文档分析
+//!
+//! 对单个文档执行词法、语法和语义分析，保留字节级 Span 供 LSP 请求使用。
+
+use lency_sema::{Resolver, ScopeStack, SemanticError, analyze_with_resolver};
+use lency_syntax::ast::Program;
+use std::path::Path;
+
+/// 单个文档的分析结果
+pub struct Analysis {
+    pub program: Program,
+    pub scopes: ScopeStack,
+    pub errors: Vec<SemanticError>,
+}
+
+/// 解析源码，Span 为字节偏移
+pub fn parse(source: &str) -> Option<Program> {
+    lency_syntax::parser::parse(source).ok()
+}
+
+/// 分析文档；语法错误时返回 None，语义错误不影响符号表的构建
+pub fn analyze(source: &str, root_dir: Option<&Path>) -> Option<Analysis> {
+    let mut program = parse(source)?;
+
+    let mut resolver = Resolver::new();
+    if let Some(root) = root_dir {
+        resolver.set_root_dir(root.to_path_buf());
+    }
+    let (result, errors) = analyze_with_resolver(&mut program, resolver);
+
+    Some(Analysis {
+        program,
+        scopes: result.scopes,
+        errors,
+    })
+}
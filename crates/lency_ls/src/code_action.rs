@@ -0,0 +1,59 @@
+//! 代码操作 (textDocument/codeAction)
+//!
+//! 语义错误转换为诊断后，携带替换范围的修复建议直接作为快速修复提供。
+
+use crate::analysis::Analysis;
+use crate::position::{position_to_offset, span_to_range};
+use std::collections::HashMap;
+use tower_lsp::lsp_types::{
+    CodeAction, CodeActionKind, CodeActionOrCommand, Diagnostic, DiagnosticSeverity, Range,
+    TextEdit, Url, WorkspaceEdit,
+};
+
+/// 收集与请求范围相交的诊断上的快速修复；只有带替换范围的建议才会生成操作
+pub fn code_actions(
+    analysis: &Analysis,
+    source: &str,
+    uri: &Url,
+    range: Range,
+) -> Vec<CodeActionOrCommand> {
+    let start = position_to_offset(source, range.start);
+    let end = position_to_offset(source, range.end);
+
+    let mut actions = Vec::new();
+    for error in &analysis.errors {
+        let diag = error.to_diagnostic();
+        let Some(span) = &diag.span else {
+            continue;
+        };
+        if span.start > end || start > span.end {
+            continue;
+        }
+
+        let lsp_diag = Diagnostic {
+            range: span_to_range(source, span),
+            severity: Some(DiagnosticSeverity::ERROR),
+            source: Some("lency".to_string()),
+            message: diag.message.clone(),
+            ..Default::default()
+        };
+        for suggestion in &diag.suggestions {
+            let Some((fix_span, text)) = &suggestion.replacement else {
+                continue;
+            };
+            let edit = TextEdit::new(span_to_range(source, fix_span), text.clone());
+            actions.push(CodeActionOrCommand::CodeAction(CodeAction {
+                title: suggestion.message.clone(),
+                kind: Some(CodeActionKind::QUICKFIX),
+                diagnostics: Some(vec![lsp_diag.clone()]),
+                edit: Some(WorkspaceEdit::new(HashMap::from([(
+                    uri.clone(),
+                    vec![edit],
+                )]))),
+                is_preferred: Some(true),
+                ..Default::default()
+            }));
+        }
+    }
+    actions
+}
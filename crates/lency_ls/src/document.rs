@@ -0,0 +1,111 @@
+//! 文档文本缓冲区
+//!
+//! 保存文本和每行起始偏移，支持按 LSP Range 做增量替换（INCREMENTAL 同步）。
+
+use tower_lsp::lsp_types::{Position, TextDocumentContentChangeEvent};
+
+#[derive(Debug, Clone, Default)]
+pub struct Document {
+    text: String,
+    /// 每行起始的字节偏移，首项恒为 0
+    line_starts: Vec<usize>,
+}
+
+impl Document {
+    pub fn new(text: String) -> Self {
+        let mut doc = Self {
+            text,
+            line_starts: vec![0],
+        };
+        doc.recompute_lines(0);
+        doc
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// 应用一次变更；没有 range 时为整体替换
+    pub fn apply_change(&mut self, change: &TextDocumentContentChangeEvent) {
+        let Some(range) = change.range else {
+            *self = Self::new(change.text.clone());
+            return;
+        };
+
+        let start = self.offset_at(range.start);
+        let end = self.offset_at(range.end).max(start);
+        self.text.replace_range(start..end, &change.text);
+
+        // 仅重算受影响行之后的行起始偏移
+        self.recompute_lines(range.start.line as usize);
+    }
+
+    /// LSP Position (UTF-16 列) -> 字节偏移；越界时截断到行尾 / 文件尾
+    pub fn offset_at(&self, position: Position) -> usize {
+        let Some(&line_start) = self.line_starts.get(position.line as usize) else {
+            return self.text.len();
+        };
+        let line_end = self
+            .line_starts
+            .get(position.line as usize + 1)
+            .map_or(self.text.len(), |next| next - 1);
+
+        let mut utf16 = 0u32;
+        for (i, ch) in self.text[line_start..line_end].char_indices() {
+            if utf16 >= position.character {
+                return line_start + i;
+            }
+            utf16 += ch.len_utf16() as u32;
+        }
+        line_end
+    }
+
+    fn recompute_lines(&mut self, from_line: usize) {
+        let from_line = from_line.min(self.line_starts.len() - 1);
+        self.line_starts.truncate(from_line + 1);
+        let start = self.line_starts[from_line];
+        self.line_starts.extend(
+            self.text[start..]
+                .match_indices('\n')
+                .map(|(i, _)| start + i + 1),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tower_lsp::lsp_types::Range;
+
+    fn edit(sl: u32, sc: u32, el: u32, ec: u32, text: &str) -> TextDocumentContentChangeEvent {
+        TextDocumentContentChangeEvent {
+            range: Some(Range::new(Position::new(sl, sc), Position::new(el, ec))),
+            range_length: None,
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_incremental_edits_match_full_text() {
+        let mut doc = Document::new("int main() {\n    return 0;\n}\n".to_string());
+
+        // 插入新行、修改返回值、删除再插入
+        doc.apply_change(&edit(1, 4, 1, 4, "var x = 1;\n    "));
+        doc.apply_change(&edit(2, 11, 2, 12, "x"));
+        doc.apply_change(&edit(0, 4, 0, 8, "start"));
+
+        assert_eq!(
+            doc.text(),
+            "int start() {\n    var x = 1;\n    return x;\n}\n"
+        );
+        assert_eq!(doc.line_starts, Document::new(doc.text.clone()).line_starts);
+    }
+
+    #[test]
+    fn test_utf16_edit() {
+        let mut doc = Document::new("string s = \"你好\";".to_string());
+        // "你" 之后插入：引号前共 11 个字符 + 引号 + 1 个汉字
+        doc.apply_change(&edit(0, 13, 0, 13, "们"));
+        assert_eq!(doc.text(), "string s = \"你们好\";");
+    }
+}
@@ -0,0 +1,100 @@
+//! 跳转到定义
+//!
+//! 优先使用语义分析记录的引用点 (`ScopeStack::resolution_at`)；
+//! 对于类型注解等未记录引用的位置，回退为按光标下的标识符查找全局符号。
+
+use crate::analysis::Analysis;
+use lency_sema::{Resolution, Symbol, SymbolId};
+use lency_syntax::ast::Span;
+use std::path::PathBuf;
+
+/// 定义位置
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Definition {
+    pub span: Span,
+    /// 定义所在文件；None 表示当前文档
+    pub path: Option<PathBuf>,
+}
+
+/// 查找 `offset` 处标识符的定义
+pub fn find_definition(analysis: &Analysis, source: &str, offset: usize) -> Option<Definition> {
+    let scopes = &analysis.scopes;
+
+    if let Some((_, resolution)) = scopes.resolution_at(offset) {
+        return match resolution {
+            Resolution::Symbol(id) => symbol_definition(analysis, *id),
+            Resolution::Member { owner, name } => member_definition(analysis, *owner, name),
+        };
+    }
+
+    // 回退：类型名等没有记录引用点的位置
+    let ident = identifier_at(source, offset)?;
+    let id = scopes.lookup_id_from(ident, 0)?;
+    symbol_definition(analysis, id)
+}
+
+fn symbol_definition(analysis: &Analysis, id: SymbolId) -> Option<Definition> {
+    let symbol = analysis.scopes.get_symbol(id)?;
+    make_definition(analysis, id, symbol.span().clone())
+}
+
+fn member_definition(analysis: &Analysis, owner: SymbolId, name: &str) -> Option<Definition> {
+    let span = match analysis.scopes.get_symbol(owner)? {
+        Symbol::Struct(s) => s
+            .methods
+            .get(name)
+            .map(|m| m.span.clone())
+            .or_else(|| s.fields.get(name).map(|f| f.span.clone()))?,
+        Symbol::Enum(e) => e.methods.get(name)?.span.clone(),
+        _ => return None,
+    };
+    make_definition(analysis, owner, span)
+}
+
+fn make_definition(analysis: &Analysis, id: SymbolId, span: Span) -> Option<Definition> {
+    // 内置符号没有源码位置
+    if span.is_empty() {
+        return None;
+    }
+    let path = analysis.scopes.symbol_origin(id).map(|p| p.to_path_buf());
+    Some(Definition { span, path })
+}
+
+/// 取 `offset` 处（或紧邻其左侧）的标识符
+pub fn identifier_at(source: &str, offset: usize) -> Option<&str> {
+    let is_ident = |c: char| c.is_alphanumeric() || c == '_';
+    let offset = offset.min(source.len());
+    if !source.is_char_boundary(offset) {
+        return None;
+    }
+
+    let start = source[..offset]
+        .char_indices()
+        .rev()
+        .take_while(|(_, c)| is_ident(*c))
+        .last()
+        .map_or(offset, |(i, _)| i);
+    let end = source[offset..]
+        .char_indices()
+        .find(|(_, c)| !is_ident(*c))
+        .map_or(source.len(), |(i, _)| offset + i);
+
+    let ident = &source[start..end];
+    match ident.chars().next() {
+        Some(c) if !c.is_ascii_digit() => Some(ident),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_identifier_at() {
+        let source = "var foo_bar = 1;";
+        assert_eq!(identifier_at(source, 4), Some("foo_bar"));
+        assert_eq!(identifier_at(source, 11), Some("foo_bar"));
+        assert_eq!(identifier_at(source, 14), None);
+    }
+}
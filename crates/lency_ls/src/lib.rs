@@ -1,3 +1,17 @@
+pub mod analysis;
+pub mod code_action;
+pub mod document;
+pub mod goto;
+pub mod outline;
+pub mod position;
+pub mod references;
+pub mod rename;
+pub mod semantic_tokens;
+
+use document::Document;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use tokio::sync::RwLock;
 use tower_lsp::jsonrpc::Result;
 use tower_lsp::lsp_types::*;
 use tower_lsp::{Client, LanguageServer, LspService, Server};
@@ -5,19 +19,70 @@ use tower_lsp::{Client, LanguageServer, LspService, Server};
 #[derive(Debug)]
 pub struct Backend {
     pub client: Client,
+    /// 已打开文档的最新内容
+    documents: RwLock<HashMap<Url, Document>>,
+    /// 工作区根目录（用于解析 import）
+    root_dir: RwLock<Option<PathBuf>>,
+}
+
+impl Backend {
+    pub fn new(client: Client) -> Self {
+        Self {
+            client,
+            documents: RwLock::new(HashMap::new()),
+            root_dir: RwLock::new(None),
+        }
+    }
+
+    async fn document(&self, uri: &Url) -> Option<String> {
+        let documents = self.documents.read().await;
+        documents.get(uri).map(|doc| doc.text().to_string())
+    }
+
+    /// 取文档内容并执行分析；文档未打开或存在语法错误时返回 None
+    async fn analyze_document(&self, uri: &Url) -> Option<(String, analysis::Analysis)> {
+        let source = self.document(uri).await?;
+        let root = self.root_dir.read().await.clone();
+        let analysis = analysis::analyze(&source, root.as_deref())?;
+        Some((source, analysis))
+    }
 }
 
 #[tower_lsp::async_trait]
 impl LanguageServer for Backend {
-    async fn initialize(&self, _: InitializeParams) -> Result<InitializeResult> {
+    async fn initialize(&self, params: InitializeParams) -> Result<InitializeResult> {
         eprintln!("[LencyLS] Received initialize request");
+        #[allow(deprecated)]
+        let root = params
+            .workspace_folders
+            .as_ref()
+            .and_then(|folders| folders.first())
+            .map(|folder| folder.uri.clone())
+            .or(params.root_uri)
+            .and_then(|uri| uri.to_file_path().ok());
+        *self.root_dir.write().await = root;
+
         Ok(InitializeResult {
             capabilities: ServerCapabilities {
                 text_document_sync: Some(TextDocumentSyncCapability::Kind(
-                    TextDocumentSyncKind::FULL,
+                    TextDocumentSyncKind::INCREMENTAL,
                 )),
                 hover_provider: Some(HoverProviderCapability::Simple(true)),
                 completion_provider: Some(CompletionOptions::default()),
+                definition_provider: Some(OneOf::Left(true)),
+                document_symbol_provider: Some(OneOf::Left(true)),
+                references_provider: Some(OneOf::Left(true)),
+                rename_provider: Some(OneOf::Left(true)),
+                code_action_provider: Some(CodeActionProviderCapability::Simple(true)),
+                semantic_tokens_provider: Some(
+                    SemanticTokensServerCapabilities::SemanticTokensOptions(
+                        SemanticTokensOptions {
+                            legend: semantic_tokens::legend(),
+                            full: Some(SemanticTokensFullOptions::Bool(true)),
+                            ..Default::default()
+                        },
+                    ),
+                ),
                 ..Default::default()
             },
             ..Default::default()
@@ -31,6 +96,32 @@ impl LanguageServer for Backend {
             .await;
     }
 
+    async fn did_open(&self, params: DidOpenTextDocumentParams) {
+        let doc = params.text_document;
+        self.documents
+            .write()
+            .await
+            .insert(doc.uri, Document::new(doc.text));
+    }
+
+    async fn did_change(&self, params: DidChangeTextDocumentParams) {
+        let mut documents = self.documents.write().await;
+        let Some(doc) = documents.get_mut(&params.text_document.uri) else {
+            return;
+        };
+        // INCREMENTAL 同步：按顺序应用每个变更
+        for change in &params.content_changes {
+            doc.apply_change(change);
+        }
+    }
+
+    async fn did_close(&self, params: DidCloseTextDocumentParams) {
+        self.documents
+            .write()
+            .await
+            .remove(&params.text_document.uri);
+    }
+
     async fn hover(&self, params: HoverParams) -> Result<Option<Hover>> {
         let pos = params.text_document_position_params.position;
         eprintln!(
@@ -46,6 +137,124 @@ impl LanguageServer for Backend {
         }))
     }
 
+    async fn goto_definition(
+        &self,
+        params: GotoDefinitionParams,
+    ) -> Result<Option<GotoDefinitionResponse>> {
+        let uri = params.text_document_position_params.text_document.uri;
+        let pos = params.text_document_position_params.position;
+        let Some((source, analysis)) = self.analyze_document(&uri).await else {
+            return Ok(None);
+        };
+
+        let offset = position::position_to_offset(&source, pos);
+        let Some(def) = goto::find_definition(&analysis, &source, offset) else {
+            return Ok(None);
+        };
+
+        let location = match def.path {
+            None => Location::new(uri, position::span_to_range(&source, &def.span)),
+            Some(path) => {
+                let (Ok(target), Ok(text)) =
+                    (Url::from_file_path(&path), std::fs::read_to_string(&path))
+                else {
+                    return Ok(None);
+                };
+                Location::new(target, position::span_to_range(&text, &def.span))
+            }
+        };
+        Ok(Some(GotoDefinitionResponse::Scalar(location)))
+    }
+
+    async fn references(&self, params: ReferenceParams) -> Result<Option<Vec<Location>>> {
+        let uri = params.text_document_position.text_document.uri;
+        let pos = params.text_document_position.position;
+        let Some((source, analysis)) = self.analyze_document(&uri).await else {
+            return Ok(None);
+        };
+
+        let offset = position::position_to_offset(&source, pos);
+        let Some(target) = references::target_at(&analysis, &source, offset) else {
+            return Ok(None);
+        };
+
+        let spans = references::find_references(
+            &analysis,
+            &source,
+            &target,
+            params.context.include_declaration,
+        );
+        let locations = spans
+            .iter()
+            .map(|span| Location::new(uri.clone(), position::span_to_range(&source, span)))
+            .collect();
+        Ok(Some(locations))
+    }
+
+    async fn rename(&self, params: RenameParams) -> Result<Option<WorkspaceEdit>> {
+        let uri = params.text_document_position.text_document.uri;
+        let pos = params.text_document_position.position;
+        let Some((source, analysis)) = self.analyze_document(&uri).await else {
+            return Ok(None);
+        };
+
+        let offset = position::position_to_offset(&source, pos);
+        let spans = rename::rename_spans(&analysis, &source, offset, &params.new_name)
+            .map_err(tower_lsp::jsonrpc::Error::invalid_params)?;
+        let edits = spans
+            .iter()
+            .map(|span| {
+                TextEdit::new(
+                    position::span_to_range(&source, span),
+                    params.new_name.clone(),
+                )
+            })
+            .collect();
+        Ok(Some(WorkspaceEdit::new(HashMap::from([(uri, edits)]))))
+    }
+
+    async fn code_action(&self, params: CodeActionParams) -> Result<Option<CodeActionResponse>> {
+        let uri = params.text_document.uri;
+        let Some((source, analysis)) = self.analyze_document(&uri).await else {
+            return Ok(None);
+        };
+
+        let actions = code_action::code_actions(&analysis, &source, &uri, params.range);
+        Ok(Some(actions))
+    }
+
+    async fn semantic_tokens_full(
+        &self,
+        params: SemanticTokensParams,
+    ) -> Result<Option<SemanticTokensResult>> {
+        let uri = params.text_document.uri;
+        let Some(source) = self.document(&uri).await else {
+            return Ok(None);
+        };
+
+        // 语法错误时仍按词法结果高亮
+        let root = self.root_dir.read().await.clone();
+        let analysis = analysis::analyze(&source, root.as_deref());
+        let data = semantic_tokens::semantic_tokens(&source, analysis.as_ref());
+        Ok(Some(SemanticTokensResult::Tokens(SemanticTokens {
+            result_id: None,
+            data,
+        })))
+    }
+
+    async fn document_symbol(
+        &self,
+        params: DocumentSymbolParams,
+    ) -> Result<Option<DocumentSymbolResponse>> {
+        let Some((source, analysis)) = self.analyze_document(&params.text_document.uri).await
+        else {
+            return Ok(None);
+        };
+
+        let symbols = outline::document_symbols(&analysis, &source);
+        Ok(Some(DocumentSymbolResponse::Nested(symbols)))
+    }
+
     async fn shutdown(&self) -> Result<()> {
         Ok(())
     }
@@ -55,6 +264,6 @@ pub async fn run_server() {
     let stdin = tokio::io::stdin();
     let stdout = tokio::io::stdout();
 
-    let (service, socket) = LspService::new(|client| Backend { client });
+    let (service, socket) = LspService::new(Backend::new);
     Server::new(stdin, stdout, socket).serve(service).await;
 }
@@ -0,0 +1,105 @@
+//! 文档大纲 (textDocument/documentSymbol)
+//!
+//! 直接使用 Resolver 收集到的全局符号构建大纲树，而不是重新遍历 AST。
+
+use crate::analysis::Analysis;
+use crate::position::{name_span, span_to_range};
+use lency_sema::{Symbol, SymbolId};
+use lency_syntax::ast::Span;
+use tower_lsp::lsp_types::{DocumentSymbol, SymbolKind};
+
+/// 构建当前文档的大纲（按源码顺序）
+pub fn document_symbols(analysis: &Analysis, source: &str) -> Vec<DocumentSymbol> {
+    let scopes = &analysis.scopes;
+    let mut entries: Vec<(Span, DocumentSymbol)> = scopes
+        .all_symbols_in(0)
+        .into_iter()
+        .filter(|(id, symbol)| is_local(analysis, *id, symbol))
+        .filter_map(|(_, symbol)| {
+            let span = symbol.span().clone();
+            to_document_symbol(symbol, source).map(|doc| (span, doc))
+        })
+        .collect();
+    entries.sort_by_key(|(span, _)| span.start);
+    entries.into_iter().map(|(_, doc)| doc).collect()
+}
+
+/// 只保留当前文档中定义的符号（排除内置符号和导入符号）
+fn is_local(analysis: &Analysis, id: SymbolId, symbol: &Symbol) -> bool {
+    !symbol.span().is_empty() && analysis.scopes.symbol_origin(id).is_none()
+}
+
+fn to_document_symbol(symbol: &Symbol, source: &str) -> Option<DocumentSymbol> {
+    let (kind, children) = match symbol {
+        Symbol::Function(_) => (SymbolKind::FUNCTION, Vec::new()),
+        Symbol::Variable(_) => (SymbolKind::VARIABLE, Vec::new()),
+        Symbol::Struct(s) => {
+            let mut fields: Vec<_> = s.fields.iter().collect();
+            fields.sort_by(|a, b| a.0.cmp(b.0));
+            let mut children: Vec<_> = fields
+                .into_iter()
+                .map(|(name, field)| make(name, SymbolKind::FIELD, &field.span, source, vec![]))
+                .collect();
+            children.extend(methods(&s.methods, source));
+            (SymbolKind::STRUCT, children)
+        }
+        Symbol::Enum(e) => {
+            let mut variants: Vec<_> = e.variants.keys().collect();
+            variants.sort();
+            let mut children: Vec<_> = variants
+                .into_iter()
+                .map(|name| make(name, SymbolKind::ENUM_MEMBER, &e.span, source, vec![]))
+                .collect();
+            children.extend(methods(&e.methods, source));
+            (SymbolKind::ENUM, children)
+        }
+        Symbol::Trait(t) => {
+            let children = t
+                .methods
+                .iter()
+                .map(|m| make(&m.name, SymbolKind::METHOD, &t.span, source, vec![]))
+                .collect();
+            (SymbolKind::INTERFACE, children)
+        }
+        Symbol::Newtype(_) => (SymbolKind::TYPE_PARAMETER, Vec::new()),
+        Symbol::Parameter(_) | Symbol::GenericParam(_) => return None,
+    };
+    Some(make(symbol.name(), kind, symbol.span(), source, children))
+}
+
+/// impl 块中的方法，按定义顺序排列
+fn methods(
+    methods: &std::collections::HashMap<String, lency_sema::FunctionSymbol>,
+    source: &str,
+) -> Vec<DocumentSymbol> {
+    let mut methods: Vec<_> = methods.values().collect();
+    methods.sort_by_key(|m| m.span.start);
+    methods
+        .into_iter()
+        .map(|m| make(&m.name, SymbolKind::METHOD, &m.span, source, vec![]))
+        .collect()
+}
+
+#[allow(deprecated)] // DocumentSymbol::deprecated 字段已弃用，但仍需填写
+fn make(
+    name: &str,
+    kind: SymbolKind,
+    span: &Span,
+    source: &str,
+    children: Vec<DocumentSymbol>,
+) -> DocumentSymbol {
+    DocumentSymbol {
+        name: name.to_string(),
+        detail: None,
+        kind,
+        tags: None,
+        deprecated: None,
+        range: span_to_range(source, span),
+        selection_range: span_to_range(source, &name_span(name, span, source)),
+        children: if children.is_empty() {
+            None
+        } else {
+            Some(children)
+        },
+    }
+}
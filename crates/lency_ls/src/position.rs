@@ -0,0 +1,72 @@
+//! LSP 位置换算
+//!
+//! LSP 使用 (行, UTF-16 列) 描述位置，编译器内部使用字节偏移。
+
+use lency_syntax::ast::Span;
+use tower_lsp::lsp_types::{Position, Range};
+
+/// 字节偏移 -> LSP Position
+pub fn offset_to_position(source: &str, offset: usize) -> Position {
+    let offset = offset.min(source.len());
+    let mut line = 0u32;
+    let mut line_start = 0usize;
+    for (i, b) in source.bytes().enumerate().take(offset) {
+        if b == b'\n' {
+            line += 1;
+            line_start = i + 1;
+        }
+    }
+    let character = source
+        .get(line_start..offset)
+        .map(|s| s.encode_utf16().count())
+        .unwrap_or(0) as u32;
+    Position { line, character }
+}
+
+/// LSP Position -> 字节偏移（越界时截断到行尾 / 文件尾）
+pub fn position_to_offset(source: &str, position: Position) -> usize {
+    let mut line_start = 0usize;
+    for _ in 0..position.line {
+        match source[line_start..].find('\n') {
+            Some(i) => line_start += i + 1,
+            None => return source.len(),
+        }
+    }
+
+    let line_end = source[line_start..]
+        .find('\n')
+        .map_or(source.len(), |i| line_start + i);
+    let mut utf16 = 0u32;
+    for (i, ch) in source[line_start..line_end].char_indices() {
+        if utf16 >= position.character {
+            return line_start + i;
+        }
+        utf16 += ch.len_utf16() as u32;
+    }
+    line_end
+}
+
+/// 字节区间 -> LSP Range
+pub fn span_to_range(source: &str, span: &Span) -> Range {
+    Range {
+        start: offset_to_position(source, span.start),
+        end: offset_to_position(source, span.end),
+    }
+}
+
+/// 在声明范围内定位名称（整词匹配），找不到时退回整个声明范围
+pub fn name_span(name: &str, span: &Span, source: &str) -> Span {
+    let is_ident = |c: char| c.is_alphanumeric() || c == '_';
+    let Some(text) = source.get(span.clone()) else {
+        return span.clone();
+    };
+    text.match_indices(name)
+        .find(|(i, _)| {
+            let before = text[..*i].chars().next_back();
+            let after = text[i + name.len()..].chars().next();
+            !before.is_some_and(is_ident) && !after.is_some_and(is_ident)
+        })
+        .map_or(span.clone(), |(i, _)| {
+            span.start + i..span.start + i + name.len()
+        })
+}
@@ -0,0 +1,80 @@
+//! 查找引用 (textDocument/references)
+//!
+//! 基于语义分析记录的引用点映射（span -> Resolution）：
+//! 先确定光标处的目标符号，再收集解析到同一目标的所有引用点。
+
+use crate::analysis::Analysis;
+use crate::position::name_span;
+use lency_sema::{Resolution, Symbol};
+use lency_syntax::ast::Span;
+
+/// 确定光标处的目标：引用点，或声明处的名称
+pub fn target_at(analysis: &Analysis, source: &str, offset: usize) -> Option<Resolution> {
+    let scopes = &analysis.scopes;
+    if let Some((_, resolution)) = scopes.resolution_at(offset) {
+        return Some(resolution.clone());
+    }
+
+    // 光标位于声明处：找名称覆盖该位置的最内层符号
+    scopes
+        .iter_scopes()
+        .flat_map(|scope| scopes.all_symbols_in(scope.id))
+        .filter(|(id, symbol)| !symbol.span().is_empty() && scopes.symbol_origin(*id).is_none())
+        .filter_map(|(id, symbol)| {
+            let span = name_span(symbol.name(), symbol.span(), source);
+            (span.start <= offset && offset <= span.end).then_some((span, id))
+        })
+        .min_by_key(|(span, _)| span.len())
+        .map(|(_, id)| Resolution::Symbol(id))
+}
+
+/// 收集所有引用 `target` 的位置（按源码顺序），可选包含声明处的名称
+pub fn find_references(
+    analysis: &Analysis,
+    source: &str,
+    target: &Resolution,
+    include_declaration: bool,
+) -> Vec<Span> {
+    let scopes = &analysis.scopes;
+    let mut spans: Vec<Span> = scopes
+        .resolutions()
+        .filter(|(_, res)| *res == target)
+        .map(|(span, _)| span.clone())
+        .collect();
+
+    if include_declaration && let Some(decl) = declaration_span(analysis, source, target) {
+        spans.push(decl);
+    }
+
+    spans.sort_by_key(|span| span.start);
+    spans.dedup();
+    spans
+}
+
+/// 声明处名称的位置；导入符号和内置符号不在当前文档中，返回 None
+fn declaration_span(analysis: &Analysis, source: &str, target: &Resolution) -> Option<Span> {
+    let scopes = &analysis.scopes;
+    let (id, name, span) = match target {
+        Resolution::Symbol(id) => {
+            let symbol = scopes.get_symbol(*id)?;
+            (*id, symbol.name(), symbol.span().clone())
+        }
+        Resolution::Member { owner, name } => {
+            let span = match scopes.get_symbol(*owner)? {
+                Symbol::Struct(s) => s
+                    .methods
+                    .get(name)
+                    .map(|m| m.span.clone())
+                    .or_else(|| s.fields.get(name).map(|f| f.span.clone()))?,
+                Symbol::Enum(e) => e.methods.get(name)?.span.clone(),
+                _ => return None,
+            };
+            (*owner, name.as_str(), span)
+        }
+    };
+
+    if span.is_empty() || scopes.symbol_origin(id).is_some() {
+        return None;
+    }
+    Some(name_span(name, &span, source))
+}
@@ -0,0 +1,93 @@
+//! 重命名 (textDocument/rename)
+//!
+//! 复用查找引用：目标符号的所有引用点加上声明处名称即为需要替换的位置。
+
+use crate::analysis::Analysis;
+use crate::references::{find_references, target_at};
+use lency_sema::{Resolution, Symbol};
+use lency_syntax::ast::Span;
+use lency_syntax::lexer::Token;
+use logos::Logos;
+
+/// 计算重命名需要替换的所有位置；新名称非法或产生冲突时返回错误信息
+pub fn rename_spans(
+    analysis: &Analysis,
+    source: &str,
+    offset: usize,
+    new_name: &str,
+) -> Result<Vec<Span>, String> {
+    if !is_identifier(new_name) {
+        return Err(format!("'{}' is not a valid identifier", new_name));
+    }
+
+    let target =
+        target_at(analysis, source, offset).ok_or_else(|| "no symbol at cursor".to_string())?;
+    check_renamable(analysis, &target)?;
+    check_collision(analysis, &target, new_name)?;
+
+    Ok(find_references(analysis, source, &target, true))
+}
+
+/// 新名称必须恰好被词法分析为一个标识符（关键字不行）
+fn is_identifier(name: &str) -> bool {
+    let mut lexer = Token::lexer(name);
+    matches!(lexer.next(), Some(Ok(Token::Ident(ref s))) if s == name) && lexer.next().is_none()
+}
+
+/// 内置符号和导入符号不在当前文档中定义，不能重命名
+fn check_renamable(analysis: &Analysis, target: &Resolution) -> Result<(), String> {
+    let scopes = &analysis.scopes;
+    let id = match target {
+        Resolution::Symbol(id) | Resolution::Member { owner: id, .. } => *id,
+    };
+    let symbol = scopes
+        .get_symbol(id)
+        .ok_or_else(|| "unknown symbol".to_string())?;
+    if symbol.span().is_empty() || scopes.symbol_origin(id).is_some() {
+        return Err(format!("'{}' is not defined in this file", symbol.name()));
+    }
+    Ok(())
+}
+
+/// 检查新名称是否与目标所在作用域（或所属类型的成员）中的已有名称冲突
+fn check_collision(analysis: &Analysis, target: &Resolution, new_name: &str) -> Result<(), String> {
+    let scopes = &analysis.scopes;
+    let collides = match target {
+        Resolution::Symbol(id) => scopes
+            .iter_scopes()
+            .find(|scope| {
+                scope.lookup_local(scopes.get_symbol(*id).map_or("", Symbol::name)) == Some(*id)
+            })
+            .is_some_and(|scope| scope.lookup_local(new_name).is_some()),
+        Resolution::Member { owner, .. } => match scopes.get_symbol(*owner) {
+            Some(Symbol::Struct(s)) => {
+                s.fields.contains_key(new_name) || s.methods.contains_key(new_name)
+            }
+            Some(Symbol::Enum(e)) => {
+                e.variants.contains_key(new_name) || e.methods.contains_key(new_name)
+            }
+            _ => false,
+        },
+    };
+
+    if collides {
+        Err(format!("'{}' is already defined in this scope", new_name))
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_identifier() {
+        assert!(is_identifier("total"));
+        assert!(is_identifier("_tmp1"));
+        assert!(!is_identifier("1abc"));
+        assert!(!is_identifier("var"));
+        assert!(!is_identifier("a b"));
+        assert!(!is_identifier(""));
+    }
+}
@@ -0,0 +1,165 @@
+//! 语义高亮 (textDocument/semanticTokens/full)
+//!
+//! 以词法分析结果为基础，对标识符再结合语义分析的解析结果区分函数、类型和变量。
+
+use crate::analysis::Analysis;
+use crate::position::offset_to_position;
+use lency_sema::{Resolution, Symbol};
+use lency_syntax::lexer::Token;
+use logos::Logos;
+use tower_lsp::lsp_types::{SemanticToken, SemanticTokenType, SemanticTokensLegend};
+
+/// 图例中的类型顺序即为编码时使用的下标
+pub const TOKEN_TYPES: &[SemanticTokenType] = &[
+    SemanticTokenType::KEYWORD,
+    SemanticTokenType::TYPE,
+    SemanticTokenType::FUNCTION,
+    SemanticTokenType::VARIABLE,
+    SemanticTokenType::STRING,
+    SemanticTokenType::NUMBER,
+    SemanticTokenType::COMMENT,
+];
+
+const KEYWORD: u32 = 0;
+const TYPE: u32 = 1;
+const FUNCTION: u32 = 2;
+const VARIABLE: u32 = 3;
+const STRING: u32 = 4;
+const NUMBER: u32 = 5;
+const COMMENT: u32 = 6;
+
+pub fn legend() -> SemanticTokensLegend {
+    SemanticTokensLegend {
+        token_types: TOKEN_TYPES.to_vec(),
+        token_modifiers: vec![],
+    }
+}
+
+/// 对整个文档分类并按 LSP 的增量格式编码；`analysis` 为 None（语法错误）时仅做词法分类
+pub fn semantic_tokens(source: &str, analysis: Option<&Analysis>) -> Vec<SemanticToken> {
+    let mut tokens = Vec::new();
+    let (mut prev_line, mut prev_start) = (0u32, 0u32);
+
+    for (tok, span) in Token::lexer(source).spanned() {
+        let Ok(tok) = tok else { continue };
+        let Some(token_type) = classify(&tok, &span, analysis) else {
+            continue;
+        };
+
+        let start = offset_to_position(source, span.start);
+        let end = offset_to_position(source, span.end);
+        // LSP 语义 token 不能跨行（多行字符串等），跳过
+        if start.line != end.line {
+            continue;
+        }
+
+        let delta_line = start.line - prev_line;
+        let delta_start = if delta_line == 0 {
+            start.character - prev_start
+        } else {
+            start.character
+        };
+        tokens.push(SemanticToken {
+            delta_line,
+            delta_start,
+            length: end.character - start.character,
+            token_type,
+            token_modifiers_bitset: 0,
+        });
+        prev_line = start.line;
+        prev_start = start.character;
+    }
+    tokens
+}
+
+fn classify(
+    tok: &Token,
+    span: &std::ops::Range<usize>,
+    analysis: Option<&Analysis>,
+) -> Option<u32> {
+    let ty = match tok {
+        Token::Var
+        | Token::Let
+        | Token::Const
+        | Token::If
+        | Token::Else
+        | Token::While
+        | Token::For
+        | Token::In
+        | Token::Break
+        | Token::Continue
+        | Token::Return
+        | Token::Import
+        | Token::Extern
+        | Token::Struct
+        | Token::Packed
+        | Token::Impl
+        | Token::Trait
+        | Token::Dyn
+        | Token::Enum
+        | Token::As
+        | Token::Null
+        | Token::True
+        | Token::False
+        | Token::Match
+        | Token::Case
+        | Token::Ok
+        | Token::Err => KEYWORD,
+        Token::TypeInt
+        | Token::TypeI8
+        | Token::TypeI16
+        | Token::TypeI32
+        | Token::TypeI64
+        | Token::TypeUInt
+        | Token::TypeFloat
+        | Token::TypeBool
+        | Token::TypeString
+        | Token::TypeVoid
+        | Token::Vec => TYPE,
+        // 内置函数在词法层面是关键字
+        Token::Print
+        | Token::ReadFile
+        | Token::WriteFile
+        | Token::Len
+        | Token::Trim
+        | Token::Split
+        | Token::Join
+        | Token::Substr
+        | Token::CharToString
+        | Token::Panic
+        | Token::Assert
+        | Token::Format
+        | Token::Fmt => FUNCTION,
+        Token::Int(_) | Token::Float(_) => NUMBER,
+        Token::String(_) => STRING,
+        Token::DocComment(_) => COMMENT,
+        Token::Ident(name) => analysis.map_or(VARIABLE, |a| classify_ident(a, name, span)),
+        _ => return None,
+    };
+    Some(ty)
+}
+
+/// 根据解析结果区分函数 / 类型 / 变量
+fn classify_ident(analysis: &Analysis, name: &str, span: &std::ops::Range<usize>) -> u32 {
+    let scopes = &analysis.scopes;
+    let symbol = match scopes.resolution_of(span) {
+        Some(Resolution::Symbol(id)) => scopes.get_symbol(*id),
+        Some(Resolution::Member { owner, name }) => {
+            return match scopes.get_symbol(*owner) {
+                Some(Symbol::Struct(s)) if s.methods.contains_key(name) => FUNCTION,
+                Some(Symbol::Enum(e)) if e.methods.contains_key(name) => FUNCTION,
+                _ => VARIABLE,
+            };
+        }
+        // 声明处、类型注解等没有记录引用点的位置：按全局符号判断
+        None => scopes.lookup_global(name),
+    };
+
+    match symbol {
+        Some(Symbol::Function(_)) => FUNCTION,
+        Some(Symbol::Struct(_) | Symbol::Enum(_) | Symbol::Trait(_) | Symbol::GenericParam(_)) => {
+            TYPE
+        }
+        _ => VARIABLE,
+    }
+}
@@ -0,0 +1,72 @@
+mod common;
+
+use common::{TEST_URI, TestServer, position_of};
+use serde_json::{Value, json};
+
+const SOURCE: &str = r#"void show(int count) {
+    print("count: " + count);
+}
+
+int main() {
+    var total = 1 + 2;
+    return total;
+}
+"#;
+
+async fn code_actions(server: &mut TestServer, start: (u32, u32), end: (u32, u32)) -> Value {
+    server
+        .request(
+            "textDocument/codeAction",
+            json!({
+                "textDocument": { "uri": TEST_URI },
+                "range": {
+                    "start": { "line": start.0, "character": start.1 },
+                    "end": { "line": end.0, "character": end.1 },
+                },
+                "context": { "diagnostics": [] },
+            }),
+        )
+        .await
+}
+
+#[tokio::test]
+async fn test_code_action_provider_is_advertised() {
+    let server = TestServer::new().await;
+    assert_eq!(server.capabilities["codeActionProvider"], true);
+}
+
+#[tokio::test]
+async fn test_string_plus_int_offers_int_to_string_fix() {
+    let mut server = TestServer::new().await;
+    server.open(SOURCE).await;
+
+    let cursor = position_of(SOURCE, "count)", 1);
+    let actions = code_actions(&mut server, cursor, cursor).await;
+    let actions = actions.as_array().unwrap();
+    assert_eq!(actions.len(), 1);
+
+    let action = &actions[0];
+    assert_eq!(action["title"], "wrap in int_to_string");
+    assert_eq!(action["kind"], "quickfix");
+    let edits = action["edit"]["changes"][TEST_URI].as_array().unwrap();
+    assert_eq!(edits.len(), 1);
+    assert_eq!(edits[0]["newText"], "int_to_string(count)");
+    // 替换范围恰好覆盖 `count` 操作数
+    assert_eq!(
+        edits[0]["range"],
+        json!({
+            "start": { "line": cursor.0, "character": cursor.1 },
+            "end": { "line": cursor.0, "character": cursor.1 + 5 },
+        })
+    );
+}
+
+#[tokio::test]
+async fn test_no_code_action_outside_diagnostics() {
+    let mut server = TestServer::new().await;
+    server.open(SOURCE).await;
+
+    let cursor = position_of(SOURCE, "total", 1);
+    let actions = code_actions(&mut server, cursor, cursor).await;
+    assert_eq!(actions, json!([]));
+}
@@ -0,0 +1,123 @@
+//! LSP 集成测试辅助：驱动 LspService 完成初始化、打开文档、发送请求
+
+#![allow(dead_code)]
+
+use lency_ls::Backend;
+use serde_json::{Value, json};
+use tower::Service;
+use tower_lsp::LspService;
+use tower_lsp::jsonrpc::{Request, Response};
+
+pub const TEST_URI: &str = "file:///tmp/lency_ls_test/main.lcy";
+
+pub struct TestServer {
+    service: LspService<Backend>,
+    next_id: i64,
+    /// initialize 返回的服务端能力
+    pub capabilities: Value,
+}
+
+impl TestServer {
+    /// 启动服务并完成 initialize 握手
+    pub async fn new() -> Self {
+        let (service, socket) = LspService::new(Backend::new);
+        // 丢弃服务端发往客户端的消息（日志等），避免阻塞
+        tokio::spawn(async move {
+            use futures::StreamExt;
+            let mut socket = socket;
+            while socket.next().await.is_some() {}
+        });
+
+        let mut server = Self {
+            service,
+            next_id: 0,
+            capabilities: Value::Null,
+        };
+        let init = server
+            .request(
+                "initialize",
+                json!({ "capabilities": {}, "processId": null, "rootUri": null }),
+            )
+            .await;
+        server.capabilities = init["capabilities"].clone();
+        server.notify("initialized", json!({})).await;
+        server
+    }
+
+    /// 发送请求并返回 result 字段
+    pub async fn request(&mut self, method: &str, params: Value) -> Value {
+        let (_, result) = self.call(method, params).await.into_parts();
+        result.expect("request failed")
+    }
+
+    /// 发送请求并返回完整的响应 JSON（包含 result 或 error）
+    pub async fn request_raw(&mut self, method: &str, params: Value) -> Value {
+        serde_json::to_value(self.call(method, params).await).unwrap()
+    }
+
+    async fn call(&mut self, method: &str, params: Value) -> Response {
+        self.next_id += 1;
+        let request = Request::build(method.to_string())
+            .id(self.next_id)
+            .params(params)
+            .finish();
+        self.service
+            .call(request)
+            .await
+            .unwrap()
+            .expect("request must produce a response")
+    }
+
+    /// 发送通知
+    pub async fn notify(&mut self, method: &str, params: Value) {
+        let notification = Request::build(method.to_string()).params(params).finish();
+        let response = self.service.call(notification).await.unwrap();
+        assert!(response.is_none());
+    }
+
+    /// 打开测试文档
+    pub async fn open(&mut self, text: &str) {
+        self.open_uri(TEST_URI, text).await;
+    }
+
+    /// 以指定 URI 打开文档
+    pub async fn open_uri(&mut self, uri: &str, text: &str) {
+        self.notify(
+            "textDocument/didOpen",
+            json!({
+                "textDocument": {
+                    "uri": uri,
+                    "languageId": "lency",
+                    "version": 1,
+                    "text": text,
+                }
+            }),
+        )
+        .await;
+    }
+
+    /// 发送 didChange，`changes` 为 contentChanges 数组
+    pub async fn change(&mut self, uri: &str, version: i32, changes: Value) {
+        self.notify(
+            "textDocument/didChange",
+            json!({
+                "textDocument": { "uri": uri, "version": version },
+                "contentChanges": changes,
+            }),
+        )
+        .await;
+    }
+}
+
+/// 计算 `needle` 在 `text` 中第 `nth` 次出现的 LSP 位置 (line, character)
+pub fn position_of(text: &str, needle: &str, nth: usize) -> (u32, u32) {
+    let offset = text
+        .match_indices(needle)
+        .nth(nth)
+        .unwrap_or_else(|| panic!("'{}' occurrence {} not found", needle, nth))
+        .0;
+    let before = &text[..offset];
+    let line = before.matches('\n').count() as u32;
+    let col = before.rsplit('\n').next().unwrap().encode_utf16().count() as u32;
+    (line, col)
+}
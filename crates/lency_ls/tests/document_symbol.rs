@@ -0,0 +1,70 @@
+mod common;
+
+use common::{TEST_URI, TestServer};
+use serde_json::{Value, json};
+
+const SOURCE: &str = r#"struct Point {
+    int x
+    int y
+}
+
+impl Point {
+    int sum() {
+        return this.x + this.y;
+    }
+}
+
+int add(int a, int b) {
+    return a + b;
+}
+
+int main() {
+    var p = Point { x: 1, y: 2 };
+    return add(p.x, p.y);
+}
+"#;
+
+fn names(symbols: &Value) -> Vec<&str> {
+    symbols
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|s| s["name"].as_str().unwrap())
+        .collect()
+}
+
+#[tokio::test]
+async fn test_document_symbol_outline() {
+    let mut server = TestServer::new().await;
+    server.open(SOURCE).await;
+
+    let symbols = server
+        .request(
+            "textDocument/documentSymbol",
+            json!({ "textDocument": { "uri": TEST_URI } }),
+        )
+        .await;
+
+    assert_eq!(names(&symbols), vec!["Point", "add", "main"]);
+
+    // SymbolKind: STRUCT = 23, FUNCTION = 12, FIELD = 8, METHOD = 6
+    let point = &symbols[0];
+    assert_eq!(point["kind"], 23);
+    assert_eq!(
+        point["range"]["start"],
+        json!({ "line": 0, "character": 0 })
+    );
+    assert_eq!(
+        point["selectionRange"]["start"],
+        json!({ "line": 0, "character": 7 })
+    );
+    assert_eq!(names(&point["children"]), vec!["x", "y", "sum"]);
+    assert_eq!(point["children"][0]["kind"], 8);
+    assert_eq!(point["children"][2]["kind"], 6);
+    assert_eq!(point["children"][2]["range"]["start"]["line"], 6);
+
+    let add = &symbols[1];
+    assert_eq!(add["kind"], 12);
+    assert_eq!(add["range"]["start"]["line"], 11);
+    assert!(add.get("children").is_none());
+}
@@ -0,0 +1,73 @@
+mod common;
+
+use common::{TEST_URI, TestServer};
+use serde_json::{Value, json};
+
+const ORIGINAL: &str = "int main() {\n    return 0;\n}\n";
+const EDITED: &str =
+    "int add(int a, int b) {\n    return a + b;\n}\n\nint main() {\n    return add(1, 2);\n}\n";
+const FULL_URI: &str = "file:///tmp/lency_ls_test/full.lcy";
+
+fn range(sl: u32, sc: u32, el: u32, ec: u32) -> Value {
+    json!({
+        "start": { "line": sl, "character": sc },
+        "end": { "line": el, "character": ec },
+    })
+}
+
+async fn outline(server: &mut TestServer, uri: &str) -> Value {
+    server
+        .request(
+            "textDocument/documentSymbol",
+            json!({ "textDocument": { "uri": uri } }),
+        )
+        .await
+}
+
+#[tokio::test]
+async fn test_incremental_sync_matches_full_replacement() {
+    let mut server = TestServer::new().await;
+    assert_eq!(server.capabilities["textDocumentSync"], 2); // INCREMENTAL
+
+    server.open(ORIGINAL).await;
+    // 分两次通知、共三处增量编辑，得到 EDITED
+    server
+        .change(
+            TEST_URI,
+            2,
+            json!([
+                { "range": range(1, 11, 1, 12), "text": "add(1, 2)" },
+                { "range": range(0, 0, 0, 0), "text": "int add(int a, int b) {\n    return a;\n}\n\n" },
+            ]),
+        )
+        .await;
+    server
+        .change(
+            TEST_URI,
+            3,
+            json!([{ "range": range(1, 12, 1, 12), "text": " + b" }]),
+        )
+        .await;
+
+    server.open_uri(FULL_URI, EDITED).await;
+
+    let incremental = outline(&mut server, TEST_URI).await;
+    let full = outline(&mut server, FULL_URI).await;
+    assert_eq!(incremental.as_array().unwrap().len(), 2);
+    assert_eq!(incremental, full);
+}
+
+#[tokio::test]
+async fn test_change_without_range_replaces_document() {
+    let mut server = TestServer::new().await;
+    server.open(ORIGINAL).await;
+    server
+        .change(TEST_URI, 2, json!([{ "text": EDITED }]))
+        .await;
+    server.open_uri(FULL_URI, EDITED).await;
+
+    assert_eq!(
+        outline(&mut server, TEST_URI).await,
+        outline(&mut server, FULL_URI).await
+    );
+}
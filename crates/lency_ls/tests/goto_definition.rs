@@ -0,0 +1,134 @@
+mod common;
+
+use common::{TEST_URI, TestServer, position_of};
+use serde_json::{Value, json};
+
+const SOURCE: &str = r#"struct Point {
+    int x
+    int y
+}
+
+impl Point {
+    int sum() {
+        return this.x + this.y;
+    }
+}
+
+int add(int a, int b) {
+    return a + b;
+}
+
+int main() {
+    var p = Point { x: 1, y: 2 };
+    var s = p.sum();
+    var total = add(p.x, s);
+    return total;
+}
+"#;
+
+/// 在 `needle` 第 `nth` 次出现处请求跳转定义
+async fn goto(server: &mut TestServer, needle: &str, nth: usize) -> Value {
+    let (line, character) = position_of(SOURCE, needle, nth);
+    server
+        .request(
+            "textDocument/definition",
+            json!({
+                "textDocument": { "uri": TEST_URI },
+                "position": { "line": line, "character": character },
+            }),
+        )
+        .await
+}
+
+/// 断言返回的 Location 位于当前文档并覆盖 `needle` 第 `nth` 次出现的位置
+fn assert_covers(location: &Value, needle: &str, nth: usize) {
+    assert_eq!(
+        location["uri"], TEST_URI,
+        "unexpected location: {}",
+        location
+    );
+    let (line, col) = position_of(SOURCE, needle, nth);
+    let start = &location["range"]["start"];
+    let end = &location["range"]["end"];
+    let pos = (line as u64, col as u64);
+    let start = (
+        start["line"].as_u64().unwrap(),
+        start["character"].as_u64().unwrap(),
+    );
+    let end = (
+        end["line"].as_u64().unwrap(),
+        end["character"].as_u64().unwrap(),
+    );
+    assert!(
+        start <= pos && pos < end,
+        "range {:?}..{:?} does not cover '{}' at {:?}",
+        start,
+        end,
+        needle,
+        pos
+    );
+}
+
+#[tokio::test]
+async fn test_goto_local_variable() {
+    let mut server = TestServer::new().await;
+    server.open(SOURCE).await;
+
+    let location = goto(&mut server, "total", 1).await;
+    assert_covers(&location, "var total", 0);
+}
+
+#[tokio::test]
+async fn test_goto_function() {
+    let mut server = TestServer::new().await;
+    server.open(SOURCE).await;
+
+    let location = goto(&mut server, "add(", 0).await;
+    assert_covers(&location, "int add", 0);
+}
+
+#[tokio::test]
+async fn test_goto_parameter() {
+    let mut server = TestServer::new().await;
+    server.open(SOURCE).await;
+
+    let location = goto(&mut server, "a + b", 0).await;
+    assert_covers(&location, "int a", 0);
+}
+
+#[tokio::test]
+async fn test_goto_struct_from_literal() {
+    let mut server = TestServer::new().await;
+    server.open(SOURCE).await;
+
+    let location = goto(&mut server, "Point {", 1).await;
+    assert_covers(&location, "struct Point", 0);
+}
+
+#[tokio::test]
+async fn test_goto_method() {
+    let mut server = TestServer::new().await;
+    server.open(SOURCE).await;
+
+    let location = goto(&mut server, "sum()", 1).await;
+    assert_covers(&location, "int sum", 0);
+}
+
+#[tokio::test]
+async fn test_goto_unknown_returns_null() {
+    let mut server = TestServer::new().await;
+    server.open(SOURCE).await;
+
+    let location = goto(&mut server, "return", 0).await;
+    assert!(location.is_null());
+}
+
+#[tokio::test]
+async fn test_goto_field() {
+    let mut server = TestServer::new().await;
+    server.open(SOURCE).await;
+
+    // 字段的定义位置为所在结构体
+    let location = goto(&mut server, "x + this", 0).await;
+    assert_covers(&location, "int x", 0);
+}
@@ -5,7 +5,7 @@ use tower_lsp::LspService;
 
 #[tokio::test]
 async fn test_lsp_initialize() {
-    let (mut service, _) = LspService::new(|client| Backend { client });
+    let (mut service, _) = LspService::new(Backend::new);
 
     let init_request = json!({
         "jsonrpc": "2.0",
@@ -0,0 +1,27 @@
+use lency_ls::position::{name_span, offset_to_position, position_to_offset};
+use tower_lsp::lsp_types::Position;
+
+#[test]
+fn test_offset_position_roundtrip() {
+    let source = "int a;\nstring s = \"你好\";\nvar b;";
+    let offset = source.find("var").unwrap();
+    let pos = offset_to_position(source, offset);
+    assert_eq!(pos, Position::new(2, 0));
+    assert_eq!(position_to_offset(source, pos), offset);
+}
+
+#[test]
+fn test_utf16_columns() {
+    let source = "\"你好\" x";
+    let offset = source.find('x').unwrap();
+    // 引号 + 两个汉字 + 引号 + 空格 = 5 个 UTF-16 单元
+    assert_eq!(offset_to_position(source, offset), Position::new(0, 5));
+    assert_eq!(position_to_offset(source, Position::new(0, 5)), offset);
+}
+
+#[test]
+fn test_name_span_whole_word() {
+    let source = "int add(int a, int b) { return a + b; }";
+    assert_eq!(name_span("a", &(0..source.len()), source), 12..13);
+    assert_eq!(name_span("add", &(0..source.len()), source), 4..7);
+}
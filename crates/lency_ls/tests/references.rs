@@ -0,0 +1,75 @@
+mod common;
+
+use common::{TEST_URI, TestServer, position_of};
+use lency_ls::analysis::analyze;
+use lency_ls::references::{find_references, target_at};
+use serde_json::{Value, json};
+
+const SOURCE: &str = r#"int main() {
+    var count = 1;
+    var doubled = count + count;
+    return doubled;
+}
+"#;
+
+async fn references(server: &mut TestServer, needle: &str, nth: usize, decl: bool) -> Value {
+    let (line, character) = position_of(SOURCE, needle, nth);
+    server
+        .request(
+            "textDocument/references",
+            json!({
+                "textDocument": { "uri": TEST_URI },
+                "position": { "line": line, "character": character },
+                "context": { "includeDeclaration": decl },
+            }),
+        )
+        .await
+}
+
+fn starts(locations: &Value) -> Vec<(u64, u64)> {
+    locations
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|loc| {
+            assert_eq!(loc["uri"], TEST_URI);
+            let start = &loc["range"]["start"];
+            (
+                start["line"].as_u64().unwrap(),
+                start["character"].as_u64().unwrap(),
+            )
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_references_to_variable() {
+    let mut server = TestServer::new().await;
+    server.open(SOURCE).await;
+
+    let refs = references(&mut server, "count", 1, false).await;
+    assert_eq!(starts(&refs), vec![(2, 18), (2, 26)]);
+}
+
+#[tokio::test]
+async fn test_references_include_declaration() {
+    let mut server = TestServer::new().await;
+    server.open(SOURCE).await;
+
+    // 从声明处发起查找
+    let refs = references(&mut server, "count", 0, true).await;
+    assert_eq!(starts(&refs), vec![(1, 8), (2, 18), (2, 26)]);
+}
+
+#[test]
+fn test_shadowed_variable_is_distinct() {
+    let source = "int main() {\n    var x = 1;\n    if true {\n        var x = 2;\n        print(x);\n    }\n    return x;\n}\n";
+    let analysis = analyze(source, None).unwrap();
+
+    let outer = target_at(&analysis, source, source.find("return x").unwrap() + 7).unwrap();
+    let refs = find_references(&analysis, source, &outer, false);
+    assert_eq!(
+        refs,
+        vec![source.rfind('x').unwrap()..source.rfind('x').unwrap() + 1]
+    );
+}
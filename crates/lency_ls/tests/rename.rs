@@ -0,0 +1,93 @@
+mod common;
+
+use common::{TEST_URI, TestServer, position_of};
+use serde_json::{Value, json};
+
+const SOURCE: &str = r#"int square(int x) {
+    return x * x;
+}
+
+int twice(int x) {
+    return x + x;
+}
+
+int main() {
+    var a = square(2);
+    var b = square(a);
+    return twice(b);
+}
+"#;
+
+async fn rename(server: &mut TestServer, needle: &str, nth: usize, new_name: &str) -> Value {
+    let (line, character) = position_of(SOURCE, needle, nth);
+    server
+        .request_raw(
+            "textDocument/rename",
+            json!({
+                "textDocument": { "uri": TEST_URI },
+                "position": { "line": line, "character": character },
+                "newName": new_name,
+            }),
+        )
+        .await
+}
+
+/// 取出编辑中所有替换位置的起点，并检查替换文本
+fn edit_starts(result: &Value, new_name: &str) -> Vec<(u32, u32)> {
+    let mut starts: Vec<(u32, u32)> = result["changes"][TEST_URI]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|edit| {
+            assert_eq!(edit["newText"], new_name);
+            let start = &edit["range"]["start"];
+            (
+                start["line"].as_u64().unwrap() as u32,
+                start["character"].as_u64().unwrap() as u32,
+            )
+        })
+        .collect();
+    starts.sort();
+    starts
+}
+
+#[tokio::test]
+async fn test_rename_function_updates_all_call_sites() {
+    let mut server = TestServer::new().await;
+    server.open(SOURCE).await;
+
+    let response = rename(&mut server, "square", 1, "sq").await;
+    let starts = edit_starts(&response["result"], "sq");
+    assert_eq!(
+        starts,
+        vec![
+            position_of(SOURCE, "square", 0),
+            position_of(SOURCE, "square", 1),
+            position_of(SOURCE, "square", 2),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_rename_parameter_stays_in_function() {
+    let mut server = TestServer::new().await;
+    server.open(SOURCE).await;
+
+    // square 的参数 x 不影响 twice 的参数 x
+    let response = rename(&mut server, "x * x", 0, "n").await;
+    let starts = edit_starts(&response["result"], "n");
+    assert_eq!(starts.len(), 3);
+    assert!(starts.iter().all(|(line, _)| *line <= 1));
+}
+
+#[tokio::test]
+async fn test_rename_rejects_invalid_and_colliding_names() {
+    let mut server = TestServer::new().await;
+    server.open(SOURCE).await;
+
+    let response = rename(&mut server, "square", 1, "var").await;
+    assert!(response.get("error").is_some());
+
+    let response = rename(&mut server, "square", 1, "twice").await;
+    assert!(response.get("error").is_some());
+}
@@ -0,0 +1,73 @@
+mod common;
+
+use common::{TEST_URI, TestServer};
+use serde_json::json;
+
+const SOURCE: &str = r#"int add(int a, int b) {
+    return a + b;
+}
+
+int main() {
+    var total = add(1, 2);
+    return total;
+}
+"#;
+
+/// 解码 LSP 增量格式，返回 (行, 列, 长度, 类型名)
+async fn decode(server: &mut TestServer) -> Vec<(u64, u64, u64, String)> {
+    let legend: Vec<String> = server.capabilities["semanticTokensProvider"]["legend"]["tokenTypes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|t| t.as_str().unwrap().to_string())
+        .collect();
+
+    let result = server
+        .request(
+            "textDocument/semanticTokens/full",
+            json!({ "textDocument": { "uri": TEST_URI } }),
+        )
+        .await;
+    let data: Vec<u64> = result["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|v| v.as_u64().unwrap())
+        .collect();
+
+    let (mut line, mut col) = (0, 0);
+    data.chunks(5)
+        .map(|t| {
+            if t[0] > 0 {
+                line += t[0];
+                col = t[1];
+            } else {
+                col += t[1];
+            }
+            (line, col, t[2], legend[t[3] as usize].clone())
+        })
+        .collect()
+}
+
+fn kind_at(tokens: &[(u64, u64, u64, String)], line: u64, col: u64) -> &str {
+    tokens
+        .iter()
+        .find(|t| t.0 == line && t.1 == col)
+        .map(|t| t.3.as_str())
+        .unwrap_or_else(|| panic!("no token at {}:{}", line, col))
+}
+
+#[tokio::test]
+async fn test_semantic_tokens_classification() {
+    let mut server = TestServer::new().await;
+    server.open(SOURCE).await;
+    let tokens = decode(&mut server).await;
+
+    assert_eq!(kind_at(&tokens, 0, 0), "type"); // int
+    assert_eq!(kind_at(&tokens, 0, 4), "function"); // add 声明
+    assert_eq!(kind_at(&tokens, 1, 4), "keyword"); // return
+    assert_eq!(kind_at(&tokens, 1, 11), "variable"); // a
+    assert_eq!(kind_at(&tokens, 5, 16), "function"); // add 调用
+    assert_eq!(kind_at(&tokens, 5, 8), "variable"); // total
+    assert_eq!(kind_at(&tokens, 5, 20), "number"); // 1
+}
@@ -5,14 +5,72 @@
 
 use lency_syntax::ast::*;
 use std::collections::HashSet;
+use std::hash::Hash;
+
+/// 按插入顺序去重的集合：保证单态化生成的声明顺序（以及 IR）在多次编译间一致
+#[derive(Debug, Clone)]
+pub struct OrderedSet<T> {
+    items: Vec<T>,
+    seen: HashSet<T>,
+}
+
+impl<T: Clone + Eq + Hash> OrderedSet<T> {
+    pub fn new() -> Self {
+        Self {
+            items: Vec::new(),
+            seen: HashSet::new(),
+        }
+    }
+
+    /// 插入元素；已存在时不改变原有顺序，返回 false
+    pub fn insert(&mut self, item: T) -> bool {
+        if self.seen.contains(&item) {
+            return false;
+        }
+        self.seen.insert(item.clone());
+        self.items.push(item);
+        true
+    }
+
+    pub fn contains(&self, item: &T) -> bool {
+        self.seen.contains(item)
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.items.iter()
+    }
+}
+
+impl<T: Clone + Eq + Hash> Default for OrderedSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> IntoIterator for OrderedSet<T> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.into_iter()
+    }
+}
 
 pub struct Collector {
-    /// 收集到的泛型类型实例化
+    /// 收集到的泛型类型实例化（按首次出现顺序）
     /// 例如: Type::Generic("Box", [int])
-    pub instantiations: HashSet<Type>,
-    /// 收集到的泛型函数实例化
+    pub instantiations: OrderedSet<Type>,
+    /// 收集到的泛型函数实例化（按首次出现顺序）
     /// 例如: ("identity", [int])
-    pub function_instantiations: HashSet<(String, Vec<Type>)>,
+    pub function_instantiations: OrderedSet<(String, Vec<Type>)>,
 }
 
 impl Default for Collector {
@@ -24,8 +82,8 @@ impl Default for Collector {
 impl Collector {
     pub fn new() -> Self {
         Self {
-            instantiations: HashSet::new(),
-            function_instantiations: HashSet::new(),
+            instantiations: OrderedSet::new(),
+            function_instantiations: OrderedSet::new(),
         }
     }
 
@@ -83,7 +141,8 @@ impl Collector {
                     }
                 }
             }
-            Decl::Var { .. } => {}
+            Decl::Newtype { ty, .. } => self.collect_type(ty),
+            Decl::Const { .. } | Decl::Var { .. } => {}
             Decl::Import { .. } => {}
         }
     }
@@ -96,6 +155,18 @@ impl Collector {
                 }
                 self.collect_expr(value);
             }
+            Stmt::LoopVarDecl {
+                ty,
+                loop_stmt,
+                default,
+                ..
+            } => {
+                if let Some(t) = ty {
+                    self.collect_type(t);
+                }
+                self.collect_stmt(loop_stmt);
+                self.collect_expr(default);
+            }
             Stmt::Assignment { target, value, .. } => {
                 self.collect_expr(target);
                 self.collect_expr(value);
@@ -122,6 +193,22 @@ impl Collector {
                     }
                 }
             }
+            Stmt::IfLet {
+                value,
+                then_block,
+                else_block,
+                ..
+            } => {
+                self.collect_expr(value);
+                for s in then_block {
+                    self.collect_stmt(s);
+                }
+                if let Some(else_stmts) = else_block {
+                    for s in else_stmts {
+                        self.collect_stmt(s);
+                    }
+                }
+            }
             Stmt::While {
                 condition, body, ..
             } => {
@@ -161,7 +248,12 @@ impl Collector {
                     self.collect_expr(v);
                 }
             }
-            Stmt::Break { .. } | Stmt::Continue { .. } => {}
+            Stmt::Break { value, .. } => {
+                if let Some(v) = value {
+                    self.collect_expr(v);
+                }
+            }
+            Stmt::Continue { .. } => {}
         }
     }
 
@@ -169,7 +261,7 @@ impl Collector {
         match &expr.kind {
             ExprKind::Literal(_) => {}
             ExprKind::Unit => {}
-            ExprKind::Variable(_) => {}
+            ExprKind::Variable(_) | ExprKind::Path { .. } => {}
             ExprKind::Binary(l, _, r) => {
                 self.collect_expr(l);
                 self.collect_expr(r);
@@ -200,11 +292,20 @@ impl Collector {
                     self.collect_expr(e);
                 }
             }
+            ExprKind::RecordLiteral(fields) => {
+                for (_, e) in fields {
+                    self.collect_expr(e);
+                }
+            }
             ExprKind::VecLiteral(elems) => {
                 for e in elems {
                     self.collect_expr(e);
                 }
             }
+            ExprKind::Repeat { value, count, .. } => {
+                self.collect_expr(value);
+                self.collect_expr(count);
+            }
             ExprKind::Match {
                 value,
                 cases,
@@ -212,6 +313,9 @@ impl Collector {
             } => {
                 self.collect_expr(value);
                 for c in cases {
+                    if let Some(guard) = &c.guard {
+                        self.collect_expr(guard);
+                    }
                     self.collect_expr(&c.body);
                 }
                 if let Some(d) = default {
@@ -262,10 +366,23 @@ impl Collector {
             }
             ExprKind::CharToString(arg) => self.collect_expr(arg),
             ExprKind::Panic(arg) => self.collect_expr(arg),
+            ExprKind::Assert(cond, message) => {
+                self.collect_expr(cond);
+                self.collect_expr(message);
+            }
             ExprKind::Format(template, args) => {
                 self.collect_expr(template);
                 self.collect_expr(args);
             }
+            ExprKind::Fmt { args, .. } => {
+                for arg in args {
+                    self.collect_expr(arg);
+                }
+            }
+            ExprKind::Cast { ty, value } => {
+                self.collect_type(ty);
+                self.collect_expr(value);
+            }
         }
     }
 
@@ -291,6 +408,15 @@ impl Collector {
                 }
                 self.collect_type(return_type);
             }
+            Type::Record(fields) => {
+                for (_, field_ty) in fields {
+                    self.collect_type(field_ty);
+                }
+            }
+            Type::Result { ok_type, err_type } => {
+                self.collect_type(ok_type);
+                self.collect_type(err_type);
+            }
             _ => {}
         }
     }
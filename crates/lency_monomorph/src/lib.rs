@@ -7,7 +7,9 @@ use self::collector::Collector;
 use self::mangling::mangle_type;
 use self::rewriter::Rewriter;
 use self::specializer::Specializer;
-use lency_syntax::ast::{Decl, Program, Type};
+use lency_sema::SemanticError;
+use lency_syntax::ast::{Decl, Program, Span, Type};
+use rayon::prelude::*;
 use std::collections::{HashMap, HashSet};
 
 /// 单态化 Pass
@@ -21,8 +23,39 @@ pub struct Monomorphizer {
     generic_definitions: HashMap<String, Decl>,
     /// 泛型 Impl 定义缓存 (struct_name -> List[Impl Decl])
     generic_impls: HashMap<String, Vec<Decl>>,
+    /// 已实现的 Trait：(类型名, Trait 名)；泛型 impl 以基础类型名登记
+    trait_impls: HashSet<(String, String)>,
+    /// 实例化链的最大深度，超过即视为无限展开
+    max_depth: usize,
+    /// 已报告过展开溢出的模板名
+    overflowed: HashSet<String>,
+    /// 单态化过程中发现的错误
+    errors: Vec<SemanticError>,
+    /// 同一轮中互不依赖的实例是否并行特化
+    parallel: bool,
 }
 
+/// 一轮工作表中待特化的实例
+struct Job {
+    /// 泛型模板名
+    template: String,
+    args: Vec<Type>,
+    /// 特化后的声明名（如 Box__int）
+    mangled_name: String,
+    /// 实例化链
+    chain: Vec<String>,
+    /// 类型实例需要一并特化该类型的泛型 impl
+    with_impls: bool,
+}
+
+/// 类型实例化工作项：(类型, 实例化链)
+type TypeWork = (Type, Vec<String>);
+/// 函数实例化工作项：((函数名, 类型实参), 实例化链)
+type FuncWork = ((String, Vec<Type>), Vec<String>);
+
+/// 默认的实例化深度上限
+pub const DEFAULT_MAX_DEPTH: usize = 64;
+
 impl Default for Monomorphizer {
     fn default() -> Self {
         Self::new()
@@ -36,9 +69,29 @@ impl Monomorphizer {
             new_decls: Vec::new(),
             generic_definitions: HashMap::new(),
             generic_impls: HashMap::new(),
+            trait_impls: HashSet::new(),
+            max_depth: DEFAULT_MAX_DEPTH,
+            overflowed: HashSet::new(),
+            errors: Vec::new(),
+            parallel: true,
         }
     }
 
+    /// 设置实例化深度上限（默认 DEFAULT_MAX_DEPTH）
+    pub fn set_max_depth(&mut self, depth: usize) {
+        self.max_depth = depth;
+    }
+
+    /// 设置是否并行特化（默认开启）；关闭后按相同顺序逐个特化，输出不变
+    pub fn set_parallel(&mut self, parallel: bool) {
+        self.parallel = parallel;
+    }
+
+    /// 取出单态化过程中收集的错误
+    pub fn take_errors(&mut self) -> Vec<SemanticError> {
+        std::mem::take(&mut self.errors)
+    }
+
     pub fn process(&mut self, program: Program) -> Program {
         // 1. 分离泛型定义（Templates）和具体定义（Concrete）
         let mut concrete_decls = Vec::new();
@@ -72,8 +125,18 @@ impl Monomorphizer {
                 Decl::Impl {
                     type_name,
                     generic_params,
+                    trait_ref,
                     ..
                 } => {
+                    if let Some(Type::Struct(trait_name) | Type::Generic(trait_name, _)) = trait_ref
+                    {
+                        let impl_key = match type_name {
+                            Type::Generic(n, _) if !generic_params.is_empty() => n.clone(),
+                            _ => mangle_type(type_name),
+                        };
+                        self.trait_impls.insert((impl_key, trait_name.clone()));
+                    }
+
                     // Collect all impl blocks for potentially generic structs.
                     // If generic_params is not empty, it's definitely a generic impl template.
                     // Even if generic_params is empty, it might be `impl Box<int>`, which we treat as concrete for now,
@@ -108,107 +171,85 @@ impl Monomorphizer {
         }
 
         // 3. Worklist Algorithm: 持续生成，直到没有新的实例化为止
-        let mut type_worklist: Vec<Type> = collector.instantiations.into_iter().collect();
-        let mut func_worklist: Vec<(String, Vec<Type>)> =
-            collector.function_instantiations.into_iter().collect();
+        //    每项携带其实例化链（从源码中的具体使用到当前类型），链长即特化深度
+        //    工作表按轮处理：同一轮的实例只依赖模板，并行特化后再按顺序合并
+        let mut type_worklist: Vec<TypeWork> = collector
+            .instantiations
+            .into_iter()
+            .map(|ty| {
+                let chain = vec![ty.to_string()];
+                (ty, chain)
+            })
+            .collect();
+        let mut func_worklist: Vec<FuncWork> = collector
+            .function_instantiations
+            .into_iter()
+            .map(|func| {
+                let chain = vec![Type::Generic(func.0.clone(), func.1.clone()).to_string()];
+                (func, chain)
+            })
+            .collect();
 
         while !type_worklist.is_empty() || !func_worklist.is_empty() {
-            // Process Types
-            while let Some(ty) = type_worklist.pop() {
+            // 3a. 顺序去重：决定本轮要特化的实例（类型在前、函数在后，保持入队顺序）
+            let mut jobs = Vec::new();
+            for (ty, chain) in type_worklist.drain(..) {
                 let mangled_name = mangle_type(&ty);
-
-                // 如果已经处理过（生成的集合中已有），则跳过
-                if self.generated_types.contains(&mangled_name) {
+                if !self.generated_types.insert(mangled_name.clone()) {
                     continue;
                 }
-                self.generated_types.insert(mangled_name.clone());
-
-                // 尝试特化
-                if let Type::Generic(name, args) = &ty {
-                    // 处理结构体特化
-                    if let Some(template) = self.generic_definitions.get(name) {
-                        let new_decl = self.specialize_template(template, args, &mangled_name);
-
-                        // 从新生成的代码中收集新的实例化需求
-                        let mut sub_collector = Collector::new();
-                        sub_collector.collect_decl(&new_decl);
-                        for new_ty in sub_collector.instantiations {
-                            let new_mangled = mangle_type(&new_ty);
-                            if !self.generated_types.contains(&new_mangled) {
-                                type_worklist.push(new_ty);
-                            }
-                        }
-                        for new_func in sub_collector.function_instantiations {
-                            // Function mangling logic (same as type for now)
-                            let dummy_ty = Type::Generic(new_func.0.clone(), new_func.1.clone());
-                            let new_mangled = mangle_type(&dummy_ty);
-                            if !self.generated_types.contains(&new_mangled) {
-                                func_worklist.push(new_func);
-                            }
-                        }
-
-                        self.new_decls.push(new_decl);
-
-                        // Also generate corresponding Impl blocks
-                        if let Some(impls) = self.generic_impls.get(name) {
-                            for impl_decl in impls {
-                                let new_impl =
-                                    self.specialize_template(impl_decl, args, &mangled_name);
-
-                                let mut sub_collector = Collector::new();
-                                sub_collector.collect_decl(&new_impl);
-                                for new_ty in sub_collector.instantiations {
-                                    let new_mangled = mangle_type(&new_ty);
-                                    if !self.generated_types.contains(&new_mangled) {
-                                        type_worklist.push(new_ty);
-                                    }
-                                }
-                                for new_func in sub_collector.function_instantiations {
-                                    let dummy_ty =
-                                        Type::Generic(new_func.0.clone(), new_func.1.clone());
-                                    let new_mangled = mangle_type(&dummy_ty);
-                                    if !self.generated_types.contains(&new_mangled) {
-                                        func_worklist.push(new_func);
-                                    }
-                                }
-                                self.new_decls.push(new_impl);
-                            }
-                        }
+                if let Type::Generic(name, args) = ty {
+                    if self.generic_definitions.contains_key(&name) {
+                        self.check_trait_bounds(&name, &args);
+                        jobs.push(Job {
+                            template: name,
+                            args,
+                            mangled_name,
+                            chain,
+                            with_impls: true,
+                        });
                     }
                 }
             }
-
-            // Process Functions
-            while let Some((func_name, args)) = func_worklist.pop() {
+            for ((func_name, args), chain) in func_worklist.drain(..) {
                 // Reuse mangle_type logic for function name
                 let dummy_ty = Type::Generic(func_name.clone(), args.clone());
                 let mangled_name = mangle_type(&dummy_ty);
-
-                if self.generated_types.contains(&mangled_name) {
+                if !self.generated_types.insert(mangled_name.clone()) {
                     continue;
                 }
-                self.generated_types.insert(mangled_name.clone());
-
-                if let Some(template) = self.generic_definitions.get(&func_name) {
-                    let new_decl = self.specialize_template(template, &args, &mangled_name);
-
-                    let mut sub_collector = Collector::new();
-                    sub_collector.collect_decl(&new_decl);
+                if self.generic_definitions.contains_key(&func_name) {
+                    self.check_trait_bounds(&func_name, &args);
+                    jobs.push(Job {
+                        template: func_name,
+                        args,
+                        mangled_name,
+                        chain,
+                        with_impls: false,
+                    });
+                }
+            }
 
-                    for new_ty in sub_collector.instantiations {
-                        let new_mangled = mangle_type(&new_ty);
-                        if !self.generated_types.contains(&new_mangled) {
-                            type_worklist.push(new_ty);
-                        }
-                    }
-                    for new_func in sub_collector.function_instantiations {
-                        let dummy_ty = Type::Generic(new_func.0.clone(), new_func.1.clone());
-                        let new_mangled = mangle_type(&dummy_ty);
-                        if !self.generated_types.contains(&new_mangled) {
-                            func_worklist.push(new_func);
-                        }
-                    }
-                    self.new_decls.push(new_decl);
+            // 3b. 特化本轮实例：彼此独立，只读模板缓存，可以并行
+            let results: Vec<Vec<(Decl, Collector)>> = if self.parallel && jobs.len() > 1 {
+                jobs.par_iter()
+                    .map(|job| self.specialize_job(job))
+                    .collect()
+            } else {
+                jobs.iter().map(|job| self.specialize_job(job)).collect()
+            };
+
+            // 3c. 按任务顺序合并，输出与顺序执行完全一致
+            for (job, specialized) in jobs.iter().zip(results) {
+                for (decl, found) in specialized {
+                    self.enqueue_instantiations(
+                        found,
+                        &job.template,
+                        &job.chain,
+                        &mut type_worklist,
+                        &mut func_worklist,
+                    );
+                    self.new_decls.push(decl);
                 }
             }
         }
@@ -235,6 +276,112 @@ impl Monomorphizer {
         Program { decls: final_decls }
     }
 
+    /// 特化一项实例：类型实例连同其泛型 impl 一起生成，并收集各声明中出现的实例化
+    fn specialize_job(&self, job: &Job) -> Vec<(Decl, Collector)> {
+        let template = &self.generic_definitions[&job.template];
+        let mut decls = vec![self.specialize_template(template, &job.args, &job.mangled_name)];
+        if job.with_impls {
+            // Also generate corresponding Impl blocks
+            if let Some(impls) = self.generic_impls.get(&job.template) {
+                for impl_decl in impls {
+                    decls.push(self.specialize_template(impl_decl, &job.args, &job.mangled_name));
+                }
+            }
+        }
+        decls
+            .into_iter()
+            .map(|decl| {
+                let mut collector = Collector::new();
+                collector.collect_decl(&decl);
+                (decl, collector)
+            })
+            .collect()
+    }
+
+    /// 把特化结果中新出现的实例化加入工作表；实例化链超过深度上限时报错而不再展开
+    fn enqueue_instantiations(
+        &mut self,
+        sub_collector: Collector,
+        template_name: &str,
+        chain: &[String],
+        type_worklist: &mut Vec<TypeWork>,
+        func_worklist: &mut Vec<FuncWork>,
+    ) {
+        let mut pending = Vec::new();
+        for new_ty in sub_collector.instantiations {
+            if !self.generated_types.contains(&mangle_type(&new_ty)) {
+                pending.push((new_ty, None));
+            }
+        }
+        for new_func in sub_collector.function_instantiations {
+            let dummy_ty = Type::Generic(new_func.0.clone(), new_func.1.clone());
+            if !self.generated_types.contains(&mangle_type(&dummy_ty)) {
+                pending.push((dummy_ty, Some(new_func)));
+            }
+        }
+
+        for (ty, func) in pending {
+            let mut next_chain = chain.to_vec();
+            next_chain.push(ty.to_string());
+            if next_chain.len() > self.max_depth {
+                // 同一模板只报告一次，避免错误刷屏
+                if !self.overflowed.insert(template_name.to_string()) {
+                    continue;
+                }
+                let span = self
+                    .generic_definitions
+                    .get(template_name)
+                    .map(decl_span)
+                    .unwrap_or(0..0);
+                self.errors.push(SemanticError::MonomorphizationOverflow {
+                    chain: next_chain,
+                    limit: self.max_depth,
+                    span,
+                });
+                continue;
+            }
+            match func {
+                Some(func) => func_worklist.push((func, next_chain)),
+                None => type_worklist.push((ty, next_chain)),
+            }
+        }
+    }
+
+    /// 检查泛型实参是否满足模板上每个类型参数的全部 Trait 约束
+    fn check_trait_bounds(&mut self, template_name: &str, args: &[Type]) {
+        let Some(template) = self.generic_definitions.get(template_name) else {
+            return;
+        };
+        let generic_params = match template {
+            Decl::Struct { generic_params, .. }
+            | Decl::Function { generic_params, .. }
+            | Decl::Enum { generic_params, .. } => generic_params,
+            _ => return,
+        };
+        let mut errors = Vec::new();
+        for (gp, ty) in generic_params.iter().zip(args) {
+            for bound in &gp.bounds {
+                let (Type::Struct(trait_name) | Type::Generic(trait_name, _)) = bound else {
+                    continue;
+                };
+                let implemented = self
+                    .trait_impls
+                    .contains(&(mangle_type(ty), trait_name.clone()))
+                    || matches!(ty, Type::Generic(base, _)
+                        if self.trait_impls.contains(&(base.clone(), trait_name.clone())));
+                if !implemented {
+                    errors.push(SemanticError::TraitBoundNotSatisfied {
+                        ty: ty.to_string(),
+                        trait_name: trait_name.clone(),
+                        param: gp.name.clone(),
+                        span: decl_span(template),
+                    });
+                }
+            }
+        }
+        self.errors.append(&mut errors);
+    }
+
     fn specialize_template(&self, template: &Decl, args: &[Type], mangled_name: &str) -> Decl {
         let generic_params = match template {
             Decl::Struct { generic_params, .. } => generic_params,
@@ -267,3 +414,17 @@ impl Monomorphizer {
         specialized
     }
 }
+
+/// 泛型模板声明的位置
+fn decl_span(decl: &Decl) -> Span {
+    match decl {
+        Decl::Struct { span, .. }
+        | Decl::Function { span, .. }
+        | Decl::Enum { span, .. }
+        | Decl::Impl { span, .. } => span.clone(),
+        _ => 0..0,
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -33,6 +33,10 @@ pub fn mangle_type(ty: &Type) -> String {
 fn mangle_type_internal(ty: &Type) -> String {
     match ty {
         Type::Int => "int".to_string(),
+        Type::I8 => "i8".to_string(),
+        Type::I16 => "i16".to_string(),
+        Type::I32 => "i32".to_string(),
+        Type::UInt => "uint".to_string(),
         Type::Float => "float".to_string(),
         Type::String => "string".to_string(),
         Type::Bool => "bool".to_string(),
@@ -41,6 +45,9 @@ fn mangle_type_internal(ty: &Type) -> String {
         // 结构体名直接使用（假设非泛型）
         Type::Struct(name) => name.clone(),
 
+        // newtype 与底层类型是不同的类型，使用自己的名字
+        Type::Newtype(name, _) => name.clone(),
+
         // 泛型实例化: Box<T> -> Box__T
         Type::Generic(name, args) => {
             let encoded_args: Vec<String> = args.iter().map(mangle_type).collect();
@@ -56,6 +63,13 @@ fn mangle_type_internal(ty: &Type) -> String {
         Type::Array { element_type, size } => {
             format!("Array__{}__{}", mangle_type(element_type), size)
         }
+        // [N]T 在名称解析后已替换为具体长度，这里只为兜底
+        Type::ConstSizedArray {
+            element_type,
+            size_name,
+        } => {
+            format!("Array__{}__{}", mangle_type(element_type), size_name)
+        }
 
         // Nullable T? -> T__opt
         Type::Nullable(inner) => {
@@ -79,10 +93,39 @@ fn mangle_type_internal(ty: &Type) -> String {
             format!("Fn__{}_{}", mangle_type(return_type), params.join("_"))
         }
 
+        // Record { int x, int y } -> Rec__x_int__y_int
+        Type::Record(fields) => {
+            let fields: Vec<String> = fields
+                .iter()
+                .map(|(name, ty)| format!("{}_{}", name, mangle_type(ty)))
+                .collect();
+            format!("Rec__{}", fields.join("__"))
+        }
+
+        // dyn Printable -> Dyn__Printable
+        Type::TraitObject(name) => format!("Dyn__{}", name),
+
+        Type::SelfType => "This".to_string(),
         Type::Error => "Error".to_string(),
     }
 }
 
+/// impl 块接收者在符号名中的写法
+///
+/// 结构体名已是单态化后的最终名字（可能已是截断后的哈希名），原样使用，
+/// 与 struct_types 的键一致；其余类型按 [`mangle_type`] 编码
+pub fn mangle_receiver(ty: &Type) -> String {
+    match ty {
+        Type::Struct(name) => name.clone(),
+        _ => mangle_type(ty),
+    }
+}
+
+/// 方法（及枚举构造器）的符号名：`{接收者}_{方法名}`，声明与调用两侧共用
+pub fn method_symbol(receiver: &str, method: &str) -> String {
+    format!("{}_{}", receiver, method)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -126,4 +169,19 @@ mod tests {
         let m2 = mangle_type(&ty);
         assert_eq!(m1, m2);
     }
+
+    #[test]
+    fn test_method_symbol_keeps_long_struct_names() {
+        // 单态化产出的结构体名可能超过截断阈值，方法符号不能对它再做一次哈希
+        let name = "VeryLongStructNameThatExceedsTheLimit";
+        let receiver = mangle_receiver(&Type::Struct(name.into()));
+        assert_eq!(
+            method_symbol(&receiver, "get"),
+            "VeryLongStructNameThatExceedsTheLimit_get"
+        );
+        assert_eq!(
+            method_symbol(&mangle_receiver(&Type::Int), "double"),
+            "int_double"
+        );
+    }
 }
@@ -25,6 +25,12 @@ impl Rewriter {
 
     fn rewrite_type_impl(ty: &Type) -> Type {
         match ty {
+            // 泛型模板字段中的 Result<T, E> 以 Generic 形式出现，替换后还原为内置 Result 类型
+            Type::Generic(name, args) if name == "Result" && args.len() == 2 => Type::Result {
+                ok_type: Box::new(Self::rewrite_type_impl(&args[0])),
+                err_type: Box::new(Self::rewrite_type_impl(&args[1])),
+            },
+
             // Box<int> -> Type::Struct("Box__int")
             Type::Generic(_, args) if !args.is_empty() => {
                 // 如果是 Generic，我们假设所有的 Generic 都已被单态化并生成了对应的 Struct
@@ -51,6 +57,26 @@ impl Rewriter {
 
             Type::Nullable(inner) => Type::Nullable(Box::new(Self::rewrite_type_impl(inner))),
 
+            Type::Record(fields) => Type::Record(
+                fields
+                    .iter()
+                    .map(|(name, field_ty)| (name.clone(), Self::rewrite_type_impl(field_ty)))
+                    .collect(),
+            ),
+
+            Type::Result { ok_type, err_type } => Type::Result {
+                ok_type: Box::new(Self::rewrite_type_impl(ok_type)),
+                err_type: Box::new(Self::rewrite_type_impl(err_type)),
+            },
+
+            Type::Function {
+                param_types,
+                return_type,
+            } => Type::Function {
+                param_types: param_types.iter().map(Self::rewrite_type_impl).collect(),
+                return_type: Box::new(Self::rewrite_type_impl(return_type)),
+            },
+
             _ => ty.clone(),
         }
     }
@@ -59,26 +85,40 @@ impl Rewriter {
         match decl {
             Decl::Struct {
                 span,
+                doc,
+                is_public,
+                is_packed,
+                is_extern,
                 name,
                 generic_params,
                 fields,
             } => Decl::Struct {
                 span,
+                doc,
+                is_public,
+                is_packed,
+                is_extern,
                 name,
                 generic_params,
                 fields: fields.into_iter().map(|f| self.rewrite_field(f)).collect(),
             },
             Decl::Function {
                 span,
+                doc,
+                is_public,
                 name,
                 generic_params,
                 params,
                 return_type,
                 body,
+                source_file,
             } => Decl::Function {
                 span,
+                doc,
+                is_public,
                 name,
                 generic_params,
+                source_file,
                 params: params.into_iter().map(|p| self.rewrite_param(p)).collect(),
                 return_type: self.rewrite_type(&return_type),
                 body: body
@@ -88,15 +128,21 @@ impl Rewriter {
             },
             Decl::ExternFunction {
                 span,
+                doc,
+                abi,
                 name,
                 generic_params,
                 params,
+                is_variadic,
                 return_type,
             } => Decl::ExternFunction {
                 span,
+                doc,
+                abi,
                 name,
                 generic_params,
                 params: params.into_iter().map(|p| self.rewrite_param(p)).collect(),
+                is_variadic,
                 return_type: self.rewrite_type(&return_type),
             },
             Decl::Impl {
@@ -115,28 +161,36 @@ impl Rewriter {
             // Trait 定义：目前不需要重写，直接保留
             Decl::Trait {
                 span,
+                doc,
+                is_public,
                 name,
                 generic_params,
                 methods,
             } => Decl::Trait {
                 span,
+                doc,
+                is_public,
                 name,
                 generic_params,
                 methods,
             },
             Decl::Enum {
                 span,
+                doc,
+                is_public,
                 name,
                 generic_params,
                 variants,
             } => Decl::Enum {
                 span,
+                doc,
+                is_public,
                 name,
                 generic_params,
                 variants: variants
                     .into_iter()
                     .map(|v| match v {
-                        EnumVariant::Unit(n) => EnumVariant::Unit(n),
+                        EnumVariant::Unit(n, value) => EnumVariant::Unit(n, value),
                         EnumVariant::Tuple(n, types) => EnumVariant::Tuple(
                             n,
                             types.into_iter().map(|t| self.rewrite_type(&t)).collect(),
@@ -144,20 +198,55 @@ impl Rewriter {
                     })
                     .collect(),
             },
+            Decl::Const {
+                span,
+                doc,
+                is_public,
+                name,
+                ty,
+                value,
+            } => Decl::Const {
+                span,
+                doc,
+                is_public,
+                name,
+                ty,
+                value,
+            },
+            Decl::Newtype {
+                span,
+                doc,
+                is_public,
+                name,
+                ty,
+            } => Decl::Newtype {
+                span,
+                doc,
+                is_public,
+                name,
+                ty: self.rewrite_type(&ty),
+            },
             Decl::Var {
                 span,
+                is_public,
                 name,
                 ty,
                 value,
             } => Decl::Var {
                 span,
+                is_public,
                 name,
                 ty: ty.map(|t| self.rewrite_type(&t)),
                 value: self.rewrite_expr(value),
             },
-            Decl::Import { items, span } => Decl::Import {
+            Decl::Import {
+                items,
+                span,
+                is_public,
+            } => Decl::Import {
                 items: items.clone(),
                 span,
+                is_public,
             },
         }
     }
@@ -166,6 +255,7 @@ impl Rewriter {
         Field {
             name: field.name,
             ty: self.rewrite_type(&field.ty),
+            default: field.default.map(|expr| self.rewrite_expr(expr)),
         }
     }
 
@@ -181,14 +271,31 @@ impl Rewriter {
             Stmt::VarDecl {
                 span,
                 name,
+                mutable,
                 ty,
                 value,
             } => Stmt::VarDecl {
                 span,
                 name,
+                mutable,
                 ty: ty.map(|t| self.rewrite_type(&t)),
                 value: self.rewrite_expr(value),
             },
+            Stmt::LoopVarDecl {
+                span,
+                name,
+                mutable,
+                ty,
+                loop_stmt,
+                default,
+            } => Stmt::LoopVarDecl {
+                span,
+                name,
+                mutable,
+                ty: ty.map(|t| self.rewrite_type(&t)),
+                loop_stmt: Box::new(self.rewrite_stmt(*loop_stmt)),
+                default: self.rewrite_expr(default),
+            },
             Stmt::Assignment {
                 span,
                 target,
@@ -221,6 +328,23 @@ impl Rewriter {
                 else_block: else_block
                     .map(|b| b.into_iter().map(|s| self.rewrite_stmt(s)).collect()),
             },
+            Stmt::IfLet {
+                span,
+                pattern,
+                value,
+                then_block,
+                else_block,
+            } => Stmt::IfLet {
+                span,
+                pattern,
+                value: self.rewrite_expr(value),
+                then_block: then_block
+                    .into_iter()
+                    .map(|s| self.rewrite_stmt(s))
+                    .collect(),
+                else_block: else_block
+                    .map(|b| b.into_iter().map(|s| self.rewrite_stmt(s)).collect()),
+            },
             Stmt::While {
                 span,
                 condition,
@@ -254,7 +378,10 @@ impl Rewriter {
                 iterable: self.rewrite_expr(iterable),
                 body: body.into_iter().map(|s| self.rewrite_stmt(s)).collect(),
             },
-            Stmt::Break { span } => Stmt::Break { span },
+            Stmt::Break { span, value } => Stmt::Break {
+                span,
+                value: value.map(|e| self.rewrite_expr(e)),
+            },
             Stmt::Continue { span } => Stmt::Continue { span },
         }
     }
@@ -302,10 +429,32 @@ impl Rewriter {
                     .collect(),
             },
 
+            ExprKind::RecordLiteral(fields) => ExprKind::RecordLiteral(
+                fields
+                    .into_iter()
+                    .map(|(n, e)| (n, self.rewrite_expr(e)))
+                    .collect(),
+            ),
+
             ExprKind::VecLiteral(elements) => {
                 ExprKind::VecLiteral(elements.into_iter().map(|e| self.rewrite_expr(e)).collect())
             }
 
+            ExprKind::Fmt { template, args } => ExprKind::Fmt {
+                template,
+                args: args.into_iter().map(|e| self.rewrite_expr(e)).collect(),
+            },
+
+            ExprKind::Repeat {
+                value,
+                count,
+                is_vec,
+            } => ExprKind::Repeat {
+                value: Box::new(self.rewrite_expr(*value)),
+                count: Box::new(self.rewrite_expr(*count)),
+                is_vec,
+            },
+
             ExprKind::Match {
                 value,
                 cases,
@@ -316,6 +465,7 @@ impl Rewriter {
                     .into_iter()
                     .map(|c| MatchCase {
                         pattern: c.pattern,
+                        guard: c.guard.map(|g| self.rewrite_expr(g)),
                         body: Box::new(self.rewrite_expr(*c.body)),
                         span: c.span,
                     })
@@ -343,6 +493,11 @@ impl Rewriter {
                 }
             }
 
+            ExprKind::Cast { ty, value } => ExprKind::Cast {
+                ty: self.rewrite_type(&ty),
+                value: Box::new(self.rewrite_expr(*value)),
+            },
+
             // Literal, Variable unchanged
             _ => expr.kind,
         };
@@ -3,23 +3,35 @@ use lency_syntax::ast::{Decl, EnumVariant, Field, GenericParam, Param};
 
 pub fn specialize(spec: &Specializer, decl: &Decl) -> Decl {
     match decl {
+        Decl::Const { .. } | Decl::Newtype { .. } => decl.clone(),
         Decl::Var {
             span,
+            is_public,
             name,
             ty,
             value,
         } => Decl::Var {
             span: span.clone(),
+            is_public: *is_public,
             name: name.clone(),
             ty: ty.clone(),       // Should specialize type
             value: value.clone(), // Should specialize expr
         },
-        Decl::Import { items, span } => Decl::Import {
+        Decl::Import {
+            items,
+            span,
+            is_public,
+        } => Decl::Import {
             span: span.clone(),
+            is_public: *is_public,
             items: items.clone(),
         },
         Decl::Struct {
             span,
+            doc,
+            is_public,
+            is_packed,
+            is_extern,
             name,
             generic_params,
             fields,
@@ -33,6 +45,10 @@ pub fn specialize(spec: &Specializer, decl: &Decl) -> Decl {
 
             Decl::Struct {
                 span: span.clone(),
+                doc: doc.clone(),
+                is_public: *is_public,
+                is_packed: *is_packed,
+                is_extern: *is_extern,
                 name: name.clone(),
                 generic_params: remaining_params,
                 fields: fields.iter().map(|f| spec.specialize_field(f)).collect(),
@@ -40,11 +56,14 @@ pub fn specialize(spec: &Specializer, decl: &Decl) -> Decl {
         }
         Decl::Function {
             span,
+            doc,
+            is_public,
             name,
             generic_params,
             params,
             return_type,
             body,
+            source_file,
         } => {
             let remaining_params: Vec<GenericParam> = generic_params
                 .iter()
@@ -54,18 +73,24 @@ pub fn specialize(spec: &Specializer, decl: &Decl) -> Decl {
 
             Decl::Function {
                 span: span.clone(),
+                doc: doc.clone(),
+                is_public: *is_public,
                 name: name.clone(),
                 generic_params: remaining_params,
                 params: params.iter().map(|p| spec.specialize_param(p)).collect(),
                 return_type: spec.specialize_type(return_type),
                 body: body.iter().map(|stmt| spec.specialize_stmt(stmt)).collect(),
+                source_file: source_file.clone(),
             }
         }
         Decl::ExternFunction {
             span,
+            doc,
+            abi,
             name,
             generic_params,
             params,
+            is_variadic,
             return_type,
         } => {
             let remaining_params: Vec<GenericParam> = generic_params
@@ -76,9 +101,12 @@ pub fn specialize(spec: &Specializer, decl: &Decl) -> Decl {
 
             Decl::ExternFunction {
                 span: span.clone(),
+                doc: doc.clone(),
+                abi: abi.clone(),
                 name: name.clone(),
                 generic_params: remaining_params,
                 params: params.iter().map(|p| spec.specialize_param(p)).collect(),
+                is_variadic: *is_variadic,
                 return_type: spec.specialize_type(return_type),
             }
         }
@@ -106,17 +134,23 @@ pub fn specialize(spec: &Specializer, decl: &Decl) -> Decl {
         // Trait 定义：目前不需要特化，直接保留
         Decl::Trait {
             span,
+            doc,
+            is_public,
             name,
             generic_params,
             methods,
         } => Decl::Trait {
             span: span.clone(),
+            doc: doc.clone(),
+            is_public: *is_public,
             name: name.clone(),
             generic_params: generic_params.clone(),
             methods: methods.clone(),
         },
         Decl::Enum {
             span,
+            doc,
+            is_public,
             name,
             generic_params,
             variants,
@@ -129,12 +163,14 @@ pub fn specialize(spec: &Specializer, decl: &Decl) -> Decl {
 
             Decl::Enum {
                 span: span.clone(),
+                doc: doc.clone(),
+                is_public: *is_public,
                 name: name.clone(),
                 generic_params: remaining_params,
                 variants: variants
                     .iter()
                     .map(|v| match v {
-                        EnumVariant::Unit(n) => EnumVariant::Unit(n.clone()),
+                        EnumVariant::Unit(n, value) => EnumVariant::Unit(n.clone(), *value),
                         EnumVariant::Tuple(n, types) => EnumVariant::Tuple(
                             n.clone(),
                             types.iter().map(|t| spec.specialize_type(t)).collect(),
@@ -150,6 +186,10 @@ pub fn specialize_field(spec: &Specializer, field: &Field) -> Field {
     Field {
         name: field.name.clone(),
         ty: spec.specialize_type(&field.ty),
+        default: field
+            .default
+            .as_ref()
+            .map(|expr| spec.specialize_expr(expr)),
     }
 }
 
@@ -6,6 +6,10 @@ pub fn specialize(spec: &Specializer, expr: &Expr) -> Expr {
         ExprKind::Literal(lit) => ExprKind::Literal(lit.clone()), // Lit 不变
         ExprKind::Unit => ExprKind::Unit,
         ExprKind::Variable(name) => ExprKind::Variable(name.clone()),
+        ExprKind::Path { type_name, member } => ExprKind::Path {
+            type_name: type_name.clone(),
+            member: member.clone(),
+        },
         ExprKind::Binary(lhs, op, rhs) => ExprKind::Binary(
             Box::new(spec.specialize_expr(lhs)),
             op.clone(),
@@ -43,10 +47,27 @@ pub fn specialize(spec: &Specializer, expr: &Expr) -> Expr {
                 .collect(),
         },
 
+        ExprKind::RecordLiteral(fields) => ExprKind::RecordLiteral(
+            fields
+                .iter()
+                .map(|(n, e)| (n.clone(), spec.specialize_expr(e)))
+                .collect(),
+        ),
+
         ExprKind::VecLiteral(elements) => {
             ExprKind::VecLiteral(elements.iter().map(|e| spec.specialize_expr(e)).collect())
         }
 
+        ExprKind::Repeat {
+            value,
+            count,
+            is_vec,
+        } => ExprKind::Repeat {
+            value: Box::new(spec.specialize_expr(value)),
+            count: Box::new(spec.specialize_expr(count)),
+            is_vec: *is_vec,
+        },
+
         ExprKind::GenericInstantiation { base, args } => ExprKind::GenericInstantiation {
             base: Box::new(spec.specialize_expr(base)),
             args: args.iter().map(|t| spec.specialize_type(t)).collect(),
@@ -62,6 +83,7 @@ pub fn specialize(spec: &Specializer, expr: &Expr) -> Expr {
                 .iter()
                 .map(|c| MatchCase {
                     pattern: c.pattern.clone(),
+                    guard: c.guard.as_ref().map(|g| spec.specialize_expr(g)),
                     body: Box::new(spec.specialize_expr(&c.body)),
                     span: c.span.clone(),
                 })
@@ -107,10 +129,22 @@ pub fn specialize(spec: &Specializer, expr: &Expr) -> Expr {
         ),
         ExprKind::CharToString(arg) => ExprKind::CharToString(Box::new(spec.specialize_expr(arg))),
         ExprKind::Panic(arg) => ExprKind::Panic(Box::new(spec.specialize_expr(arg))),
+        ExprKind::Assert(cond, message) => ExprKind::Assert(
+            Box::new(spec.specialize_expr(cond)),
+            Box::new(spec.specialize_expr(message)),
+        ),
         ExprKind::Format(template, args) => ExprKind::Format(
             Box::new(spec.specialize_expr(template)),
             Box::new(spec.specialize_expr(args)),
         ),
+        ExprKind::Fmt { template, args } => ExprKind::Fmt {
+            template: template.clone(),
+            args: args.iter().map(|e| spec.specialize_expr(e)).collect(),
+        },
+        ExprKind::Cast { ty, value } => ExprKind::Cast {
+            ty: spec.specialize_type(ty),
+            value: Box::new(spec.specialize_expr(value)),
+        },
     };
 
     Expr {
@@ -6,14 +6,31 @@ pub fn specialize(spec: &Specializer, stmt: &Stmt) -> Stmt {
         Stmt::VarDecl {
             span,
             name,
+            mutable,
             ty,
             value,
         } => Stmt::VarDecl {
             span: span.clone(),
             name: name.clone(),
+            mutable: *mutable,
             ty: ty.as_ref().map(|t| spec.specialize_type(t)),
             value: spec.specialize_expr(value),
         },
+        Stmt::LoopVarDecl {
+            span,
+            name,
+            mutable,
+            ty,
+            loop_stmt,
+            default,
+        } => Stmt::LoopVarDecl {
+            span: span.clone(),
+            name: name.clone(),
+            mutable: *mutable,
+            ty: ty.as_ref().map(|t| spec.specialize_type(t)),
+            loop_stmt: Box::new(spec.specialize_stmt(loop_stmt)),
+            default: spec.specialize_expr(default),
+        },
         Stmt::Assignment {
             span,
             target,
@@ -42,6 +59,21 @@ pub fn specialize(spec: &Specializer, stmt: &Stmt) -> Stmt {
                 .as_ref()
                 .map(|b| b.iter().map(|s| spec.specialize_stmt(s)).collect()),
         },
+        Stmt::IfLet {
+            span,
+            pattern,
+            value,
+            then_block,
+            else_block,
+        } => Stmt::IfLet {
+            span: span.clone(),
+            pattern: pattern.clone(),
+            value: spec.specialize_expr(value),
+            then_block: then_block.iter().map(|s| spec.specialize_stmt(s)).collect(),
+            else_block: else_block
+                .as_ref()
+                .map(|b| b.iter().map(|s| spec.specialize_stmt(s)).collect()),
+        },
         Stmt::While {
             span,
             condition,
@@ -75,7 +107,10 @@ pub fn specialize(spec: &Specializer, stmt: &Stmt) -> Stmt {
             iterable: spec.specialize_expr(iterable),
             body: body.iter().map(|s| spec.specialize_stmt(s)).collect(),
         },
-        Stmt::Break { span } => Stmt::Break { span: span.clone() },
+        Stmt::Break { span, value } => Stmt::Break {
+            span: span.clone(),
+            value: value.as_ref().map(|e| spec.specialize_expr(e)),
+        },
         Stmt::Continue { span } => Stmt::Continue { span: span.clone() },
     }
 }
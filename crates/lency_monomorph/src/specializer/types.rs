@@ -38,6 +38,31 @@ pub fn specialize(spec: &Specializer, ty: &Type) -> Type {
 
         Type::Nullable(inner) => Type::Nullable(Box::new(spec.specialize_type(inner))),
 
+        Type::Record(fields) => Type::Record(
+            fields
+                .iter()
+                .map(|(name, field_ty)| (name.clone(), spec.specialize_type(field_ty)))
+                .collect(),
+        ),
+
+        // Result<T, E> -> Result<int, Error>
+        Type::Result { ok_type, err_type } => Type::Result {
+            ok_type: Box::new(spec.specialize_type(ok_type)),
+            err_type: Box::new(spec.specialize_type(err_type)),
+        },
+
+        // T(T) -> int(int)
+        Type::Function {
+            param_types,
+            return_type,
+        } => Type::Function {
+            param_types: param_types
+                .iter()
+                .map(|param| spec.specialize_type(param))
+                .collect(),
+            return_type: Box::new(spec.specialize_type(return_type)),
+        },
+
         // 基础类型不变
         _ => ty.clone(),
     }
@@ -0,0 +1,193 @@
+//! 单态化测试
+
+use super::*;
+use lency_syntax::ast::EnumVariant;
+
+type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+/// 解析并分析源码，错误转为字符串
+fn analyzed(source: &str) -> Result<Program, String> {
+    let mut program = lency_syntax::parser::parse(source)
+        .map_err(|errors| format!("parse failed: {:?}", errors))?;
+    lency_sema::analyze(&mut program).map_err(|errors| format!("analysis failed: {:?}", errors))?;
+    Ok(program)
+}
+
+#[test]
+fn test_nested_generic_field_types_are_substituted() -> TestResult {
+    let program = analyzed(
+        r#"
+        struct Error { string message }
+        struct Box<T> { T value }
+        struct Slot<T> {
+            Box<Box<T>> nested
+            Result<T, Error> outcome
+            T(T) step
+        }
+        int inc(int x) { return x + 1 }
+        int main() {
+            var inner = Box<int> { value: 41 }
+            var slot = Slot<int> {
+                nested: Box<Box<int>> { value: inner },
+                outcome: Ok(1),
+                step: inc
+            }
+            return slot.nested.value.value
+        }
+    "#,
+    )?;
+
+    let program = Monomorphizer::new().process(program);
+    let fields = program
+        .decls
+        .iter()
+        .find_map(|decl| match decl {
+            Decl::Struct { name, fields, .. } if name == "Slot__int" => Some(fields),
+            _ => None,
+        })
+        .ok_or("Slot<int> not instantiated")?;
+    let field_ty = |name: &str| {
+        fields
+            .iter()
+            .find(|f| f.name == name)
+            .map(|f| f.ty.clone())
+            .ok_or(format!("field {} not found", name))
+    };
+
+    assert_eq!(field_ty("nested")?, Type::Struct("Box__Box__int".into()));
+    assert_eq!(
+        field_ty("outcome")?,
+        Type::Result {
+            ok_type: Box::new(Type::Int),
+            err_type: Box::new(Type::Struct("Error".into())),
+        }
+    );
+    assert_eq!(
+        field_ty("step")?,
+        Type::Function {
+            param_types: vec![Type::Int],
+            return_type: Box::new(Type::Int),
+        }
+    );
+    Ok(())
+}
+
+#[test]
+fn test_parallel_specialization_matches_sequential() -> TestResult {
+    let source = r#"
+        struct Box<T> { T value }
+        struct Pair<A, B> { A first B second }
+        impl<T> Box<T> {
+            T get() { return this.value }
+        }
+        T id<T>(T x) { return x }
+        Pair<A, B> pair<A, B>(A a, B b) { return Pair<A, B> { first: a, second: b } }
+        Box<T> boxed<T>(T x) { return Box<T> { value: id::<T>(x) } }
+        int main() {
+            var a = boxed::<int>(1)
+            var b = boxed::<string>("s")
+            var c = boxed::<float>(1.5)
+            var d = boxed::<bool>(true)
+            var p = pair::<int, string>(a.get(), b.get())
+            var q = pair::<float, bool>(c.get(), d.get())
+            var r = pair::<Box<int>, Box<string>>(a, b)
+            var w = Box<Box<Box<int>>> { value: Box<Box<int>> { value: a } }
+            return p.first + r.first.get() + w.value.value.value
+        }
+    "#;
+    let monomorphize = |parallel: bool| {
+        let program = analyzed(source)?;
+        let mut mono = Monomorphizer::new();
+        mono.set_parallel(parallel);
+        let program = mono.process(program);
+        let errors = mono.take_errors();
+        assert!(errors.is_empty(), "{:?}", errors);
+        Ok::<_, String>(program)
+    };
+
+    let sequential = monomorphize(false)?;
+    let parallel = monomorphize(true)?;
+    let names: Vec<String> = sequential
+        .decls
+        .iter()
+        .filter_map(|decl| match decl {
+            Decl::Struct { name, .. } | Decl::Function { name, .. } => Some(name.clone()),
+            _ => None,
+        })
+        .collect();
+    for expected in [
+        "Box__int",
+        "Box__Box__Box__int",
+        "Pair__float_bool",
+        "boxed__bool",
+        "id__string",
+    ] {
+        assert!(
+            names.iter().any(|n| n == expected),
+            "missing {} in {:?}",
+            expected,
+            names
+        );
+    }
+    assert_eq!(format!("{:#?}", sequential), format!("{:#?}", parallel));
+    Ok(())
+}
+
+#[test]
+fn test_generic_enum_instances_are_specialized() -> TestResult {
+    let program = analyzed(
+        r#"
+        enum Maybe<T> {
+            Some(T),
+            None
+        }
+        void main() {
+            var a = Maybe::<int>.Some(42)
+            var b = Maybe::<string>.Some("hi")
+            match b {
+                case Maybe.Some(s) => print(s),
+                case Maybe.None => print("none")
+            }
+        }
+    "#,
+    )?;
+
+    let program = Monomorphizer::new().process(program);
+    let variants_of = |enum_name: &str| {
+        program
+            .decls
+            .iter()
+            .find_map(|decl| match decl {
+                Decl::Enum {
+                    name,
+                    generic_params,
+                    variants,
+                    ..
+                } if name == enum_name => {
+                    assert!(generic_params.is_empty());
+                    Some(variants.clone())
+                }
+                _ => None,
+            })
+            .ok_or(format!("{} not instantiated", enum_name))
+    };
+
+    let payloads = |enum_name: &str| -> Result<Vec<Vec<Type>>, String> {
+        Ok(variants_of(enum_name)?
+            .into_iter()
+            .map(|variant| match variant {
+                EnumVariant::Unit(..) => vec![],
+                EnumVariant::Tuple(_, types) => types,
+            })
+            .collect())
+    };
+
+    assert_eq!(payloads("Maybe__int")?, vec![vec![Type::Int], vec![]]);
+    assert_eq!(payloads("Maybe__string")?, vec![vec![Type::String], vec![]]);
+    // 泛型模板本身不进入输出
+    assert!(!program
+        .decls
+        .iter()
+        .any(|decl| matches!(decl, Decl::Enum { name, .. } if name == "Maybe")));
+    Ok(())
+}
@@ -7,6 +7,7 @@ pub mod file;
 pub mod hashmap;
 pub mod hashmap_string;
 pub mod string;
+pub mod string_bytes;
 use std::alloc::{alloc, dealloc, realloc, Layout};
 use std::ffi::{CStr, CString};
 use std::os::raw::c_char;
@@ -127,8 +128,20 @@ impl LencyVec {
             *self.data.offset(index as isize) = value;
         }
     }
+
+    /// 按比较器排序（稳定排序），比较器返回负数/0/正数表示小于/等于/大于
+    pub fn sort_by(&mut self, cmp: LencyComparator) {
+        if self.len <= 1 {
+            return;
+        }
+        let elements = unsafe { std::slice::from_raw_parts_mut(self.data, self.len as usize) };
+        elements.sort_by(|a, b| cmp(*a, *b).cmp(&0));
+    }
 }
 
+/// Lency 函数作为比较器传入运行时时的函数指针类型: int(T, T)
+pub type LencyComparator = extern "C" fn(i64, i64) -> i64;
+
 impl Drop for LencyVec {
     fn drop(&mut self) {
         if !self.data.is_null() {
@@ -216,6 +229,19 @@ pub unsafe extern "C" fn lency_vec_set(vec: *mut LencyVec, index: i64, value: i6
     }
 }
 
+/// Sort the Vec with a comparator function compiled from Lency code
+///
+/// # Safety
+/// `vec` must be a valid pointer returned by `lency_vec_new`
+#[no_mangle]
+pub unsafe extern "C" fn lency_vec_sort_by(vec: *mut LencyVec, cmp: LencyComparator) {
+    unsafe {
+        if !vec.is_null() {
+            (*vec).sort_by(cmp);
+        }
+    }
+}
+
 /// Free a Vec
 ///
 /// # Safety
@@ -400,4 +426,24 @@ mod tests {
         vec.set(0, 99);
         assert_eq!(vec.get(0), 99);
     }
+
+    extern "C" fn descending(a: i64, b: i64) -> i64 {
+        b - a
+    }
+
+    #[test]
+    fn test_vec_sort_by_comparator() {
+        let vec = lency_vec_new(4);
+        unsafe {
+            for x in [3, 1, 4, 1, 5] {
+                lency_vec_push(vec, x);
+            }
+            lency_vec_sort_by(vec, descending);
+            let sorted: Vec<i64> = (0..lency_vec_len(vec))
+                .map(|i| lency_vec_get(vec, i))
+                .collect();
+            assert_eq!(sorted, vec![5, 4, 3, 1, 1]);
+            lency_vec_free(vec);
+        }
+    }
 }
@@ -36,12 +36,25 @@ pub unsafe extern "C" fn lency_string_eq(lhs: *const c_char, rhs: *const c_char)
     }
 }
 
-/// 获取字符串长度
+/// 获取字符串长度（Unicode 标量值个数，而不是字节数）
 ///
 /// # Safety
 /// `ptr` must be a valid null-terminated C string
 #[no_mangle]
 pub unsafe extern "C" fn lency_string_len(ptr: *const c_char) -> i64 {
+    if ptr.is_null() {
+        return 0;
+    }
+    let bytes = unsafe { CStr::from_ptr(ptr) }.to_bytes();
+    bytes.iter().filter(|&&b| !is_utf8_continuation(b)).count() as i64
+}
+
+/// 获取字符串的 UTF-8 字节数
+///
+/// # Safety
+/// `ptr` must be a valid null-terminated C string
+#[no_mangle]
+pub unsafe extern "C" fn lency_string_byte_len(ptr: *const c_char) -> i64 {
     if ptr.is_null() {
         return 0;
     }
@@ -49,6 +62,43 @@ pub unsafe extern "C" fn lency_string_len(ptr: *const c_char) -> i64 {
     c_str.to_bytes().len() as i64
 }
 
+/// 获取第 `index` 个字符（按 Unicode 标量值计数）的码点
+/// 越界时返回 -1，目标字符不是合法 UTF-8 时返回 -2
+///
+/// # Safety
+/// `ptr` must be a valid null-terminated C string
+#[no_mangle]
+pub unsafe extern "C" fn lency_string_char_at(ptr: *const c_char, index: i64) -> i64 {
+    if ptr.is_null() || index < 0 {
+        return -1;
+    }
+    let bytes = unsafe { CStr::from_ptr(ptr) }.to_bytes();
+
+    // 只扫描到目标字符为止，不对整串做 UTF-8 校验
+    let Some(start) = bytes
+        .iter()
+        .enumerate()
+        .filter(|(_, &b)| !is_utf8_continuation(b))
+        .nth(index as usize)
+        .map(|(i, _)| i)
+    else {
+        return -1;
+    };
+    let end = bytes[start + 1..]
+        .iter()
+        .position(|&b| !is_utf8_continuation(b))
+        .map_or(bytes.len(), |offset| start + 1 + offset);
+
+    match std::str::from_utf8(&bytes[start..end]) {
+        Ok(ch) => ch.chars().next().map_or(-2, |c| c as i64),
+        Err(_) => -2,
+    }
+}
+
+fn is_utf8_continuation(byte: u8) -> bool {
+    byte & 0xC0 == 0x80
+}
+
 /// 去除字符串首尾空白
 /// 返回新分配的字符串
 ///
@@ -189,7 +239,7 @@ pub unsafe extern "C" fn lency_string_join(
     result
 }
 
-/// 提取子串
+/// 提取子串（`start` 与 `len` 按字符计数）
 /// 返回新分配的字符串
 ///
 /// # Safety
@@ -210,8 +260,14 @@ pub unsafe extern "C" fn lency_string_substr(
         Err(_) => return std::ptr::null_mut(),
     };
 
-    let start_idx = start as usize;
-    let end_idx = std::cmp::min(start_idx + len as usize, s.len());
+    // start/len 按字符计数，与 lency_string_len 一致
+    let byte_offset = |chars: usize| {
+        s.char_indices()
+            .nth(chars)
+            .map_or(s.len(), |(offset, _)| offset)
+    };
+    let start_idx = byte_offset(start as usize);
+    let end_idx = byte_offset((start as usize).saturating_add(len as usize));
 
     if start_idx >= s.len() {
         // 返回空字符串
@@ -244,25 +300,22 @@ pub unsafe extern "C" fn lency_string_substr(
 /// 调用者负责释放返回的内存
 #[no_mangle]
 pub unsafe extern "C" fn lency_char_to_string(char_code: i64) -> *mut c_char {
-    // 分配2字节: 1个字符 + null terminator
-    let result = unsafe { libc::malloc(2) as *mut c_char };
+    // 非法码点退化为 '?'
+    let ch = u32::try_from(char_code)
+        .ok()
+        .and_then(char::from_u32)
+        .unwrap_or('?');
+    let mut buf = [0u8; 4];
+    let encoded = ch.encode_utf8(&mut buf).as_bytes();
+
+    let result = unsafe { libc::malloc(encoded.len() + 1) as *mut c_char };
     if result.is_null() {
         return std::ptr::null_mut();
     }
 
-    // 简单处理: 只支持 ASCII (0-127)
-    // 对于 Unicode，需要更复杂的 UTF-8 编码
-    if (0..=127).contains(&char_code) {
-        unsafe {
-            *result = char_code as c_char;
-            *result.add(1) = 0; // null terminator
-        }
-    } else {
-        // 非 ASCII: 返回空字符串或 '?'
-        unsafe {
-            *result = b'?' as c_char;
-            *result.add(1) = 0;
-        }
+    unsafe {
+        std::ptr::copy_nonoverlapping(encoded.as_ptr(), result as *mut u8, encoded.len());
+        *result.add(encoded.len()) = 0; // null terminator
     }
 
     result
@@ -356,6 +409,49 @@ mod tests {
         assert_eq!(unsafe { lency_string_len(empty.as_ptr()) }, 0);
     }
 
+    #[test]
+    fn test_string_len_counts_chars() {
+        // "héllo, 世界" 共 9 个字符，é 占 2 字节，世/界 各占 3 字节
+        let s = CString::new("héllo, 世界").unwrap();
+        assert_eq!(unsafe { lency_string_len(s.as_ptr()) }, 9);
+        assert_eq!(unsafe { lency_string_byte_len(s.as_ptr()) }, 14);
+    }
+
+    #[test]
+    fn test_string_char_at() {
+        let s = CString::new("aé世😀").unwrap();
+        let at = |i| unsafe { lency_string_char_at(s.as_ptr(), i) };
+        assert_eq!(at(0), 'a' as i64);
+        assert_eq!(at(1), 'é' as i64);
+        assert_eq!(at(2), '世' as i64);
+        assert_eq!(at(3), '😀' as i64);
+        assert_eq!(at(4), -1);
+        assert_eq!(at(-1), -1);
+
+        // 字符数少于字节数：按字节看仍在范围内的下标也算越界
+        let s = CString::new("héllo").unwrap();
+        assert_eq!(unsafe { lency_string_char_at(s.as_ptr(), 4) }, 'o' as i64);
+        assert_eq!(unsafe { lency_string_char_at(s.as_ptr(), 5) }, -1);
+
+        // 截断的多字节序列与越界区分开
+        let s = CString::new(vec![b'a', 0xC3, b'b']).unwrap();
+        assert_eq!(unsafe { lency_string_char_at(s.as_ptr(), 1) }, -2);
+        assert_eq!(unsafe { lency_string_char_at(s.as_ptr(), 2) }, 'b' as i64);
+    }
+
+    #[test]
+    fn test_char_to_string_encodes_utf8() {
+        for ch in ['a', 'é', '世', '😀'] {
+            let result = unsafe { lency_char_to_string(ch as i64) };
+            let text = unsafe { CStr::from_ptr(result) }.to_str().unwrap();
+            assert_eq!(text, ch.to_string());
+            unsafe { libc::free(result as *mut libc::c_void) };
+        }
+        let invalid = unsafe { lency_char_to_string(-5) };
+        assert_eq!(unsafe { CStr::from_ptr(invalid) }.to_bytes(), b"?");
+        unsafe { libc::free(invalid as *mut libc::c_void) };
+    }
+
     #[test]
     fn test_string_trim() {
         let s = CString::new("  hello world  ").unwrap();
@@ -448,6 +544,20 @@ mod tests {
         unsafe { libc::free(result2 as *mut libc::c_void) };
     }
 
+    #[test]
+    fn test_string_substr_counts_chars() {
+        let s = CString::new("你好, world").unwrap();
+
+        let result = unsafe { lency_string_substr(s.as_ptr(), 1, 3) };
+        assert_eq!(unsafe { CStr::from_ptr(result) }.to_str().unwrap(), "好, ");
+        unsafe { libc::free(result as *mut libc::c_void) };
+
+        // 超出末尾时截断
+        let result = unsafe { lency_string_substr(s.as_ptr(), 4, 100) };
+        assert_eq!(unsafe { CStr::from_ptr(result) }.to_str().unwrap(), "world");
+        unsafe { libc::free(result as *mut libc::c_void) };
+    }
+
     #[test]
     fn test_string_eq() {
         let lhs = CString::new("hello").unwrap();
@@ -0,0 +1,89 @@
+//! Lency String Byte Runtime
+//!
+//! 字符串与字节数组之间的转换，供按字节扫描的代码使用
+
+use std::ffi::CStr;
+use std::os::raw::c_char;
+
+use crate::LencyVec;
+
+/// 把字符串的 UTF-8 字节拷贝到新的 Vec<int> 中
+/// 扫描器先取一次字节数组，之后按下标 O(1) 访问，避免逐字符调用 len/substr
+///
+/// # Safety
+/// `ptr` must be a valid null-terminated C string
+#[no_mangle]
+pub unsafe extern "C" fn lency_string_bytes(ptr: *const c_char) -> *mut LencyVec {
+    if ptr.is_null() {
+        return Box::into_raw(LencyVec::new(0));
+    }
+    let bytes = unsafe { CStr::from_ptr(ptr) }.to_bytes();
+    let mut vec = LencyVec::new(bytes.len() as i64);
+    for &b in bytes {
+        vec.push(b as i64);
+    }
+    Box::into_raw(vec)
+}
+
+/// 用字节数组中 `[start, start + len)` 的字节构造新字符串
+/// 范围超出数组时截断；切开多字节字符产生的非法序列替换为 U+FFFD
+///
+/// # Safety
+/// `vec_ptr` must be a valid LencyVec holding byte values
+#[no_mangle]
+pub unsafe extern "C" fn lency_string_from_bytes(
+    vec_ptr: *const LencyVec,
+    start: i64,
+    len: i64,
+) -> *mut c_char {
+    if vec_ptr.is_null() || start < 0 || len < 0 {
+        return std::ptr::null_mut();
+    }
+    let vec = unsafe { &*vec_ptr };
+    let end = start.saturating_add(len).min(vec.len());
+    let bytes: Vec<u8> = (start..end).map(|i| vec.get(i) as u8).collect();
+    let text = String::from_utf8_lossy(&bytes);
+
+    let result = unsafe { libc::malloc(text.len() + 1) as *mut c_char };
+    if result.is_null() {
+        return std::ptr::null_mut();
+    }
+    unsafe {
+        std::ptr::copy_nonoverlapping(text.as_ptr(), result as *mut u8, text.len());
+        *result.add(text.len()) = 0;
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::CString;
+
+    #[test]
+    fn test_string_bytes_round_trip() {
+        let s = CString::new("aé世").unwrap();
+        let vec = unsafe { lency_string_bytes(s.as_ptr()) };
+        let bytes: Vec<i64> = (0..unsafe { (*vec).len() })
+            .map(|i| unsafe { (*vec).get(i) })
+            .collect();
+        assert_eq!(bytes, vec![0x61, 0xC3, 0xA9, 0xE4, 0xB8, 0x96]);
+
+        let text = |start, len| {
+            let ptr = unsafe { lency_string_from_bytes(vec, start, len) };
+            let owned = unsafe { CStr::from_ptr(ptr) }.to_str().unwrap().to_string();
+            unsafe { libc::free(ptr as *mut libc::c_void) };
+            owned
+        };
+        assert_eq!(text(0, 6), "aé世");
+        assert_eq!(text(1, 2), "é");
+        // 超出范围截断，切开多字节字符时替换为 U+FFFD
+        assert_eq!(text(3, 100), "世");
+        assert_eq!(text(0, 2), "a\u{FFFD}");
+        assert_eq!(text(6, 1), "");
+
+        unsafe {
+            let _ = Box::from_raw(vec);
+        }
+    }
+}
@@ -14,6 +14,14 @@ pub enum SemanticError {
     #[error("undefined variable '{name}'")]
     UndefinedVariable { name: String, span: Span },
 
+    /// 局部变量在同一块中的声明之前被使用
+    #[error("variable '{name}' is used before its definition")]
+    UseBeforeDefinition {
+        name: String,
+        span: Span,
+        defined_at: Span,
+    },
+
     /// 未定义的函数
     #[error("undefined function '{name}'")]
     UndefinedFunction { name: String, span: Span },
@@ -30,10 +38,51 @@ pub enum SemanticError {
         previous_span: Span,
     },
 
+    /// 枚举变体的判别值重复
+    #[error("duplicate discriminant {value} for variant '{variant}' in enum '{enum_name}'")]
+    DuplicateDiscriminant {
+        enum_name: String,
+        variant: String,
+        value: i64,
+        span: Span,
+    },
+
+    /// 引用了其他模块中未公开 (非 pub) 的符号
+    #[error("'{name}' is private to its module and cannot be used here")]
+    PrivateSymbol { name: String, span: Span },
+
+    /// 数组长度引用的名称不是整数常量
+    #[error("array size '{name}' must be a non-negative int constant")]
+    InvalidArraySize { name: String, span: Span },
+
     /// 导入模块错误
     #[error("import error: {message}")]
     ImportError { message: String, span: Span },
 
+    /// 泛型实参不满足类型参数的 Trait 约束
+    #[error("type '{ty}' does not implement trait '{trait_name}' required by generic parameter '{param}'")]
+    TraitBoundNotSatisfied {
+        ty: String,
+        trait_name: String,
+        param: String,
+        span: Span,
+    },
+
+    /// 泛型实例化无限展开（如 struct S<T> { S<Vec<T>> next }）
+    #[error("generic instantiation exceeds depth limit {limit}: {}", format_chain(.chain))]
+    MonomorphizationOverflow {
+        chain: Vec<String>,
+        limit: usize,
+        span: Span,
+    },
+
+    /// 模块之间循环导入；cycle 依次列出环上的文件，首尾相同
+    #[error("circular import: {}", format_cycle(.cycle))]
+    CircularImport {
+        cycle: Vec<std::path::PathBuf>,
+        span: Span,
+    },
+
     // ============ 类型检查错误 ============
     /// 类型不匹配
     #[error("type mismatch: expected '{expected}', found '{found}'")]
@@ -43,10 +92,50 @@ pub enum SemanticError {
         span: Span,
     },
 
+    /// 不允许的显式类型转换
+    #[error("cannot cast '{from}' to '{to}'")]
+    InvalidCast {
+        from: String,
+        to: String,
+        span: Span,
+    },
+
+    /// 结构体字段默认值只能是字面量
+    #[error("default value of field '{field}' in struct '{name}' must be a literal")]
+    FieldDefaultNotLiteral {
+        name: String,
+        field: String,
+        span: Span,
+    },
+
+    /// 结构体字面量缺少没有默认值的字段
+    #[error("struct literal '{class}' is missing required field '{field}'")]
+    MissingField {
+        class: String,
+        field: String,
+        span: Span,
+    },
+
+    /// 常量只能用字面量（或由字面量组成的数组与结构体）初始化
+    #[error("const '{name}' must be initialized with int, float or bool literals, or arrays and structs of them")]
+    ConstNotLiteral { name: String, span: Span },
+
+    /// 给 let 绑定、常量或模式绑定等不可变变量赋值
+    #[error("cannot assign to immutable variable '{name}'")]
+    AssignToImmutable { name: String, span: Span },
+
+    /// 赋值目标不是左值（只允许变量、字段和索引）
+    #[error("invalid assignment target")]
+    InvalidAssignmentTarget { span: Span },
+
     /// 无法推导类型
     #[error("cannot infer type for '{name}', please add type annotation")]
     CannotInferType { name: String, span: Span },
 
+    /// 未标注类型的变量用 null 初始化，无法推导具体的可空类型
+    #[error("cannot infer type of 'null', please add a nullable type annotation (e.g. 'var x: int? = null')")]
+    CannotInferNullType { span: Span },
+
     /// 二元操作类型错误
     #[error("operator '{op}' cannot be applied to types '{left}' and '{right}'")]
     InvalidBinaryOp {
@@ -54,6 +143,8 @@ pub enum SemanticError {
         left: String,
         right: String,
         span: Span,
+        /// 可直接应用的修复：(操作数范围, 替换文本)
+        fix: Option<Box<(Span, String)>>,
     },
 
     /// 一元操作类型错误
@@ -73,6 +164,10 @@ pub enum SemanticError {
     #[error("value of type '{ty}' might be null, use 'if != null' check or '?' operator")]
     PossibleNullAccess { ty: String, span: Span },
 
+    /// 可空 bool 直接用作条件（null 既不是 true 也不是 false）
+    #[error("condition of type '{ty}' might be null, provide a default with 'x ?? false' or 'x.value_or(false)'")]
+    NullableConditionRequiresCheck { ty: String, span: Span },
+
     // ============ 函数调用错误 ============
     /// 参数数量不匹配
     #[error("function '{name}' expects {expected} arguments, but got {found}")]
@@ -83,6 +178,25 @@ pub enum SemanticError {
         span: Span,
     },
 
+    /// 方法参数数量不匹配，带上接收者类型（如 Vec<int>.push）；用户方法附带声明签名
+    #[error("method '{receiver}.{method}' expects {expected} arguments, but got {found}{}", format_signature(.signature))]
+    MethodArgumentCountMismatch {
+        receiver: String,
+        method: String,
+        expected: usize,
+        found: usize,
+        signature: Option<String>,
+        span: Span,
+    },
+
+    /// fmt! 模板的 {} 占位符数量与实参数量不一致
+    #[error("format string has {placeholders} placeholders, but {found} arguments were given")]
+    FormatArgMismatch {
+        placeholders: usize,
+        found: usize,
+        span: Span,
+    },
+
     /// 返回类型错误
     #[error("return type mismatch: expected '{expected}', found '{found}'")]
     ReturnTypeMismatch {
@@ -99,6 +213,19 @@ pub enum SemanticError {
         span: Span,
     },
 
+    // ============ C 互操作错误 ============
+    /// extern 声明使用了不支持的调用约定
+    #[error("unsupported ABI \"{abi}\"")]
+    UnsupportedAbi { abi: String, span: Span },
+
+    /// 类型不能按值跨越 C ABI（extern struct 字段、extern "C" 函数的参数与返回值）
+    #[error("type '{ty}' cannot be passed by value across the C ABI")]
+    NotFfiSafe { ty: String, span: Span },
+
+    /// 可变参数部分只能传基础类型
+    #[error("type '{ty}' cannot be passed as a variadic argument")]
+    InvalidVariadicArgument { ty: String, span: Span },
+
     // ============ 类相关错误 ============
     /// 未定义的字段
     #[error("type '{class}' has no field named '{field}'")]
@@ -108,6 +235,30 @@ pub enum SemanticError {
         span: Span,
     },
 
+    /// 通过 Type::method 调用引用了 this 的方法
+    #[error("method '{method}' uses 'this' and cannot be called as '{class}::{method}'")]
+    NotAnAssociatedFunction {
+        class: String,
+        method: String,
+        span: Span,
+    },
+
+    /// 关联函数路径未被调用
+    #[error("associated function '{class}::{method}' must be called")]
+    AssociatedFunctionNotCalled {
+        class: String,
+        method: String,
+        span: Span,
+    },
+
+    /// `?` 传播的错误类型与所在函数的错误类型不兼容
+    #[error("cannot propagate error type '{from}' with '?' in a function returning error type '{to}' (add '{to}::from({from})' to convert)")]
+    IncompatibleErrorType {
+        from: String,
+        to: String,
+        span: Span,
+    },
+
     /// 未定义的方法
     #[error("type '{class}' has no method named '{method}'")]
     UndefinedMethod {
@@ -136,6 +287,14 @@ pub enum SemanticError {
     #[error("'continue' outside loop")]
     ContinueOutsideLoop { span: Span },
 
+    /// 普通循环中的 break 带了值（只有作为值的循环才能 break value）
+    #[error("'break' with a value is only allowed in a loop whose value is used, e.g. 'var x = while ... else default'")]
+    BreakValueOutsideLoopValue { span: Span },
+
+    /// 作为值的循环中的 break 没有提供值
+    #[error("'break' in a loop whose value is used must provide a value")]
+    MissingBreakValue { span: Span },
+
     // ============ 数组相关错误 ============
     /// 数组索引编译期越界
     #[error(
@@ -143,6 +302,14 @@ pub enum SemanticError {
     )]
     ArrayIndexOutOfBounds { index: i64, size: usize, span: Span },
 
+    /// 数组字面量长度与声明的固定长度不一致
+    #[error("array length mismatch: expected {expected} elements, found {found}")]
+    ArrayLengthMismatch {
+        expected: usize,
+        found: usize,
+        span: Span,
+    },
+
     // ============ 泛型相关错误 ============
     /// 泛型参数数量不匹配
     #[error("generic type '{name}' expects {expected} type arguments, but got {found}")]
@@ -163,6 +330,9 @@ pub enum SemanticError {
 
     // ============ Trait 相关错误 ============
     /// 未定义的 Trait
+    #[error("'This' can only be used inside an impl block")]
+    SelfTypeOutsideImpl { span: Span },
+
     #[error("undefined trait '{name}'")]
     UndefinedTrait { name: String, span: Span },
 
@@ -184,6 +354,14 @@ pub enum SemanticError {
         span: Span,
     },
 
+    /// 值不能转换为 Trait 对象 dyn T
+    #[error("type '{ty}' cannot be used as 'dyn {trait_name}'")]
+    InvalidTraitObject {
+        ty: String,
+        trait_name: String,
+        span: Span,
+    },
+
     // ============ 模式匹配错误 ============
     /// 模式匹配不穷尽
     #[error("pattern not exhaustive. Missing variants: {missing_variants:?}")]
@@ -191,6 +369,18 @@ pub enum SemanticError {
         missing_variants: Vec<String>,
         span: Span,
     },
+
+    /// 区间模式的起点大于终点，永远不会匹配
+    #[error("range pattern {start}..={end} is empty, the start must not exceed the end")]
+    EmptyRangePattern { start: i64, end: i64, span: Span },
+
+    /// 或模式中绑定了变量（各分支无法保证都提供该绑定）
+    #[error("variable '{name}' cannot be bound inside an or-pattern")]
+    OrPatternBinding { name: String, span: Span },
+
+    /// 前面的分支已覆盖该分支的所有值（如位于 `_` 之后），该分支永远不会执行
+    #[error("unreachable pattern: already covered by an earlier arm")]
+    UnreachablePattern { span: Span },
 }
 
 impl SemanticError {
@@ -198,34 +388,65 @@ impl SemanticError {
     pub fn span(&self) -> &Span {
         match self {
             Self::UndefinedVariable { span, .. } => span,
+            Self::UseBeforeDefinition { span, .. } => span,
             Self::UndefinedFunction { span, .. } => span,
             Self::UndefinedType { span, .. } => span,
             Self::DuplicateDefinition { span, .. } => span,
+            Self::DuplicateDiscriminant { span, .. } => span,
+            Self::PrivateSymbol { span, .. } => span,
+            Self::InvalidArraySize { span, .. } => span,
             Self::ImportError { span, .. } => span,
+            Self::CircularImport { span, .. } => span,
+            Self::MonomorphizationOverflow { span, .. } => span,
+            Self::TraitBoundNotSatisfied { span, .. } => span,
             Self::TypeMismatch { span, .. } => span,
+            Self::InvalidCast { span, .. } => span,
             Self::CannotInferType { span, .. } => span,
+            Self::CannotInferNullType { span } => span,
+            Self::FieldDefaultNotLiteral { span, .. } => span,
+            Self::MissingField { span, .. } => span,
+            Self::ConstNotLiteral { span, .. } => span,
+            Self::AssignToImmutable { span, .. } => span,
             Self::InvalidBinaryOp { span, .. } => span,
             Self::InvalidUnaryOp { span, .. } => span,
             Self::NullAssignmentToNonNullable { span, .. } => span,
             Self::PossibleNullAccess { span, .. } => span,
+            Self::NullableConditionRequiresCheck { span, .. } => span,
             Self::ArgumentCountMismatch { span, .. } => span,
+            Self::MethodArgumentCountMismatch { span, .. } => span,
+            Self::FormatArgMismatch { span, .. } => span,
             Self::ReturnTypeMismatch { span, .. } => span,
             Self::MissingReturn { span, .. } => span,
+            Self::UnsupportedAbi { span, .. } => span,
+            Self::NotFfiSafe { span, .. } => span,
+            Self::InvalidVariadicArgument { span, .. } => span,
             Self::UndefinedField { span, .. } => span,
+            Self::NotAnAssociatedFunction { span, .. } => span,
+            Self::IncompatibleErrorType { span, .. } => span,
+            Self::AssociatedFunctionNotCalled { span, .. } => span,
             Self::UndefinedMethod { span, .. } => span,
             Self::NotAClass { span, .. } => span,
             Self::NotAStruct { span, .. } => span,
             Self::NotCallable { span, .. } => span,
             Self::BreakOutsideLoop { span } => span,
             Self::ContinueOutsideLoop { span } => span,
+            Self::BreakValueOutsideLoopValue { span } => span,
+            Self::MissingBreakValue { span } => span,
             Self::ArrayIndexOutOfBounds { span, .. } => span,
+            Self::ArrayLengthMismatch { span, .. } => span,
+            Self::InvalidAssignmentTarget { span } => span,
             Self::GenericArityMismatch { span, .. } => span,
             Self::NotAGenericType { span, .. } => span,
             Self::InvalidGenericArg { span, .. } => span,
+            Self::SelfTypeOutsideImpl { span } => span,
             Self::UndefinedTrait { span, .. } => span,
             Self::MissingTraitMethod { span, .. } => span,
             Self::TraitMethodSignatureMismatch { span, .. } => span,
+            Self::InvalidTraitObject { span, .. } => span,
             Self::PatternNotExhaustive { span, .. } => span,
+            Self::EmptyRangePattern { span, .. } => span,
+            Self::OrPatternBinding { span, .. } => span,
+            Self::UnreachablePattern { span } => span,
         }
     }
 
@@ -238,9 +459,34 @@ impl SemanticError {
             Self::UndefinedVariable { name, .. } => {
                 diag = diag.with_note(format!("Did you declare the variable '{}' before using it? (If this is during Lency bootstrapping, check your definitions in the parser)", name));
             }
+            Self::UseBeforeDefinition { name, .. } => {
+                diag = diag.with_note(format!("local variables are visible only after their declaration; move the declaration of '{}' above this use", name));
+            }
             Self::UndefinedFunction { name, .. } => {
                 diag = diag.with_note(format!("Did you define the function '{}'? (If this is during Lency bootstrapping, ensure the function is imported or defined locally)", name));
             }
+            Self::UnsupportedAbi { .. } => {
+                diag = diag.with_note("only extern \"C\" is supported");
+            }
+            Self::NotFfiSafe { .. } => {
+                diag = diag.with_note(
+                    "declare it as 'extern struct' with integer, float, bool or string fields",
+                );
+            }
+            Self::InvalidTraitObject { trait_name, .. } => {
+                diag = diag.with_note(format!(
+                    "only struct types that implement '{}' can be converted to a trait object",
+                    trait_name
+                ));
+            }
+            Self::InvalidBinaryOp { fix: Some(fix), .. } => {
+                let (span, text) = fix.as_ref();
+                let func = text.split_once('(').map_or(text.as_str(), |(func, _)| func);
+                diag = diag.with_suggestion(
+                    lency_diagnostics::Suggestion::new(format!("wrap in {}", func))
+                        .with_replacement(span.clone(), text.clone()),
+                );
+            }
             _ => {}
         }
 
@@ -248,8 +494,78 @@ impl SemanticError {
     }
 }
 
+/// 将导入环格式化为 a.lcy -> b.lcy -> a.lcy
+fn format_cycle(cycle: &[std::path::PathBuf]) -> String {
+    cycle
+        .iter()
+        .map(|p| p.display().to_string())
+        .collect::<Vec<_>>()
+        .join(" -> ")
+}
+
+/// 方法声明签名的附注：`(declared as 'int add(int a)')`，内置方法没有签名
+fn format_signature(signature: &Option<String>) -> String {
+    signature
+        .as_ref()
+        .map(|sig| format!(" (declared as '{}')", sig))
+        .unwrap_or_default()
+}
+
+/// 将实例化链格式化为 A -> B -> ...；过长时省略中段
+fn format_chain(chain: &[String]) -> String {
+    const HEAD: usize = 3;
+    const TAIL: usize = 1;
+    if chain.len() <= HEAD + TAIL + 1 {
+        return chain.join(" -> ");
+    }
+    format!(
+        "{} -> ... ({} more) ... -> {}",
+        chain[..HEAD].join(" -> "),
+        chain.len() - HEAD - TAIL,
+        chain[chain.len() - TAIL..].join(" -> ")
+    )
+}
+
 impl From<SemanticError> for lency_diagnostics::Diagnostic {
     fn from(err: SemanticError) -> Self {
         err.to_diagnostic()
     }
 }
+
+/// 语义分析警告：不影响编译结果，只提示可能的问题
+#[derive(Debug, Clone, Error)]
+pub enum SemanticWarning {
+    /// 嵌套作用域中的变量遮蔽了外层同名变量
+    #[error("variable '{name}' shadows a variable from an outer scope")]
+    ShadowedVariable {
+        name: String,
+        span: Span,
+        previous_span: Span,
+    },
+    /// if 条件在编译期恒为真或恒为假，另一个分支永远不会执行
+    #[error("condition is always {value}; the {branch} branch is never executed")]
+    DeadBranch {
+        value: bool,
+        branch: &'static str,
+        span: Span,
+    },
+    /// 浮点数用 == / != 直接比较，舍入误差常使结果出乎意料
+    #[error("comparing floats with '{op}' is unreliable; check whether their difference is within an epsilon instead")]
+    FloatEquality { op: &'static str, span: Span },
+}
+
+impl SemanticWarning {
+    /// 获取警告的位置
+    pub fn span(&self) -> &Span {
+        match self {
+            Self::ShadowedVariable { span, .. }
+            | Self::DeadBranch { span, .. }
+            | Self::FloatEquality { span, .. } => span,
+        }
+    }
+
+    /// 转换为统一诊断格式
+    pub fn to_diagnostic(&self) -> lency_diagnostics::Diagnostic {
+        lency_diagnostics::Diagnostic::warning(self.to_string()).span(self.span().clone())
+    }
+}
@@ -28,14 +28,14 @@ pub mod types;
 
 // 重新导出核心类型
 pub use crate::symbol::{
-    FieldInfo, FunctionSymbol, GenericParamSymbol, ParameterSymbol, StructSymbol, Symbol,
-    TraitMethodSignature, TraitSymbol, VariableSymbol,
+    FieldInfo, FunctionSymbol, GenericParamSymbol, ParameterSymbol, StructSymbol, Symbol, SymbolId,
+    SymbolKind, TraitMethodSignature, TraitSymbol, VariableSymbol,
 };
-pub use error::SemanticError;
+pub use error::{SemanticError, SemanticWarning};
 pub use null_safety::NullSafetyChecker;
 pub use operators::{BinaryOpRegistry, UnaryOpRegistry};
-pub use resolver::Resolver;
-pub use scope::{Scope, ScopeId, ScopeKind, ScopeStack};
+pub use resolver::{clear_import_cache, Resolver};
+pub use scope::{Resolution, Scope, ScopeId, ScopeKind, ScopeStack};
 pub use type_check::TypeChecker;
 pub use type_infer::TypeInferer;
 pub use types::{TypeInfo, TypeRegistry};
@@ -47,6 +47,8 @@ use lency_syntax::ast::Program;
 pub struct AnalysisResult {
     /// 符号表（包含所有定义的符号）
     pub scopes: ScopeStack,
+    /// 警告（不影响分析是否成功）
+    pub warnings: Vec<SemanticWarning>,
 }
 
 /// 分析整个程序
@@ -60,22 +62,41 @@ pub struct AnalysisResult {
 ///
 /// 返回所有收集到的语义错误
 pub fn analyze(program: &mut Program) -> Result<AnalysisResult, Vec<SemanticError>> {
+    let (result, errors) = analyze_with_resolver(program, Resolver::new());
+    if errors.is_empty() {
+        Ok(result)
+    } else {
+        Err(errors)
+    }
+}
+
+/// 使用给定的 Resolver 分析程序，无论是否出错都返回符号表
+///
+/// 供 LSP 等工具使用：即使源码有错误，也需要符号表来提供导航功能。
+/// 调用者可以预先配置 Resolver（例如通过 `set_root_dir` 指定导入根目录）。
+pub fn analyze_with_resolver(
+    program: &mut Program,
+    mut resolver: Resolver,
+) -> (AnalysisResult, Vec<SemanticError>) {
     let mut all_errors: Vec<SemanticError> = Vec::new();
 
     // Pass 1: 名称解析
-    let mut resolver = Resolver::new();
     if let Err(errors) = resolver.resolve(program) {
         all_errors.extend(errors);
     }
 
     // 即使有错误也继续，收集尽可能多的错误信息
+    let mut warnings = resolver.take_warnings();
+    let warn_float_equality = resolver.warn_float_equality;
     let mut scopes = resolver.into_scopes();
 
     // Pass 2: 类型检查
     let mut type_checker = TypeChecker::new(&mut scopes);
+    type_checker.set_warn_float_equality(warn_float_equality);
     if let Err(errors) = type_checker.check(program) {
         all_errors.extend(errors);
     }
+    warnings.extend(type_checker.take_warnings());
 
     // Pass 3: 空安全检查
     let mut null_checker = NullSafetyChecker::new(&mut scopes);
@@ -83,158 +104,8 @@ pub fn analyze(program: &mut Program) -> Result<AnalysisResult, Vec<SemanticErro
         all_errors.extend(errors);
     }
 
-    if all_errors.is_empty() {
-        Ok(AnalysisResult { scopes })
-    } else {
-        Err(all_errors)
-    }
+    (AnalysisResult { scopes, warnings }, all_errors)
 }
 
 #[cfg(test)]
-mod tests {
-    use super::*;
-    use lency_syntax::ast::*;
-
-    /// 创建一个简单的测试程序
-    fn make_simple_program() -> Program {
-        // int main() { var x = 10; return x; }
-        Program {
-            decls: vec![Decl::Function {
-                span: 0..50,
-                name: "main".to_string(),
-                generic_params: vec![],
-                params: vec![],
-                return_type: Type::Int,
-                body: vec![
-                    Stmt::VarDecl {
-                        span: 10..20,
-                        name: "x".to_string(),
-                        ty: Some(Type::Int),
-                        value: Expr {
-                            kind: ExprKind::Literal(Literal::Int(10)),
-                            span: 15..17,
-                        },
-                    },
-                    Stmt::Return {
-                        span: 20..30,
-                        value: Some(Expr {
-                            kind: ExprKind::Variable("x".to_string()),
-                            span: 27..28,
-                        }),
-                    },
-                ],
-            }],
-        }
-    }
-
-    #[test]
-    fn test_analyze_simple_program() {
-        let mut program = make_simple_program();
-        let result = analyze(&mut program);
-        assert!(
-            result.is_ok(),
-            "Analysis should succeed: {:?}",
-            result.err()
-        );
-    }
-
-    #[test]
-    fn test_undefined_variable_error() {
-        // int main() { return y; }  -- y 未定义
-        let mut program = Program {
-            decls: vec![Decl::Function {
-                span: 0..30,
-                name: "main".to_string(),
-                generic_params: vec![],
-                params: vec![],
-                return_type: Type::Int,
-                body: vec![Stmt::Return {
-                    span: 10..20,
-                    value: Some(Expr {
-                        kind: ExprKind::Variable("y".to_string()),
-                        span: 17..18,
-                    }),
-                }],
-            }],
-        };
-
-        let result = analyze(&mut program);
-        assert!(result.is_err());
-
-        let errors = result.unwrap_err();
-        assert!(errors
-            .iter()
-            .any(|e| matches!(e, SemanticError::UndefinedVariable { name, .. } if name == "y")));
-    }
-
-    #[test]
-    fn test_type_mismatch_error() {
-        // int main() { var x: int = "hello"; return x; }
-        let mut program = Program {
-            decls: vec![Decl::Function {
-                span: 0..50,
-                name: "main".to_string(),
-                generic_params: vec![],
-                params: vec![],
-                return_type: Type::Int,
-                body: vec![
-                    Stmt::VarDecl {
-                        span: 10..30,
-                        name: "x".to_string(),
-                        ty: Some(Type::Int),
-                        value: Expr {
-                            kind: ExprKind::Literal(Literal::String("hello".to_string())),
-                            span: 20..27,
-                        },
-                    },
-                    Stmt::Return {
-                        span: 30..40,
-                        value: Some(Expr {
-                            kind: ExprKind::Variable("x".to_string()),
-                            span: 37..38,
-                        }),
-                    },
-                ],
-            }],
-        };
-
-        let result = analyze(&mut program);
-        assert!(result.is_err());
-
-        let errors = result.unwrap_err();
-        assert!(errors
-            .iter()
-            .any(|e| matches!(e, SemanticError::TypeMismatch { .. })));
-    }
-
-    #[test]
-    fn test_null_safety_error() {
-        // void test() { var s: string = null; }  -- null 赋给非空类型
-        let mut program = Program {
-            decls: vec![Decl::Function {
-                span: 0..50,
-                name: "test".to_string(),
-                generic_params: vec![],
-                params: vec![],
-                return_type: Type::Void,
-                body: vec![Stmt::VarDecl {
-                    span: 10..40,
-                    name: "s".to_string(),
-                    ty: Some(Type::String),
-                    value: Expr {
-                        kind: ExprKind::Literal(Literal::Null),
-                        span: 30..34,
-                    },
-                }],
-            }],
-        };
-
-        let result = analyze(&mut program);
-        assert!(result.is_err());
-
-        let errors = result.unwrap_err();
-        assert!(errors
-            .iter()
-            .any(|e| matches!(e, SemanticError::NullAssignmentToNonNullable { .. })));
-    }
-}
+mod tests;
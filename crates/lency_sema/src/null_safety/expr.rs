@@ -28,7 +28,19 @@ pub fn check_expr(checker: &mut NullSafetyChecker, expr: &mut Expr) {
             checker.check_expr(object);
         }
         ExprKind::Call { callee, args } => {
-            checker.check_expr(callee);
+            // T? 的内置方法 (value_or、map) 自行处理 null，只检查接收者内部
+            let nullable_builtin = match &mut callee.kind {
+                ExprKind::Get { object, name } if crate::type_infer::is_nullable_builtin(name) => {
+                    let mut inferer =
+                        TypeInferer::with_scope(checker.scopes, checker.current_scope);
+                    matches!(inferer.infer(object), Ok(Type::Nullable(_)))
+                }
+                _ => false,
+            };
+            match &mut callee.kind {
+                ExprKind::Get { object, .. } if nullable_builtin => checker.check_expr(object),
+                _ => checker.check_expr(callee),
+            }
             for arg in args {
                 checker.check_expr(arg);
             }
@@ -73,6 +85,9 @@ pub fn check_expr(checker: &mut NullSafetyChecker, expr: &mut Expr) {
         } => {
             checker.check_expr(value);
             for case in cases {
+                if let Some(guard) = &mut case.guard {
+                    checker.check_expr(guard);
+                }
                 checker.check_expr(&mut case.body);
             }
             if let Some(def) = default {
@@ -83,7 +98,7 @@ pub fn check_expr(checker: &mut NullSafetyChecker, expr: &mut Expr) {
             checker.check_expr(expr);
         }
 
-        ExprKind::StructLiteral { fields, .. } => {
+        ExprKind::StructLiteral { fields, .. } | ExprKind::RecordLiteral(fields) => {
             // Check all field value expressions
             for (_, value) in fields {
                 checker.check_expr(value);
@@ -94,11 +109,15 @@ pub fn check_expr(checker: &mut NullSafetyChecker, expr: &mut Expr) {
             checker.check_expr(base);
         }
 
-        ExprKind::VecLiteral(elements) => {
+        ExprKind::VecLiteral(elements) | ExprKind::Fmt { args: elements, .. } => {
             for elem in elements {
                 checker.check_expr(elem);
             }
         }
+        ExprKind::Repeat { value, count, .. } => {
+            checker.check_expr(value);
+            checker.check_expr(count);
+        }
 
         // Variable 和 Literal 不需要递归检查
         _ => {}
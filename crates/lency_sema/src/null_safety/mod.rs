@@ -116,7 +116,7 @@ impl<'a> NullSafetyChecker<'a> {
 
             // Enum 定义：也不需要（变体类型检查在 Type Check）
             Decl::Enum { .. } => {}
-            Decl::Var { .. } => {}
+            Decl::Const { .. } | Decl::Var { .. } | Decl::Newtype { .. } => {}
             Decl::Import { .. } => {}
         }
     }
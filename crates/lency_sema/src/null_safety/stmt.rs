@@ -10,9 +10,21 @@ pub fn check_stmt(checker: &mut NullSafetyChecker, stmt: &mut Stmt) {
             ty,
             value,
             span,
+            ..
         } => {
             check_var_decl(checker, name, ty.as_ref(), value, span);
         }
+        Stmt::LoopVarDecl {
+            name,
+            ty,
+            loop_stmt,
+            default,
+            span,
+            ..
+        } => {
+            checker.check_stmt(loop_stmt);
+            check_var_decl(checker, name, ty.as_ref(), default, span);
+        }
         Stmt::Assignment {
             target,
             value,
@@ -38,6 +50,26 @@ pub fn check_stmt(checker: &mut NullSafetyChecker, stmt: &mut Stmt) {
         } => {
             check_if(checker, condition, then_block, else_block.as_deref_mut());
         }
+        Stmt::IfLet {
+            value,
+            then_block,
+            else_block,
+            ..
+        } => {
+            checker.check_expr(value);
+            checker.with_child_scope(|checker: &mut NullSafetyChecker| {
+                for stmt in then_block {
+                    checker.check_stmt(stmt);
+                }
+            });
+            if let Some(else_stmts) = else_block {
+                checker.with_child_scope(|checker: &mut NullSafetyChecker| {
+                    for stmt in else_stmts {
+                        checker.check_stmt(stmt);
+                    }
+                });
+            }
+        }
         Stmt::While {
             condition, body, ..
         } => {
@@ -92,6 +124,11 @@ pub fn check_stmt(checker: &mut NullSafetyChecker, stmt: &mut Stmt) {
                 checker.check_expr(expr);
             }
         }
+        Stmt::Break {
+            value: Some(value), ..
+        } => {
+            checker.check_expr(value);
+        }
         Stmt::Break { .. } | Stmt::Continue { .. } => {
             // 控制流语句无空安全问题
         }
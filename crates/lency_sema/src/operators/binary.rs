@@ -21,6 +21,12 @@ pub struct BinaryOpSignature {
 /// 集中管理所有二元运算符的类型规则
 pub struct BinaryOpRegistry {
     signatures: Vec<BinaryOpSignature>,
+    /// 枚举类型支持的运算符（同一枚举类型之间，结果为 bool）
+    enum_ops: Vec<BinaryOp>,
+    /// 结构体类型支持的运算符（同一结构体类型之间逐字段比较，结果为 bool）
+    struct_ops: Vec<BinaryOp>,
+    /// 可重载的运算符：(运算符, Trait 名, 方法名)
+    operator_traits: Vec<(BinaryOp, &'static str, &'static str)>,
 }
 
 impl BinaryOpRegistry {
@@ -28,6 +34,9 @@ impl BinaryOpRegistry {
     pub fn new() -> Self {
         let mut registry = Self {
             signatures: Vec::new(),
+            enum_ops: Vec::new(),
+            struct_ops: Vec::new(),
+            operator_traits: Vec::new(),
         };
         registry.register_builtins();
         registry
@@ -38,6 +47,9 @@ impl BinaryOpRegistry {
         self.register_arithmetic();
         self.register_comparison();
         self.register_logical();
+        self.register_enum();
+        self.register_struct();
+        self.register_operator_traits();
     }
 
     /// 注册算术运算符
@@ -47,9 +59,17 @@ impl BinaryOpRegistry {
         use BinaryOp::*;
         use Type::*;
 
-        // int op int -> int
+        // 整数 op 整数 -> 较宽的整数类型（同宽度时保持原类型，如 i32 + i32 -> i32）
+        for (lhs, rhs) in int_pairs() {
+            let result = wider_int(&lhs, &rhs);
+            for op in [Add, Sub, Mul, Div, Mod] {
+                self.add(op, lhs.clone(), rhs.clone(), result.clone());
+            }
+        }
+
+        // uint op uint -> uint（溢出按 2^64 回绕；不与有符号整数混合运算）
         for op in [Add, Sub, Mul, Div, Mod] {
-            self.add(op.clone(), Int, Int, Int);
+            self.add(op, UInt, UInt, UInt);
         }
 
         // float op float -> float
@@ -57,10 +77,12 @@ impl BinaryOpRegistry {
             self.add(op.clone(), Float, Float, Float);
         }
 
-        // 数值提升: int/float -> float
-        for op in [Add, Sub, Mul, Div, Mod] {
-            self.add(op.clone(), Int, Float, Float);
-            self.add(op.clone(), Float, Int, Float);
+        // 数值提升: 整数/float -> float
+        for int in INT_TYPES {
+            for op in [Add, Sub, Mul, Div, Mod] {
+                self.add(op.clone(), int.clone(), Float, Float);
+                self.add(op, Float, int.clone(), Float);
+            }
         }
 
         // 字符串连接: string + string -> string
@@ -74,9 +96,16 @@ impl BinaryOpRegistry {
         use BinaryOp::*;
         use Type::*;
 
-        // int 比较
+        // 整数比较（不同宽度之间先拓宽到较宽的类型）
+        for (lhs, rhs) in int_pairs() {
+            for op in [Eq, Neq, Lt, Gt, Leq, Geq] {
+                self.add(op, lhs.clone(), rhs.clone(), Bool);
+            }
+        }
+
+        // uint 比较（代码生成使用无符号谓词）
         for op in [Eq, Neq, Lt, Gt, Leq, Geq] {
-            self.add(op.clone(), Int, Int, Bool);
+            self.add(op, UInt, UInt, Bool);
         }
 
         // float 比较
@@ -109,6 +138,35 @@ impl BinaryOpRegistry {
         }
     }
 
+    /// 注册枚举运算符
+    ///
+    /// 枚举只支持相等比较（比较 tag 与 payload），不支持 <, > 等排序比较
+    fn register_enum(&mut self) {
+        self.enum_ops = vec![BinaryOp::Eq, BinaryOp::Neq];
+    }
+
+    /// 注册结构体运算符
+    ///
+    /// 结构体按字段深度比较相等性，不支持排序比较
+    fn register_struct(&mut self) {
+        self.struct_ops = vec![BinaryOp::Eq, BinaryOp::Neq];
+    }
+
+    /// 注册可重载的运算符
+    ///
+    /// 用户类型实现对应 Trait（见 std.ops）后，`a + b` 会降级为 `a.add(b)`
+    fn register_operator_traits(&mut self) {
+        use BinaryOp::*;
+
+        self.operator_traits = vec![
+            (Add, "Add", "add"),
+            (Sub, "Sub", "sub"),
+            (Mul, "Mul", "mul"),
+            (Div, "Div", "div"),
+            (Mod, "Mod", "mod"),
+        ];
+    }
+
     /// 添加运算符签名
     fn add(&mut self, op: BinaryOp, lhs: Type, rhs: Type, result: Type) {
         self.signatures.push(BinaryOpSignature {
@@ -177,10 +235,87 @@ impl BinaryOpRegistry {
             left: lhs.to_string(),
             right: rhs.to_string(),
             span: span.clone(),
+            fix: None,
+        })
+    }
+
+    /// 查找运算符对应的 (Trait 名, 方法名)
+    ///
+    /// 只有算术运算符可以通过 Trait 重载
+    pub fn operator_trait(&self, op: &BinaryOp) -> Option<(&'static str, &'static str)> {
+        self.operator_traits
+            .iter()
+            .find(|(o, _, _)| o == op)
+            .map(|(_, trait_name, method)| (*trait_name, *method))
+    }
+
+    /// 查找枚举类型的运算符
+    ///
+    /// 枚举类型由调用者识别（注册表本身不持有符号表），两侧必须是同一枚举类型
+    pub fn lookup_enum(
+        &self,
+        op: &BinaryOp,
+        lhs: &Type,
+        rhs: &Type,
+        span: &std::ops::Range<usize>,
+    ) -> Result<Type, SemanticError> {
+        Self::lookup_same_type(&self.enum_ops, op, lhs, rhs, span)
+    }
+
+    /// 查找结构体类型的运算符
+    ///
+    /// 与枚举相同，结构体由调用者识别，两侧必须是同一结构体类型
+    pub fn lookup_struct(
+        &self,
+        op: &BinaryOp,
+        lhs: &Type,
+        rhs: &Type,
+        span: &std::ops::Range<usize>,
+    ) -> Result<Type, SemanticError> {
+        Self::lookup_same_type(&self.struct_ops, op, lhs, rhs, span)
+    }
+
+    /// 两侧为同一类型且运算符在允许列表中时结果为 bool
+    fn lookup_same_type(
+        ops: &[BinaryOp],
+        op: &BinaryOp,
+        lhs: &Type,
+        rhs: &Type,
+        span: &std::ops::Range<usize>,
+    ) -> Result<Type, SemanticError> {
+        if lhs == rhs && ops.contains(op) {
+            return Ok(Type::Bool);
+        }
+
+        Err(SemanticError::InvalidBinaryOp {
+            op: format!("{:?}", op),
+            left: lhs.to_string(),
+            right: rhs.to_string(),
+            span: span.clone(),
+            fix: None,
         })
     }
 }
 
+/// 所有整数类型，按位宽从小到大排列
+const INT_TYPES: [Type; 4] = [Type::I8, Type::I16, Type::I32, Type::Int];
+
+/// 任意两个整数类型的组合（含相同类型）
+fn int_pairs() -> impl Iterator<Item = (Type, Type)> {
+    INT_TYPES
+        .into_iter()
+        .flat_map(|lhs| INT_TYPES.into_iter().map(move |rhs| (lhs.clone(), rhs)))
+}
+
+/// 两个整数类型中位宽较大的一个
+fn wider_int(lhs: &Type, rhs: &Type) -> Type {
+    if lhs.int_bits() >= rhs.int_bits() {
+        lhs.clone()
+    } else {
+        rhs.clone()
+    }
+}
+
 impl Default for BinaryOpRegistry {
     fn default() -> Self {
         Self::new()
@@ -200,6 +335,60 @@ mod tests {
         assert_eq!(result.unwrap(), Type::Int);
     }
 
+    #[test]
+    fn test_operator_trait_mapping() {
+        let registry = BinaryOpRegistry::new();
+
+        assert_eq!(
+            registry.operator_trait(&BinaryOp::Add),
+            Some(("Add", "add"))
+        );
+        assert_eq!(
+            registry.operator_trait(&BinaryOp::Mod),
+            Some(("Mod", "mod"))
+        );
+        assert_eq!(registry.operator_trait(&BinaryOp::Eq), None);
+    }
+
+    #[test]
+    fn test_arithmetic_preserves_and_widens_int_width() {
+        let registry = BinaryOpRegistry::new();
+
+        // i32 + i32 -> i32
+        let result = registry.lookup(&BinaryOp::Add, &Type::I32, &Type::I32, &(0..1));
+        assert_eq!(result.unwrap(), Type::I32);
+
+        // i8 * i32 -> i32, i32 - int -> int
+        let result = registry.lookup(&BinaryOp::Mul, &Type::I8, &Type::I32, &(0..1));
+        assert_eq!(result.unwrap(), Type::I32);
+        let result = registry.lookup(&BinaryOp::Sub, &Type::I32, &Type::Int, &(0..1));
+        assert_eq!(result.unwrap(), Type::Int);
+
+        let result = registry.lookup(&BinaryOp::Lt, &Type::I16, &Type::Int, &(0..1));
+        assert_eq!(result.unwrap(), Type::Bool);
+    }
+
+    #[test]
+    fn test_unsigned_does_not_mix_with_signed() {
+        let registry = BinaryOpRegistry::new();
+
+        let result = registry.lookup(&BinaryOp::Div, &Type::UInt, &Type::UInt, &(0..1));
+        assert_eq!(result.unwrap(), Type::UInt);
+        let result = registry.lookup(&BinaryOp::Lt, &Type::UInt, &Type::UInt, &(0..1));
+        assert_eq!(result.unwrap(), Type::Bool);
+
+        // uint 与有符号整数 / float 混合需要显式 as
+        assert!(registry
+            .lookup(&BinaryOp::Add, &Type::UInt, &Type::Int, &(0..1))
+            .is_err());
+        assert!(registry
+            .lookup(&BinaryOp::Lt, &Type::I32, &Type::UInt, &(0..1))
+            .is_err());
+        assert!(registry
+            .lookup(&BinaryOp::Mul, &Type::UInt, &Type::Float, &(0..1))
+            .is_err());
+    }
+
     #[test]
     fn test_arithmetic_float() {
         let registry = BinaryOpRegistry::new();
@@ -297,4 +486,43 @@ mod tests {
         let result = registry.lookup(&BinaryOp::Add, &Type::Bool, &Type::Bool, &(0..1));
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_enum_equality() {
+        let registry = BinaryOpRegistry::new();
+        let color = Type::Struct("Color".to_string());
+
+        // 枚举只支持 == / !=
+        for op in [BinaryOp::Eq, BinaryOp::Neq] {
+            let result = registry.lookup_enum(&op, &color, &color, &(0..1));
+            assert_eq!(result.unwrap(), Type::Bool);
+        }
+
+        // 枚举不可排序
+        let result = registry.lookup_enum(&BinaryOp::Lt, &color, &color, &(0..1));
+        assert!(result.is_err());
+
+        // 不同枚举类型之间不能比较
+        let shape = Type::Struct("Shape".to_string());
+        let result = registry.lookup_enum(&BinaryOp::Eq, &color, &shape, &(0..1));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_struct_equality() {
+        let registry = BinaryOpRegistry::new();
+        let point = Type::Struct("Point".to_string());
+
+        for op in [BinaryOp::Eq, BinaryOp::Neq] {
+            let result = registry.lookup_struct(&op, &point, &point, &(0..1));
+            assert_eq!(result.unwrap(), Type::Bool);
+        }
+
+        let result = registry.lookup_struct(&BinaryOp::Gt, &point, &point, &(0..1));
+        assert!(result.is_err());
+
+        let line = Type::Struct("Line".to_string());
+        let result = registry.lookup_struct(&BinaryOp::Eq, &point, &line, &(0..1));
+        assert!(result.is_err());
+    }
 }
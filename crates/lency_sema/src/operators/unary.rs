@@ -37,8 +37,10 @@ impl UnaryOpRegistry {
         use Type::*;
         use UnaryOp::*;
 
-        // 负号：-int -> int, -float -> float
-        self.add(Neg, Int, Int);
+        // 负号：-int -> int（各宽度整数保持原类型）, -float -> float
+        for int in [I8, I16, I32, Int] {
+            self.add(Neg, int.clone(), int);
+        }
         self.add(Neg, Float, Float);
 
         // 逻辑非：!bool -> bool
@@ -16,13 +16,15 @@ pub fn register_builtins(scopes: &mut ScopeStack) {
             .into_iter()
             .map(|(n, t)| (n.to_string(), t))
             .collect();
-        let sym = Symbol::Function(crate::symbol::FunctionSymbol::new(
+        let mut func = crate::symbol::FunctionSymbol::new(
             name.to_string(),
             param_vec,
             return_type,
             dummy_span.clone(),
-        ));
-        scopes.define(sym).ok();
+        );
+        // 内置函数在所有模块中可见
+        func.is_public = true;
+        scopes.define(Symbol::Function(func)).ok();
     };
 
     // HashMap FFI functions
@@ -52,8 +54,38 @@ pub fn register_builtins(scopes: &mut ScopeStack) {
     // Type conversion FFI functions
     define_extern_fn("int_to_string", vec![("n", Type::Int)], Type::String);
     define_extern_fn("float_to_string", vec![("f", Type::Float)], Type::String);
-    define_extern_fn("parse_int", vec![("s", Type::String)], Type::Int);
-    define_extern_fn("parse_float", vec![("s", Type::String)], Type::Float);
+    // parse_int/parse_float 返回 int!/float!，解析失败时为 Err(Error)
+    let parse_result = |ok_type: Type| Type::Result {
+        ok_type: Box::new(ok_type),
+        err_type: Box::new(Type::Struct("Error".to_string())),
+    };
+    define_extern_fn(
+        "parse_int",
+        vec![("s", Type::String)],
+        parse_result(Type::Int),
+    );
+    define_extern_fn(
+        "parse_float",
+        vec![("s", Type::String)],
+        parse_result(Type::Float),
+    );
+
+    // String FFI functions
+    define_extern_fn("byte_len", vec![("s", Type::String)], Type::Int);
+    define_extern_fn(
+        "string_bytes",
+        vec![("s", Type::String)],
+        Type::Vec(Box::new(Type::Int)),
+    );
+    define_extern_fn(
+        "bytes_to_string",
+        vec![
+            ("bytes", Type::Vec(Box::new(Type::Int))),
+            ("start", Type::Int),
+            ("len", Type::Int),
+        ],
+        Type::String,
+    );
 
     // File system FFI functions
     define_extern_fn("file_exists", vec![("path", Type::String)], Type::Bool);
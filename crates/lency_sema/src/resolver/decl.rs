@@ -4,28 +4,99 @@ use crate::symbol::{
     FunctionSymbol, GenericParamSymbol, StructSymbol, TraitMethodSignature, TraitSymbol,
 };
 use crate::{SemanticError, Symbol};
-use lency_syntax::ast::Decl;
+use lency_syntax::ast::{Decl, Expr, ExprKind, Literal, Type, UnaryOp};
+
+/// 收集一组顶层声明（Pass 1）
+///
+/// 常量与 newtype 先于其他声明收集，使结构体字段等处的数组长度可以引用后面定义的常量、
+/// 函数签名可以使用后面定义的 newtype
+pub fn collect_decls(resolver: &mut Resolver, decls: &[Decl]) -> Vec<Decl> {
+    let (consts, others): (Vec<&Decl>, Vec<&Decl>) = decls
+        .iter()
+        .partition(|decl| matches!(decl, Decl::Const { .. } | Decl::Newtype { .. }));
+
+    let mut synthetics = Vec::new();
+    for decl in consts.into_iter().chain(others) {
+        synthetics.append(&mut collect_decl(resolver, decl));
+    }
+    synthetics
+}
 
-/// 收集顶层声明（Pass 1）
 /// 收集顶层声明（Pass 1）
 pub fn collect_decl(resolver: &mut Resolver, decl: &Decl) -> Vec<Decl> {
     match decl {
-        Decl::Import { items, span } => {
+        Decl::Import {
+            items,
+            span,
+            is_public,
+        } => {
             let mut all_new_decls = Vec::new();
             for item in items {
+                if *is_public {
+                    if let Some(module) = resolver.current_module.clone() {
+                        let target = super::imports::module_file(resolver, &item.path);
+                        resolver.reexports.entry(module).or_default().push(target);
+                    }
+                }
                 let mut decls = if let Some(alias_name) = &item.alias {
                     resolver.resolve_import_as(&item.path, alias_name, span)
                 } else {
-                    resolver.resolve_import(&item.path, span)
+                    resolver.resolve_import(&item.path, item.names.as_deref(), span)
                 };
                 all_new_decls.append(&mut decls);
             }
             all_new_decls
         }
-        Decl::Var { span, name, ty, .. } => {
+        Decl::Const {
+            span,
+            is_public,
+            name,
+            ty,
+            value,
+            ..
+        } => {
+            let mut ty = ty
+                .clone()
+                .or_else(|| const_initializer_type(value))
+                .unwrap_or(lency_syntax::ast::Type::Error);
+            resolver.normalize_type(&mut ty);
+            if let Some(v) = const_int_value(resolver, value) {
+                resolver.const_values.insert(name.clone(), v);
+            }
+            let mut sym = crate::symbol::VariableSymbol::new(name.clone(), ty, false, span.clone());
+            sym.is_public = *is_public;
+            if let Err(e) = resolver.scopes.define(crate::symbol::Symbol::Variable(sym)) {
+                resolver.errors.push(e);
+            }
+            Vec::new()
+        }
+        Decl::Newtype {
+            span,
+            is_public,
+            name,
+            ty,
+            ..
+        } => {
+            let mut inner = ty.clone();
+            resolver.normalize_type(&mut inner);
+            let mut sym = crate::symbol::NewtypeSymbol::new(name.clone(), inner, span.clone());
+            sym.is_public = *is_public;
+            if let Err(e) = resolver.scopes.define(Symbol::Newtype(sym)) {
+                resolver.errors.push(e);
+            }
+            Vec::new()
+        }
+        Decl::Var {
+            span,
+            is_public,
+            name,
+            ty,
+            ..
+        } => {
             let mut ty = ty.clone().unwrap_or(lency_syntax::ast::Type::Void);
             resolver.normalize_type(&mut ty);
-            let sym = crate::symbol::VariableSymbol::new(name.clone(), ty, true, span.clone());
+            let mut sym = crate::symbol::VariableSymbol::new(name.clone(), ty, true, span.clone());
+            sym.is_public = *is_public;
             if let Err(e) = resolver.scopes.define(crate::symbol::Symbol::Variable(sym)) {
                 resolver.errors.push(e);
             }
@@ -33,6 +104,7 @@ pub fn collect_decl(resolver: &mut Resolver, decl: &Decl) -> Vec<Decl> {
         }
         Decl::Function {
             name,
+            is_public,
             generic_params,
             params,
             return_type,
@@ -41,7 +113,7 @@ pub fn collect_decl(resolver: &mut Resolver, decl: &Decl) -> Vec<Decl> {
         } => {
             let generic_param_symbols: Vec<GenericParamSymbol> = generic_params
                 .iter()
-                .map(|p| GenericParamSymbol::new(p.name.clone(), p.bound.clone(), p.span.clone()))
+                .map(|p| GenericParamSymbol::new(p.name.clone(), p.bounds.clone(), p.span.clone()))
                 .collect();
 
             let normalized_params: Vec<_> = params
@@ -57,13 +129,14 @@ pub fn collect_decl(resolver: &mut Resolver, decl: &Decl) -> Vec<Decl> {
             resolver
                 .normalize_type_with_generics(&mut normalized_return_type, &generic_param_symbols);
 
-            let func_symbol = FunctionSymbol::new_generic(
+            let mut func_symbol = FunctionSymbol::new_generic(
                 name.clone(),
                 generic_param_symbols.clone(),
                 normalized_params,
                 normalized_return_type,
                 span.clone(),
             );
+            func_symbol.is_public = *is_public;
 
             if let Err(e) = resolver.scopes.define(Symbol::Function(func_symbol)) {
                 resolver.errors.push(e);
@@ -75,6 +148,7 @@ pub fn collect_decl(resolver: &mut Resolver, decl: &Decl) -> Vec<Decl> {
             name,
             generic_params,
             params,
+            is_variadic,
             return_type,
             span,
             ..
@@ -83,7 +157,7 @@ pub fn collect_decl(resolver: &mut Resolver, decl: &Decl) -> Vec<Decl> {
             for param in generic_params {
                 gps.push(GenericParamSymbol::new(
                     param.name.clone(),
-                    param.bound.clone(),
+                    param.bounds.clone(),
                     param.span.clone(),
                 ));
             }
@@ -102,13 +176,16 @@ pub fn collect_decl(resolver: &mut Resolver, decl: &Decl) -> Vec<Decl> {
             resolver
                 .normalize_type_with_generics(&mut normalized_return_type, &generic_param_symbols);
 
-            let func_symbol = FunctionSymbol::new_generic(
+            let mut func_symbol = FunctionSymbol::new_generic(
                 name.clone(),
                 generic_param_symbols.clone(),
                 normalized_params,
                 normalized_return_type,
                 span.clone(),
             );
+            // extern 函数链接到全局运行时符号，始终可见
+            func_symbol.is_public = true;
+            func_symbol.is_variadic = *is_variadic;
 
             if let Err(e) = resolver.scopes.define(Symbol::Function(func_symbol)) {
                 resolver.errors.push(e);
@@ -120,13 +197,15 @@ pub fn collect_decl(resolver: &mut Resolver, decl: &Decl) -> Vec<Decl> {
             generic_params,
             fields,
             span,
+            is_extern,
+            is_public,
             ..
         } => {
             let mut gps = Vec::new();
             for param in generic_params {
                 gps.push(GenericParamSymbol::new(
                     param.name.clone(),
-                    param.bound.clone(),
+                    param.bounds.clone(),
                     param.span.clone(),
                 ));
             }
@@ -137,11 +216,18 @@ pub fn collect_decl(resolver: &mut Resolver, decl: &Decl) -> Vec<Decl> {
                 generic_param_symbols.clone(),
                 span.clone(),
             );
+            struct_symbol.is_extern = *is_extern;
+            struct_symbol.is_public = *is_public;
 
             for field in fields {
                 let mut field_ty = field.ty.clone();
                 resolver.normalize_type_with_generics(&mut field_ty, &generic_param_symbols);
-                struct_symbol.add_field(field.name.clone(), field_ty, span.clone());
+                struct_symbol.add_field(
+                    field.name.clone(),
+                    field_ty,
+                    field.default.clone(),
+                    span.clone(),
+                );
             }
 
             if let Err(e) = resolver.scopes.define(Symbol::Struct(struct_symbol)) {
@@ -152,15 +238,17 @@ pub fn collect_decl(resolver: &mut Resolver, decl: &Decl) -> Vec<Decl> {
         Decl::Impl { .. } => Vec::new(),
         Decl::Trait {
             name,
+            is_public,
             generic_params,
             methods,
             span,
+            ..
         } => {
             let mut gps = Vec::new();
             for param in generic_params {
                 gps.push(GenericParamSymbol::new(
                     param.name.clone(),
-                    param.bound.clone(),
+                    param.bounds.clone(),
                     param.span.clone(),
                 ));
             }
@@ -173,7 +261,7 @@ pub fn collect_decl(resolver: &mut Resolver, decl: &Decl) -> Vec<Decl> {
             };
 
             for method in methods {
-                let method_sig = TraitMethodSignature::new(
+                let mut method_sig = TraitMethodSignature::new(
                     method.name.clone(),
                     method
                         .params
@@ -182,8 +270,12 @@ pub fn collect_decl(resolver: &mut Resolver, decl: &Decl) -> Vec<Decl> {
                         .collect(),
                     method.return_type.clone(),
                 );
+                if let Some(body) = &method.default_body {
+                    method_sig = method_sig.with_default_body(body.clone(), method.span.clone());
+                }
                 trait_symbol.add_method(method_sig);
             }
+            trait_symbol.is_public = *is_public;
 
             if let Err(e) = resolver.scopes.define(Symbol::Trait(trait_symbol)) {
                 resolver.errors.push(e);
@@ -192,15 +284,17 @@ pub fn collect_decl(resolver: &mut Resolver, decl: &Decl) -> Vec<Decl> {
         }
         Decl::Enum {
             name,
+            is_public,
             generic_params,
             variants,
             span,
+            ..
         } => {
             let mut gps = Vec::new();
             for param in generic_params {
                 gps.push(GenericParamSymbol::new(
                     param.name.clone(),
-                    param.bound.clone(),
+                    param.bounds.clone(),
                     param.span.clone(),
                 ));
             }
@@ -218,7 +312,7 @@ pub fn collect_decl(resolver: &mut Resolver, decl: &Decl) -> Vec<Decl> {
 
             for variant in variants {
                 match variant {
-                    lency_syntax::ast::EnumVariant::Unit(n) => {
+                    lency_syntax::ast::EnumVariant::Unit(n, _) => {
                         enum_symbol.add_variant(n.clone(), vec![])
                     }
                     lency_syntax::ast::EnumVariant::Tuple(n, types) => {
@@ -227,6 +321,21 @@ pub fn collect_decl(resolver: &mut Resolver, decl: &Decl) -> Vec<Decl> {
                 }
             }
 
+            // 判别值必须互不相同（包括隐式递增得到的值）
+            let discriminants = lency_syntax::ast::EnumVariant::discriminants(variants);
+            let mut seen = std::collections::HashSet::new();
+            for (variant, value) in variants.iter().zip(discriminants) {
+                if !seen.insert(value) {
+                    resolver.errors.push(SemanticError::DuplicateDiscriminant {
+                        enum_name: name.clone(),
+                        variant: variant.name().to_string(),
+                        value,
+                        span: span.clone(),
+                    });
+                }
+            }
+
+            enum_symbol.is_public = *is_public;
             if let Err(e) = resolver.scopes.define(Symbol::Enum(enum_symbol)) {
                 resolver.errors.push(e);
             }
@@ -270,11 +379,12 @@ pub fn collect_impl_methods(resolver: &mut Resolver, decl: &Decl) {
         // Collect generic param symbols from Impl block
         let generic_param_symbols: Vec<GenericParamSymbol> = generic_params
             .iter()
-            .map(|p| GenericParamSymbol::new(p.name.clone(), p.bound.clone(), p.span.clone()))
+            .map(|p| GenericParamSymbol::new(p.name.clone(), p.bounds.clone(), p.span.clone()))
             .collect();
 
         // 1. Pre-process methods (Normalize types using resolver)
         // This requires mutable access to resolver, but NOT to struct_sym (scopes)
+        let outer_self_type = resolver.self_type.replace(type_name.clone());
         let mut methods_to_add = Vec::new();
         for method in methods {
             if let Decl::Function {
@@ -309,6 +419,7 @@ pub fn collect_impl_methods(resolver: &mut Resolver, decl: &Decl) {
                 methods_to_add.push((name.clone(), func_symbol));
             }
         }
+        resolver.self_type = outer_self_type;
 
         // 2. Add to Struct Symbol (Requires mutable access to scopes)
         // Now we can borrow resolver.scopes mutably without conflict
@@ -330,13 +441,28 @@ pub fn collect_impl_methods(resolver: &mut Resolver, decl: &Decl) {
 pub fn resolve_decl(resolver: &mut Resolver, decl: &mut Decl) {
     match decl {
         Decl::Function { .. } => super::decl_impl::resolve_function(resolver, decl),
-        Decl::ExternFunction { .. } => {
-            // No body to resolve
-        }
+        Decl::ExternFunction { .. } => super::decl_impl::resolve_extern_function(resolver, decl),
         Decl::Struct { .. } => super::decl_impl::resolve_struct(resolver, decl),
         Decl::Impl { .. } => super::decl_impl::resolve_impl(resolver, decl),
         Decl::Trait { .. } => super::decl_impl::resolve_trait(resolver, decl),
         Decl::Enum { .. } => super::decl_impl::resolve_enum(resolver, decl),
+        Decl::Const {
+            ty, value, span, ..
+        } => {
+            resolver.resolve_expr(value);
+            match ty {
+                Some(t) => {
+                    resolver.normalize_type(t);
+                    resolver.resolve_type(t, span);
+                }
+                // 未标注类型时按字面量补全，后续阶段（代码生成）直接使用
+                None => *ty = const_initializer_type(value),
+            }
+        }
+        Decl::Newtype { ty, span, .. } => {
+            resolver.normalize_type(ty);
+            resolver.resolve_type(ty, span);
+        }
         Decl::Var { value, .. } => {
             // Resolve initialization expression
             resolver.resolve_expr(value);
@@ -344,3 +470,51 @@ pub fn resolve_decl(resolver: &mut Resolver, decl: &mut Decl) {
         Decl::Import { .. } => {} // Noop for now
     }
 }
+
+/// 常量初始化器（字面量，可带负号）对应的类型
+pub(crate) fn const_literal_type(value: &Expr) -> Option<Type> {
+    match &value.kind {
+        ExprKind::Literal(Literal::Int(_)) => Some(Type::Int),
+        ExprKind::Literal(Literal::Float(_)) => Some(Type::Float),
+        ExprKind::Literal(Literal::Bool(_)) => Some(Type::Bool),
+        ExprKind::Unary(UnaryOp::Neg, inner) => match const_literal_type(inner)? {
+            Type::Bool => None,
+            ty => Some(ty),
+        },
+        _ => None,
+    }
+}
+
+/// 常量初始化器的类型：字面量之外还允许由常量组成的数组与结构体字面量（查找表）
+pub(crate) fn const_initializer_type(value: &Expr) -> Option<Type> {
+    match &value.kind {
+        ExprKind::Array(elements) => Some(Type::Array {
+            element_type: Box::new(const_initializer_type(elements.first()?)?),
+            size: elements.len(),
+        }),
+        ExprKind::StructLiteral { type_, .. } => Some(type_.clone()),
+        _ => const_literal_type(value),
+    }
+}
+
+/// 常量初始化器中第一个无法在编译期求值的子表达式
+pub(crate) fn non_const_part(value: &Expr) -> Option<&Expr> {
+    match &value.kind {
+        ExprKind::Array(elements) => elements.iter().find_map(non_const_part),
+        ExprKind::StructLiteral { fields, .. } => {
+            fields.iter().find_map(|(_, field)| non_const_part(field))
+        }
+        _ if const_literal_type(value).is_some() => None,
+        _ => Some(value),
+    }
+}
+
+/// 计算整数常量的值：整数字面量、取负或引用已收集的常量
+fn const_int_value(resolver: &Resolver, value: &Expr) -> Option<i64> {
+    match &value.kind {
+        ExprKind::Literal(Literal::Int(n)) => Some(*n),
+        ExprKind::Unary(UnaryOp::Neg, inner) => const_int_value(resolver, inner)?.checked_neg(),
+        ExprKind::Variable(name) => resolver.const_values.get(name).copied(),
+        _ => None,
+    }
+}
@@ -0,0 +1,92 @@
+//! C Interop Resolution
+//!
+//! extern struct 与 extern "C" 声明的检查：只有 C 可表示的类型才能按值跨越 C ABI
+
+use crate::error::SemanticError;
+use crate::resolver::Resolver;
+use crate::symbol::Symbol;
+use lency_syntax::ast::{Decl, Type};
+
+/// 检查 extern 函数声明：调用约定只支持 "C"，按值传递的结构体必须是 extern struct
+pub fn resolve_extern_function(resolver: &mut Resolver, decl: &mut Decl) {
+    if let Decl::ExternFunction {
+        abi: Some(abi),
+        params,
+        return_type,
+        span,
+        ..
+    } = decl
+    {
+        if abi != "C" {
+            resolver.errors.push(SemanticError::UnsupportedAbi {
+                abi: abi.clone(),
+                span: span.clone(),
+            });
+            return;
+        }
+
+        let types: Vec<Type> = params
+            .iter()
+            .map(|p| p.ty.clone())
+            .chain(std::iter::once(return_type.clone()))
+            .collect();
+        for mut ty in types {
+            resolver.normalize_type(&mut ty);
+            if let Type::Struct(name) = &ty {
+                let is_extern = matches!(
+                    resolver.scopes.lookup(name),
+                    Some(Symbol::Struct(s)) if s.is_extern
+                );
+                if !is_extern {
+                    resolver.errors.push(SemanticError::NotFfiSafe {
+                        ty: ty.to_string(),
+                        span: span.clone(),
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// extern struct 的字段在 C 侧按值内联，只允许标量（整数、浮点、bool）和 string（char*）
+pub fn check_extern_fields(resolver: &mut Resolver, decl: &Decl) {
+    if let Decl::Struct {
+        name,
+        generic_params,
+        fields,
+        span,
+        is_extern: true,
+        ..
+    } = decl
+    {
+        if !generic_params.is_empty() {
+            resolver.errors.push(SemanticError::NotFfiSafe {
+                ty: name.clone(),
+                span: span.clone(),
+            });
+        }
+        for field in fields {
+            if !is_c_scalar(&field.ty) {
+                resolver.errors.push(SemanticError::NotFfiSafe {
+                    ty: field.ty.to_string(),
+                    span: span.clone(),
+                });
+            }
+        }
+    }
+}
+
+fn is_c_scalar(ty: &Type) -> bool {
+    match ty {
+        Type::Int
+        | Type::I8
+        | Type::I16
+        | Type::I32
+        | Type::UInt
+        | Type::Float
+        | Type::Bool
+        | Type::String => true,
+        Type::Newtype(_, inner) => is_c_scalar(inner),
+        _ => false,
+    }
+}
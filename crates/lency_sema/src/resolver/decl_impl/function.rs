@@ -17,10 +17,11 @@ pub fn resolve_function(resolver: &mut Resolver, decl: &mut Decl) {
         resolver.scopes.enter_scope(ScopeKind::Function);
 
         let mut gps = Vec::new();
-        for param in generic_params {
+        for param in generic_params.iter() {
+            resolver.resolve_bounds(param);
             gps.push(GenericParamSymbol::new(
                 param.name.clone(),
-                param.bound.clone(),
+                param.bounds.clone(),
                 param.span.clone(),
             ));
         }
@@ -43,6 +44,8 @@ pub fn resolve_function(resolver: &mut Resolver, decl: &mut Decl) {
         resolver.normalize_type(return_type);
         resolver.resolve_type(return_type, span);
 
+        resolver.block_locals.clear();
+        crate::resolver::stmt::collect_block_locals(body, &mut resolver.block_locals);
         for stmt in body {
             resolver.resolve_stmt(stmt);
         }
@@ -1,6 +1,9 @@
+use crate::resolver::types::substitute_self_type;
 use crate::resolver::Resolver;
 use crate::scope::ScopeKind;
-use crate::symbol::{FunctionSymbol, GenericParamSymbol, ParameterSymbol, Symbol};
+use crate::symbol::{
+    FunctionSymbol, GenericParamSymbol, ParameterSymbol, Symbol, TraitMethodSignature, TraitSymbol,
+};
 use crate::SemanticError;
 use lency_syntax::ast::{Decl, Type};
 
@@ -49,6 +52,22 @@ pub fn resolve_impl(resolver: &mut Resolver, decl: &mut Decl) {
             return;
         }
 
+        // 方法签名中的 This 替换为目标类型，方法体中的 This 在规范化时替换
+        for method in methods.iter_mut() {
+            if let Decl::Function {
+                params,
+                return_type,
+                ..
+            } = method
+            {
+                for param in params.iter_mut() {
+                    substitute_self_type(&mut param.ty, type_name);
+                }
+                substitute_self_type(return_type, type_name);
+            }
+        }
+        let outer_self_type = resolver.self_type.replace(type_name.clone());
+
         if let Some(trait_ty) = trait_ref {
             let trait_name_str = match trait_ty {
                 Type::Struct(name) => name.clone(),
@@ -69,8 +88,25 @@ pub fn resolve_impl(resolver: &mut Resolver, decl: &mut Decl) {
                         })
                         .collect();
 
+                    // 未实现的方法使用 Trait 的默认实现，没有默认实现才报错
+                    // 默认方法体位于 Trait 所在的文件
+                    let trait_file = resolver
+                        .scopes
+                        .symbol_origin(trait_id)
+                        .map(|path| crate::resolver::imports::source_file_name(resolver, path));
+                    let mut inherited = Vec::new();
                     for trait_method in &trait_sym.methods {
-                        if !impl_method_names.contains(&trait_method.name.as_str()) {
+                        if impl_method_names.contains(&trait_method.name.as_str()) {
+                            continue;
+                        }
+                        if let Some(mut default) =
+                            inherit_default(trait_sym, trait_ty, trait_method)
+                        {
+                            if let Decl::Function { source_file, .. } = &mut default {
+                                source_file.clone_from(&trait_file);
+                            }
+                            inherited.push(default);
+                        } else {
                             resolver.errors.push(SemanticError::MissingTraitMethod {
                                 trait_name: trait_name_str.clone(),
                                 method_name: trait_method.name.clone(),
@@ -78,6 +114,7 @@ pub fn resolve_impl(resolver: &mut Resolver, decl: &mut Decl) {
                             });
                         }
                     }
+                    methods.extend(inherited);
 
                     for method in methods.iter() {
                         if let Decl::Function {
@@ -227,6 +264,12 @@ pub fn resolve_impl(resolver: &mut Resolver, decl: &mut Decl) {
                             }
                         }
                     }
+
+                    if let Some(Symbol::Struct(struct_sym)) =
+                        resolver.scopes.get_symbol_mut(struct_id)
+                    {
+                        struct_sym.add_trait(trait_name_str.clone());
+                    }
                 }
             } else {
                 resolver.errors.push(SemanticError::UndefinedTrait {
@@ -239,12 +282,13 @@ pub fn resolve_impl(resolver: &mut Resolver, decl: &mut Decl) {
             // Coerce AST Generic Params to Symbols for normalization
             let generic_param_symbols: Vec<GenericParamSymbol> = generic_params
                 .iter()
-                .map(|p| GenericParamSymbol::new(p.name.clone(), p.bound.clone(), p.span.clone()))
+                .map(|p| GenericParamSymbol::new(p.name.clone(), p.bounds.clone(), p.span.clone()))
                 .collect();
 
             for method in methods.iter() {
                 if let Decl::Function {
                     name: method_name,
+                    is_public,
                     params,
                     return_type,
                     span: method_span,
@@ -267,12 +311,14 @@ pub fn resolve_impl(resolver: &mut Resolver, decl: &mut Decl) {
                         &generic_param_symbols,
                     );
 
-                    let func_sym = FunctionSymbol::new(
+                    let mut func_sym = FunctionSymbol::new(
                         method_name.clone(),
                         normalized_params,
                         normalized_return_type,
                         method_span.clone(),
                     );
+                    // `import x as m` 把模块函数包装为方法，保留其可见性
+                    func_sym.is_public = *is_public;
 
                     // Sprint 15: Support adding methods to both Struct and Enum
                     match resolver.scopes.get_symbol_mut(struct_id) {
@@ -290,6 +336,7 @@ pub fn resolve_impl(resolver: &mut Resolver, decl: &mut Decl) {
 
         for method in methods {
             if let Decl::Function {
+                name: method_name,
                 params,
                 return_type,
                 body,
@@ -300,11 +347,12 @@ pub fn resolve_impl(resolver: &mut Resolver, decl: &mut Decl) {
                 resolver.scopes.enter_scope(ScopeKind::Function);
 
                 for gp in generic_params.iter() {
-                    if let Some(bound) = &gp.bound {
-                        resolver.resolve_type(bound, &gp.span);
-                    }
-                    let gp_symbol =
-                        GenericParamSymbol::new(gp.name.clone(), gp.bound.clone(), gp.span.clone());
+                    resolver.resolve_bounds(gp);
+                    let gp_symbol = GenericParamSymbol::new(
+                        gp.name.clone(),
+                        gp.bounds.clone(),
+                        gp.span.clone(),
+                    );
                     if let Err(e) = resolver.scopes.define(Symbol::GenericParam(gp_symbol)) {
                         resolver.errors.push(e);
                     }
@@ -342,12 +390,70 @@ pub fn resolve_impl(resolver: &mut Resolver, decl: &mut Decl) {
                 resolver.normalize_type(return_type);
                 resolver.resolve_type(return_type, span);
 
+                resolver.this_referenced = false;
+                resolver.block_locals.clear();
+                crate::resolver::stmt::collect_block_locals(body, &mut resolver.block_locals);
                 for stmt in body {
                     resolver.resolve_stmt(stmt);
                 }
+                // 未引用 this 的方法可通过 Type::method 调用
+                let is_static = !resolver.this_referenced;
+                match resolver.scopes.get_symbol_mut(struct_id) {
+                    Some(Symbol::Struct(struct_sym)) => {
+                        if let Some(func) = struct_sym.methods.get_mut(method_name.as_str()) {
+                            func.is_static = is_static;
+                        }
+                    }
+                    Some(Symbol::Enum(enum_sym)) => {
+                        if let Some(func) = enum_sym.methods.get_mut(method_name.as_str()) {
+                            func.is_static = is_static;
+                        }
+                    }
+                    _ => {}
+                }
 
                 resolver.scopes.exit_scope();
             }
         }
+
+        resolver.self_type = outer_self_type;
+    }
+}
+
+/// 为实现者生成 Trait 默认方法，泛型 Trait 的类型参数替换为 impl 中给出的实参
+fn inherit_default(
+    trait_sym: &TraitSymbol,
+    trait_ty: &Type,
+    trait_method: &TraitMethodSignature,
+) -> Option<Decl> {
+    let mut method = trait_method.default_method()?;
+    let Type::Generic(_, args) = trait_ty else {
+        return Some(method);
+    };
+    let substitute = |ty: &mut Type| {
+        let name = match ty {
+            Type::Struct(name) => name,
+            Type::Generic(name, type_args) if type_args.is_empty() => name,
+            _ => return,
+        };
+        let position = trait_sym
+            .generic_params
+            .iter()
+            .position(|gp| &gp.name == name);
+        if let Some(arg) = position.and_then(|i| args.get(i)) {
+            *ty = arg.clone();
+        }
+    };
+    if let Decl::Function {
+        params,
+        return_type,
+        ..
+    } = &mut method
+    {
+        for param in params.iter_mut() {
+            substitute(&mut param.ty);
+        }
+        substitute(return_type);
     }
+    Some(method)
 }
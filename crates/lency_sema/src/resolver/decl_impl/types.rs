@@ -1,11 +1,14 @@
+use crate::error::SemanticError;
+use crate::resolver::decl::const_literal_type;
 use crate::resolver::Resolver;
 use crate::scope::ScopeKind;
 use crate::symbol::{GenericParamSymbol, Symbol};
-use lency_syntax::ast::Decl;
+use crate::type_infer::{is_compatible, narrow_int_literal};
+use lency_syntax::ast::{Decl, Expr, ExprKind, Field, Literal, Type};
 
 pub fn resolve_struct(resolver: &mut Resolver, decl: &mut Decl) {
     if let Decl::Struct {
-        name: _,
+        name,
         generic_params,
         fields,
         span,
@@ -16,8 +19,9 @@ pub fn resolve_struct(resolver: &mut Resolver, decl: &mut Decl) {
         if has_generics {
             resolver.scopes.enter_scope(ScopeKind::Block);
             for gp in generic_params {
+                resolver.resolve_bounds(gp);
                 let gp_symbol =
-                    GenericParamSymbol::new(gp.name.clone(), gp.bound.clone(), gp.span.clone());
+                    GenericParamSymbol::new(gp.name.clone(), gp.bounds.clone(), gp.span.clone());
                 if let Err(e) = resolver.scopes.define(Symbol::GenericParam(gp_symbol)) {
                     resolver.errors.push(e);
                 }
@@ -27,12 +31,55 @@ pub fn resolve_struct(resolver: &mut Resolver, decl: &mut Decl) {
         for field in fields {
             resolver.normalize_type(&mut field.ty);
             resolver.resolve_type(&field.ty, span);
+            check_field_default(resolver, name, field);
         }
 
         if has_generics {
             resolver.scopes.exit_scope();
         }
     }
+    super::ffi::check_extern_fields(resolver, decl);
+}
+
+/// 字段默认值必须是与字段类型兼容的字面量
+///
+/// 默认值会被复制到每个省略该字段的结构体字面量中，限制为字面量可保证它不依赖作用域
+fn check_field_default(resolver: &mut Resolver, struct_name: &str, field: &Field) {
+    let Some(default) = &field.default else {
+        return;
+    };
+    let mut default = default.clone();
+    narrow_int_literal(&field.ty, &mut default);
+    let Some(default_ty) = field_default_type(&default) else {
+        resolver.errors.push(SemanticError::FieldDefaultNotLiteral {
+            name: struct_name.to_string(),
+            field: field.name.clone(),
+            span: default.span.clone(),
+        });
+        return;
+    };
+    if !is_compatible(&field.ty, &default_ty) {
+        resolver.errors.push(SemanticError::TypeMismatch {
+            expected: field.ty.to_string(),
+            found: default_ty.to_string(),
+            span: default.span.clone(),
+        });
+    }
+}
+
+/// 字段默认值的类型：常量字面量之外还允许字符串和 null
+fn field_default_type(default: &Expr) -> Option<Type> {
+    match &default.kind {
+        ExprKind::Literal(Literal::String(_)) => Some(Type::String),
+        ExprKind::Literal(Literal::Null) => Some(Type::Nullable(Box::new(Type::Error))),
+        // 窄整数字段的字面量默认值（如 `i32 x = 0`）会被包装为 `0 as i32`
+        ExprKind::Cast { ty, value }
+            if ty.int_bits().is_some() && const_literal_type(value) == Some(Type::Int) =>
+        {
+            Some(ty.clone())
+        }
+        _ => const_literal_type(default),
+    }
 }
 
 pub fn resolve_trait(resolver: &mut Resolver, decl: &mut Decl) {
@@ -47,11 +94,9 @@ pub fn resolve_trait(resolver: &mut Resolver, decl: &mut Decl) {
         if has_generics {
             resolver.scopes.enter_scope(ScopeKind::Block);
             for gp in generic_params {
-                if let Some(bound) = &gp.bound {
-                    resolver.resolve_type(bound, &gp.span);
-                }
+                resolver.resolve_bounds(gp);
                 let gp_symbol =
-                    GenericParamSymbol::new(gp.name.clone(), gp.bound.clone(), gp.span.clone());
+                    GenericParamSymbol::new(gp.name.clone(), gp.bounds.clone(), gp.span.clone());
                 if let Err(e) = resolver.scopes.define(Symbol::GenericParam(gp_symbol)) {
                     resolver.errors.push(e);
                 }
@@ -85,11 +130,9 @@ pub fn resolve_enum(resolver: &mut Resolver, decl: &mut Decl) {
         if has_generics {
             resolver.scopes.enter_scope(ScopeKind::Block);
             for gp in generic_params {
-                if let Some(bound) = &gp.bound {
-                    resolver.resolve_type(bound, &gp.span);
-                }
+                resolver.resolve_bounds(gp);
                 let gp_symbol =
-                    GenericParamSymbol::new(gp.name.clone(), gp.bound.clone(), gp.span.clone());
+                    GenericParamSymbol::new(gp.name.clone(), gp.bounds.clone(), gp.span.clone());
                 if let Err(e) = resolver.scopes.define(Symbol::GenericParam(gp_symbol)) {
                     resolver.errors.push(e);
                 }
@@ -98,7 +141,7 @@ pub fn resolve_enum(resolver: &mut Resolver, decl: &mut Decl) {
 
         for variant in variants {
             match variant {
-                lency_syntax::ast::EnumVariant::Unit(_) => {}
+                lency_syntax::ast::EnumVariant::Unit(..) => {}
                 lency_syntax::ast::EnumVariant::Tuple(_, types) => {
                     for ty in types {
                         resolver.normalize_type(ty);
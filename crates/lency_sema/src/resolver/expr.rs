@@ -1,16 +1,62 @@
 use super::Resolver;
 use crate::error::SemanticError;
-use crate::scope::ScopeKind;
+use crate::scope::{Resolution, ScopeKind};
 use crate::symbol::{Symbol, VariableSymbol};
-use lency_syntax::ast::{Expr, ExprKind, MatchPattern, Type};
+use lency_syntax::ast::{Expr, ExprKind, Literal, MatchPattern, Type};
 
 pub fn resolve_expr(resolver: &mut Resolver, expr: &mut Expr) {
     match &mut expr.kind {
         ExprKind::Variable(name) => {
-            // 检查变量是否已定义
-            if resolver.scopes.lookup(name).is_none() {
-                resolver.errors.push(SemanticError::UndefinedVariable {
-                    name: name.clone(),
+            if name == "this" {
+                resolver.this_referenced = true;
+            }
+            // 检查变量是否已定义，并记录引用点供工具使用
+            match resolver.scopes.lookup_id(name) {
+                Some(id) if resolver.is_hidden_import(id) => {
+                    resolver.errors.push(SemanticError::UndefinedVariable {
+                        name: name.clone(),
+                        span: expr.span.clone(),
+                    })
+                }
+                Some(id) => {
+                    if !resolver.is_accessible(id) {
+                        resolver.errors.push(SemanticError::PrivateSymbol {
+                            name: name.clone(),
+                            span: expr.span.clone(),
+                        });
+                    }
+                    resolver
+                        .scopes
+                        .record_resolution(expr.span.clone(), Resolution::Symbol(id))
+                }
+                None => {
+                    // 同一块中稍后才声明的局部变量：按词法顺序不可见
+                    let later = resolver.block_locals.iter().find(|(local, decl, start)| {
+                        local == name && *start <= expr.span.start && expr.span.start < decl.start
+                    });
+                    resolver.errors.push(match later {
+                        Some((_, decl, _)) => SemanticError::UseBeforeDefinition {
+                            name: name.clone(),
+                            span: expr.span.clone(),
+                            defined_at: decl.clone(),
+                        },
+                        None => SemanticError::UndefinedVariable {
+                            name: name.clone(),
+                            span: expr.span.clone(),
+                        },
+                    })
+                }
+            }
+        }
+        ExprKind::Path { type_name, .. } => {
+            // 方法名的解析在类型检查阶段完成
+            resolver.check_accessible(type_name, &expr.span);
+            if !matches!(
+                resolver.scopes.lookup(type_name),
+                Some(Symbol::Struct(_) | Symbol::Enum(_))
+            ) {
+                resolver.errors.push(SemanticError::UndefinedType {
+                    name: type_name.clone(),
                     span: expr.span.clone(),
                 });
             }
@@ -47,6 +93,22 @@ pub fn resolve_expr(resolver: &mut Resolver, expr: &mut Expr) {
                 resolver.resolve_expr(elem);
             }
         }
+        ExprKind::Repeat {
+            value,
+            count,
+            is_vec,
+        } => {
+            resolver.resolve_expr(value);
+            resolver.resolve_expr(count);
+            // [v; SIZE] 的长度引用常量时替换为其值，后续阶段只需处理字面量
+            if !*is_vec {
+                if let ExprKind::Variable(name) = &count.kind {
+                    if let Some(size) = resolver.const_array_size(name) {
+                        count.kind = ExprKind::Literal(Literal::Int(size as i64));
+                    }
+                }
+            }
+        }
         ExprKind::GenericInstantiation { base, args } => {
             // Resolve the base expression (the function being called)
             resolver.resolve_expr(base);
@@ -68,6 +130,9 @@ pub fn resolve_expr(resolver: &mut Resolver, expr: &mut Expr) {
             for case in cases {
                 resolver.scopes.enter_scope(ScopeKind::Block);
                 declare_pattern_vars(resolver, &case.pattern);
+                if let Some(guard) = &mut case.guard {
+                    resolver.resolve_expr(guard);
+                }
                 resolver.resolve_expr(&mut case.body);
                 resolver.scopes.exit_scope();
             }
@@ -92,6 +157,11 @@ pub fn resolve_expr(resolver: &mut Resolver, expr: &mut Expr) {
                 resolver.resolve_expr(value);
             }
         }
+        ExprKind::RecordLiteral(fields) => {
+            for (_, value) in fields {
+                resolver.resolve_expr(value);
+            }
+        }
         // Result 相关表达式
         ExprKind::Try(inner) => resolver.resolve_expr(inner),
         ExprKind::Ok(inner) => resolver.resolve_expr(inner),
@@ -152,14 +222,28 @@ pub fn resolve_expr(resolver: &mut Resolver, expr: &mut Expr) {
         ExprKind::Panic(arg) => {
             resolver.resolve_expr(arg);
         }
+        ExprKind::Assert(cond, message) => {
+            resolver.resolve_expr(cond);
+            resolver.resolve_expr(message);
+        }
         ExprKind::Format(template, args) => {
             resolver.resolve_expr(template);
             resolver.resolve_expr(args);
         }
+        ExprKind::Fmt { args, .. } => {
+            for arg in args {
+                resolver.resolve_expr(arg);
+            }
+        }
+        ExprKind::Cast { ty, value } => {
+            resolver.resolve_expr(value);
+            resolver.normalize_type(ty);
+            resolver.resolve_type(ty, &expr.span);
+        }
     }
 }
 
-fn declare_pattern_vars(resolver: &mut Resolver, pattern: &MatchPattern) {
+pub(super) fn declare_pattern_vars(resolver: &mut Resolver, pattern: &MatchPattern) {
     match pattern {
         MatchPattern::Variable(name) => {
             let var_sym = VariableSymbol::new(
@@ -172,7 +256,7 @@ fn declare_pattern_vars(resolver: &mut Resolver, pattern: &MatchPattern) {
                 resolver.errors.push(e);
             }
         }
-        MatchPattern::Variant { sub_patterns, .. } => {
+        MatchPattern::Variant { sub_patterns, .. } | MatchPattern::Or(sub_patterns) => {
             for pat in sub_patterns {
                 declare_pattern_vars(resolver, pat);
             }
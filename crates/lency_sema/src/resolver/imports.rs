@@ -1,15 +1,13 @@
 use super::decl;
+use super::parse_cache;
 use super::Resolver;
 use crate::error::SemanticError;
+use crate::symbol::SymbolId;
 use lency_syntax::ast::{Decl, Expr, ExprKind, Span, Type};
+use std::path::{Path, PathBuf};
 
-/// 解析导入模块
-pub fn resolve_import(
-    resolver: &mut Resolver,
-    path_components: &[String],
-    span: &Span,
-) -> Vec<Decl> {
-    // 1. 构建路径
+/// 模块路径对应的源文件：std.* 位于 lib/std，其余相对项目根目录
+pub(crate) fn module_file(resolver: &Resolver, path_components: &[String]) -> PathBuf {
     let mut path_buf = resolver.root_dir.clone();
     if !path_components.is_empty() && path_components[0] == "std" {
         path_buf.push("lib");
@@ -23,56 +21,182 @@ pub fn resolve_import(
         }
     }
     path_buf.set_extension("lcy");
+    path_buf
+}
 
-    // 2. 检查环形依赖
-    if resolver.visited_modules.contains(&path_buf) {
-        return Vec::new();
+/// 源文件在 panic 消息中显示的路径：位于项目根目录下时取相对路径
+pub(crate) fn source_file_name(resolver: &Resolver, path: &Path) -> String {
+    path.strip_prefix(&resolver.root_dir)
+        .unwrap_or(path)
+        .display()
+        .to_string()
+}
+
+/// 为导入模块中的函数与方法记录源文件；已记录的（如来自其他模块的 Trait 默认方法）保持不变
+pub(crate) fn mark_source_file(decls: &mut [Decl], file: &str) {
+    for decl in decls {
+        match decl {
+            Decl::Function { source_file, .. } => {
+                source_file.get_or_insert_with(|| file.to_string());
+            }
+            Decl::Impl { methods, .. } => mark_source_file(methods, file),
+            _ => {}
+        }
     }
+}
 
-    // 3. 将当前模块加入栈
-    resolver.visited_modules.insert(path_buf.clone());
+/// 解析导入模块
+pub fn resolve_import(
+    resolver: &mut Resolver,
+    path_components: &[String],
+    names: Option<&[String]>,
+    span: &Span,
+) -> Vec<Decl> {
+    // 1. 构建路径
+    let path_buf = module_file(resolver, path_components);
 
-    // 4. 读取并解析文件
-    let source = match std::fs::read_to_string(&path_buf) {
-        Ok(s) => s,
-        Err(e) => {
-            let err = SemanticError::ImportError {
-                message: format!("failed to read file '{:?}': {}", path_buf, e),
+    // 2. 检查环形依赖：仍在加载栈中说明形成了环；已加载完毕的模块直接复用
+    if let Some(pos) = resolver.import_stack.iter().position(|p| *p == path_buf) {
+        let cycle: Vec<PathBuf> = resolver.import_stack[pos..]
+            .iter()
+            .chain(std::iter::once(&path_buf))
+            .cloned()
+            .collect();
+        if !is_split_module(&cycle) {
+            let cycle = cycle
+                .iter()
+                .map(|p| {
+                    p.strip_prefix(&resolver.root_dir)
+                        .unwrap_or(p)
+                        .to_path_buf()
+                })
+                .collect();
+            resolver.errors.push(SemanticError::CircularImport {
+                cycle,
                 span: span.clone(),
-            };
-            resolver.errors.push(err);
+            });
             return Vec::new();
         }
-    };
+    }
+    if resolver.visited_modules.contains(&path_buf) {
+        expose_imported(resolver, path_components, &path_buf, names, span);
+        return Vec::new();
+    }
+
+    // 3. 将当前模块加入栈
+    resolver.visited_modules.insert(path_buf.clone());
 
-    // 5. Parse
-    match lency_syntax::parser::parse(&source) {
+    // 4. 读取并解析文件（未改动的模块复用进程内缓存）
+    match parse_cache::load_module(&path_buf) {
         Ok(prog) => {
             // 6. 递归收集 (Pass 1)
-            let mut all_synthetics = Vec::new();
-            for decl in &prog.decls {
-                let mut synthetics = decl::collect_decl(resolver, decl);
-                all_synthetics.append(&mut synthetics);
+            let first_id = resolver.scopes.all_symbols().len();
+            let importer = resolver.current_module.replace(path_buf.clone());
+            resolver.import_stack.push(path_buf.clone());
+            let mut all_synthetics = decl::collect_decls(resolver, &prog.decls);
+            resolver.import_stack.pop();
+            resolver.current_module = importer;
+            let end_id = resolver.scopes.all_symbols().len();
+            resolver
+                .scopes
+                .mark_symbol_origin(first_id..end_id, &path_buf);
+            if names.is_some() {
+                // 选择性导入：先隐藏本模块定义的全部符号，再放开点名的符号
+                let own: Vec<_> = (first_id..end_id)
+                    .filter(|id| resolver.scopes.symbol_origin(*id) == Some(path_buf.as_path()))
+                    .collect();
+                resolver.hidden_imports.extend(own);
             }
+            expose_imported(resolver, path_components, &path_buf, names, span);
 
             // Store program
             let mut prog = prog;
             prog.decls.append(&mut all_synthetics);
-            resolver.loaded_programs.push(prog);
+            resolver.loaded_programs.push((path_buf, prog));
 
             Vec::new()
         }
-        Err(e) => {
-            let err = SemanticError::ImportError {
-                message: format!("parse error in '{:?}': {:?}", path_buf, e),
+        Err(message) => {
+            resolver.errors.push(SemanticError::ImportError {
+                message,
                 span: span.clone(),
-            };
-            resolver.errors.push(err);
+            });
             Vec::new()
         }
     }
 }
 
+/// 环上的文件是否都属于同一个拆分模块：X.lcy 与 X/ 目录下的子文件互相导入不算循环导入
+fn is_split_module(cycle: &[PathBuf]) -> bool {
+    cycle.iter().any(|root| {
+        let dir = root.with_extension("");
+        cycle.iter().all(|p| p == root || p.starts_with(&dir))
+    })
+}
+
+/// 模块导出符号的来源：模块自身及其 pub import 的模块（递归）
+fn exported_modules(resolver: &Resolver, path: &Path) -> Vec<PathBuf> {
+    let mut modules = vec![path.to_path_buf()];
+    let mut i = 0;
+    while i < modules.len() {
+        if let Some(targets) = resolver.reexports.get(&modules[i]) {
+            for target in targets {
+                if !modules.contains(target) {
+                    modules.push(target.clone());
+                }
+            }
+        }
+        i += 1;
+    }
+    modules
+}
+
+/// 放开模块中被导入的符号：names 为 None 时放开全部，否则只放开点名且 pub 的符号
+fn expose_imported(
+    resolver: &mut Resolver,
+    path_components: &[String],
+    path: &Path,
+    names: Option<&[String]>,
+    span: &Span,
+) {
+    let modules = exported_modules(resolver, path);
+    let own: Vec<SymbolId> = (0..resolver.scopes.all_symbols().len())
+        .filter(|id| {
+            resolver
+                .scopes
+                .symbol_origin(*id)
+                .is_some_and(|origin| modules.iter().any(|m| m == origin))
+        })
+        .collect();
+    let Some(names) = names else {
+        for id in own {
+            resolver.hidden_imports.remove(&id);
+        }
+        return;
+    };
+    for name in names {
+        let exported = own.iter().copied().find(|id| {
+            matches!(
+                resolver.scopes.get_symbol(*id),
+                Some(sym) if sym.name() == name && resolver.is_accessible(*id)
+            )
+        });
+        match exported {
+            Some(id) => {
+                resolver.hidden_imports.remove(&id);
+            }
+            None => resolver.errors.push(SemanticError::ImportError {
+                message: format!(
+                    "module '{}' has no exported symbol '{}'",
+                    path_components.join("."),
+                    name
+                ),
+                span: span.clone(),
+            }),
+        }
+    }
+}
+
 /// 解析带别名的导入 `import path as alias`
 pub fn resolve_import_as(
     resolver: &mut Resolver,
@@ -101,18 +225,7 @@ pub fn resolve_import_as(
         resolver.visited_modules.insert(path_buf.clone());
     }
 
-    let source = match std::fs::read_to_string(&path_buf) {
-        Ok(s) => s,
-        Err(e) => {
-            resolver.errors.push(SemanticError::ImportError {
-                message: format!("failed to read file '{:?}': {}", path_buf, e),
-                span: span.clone(),
-            });
-            return Vec::new();
-        }
-    };
-
-    match lency_syntax::parser::parse(&source) {
+    match parse_cache::load_module(&path_buf) {
         Ok(prog) => {
             // Synthesize Wrapper
             let mut synthetic_decls = Vec::new();
@@ -121,6 +234,10 @@ pub fn resolve_import_as(
             // Struct
             synthetic_decls.push(Decl::Struct {
                 span: span.clone(),
+                doc: None,
+                is_public: true,
+                is_packed: false,
+                is_extern: false,
                 name: struct_name.clone(),
                 generic_params: Vec::new(),
                 fields: Vec::new(),
@@ -133,19 +250,26 @@ pub fn resolve_import_as(
                 match decl {
                     Decl::Function {
                         span,
+                        doc,
+                        is_public,
                         name,
                         generic_params,
                         params,
                         return_type,
                         body,
+                        source_file,
                     } => {
                         methods.push(Decl::Function {
                             span,
+                            doc,
+                            is_public,
                             name,
                             generic_params,
                             params,
                             return_type,
                             body,
+                            source_file: source_file
+                                .or_else(|| Some(source_file_name(resolver, &path_buf))),
                         });
                     }
                     _ => {
@@ -171,6 +295,7 @@ pub fn resolve_import_as(
             // Global Var
             synthetic_decls.push(Decl::Var {
                 span: span.clone(),
+                is_public: true,
                 name: alias.to_string(),
                 ty: Some(Type::Struct(struct_name.clone())),
                 value: Expr {
@@ -185,17 +310,25 @@ pub fn resolve_import_as(
             // Recursively collect symbols for synthetics
             let mut deep_synthetics = Vec::new();
             for decl in &synthetic_decls {
+                let first_id = resolver.scopes.all_symbols().len();
                 let mut nested = decl::collect_decl(resolver, decl);
                 deep_synthetics.append(&mut nested);
+                // 别名变量指向主文件中的 import 语句；包装结构体的方法来自导入文件
+                if !matches!(decl, Decl::Var { name, .. } if name == alias) {
+                    let end_id = resolver.scopes.all_symbols().len();
+                    resolver
+                        .scopes
+                        .mark_symbol_origin(first_id..end_id, &path_buf);
+                }
             }
 
             synthetic_decls.append(&mut deep_synthetics);
 
             synthetic_decls
         }
-        Err(e) => {
+        Err(message) => {
             resolver.errors.push(SemanticError::ImportError {
-                message: format!("parse error in '{:?}': {:?}", path_buf, e),
+                message,
                 span: span.clone(),
             });
             Vec::new()
@@ -8,24 +8,49 @@ pub mod decl;
 mod decl_impl;
 pub mod expr;
 mod imports;
+mod parse_cache;
 pub mod stmt;
 mod types;
 
-use crate::error::SemanticError;
+use crate::error::{SemanticError, SemanticWarning};
 use crate::scope::ScopeStack;
 use crate::symbol::Symbol;
 use lency_syntax::ast::{Decl, Expr, Program, Span, Stmt, Type};
+pub use parse_cache::clear_import_cache;
+#[cfg(test)]
+pub(crate) use parse_cache::parse_count;
 
 /// 名称解析器
 pub struct Resolver {
     pub(crate) scopes: ScopeStack,
     pub(crate) errors: Vec<SemanticError>,
-    /// 已访问的模块路径（防止循环导入）
+    pub(crate) warnings: Vec<SemanticWarning>,
+    /// 是否对嵌套作用域中的变量遮蔽给出警告
+    pub(crate) warn_shadowing: bool,
+    /// 是否对浮点数的 == / != 比较给出警告（由类型检查阶段产生）
+    pub(crate) warn_float_equality: bool,
+    /// 已访问的模块路径（避免重复加载）
     pub(crate) visited_modules: std::collections::HashSet<std::path::PathBuf>,
-    /// 已加载的程序模块（保持 AST 所有权）
-    pub(crate) loaded_programs: Vec<Program>,
+    /// 正在加载的模块栈（用于检测循环导入）
+    pub(crate) import_stack: Vec<std::path::PathBuf>,
+    /// 已加载的程序模块及其文件路径（保持 AST 所有权）
+    pub(crate) loaded_programs: Vec<(std::path::PathBuf, Program)>,
+    /// 正在解析的模块文件；主程序为 None
+    pub(crate) current_module: Option<std::path::PathBuf>,
+    /// 选择性导入 (import foo.{a}) 时未被点名的符号：仅在其所属模块内可见
+    pub(crate) hidden_imports: std::collections::HashSet<crate::symbol::SymbolId>,
+    /// 模块通过 pub import 再导出的模块
+    pub(crate) reexports: std::collections::HashMap<std::path::PathBuf, Vec<std::path::PathBuf>>,
     /// 项目根目录
     pub(crate) root_dir: std::path::PathBuf,
+    /// 整数常量的值（用于解析 [N]T 中的数组长度）
+    pub(crate) const_values: std::collections::HashMap<String, i64>,
+    /// 正在处理的 impl 块的目标类型（`This` 的替换目标）
+    pub(crate) self_type: Option<Type>,
+    /// 当前方法体是否引用了 this（未引用的方法可作为关联函数调用）
+    pub(crate) this_referenced: bool,
+    /// 当前函数体中的局部变量声明：(名称, 声明位置, 所在块的起始偏移)，用于报告先用后声明
+    pub(crate) block_locals: Vec<(String, std::ops::Range<usize>, usize)>,
 }
 
 impl Resolver {
@@ -34,11 +59,9 @@ impl Resolver {
         // Register built-in structural types (int, bool, string, float)
         let dummy_span = 0..0;
         let mut define_builtin = |name: &str| {
-            let sym = Symbol::Struct(crate::symbol::StructSymbol::new(
-                name.to_string(),
-                dummy_span.clone(),
-            ));
-            scopes.define(sym).ok();
+            let mut sym = crate::symbol::StructSymbol::new(name.to_string(), dummy_span.clone());
+            sym.is_public = true;
+            scopes.define(Symbol::Struct(sym)).ok();
         };
 
         define_builtin("int");
@@ -50,12 +73,21 @@ impl Resolver {
         let result_symbol = Symbol::Enum(crate::symbol::EnumSymbol {
             name: "Result".to_string(),
             generic_params: vec![
-                crate::symbol::GenericParamSymbol::new("T".to_string(), None, dummy_span.clone()),
-                crate::symbol::GenericParamSymbol::new("E".to_string(), None, dummy_span.clone()),
+                crate::symbol::GenericParamSymbol::new(
+                    "T".to_string(),
+                    Vec::new(),
+                    dummy_span.clone(),
+                ),
+                crate::symbol::GenericParamSymbol::new(
+                    "E".to_string(),
+                    Vec::new(),
+                    dummy_span.clone(),
+                ),
             ],
             variants: std::collections::HashMap::new(), // Ok and Err are handled by compiler
             methods: std::collections::HashMap::new(),  // Will be populated by user impl
             span: dummy_span.clone(),
+            is_public: true,
         });
         scopes.define(result_symbol).ok();
 
@@ -65,9 +97,20 @@ impl Resolver {
         Self {
             scopes,
             errors: Vec::new(),
+            warnings: Vec::new(),
+            warn_shadowing: true,
+            warn_float_equality: true,
             visited_modules: std::collections::HashSet::new(),
+            import_stack: Vec::new(),
             loaded_programs: Vec::new(),
+            current_module: None,
+            hidden_imports: std::collections::HashSet::new(),
+            reexports: std::collections::HashMap::new(),
             root_dir: std::env::current_dir().unwrap_or_default(),
+            const_values: std::collections::HashMap::new(),
+            self_type: None,
+            this_referenced: false,
+            block_locals: Vec::new(),
         }
     }
 
@@ -90,14 +133,90 @@ impl Resolver {
         types::normalize_type_with_generics(self, ty, generics);
     }
 
+    /// 符号是否可以在当前模块中引用：同模块不受限，跨模块只能引用 pub 声明
+    pub(crate) fn is_accessible(&self, id: crate::symbol::SymbolId) -> bool {
+        let Some(symbol) = self.scopes.get_symbol(id) else {
+            return true;
+        };
+        symbol.is_public() || self.scopes.symbol_origin(id) == self.current_module.as_deref()
+    }
+
+    /// 按名称检查可见性，跨模块引用私有符号时报告错误
+    pub(crate) fn check_accessible(&mut self, name: &str, span: &Span) {
+        if let Some(id) = self.scopes.lookup_id(name) {
+            if !self.is_accessible(id) {
+                self.errors.push(SemanticError::PrivateSymbol {
+                    name: name.to_string(),
+                    span: span.clone(),
+                });
+            }
+        }
+    }
+
+    /// 符号是否因选择性导入而对当前模块不可见
+    pub(crate) fn is_hidden_import(&self, id: crate::symbol::SymbolId) -> bool {
+        self.hidden_imports.contains(&id)
+            && self.scopes.symbol_origin(id) != self.current_module.as_deref()
+    }
+
+    /// 开关变量遮蔽警告（默认开启）
+    pub fn set_warn_shadowing(&mut self, enabled: bool) {
+        self.warn_shadowing = enabled;
+    }
+
+    /// 开关浮点数相等比较警告（默认开启）
+    pub fn set_warn_float_equality(&mut self, enabled: bool) {
+        self.warn_float_equality = enabled;
+    }
+
+    /// 声明局部变量前调用：遮蔽外层变量时记录警告
+    pub(crate) fn check_shadowing(&mut self, name: &str, span: &Span) {
+        if !self.warn_shadowing {
+            return;
+        }
+        if let Some(previous) = self.scopes.lookup_shadowed(name) {
+            self.warnings.push(SemanticWarning::ShadowedVariable {
+                name: name.to_string(),
+                span: span.clone(),
+                previous_span: previous.span().clone(),
+            });
+        }
+    }
+
+    /// if 条件可在编译期确定时，对永远不会执行的非空分支记录警告
+    pub(crate) fn check_dead_branch(
+        &mut self,
+        condition: &Expr,
+        then_block: &[Stmt],
+        else_block: Option<&[Stmt]>,
+    ) {
+        let Some(value) = condition.const_bool() else {
+            return;
+        };
+        let (branch, dead) = if value {
+            ("else", else_block.unwrap_or_default())
+        } else {
+            ("then", then_block)
+        };
+        if !dead.is_empty() {
+            self.warnings.push(SemanticWarning::DeadBranch {
+                value,
+                branch,
+                span: condition.span.clone(),
+            });
+        }
+    }
+
+    /// 查找可用作数组长度的常量值（非负整数）
+    pub(crate) fn const_array_size(&self, name: &str) -> Option<usize> {
+        let value = *self.const_values.get(name)?;
+        usize::try_from(value).ok()
+    }
+
     /// 解析整个程序
     pub fn resolve(&mut self, program: &mut Program) -> Result<(), Vec<SemanticError>> {
         // Pass 1: 收集顶层声明
-        let mut synthetics_to_add = Vec::new();
-        for decl in &program.decls {
-            let mut synthetics = self.collect_decl(decl);
-            synthetics_to_add.append(&mut synthetics);
-        }
+        let mut synthetics_to_add = decl::collect_decls(self, &program.decls);
         program.decls.append(&mut synthetics_to_add);
 
         // Pass 2: 解析函数体 (Main Program)
@@ -105,18 +224,18 @@ impl Resolver {
             self.resolve_decl(decl);
         }
 
-        // Merge loaded modules into program
-        let mut all_loaded_decls: Vec<Decl> = Vec::new();
-        for mut prog in std::mem::take(&mut self.loaded_programs) {
-            all_loaded_decls.append(&mut prog.decls);
-        }
-
-        // Run Pass 2 on loaded decls
-        for decl in &mut all_loaded_decls {
-            self.resolve_decl(decl);
+        // Run Pass 2 on loaded modules, then merge them into program
+        for (path, mut prog) in std::mem::take(&mut self.loaded_programs) {
+            // 合并前记录函数的源文件，代码生成据此报告 panic 位置
+            let source_file = imports::source_file_name(self, &path);
+            imports::mark_source_file(&mut prog.decls, &source_file);
+            self.current_module = Some(path);
+            for decl in &mut prog.decls {
+                self.resolve_decl(decl);
+            }
+            program.decls.append(&mut prog.decls);
         }
-
-        program.decls.append(&mut all_loaded_decls);
+        self.current_module = None;
 
         if self.errors.is_empty() {
             Ok(())
@@ -129,9 +248,10 @@ impl Resolver {
     pub fn resolve_import(
         &mut self,
         path_components: &[String],
+        names: Option<&[String]>,
         span: &lency_syntax::ast::Span,
     ) -> Vec<Decl> {
-        imports::resolve_import(self, path_components, span)
+        imports::resolve_import(self, path_components, names, span)
     }
 
     /// 解析带别名的导入 (delegated to imports.rs)
@@ -146,10 +266,6 @@ impl Resolver {
 
     // --- Delegation ---
 
-    pub(crate) fn collect_decl(&mut self, decl: &Decl) -> Vec<Decl> {
-        decl::collect_decl(self, decl)
-    }
-
     pub(crate) fn resolve_decl(&mut self, decl: &mut Decl) {
         decl::resolve_decl(self, decl);
     }
@@ -167,6 +283,25 @@ impl Resolver {
         types::resolve_type(self, ty, span);
     }
 
+    /// 验证泛型参数的每个约束都是已定义的 Trait
+    pub(crate) fn resolve_bounds(&mut self, gp: &lency_syntax::ast::GenericParam) {
+        for bound in &gp.bounds {
+            self.resolve_type(bound, &gp.span);
+            let name = match bound {
+                Type::Struct(name) | Type::Generic(name, _) => name,
+                _ => continue,
+            };
+            if let Some(sym) = self.scopes.lookup(name) {
+                if !matches!(sym, Symbol::Trait(_)) {
+                    self.errors.push(SemanticError::UndefinedTrait {
+                        name: name.clone(),
+                        span: gp.span.clone(),
+                    });
+                }
+            }
+        }
+    }
+
     // --- Accessors ---
 
     pub fn scopes(&self) -> &ScopeStack {
@@ -180,6 +315,14 @@ impl Resolver {
     pub fn errors(&self) -> &[SemanticError] {
         &self.errors
     }
+
+    pub fn warnings(&self) -> &[SemanticWarning] {
+        &self.warnings
+    }
+
+    pub fn take_warnings(&mut self) -> Vec<SemanticWarning> {
+        std::mem::take(&mut self.warnings)
+    }
 }
 
 impl Default for Resolver {
@@ -0,0 +1,71 @@
+//! Module Parse Cache
+//!
+//! 导入模块的进程内解析缓存：以规范化路径 + 修改时间为键，
+//! 同一进程内多次编译（LSP、测试、批量构建）不再重复读取和解析未改动的 lib/std 模块。
+
+use lency_syntax::ast::Program;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::SystemTime;
+
+struct CachedModule {
+    modified: SystemTime,
+    program: Program,
+    /// 该路径实际被解析的次数
+    parses: usize,
+}
+
+fn cache() -> &'static Mutex<HashMap<PathBuf, CachedModule>> {
+    static CACHE: OnceLock<Mutex<HashMap<PathBuf, CachedModule>>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// 读取并解析模块文件，文件未改动时直接返回缓存的 AST；失败时返回错误描述
+pub(crate) fn load_module(path: &Path) -> Result<Program, String> {
+    let read_error = |e: std::io::Error| format!("failed to read file '{:?}': {}", path, e);
+    let key = path.canonicalize().map_err(read_error)?;
+    let modified = key
+        .metadata()
+        .and_then(|m| m.modified())
+        .map_err(read_error)?;
+
+    let mut cache = cache().lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(entry) = cache.get(&key) {
+        if entry.modified == modified {
+            return Ok(entry.program.clone());
+        }
+    }
+
+    let source = std::fs::read_to_string(&key).map_err(read_error)?;
+    let program = lency_syntax::parser::parse(&source)
+        .map_err(|e| format!("parse error in '{:?}': {:?}", path, e))?;
+    let parses = cache.get(&key).map_or(0, |entry| entry.parses) + 1;
+    cache.insert(
+        key,
+        CachedModule {
+            modified,
+            program: program.clone(),
+            parses,
+        },
+    );
+    Ok(program)
+}
+
+/// 清空导入模块的解析缓存
+pub fn clear_import_cache() {
+    cache().lock().unwrap_or_else(|e| e.into_inner()).clear();
+}
+
+/// 模块文件被实际解析的次数（未缓存时为 0）
+#[cfg(test)]
+pub(crate) fn parse_count(path: &Path) -> usize {
+    let Ok(key) = path.canonicalize() else {
+        return 0;
+    };
+    cache()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(&key)
+        .map_or(0, |entry| entry.parses)
+}
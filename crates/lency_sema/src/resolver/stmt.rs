@@ -1,45 +1,33 @@
 use super::Resolver;
 use crate::scope::ScopeKind;
 use crate::symbol::{Symbol, VariableSymbol};
-use lency_syntax::ast::{Stmt, Type};
+use lency_syntax::ast::{Expr, Stmt, Type};
 
 pub fn resolve_stmt(resolver: &mut Resolver, stmt: &mut Stmt) {
     match stmt {
         Stmt::VarDecl {
             name,
+            mutable,
             ty,
             value,
             span,
         } => {
             // 先解析初始化表达式（变量在自己的初始化器中不可见）
             resolver.resolve_expr(value);
-
-            // 如果有显式类型声明，验证类型
-            if let Some(t) = ty {
-                resolver.normalize_type(t);
-                resolver.resolve_type(t, span);
-            }
-
-            // 推导类型（如果没有显式声明）
-            let var_ty = ty.clone().unwrap_or_else(|| {
-                // 使用 TypeInferer 推导变量类型
-                let scope_id = resolver.scopes.current_scope();
-                let mut inferer =
-                    crate::type_infer::TypeInferer::with_scope(&mut resolver.scopes, scope_id);
-                inferer.infer(value).unwrap_or(Type::Error)
-            });
-
-            // 添加变量到当前作用域
-            let var_symbol = VariableSymbol::new(
-                name.clone(),
-                var_ty,
-                true, // var 是可变的
-                span.clone(),
-            );
-
-            if let Err(e) = resolver.scopes.define(Symbol::Variable(var_symbol)) {
-                resolver.errors.push(e);
-            }
+            declare_local(resolver, name, *mutable, ty, value, span);
+        }
+        Stmt::LoopVarDecl {
+            name,
+            mutable,
+            ty,
+            loop_stmt,
+            default,
+            span,
+        } => {
+            // 循环体与默认值中都看不到正在声明的变量；未标注类型时按默认值推导
+            resolver.resolve_stmt(loop_stmt);
+            resolver.resolve_expr(default);
+            declare_local(resolver, name, *mutable, ty, default, span);
         }
         Stmt::Assignment { target, value, .. } => {
             resolver.resolve_expr(target);
@@ -63,6 +51,7 @@ pub fn resolve_stmt(resolver: &mut Resolver, stmt: &mut Stmt) {
             ..
         } => {
             resolver.resolve_expr(condition);
+            resolver.check_dead_branch(condition, then_block, else_block.as_deref());
 
             // then 分支
             resolver.scopes.enter_scope(ScopeKind::Block);
@@ -80,6 +69,31 @@ pub fn resolve_stmt(resolver: &mut Resolver, stmt: &mut Stmt) {
                 resolver.scopes.exit_scope();
             }
         }
+        Stmt::IfLet {
+            pattern,
+            value,
+            then_block,
+            else_block,
+            ..
+        } => {
+            resolver.resolve_expr(value);
+
+            // then 分支：模式绑定只在 then 块内可见
+            resolver.scopes.enter_scope(ScopeKind::Block);
+            super::expr::declare_pattern_vars(resolver, pattern);
+            for stmt in then_block {
+                resolver.resolve_stmt(stmt);
+            }
+            resolver.scopes.exit_scope();
+
+            if let Some(else_stmts) = else_block {
+                resolver.scopes.enter_scope(ScopeKind::Block);
+                for stmt in else_stmts {
+                    resolver.resolve_stmt(stmt);
+                }
+                resolver.scopes.exit_scope();
+            }
+        }
         Stmt::While {
             condition, body, ..
         } => {
@@ -160,8 +174,117 @@ pub fn resolve_stmt(resolver: &mut Resolver, stmt: &mut Stmt) {
                 resolver.resolve_expr(expr);
             }
         }
+        Stmt::Break {
+            value: Some(value), ..
+        } => {
+            resolver.resolve_expr(value);
+        }
         Stmt::Break { .. } | Stmt::Continue { .. } => {
             // 不需要解析
         }
     }
 }
+
+/// 在当前作用域定义局部变量，类型取显式声明或由 `value` 推导
+fn declare_local(
+    resolver: &mut Resolver,
+    name: &str,
+    mutable: bool,
+    ty: &mut Option<Type>,
+    value: &mut Expr,
+    span: &std::ops::Range<usize>,
+) {
+    // 如果有显式类型声明，验证类型
+    if let Some(t) = ty {
+        resolver.normalize_type(t);
+        resolver.resolve_type(t, span);
+    }
+
+    // 推导类型（如果没有显式声明）
+    let var_ty = ty.clone().unwrap_or_else(|| {
+        // 使用 TypeInferer 推导变量类型
+        let scope_id = resolver.scopes.current_scope();
+        let mut inferer =
+            crate::type_infer::TypeInferer::with_scope(&mut resolver.scopes, scope_id);
+        inferer.infer(value).unwrap_or(Type::Error)
+    });
+
+    // 添加变量到当前作用域
+    resolver.check_shadowing(name, span);
+
+    // var 可变，let 不可变
+    let var_symbol = VariableSymbol::new(name.to_string(), var_ty, mutable, span.clone());
+
+    if let Err(e) = resolver.scopes.define(Symbol::Variable(var_symbol)) {
+        resolver.errors.push(e);
+    }
+}
+
+/// 收集函数体内各块直接声明的局部变量：(名称, 声明位置, 所在块的起始偏移)
+///
+/// 声明之前、同一块之内的引用即为先用后声明
+pub(crate) fn collect_block_locals(
+    stmts: &[Stmt],
+    out: &mut Vec<(String, std::ops::Range<usize>, usize)>,
+) {
+    let Some(block_start) = stmts.first().and_then(stmt_start) else {
+        return;
+    };
+    for stmt in stmts {
+        match stmt {
+            Stmt::VarDecl { name, span, .. } => out.push((name.clone(), span.clone(), block_start)),
+            Stmt::LoopVarDecl {
+                name,
+                span,
+                loop_stmt,
+                ..
+            } => {
+                out.push((name.clone(), span.clone(), block_start));
+                collect_block_locals(std::slice::from_ref(loop_stmt.as_ref()), out);
+            }
+            Stmt::Block(body)
+            | Stmt::While { body, .. }
+            | Stmt::For { body, .. }
+            | Stmt::ForIn { body, .. } => collect_block_locals(body, out),
+            Stmt::If {
+                then_block,
+                else_block,
+                ..
+            }
+            | Stmt::IfLet {
+                then_block,
+                else_block,
+                ..
+            } => {
+                collect_block_locals(then_block, out);
+                if let Some(else_stmts) = else_block {
+                    collect_block_locals(else_stmts, out);
+                }
+            }
+            Stmt::Assignment { .. }
+            | Stmt::Expression(_)
+            | Stmt::Return { .. }
+            | Stmt::Break { .. }
+            | Stmt::Continue { .. } => {}
+        }
+    }
+}
+
+/// 语句的起始偏移（空块没有位置）
+fn stmt_start(stmt: &Stmt) -> Option<usize> {
+    match stmt {
+        Stmt::Expression(expr) => Some(expr.span.start),
+        Stmt::Block(stmts) => stmts.first().and_then(stmt_start),
+        Stmt::VarDecl { span, .. }
+        | Stmt::LoopVarDecl { span, .. }
+        | Stmt::Assignment { span, .. }
+        | Stmt::If { span, .. }
+        | Stmt::IfLet { span, .. }
+        | Stmt::While { span, .. }
+        | Stmt::For { span, .. }
+        | Stmt::ForIn { span, .. }
+        | Stmt::Return { span, .. }
+        | Stmt::Break { span, .. }
+        | Stmt::Continue { span } => Some(span.start),
+    }
+}
@@ -30,13 +30,36 @@ pub fn normalize_type(resolver: &mut Resolver, ty: &mut Type) {
         Type::Array { element_type, .. } => {
             normalize_type(resolver, element_type);
         }
+        Type::ConstSizedArray {
+            element_type,
+            size_name,
+        } => {
+            normalize_type(resolver, element_type);
+            if let Some(size) = resolver.const_array_size(size_name) {
+                *ty = Type::Array {
+                    element_type: element_type.clone(),
+                    size,
+                };
+            }
+        }
         Type::Result { ok_type, err_type } => {
             normalize_type(resolver, ok_type);
             normalize_type(resolver, err_type);
         }
-        Type::Struct(name) => {
-            if let Some(Symbol::GenericParam(_)) = resolver.scopes.lookup(name) {
-                *ty = Type::GenericParam(name.clone());
+        Type::Struct(name) => match resolver.scopes.lookup(name) {
+            Some(Symbol::GenericParam(_)) => *ty = Type::GenericParam(name.clone()),
+            Some(Symbol::Newtype(n)) => *ty = n.as_type(),
+            _ => {}
+        },
+        Type::Record(fields) => {
+            for (_, field_ty) in fields {
+                normalize_type(resolver, field_ty);
+            }
+        }
+        Type::SelfType => {
+            if let Some(self_ty) = resolver.self_type.clone() {
+                *ty = self_ty;
+                normalize_type(resolver, ty);
             }
         }
         _ => {}
@@ -74,6 +97,18 @@ pub fn normalize_type_with_generics(
         Type::Array { element_type, .. } => {
             normalize_type_with_generics(resolver, element_type, generics);
         }
+        Type::ConstSizedArray {
+            element_type,
+            size_name,
+        } => {
+            normalize_type_with_generics(resolver, element_type, generics);
+            if let Some(size) = resolver.const_array_size(size_name) {
+                *ty = Type::Array {
+                    element_type: element_type.clone(),
+                    size,
+                };
+            }
+        }
         Type::Result { ok_type, err_type } => {
             normalize_type_with_generics(resolver, ok_type, generics);
             normalize_type_with_generics(resolver, err_type, generics);
@@ -83,6 +118,19 @@ pub fn normalize_type_with_generics(
                 *ty = Type::GenericParam(name.clone());
             } else if let Some(Symbol::GenericParam(_)) = resolver.scopes.lookup(name) {
                 *ty = Type::GenericParam(name.clone());
+            } else if let Some(Symbol::Newtype(n)) = resolver.scopes.lookup(name) {
+                *ty = n.as_type();
+            }
+        }
+        Type::Record(fields) => {
+            for (_, field_ty) in fields {
+                normalize_type_with_generics(resolver, field_ty, generics);
+            }
+        }
+        Type::SelfType => {
+            if let Some(self_ty) = resolver.self_type.clone() {
+                *ty = self_ty;
+                normalize_type_with_generics(resolver, ty, generics);
             }
         }
         _ => {}
@@ -91,6 +139,14 @@ pub fn normalize_type_with_generics(
 
 /// 验证类型引用（包括泛型参数检查）
 pub fn resolve_type(resolver: &mut Resolver, ty: &Type, span: &Span) {
+    // 跨模块只能引用 pub 类型
+    if let Type::Struct(name)
+    | Type::Generic(name, _)
+    | Type::Newtype(name, _)
+    | Type::TraitObject(name) = ty
+    {
+        resolver.check_accessible(name, span);
+    }
     match ty {
         Type::Generic(name, args) => {
             let sym = resolver.scopes.lookup(name);
@@ -157,7 +213,7 @@ pub fn resolve_type(resolver: &mut Resolver, ty: &Type, span: &Span) {
                     });
                 }
             }
-            Some(Symbol::GenericParam(_)) => {}
+            Some(Symbol::GenericParam(_) | Symbol::Newtype(_)) => {}
             Some(Symbol::Trait(t)) => {
                 if !t.generic_params.is_empty() {
                     resolver.errors.push(SemanticError::GenericArityMismatch {
@@ -199,6 +255,97 @@ pub fn resolve_type(resolver: &mut Resolver, ty: &Type, span: &Span) {
         | Type::Nullable(inner) => {
             resolve_type(resolver, inner, span);
         }
+        // 规范化后仍未替换，说明长度不是已知的整数常量
+        Type::ConstSizedArray {
+            element_type,
+            size_name,
+        } => {
+            resolver.errors.push(SemanticError::InvalidArraySize {
+                name: size_name.clone(),
+                span: span.clone(),
+            });
+            resolve_type(resolver, element_type, span);
+        }
+        Type::Record(fields) => {
+            // 字段已按名称排序，重复字段必然相邻
+            for pair in fields.windows(2) {
+                if pair[0].0 == pair[1].0 {
+                    resolver.errors.push(SemanticError::DuplicateDefinition {
+                        name: pair[1].0.clone(),
+                        span: span.clone(),
+                        previous_span: span.clone(),
+                    });
+                }
+            }
+            for (_, field_ty) in fields {
+                resolve_type(resolver, field_ty, span);
+            }
+        }
+        // dyn T 只能引用非泛型 Trait
+        Type::TraitObject(name) => match resolver.scopes.lookup(name) {
+            Some(Symbol::Trait(t)) if !t.generic_params.is_empty() => {
+                resolver.errors.push(SemanticError::GenericArityMismatch {
+                    name: name.clone(),
+                    expected: t.generic_params.len(),
+                    found: 0,
+                    span: span.clone(),
+                });
+            }
+            Some(Symbol::Trait(_)) => {}
+            _ => {
+                resolver.errors.push(SemanticError::UndefinedTrait {
+                    name: name.clone(),
+                    span: span.clone(),
+                });
+            }
+        },
+        // 规范化后仍未替换，说明不在 impl 块中
+        Type::SelfType => {
+            resolver
+                .errors
+                .push(SemanticError::SelfTypeOutsideImpl { span: span.clone() });
+        }
+        _ => {}
+    }
+}
+
+/// 把类型中的 `This` 替换为 impl 的目标类型
+pub fn substitute_self_type(ty: &mut Type, target: &Type) {
+    match ty {
+        Type::SelfType => *ty = target.clone(),
+        Type::Generic(_, args) => {
+            for arg in args {
+                substitute_self_type(arg, target);
+            }
+        }
+        Type::Nullable(inner)
+        | Type::Vec(inner)
+        | Type::Array {
+            element_type: inner,
+            ..
+        }
+        | Type::ConstSizedArray {
+            element_type: inner,
+            ..
+        } => substitute_self_type(inner, target),
+        Type::Result { ok_type, err_type } => {
+            substitute_self_type(ok_type, target);
+            substitute_self_type(err_type, target);
+        }
+        Type::Function {
+            param_types,
+            return_type,
+        } => {
+            for param in param_types {
+                substitute_self_type(param, target);
+            }
+            substitute_self_type(return_type, target);
+        }
+        Type::Record(fields) => {
+            for (_, field_ty) in fields {
+                substitute_self_type(field_ty, target);
+            }
+        }
         _ => {}
     }
 }
@@ -12,6 +12,15 @@ pub use stack::ScopeStack;
 /// 作用域 ID
 pub type ScopeId = usize;
 
+/// 名称引用的解析结果（供 LSP 等工具从使用点跳转到定义）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Resolution {
+    /// 解析到符号表中的符号（变量、参数、函数、结构体等）
+    Symbol(SymbolId),
+    /// 解析到结构体/枚举的成员（字段或方法），owner 为所属类型的符号
+    Member { owner: SymbolId, name: String },
+}
+
 /// 单个作用域
 #[derive(Debug, Clone)]
 pub struct Scope {
@@ -60,4 +69,9 @@ impl Scope {
     pub fn lookup_local(&self, name: &str) -> Option<SymbolId> {
         self.symbols.get(name).copied()
     }
+
+    /// 遍历本作用域直接定义的符号 (名称, 符号 ID)，顺序不保证
+    pub fn symbols(&self) -> impl Iterator<Item = (&str, SymbolId)> {
+        self.symbols.iter().map(|(name, id)| (name.as_str(), *id))
+    }
 }
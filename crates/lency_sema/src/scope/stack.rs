@@ -1,6 +1,9 @@
-use super::{Scope, ScopeId, ScopeKind};
+use super::{Resolution, Scope, ScopeId, ScopeKind};
 use crate::error::SemanticError;
 use crate::symbol::{Symbol, SymbolId};
+use lency_syntax::ast::Span;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 
 /// 作用域栈 - 管理嵌套作用域
 #[derive(Debug)]
@@ -9,6 +12,10 @@ pub struct ScopeStack {
     current: ScopeId,
     // 存储所有的符号
     symbols: Vec<Symbol>,
+    // 引用点 -> 解析结果（按 span 去重，多个 Pass 重复记录不会产生重复项）
+    resolutions: HashMap<Span, Resolution>,
+    // 来自导入模块的符号 -> 源文件路径
+    symbol_origins: HashMap<SymbolId, PathBuf>,
 }
 
 impl ScopeStack {
@@ -19,6 +26,8 @@ impl ScopeStack {
             scopes: vec![global_scope],
             current: 0,
             symbols: Vec::new(),
+            resolutions: HashMap::new(),
+            symbol_origins: HashMap::new(),
         }
     }
 
@@ -72,6 +81,23 @@ impl ScopeStack {
         Ok(id)
     }
 
+    /// 查找被遮蔽的局部变量：从当前作用域的父作用域向上查找（不含全局作用域）
+    ///
+    /// 只返回变量和参数，同一作用域内的重名由 `define` 报错
+    pub fn lookup_shadowed(&self, name: &str) -> Option<&Symbol> {
+        let mut current_id = self.scopes[self.current].parent?;
+        while current_id != 0 {
+            if let Some(symbol_id) = self.scopes[current_id].lookup_local(name) {
+                return match &self.symbols[symbol_id] {
+                    symbol @ (Symbol::Variable(_) | Symbol::Parameter(_)) => Some(symbol),
+                    _ => None,
+                };
+            }
+            current_id = self.scopes[current_id].parent?;
+        }
+        None
+    }
+
     /// 查找符号（从当前作用域向上查找）
     pub fn lookup(&self, name: &str) -> Option<&Symbol> {
         self.lookup_id(name).and_then(|id| self.symbols.get(id))
@@ -118,6 +144,17 @@ impl ScopeStack {
         None
     }
 
+    /// 从指定作用域开始查找符号 ID（向上查找）
+    pub fn lookup_id_from(&self, name: &str, start_scope: ScopeId) -> Option<SymbolId> {
+        let mut current_id = start_scope;
+        loop {
+            if let Some(symbol_id) = self.scopes[current_id].lookup_local(name) {
+                return Some(symbol_id);
+            }
+            current_id = self.scopes[current_id].parent?;
+        }
+    }
+
     /// 仅在当前作用域查找（不向上查找）
     pub fn lookup_local(&self, name: &str) -> Option<&Symbol> {
         self.scopes[self.current]
@@ -222,6 +259,77 @@ impl ScopeStack {
         &self.symbols
     }
 
+    /// 遍历所有作用域（按创建顺序，0 为全局作用域）
+    pub fn iter_scopes(&self) -> impl Iterator<Item = &Scope> {
+        self.scopes.iter()
+    }
+
+    /// 获取特定作用域的只读引用
+    pub fn get_scope(&self, id: ScopeId) -> Option<&Scope> {
+        self.scopes.get(id)
+    }
+
+    /// 列出某个作用域内直接定义的所有符号（不含父/子作用域），按定义顺序排列
+    pub fn all_symbols_in(&self, scope: ScopeId) -> Vec<(SymbolId, &Symbol)> {
+        let Some(scope) = self.scopes.get(scope) else {
+            return Vec::new();
+        };
+        let mut ids: Vec<SymbolId> = scope.symbols().map(|(_, id)| id).collect();
+        ids.sort_unstable();
+        ids.into_iter()
+            .filter_map(|id| self.symbols.get(id).map(|sym| (id, sym)))
+            .collect()
+    }
+
+    /// 记录一个引用点的解析结果
+    pub fn record_resolution(&mut self, span: Span, resolution: Resolution) {
+        self.resolutions.insert(span, resolution);
+    }
+
+    /// 记录成员访问 `obj.name` 的解析结果；`get_span` 为整个 Get 表达式，成员名位于其末尾
+    pub fn record_member_resolution(&mut self, owner: SymbolId, name: &str, get_span: &Span) {
+        let name_span = get_span.end.saturating_sub(name.len())..get_span.end;
+        self.record_resolution(
+            name_span,
+            Resolution::Member {
+                owner,
+                name: name.to_string(),
+            },
+        );
+    }
+
+    /// 遍历所有引用点
+    pub fn resolutions(&self) -> impl Iterator<Item = (&Span, &Resolution)> {
+        self.resolutions.iter()
+    }
+
+    /// 获取恰好位于 `span` 的引用点
+    pub fn resolution_of(&self, span: &Span) -> Option<&Resolution> {
+        self.resolutions.get(span)
+    }
+
+    /// 查找覆盖指定源码偏移的最内层引用点
+    pub fn resolution_at(&self, offset: usize) -> Option<(&Span, &Resolution)> {
+        self.resolutions
+            .iter()
+            .filter(|(span, _)| span.start <= offset && offset <= span.end)
+            .min_by_key(|(span, _)| span.end - span.start)
+    }
+
+    /// 标记一段符号 ID 区间来自某个导入文件（已标记的符号保持不变，嵌套导入以最内层为准）
+    pub fn mark_symbol_origin(&mut self, ids: std::ops::Range<SymbolId>, path: &Path) {
+        for id in ids {
+            self.symbol_origins
+                .entry(id)
+                .or_insert_with(|| path.to_path_buf());
+        }
+    }
+
+    /// 获取符号所在的导入文件；主程序中定义的符号返回 None
+    pub fn symbol_origin(&self, id: SymbolId) -> Option<&Path> {
+        self.symbol_origins.get(&id).map(PathBuf::as_path)
+    }
+
     /// 获取特定作用域的可变引用 (用于 Flow Analysis 注入 refinement)
     pub fn get_scope_mut(&mut self, id: ScopeId) -> Option<&mut Scope> {
         self.scopes.get_mut(id)
@@ -287,4 +395,72 @@ mod tests {
             assert_eq!(name, "x");
         }
     }
+
+    #[test]
+    fn test_enumerate_program_symbols() {
+        use crate::symbol::SymbolKind;
+        use crate::Resolver;
+        use lency_syntax::ast::Decl;
+
+        let source = r#"
+            struct Point { int x int y }
+            int add(int a, int b) {
+                var sum = a + b;
+                return sum;
+            }
+        "#;
+        let mut program = lency_syntax::parser::parse(source).unwrap(); // allow: unwrap
+        let mut resolver = Resolver::new();
+        resolver.resolve(&mut program).unwrap(); // allow: unwrap
+        let scopes = resolver.into_scopes();
+
+        let decl_span = |name: &str| {
+            program
+                .decls
+                .iter()
+                .find_map(|d| match d {
+                    Decl::Struct { name: n, span, .. } | Decl::Function { name: n, span, .. }
+                        if n == name =>
+                    {
+                        Some(span.clone())
+                    }
+                    _ => None,
+                })
+                .unwrap() // allow: unwrap
+        };
+
+        // 全局作用域：用户定义的结构体和函数（内置符号的 span 为 0..0，这里跳过）
+        let globals: Vec<_> = scopes
+            .all_symbols_in(0)
+            .into_iter()
+            .filter(|(_, sym)| !sym.span().is_empty())
+            .map(|(_, sym)| (sym.name().to_string(), sym.kind(), sym.span().clone()))
+            .collect();
+        assert_eq!(
+            globals,
+            vec![
+                ("Point".to_string(), SymbolKind::Struct, decl_span("Point")),
+                ("add".to_string(), SymbolKind::Function, decl_span("add")),
+            ]
+        );
+
+        // 函数作用域：参数和局部变量
+        let func_scope = scopes
+            .iter_scopes()
+            .find(|s| s.kind == ScopeKind::Function)
+            .unwrap(); // allow: unwrap
+        let locals: Vec<_> = scopes
+            .all_symbols_in(func_scope.id)
+            .into_iter()
+            .map(|(_, sym)| (sym.name().to_string(), sym.kind()))
+            .collect();
+        assert_eq!(
+            locals,
+            vec![
+                ("a".to_string(), SymbolKind::Parameter),
+                ("b".to_string(), SymbolKind::Parameter),
+                ("sum".to_string(), SymbolKind::Variable),
+            ]
+        );
+    }
 }
@@ -3,7 +3,7 @@
 //! 符号系统定义，采用可扩展的枚举设计，符合开闭原则。
 //! 新增符号类型只需添加新的变体，不影响现有代码。
 
-use lency_syntax::ast::{Span, Type};
+use lency_syntax::ast::{Decl, Expr, Param, Span, Stmt, Type};
 use std::collections::HashMap;
 
 /// 符号 ID，用于在符号表中唯一标识
@@ -16,14 +16,35 @@ pub type SymbolId = usize;
 #[derive(Debug, Clone)]
 pub struct GenericParamSymbol {
     pub name: String,
-    pub bound: Option<Type>, // 约束类型，如 T: Display
+    pub bounds: Vec<Type>, // 约束类型，如 T: Display + Comparable
     pub span: Span,
 }
 
 impl GenericParamSymbol {
-    pub fn new(name: String, bound: Option<Type>, span: Span) -> Self {
-        Self { name, bound, span }
+    pub fn new(name: String, bounds: Vec<Type>, span: Span) -> Self {
+        Self { name, bounds, span }
     }
+
+    /// 约束中的 Trait 名称
+    pub fn bound_traits(&self) -> impl Iterator<Item = &str> {
+        self.bounds.iter().filter_map(|b| match b {
+            Type::Struct(name) | Type::Generic(name, _) => Some(name.as_str()),
+            _ => None,
+        })
+    }
+}
+
+/// 符号种类（供 LSP、文档生成等工具区分符号，不携带具体数据）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SymbolKind {
+    Variable,
+    Function,
+    Parameter,
+    Struct,
+    GenericParam,
+    Trait,
+    Enum,
+    Newtype,
 }
 
 /// 符号 - 程序中所有命名实体的统一表示
@@ -40,6 +61,7 @@ pub enum Symbol {
     GenericParam(GenericParamSymbol), // 泛型参数符号
     Trait(TraitSymbol),               // Trait 符号
     Enum(EnumSymbol),                 // Enum 符号
+    Newtype(NewtypeSymbol),           // newtype 符号
 }
 
 impl Symbol {
@@ -53,6 +75,7 @@ impl Symbol {
             Symbol::GenericParam(g) => &g.name,
             Symbol::Trait(t) => &t.name,
             Symbol::Enum(e) => &e.name,
+            Symbol::Newtype(n) => &n.name,
         }
     }
 
@@ -66,6 +89,21 @@ impl Symbol {
             Symbol::GenericParam(g) => &g.span,
             Symbol::Trait(t) => &t.span,
             Symbol::Enum(e) => &e.span,
+            Symbol::Newtype(n) => &n.span,
+        }
+    }
+
+    /// 获取符号种类
+    pub fn kind(&self) -> SymbolKind {
+        match self {
+            Symbol::Variable(_) => SymbolKind::Variable,
+            Symbol::Function(_) => SymbolKind::Function,
+            Symbol::Parameter(_) => SymbolKind::Parameter,
+            Symbol::Struct(_) => SymbolKind::Struct,
+            Symbol::GenericParam(_) => SymbolKind::GenericParam,
+            Symbol::Trait(_) => SymbolKind::Trait,
+            Symbol::Enum(_) => SymbolKind::Enum,
+            Symbol::Newtype(_) => SymbolKind::Newtype,
         }
     }
 
@@ -79,8 +117,50 @@ impl Symbol {
             Symbol::GenericParam(_) => None, // 泛型参数本身不是值类型
             Symbol::Trait(_) => None,        // Trait 不是值类型
             Symbol::Enum(_) => None,         // Enum本身是类型
+            Symbol::Newtype(_) => None,      // newtype 本身是类型
         }
     }
+
+    /// 是否可被其他模块引用；参数和泛型参数只在声明处可见，视为公开
+    pub fn is_public(&self) -> bool {
+        match self {
+            Symbol::Variable(v) => v.is_public,
+            Symbol::Function(f) => f.is_public,
+            Symbol::Parameter(_) | Symbol::GenericParam(_) => true,
+            Symbol::Struct(s) => s.is_public,
+            Symbol::Trait(t) => t.is_public,
+            Symbol::Enum(e) => e.is_public,
+            Symbol::Newtype(n) => n.is_public,
+        }
+    }
+}
+
+/// newtype 符号
+///
+/// 对应 `newtype UserId = int`，`inner` 是底层类型
+#[derive(Debug, Clone)]
+pub struct NewtypeSymbol {
+    pub name: String,
+    pub inner: Type,
+    pub span: Span,
+    /// 是否是公开的 (pub)
+    pub is_public: bool,
+}
+
+impl NewtypeSymbol {
+    pub fn new(name: String, inner: Type, span: Span) -> Self {
+        Self {
+            name,
+            inner,
+            span,
+            is_public: false,
+        }
+    }
+
+    /// 在类型中使用该 newtype 时的表示
+    pub fn as_type(&self) -> Type {
+        Type::Newtype(self.name.clone(), Box::new(self.inner.clone()))
+    }
 }
 
 /// 变量符号
@@ -90,10 +170,12 @@ impl Symbol {
 pub struct VariableSymbol {
     pub name: String,
     pub ty: Type,
-    pub is_mutable: bool, // var = true, const = false
+    pub is_mutable: bool, // var = true, let / const = false
     pub span: Span,
     /// 是否已初始化（用于检测使用未初始化变量）
     pub is_initialized: bool,
+    /// 是否是公开的 (pub)；局部变量只在自身作用域可见，总为 true
+    pub is_public: bool,
 }
 
 impl VariableSymbol {
@@ -104,6 +186,7 @@ impl VariableSymbol {
             is_mutable,
             span,
             is_initialized: true,
+            is_public: true,
         }
     }
 }
@@ -121,6 +204,10 @@ pub struct FunctionSymbol {
     pub span: Span,
     /// 是否是公开的 (pub)
     pub is_public: bool,
+    /// impl 方法体未引用 this，可通过 Type::method 调用
+    pub is_static: bool,
+    /// 可变参数的 extern 函数：固定参数之后可再传任意个基础类型实参
+    pub is_variadic: bool,
 }
 
 impl FunctionSymbol {
@@ -132,6 +219,8 @@ impl FunctionSymbol {
             return_type,
             span,
             is_public: false,
+            is_static: false,
+            is_variadic: false,
         }
     }
 
@@ -150,6 +239,8 @@ impl FunctionSymbol {
             return_type,
             span,
             is_public: false,
+            is_static: false,
+            is_variadic: false,
         }
     }
 
@@ -162,12 +253,25 @@ impl FunctionSymbol {
     pub fn arity(&self) -> usize {
         self.params.len()
     }
+
+    /// 声明签名，如 `int add(int a, int b)`；隐式的 this 参数不显示
+    pub fn signature(&self) -> String {
+        let params: Vec<String> = self
+            .params
+            .iter()
+            .filter(|(name, _)| name != "this")
+            .map(|(name, ty)| format!("{} {}", ty, name))
+            .collect();
+        format!("{} {}({})", self.return_type, self.name, params.join(", "))
+    }
 }
 
 /// 字段信息
 #[derive(Debug, Clone)]
 pub struct FieldInfo {
     pub ty: Type,
+    /// 默认值（字面量），结构体字面量省略该字段时填入
+    pub default: Option<Expr>,
     pub span: Span,
 }
 
@@ -204,7 +308,13 @@ pub struct StructSymbol {
     pub generic_params: Vec<GenericParamSymbol>, // 泛型参数列表
     pub fields: HashMap<String, FieldInfo>,
     pub methods: HashMap<String, FunctionSymbol>,
+    /// 已实现的 Trait 名称（`impl Trait for Struct`）
+    pub traits: Vec<String>,
+    /// `extern struct`：可按值传给 extern "C" 函数
+    pub is_extern: bool,
     pub span: Span,
+    /// 是否是公开的 (pub)
+    pub is_public: bool,
 }
 
 impl StructSymbol {
@@ -214,7 +324,10 @@ impl StructSymbol {
             generic_params: Vec::new(),
             fields: HashMap::new(),
             methods: HashMap::new(),
+            traits: Vec::new(),
+            is_extern: false,
             span,
+            is_public: false,
         }
     }
 
@@ -225,7 +338,10 @@ impl StructSymbol {
             generic_params,
             fields: HashMap::new(),
             methods: HashMap::new(),
+            traits: Vec::new(),
+            is_extern: false,
             span,
+            is_public: false,
         }
     }
 
@@ -235,8 +351,8 @@ impl StructSymbol {
     }
 
     /// 添加字段
-    pub fn add_field(&mut self, name: String, ty: Type, span: Span) {
-        self.fields.insert(name, FieldInfo { ty, span });
+    pub fn add_field(&mut self, name: String, ty: Type, default: Option<Expr>, span: Span) {
+        self.fields.insert(name, FieldInfo { ty, default, span });
     }
 
     /// 查找字段
@@ -253,16 +369,30 @@ impl StructSymbol {
     pub fn get_method(&self, name: &str) -> Option<&FunctionSymbol> {
         self.methods.get(name)
     }
+
+    /// 记录已实现的 Trait
+    pub fn add_trait(&mut self, name: String) {
+        if !self.traits.contains(&name) {
+            self.traits.push(name);
+        }
+    }
+
+    /// 是否实现了指定 Trait
+    pub fn implements(&self, trait_name: &str) -> bool {
+        self.traits.iter().any(|t| t == trait_name)
+    }
 }
 
 /// Trait 方法签名
 ///
-/// 表示 Trait 中定义的方法签名（无函数体）
+/// 表示 Trait 中定义的方法签名，可带默认实现
 #[derive(Debug, Clone)]
 pub struct TraitMethodSignature {
     pub name: String,
     pub params: Vec<(String, Type)>,
     pub return_type: Type,
+    /// 默认实现（未解析的函数体）与其位置
+    pub default_body: Option<(Vec<Stmt>, Span)>,
 }
 
 impl TraitMethodSignature {
@@ -271,8 +401,38 @@ impl TraitMethodSignature {
             name,
             params,
             return_type,
+            default_body: None,
         }
     }
+
+    /// 附加默认实现
+    pub fn with_default_body(mut self, body: Vec<Stmt>, span: Span) -> Self {
+        self.default_body = Some((body, span));
+        self
+    }
+
+    /// 为实现者生成默认方法的声明；没有默认实现时返回 None
+    pub fn default_method(&self) -> Option<Decl> {
+        let (body, span) = self.default_body.as_ref()?;
+        Some(Decl::Function {
+            span: span.clone(),
+            doc: None,
+            is_public: false,
+            name: self.name.clone(),
+            generic_params: Vec::new(),
+            params: self
+                .params
+                .iter()
+                .map(|(name, ty)| Param {
+                    name: name.clone(),
+                    ty: ty.clone(),
+                })
+                .collect(),
+            return_type: self.return_type.clone(),
+            body: body.clone(),
+            source_file: None,
+        })
+    }
 }
 
 /// Trait 符号
@@ -285,6 +445,8 @@ pub struct TraitSymbol {
     pub generic_params: Vec<GenericParamSymbol>,
     pub methods: Vec<TraitMethodSignature>,
     pub span: Span,
+    /// 是否是公开的 (pub)
+    pub is_public: bool,
 }
 
 impl TraitSymbol {
@@ -294,6 +456,7 @@ impl TraitSymbol {
             generic_params: Vec::new(),
             methods: Vec::new(),
             span,
+            is_public: false,
         }
     }
 
@@ -304,6 +467,7 @@ impl TraitSymbol {
             generic_params,
             methods: Vec::new(),
             span,
+            is_public: false,
         }
     }
 
@@ -333,6 +497,8 @@ pub struct EnumSymbol {
     pub variants: HashMap<String, Vec<Type>>, // 变体名 -> 字段类型列表
     pub methods: HashMap<String, FunctionSymbol>,
     pub span: Span,
+    /// 是否是公开的 (pub)
+    pub is_public: bool,
 }
 
 impl EnumSymbol {
@@ -343,6 +509,7 @@ impl EnumSymbol {
             variants: HashMap::new(),
             methods: HashMap::new(),
             span,
+            is_public: false,
         }
     }
 
@@ -353,6 +520,7 @@ impl EnumSymbol {
             variants: HashMap::new(),
             methods: HashMap::new(),
             span,
+            is_public: false,
         }
     }
 
@@ -368,6 +536,11 @@ impl EnumSymbol {
         self.variants.get(name)
     }
 
+    /// 是否所有变体都不带数据（可与 int 互相转换）
+    pub fn is_unit_only(&self) -> bool {
+        self.variants.values().all(|fields| fields.is_empty())
+    }
+
     pub fn add_method(&mut self, name: String, method: FunctionSymbol) {
         self.methods.insert(name, method);
     }
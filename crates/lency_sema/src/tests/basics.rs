@@ -0,0 +1,279 @@
+use crate::*;
+use lency_syntax::ast::*;
+
+/// 创建一个简单的测试程序
+fn make_simple_program() -> Program {
+    // int main() { var x = 10; return x; }
+    Program {
+        decls: vec![Decl::Function {
+            span: 0..50,
+            doc: None,
+            is_public: false,
+            name: "main".to_string(),
+            generic_params: vec![],
+            params: vec![],
+            return_type: Type::Int,
+            body: vec![
+                Stmt::VarDecl {
+                    span: 10..20,
+                    name: "x".to_string(),
+                    mutable: true,
+                    ty: Some(Type::Int),
+                    value: Expr {
+                        kind: ExprKind::Literal(Literal::Int(10)),
+                        span: 15..17,
+                    },
+                },
+                Stmt::Return {
+                    span: 20..30,
+                    value: Some(Expr {
+                        kind: ExprKind::Variable("x".to_string()),
+                        span: 27..28,
+                    }),
+                },
+            ],
+            source_file: None,
+        }],
+    }
+}
+
+#[test]
+fn test_analyze_simple_program() {
+    let mut program = make_simple_program();
+    let result = analyze(&mut program);
+    assert!(
+        result.is_ok(),
+        "Analysis should succeed: {:?}",
+        result.err()
+    );
+}
+
+#[test]
+fn test_undefined_variable_error() {
+    // int main() { return y; }  -- y 未定义
+    let mut program = Program {
+        decls: vec![Decl::Function {
+            span: 0..30,
+            doc: None,
+            is_public: false,
+            name: "main".to_string(),
+            generic_params: vec![],
+            params: vec![],
+            return_type: Type::Int,
+            body: vec![Stmt::Return {
+                span: 10..20,
+                value: Some(Expr {
+                    kind: ExprKind::Variable("y".to_string()),
+                    span: 17..18,
+                }),
+            }],
+            source_file: None,
+        }],
+    };
+
+    let result = analyze(&mut program);
+    assert!(result.is_err());
+
+    let errors = result.unwrap_err();
+    assert!(errors
+        .iter()
+        .any(|e| matches!(e, SemanticError::UndefinedVariable { name, .. } if name == "y")));
+}
+
+#[test]
+fn test_type_mismatch_error() {
+    // int main() { var x: int = "hello"; return x; }
+    let mut program = Program {
+        decls: vec![Decl::Function {
+            span: 0..50,
+            doc: None,
+            is_public: false,
+            name: "main".to_string(),
+            generic_params: vec![],
+            params: vec![],
+            return_type: Type::Int,
+            body: vec![
+                Stmt::VarDecl {
+                    span: 10..30,
+                    name: "x".to_string(),
+                    mutable: true,
+                    ty: Some(Type::Int),
+                    value: Expr {
+                        kind: ExprKind::Literal(Literal::String("hello".to_string())),
+                        span: 20..27,
+                    },
+                },
+                Stmt::Return {
+                    span: 30..40,
+                    value: Some(Expr {
+                        kind: ExprKind::Variable("x".to_string()),
+                        span: 37..38,
+                    }),
+                },
+            ],
+            source_file: None,
+        }],
+    };
+
+    let result = analyze(&mut program);
+    assert!(result.is_err());
+
+    let errors = result.unwrap_err();
+    assert!(errors
+        .iter()
+        .any(|e| matches!(e, SemanticError::TypeMismatch { .. })));
+}
+
+#[test]
+fn test_null_safety_error() {
+    // void test() { var s: string = null; }  -- null 赋给非空类型
+    let mut program = Program {
+        decls: vec![Decl::Function {
+            span: 0..50,
+            doc: None,
+            is_public: false,
+            name: "test".to_string(),
+            generic_params: vec![],
+            params: vec![],
+            return_type: Type::Void,
+            body: vec![Stmt::VarDecl {
+                span: 10..40,
+                name: "s".to_string(),
+                mutable: true,
+                ty: Some(Type::String),
+                value: Expr {
+                    kind: ExprKind::Literal(Literal::Null),
+                    span: 30..34,
+                },
+            }],
+            source_file: None,
+        }],
+    };
+
+    let result = analyze(&mut program);
+    assert!(result.is_err());
+
+    let errors = result.unwrap_err();
+    assert!(errors
+        .iter()
+        .any(|e| matches!(e, SemanticError::NullAssignmentToNonNullable { .. })));
+}
+
+#[test]
+fn test_panic_satisfies_missing_return() {
+    // int pick(int x) { if x > 0 { return x } panic("negative") }
+    let mut program = lency_syntax::parser::parse(
+        r#"
+            int pick(int x) {
+                if x > 0 {
+                    return x
+                }
+                panic("negative")
+            }
+        "#,
+    )
+    .expect("parse failed");
+
+    let result = analyze(&mut program);
+    assert!(
+        result.is_ok(),
+        "panic should end the function: {:?}",
+        result.err()
+    );
+}
+
+#[test]
+fn test_missing_return_still_reported_without_panic() {
+    let mut program = lency_syntax::parser::parse(
+        r#"
+            int pick(int x) {
+                if x > 0 {
+                    return x
+                }
+                print("negative")
+            }
+        "#,
+    )
+    .expect("parse failed");
+
+    let errors = analyze(&mut program).unwrap_err();
+    assert!(errors
+        .iter()
+        .any(|e| matches!(e, SemanticError::MissingReturn { .. })));
+}
+
+#[test]
+fn test_untyped_null_var_requires_annotation() {
+    let mut program = lency_syntax::parser::parse(
+        r#"
+            void test() {
+                var x = null
+            }
+        "#,
+    )
+    .expect("parse failed");
+
+    let errors = analyze(&mut program).unwrap_err();
+    assert!(errors
+        .iter()
+        .any(|e| matches!(e, SemanticError::CannotInferNullType { .. })));
+}
+
+#[test]
+fn test_annotated_null_var_is_accepted() {
+    let mut program = lency_syntax::parser::parse(
+        r#"
+            void test() {
+                var x: int? = null
+            }
+        "#,
+    )
+    .expect("parse failed");
+
+    assert!(analyze(&mut program).is_ok());
+}
+
+#[test]
+fn test_repeat_literal_types() {
+    let mut program = lency_syntax::parser::parse(
+        r#"
+            const SIZE = 3
+            int main() {
+                var a: [SIZE]int = [7; SIZE]
+                var n = a[0]
+                var v: Vec<int> = vec![0; n]
+                var bad = [0; n]
+                return a[2]
+            }
+        "#,
+    )
+    .expect("parse failed");
+
+    let errors = analyze(&mut program).unwrap_err();
+    // 数组要求常量长度，Vec 的长度可在运行时计算
+    assert!(matches!(
+        errors.as_slice(),
+        [SemanticError::InvalidArraySize { name, .. }, ..] if name == "n"
+    ));
+    assert!(!errors
+        .iter()
+        .any(|e| matches!(e, SemanticError::TypeMismatch { .. })));
+}
+
+#[test]
+fn test_condition_type_error_points_at_condition() {
+    let source = "void test(int n) {\n    if n + 1 { }\n    while n * 2 { }\n}";
+    let mut program = lency_syntax::parser::parse(source).expect("parse failed");
+
+    let errors = analyze(&mut program).unwrap_err();
+    let underlined: Vec<&str> = errors
+        .iter()
+        .filter_map(|e| match e {
+            SemanticError::TypeMismatch { expected, span, .. } if expected == "bool" => {
+                Some(&source[span.clone()])
+            }
+            _ => None,
+        })
+        .collect();
+    assert_eq!(underlined, vec!["n + 1", "n * 2"]);
+}
@@ -0,0 +1,241 @@
+use crate::*;
+
+#[test]
+fn test_const_cannot_be_reassigned() {
+    let mut program = lency_syntax::parser::parse(
+        r#"
+            const int LIMIT = 3
+
+            void test() {
+                LIMIT = 4
+            }
+        "#,
+    )
+    .expect("parse failed");
+
+    let errors = analyze(&mut program).unwrap_err();
+    assert!(errors
+        .iter()
+        .any(|e| matches!(e, SemanticError::AssignToImmutable { name, .. } if name == "LIMIT")));
+}
+
+#[test]
+fn test_let_binding_cannot_be_reassigned() {
+    let mut program = lency_syntax::parser::parse(
+        r#"
+            void test() {
+                let x = 1
+                x = 2
+            }
+        "#,
+    )
+    .expect("parse failed");
+
+    let errors = analyze(&mut program).unwrap_err();
+    assert!(errors
+        .iter()
+        .any(|e| matches!(e, SemanticError::AssignToImmutable { name, .. } if name == "x")));
+}
+
+#[test]
+fn test_var_binding_can_be_reassigned() {
+    let mut program = lency_syntax::parser::parse(
+        r#"
+            void test() {
+                var x = 1
+                x = 2
+            }
+        "#,
+    )
+    .expect("parse failed");
+
+    let result = analyze(&mut program);
+    assert!(result.is_ok(), "{:?}", result.err());
+}
+
+#[test]
+fn test_redefinition_in_same_scope_is_error() {
+    let mut program = lency_syntax::parser::parse(
+        r#"
+            void test() {
+                var x = 1
+                var x = 2
+            }
+        "#,
+    )
+    .expect("parse failed");
+
+    let errors = analyze(&mut program).unwrap_err();
+    assert!(errors
+        .iter()
+        .any(|e| matches!(e, SemanticError::DuplicateDefinition { name, .. } if name == "x")));
+}
+
+#[test]
+fn test_shadowing_in_nested_scope_warns() {
+    let mut program = lency_syntax::parser::parse(
+        r#"
+            int test(int n) {
+                var x = 1
+                if true {
+                    var x = 2
+                    var n = 3
+                }
+                return x
+            }
+        "#,
+    )
+    .expect("parse failed");
+
+    let result = analyze(&mut program).expect("shadowing should not be an error");
+    let names: Vec<&str> = result
+        .warnings
+        .iter()
+        .filter_map(|w| match w {
+            SemanticWarning::ShadowedVariable { name, .. } => Some(name.as_str()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(names, vec!["x", "n"]);
+}
+
+#[test]
+fn test_constant_condition_warns_about_dead_branch() {
+    let mut program = lency_syntax::parser::parse(
+        r#"
+            int test(int n) {
+                if false { print(1) }
+                if !(1 < 2) || n > 0 { print(2) }
+                if true && 3 != 3 { print(3) } else { print(4) }
+                if true { print(5) }
+                if -1 <= 1 { print(8) } else { print(9) }
+                if n > 0 { print(6) } else { print(7) }
+                return 0
+            }
+
+            int always() {
+                if true { return 1 }
+            }
+        "#,
+    )
+    .expect("parse failed");
+
+    let result = analyze(&mut program).expect("dead branches should not be an error");
+    let dead: Vec<(bool, &str)> = result
+        .warnings
+        .iter()
+        .filter_map(|w| match w {
+            SemanticWarning::DeadBranch { value, branch, .. } => Some((*value, *branch)),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(dead, vec![(false, "then"), (false, "then"), (true, "else")]);
+}
+
+#[test]
+fn test_shadowing_warning_can_be_disabled() {
+    let mut program = lency_syntax::parser::parse(
+        r#"
+            void test() {
+                var x = 1
+                while false {
+                    var x = 2
+                }
+            }
+        "#,
+    )
+    .expect("parse failed");
+
+    let mut resolver = Resolver::new();
+    resolver.set_warn_shadowing(false);
+    let (result, errors) = analyze_with_resolver(&mut program, resolver);
+    assert!(errors.is_empty(), "{:?}", errors);
+    assert!(result.warnings.is_empty());
+}
+
+#[test]
+fn test_loop_break_value_initializes_variable() {
+    let mut program = lency_syntax::parser::parse(
+        r#"
+            int first_square_over(int limit) {
+                var i = 0
+                var found = while i < 100 {
+                    if i * i > limit {
+                        break i * i
+                    }
+                    i = i + 1
+                } else -1
+                return found
+            }
+        "#,
+    )
+    .expect("parse failed");
+
+    let result = analyze(&mut program);
+    assert!(result.is_ok(), "{:?}", result.err());
+}
+
+#[test]
+fn test_loop_break_values_must_agree() {
+    let mut program = lency_syntax::parser::parse(
+        r#"
+            void test(int n) {
+                var i = 0
+                var found = while i < n {
+                    if i == 3 {
+                        break "three"
+                    }
+                    if i == 4 {
+                        break
+                    }
+                    i = i + 1
+                } else 0
+                while true {
+                    break 1
+                }
+            }
+        "#,
+    )
+    .expect("parse failed");
+
+    let errors = analyze(&mut program).unwrap_err();
+    assert!(errors.iter().any(
+            |e| matches!(e, SemanticError::TypeMismatch { expected, found, .. } if expected == "int" && found == "string")
+        ), "{:?}", errors);
+    assert!(errors
+        .iter()
+        .any(|e| matches!(e, SemanticError::MissingBreakValue { .. })));
+    assert!(errors
+        .iter()
+        .any(|e| matches!(e, SemanticError::BreakValueOutsideLoopValue { .. })));
+}
+
+#[test]
+fn test_local_use_before_definition() {
+    let mut program = lency_syntax::parser::parse(
+        r#"
+            int main() {
+                var y = x
+                var x = 1
+                if true {
+                    print(w)
+                }
+                return y + later(x)
+            }
+            int later(int v) { return v }
+        "#,
+    )
+    .expect("parse failed");
+
+    let errors = analyze(&mut program).unwrap_err();
+    assert!(errors
+        .iter()
+        .any(|e| matches!(e, SemanticError::UseBeforeDefinition { name, .. } if name == "x")));
+    // 从未声明的变量仍报告未定义；前向引用的函数不报错
+    assert!(errors
+        .iter()
+        .any(|e| matches!(e, SemanticError::UndefinedVariable { name, .. } if name == "w")));
+    assert!(!errors
+        .iter()
+        .any(|e| matches!(e, SemanticError::UndefinedFunction { .. })));
+}
@@ -0,0 +1,167 @@
+use crate::*;
+
+#[test]
+fn test_extern_c_by_value_structs() {
+    let mut program = lency_syntax::parser::parse(
+        r#"
+            extern struct Rect { int w int h }
+            extern struct Bag { Vec<int> items }
+            struct Point { int x int y }
+            newtype Handle = int
+            extern "C" int rect_area(Rect r);
+            extern "C" Rect rect_scale(Rect r, Handle k);
+            extern "C" int point_x(Point p);
+            extern "Rust" int answer();
+            extern int legacy(Point p);
+        "#,
+    )
+    .expect("parse failed");
+
+    let errors = analyze(&mut program).unwrap_err();
+    let messages: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+    assert_eq!(
+        messages,
+        vec![
+            "type 'Vec<int>' cannot be passed by value across the C ABI",
+            "type 'Point' cannot be passed by value across the C ABI",
+            "unsupported ABI \"Rust\"",
+        ]
+    );
+}
+
+#[test]
+fn test_variadic_extern_arguments() {
+    let mut program = lency_syntax::parser::parse(
+        r#"
+            extern int printf(string, ...);
+            extern int abs(int x);
+            struct Point { int x int y }
+            int main() {
+                printf("%d %s %f %d\n", 1, "a", 2.5, true)
+                printf("%d", Point { x: 1, y: 2 })
+                printf()
+                return abs(1, 2)
+            }
+        "#,
+    )
+    .expect("parse failed");
+
+    let errors = analyze(&mut program).unwrap_err();
+    let messages: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+    assert_eq!(
+        messages,
+        vec![
+            "type 'Point' cannot be passed as a variadic argument",
+            "function 'printf' expects 1 arguments, but got 0",
+            "function 'abs' expects 1 arguments, but got 2",
+        ]
+    );
+}
+
+#[test]
+fn test_fmt_macro_checks_placeholder_count() {
+    let mut program = lency_syntax::parser::parse(
+        r#"
+            struct Point { int x int y }
+            string ok(int a, float b, bool c, string d) {
+                return fmt!("{} {} {} {}", a, b, c, d) + fmt!("plain")
+            }
+            string too_few() { return fmt!("{} + {} = {}", 1, 2) }
+            string too_many() { return fmt!("{}", 1, 2) }
+            string bad_arg() { return fmt!("{}", Point { x: 1, y: 2 }) }
+        "#,
+    )
+    .expect("parse failed");
+
+    let errors = analyze(&mut program).unwrap_err();
+    let messages: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+    assert_eq!(
+        messages,
+        vec![
+            "format string has 3 placeholders, but 2 arguments were given",
+            "format string has 1 placeholders, but 2 arguments were given",
+            "type mismatch: expected 'string, int, float or bool', found 'Point'",
+        ]
+    );
+}
+
+#[test]
+fn test_method_argument_count_mismatch_names_receiver() {
+    let mut program = lency_syntax::parser::parse(
+        r#"
+            struct Counter { int n }
+            impl Counter {
+                int add(int a, int b) { return this.n + a + b }
+            }
+            void main() {
+                var v = vec![1, 2]
+                v.push(1, 2)
+                var c = Counter { n: 1 }
+                var r = c.add(1)
+            }
+        "#,
+    )
+    .expect("parse failed");
+
+    let errors = analyze(&mut program).unwrap_err();
+    let messages: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+    assert_eq!(
+            messages,
+            vec![
+                "method 'Vec<int>.push' expects 1 arguments, but got 2",
+                "method 'Counter.add' expects 2 arguments, but got 1 (declared as 'int add(int a, int b)')",
+            ]
+        );
+}
+
+#[test]
+fn test_function_name_as_value_has_function_type() {
+    let mut program = lency_syntax::parser::parse(
+        r#"
+            int descending(int a, int b) { return b - a }
+            bool less(int a, int b) { return a < b }
+            void main() {
+                var v = vec![3, 1, 2]
+                v.sort_by(descending)
+                var cmp: int(int, int) = descending
+                v.sort_by(less)
+            }
+        "#,
+    )
+    .expect("parse failed");
+
+    let errors = analyze(&mut program).unwrap_err();
+    let messages: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+    assert_eq!(
+        messages,
+        vec!["type mismatch: expected 'int(int, int)', found 'bool(int, int)'"]
+    );
+}
+
+#[test]
+fn test_primitive_to_string_method() {
+    let mut program = lency_syntax::parser::parse(
+        r#"
+            string show(int n, float f, bool b) {
+                var s: string = n.to_string() + f.to_string()
+                return s + b.to_string()
+            }
+            string bad(int n) {
+                return n.to_string(10)
+            }
+        "#,
+    )
+    .expect("parse failed");
+
+    let errors = analyze(&mut program).unwrap_err();
+    assert_eq!(errors.len(), 1, "{:?}", errors);
+    assert!(matches!(
+        &errors[0],
+        SemanticError::MethodArgumentCountMismatch { method, expected: 0, found: 1, .. }
+            if method == "to_string"
+    ));
+    assert_eq!(
+        errors[0].to_string(),
+        "method 'int.to_string' expects 0 arguments, but got 1"
+    );
+}
@@ -0,0 +1,156 @@
+use crate::*;
+
+#[test]
+fn test_newtype_is_distinct_from_inner_type() {
+    let mut program = lency_syntax::parser::parse(
+        r#"
+            UserId lookup(UserId id) { return id }
+
+            newtype UserId = int
+
+            void test() {
+                var wrapped: UserId = cast<UserId>(42)
+                var raw: int = cast<int>(wrapped)
+                var same = lookup(wrapped) == wrapped
+                var bad: UserId = 42
+                var leaked: int = wrapped
+                var sum = wrapped + 1
+            }
+        "#,
+    )
+    .expect("parse failed");
+
+    let errors = analyze(&mut program).unwrap_err();
+    let mismatches: Vec<(&str, &str)> = errors
+        .iter()
+        .filter_map(|e| match e {
+            SemanticError::TypeMismatch {
+                expected, found, ..
+            } => Some((expected.as_str(), found.as_str())),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        mismatches,
+        vec![("UserId", "int"), ("int", "UserId")],
+        "{:?}",
+        errors
+    );
+    assert!(errors
+        .iter()
+        .any(|e| matches!(e, SemanticError::InvalidBinaryOp { .. })));
+    assert_eq!(errors.len(), 3, "{:?}", errors);
+}
+
+#[test]
+fn test_cast_only_between_newtype_and_inner_type() {
+    let mut program = lency_syntax::parser::parse(
+        r#"
+            newtype UserId = int
+            newtype Name = string
+
+            void test() {
+                var a = cast<UserId>("alice")
+                var b = cast<Name>(cast<UserId>(1))
+            }
+        "#,
+    )
+    .expect("parse failed");
+
+    let errors = analyze(&mut program).unwrap_err();
+    let casts: Vec<(&str, &str)> = errors
+        .iter()
+        .filter_map(|e| match e {
+            SemanticError::InvalidCast { from, to, .. } => Some((from.as_str(), to.as_str())),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(casts, vec![("string", "UserId"), ("UserId", "Name")]);
+}
+
+#[test]
+fn test_as_casts_between_numeric_types() {
+    let mut program = lency_syntax::parser::parse(
+        r#"
+            int truncate(float x) { return x as int }
+            float widen(int n) { return n as float }
+            int code(int c) { return c as int }
+        "#,
+    )
+    .expect("parse failed");
+
+    let result = analyze(&mut program);
+    assert!(result.is_ok(), "{:?}", result.err());
+}
+
+#[test]
+fn test_as_rejects_non_numeric_casts() {
+    let mut program = lency_syntax::parser::parse(
+        r#"
+            void test(string s, bool b) {
+                var a = s as int
+                var c = b as int
+                var d = 1.5 as string
+            }
+        "#,
+    )
+    .expect("parse failed");
+
+    let errors = analyze(&mut program).unwrap_err();
+    let casts: Vec<(&str, &str)> = errors
+        .iter()
+        .filter_map(|e| match e {
+            SemanticError::InvalidCast { from, to, .. } => Some((from.as_str(), to.as_str())),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        casts,
+        vec![("string", "int"), ("bool", "int"), ("float", "string")]
+    );
+}
+
+#[test]
+fn test_sized_int_variable_and_widening() {
+    let mut program = lency_syntax::parser::parse(
+        r#"
+            i64 total(i32 step) {
+                var small: i32 = 40
+                small = small + step
+                var wide: i64 = small
+                wide = small * 2
+                return wide
+            }
+        "#,
+    )
+    .expect("parse failed");
+
+    let result = analyze(&mut program);
+    assert!(result.is_ok(), "{:?}", result.err());
+}
+
+#[test]
+fn test_sized_int_rejects_implicit_narrowing() {
+    let mut program = lency_syntax::parser::parse(
+        r#"
+            void test(int n) {
+                var a: i32 = n
+                var b: i8 = 300
+                var c: i16 = n as i16
+            }
+        "#,
+    )
+    .expect("parse failed");
+
+    let errors = analyze(&mut program).unwrap_err();
+    let mismatches: Vec<(&str, &str)> = errors
+        .iter()
+        .filter_map(|e| match e {
+            SemanticError::TypeMismatch {
+                expected, found, ..
+            } => Some((expected.as_str(), found.as_str())),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(mismatches, vec![("i32", "int"), ("i8", "int")]);
+}
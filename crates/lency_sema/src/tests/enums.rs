@@ -0,0 +1,82 @@
+use crate::*;
+
+#[test]
+fn test_enum_duplicate_discriminant_is_error() {
+    let mut program = lency_syntax::parser::parse("enum Color { Red = 1, Green, Blue = 2 }")
+        .expect("parse failed");
+
+    let errors = analyze(&mut program).unwrap_err();
+    assert!(errors.iter().any(|e| matches!(
+        e,
+        SemanticError::DuplicateDiscriminant { variant, value: 2, .. } if variant == "Blue"
+    )));
+}
+
+#[test]
+fn test_enum_discriminant_method_returns_int() {
+    let mut program = lency_syntax::parser::parse(
+        r#"
+            enum Color { Red = 1, Green = 2, Blue = 4 }
+            int tag(Color c) {
+                return c.discriminant()
+            }
+        "#,
+    )
+    .expect("parse failed");
+
+    let result = analyze(&mut program);
+    assert!(result.is_ok(), "{:?}", result.err());
+}
+
+#[test]
+fn test_unit_enum_int_conversion() {
+    let mut program = lency_syntax::parser::parse(
+        r#"
+            enum Color { Red = 1, Green = 2, Blue = 4 }
+            Color? roundtrip(Color c) {
+                var tag = c.as_int()
+                return Color.from_int(tag)
+            }
+        "#,
+    )
+    .expect("parse failed");
+
+    let result = analyze(&mut program);
+    assert!(result.is_ok(), "{:?}", result.err());
+}
+
+#[test]
+fn test_from_int_requires_int_argument() {
+    let mut program = lency_syntax::parser::parse(
+        r#"
+            enum Color { Red, Green }
+            void test() {
+                var c = Color.from_int("red")
+            }
+        "#,
+    )
+    .expect("parse failed");
+
+    let errors = analyze(&mut program).unwrap_err();
+    assert!(errors
+        .iter()
+        .any(|e| matches!(e, SemanticError::TypeMismatch { expected, .. } if expected == "int")));
+}
+
+#[test]
+fn test_int_conversion_rejected_for_payload_enum() {
+    let mut program = lency_syntax::parser::parse(
+        r#"
+            enum Shape { Circle(int), Empty }
+            int test(Shape s) {
+                return s.as_int()
+            }
+        "#,
+    )
+    .expect("parse failed");
+
+    let errors = analyze(&mut program).unwrap_err();
+    assert!(errors
+        .iter()
+        .any(|e| matches!(e, SemanticError::UndefinedMethod { method, .. } if method == "as_int")));
+}
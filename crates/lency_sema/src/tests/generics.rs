@@ -0,0 +1,216 @@
+use crate::*;
+use lency_syntax::ast::*;
+
+/// 取出函数 `name` 中 return 语句所调用泛型函数的类型实参
+fn returned_call_type_args(program: &Program, name: &str) -> Vec<Type> {
+    let body = program
+        .decls
+        .iter()
+        .find_map(|d| match d {
+            Decl::Function { name: n, body, .. } if n == name => Some(body),
+            _ => None,
+        })
+        .expect("function not found");
+    let Some(Stmt::Return {
+        value:
+            Some(Expr {
+                kind: ExprKind::Call { callee, .. },
+                ..
+            }),
+        ..
+    }) = body.last()
+    else {
+        panic!("expected `return call(...)`");
+    };
+    match &callee.kind {
+        ExprKind::GenericInstantiation { args, .. } => args.clone(),
+        other => panic!("callee was not instantiated: {:?}", other),
+    }
+}
+
+#[test]
+fn test_generic_call_infers_type_arg_from_argument() {
+    let mut program = lency_syntax::parser::parse(
+        r#"
+            T id<T>(T x) { return x }
+            int one() { return id(5) }
+            string name() { return id("lency") }
+        "#,
+    )
+    .expect("parse failed");
+
+    let result = analyze(&mut program);
+    assert!(result.is_ok(), "{:?}", result.err());
+    assert_eq!(returned_call_type_args(&program, "one"), vec![Type::Int]);
+    assert_eq!(
+        returned_call_type_args(&program, "name"),
+        vec![Type::String]
+    );
+}
+
+#[test]
+fn test_generic_call_infers_type_arg_from_agreeing_arguments() {
+    let mut program = lency_syntax::parser::parse(
+        r#"
+            T first<T>(T a, T b) { return a }
+            bool pick() { return first(true, false) }
+        "#,
+    )
+    .expect("parse failed");
+
+    let result = analyze(&mut program);
+    assert!(result.is_ok(), "{:?}", result.err());
+    assert_eq!(returned_call_type_args(&program, "pick"), vec![Type::Bool]);
+}
+
+#[test]
+fn test_generic_call_with_conflicting_arguments_is_error() {
+    let mut program = lency_syntax::parser::parse(
+        r#"
+            T first<T>(T a, T b) { return a }
+            int pick() { return first(1, "two") }
+        "#,
+    )
+    .expect("parse failed");
+
+    let errors = analyze(&mut program).unwrap_err();
+    assert!(errors.iter().any(|e| matches!(
+            e,
+            SemanticError::TypeMismatch { expected, found, .. } if expected == "int" && found == "string"
+        )));
+}
+
+#[test]
+fn test_generic_call_checks_non_generic_parameter() {
+    let mut program = lency_syntax::parser::parse(
+        r#"
+            int f<T>(T a, int b) { return b }
+            int main() { return f(1, "str") }
+        "#,
+    )
+    .expect("parse failed");
+
+    let errors = analyze(&mut program).unwrap_err();
+    assert!(errors.iter().any(|e| matches!(
+            e,
+            SemanticError::TypeMismatch { expected, found, .. } if expected == "int" && found == "string"
+        )));
+}
+
+#[test]
+fn test_generic_call_rejects_null_for_non_nullable_type_arg() {
+    let mut program = lency_syntax::parser::parse(
+        r#"
+            T pick<T>(T a, T b) { return b }
+            int main() { return pick(null, 3) }
+        "#,
+    )
+    .expect("parse failed");
+
+    let errors = analyze(&mut program).unwrap_err();
+    assert!(errors.iter().any(|e| matches!(
+        e,
+        SemanticError::TypeMismatch { expected, .. } if expected == "int"
+    )));
+}
+
+#[test]
+fn test_generic_call_rejects_mismatched_shape() {
+    let mut program = lency_syntax::parser::parse(
+        r#"
+            int count<T>(Vec<T> items) { return items.len() }
+            int main() { return count(3) }
+        "#,
+    )
+    .expect("parse failed");
+
+    let errors = analyze(&mut program).unwrap_err();
+    assert!(errors.iter().any(|e| matches!(
+            e,
+            SemanticError::TypeMismatch { expected, found, .. } if expected == "Vec<T>" && found == "int"
+        )));
+}
+
+#[test]
+fn test_type_missing_one_of_two_bounds_is_rejected() {
+    let mut program = lency_syntax::parser::parse(
+        r#"
+            trait Printable { void show(); }
+            trait Comparable { int compare(int other); }
+            struct Both { int v }
+            struct OnlyPrint { int v }
+            impl Printable for Both { void show() { } }
+            impl Comparable for Both { int compare(int other) { return this.v - other } }
+            impl Printable for OnlyPrint { void show() { } }
+            int use_it<T: Printable + Comparable>(T item) {
+                item.show()
+                return item.compare(1)
+            }
+            int ok() { return use_it(Both { v: 3 }) }
+            int bad() { return use_it(OnlyPrint { v: 4 }) }
+        "#,
+    )
+    .expect("parse failed");
+
+    let errors = analyze(&mut program).unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(
+        &errors[0],
+        SemanticError::TraitBoundNotSatisfied { ty, trait_name, param, .. }
+            if ty == "OnlyPrint" && trait_name == "Comparable" && param == "T"
+    ));
+}
+
+#[test]
+fn test_self_type_resolves_to_impl_target() {
+    let mut program = lency_syntax::parser::parse(
+        r#"
+            struct Point { int x }
+            impl Point {
+                This with_x(int v) {
+                    this.x = v
+                    return this
+                }
+            }
+            struct Box<T> { T value }
+            impl<T> Box<T> {
+                This put(T v) {
+                    this.value = v
+                    return this
+                }
+            }
+            int main() {
+                var p = Point { x: 0 }.with_x(1)
+                return p.x
+            }
+        "#,
+    )
+    .expect("parse failed");
+
+    let result = analyze(&mut program).expect("analysis failed");
+    let Some(Symbol::Struct(point)) = result.scopes.lookup("Point") else {
+        panic!("Point not found");
+    };
+    assert_eq!(
+        point.get_method("with_x").unwrap().return_type,
+        Type::Struct("Point".to_string())
+    );
+    let Some(Symbol::Struct(boxed)) = result.scopes.lookup("Box") else {
+        panic!("Box not found");
+    };
+    assert_eq!(
+        boxed.get_method("put").unwrap().return_type,
+        Type::Generic("Box".to_string(), vec![Type::GenericParam("T".to_string())])
+    );
+}
+
+#[test]
+fn test_self_type_outside_impl_is_error() {
+    let mut program =
+        lency_syntax::parser::parse("This make() { return 0 }").expect("parse failed");
+
+    let errors = analyze(&mut program).unwrap_err();
+    assert!(errors
+        .iter()
+        .any(|e| matches!(e, SemanticError::SelfTypeOutsideImpl { .. })));
+}
@@ -0,0 +1,147 @@
+use crate::*;
+use lency_syntax::ast::Decl;
+
+#[test]
+fn test_circular_import_is_reported() {
+    let mut program = lency_syntax::parser::parse(
+        "import tests.example.modules.cycle_a\nint main() { return 0 }",
+    )
+    .expect("parse failed");
+
+    let mut resolver = Resolver::new();
+    resolver.set_root_dir(std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../.."));
+    let (_, errors) = analyze_with_resolver(&mut program, resolver);
+    let cycle = errors
+        .iter()
+        .find_map(|e| match e {
+            SemanticError::CircularImport { cycle, .. } => Some(cycle),
+            _ => None,
+        })
+        .expect("expected a circular import error");
+    let names: Vec<_> = cycle
+        .iter()
+        .map(|p| p.file_name().unwrap().to_string_lossy().into_owned())
+        .collect();
+    assert_eq!(names, vec!["cycle_a.lcy", "cycle_b.lcy", "cycle_a.lcy"]);
+}
+
+#[test]
+fn test_private_struct_is_not_visible_across_modules() {
+    let cases = [
+        ("var v = Visible { value: 1 }\n return v.value", None),
+        (
+            "var h = Hidden { value: 1 }\n return h.value",
+            Some("Hidden"),
+        ),
+        ("Hidden? h = null\n return 0", Some("Hidden")),
+    ];
+    for (body, private) in cases {
+        let source = format!(
+            "import tests.example.modules.visibility\nint main() {{ {} }}",
+            body
+        );
+        let mut program = lency_syntax::parser::parse(&source).expect("parse failed");
+        let mut resolver = Resolver::new();
+        resolver.set_root_dir(std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../.."));
+        let (_, errors) = analyze_with_resolver(&mut program, resolver);
+        let found = errors.iter().find_map(|e| match e {
+            SemanticError::PrivateSymbol { name, .. } => Some(name.as_str()),
+            _ => None,
+        });
+        assert_eq!(found, private, "{}: {:?}", body, errors);
+    }
+}
+
+#[test]
+fn test_unchanged_import_is_parsed_once() {
+    let root = std::env::temp_dir().join(format!("lency_parse_cache_{}", std::process::id()));
+    std::fs::create_dir_all(&root).unwrap();
+    let module = root.join("cached_util.lcy");
+    std::fs::write(&module, "pub int twice(int x) { return x * 2 }").unwrap();
+
+    for _ in 0..2 {
+        let mut program =
+            lency_syntax::parser::parse("import cached_util\nint main() { return twice(21) }")
+                .expect("parse failed");
+        let mut resolver = Resolver::new();
+        resolver.set_root_dir(root.clone());
+        let (_, errors) = analyze_with_resolver(&mut program, resolver);
+        assert!(errors.is_empty(), "{:?}", errors);
+    }
+    assert_eq!(resolver::parse_count(&module), 1);
+
+    clear_import_cache();
+    assert_eq!(resolver::parse_count(&module), 0);
+
+    std::fs::remove_dir_all(&root).ok();
+}
+
+#[test]
+fn test_mutually_referencing_structs_in_reverse_order() {
+    let mut program = lency_syntax::parser::parse(
+        r#"
+            struct B { A? a }
+            struct A { B? b  int v }
+            int main() {
+                var b = B { a: A { b: null, v: 1 } }
+                return b.a?.v ?? 0
+            }
+            "#,
+    )
+    .expect("parse failed");
+    assert!(analyze(&mut program).is_ok());
+
+    // 跨文件：拆分模块互相导入，字段类型在所有模块的声明收集完之后才校验
+    let root = std::env::temp_dir().join(format!("lency_forward_ref_{}", std::process::id()));
+    std::fs::create_dir_all(root.join("shapes")).unwrap();
+    std::fs::write(
+        root.join("shapes.lcy"),
+        "import shapes.more\npub struct A { B? b  int v }",
+    )
+    .unwrap();
+    std::fs::write(
+        root.join("shapes/more.lcy"),
+        "import shapes\npub struct B { A? a }",
+    )
+    .unwrap();
+
+    for import in ["shapes", "shapes.more"] {
+        let source = format!(
+                "import {}\nint main() {{ var b = B {{ a: A {{ b: null, v: 1 }} }}\n return b.a?.v ?? 0 }}",
+                import
+            );
+        let mut program = lency_syntax::parser::parse(&source).expect("parse failed");
+        let mut resolver = Resolver::new();
+        resolver.set_root_dir(root.clone());
+        let (_, errors) = analyze_with_resolver(&mut program, resolver);
+        assert!(errors.is_empty(), "import {}: {:?}", import, errors);
+    }
+
+    std::fs::remove_dir_all(&root).ok();
+}
+
+#[test]
+fn test_imported_functions_record_source_file() {
+    let mut program = lency_syntax::parser::parse(
+        "import tests.example.modules.visibility\nint main() { return visible_value() }",
+    )
+    .expect("parse failed");
+    let mut resolver = Resolver::new();
+    resolver.set_root_dir(std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../.."));
+    let (_, errors) = analyze_with_resolver(&mut program, resolver);
+    assert!(errors.is_empty(), "{:?}", errors);
+
+    let source_of = |wanted: &str| {
+        program.decls.iter().find_map(|decl| match decl {
+            Decl::Function {
+                name, source_file, ..
+            } if name == wanted => Some(source_file.clone()),
+            _ => None,
+        })
+    };
+    // 导入函数的路径相对于项目根目录，本文件中的函数不记录
+    let expected = Some(Some("tests/example/modules/visibility.lcy".to_string()));
+    assert_eq!(source_of("visible_value"), expected);
+    assert_eq!(source_of("hidden_value"), expected);
+    assert_eq!(source_of("main"), Some(None));
+}
@@ -0,0 +1,218 @@
+use crate::*;
+
+#[test]
+fn test_match_guard_sees_pattern_binding() {
+    let mut program = lency_syntax::parser::parse(
+        r#"
+            string sign(int x) {
+                return match x {
+                    case n if n > 0 => "positive",
+                    case 0 => "zero",
+                    case _ => "negative"
+                }
+            }
+        "#,
+    )
+    .expect("parse failed");
+
+    let result = analyze(&mut program);
+    assert!(result.is_ok(), "{:?}", result.err());
+}
+
+#[test]
+fn test_match_guard_must_be_bool() {
+    let mut program = lency_syntax::parser::parse(
+        r#"
+            int test(int x) {
+                return match x {
+                    case n if n + 1 => 1,
+                    case _ => 0
+                }
+            }
+        "#,
+    )
+    .expect("parse failed");
+
+    let errors = analyze(&mut program).unwrap_err();
+    assert!(errors.iter().any(
+            |e| matches!(e, SemanticError::TypeMismatch { expected, found, .. } if expected == "bool" && found == "int")
+        ), "{:?}", errors);
+}
+
+#[test]
+fn test_guarded_arms_do_not_count_as_exhaustive() {
+    let mut program = lency_syntax::parser::parse(
+        r#"
+            enum Shape { Circle(int), Empty }
+            int test(Shape s) {
+                return match s {
+                    case Circle(r) if r > 10 => 2,
+                    case Shape.Empty => 0
+                }
+            }
+        "#,
+    )
+    .expect("parse failed");
+
+    let errors = analyze(&mut program).unwrap_err();
+    assert!(errors.iter().any(|e| matches!(
+            e,
+            SemanticError::PatternNotExhaustive { missing_variants, .. } if missing_variants == &["Circle"]
+        )), "{:?}", errors);
+}
+
+#[test]
+fn test_bool_match_exhaustiveness() {
+    let mut program = lency_syntax::parser::parse(
+        r#"
+            int both(bool b) {
+                return match b {
+                    case true => 1,
+                    case false => 0
+                }
+            }
+            int either(bool b) {
+                return match b {
+                    case true | false => 1
+                }
+            }
+        "#,
+    )
+    .expect("parse failed");
+    assert!(analyze(&mut program).is_ok());
+
+    // 缺少一个分支（带守卫的分支不算覆盖）
+    let mut program = lency_syntax::parser::parse(
+        r#"
+            int only_true(bool b, int n) {
+                return match b {
+                    case true => 1,
+                    case false if n > 0 => 0
+                }
+            }
+        "#,
+    )
+    .expect("parse failed");
+    let errors = analyze(&mut program).unwrap_err();
+    assert!(errors.iter().any(|e| matches!(
+            e,
+            SemanticError::PatternNotExhaustive { missing_variants, .. } if missing_variants == &["false"]
+        )), "{:?}", errors);
+}
+
+#[test]
+fn test_match_arm_after_catch_all_is_unreachable() {
+    let source = r#"
+            enum Light { Red, Yellow, Green }
+            int code(int x) {
+                return match x {
+                    case _ => 0,
+                    case 1 => 1
+                }
+            }
+            int light(Light l) {
+                return match l {
+                    case Light.Red | Light.Yellow => 1,
+                    case Light.Yellow => 2,
+                    case Light.Green => 3
+                }
+            }
+            int guarded(int x) {
+                return match x {
+                    case n if n > 0 => 1,
+                    case 5 => 5,
+                    case _ => 0
+                }
+            }
+        "#;
+    let mut program = lency_syntax::parser::parse(source).expect("parse failed");
+
+    let errors = analyze(&mut program).unwrap_err();
+    let unreachable: Vec<&str> = errors
+        .iter()
+        .filter_map(|e| match e {
+            SemanticError::UnreachablePattern { span } => Some(&source[span.clone()]),
+            _ => None,
+        })
+        .collect();
+    // 带守卫的分支可能落空，其后的分支仍然可达
+    assert_eq!(
+        unreachable,
+        vec!["case 1 => 1", "case Light.Yellow => 2"],
+        "{:?}",
+        errors
+    );
+}
+
+#[test]
+fn test_match_range_and_or_patterns() {
+    let mut program = lency_syntax::parser::parse(
+        r#"
+            enum Light { Red, Yellow, Green }
+            string status(int code) {
+                return match code {
+                    case 200..=299 => "ok",
+                    case 400 | 404 => "client error",
+                    case _ => "other"
+                }
+            }
+            bool stop(Light l) {
+                return match l {
+                    case Light.Red | Light.Yellow => true,
+                    case Light.Green => false
+                }
+            }
+        "#,
+    )
+    .expect("parse failed");
+
+    let result = analyze(&mut program);
+    assert!(result.is_ok(), "{:?}", result.err());
+}
+
+#[test]
+fn test_invalid_range_and_or_patterns() {
+    let mut program = lency_syntax::parser::parse(
+        r#"
+            enum Shape { Circle(int), Empty }
+            int empty_range(int x) {
+                return match x {
+                    case 10..=1 => 1,
+                    case _ => 0
+                }
+            }
+            int string_range(string s) {
+                return match s {
+                    case "a"..="z" => 1,
+                    case _ => 0
+                }
+            }
+            int or_binding(Shape s) {
+                return match s {
+                    case Shape.Circle(r) | Shape.Empty => 1
+                }
+            }
+        "#,
+    )
+    .expect("parse failed");
+
+    let errors = analyze(&mut program).unwrap_err();
+    assert!(errors.iter().any(|e| matches!(
+        e,
+        SemanticError::EmptyRangePattern {
+            start: 10,
+            end: 1,
+            ..
+        }
+    )));
+    assert!(errors.iter().any(
+            |e| matches!(e, SemanticError::TypeMismatch { expected, found, .. } if expected == "int" && found == "string")
+        ), "{:?}", errors);
+    assert!(
+        errors
+            .iter()
+            .any(|e| matches!(e, SemanticError::OrPatternBinding { name, .. } if name == "r")),
+        "{:?}",
+        errors
+    );
+}
@@ -0,0 +1,15 @@
+//! 语义分析测试：按主题拆分
+
+mod basics;
+mod bindings;
+mod calls;
+mod casts;
+mod enums;
+mod generics;
+mod imports;
+mod matching;
+mod nullable;
+mod operators;
+mod results;
+mod structs;
+mod traits;
@@ -0,0 +1,161 @@
+use crate::*;
+
+#[test]
+fn test_if_let_binds_unwrapped_nullable() {
+    let mut program = lency_syntax::parser::parse(
+        r#"
+            int test(string? s) {
+                if let v = s {
+                    return len(v)
+                }
+                return 0
+            }
+        "#,
+    )
+    .expect("parse failed");
+
+    let result = analyze(&mut program);
+    assert!(result.is_ok(), "{:?}", result.err());
+}
+
+#[test]
+fn test_if_let_binds_enum_variant_fields() {
+    let mut program = lency_syntax::parser::parse(
+        r#"
+            enum Shape {
+                Circle(int),
+                Rect(int, int)
+            }
+
+            int area(Shape s) {
+                if let Shape.Rect(w, h) = s {
+                    return w * h
+                } else {
+                    return 0
+                }
+            }
+        "#,
+    )
+    .expect("parse failed");
+
+    let result = analyze(&mut program);
+    assert!(result.is_ok(), "{:?}", result.err());
+}
+
+#[test]
+fn test_if_let_binding_not_visible_after_block() {
+    let mut program = lency_syntax::parser::parse(
+        r#"
+            int test(string? s) {
+                if let v = s {
+                    print(v)
+                }
+                return len(v)
+            }
+        "#,
+    )
+    .expect("parse failed");
+
+    let errors = analyze(&mut program).unwrap_err();
+    assert!(errors
+        .iter()
+        .any(|e| matches!(e, SemanticError::UndefinedVariable { name, .. } if name == "v")));
+}
+
+#[test]
+fn test_nullable_value_or_and_map() {
+    let mut program = lency_syntax::parser::parse(
+        r#"
+            string? find(int k) {
+                if k > 0 { return "hit" }
+                return null
+            }
+            int main() {
+                var name = find(0).value_or("none")
+                var size: int? = find(1).map(|string s| => len(s))
+                return size.value_or(0)
+            }
+        "#,
+    )
+    .expect("parse failed");
+
+    let result = analyze(&mut program);
+    assert!(result.is_ok(), "{:?}", result.err());
+}
+
+#[test]
+fn test_nullable_value_or_default_must_match_inner_type() {
+    let mut program = lency_syntax::parser::parse(
+        r#"
+            string? find(int k) { return null }
+            int main() {
+                var name = find(0).value_or(0)
+                return 0
+            }
+        "#,
+    )
+    .expect("parse failed");
+
+    let errors = analyze(&mut program).unwrap_err();
+    assert!(errors.iter().any(|e| matches!(
+        e,
+        SemanticError::TypeMismatch { expected, found, .. }
+            if expected == "string" && found == "int"
+    )));
+}
+
+#[test]
+fn test_if_let_rejects_non_nullable_value() {
+    let mut program = lency_syntax::parser::parse(
+        r#"
+            void test() {
+                var n = 5
+                if let v = n {
+                    print(v)
+                }
+            }
+        "#,
+    )
+    .expect("parse failed");
+
+    let errors = analyze(&mut program).unwrap_err();
+    assert!(errors.iter().any(|e| matches!(
+        e,
+        SemanticError::TypeMismatch { expected, .. } if expected == "nullable or enum type"
+    )));
+}
+
+#[test]
+fn test_nullable_bool_condition_requires_check() {
+    let mut program = lency_syntax::parser::parse(
+        r#"
+            void test(bool? flag) {
+                if flag {
+                    print("on")
+                }
+                while flag {
+                    print("loop")
+                }
+                var both = flag && true
+                var neither = !flag
+                if flag ?? false {
+                    print("checked")
+                }
+                if flag.value_or(false) {
+                    print("defaulted")
+                }
+            }
+        "#,
+    )
+    .expect("parse failed");
+
+    let errors = analyze(&mut program).unwrap_err();
+    let nullable_conditions = errors
+            .iter()
+            .filter(|e| {
+                matches!(e, SemanticError::NullableConditionRequiresCheck { ty, .. } if ty == "bool?")
+            })
+            .count();
+    assert_eq!(nullable_conditions, 4, "{:?}", errors);
+    assert_eq!(errors.len(), 4, "{:?}", errors);
+}
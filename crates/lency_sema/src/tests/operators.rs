@@ -0,0 +1,163 @@
+use crate::*;
+use lency_syntax::ast::*;
+
+#[test]
+fn test_operator_overload_lowers_to_method_call() -> Result<(), String> {
+    let mut program = lency_syntax::parser::parse(
+        r#"
+            trait Add<T> {
+                T add(T other);
+            }
+            struct Vector {
+                int x
+            }
+            impl Add<Vector> for Vector {
+                Vector add(Vector other) {
+                    return Vector { x: this.x + other.x }
+                }
+            }
+            int main() {
+                var a = Vector { x: 1 }
+                var c = a + a
+                return c.x
+            }
+        "#,
+    )
+    .map_err(|e| format!("parse failed: {:?}", e))?;
+
+    analyze(&mut program).map_err(|e| format!("analysis failed: {:?}", e))?;
+
+    // a + a 被降级为 a.add(a)
+    let Some(Decl::Function { body, .. }) = program
+        .decls
+        .iter()
+        .find(|d| matches!(d, Decl::Function { name, .. } if name == "main"))
+    else {
+        return Err("main not found".into());
+    };
+    let Some(Stmt::VarDecl { value, .. }) = body.get(1) else {
+        return Err("expected var decl".into());
+    };
+    assert!(matches!(
+        &value.kind,
+        ExprKind::Call { callee, .. }
+            if matches!(&callee.kind, ExprKind::Get { name, .. } if name == "add")
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_operator_without_trait_impl_is_rejected() -> Result<(), String> {
+    // 有 add 方法但没有实现 Add trait，不能使用 +
+    let mut program = lency_syntax::parser::parse(
+        r#"
+            struct Vector {
+                int x
+            }
+            impl Vector {
+                Vector add(Vector other) {
+                    return Vector { x: this.x + other.x }
+                }
+            }
+            int main() {
+                var a = Vector { x: 1 }
+                var c = a + a
+                return 0
+            }
+        "#,
+    )
+    .map_err(|e| format!("parse failed: {:?}", e))?;
+
+    let Err(errors) = analyze(&mut program) else {
+        return Err("expected a semantic error".into());
+    };
+    assert!(errors
+        .iter()
+        .any(|e| matches!(e, SemanticError::InvalidBinaryOp { .. })));
+    Ok(())
+}
+
+const INDEX_OVERLOAD_PRELUDE: &str = r#"
+        trait Index<K, V> {
+            V index(K key);
+        }
+        struct Table {
+            int base
+        }
+        impl Index<string, int> for Table {
+            int index(string key) {
+                return this.base + len(key)
+            }
+        }
+    "#;
+
+#[test]
+fn test_index_overload_uses_method_return_type() {
+    let source = format!(
+        "{}{}",
+        INDEX_OVERLOAD_PRELUDE,
+        r#"
+            int main() {
+                var t = Table { base: 1 }
+                var n: int = t["key"]
+                return n
+            }
+        "#
+    );
+    let mut program = lency_syntax::parser::parse(&source).expect("parse failed");
+
+    let result = analyze(&mut program);
+    assert!(result.is_ok(), "{:?}", result.err());
+}
+
+#[test]
+fn test_index_overload_is_not_assignable() {
+    let source = format!(
+        "{}{}",
+        INDEX_OVERLOAD_PRELUDE,
+        r#"
+            int main() {
+                var t = Table { base: 1 }
+                t["key"] = 3
+                return 0
+            }
+        "#
+    );
+    let mut program = lency_syntax::parser::parse(&source).expect("parse failed");
+
+    let errors = analyze(&mut program).unwrap_err();
+    assert!(errors
+        .iter()
+        .any(|e| matches!(e, SemanticError::InvalidAssignmentTarget { .. })));
+}
+
+#[test]
+fn test_float_equality_warns() {
+    let source = r#"
+            int test(float a, int n) {
+                if a == 0.3 { return 1 }
+                if n != 3 { return 2 }
+                var differs = a != 1.5
+                return 0
+            }
+        "#;
+
+    let mut program = lency_syntax::parser::parse(source).expect("parse failed");
+    let result = analyze(&mut program).expect("float equality should not be an error");
+    let ops: Vec<&str> = result
+        .warnings
+        .iter()
+        .filter_map(|w| match w {
+            SemanticWarning::FloatEquality { op, .. } => Some(*op),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(ops, vec!["==", "!="]);
+
+    let mut program = lency_syntax::parser::parse(source).expect("parse failed");
+    let mut resolver = Resolver::new();
+    resolver.set_warn_float_equality(false);
+    let (result, errors) = analyze_with_resolver(&mut program, resolver);
+    assert!(errors.is_empty(), "{:?}", errors);
+    assert!(result.warnings.is_empty());
+}
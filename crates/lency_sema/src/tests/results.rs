@@ -0,0 +1,148 @@
+use crate::*;
+
+#[test]
+fn test_try_with_matching_error_type() {
+    let mut program = lency_syntax::parser::parse(
+        r#"
+            struct IoError { int code }
+            Result<int, IoError> read(int x) {
+                if x > 0 { return Ok(x) }
+                return Err(IoError { code: 1 })
+            }
+            Result<int, IoError> twice(int x) {
+                var v = read(x)?
+                return Ok(v * 2)
+            }
+        "#,
+    )
+    .expect("parse failed");
+
+    assert!(analyze(&mut program).is_ok());
+}
+
+#[test]
+fn test_try_with_incompatible_error_type_is_error() {
+    let mut program = lency_syntax::parser::parse(
+        r#"
+            struct IoError { int code }
+            struct AppError { string message }
+            Result<int, IoError> read(int x) {
+                return Err(IoError { code: 1 })
+            }
+            Result<int, AppError> run(int x) {
+                var v = read(x)?
+                return Ok(v)
+            }
+        "#,
+    )
+    .expect("parse failed");
+
+    let errors = analyze(&mut program).unwrap_err();
+    assert!(errors.iter().any(|e| matches!(
+        e,
+        SemanticError::IncompatibleErrorType { from, to, .. }
+            if from == "IoError" && to == "AppError"
+    )));
+}
+
+#[test]
+fn test_try_converts_error_type_via_from() {
+    let mut program = lency_syntax::parser::parse(
+        r#"
+            struct IoError { int code }
+            struct AppError { int code }
+            impl AppError {
+                AppError from(IoError e) { return AppError { code: e.code } }
+            }
+            Result<int, IoError> read(int x) {
+                return Err(IoError { code: 1 })
+            }
+            Result<int, AppError> run(int x) {
+                var v = read(x)?
+                return Ok(v)
+            }
+        "#,
+    )
+    .expect("parse failed");
+
+    assert!(analyze(&mut program).is_ok());
+}
+
+#[test]
+fn test_result_helper_methods() {
+    let mut program = lency_syntax::parser::parse(
+        r#"
+            struct IoError { int code }
+            Result<int, IoError> read(int x) {
+                if x > 0 { return Ok(x) }
+                return Err(IoError { code: 1 })
+            }
+            int main() {
+                var ok = read(1)
+                var err = read(0)
+                var a = ok.is_ok()
+                var b = err.is_err()
+                if a && b { return 1 }
+                var c = ok.unwrap_or(0) + err.unwrap_or(0)
+                var d = ok.map(|int v| => v + 1)
+                var e: int = d.unwrap_or(0)
+                return c
+            }
+        "#,
+    )
+    .expect("parse failed");
+
+    let result = analyze(&mut program);
+    assert!(result.is_ok(), "{:?}", result.err());
+}
+
+#[test]
+fn test_result_unwrap_or_default_must_match_ok_type() {
+    let mut program = lency_syntax::parser::parse(
+        r#"
+            int! read(int x) { return Ok(x) }
+            int main() {
+                var r = read(1)
+                return r.unwrap_or("zero")
+            }
+        "#,
+    )
+    .expect("parse failed");
+
+    let errors = analyze(&mut program).unwrap_err();
+    assert!(errors.iter().any(|e| matches!(
+        e,
+        SemanticError::TypeMismatch { expected, found, .. }
+            if expected == "int" && found == "string"
+    )));
+}
+
+#[test]
+fn test_parse_int_returns_result() {
+    let mut program = lency_syntax::parser::parse(
+        r#"
+            struct Error { string message }
+            int! total(string a, string b) {
+                var x = parse_int(a)?
+                var y: float = parse_float(b).unwrap_or(0.0)
+                return Ok(x)
+            }
+            int main() {
+                return parse_int("1") + 1
+            }
+        "#,
+    )
+    .expect("parse failed");
+
+    // parse_int 返回 int!，不能直接当 int 使用
+    let errors = analyze(&mut program).unwrap_err();
+    assert_eq!(errors.len(), 1, "{:?}", errors);
+    assert!(
+        matches!(
+            &errors[0],
+            SemanticError::InvalidBinaryOp { left, .. } if left == "Result<int, Error>"
+        ),
+        "{:?}",
+        errors
+    );
+}
@@ -0,0 +1,228 @@
+use crate::*;
+use lency_syntax::ast::*;
+
+#[test]
+fn test_struct_field_defaults_fill_omitted_fields() {
+    let mut program = lency_syntax::parser::parse(
+        r#"
+            struct Config {
+                string host
+                int retries = 3
+                float? timeout = null
+            }
+            int main() {
+                var a = Config { host: "localhost" }
+                var b = Config { host: "example.com", retries: 5 }
+                return a.retries + b.retries
+            }
+        "#,
+    )
+    .expect("parse failed");
+
+    let result = analyze(&mut program);
+    assert!(result.is_ok(), "{:?}", result.err());
+
+    let Decl::Function { body, .. } = &program.decls[1] else {
+        panic!("expected main function");
+    };
+    let Stmt::VarDecl { value, .. } = &body[0] else {
+        panic!("expected var decl");
+    };
+    let ExprKind::StructLiteral { fields, .. } = &value.kind else {
+        panic!("expected struct literal");
+    };
+    let names: Vec<_> = fields.iter().map(|(name, _)| name.as_str()).collect();
+    assert_eq!(names, ["host", "retries", "timeout"]);
+}
+
+#[test]
+fn test_struct_literal_requires_fields_without_defaults() {
+    let mut program = lency_syntax::parser::parse(
+        r#"
+            struct Config {
+                string host
+                int port
+                int retries = 3
+            }
+            int main() {
+                var c = Config { retries: 1 }
+                return 0
+            }
+        "#,
+    )
+    .expect("parse failed");
+
+    let errors = analyze(&mut program).unwrap_err();
+    let missing: Vec<_> = errors
+        .iter()
+        .filter_map(|e| match e {
+            SemanticError::MissingField { class, field, .. } if class == "Config" => {
+                Some(field.as_str())
+            }
+            _ => None,
+        })
+        .collect();
+    assert_eq!(missing, ["host", "port"], "{:?}", errors);
+}
+
+#[test]
+fn test_struct_literal_rejects_duplicate_field() {
+    let mut program = lency_syntax::parser::parse(
+        r#"
+            struct Point {
+                int x
+                int y
+            }
+            int main() {
+                var p = Point { x: 1, y: 2, x: 3 }
+                return p.x
+            }
+        "#,
+    )
+    .expect("parse failed");
+
+    let errors = analyze(&mut program).unwrap_err();
+    assert!(errors.iter().any(|e| matches!(
+        e,
+        SemanticError::DuplicateDefinition { name, .. } if name == "x"
+    )));
+}
+
+#[test]
+fn test_struct_field_default_must_be_matching_literal() {
+    let mut program = lency_syntax::parser::parse(
+        r#"
+            int three() { return 3 }
+            struct Config {
+                int retries = three()
+                string name = 1
+            }
+        "#,
+    )
+    .expect("parse failed");
+
+    let errors = analyze(&mut program).unwrap_err();
+    assert!(errors.iter().any(|e| matches!(
+        e,
+        SemanticError::FieldDefaultNotLiteral { field, .. } if field == "retries"
+    )));
+    assert!(errors.iter().any(|e| matches!(
+        e,
+        SemanticError::TypeMismatch { expected, found, .. }
+            if expected == "string" && found == "int"
+    )));
+}
+
+#[test]
+fn test_struct_equality_requires_same_struct() {
+    let mut program = lency_syntax::parser::parse(
+        r#"
+            struct Point {
+                int x
+                int y
+            }
+            struct Size {
+                int w
+                int h
+            }
+            bool check(Point a, Point b, Size s) {
+                var same = a == b && !(a != b)
+                var ordered = a < b
+                var mixed = a == s
+                return same
+            }
+        "#,
+    )
+    .expect("parse failed");
+
+    let errors = analyze(&mut program).unwrap_err();
+    let invalid: Vec<_> = errors
+        .iter()
+        .filter_map(|e| match e {
+            SemanticError::InvalidBinaryOp { op, .. } => Some(op.as_str()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(invalid, ["Lt", "Eq"], "{:?}", errors);
+}
+
+#[test]
+fn test_const_array_size_is_resolved() {
+    let mut program = lency_syntax::parser::parse(
+        r#"
+            struct Buf {
+                [SIZE]int data
+            }
+
+            const int SIZE = 4
+
+            void test() {
+                var local: [SIZE]int = [1, 2, 3, 4]
+            }
+        "#,
+    )
+    .expect("parse failed");
+
+    let result = analyze(&mut program);
+    assert!(result.is_ok(), "{:?}", result.err());
+
+    // Resolver 直接改写语法树中的类型，后续阶段只会看到具体长度
+    let Decl::Struct { fields, .. } = &program.decls[0] else {
+        panic!("Expected struct decl");
+    };
+    assert_eq!(
+        fields[0].ty,
+        Type::Array {
+            element_type: Box::new(Type::Int),
+            size: 4
+        }
+    );
+}
+
+#[test]
+fn test_array_size_requires_int_const() {
+    let mut program = lency_syntax::parser::parse(
+        r#"
+            const float RATIO = 0.5
+
+            struct Buf {
+                [RATIO]int data
+            }
+        "#,
+    )
+    .expect("parse failed");
+
+    let errors = analyze(&mut program).unwrap_err();
+    assert!(errors
+        .iter()
+        .any(|e| matches!(e, SemanticError::InvalidArraySize { name, .. } if name == "RATIO")));
+}
+
+#[test]
+fn test_nested_generic_field_access() {
+    let mut program = lency_syntax::parser::parse(
+        r#"
+            struct Error { string message }
+            struct Box<T> { T value }
+            struct Slot<T> {
+                Box<Box<T>> nested
+                Result<T, Error> outcome
+                T(T) step
+            }
+            int inc(int x) { return x + 1 }
+            int main() {
+                var slot = Slot<int> {
+                    nested: Box<Box<int>> { value: Box<int> { value: 41 } },
+                    outcome: Ok(1),
+                    step: inc
+                }
+                var f = slot.step
+                return f(slot.nested.value.value) + slot.outcome.unwrap_or(0)
+            }
+        "#,
+    )
+    .expect("parse failed");
+
+    let result = analyze(&mut program);
+    assert!(result.is_ok(), "{:?}", result.err());
+}
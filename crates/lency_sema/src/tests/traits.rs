@@ -0,0 +1,233 @@
+use crate::*;
+use lency_syntax::ast::*;
+
+#[test]
+fn test_trait_default_method_is_inherited() {
+    let mut program = lency_syntax::parser::parse(
+        r#"
+            trait Greeter {
+                string name();
+                string greet() { return "hello " + this.name() }
+            }
+            struct User { string n }
+            impl Greeter for User {
+                string name() { return this.n }
+            }
+            string main() {
+                var u = User { n: "amy" }
+                return u.greet()
+            }
+        "#,
+    )
+    .expect("parse failed");
+
+    let result = analyze(&mut program).expect("analysis failed");
+    let Some(Symbol::Struct(user)) = result.scopes.lookup("User") else {
+        panic!("User not found");
+    };
+    assert_eq!(user.get_method("greet").unwrap().return_type, Type::String);
+    // 默认实现被补进 impl 块，后续阶段按普通方法生成代码
+    let Some(Decl::Impl { methods, .. }) = program
+        .decls
+        .iter()
+        .find(|d| matches!(d, Decl::Impl { .. }))
+    else {
+        panic!("impl not found");
+    };
+    assert!(methods
+        .iter()
+        .any(|m| matches!(m, Decl::Function { name, .. } if name == "greet")));
+}
+
+#[test]
+fn test_trait_object_coercion_and_dispatch() {
+    let mut program = lency_syntax::parser::parse(
+        r#"
+            trait Shape { int area(); }
+            struct Square { int side }
+            struct Circle { int r }
+            impl Shape for Square { int area() { return this.side * this.side } }
+            struct Label { string text }
+            int main() {
+                var shapes: Vec<dyn Shape> = vec![]
+                shapes.push(Square { side: 2 })
+                var first: dyn Shape = shapes.get(0)
+                shapes.push(Circle { r: 1 })
+                var bad: dyn Label = Label { text: "x" }
+                return first.area()
+            }
+        "#,
+    )
+    .expect("parse failed");
+
+    let errors = analyze(&mut program).unwrap_err();
+    let messages: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+    assert_eq!(
+        messages,
+        vec![
+            "undefined trait 'Label'",
+            "type 'Circle' cannot be used as 'dyn Shape'",
+            "type 'Label' cannot be used as 'dyn Label'",
+        ]
+    );
+
+    // 存入 Vec<dyn Shape> 的值被改写为显式的 `as dyn Shape`，代码生成据此构造胖指针
+    let Some(Decl::Function { body, .. }) = program
+        .decls
+        .iter()
+        .find(|d| matches!(d, Decl::Function { name, .. } if name == "main"))
+    else {
+        panic!("main not found");
+    };
+    let Stmt::Expression(push) = &body[1] else {
+        panic!("expected push call");
+    };
+    let ExprKind::Call { args, .. } = &push.kind else {
+        panic!("expected call");
+    };
+    assert!(matches!(
+        &args[0].kind,
+        ExprKind::Cast { ty: Type::TraitObject(name), .. } if name == "Shape"
+    ));
+}
+
+#[test]
+fn test_nullable_trait_object_coercion() {
+    let mut program = lency_syntax::parser::parse(
+        r#"
+            trait Shape { int area(); }
+            struct Square { int side }
+            impl Shape for Square { int area() { return this.side * this.side } }
+            struct Canvas { Vec<dyn Shape>? shapes }
+            int measure(dyn Shape? s) { return 0 }
+            int main() {
+                var s: dyn Shape? = Square { side: 2 }
+                s = null
+                var q: Square? = null
+                var t: dyn Shape? = q
+                var c = Canvas { shapes: vec![Square { side: 3 }] }
+                return measure(Square { side: 1 })
+            }
+        "#,
+    )
+    .expect("parse failed");
+    analyze(&mut program).expect("analysis failed");
+
+    let Some(Decl::Function { body, .. }) = program
+        .decls
+        .iter()
+        .find(|d| matches!(d, Decl::Function { name, .. } if name == "main"))
+    else {
+        panic!("main not found");
+    };
+    let nullable_shape = Type::Nullable(Box::new(Type::TraitObject("Shape".to_string())));
+    let is_cast_to = |expr: &Expr, target: &Type| matches!(&expr.kind, ExprKind::Cast { ty, .. } if ty == target);
+    // 期望 dyn Shape? 的非空值改写为 `as dyn Shape?`，null 保持不变
+    let Stmt::VarDecl { value, .. } = &body[0] else {
+        panic!("expected var decl");
+    };
+    assert!(is_cast_to(value, &nullable_shape));
+    let Stmt::Assignment { value, .. } = &body[1] else {
+        panic!("expected assignment");
+    };
+    assert!(matches!(value.kind, ExprKind::Literal(Literal::Null)));
+    let Stmt::VarDecl { value, .. } = &body[3] else {
+        panic!("expected var decl");
+    };
+    assert!(is_cast_to(value, &nullable_shape));
+    // Vec<dyn Shape>? 字段的 vec![...] 元素逐个转换
+    let Stmt::VarDecl { value, .. } = &body[4] else {
+        panic!("expected var decl");
+    };
+    let ExprKind::StructLiteral { fields, .. } = &value.kind else {
+        panic!("expected struct literal");
+    };
+    let ExprKind::VecLiteral(elements) = &fields[0].1.kind else {
+        panic!("expected vec literal");
+    };
+    assert!(is_cast_to(
+        &elements[0],
+        &Type::TraitObject("Shape".to_string())
+    ));
+    let Stmt::Return {
+        value: Some(call), ..
+    } = &body[5]
+    else {
+        panic!("expected return");
+    };
+    let ExprKind::Call { args, .. } = &call.kind else {
+        panic!("expected call");
+    };
+    assert!(is_cast_to(&args[0], &nullable_shape));
+}
+
+#[test]
+fn test_trait_method_without_default_is_still_required() {
+    let mut program = lency_syntax::parser::parse(
+        r#"
+            trait Greeter {
+                string name();
+                string greet() { return "hello " + this.name() }
+            }
+            struct User { string n }
+            impl Greeter for User { }
+        "#,
+    )
+    .expect("parse failed");
+
+    let errors = analyze(&mut program).unwrap_err();
+    let missing: Vec<_> = errors
+        .iter()
+        .filter_map(|e| match e {
+            SemanticError::MissingTraitMethod { method_name, .. } => Some(method_name),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(missing, vec!["name"]);
+}
+
+#[test]
+fn test_associated_function_call() {
+    let mut program = lency_syntax::parser::parse(
+        r#"
+            struct Point { int x int y }
+            impl Point {
+                Point origin() { return Point { x: 0, y: 0 } }
+                int sum() { return this.x + this.y }
+            }
+            int main() {
+                var p = Point::origin()
+                return p.sum()
+            }
+        "#,
+    )
+    .expect("parse failed");
+
+    let result = analyze(&mut program).expect("analysis failed");
+    let Some(Symbol::Struct(point)) = result.scopes.lookup("Point") else {
+        panic!("Point not found");
+    };
+    assert!(point.get_method("origin").unwrap().is_static);
+    assert!(!point.get_method("sum").unwrap().is_static);
+}
+
+#[test]
+fn test_associated_call_to_method_using_this_is_error() {
+    let mut program = lency_syntax::parser::parse(
+        r#"
+            struct Point { int x }
+            impl Point {
+                int get() { return this.x }
+            }
+            int main() { return Point::get() }
+        "#,
+    )
+    .expect("parse failed");
+
+    let errors = analyze(&mut program).unwrap_err();
+    assert!(errors.iter().any(|e| matches!(
+        e,
+        SemanticError::NotAnAssociatedFunction { class, method, .. }
+            if class == "Point" && method == "get"
+    )));
+}
@@ -44,11 +44,40 @@ pub fn check_decl(checker: &mut TypeChecker, decl: &mut Decl) {
                 checker.next_child_index += 1;
             }
         }
+        Decl::Const {
+            name, ty, value, ..
+        } => {
+            // 常量值需要在编译期确定：字面量，或由字面量组成的数组与结构体
+            if let Some(part) = crate::resolver::decl::non_const_part(value) {
+                checker.errors.push(SemanticError::ConstNotLiteral {
+                    name: name.clone(),
+                    span: part.span.clone(),
+                });
+                return;
+            }
+            match checker.infer_type(value) {
+                Ok(value_ty) => {
+                    if let Some(annotation) = ty {
+                        // 窄整数常量（如 `const MASK: i32 = 255`）接受范围内的整数字面量
+                        if *annotation != value_ty
+                            && !crate::type_infer::int_literal_fits(annotation, value)
+                        {
+                            checker.errors.push(SemanticError::TypeMismatch {
+                                expected: annotation.to_string(),
+                                found: value_ty.to_string(),
+                                span: value.span.clone(),
+                            });
+                        }
+                    }
+                }
+                Err(e) => checker.errors.push(e),
+            }
+        }
+        Decl::Newtype { .. } => {
+            // 底层类型在 resolver 阶段已验证
+        }
         Decl::Var {
-            span: _,
-            name,
-            ty,
-            value,
+            name, ty, value, ..
         } => {
             // Global Scopes are already active?
             // TypeChecker runs in global scope by default.
@@ -79,9 +108,11 @@ pub fn check_decl(checker: &mut TypeChecker, decl: &mut Decl) {
                     if let Some(crate::symbol::Symbol::Variable(sym)) =
                         checker.scopes.get_symbol_mut(id)
                     {
-                        sym.ty = value_ty;
+                        sym.ty = value_ty.clone();
                     }
                 }
+                // 回填推导出的类型，代码生成据此声明全局变量
+                *ty = Some(value_ty);
             }
         }
         Decl::Import { .. } => {} // No-op
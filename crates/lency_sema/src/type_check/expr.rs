@@ -1,7 +1,7 @@
 use super::TypeChecker;
 use crate::error::SemanticError;
 use crate::symbol::{FunctionSymbol, Symbol};
-use crate::type_infer::{is_compatible, substitute_type};
+use crate::type_infer::{is_compatible, method_arity_error, narrow_int_literal, substitute_type};
 use lency_syntax::ast::{Expr, ExprKind, Type};
 use std::collections::HashMap;
 
@@ -12,8 +12,9 @@ pub fn check_call(
     args: &mut [Expr],
     span: &std::ops::Range<usize>,
 ) -> Result<Type, SemanticError> {
-    // 解析被调用者 (包括泛型实例化)
-    let (func, is_method, subst_map) = match &mut callee.kind {
+    let callee_span = callee.span.clone();
+    // 解析被调用者 (包括泛型实例化)；方法调用同时记下接收者类型，用于错误信息
+    let (func, receiver, subst_map) = match &mut callee.kind {
         ExprKind::GenericInstantiation {
             base,
             args: type_args,
@@ -38,7 +39,7 @@ pub fn check_call(
                             for (param, arg_ty) in f.generic_params.iter().zip(type_args.iter()) {
                                 map.insert(param.name.as_str().to_string(), arg_ty.clone());
                             }
-                            (f.clone(), false, map)
+                            (f.clone(), None, map)
                         }
                         _ => {
                             return Err(SemanticError::NotCallable {
@@ -59,7 +60,7 @@ pub fn check_call(
         ExprKind::Variable(name) => {
             // 普通函数调用
             match checker.scopes.lookup(name) {
-                Some(Symbol::Function(f)) => (f.clone(), false, HashMap::new()),
+                Some(Symbol::Function(f)) => (f.clone(), None, HashMap::new()),
                 Some(Symbol::Struct(s)) => {
                     // 构造函数
                     let func_sym = FunctionSymbol {
@@ -73,8 +74,10 @@ pub fn check_call(
                         generic_params: s.generic_params.clone(), // Struct generic params
                         span: s.span.clone(),
                         is_public: true, // Constructors are usually public or match struct visibility
+                        is_static: true,
+                        is_variadic: false,
                     };
-                    (func_sym, false, HashMap::new())
+                    (func_sym, None, HashMap::new())
                 }
                 _ => {
                     return Err(SemanticError::NotCallable {
@@ -107,6 +110,11 @@ pub fn check_call(
                 match symbol {
                     Some(Symbol::Struct(struct_sym)) => {
                         if let Some(method) = struct_sym.get_method(name) {
+                            if let Some(owner) = checker.scopes.lookup_id(&type_name) {
+                                checker
+                                    .scopes
+                                    .record_member_resolution(owner, name, &callee_span);
+                            }
                             let mut func = method.clone();
                             // Fix 'this' type for primitives
                             let this_type = match &obj_type {
@@ -128,7 +136,7 @@ pub fn check_call(
                                 HashMap::new()
                             };
 
-                            (func, true, subst_map)
+                            (func, Some(obj_type.to_string()), subst_map)
                         } else {
                             return Err(SemanticError::UndefinedMethod {
                                 class: type_name.clone(),
@@ -157,7 +165,18 @@ pub fn check_call(
                         if let Some(Symbol::GenericParam(gp_sym)) =
                             checker.scopes.lookup(&param_name)
                         {
-                            if let Some(bound_ty) = &gp_sym.bound {
+                            // 多个约束时选用声明了该方法的 Trait
+                            let bound = gp_sym
+                                .bounds
+                                .iter()
+                                .find(|b| {
+                                    matches!(b, Type::Struct(t) if matches!(
+                                        checker.scopes.lookup(t),
+                                        Some(Symbol::Trait(ts)) if ts.get_method(name).is_some()
+                                    ))
+                                })
+                                .or(gp_sym.bounds.first());
+                            if let Some(bound_ty) = bound {
                                 // 解析约束类型
                                 // 目前假设 bound 是 Type::Struct(TraitName) 形式（由 Parser 生成）
                                 if let Type::Struct(trait_name) = bound_ty {
@@ -183,8 +202,10 @@ pub fn check_call(
                                                 generic_params: vec![], // Trait 方法特定的泛型参数？暂不支持
                                                 span: trait_sym.span.clone(), // 使用 Trait 的 span 作为近似
                                                 is_public: true, // Trait 方法通过接口总是可见的
+                                                is_static: false,
+                                                is_variadic: false,
                                             };
-                                            (func_sym, true, HashMap::new())
+                                            (func_sym, Some(param_name.clone()), HashMap::new())
                                         } else {
                                             return Err(SemanticError::UndefinedMethod {
                                                 class: format!("Trait {}", trait_name),
@@ -223,12 +244,13 @@ pub fn check_call(
                             "push" => {
                                 // push(val)
                                 if args.len() != 1 {
-                                    return Err(SemanticError::ArgumentCountMismatch {
-                                        name: "push".to_string(),
-                                        expected: 1,
-                                        found: args.len(),
-                                        span: span.clone(),
-                                    });
+                                    return Err(method_arity_error(
+                                        &Type::Vec(inner_type.clone()),
+                                        "push",
+                                        1,
+                                        args,
+                                        span,
+                                    ));
                                 }
                                 let arg_ty = checker.infer_type(&mut args[0])?;
                                 if !is_compatible(&inner_type, &arg_ty) {
@@ -243,36 +265,39 @@ pub fn check_call(
                             "pop" => {
                                 // pop() -> T
                                 if !args.is_empty() {
-                                    return Err(SemanticError::ArgumentCountMismatch {
-                                        name: "pop".to_string(),
-                                        expected: 0,
-                                        found: args.len(),
-                                        span: span.clone(),
-                                    });
+                                    return Err(method_arity_error(
+                                        &Type::Vec(inner_type.clone()),
+                                        "pop",
+                                        0,
+                                        args,
+                                        span,
+                                    ));
                                 }
                                 return Ok(*inner_type);
                             }
                             "len" => {
                                 // len() -> int
                                 if !args.is_empty() {
-                                    return Err(SemanticError::ArgumentCountMismatch {
-                                        name: "len".to_string(),
-                                        expected: 0,
-                                        found: args.len(),
-                                        span: span.clone(),
-                                    });
+                                    return Err(method_arity_error(
+                                        &Type::Vec(inner_type.clone()),
+                                        "len",
+                                        0,
+                                        args,
+                                        span,
+                                    ));
                                 }
                                 return Ok(Type::Int);
                             }
                             "get" => {
                                 // get(index) -> T
                                 if args.len() != 1 {
-                                    return Err(SemanticError::ArgumentCountMismatch {
-                                        name: "get".to_string(),
-                                        expected: 1,
-                                        found: args.len(),
-                                        span: span.clone(),
-                                    });
+                                    return Err(method_arity_error(
+                                        &Type::Vec(inner_type.clone()),
+                                        "get",
+                                        1,
+                                        args,
+                                        span,
+                                    ));
                                 }
                                 let arg_ty = checker.infer_type(&mut args[0])?;
                                 if !is_compatible(&Type::Int, &arg_ty) {
@@ -287,12 +312,13 @@ pub fn check_call(
                             "set" => {
                                 // set(index, val) -> void
                                 if args.len() != 2 {
-                                    return Err(SemanticError::ArgumentCountMismatch {
-                                        name: "set".to_string(),
-                                        expected: 2,
-                                        found: args.len(),
-                                        span: span.clone(),
-                                    });
+                                    return Err(method_arity_error(
+                                        &Type::Vec(inner_type.clone()),
+                                        "set",
+                                        2,
+                                        args,
+                                        span,
+                                    ));
                                 }
                                 let index_ty = checker.infer_type(&mut args[0])?;
                                 if !is_compatible(&Type::Int, &index_ty) {
@@ -312,6 +338,31 @@ pub fn check_call(
                                 }
                                 return Ok(Type::Void);
                             }
+                            "sort_by" => {
+                                // sort_by(cmp) -> void, cmp: int(T, T)
+                                if args.len() != 1 {
+                                    return Err(method_arity_error(
+                                        &Type::Vec(inner_type.clone()),
+                                        "sort_by",
+                                        1,
+                                        args,
+                                        span,
+                                    ));
+                                }
+                                let comparator = Type::Function {
+                                    param_types: vec![(*inner_type).clone(), (*inner_type).clone()],
+                                    return_type: Box::new(Type::Int),
+                                };
+                                let cmp_ty = checker.infer_type(&mut args[0])?;
+                                if !is_compatible(&comparator, &cmp_ty) {
+                                    return Err(SemanticError::TypeMismatch {
+                                        expected: comparator.to_string(),
+                                        found: cmp_ty.to_string(),
+                                        span: args[0].span.clone(),
+                                    });
+                                }
+                                return Ok(Type::Void);
+                            }
                             _ => {
                                 return Err(SemanticError::UndefinedMethod {
                                     class: format!("Vec<{}>", inner_type),
@@ -338,6 +389,7 @@ pub fn check_call(
 
     // 检查参数数量
     // 如果是方法调用，定义中有隐式 this 参数，所以 args.len() + 1 应该等于 params.len()
+    let is_method = receiver.is_some();
     let expected_args = if is_method {
         func.params.len() - 1
     } else {
@@ -345,11 +397,21 @@ pub fn check_call(
     };
 
     if args.len() != expected_args {
-        return Err(SemanticError::ArgumentCountMismatch {
-            name: func.name.clone(),
-            expected: expected_args,
-            found: args.len(),
-            span: span.clone(),
+        return Err(match receiver {
+            Some(receiver) => SemanticError::MethodArgumentCountMismatch {
+                receiver,
+                method: func.name.clone(),
+                expected: expected_args,
+                found: args.len(),
+                signature: Some(func.signature()),
+                span: span.clone(),
+            },
+            None => SemanticError::ArgumentCountMismatch {
+                name: func.name.clone(),
+                expected: expected_args,
+                found: args.len(),
+                span: span.clone(),
+            },
         });
     }
 
@@ -358,16 +420,17 @@ pub fn check_call(
     let params_iter = func.params.iter().skip(skip_count);
 
     for (arg, (_, param_ty)) in args.iter_mut().zip(params_iter) {
-        let arg_ty = checker.infer_type(arg)?;
         // 关键：检查参数前先替换其中的泛型参数
         let expected_ty = substitute_type(param_ty, &subst_map);
+        narrow_int_literal(&expected_ty, arg);
+        let arg_ty = checker.infer_type(arg)?;
 
         if !is_compatible(&expected_ty, &arg_ty) {
-            checker.errors.push(SemanticError::TypeMismatch {
-                expected: expected_ty.to_string(),
-                found: arg_ty.to_string(),
-                span: arg.span.clone(), // Use arg.span
-            });
+            checker.errors.push(TypeChecker::mismatch_error(
+                &expected_ty,
+                &arg_ty,
+                &arg.span,
+            ));
         }
     }
 
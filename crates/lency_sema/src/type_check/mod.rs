@@ -3,10 +3,10 @@
 //! 类型检查模块，验证程序的类型正确性。
 //! 遵循 Lency "Safety by Default" 哲学：严格的类型检查，拒绝隐式错误。
 
-use crate::error::SemanticError;
+use crate::error::{SemanticError, SemanticWarning};
 use crate::scope::ScopeStack;
 use crate::type_infer::TypeInferer;
-use lency_syntax::ast::{Decl, Expr, Program, Stmt, Type};
+use lency_syntax::ast::{Decl, Expr, ExprKind, Program, Stmt, Type};
 
 pub mod decl;
 pub mod expr;
@@ -22,6 +22,11 @@ pub struct TypeChecker<'a> {
     pub(crate) next_child_index: usize,
     /// 当前循环嵌套深度
     pub(crate) loop_depth: usize,
+    /// 作为值的循环：(循环体所在的嵌套深度, 收集到的 break 值类型及位置)
+    pub(crate) loop_values: Vec<(usize, Vec<(Type, lency_syntax::ast::Span)>)>,
+    pub(crate) warnings: Vec<SemanticWarning>,
+    /// 是否对浮点数的 == / != 比较给出警告（默认开启）
+    pub(crate) warn_float_equality: bool,
 }
 
 impl<'a> TypeChecker<'a> {
@@ -32,13 +37,34 @@ impl<'a> TypeChecker<'a> {
             current_return_type: None,
             next_child_index: 0,
             loop_depth: 0,
+            loop_values: Vec::new(),
+            warnings: Vec::new(),
+            warn_float_equality: true,
         }
     }
 
+    /// 开关浮点数相等比较警告
+    pub fn set_warn_float_equality(&mut self, enabled: bool) {
+        self.warn_float_equality = enabled;
+    }
+
+    /// 取出类型检查阶段产生的警告
+    pub fn take_warnings(&mut self) -> Vec<SemanticWarning> {
+        std::mem::take(&mut self.warnings)
+    }
+
     /// 检查整个程序
     pub fn check(&mut self, program: &mut Program) -> Result<(), Vec<SemanticError>> {
+        // 全局变量先按声明顺序检查，使函数体中引用的全局变量已有推导类型
+        for decl in &mut program.decls {
+            if matches!(decl, Decl::Var { .. }) {
+                self.check_decl(decl);
+            }
+        }
         for decl in &mut program.decls {
-            self.check_decl(decl);
+            if !matches!(decl, Decl::Var { .. }) {
+                self.check_decl(decl);
+            }
         }
 
         if self.errors.is_empty() {
@@ -71,7 +97,51 @@ impl<'a> TypeChecker<'a> {
     /// 推导表达式类型（封装 TypeInferer）
     pub(crate) fn infer_type(&mut self, expr: &mut Expr) -> Result<Type, SemanticError> {
         let mut inferer = TypeInferer::new(self.scopes);
-        inferer.infer(expr)
+        inferer.return_type = self.current_return_type.clone();
+        let result = inferer.infer(expr);
+        let errors = std::mem::take(&mut inferer.errors);
+        self.errors.extend(errors);
+        for warning in std::mem::take(&mut inferer.warnings) {
+            let suppressed = matches!(warning, SemanticWarning::FloatEquality { .. })
+                && !self.warn_float_equality;
+            // 同一表达式可能被推导多次，只保留一份
+            if !suppressed && !self.warnings.iter().any(|w| w.span() == warning.span()) {
+                self.warnings.push(warning);
+            }
+        }
+        result
+    }
+
+    /// 构造类型不兼容错误：固定长度数组仅长度不同时给出更具体的 ArrayLengthMismatch
+    pub(crate) fn mismatch_error(
+        expected: &Type,
+        found: &Type,
+        span: &std::ops::Range<usize>,
+    ) -> SemanticError {
+        if let (
+            Type::Array {
+                element_type: expected_elem,
+                size: expected_size,
+            },
+            Type::Array {
+                element_type: found_elem,
+                size: found_size,
+            },
+        ) = (expected, found)
+        {
+            if expected_size != found_size && expected_elem == found_elem {
+                return SemanticError::ArrayLengthMismatch {
+                    expected: *expected_size,
+                    found: *found_size,
+                    span: span.clone(),
+                };
+            }
+        }
+        SemanticError::TypeMismatch {
+            expected: expected.to_string(),
+            found: found.to_string(),
+            span: span.clone(),
+        }
     }
 
     /// 检查代码块是否有返回语句
@@ -83,7 +153,29 @@ impl<'a> TypeChecker<'a> {
         for stmt in stmts {
             match stmt {
                 Stmt::Return { .. } => return true,
+                // panic 永不返回，之后的代码不可达
+                Stmt::Expression(Expr {
+                    kind: ExprKind::Panic(_),
+                    ..
+                }) => return true,
                 Stmt::If {
+                    condition,
+                    then_block,
+                    else_block,
+                    ..
+                } => {
+                    let else_returns = || else_block.as_deref().is_some_and(Self::has_return_impl);
+                    // 条件恒定时只看会执行的分支，否则两个分支都有 return 才算完整覆盖
+                    let covered = match condition.const_bool() {
+                        Some(true) => Self::has_return_impl(then_block),
+                        Some(false) => else_returns(),
+                        None => Self::has_return_impl(then_block) && else_returns(),
+                    };
+                    if covered {
+                        return true;
+                    }
+                }
+                Stmt::IfLet {
                     then_block,
                     else_block,
                     ..
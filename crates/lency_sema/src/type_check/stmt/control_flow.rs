@@ -2,27 +2,42 @@ use super::{check_block_with_scope, check_stmt};
 use crate::error::SemanticError;
 use crate::symbol::Symbol;
 use crate::type_check::TypeChecker;
-use lency_syntax::ast::{BinaryOp, Expr, ExprKind, Literal, Stmt, Type};
+use lency_syntax::ast::{BinaryOp, Expr, ExprKind, Literal, MatchPattern, Stmt, Type};
 
-pub fn check_if(
-    checker: &mut TypeChecker,
-    condition: &mut Expr,
-    then_block: &mut [Stmt],
-    else_block: Option<&mut [Stmt]>,
-    span: &std::ops::Range<usize>,
-) {
-    // 条件必须是 bool
+/// 检查条件表达式必须是 bool
+///
+/// 错误指向条件表达式本身而非整条语句；
+/// `bool?` 单独报错：null 既不是 true 也不是 false，需要显式比较或提供默认值
+fn check_condition(checker: &mut TypeChecker, condition: &mut Expr) {
     match checker.infer_type(condition) {
+        Ok(Type::Nullable(inner)) if *inner == Type::Bool => {
+            checker
+                .errors
+                .push(SemanticError::NullableConditionRequiresCheck {
+                    ty: Type::Nullable(inner).to_string(),
+                    span: condition.span.clone(),
+                });
+        }
         Ok(ty) if ty != Type::Bool => {
             checker.errors.push(SemanticError::TypeMismatch {
                 expected: "bool".to_string(),
                 found: ty.to_string(),
-                span: span.clone(),
+                span: condition.span.clone(),
             });
         }
         Err(e) => checker.errors.push(e),
         _ => {}
     }
+}
+
+pub fn check_if(
+    checker: &mut TypeChecker,
+    condition: &mut Expr,
+    then_block: &mut [Stmt],
+    else_block: Option<&mut [Stmt]>,
+) {
+    // 条件必须是 bool
+    check_condition(checker, condition);
 
     // --- Smart Casting (Flow Analysis) ---
     // Extract variable name from condition
@@ -85,24 +100,9 @@ fn extract_smart_cast_var(condition: &Expr) -> Option<String> {
     None
 }
 
-pub fn check_while(
-    checker: &mut TypeChecker,
-    condition: &mut Expr,
-    body: &mut [Stmt],
-    span: &std::ops::Range<usize>,
-) {
+pub fn check_while(checker: &mut TypeChecker, condition: &mut Expr, body: &mut [Stmt]) {
     // 条件必须是 bool
-    match checker.infer_type(condition) {
-        Ok(ty) if ty != Type::Bool => {
-            checker.errors.push(SemanticError::TypeMismatch {
-                expected: "bool".to_string(),
-                found: ty.to_string(),
-                span: span.clone(),
-            });
-        }
-        Err(e) => checker.errors.push(e),
-        _ => {}
-    }
+    check_condition(checker, condition);
 
     checker.loop_depth += 1;
     // 检查循环体 (带作用域)
@@ -116,7 +116,6 @@ pub fn check_for(
     condition: Option<&mut Expr>,
     update: Option<&mut Stmt>,
     body: &mut [Stmt],
-    span: &std::ops::Range<usize>,
 ) {
     // 保存当前作用域
     let parent_scope = checker.scopes.current_scope();
@@ -138,17 +137,7 @@ pub fn check_for(
 
         // 2. 检查条件表达式
         if let Some(cond) = condition {
-            match checker.infer_type(cond) {
-                Ok(ty) if ty != Type::Bool => {
-                    checker.errors.push(SemanticError::TypeMismatch {
-                        expected: "bool".to_string(),
-                        found: ty.to_string(),
-                        span: span.clone(),
-                    });
-                }
-                Err(e) => checker.errors.push(e),
-                _ => {}
-            }
+            check_condition(checker, cond);
         }
 
         // 3. 检查更新语句
@@ -196,9 +185,11 @@ pub fn check_for_in(
     // 1. check iterable (outside of loop scope)
     let elem_ty = match checker.infer_type(iterable) {
         Ok(Type::Array { element_type, .. }) => *element_type,
+        // Vec 按值逐个取出元素，长度在进入循环前读取一次
+        Ok(Type::Vec(element_type)) => *element_type,
         Ok(ty) => {
             checker.errors.push(SemanticError::TypeMismatch {
-                expected: "Array".to_string(),
+                expected: "Array or Vec".to_string(),
                 found: ty.to_string(),
                 span: iterable.span.clone(),
             });
@@ -249,3 +240,120 @@ pub fn check_for_in(
         checker.loop_depth -= 1;
     }
 }
+
+/// 检查 if let 语句
+///
+/// - `T?`：模式必须是变量绑定，then 块中绑定为解包后的 `T`
+/// - 枚举：模式与 match 分支相同，绑定变体字段
+pub fn check_if_let(
+    checker: &mut TypeChecker,
+    pattern: &mut MatchPattern,
+    value: &mut Expr,
+    then_block: &mut [Stmt],
+    else_block: Option<&mut [Stmt]>,
+    span: &std::ops::Range<usize>,
+) {
+    let bindings = match checker.infer_type(value) {
+        Ok(value_ty) => match if_let_bindings(checker, pattern, &value_ty, span) {
+            Ok(bindings) => bindings,
+            Err(e) => {
+                checker.errors.push(e);
+                Vec::new()
+            }
+        },
+        Err(e) => {
+            checker.errors.push(e);
+            Vec::new()
+        }
+    };
+
+    // then 块作用域（与 Resolver 对齐），先写入绑定的类型再检查语句
+    let parent_scope = checker.scopes.current_scope();
+    let children = checker.scopes.get_child_scopes(parent_scope);
+
+    if let Some(&then_scope) = children.get(checker.next_child_index) {
+        checker.scopes.set_current(then_scope);
+        checker.next_child_index += 1;
+
+        let prev_child_index = checker.next_child_index;
+        checker.next_child_index = 0;
+
+        for (name, ty) in bindings {
+            let symbol_id = checker
+                .scopes
+                .get_scope(then_scope)
+                .and_then(|scope| scope.lookup_local(&name));
+            if let Some(Symbol::Variable(var_sym)) =
+                symbol_id.and_then(|id| checker.scopes.get_symbol_mut(id))
+            {
+                var_sym.ty = ty;
+            }
+        }
+
+        for stmt in then_block.iter_mut() {
+            check_stmt(checker, stmt);
+        }
+
+        checker.next_child_index = prev_child_index;
+        checker.scopes.set_current(parent_scope);
+    }
+
+    if let Some(else_stmts) = else_block {
+        check_block_with_scope(checker, else_stmts);
+    }
+}
+
+/// 计算 if let 模式引入的绑定
+fn if_let_bindings(
+    checker: &mut TypeChecker,
+    pattern: &mut MatchPattern,
+    value_ty: &Type,
+    span: &std::ops::Range<usize>,
+) -> Result<Vec<(String, Type)>, SemanticError> {
+    if let Type::Nullable(inner) = value_ty {
+        return match pattern {
+            MatchPattern::Variable(name) => Ok(vec![(name.clone(), (**inner).clone())]),
+            MatchPattern::Wildcard => Ok(Vec::new()),
+            _ => Err(SemanticError::TypeMismatch {
+                expected: "variable binding".to_string(),
+                found: value_ty.to_string(),
+                span: span.clone(),
+            }),
+        };
+    }
+
+    let enum_variants = match value_ty {
+        Type::Struct(name) | Type::Generic(name, _) => match checker.scopes.lookup(name) {
+            Some(Symbol::Enum(e)) => Some(e.variants.keys().cloned().collect::<Vec<_>>()),
+            _ => None,
+        },
+        _ => None,
+    };
+    let Some(variants) = enum_variants else {
+        return Err(SemanticError::TypeMismatch {
+            expected: "nullable or enum type".to_string(),
+            found: value_ty.to_string(),
+            span: span.clone(),
+        });
+    };
+
+    // 无字段变体在语法上与变量绑定相同（if let None = x），按变体处理
+    if let MatchPattern::Variable(name) = pattern {
+        if variants.contains(name) {
+            *pattern = MatchPattern::Variant {
+                name: name.clone(),
+                sub_patterns: Vec::new(),
+            };
+        }
+    }
+    if !matches!(pattern, MatchPattern::Variant { .. }) {
+        return Err(SemanticError::TypeMismatch {
+            expected: "enum variant pattern".to_string(),
+            found: value_ty.to_string(),
+            span: span.clone(),
+        });
+    }
+
+    let mut inferer = crate::type_infer::TypeInferer::new(checker.scopes);
+    inferer.pattern_bindings(pattern, value_ty, span)
+}
@@ -1,10 +1,11 @@
 use super::TypeChecker;
 use crate::error::SemanticError;
-use crate::type_infer::is_compatible;
-use lency_syntax::ast::{Expr, Stmt, Type};
+use crate::symbol::Symbol;
+use crate::type_infer::{is_compatible, narrow_int_literal, wrap_trait_object};
+use lency_syntax::ast::{Expr, ExprKind, Literal, Stmt, Type};
 
 pub mod control_flow;
-use control_flow::{check_for, check_for_in, check_if, check_while};
+use control_flow::{check_for, check_for_in, check_if, check_if_let, check_while};
 
 /// 辅助函数：进入新的作用域并检查代码块
 pub(crate) fn check_block_with_scope(checker: &mut TypeChecker, stmts: &mut [Stmt]) {
@@ -44,9 +45,19 @@ pub fn check_stmt(checker: &mut TypeChecker, stmt: &mut Stmt) {
             ty,
             value,
             span,
+            ..
         } => {
             check_var_decl(checker, name, ty.as_ref(), value, span);
         }
+        Stmt::LoopVarDecl {
+            name,
+            ty,
+            loop_stmt,
+            default,
+            ..
+        } => {
+            check_loop_var_decl(checker, name, ty.as_ref(), loop_stmt, default);
+        }
         Stmt::Assignment {
             target,
             value,
@@ -67,29 +78,37 @@ pub fn check_stmt(checker: &mut TypeChecker, stmt: &mut Stmt) {
             condition,
             then_block,
             else_block,
+            ..
+        } => {
+            check_if(checker, condition, then_block, else_block.as_deref_mut());
+        }
+        Stmt::IfLet {
+            pattern,
+            value,
+            then_block,
+            else_block,
             span,
         } => {
-            check_if(
+            check_if_let(
                 checker,
-                condition,
+                pattern,
+                value,
                 then_block,
                 else_block.as_deref_mut(),
                 span,
             );
         }
         Stmt::While {
-            condition,
-            body,
-            span,
+            condition, body, ..
         } => {
-            check_while(checker, condition, body, span);
+            check_while(checker, condition, body);
         }
         Stmt::For {
             init,
             condition,
             update,
             body,
-            span,
+            ..
         } => {
             check_for(
                 checker,
@@ -97,18 +116,13 @@ pub fn check_stmt(checker: &mut TypeChecker, stmt: &mut Stmt) {
                 condition.as_mut(),
                 update.as_deref_mut(),
                 body,
-                span,
             );
         }
         Stmt::Return { value, span } => {
             check_return(checker, value.as_mut(), span);
         }
-        Stmt::Break { span } => {
-            if checker.loop_depth == 0 {
-                checker
-                    .errors
-                    .push(SemanticError::BreakOutsideLoop { span: span.clone() });
-            }
+        Stmt::Break { span, value } => {
+            check_break(checker, value.as_mut(), span);
         }
         Stmt::Continue { span } => {
             if checker.loop_depth == 0 {
@@ -135,6 +149,19 @@ fn check_var_decl(
     value: &mut Expr,
     span: &std::ops::Range<usize>,
 ) {
+    // var x = null 没有类型标注时无法得知 x 的具体类型
+    if declared_ty.is_none() && matches!(value.kind, ExprKind::Literal(Literal::Null)) {
+        checker.errors.push(SemanticError::CannotInferNullType {
+            span: value.span.clone(),
+        });
+        return;
+    }
+
+    if let Some(expected) = declared_ty {
+        narrow_int_literal(expected, value);
+        wrap_trait_object(expected, value);
+    }
+
     // 推导初始化表达式的类型
     let value_ty = match checker.infer_type(value) {
         Ok(ty) => ty,
@@ -147,11 +174,9 @@ fn check_var_decl(
     // 如果有显式类型声明，检查兼容性
     if let Some(expected) = declared_ty {
         if !is_compatible(expected, &value_ty) {
-            checker.errors.push(SemanticError::TypeMismatch {
-                expected: expected.to_string(),
-                found: value_ty.to_string(),
-                span: span.clone(),
-            });
+            checker
+                .errors
+                .push(TypeChecker::mismatch_error(expected, &value_ty, span));
         }
     }
 
@@ -173,12 +198,103 @@ fn check_var_decl(
     }
 }
 
+/// 检查以循环的值初始化的变量：默认值与每个 break 值都必须与变量类型兼容
+fn check_loop_var_decl(
+    checker: &mut TypeChecker,
+    name: &str,
+    declared_ty: Option<&Type>,
+    loop_stmt: &mut Stmt,
+    default: &mut Expr,
+) {
+    // break value 只属于这一层循环，嵌套的普通循环中不能带值
+    checker
+        .loop_values
+        .push((checker.loop_depth + 1, Vec::new()));
+    check_stmt(checker, loop_stmt);
+    let breaks = checker
+        .loop_values
+        .pop()
+        .map(|(_, breaks)| breaks)
+        .unwrap_or_default();
+
+    let default_ty = match checker.infer_type(default) {
+        Ok(ty) => ty,
+        Err(e) => {
+            checker.errors.push(e);
+            return;
+        }
+    };
+
+    // 变量类型取显式声明，否则取默认值的类型
+    // 各出口的值在循环结束处汇合，类型必须完全一致（不做 int -> float 等隐式转换）
+    let var_ty = declared_ty.cloned().unwrap_or_else(|| default_ty.clone());
+    let sources = std::iter::once((default_ty, default.span.clone())).chain(breaks);
+    for (ty, value_span) in sources {
+        if ty != var_ty {
+            checker
+                .errors
+                .push(TypeChecker::mismatch_error(&var_ty, &ty, &value_span));
+        }
+    }
+
+    if declared_ty.is_none() {
+        if let Some(symbol_id) = checker.scopes.lookup_id(name) {
+            if let Some(Symbol::Variable(var_sym)) = checker.scopes.get_symbol_mut(symbol_id) {
+                var_sym.ty = var_ty;
+            }
+        }
+    }
+}
+
+/// 检查 break：必须在循环内；作为值的循环必须 break value，普通循环不能带值
+fn check_break(checker: &mut TypeChecker, value: Option<&mut Expr>, span: &std::ops::Range<usize>) {
+    if checker.loop_depth == 0 {
+        checker
+            .errors
+            .push(SemanticError::BreakOutsideLoop { span: span.clone() });
+        return;
+    }
+
+    let depth = checker.loop_depth;
+    let in_loop_value = matches!(checker.loop_values.last(), Some((d, _)) if *d == depth);
+    match value {
+        Some(expr) if in_loop_value => match checker.infer_type(expr) {
+            Ok(ty) => {
+                if let Some((_, breaks)) = checker.loop_values.last_mut() {
+                    breaks.push((ty, expr.span.clone()));
+                }
+            }
+            Err(e) => checker.errors.push(e),
+        },
+        Some(expr) => checker
+            .errors
+            .push(SemanticError::BreakValueOutsideLoopValue {
+                span: expr.span.clone(),
+            }),
+        None if in_loop_value => checker
+            .errors
+            .push(SemanticError::MissingBreakValue { span: span.clone() }),
+        None => {}
+    }
+}
+
 fn check_assignment(
     checker: &mut TypeChecker,
     target: &mut Expr,
     value: &mut Expr,
     span: &std::ops::Range<usize>,
 ) {
+    // 左值：变量、字段访问、数组 / Vec 索引
+    if !matches!(
+        target.kind,
+        ExprKind::Variable(_) | ExprKind::Get { .. } | ExprKind::Index { .. }
+    ) {
+        checker.errors.push(SemanticError::InvalidAssignmentTarget {
+            span: target.span.clone(),
+        });
+        return;
+    }
+
     let target_ty = match checker.infer_type(target) {
         Ok(ty) => ty,
         Err(e) => {
@@ -187,6 +303,31 @@ fn check_assignment(
         }
     };
 
+    // 常量与模式绑定不可重新赋值
+    if let ExprKind::Variable(name) = &target.kind {
+        if let Some(Symbol::Variable(var)) = checker.scopes.lookup(name) {
+            if !var.is_mutable {
+                checker.errors.push(SemanticError::AssignToImmutable {
+                    name: name.clone(),
+                    span: target.span.clone(),
+                });
+                return;
+            }
+        }
+    }
+
+    // 重载的索引运算 obj[key] 是方法调用的结果，不能作为左值
+    if let ExprKind::Index { array, .. } = &mut target.kind {
+        if let Ok(Type::Struct(_)) = checker.infer_type(array) {
+            checker.errors.push(SemanticError::InvalidAssignmentTarget {
+                span: target.span.clone(),
+            });
+            return;
+        }
+    }
+
+    narrow_int_literal(&target_ty, value);
+    wrap_trait_object(&target_ty, value);
     let value_ty = match checker.infer_type(value) {
         Ok(ty) => ty,
         Err(e) => {
@@ -196,11 +337,9 @@ fn check_assignment(
     };
 
     if !is_compatible(&target_ty, &value_ty) {
-        checker.errors.push(SemanticError::TypeMismatch {
-            expected: target_ty.to_string(),
-            found: value_ty.to_string(),
-            span: span.clone(),
-        });
+        checker
+            .errors
+            .push(TypeChecker::mismatch_error(&target_ty, &value_ty, span));
     }
 }
 
@@ -218,18 +357,22 @@ fn check_return(
     };
 
     match (value, &expected) {
-        (Some(expr), _) => match checker.infer_type(expr) {
-            Ok(actual) => {
-                if !is_compatible(&expected, &actual) {
-                    checker.errors.push(SemanticError::ReturnTypeMismatch {
-                        expected: expected.to_string(),
-                        found: actual.to_string(),
-                        span: span.clone(),
-                    });
+        (Some(expr), _) => {
+            narrow_int_literal(&expected, expr);
+            wrap_trait_object(&expected, expr);
+            match checker.infer_type(expr) {
+                Ok(actual) => {
+                    if !is_compatible(&expected, &actual) {
+                        checker.errors.push(SemanticError::ReturnTypeMismatch {
+                            expected: expected.to_string(),
+                            found: actual.to_string(),
+                            span: span.clone(),
+                        });
+                    }
                 }
+                Err(e) => checker.errors.push(e),
             }
-            Err(e) => checker.errors.push(e),
-        },
+        }
         (None, ty) if *ty != Type::Void => {
             checker.errors.push(SemanticError::ReturnTypeMismatch {
                 expected: expected.to_string(),
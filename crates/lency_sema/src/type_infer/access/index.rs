@@ -1,6 +1,7 @@
 use super::super::TypeInferer;
 use crate::error::SemanticError;
-use lency_syntax::ast::{Expr, ExprKind, Literal, Type};
+use crate::symbol::Symbol;
+use lency_syntax::ast::{BinaryOp, Expr, ExprKind, Literal, Type, UnaryOp};
 
 impl<'a> TypeInferer<'a> {
     /// 推导数组字面量类型
@@ -17,26 +18,87 @@ impl<'a> TypeInferer<'a> {
             });
         }
 
-        // 推导第一个元素的类型作为数组元素类型
-        let first_ty = self.infer(&mut elements[0])?;
+        // 所有元素统一为同一类型（允许 int 提升为 float）
+        let element_type = self.unify_element_types(elements)?;
+
+        // 返回固定大小数组类型: [T; N]
+        Ok(Type::Array {
+            element_type: Box::new(element_type),
+            size: elements.len(),
+        })
+    }
+
+    /// 推导重复初始化 `[v; N]` / `vec![v; n]` 的类型
+    ///
+    /// 数组的长度必须是编译期常量（字面量或已由 resolver 替换的整数常量），
+    /// Vec 的长度可以是任意 int 表达式
+    pub(crate) fn infer_repeat_impl(
+        &mut self,
+        value: &mut Expr,
+        count: &mut Expr,
+        is_vec: bool,
+    ) -> Result<Type, SemanticError> {
+        let element_type = self.infer(value)?;
+        let count_ty = self.infer(count)?;
+        if count_ty.int_bits().is_none() {
+            return Err(SemanticError::TypeMismatch {
+                expected: "int".to_string(),
+                found: count_ty.to_string(),
+                span: count.span.clone(),
+            });
+        }
+
+        if is_vec {
+            return Ok(Type::Vec(Box::new(element_type)));
+        }
+
+        match &count.kind {
+            ExprKind::Literal(Literal::Int(n)) if *n >= 0 => Ok(Type::Array {
+                element_type: Box::new(element_type),
+                size: *n as usize,
+            }),
+            ExprKind::Variable(name) => Err(SemanticError::InvalidArraySize {
+                name: name.clone(),
+                span: count.span.clone(),
+            }),
+            _ => Err(SemanticError::InvalidArraySize {
+                name: "repeat count".to_string(),
+                span: count.span.clone(),
+            }),
+        }
+    }
+
+    /// 推导数组 / Vec 字面量元素的公共类型
+    ///
+    /// 元素类型必须一致，唯一的例外是 int 与 float 混合时统一提升为 float。
+    /// `elements` 不能为空。
+    pub(crate) fn unify_element_types(
+        &mut self,
+        elements: &mut [Expr],
+    ) -> Result<Type, SemanticError> {
+        let mut common_type = self.infer(&mut elements[0])?;
 
-        // 检查所有元素类型一致
         for elem in elements.iter_mut().skip(1) {
             let elem_ty = self.infer(elem)?;
-            if elem_ty != first_ty {
+
+            if common_type == elem_ty {
+                continue;
+            }
+
+            // 类型提升规则
+            if common_type == Type::Int && elem_ty == Type::Float {
+                common_type = Type::Float;
+            } else if common_type == Type::Float && elem_ty == Type::Int {
+                // Keep Float
+            } else {
                 return Err(SemanticError::TypeMismatch {
-                    expected: first_ty.to_string(),
+                    expected: common_type.to_string(),
                     found: elem_ty.to_string(),
                     span: elem.span.clone(),
                 });
             }
         }
-
-        // 返回固定大小数组类型: [T; N]
-        Ok(Type::Array {
-            element_type: Box::new(first_ty),
-            size: elements.len(),
-        })
+        Ok(common_type)
     }
 
     /// 推导数组索引类型
@@ -49,6 +111,11 @@ impl<'a> TypeInferer<'a> {
         let array_ty = self.infer(array)?;
         let index_ty = self.infer(index)?;
 
+        // 用户类型实现了 Index trait：obj[i] 调用 obj.index(i)，类型取方法返回值
+        if let Type::Struct(name) = &array_ty {
+            return self.infer_index_overload(name, &index_ty, index, span);
+        }
+
         // 索引必须是 int 类型
         if index_ty != Type::Int {
             return Err(SemanticError::TypeMismatch {
@@ -58,27 +125,15 @@ impl<'a> TypeInferer<'a> {
             });
         }
 
-        // 编译期边界检查：如果索引是常量，检查是否越界
-        if let ExprKind::Literal(Literal::Int(idx_val)) = &index.kind {
-            if let Type::Array { size, .. } = &array_ty {
-                // 检查负数索引
-                if *idx_val < 0 {
-                    return Err(SemanticError::ArrayIndexOutOfBounds {
-                        index: *idx_val,
-                        size: *size,
-                        span: index.span.clone(),
-                    });
-                }
-
-                // 检查越界
-                let idx_usize = *idx_val as usize;
-                if idx_usize >= *size {
-                    return Err(SemanticError::ArrayIndexOutOfBounds {
-                        index: *idx_val,
-                        size: *size,
-                        span: index.span.clone(),
-                    });
-                }
+        // 编译期边界检查：如果索引是常量（如 `2`、`-1`、`1 + 4`），检查是否越界
+        // 非常量索引仍由代码生成阶段插入运行时检查
+        if let (Some(idx_val), Type::Array { size, .. }) = (constant_index(index), &array_ty) {
+            if idx_val < 0 || idx_val as usize >= *size {
+                return Err(SemanticError::ArrayIndexOutOfBounds {
+                    index: idx_val,
+                    size: *size,
+                    span: index.span.clone(),
+                });
             }
         }
 
@@ -118,3 +173,62 @@ impl<'a> TypeInferer<'a> {
         }
     }
 }
+
+impl<'a> TypeInferer<'a> {
+    /// 推导用户类型的索引运算 `obj[key]`
+    ///
+    /// 结构体必须实现 Index trait（见 std.ops），key 类型需与 index 方法参数兼容
+    fn infer_index_overload(
+        &self,
+        struct_name: &str,
+        key_ty: &Type,
+        key: &Expr,
+        span: &std::ops::Range<usize>,
+    ) -> Result<Type, SemanticError> {
+        let method = match self.lookup(struct_name) {
+            Some(Symbol::Struct(s)) if s.implements("Index") => s.get_method("index"),
+            _ => None,
+        };
+        let Some(method) = method else {
+            return Err(SemanticError::TypeMismatch {
+                expected: "array, list, string or type implementing Index".to_string(),
+                found: struct_name.to_string(),
+                span: span.clone(),
+            });
+        };
+
+        if let Some((_, param_ty)) = method.params.first() {
+            if !crate::type_infer::is_compatible(param_ty, key_ty) {
+                return Err(SemanticError::TypeMismatch {
+                    expected: param_ty.to_string(),
+                    found: key_ty.to_string(),
+                    span: key.span.clone(),
+                });
+            }
+        }
+        Ok(method.return_type.clone())
+    }
+}
+
+/// 对整数常量索引求值：支持字面量、取负和整数四则运算
+///
+/// 溢出或除零时返回 `None`，交给运行时处理。
+fn constant_index(expr: &Expr) -> Option<i64> {
+    match &expr.kind {
+        ExprKind::Literal(Literal::Int(v)) => Some(*v),
+        ExprKind::Unary(UnaryOp::Neg, operand) => constant_index(operand)?.checked_neg(),
+        ExprKind::Binary(left, op, right) => {
+            let l = constant_index(left)?;
+            let r = constant_index(right)?;
+            match op {
+                BinaryOp::Add => l.checked_add(r),
+                BinaryOp::Sub => l.checked_sub(r),
+                BinaryOp::Mul => l.checked_mul(r),
+                BinaryOp::Div => l.checked_div(r),
+                BinaryOp::Mod => l.checked_rem(r),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
@@ -97,7 +97,9 @@ impl<'a> TypeInferer<'a> {
                 {
                     // 查找字段
                     if let Some(field_info) = struct_sym.get_field(name) {
-                        return Ok(field_info.ty.clone());
+                        let field_ty = field_info.ty.clone();
+                        self.record_member_resolution(struct_name, name, span);
+                        return Ok(field_ty);
                     } else {
                         return Err(SemanticError::UndefinedField {
                             class: struct_name.clone(),
@@ -134,10 +136,10 @@ impl<'a> TypeInferer<'a> {
                             subst_map.insert(param.name.clone(), arg.clone());
                         }
 
-                        return Ok(crate::type_infer::substitute_type(
-                            &field_info.ty,
-                            &subst_map,
-                        ));
+                        let field_ty =
+                            crate::type_infer::substitute_type(&field_info.ty, &subst_map);
+                        self.record_member_resolution(struct_name, name, span);
+                        return Ok(field_ty);
                     } else {
                         return Err(SemanticError::UndefinedField {
                             class: struct_name.clone(),
@@ -152,6 +154,17 @@ impl<'a> TypeInferer<'a> {
                 })
             }
 
+            // 匿名记录的字段访问
+            Type::Record(fields) => fields
+                .iter()
+                .find(|(field, _)| field == name)
+                .map(|(_, ty)| ty.clone())
+                .ok_or_else(|| SemanticError::UndefinedField {
+                    class: obj_ty.to_string(),
+                    field: name.to_string(),
+                    span: span.clone(),
+                }),
+
             // 数组的 .length 属性
             Type::Array { .. } => {
                 if name == "length" {
@@ -7,6 +7,19 @@ use crate::error::SemanticError;
 use lency_syntax::ast::{Expr, Type};
 
 impl<'a> TypeInferer<'a> {
+    /// 记录成员访问 `obj.name` 的解析结果（供 LSP 跳转使用）
+    pub(crate) fn record_member_resolution(
+        &mut self,
+        owner: &str,
+        name: &str,
+        get_span: &std::ops::Range<usize>,
+    ) {
+        if let Some(owner_id) = self.scopes.lookup_id_from(owner, self.current_scope) {
+            self.scopes
+                .record_member_resolution(owner_id, name, get_span);
+        }
+    }
+
     pub(crate) fn infer_variable(
         &self,
         name: &str,
@@ -41,6 +54,15 @@ impl<'a> TypeInferer<'a> {
         self.infer_array_impl(elements, span)
     }
 
+    pub(crate) fn infer_repeat(
+        &mut self,
+        value: &mut Expr,
+        count: &mut Expr,
+        is_vec: bool,
+    ) -> Result<Type, SemanticError> {
+        self.infer_repeat_impl(value, count, is_vec)
+    }
+
     pub(crate) fn infer_index(
         &mut self,
         array: &mut Expr,
@@ -20,11 +20,18 @@ impl<'a> TypeInferer<'a> {
                 match symbol.ty() {
                     Some(ty) => Ok(ty.clone()),
                     None => {
-                        // 函数名不是值类型
+                        // 函数名作为值使用时得到函数指针，类型为 ret(params...)
                         if let Symbol::Function(func) = symbol {
-                            // 返回函数类型的占位（暂时用 Void 表示）
-                            // 未来可扩展为 FunctionType
-                            Ok(func.return_type.clone())
+                            if !func.generic_params.is_empty() {
+                                return Err(SemanticError::CannotInferType {
+                                    name: format!("type arguments of generic function '{}'", name),
+                                    span: span.clone(),
+                                });
+                            }
+                            Ok(Type::Function {
+                                param_types: func.params.iter().map(|(_, ty)| ty.clone()).collect(),
+                                return_type: Box::new(func.return_type.clone()),
+                            })
                         } else {
                             Ok(Type::Error)
                         }
@@ -6,6 +6,15 @@ use lency_syntax::ast::{Expr, ExprKind, Type};
 impl<'a> TypeInferer<'a> {
     pub(crate) fn infer_adt(&mut self, expr: &mut Expr) -> Result<Type, SemanticError> {
         match &mut expr.kind {
+            ExprKind::RecordLiteral(fields) => {
+                check_duplicate_fields(fields)?;
+
+                let mut field_types = Vec::with_capacity(fields.len());
+                for (name, value) in fields.iter_mut() {
+                    field_types.push((name.clone(), self.infer(value)?));
+                }
+                Ok(Type::record(field_types))
+            }
             ExprKind::StructLiteral { type_, fields } => {
                 // 解构类型名称和泛型参数
                 let (type_name, generic_args) = match type_ {
@@ -45,16 +54,20 @@ impl<'a> TypeInferer<'a> {
                         subst_map.insert(param.name.clone(), arg.clone());
                     }
 
+                    check_duplicate_fields(fields)?;
+
                     // 检查所有字段
-                    for (field_name, field_expr) in fields {
+                    for (field_name, field_expr) in fields.iter_mut() {
                         // 验证字段存在
                         if let Some(field_info) = struct_fields.get(field_name) {
-                            // 推导字段值的类型
-                            let expr_ty = self.infer(field_expr)?;
-
                             // 获取期望类型并应用泛型替换
                             let expected_ty = substitute_type(&field_info.ty, &subst_map);
 
+                            // 推导字段值的类型
+                            super::narrow_int_literal(&expected_ty, field_expr);
+                            super::wrap_trait_object(&expected_ty, field_expr);
+                            let expr_ty = self.infer(field_expr)?;
+
                             if !is_compatible(&expected_ty, &expr_ty) {
                                 return Err(SemanticError::TypeMismatch {
                                     expected: expected_ty.to_string(),
@@ -70,6 +83,28 @@ impl<'a> TypeInferer<'a> {
                             });
                         }
                     }
+
+                    // 省略的字段：有默认值的填入字面量，其余逐个报缺失
+                    // 缺字段不影响字面量的类型，记录错误后继续推导
+                    let mut omitted: Vec<_> = struct_fields
+                        .iter()
+                        .filter(|(name, _)| !fields.iter().any(|(n, _)| n == *name))
+                        .collect();
+                    omitted.sort_by_key(|(name, _)| *name);
+                    for (name, info) in omitted {
+                        match &info.default {
+                            Some(default) => {
+                                let mut default = default.clone();
+                                super::narrow_int_literal(&info.ty, &mut default);
+                                fields.push((name.clone(), default));
+                            }
+                            None => self.errors.push(SemanticError::MissingField {
+                                class: type_name.clone(),
+                                field: name.clone(),
+                                span: expr.span.clone(),
+                            }),
+                        }
+                    }
                     Ok(type_.clone())
                 } else {
                     Err(SemanticError::UndefinedType {
@@ -84,28 +119,7 @@ impl<'a> TypeInferer<'a> {
                     return Ok(Type::Vec(Box::new(Type::Void)));
                 }
 
-                let mut common_type = self.infer(&mut elements[0])?;
-
-                for elem in elements.iter_mut().skip(1) {
-                    let elem_ty = self.infer(elem)?;
-
-                    if common_type == elem_ty {
-                        continue;
-                    }
-
-                    // 类型提升规则
-                    if common_type == Type::Int && elem_ty == Type::Float {
-                        common_type = Type::Float;
-                    } else if common_type == Type::Float && elem_ty == Type::Int {
-                        // Keep Float
-                    } else {
-                        return Err(SemanticError::TypeMismatch {
-                            expected: common_type.to_string(),
-                            found: elem_ty.to_string(),
-                            span: elem.span.clone(),
-                        });
-                    }
-                }
+                let common_type = self.unify_element_types(elements)?;
                 Ok(Type::Vec(Box::new(common_type)))
             }
             ExprKind::GenericInstantiation { base: _, args: _ } => {
@@ -116,31 +130,39 @@ impl<'a> TypeInferer<'a> {
             }
             // Result 相关表达式
             ExprKind::Try(inner) => {
-                // expr? 解包 Result，返回 ok_type
+                // expr? 解包 Result，返回 ok_type；错误类型需能传播到所在函数的返回类型
                 let inner_ty = self.infer(inner)?;
-                match inner_ty {
-                    Type::Result { ok_type, .. } => Ok(*ok_type),
-                    _ => Err(SemanticError::TypeMismatch {
+                let Some((ok_type, err_type)) = result_parts(&inner_ty) else {
+                    return Err(SemanticError::TypeMismatch {
                         expected: "Result<T, E>".to_string(),
                         found: inner_ty.to_string(),
                         span: expr.span.clone(),
-                    }),
+                    });
+                };
+                let target_err = self
+                    .return_type
+                    .as_ref()
+                    .and_then(result_parts)
+                    .map(|(_, err)| err);
+                if let Some(target_err) = target_err {
+                    self.check_error_propagation(&err_type, &target_err, &expr.span)?;
                 }
+                Ok(ok_type)
             }
             ExprKind::Ok(inner) => {
-                // Ok(x) 的类型是 Result<typeof(x), Error>
+                // Ok(x) 的类型是 Result<typeof(x), void>，错误类型由上下文决定
                 let inner_ty = self.infer(inner)?;
                 Ok(Type::Result {
                     ok_type: Box::new(inner_ty),
-                    err_type: Box::new(Type::Struct("Error".to_string())),
+                    err_type: Box::new(Type::Void),
                 })
             }
             ExprKind::Err(inner) => {
-                // Err(msg) 的类型需要知道 ok_type，暂时返回 Result<void, Error>
-                self.infer(inner)?;
+                // Err(e) 的类型是 Result<void, typeof(e)>，ok_type 由上下文决定
+                let err_ty = self.infer(inner)?;
                 Ok(Type::Result {
                     ok_type: Box::new(Type::Void),
-                    err_type: Box::new(Type::Struct("Error".to_string())),
+                    err_type: Box::new(err_ty),
                 })
             }
             // 闭包
@@ -176,6 +198,53 @@ impl<'a> TypeInferer<'a> {
             _ => unreachable!("Not an ADT expression"),
         }
     }
+
+    /// `?` 传播错误：错误类型需与所在函数的错误类型兼容，
+    /// 或目标错误类型提供关联函数 `Target::from(Source)` 用于转换
+    pub(crate) fn check_error_propagation(
+        &self,
+        source: &Type,
+        target: &Type,
+        span: &std::ops::Range<usize>,
+    ) -> Result<(), SemanticError> {
+        if matches!(source, Type::Void) || super::is_compatible(target, source) {
+            return Ok(());
+        }
+        let converter = match target {
+            Type::Struct(name) => match self.lookup(name) {
+                Some(Symbol::Struct(s)) => s.get_method("from"),
+                Some(Symbol::Enum(e)) => e.methods.get("from"),
+                _ => None,
+            },
+            _ => None,
+        };
+        let convertible = converter.is_some_and(|f| {
+            f.is_static
+                && f.params.len() == 1
+                && super::is_compatible(&f.params[0].1, source)
+                && super::is_compatible(target, &f.return_type)
+        });
+        if convertible {
+            Ok(())
+        } else {
+            Err(SemanticError::IncompatibleErrorType {
+                from: source.to_string(),
+                to: target.to_string(),
+                span: span.clone(),
+            })
+        }
+    }
+}
+
+/// 拆出 Result 的 (ok_type, err_type)，兼容 `T!` 与 `Result<T, E>` 两种写法
+pub(crate) fn result_parts(ty: &Type) -> Option<(Type, Type)> {
+    match ty {
+        Type::Result { ok_type, err_type } => Some((*ok_type.clone(), *err_type.clone())),
+        Type::Generic(name, args) if name == "Result" && args.len() == 2 => {
+            Some((args[0].clone(), args[1].clone()))
+        }
+        _ => None,
+    }
 }
 
 /// 替换类型中的泛型参数
@@ -211,6 +280,151 @@ pub(crate) fn substitute_type(
                 ty.clone()
             }
         }
+        Type::Record(fields) => Type::Record(
+            fields
+                .iter()
+                .map(|(name, field_ty)| (name.clone(), substitute_type(field_ty, mapping)))
+                .collect(),
+        ),
+        Type::Result { ok_type, err_type } => Type::Result {
+            ok_type: Box::new(substitute_type(ok_type, mapping)),
+            err_type: Box::new(substitute_type(err_type, mapping)),
+        },
+        Type::Function {
+            param_types,
+            return_type,
+        } => Type::Function {
+            param_types: param_types
+                .iter()
+                .map(|param| substitute_type(param, mapping))
+                .collect(),
+            return_type: Box::new(substitute_type(return_type, mapping)),
+        },
         _ => ty.clone(),
     }
 }
+
+/// 将形参类型与实参类型对齐，求解其中出现的泛型参数（substitute_type 的逆过程）
+///
+/// 同一泛型参数被绑定到不同类型时返回 Err((已绑定类型, 新类型))；
+/// 含泛型参数的形参与实参形状不一致时返回 Err((形参类型, 实参类型))。
+/// 不含泛型参数的部分不在这里检查，由调用方在代入后统一做兼容性检查。
+pub(crate) fn unify_generic(
+    param_ty: &Type,
+    arg_ty: &Type,
+    generic_names: &[String],
+    mapping: &mut std::collections::HashMap<String, Type>,
+) -> Result<(), (Type, Type)> {
+    match (param_ty, arg_ty) {
+        // null 字面量和出错的实参不提供任何信息
+        (_, Type::Error) => Ok(()),
+        (_, Type::Nullable(inner)) if matches!(**inner, Type::Error) => Ok(()),
+        (Type::GenericParam(name) | Type::Struct(name), _) if generic_names.contains(name) => {
+            match mapping.get(name) {
+                Some(bound) if bound != arg_ty => Err((bound.clone(), arg_ty.clone())),
+                Some(_) => Ok(()),
+                None => {
+                    mapping.insert(name.clone(), arg_ty.clone());
+                    Ok(())
+                }
+            }
+        }
+        (Type::Vec(p), Type::Vec(a)) | (Type::Nullable(p), Type::Nullable(a)) => {
+            unify_generic(p, a, generic_names, mapping)
+        }
+        // 可空形参接受非空实参：T? 与 int 对齐时 T = int
+        (Type::Nullable(p), a) => unify_generic(p, a, generic_names, mapping),
+        (
+            Type::Array {
+                element_type: p, ..
+            },
+            Type::Array {
+                element_type: a, ..
+            },
+        ) => unify_generic(p, a, generic_names, mapping),
+        (Type::Generic(p_name, p_args), Type::Generic(a_name, a_args))
+            if p_name == a_name && p_args.len() == a_args.len() =>
+        {
+            for (p, a) in p_args.iter().zip(a_args) {
+                unify_generic(p, a, generic_names, mapping)?;
+            }
+            Ok(())
+        }
+        (
+            Type::Function {
+                param_types: p_params,
+                return_type: p_ret,
+            },
+            Type::Function {
+                param_types: a_params,
+                return_type: a_ret,
+            },
+        ) if p_params.len() == a_params.len() => {
+            for (p, a) in p_params.iter().zip(a_params) {
+                unify_generic(p, a, generic_names, mapping)?;
+            }
+            unify_generic(p_ret, a_ret, generic_names, mapping)
+        }
+        (
+            Type::Result {
+                ok_type: p_ok,
+                err_type: p_err,
+            },
+            Type::Result {
+                ok_type: a_ok,
+                err_type: a_err,
+            },
+        ) => {
+            // Ok/Err 构造器产生的 Void 一侧不提供信息
+            if !matches!(**a_ok, Type::Void) {
+                unify_generic(p_ok, a_ok, generic_names, mapping)?;
+            }
+            if !matches!(**a_err, Type::Void) {
+                unify_generic(p_err, a_err, generic_names, mapping)?;
+            }
+            Ok(())
+        }
+        (param, arg) if mentions_generic(param, generic_names) => Err((param.clone(), arg.clone())),
+        _ => Ok(()),
+    }
+}
+
+/// 类型中是否出现了给定的泛型参数
+fn mentions_generic(ty: &Type, generic_names: &[String]) -> bool {
+    match ty {
+        Type::GenericParam(name) | Type::Struct(name) => generic_names.contains(name),
+        Type::Vec(inner) | Type::Nullable(inner) => mentions_generic(inner, generic_names),
+        Type::Array { element_type, .. } => mentions_generic(element_type, generic_names),
+        Type::Generic(_, args) => args.iter().any(|arg| mentions_generic(arg, generic_names)),
+        Type::Record(fields) => fields
+            .iter()
+            .any(|(_, field_ty)| mentions_generic(field_ty, generic_names)),
+        Type::Result { ok_type, err_type } => {
+            mentions_generic(ok_type, generic_names) || mentions_generic(err_type, generic_names)
+        }
+        Type::Function {
+            param_types,
+            return_type,
+        } => {
+            param_types
+                .iter()
+                .any(|param| mentions_generic(param, generic_names))
+                || mentions_generic(return_type, generic_names)
+        }
+        _ => false,
+    }
+}
+
+/// 字面量中同名字段只能出现一次
+fn check_duplicate_fields(fields: &[(String, Expr)]) -> Result<(), SemanticError> {
+    for (i, (name, value)) in fields.iter().enumerate() {
+        if let Some((_, prev)) = fields[..i].iter().find(|(n, _)| n == name) {
+            return Err(SemanticError::DuplicateDefinition {
+                name: name.clone(),
+                span: value.span.clone(),
+                previous_span: prev.span.clone(),
+            });
+        }
+    }
+    Ok(())
+}
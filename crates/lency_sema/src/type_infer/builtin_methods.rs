@@ -0,0 +1,184 @@
+//! 内置方法：Result、可空类型与 Vec 上不经 impl 查找的方法
+
+use super::call::method_arity_error;
+use super::TypeInferer;
+use crate::error::SemanticError;
+use lency_syntax::ast::{Expr, Type};
+
+impl<'a> TypeInferer<'a> {
+    /// Result 内置方法的类型检查；不是内置方法时返回 None，继续查找 impl Result 中的方法
+    pub(super) fn infer_result_method(
+        &mut self,
+        name: &str,
+        ok_type: &Type,
+        err_type: &Type,
+        args: &mut [Expr],
+        span: &std::ops::Range<usize>,
+    ) -> Result<Option<Type>, SemanticError> {
+        let expected_args = match name {
+            "is_ok" | "is_err" | "unwrap" => 0,
+            "expect" | "unwrap_or" | "map" => 1,
+            _ => return Ok(None),
+        };
+        if args.len() != expected_args {
+            let receiver = Type::Result {
+                ok_type: Box::new(ok_type.clone()),
+                err_type: Box::new(err_type.clone()),
+            };
+            return Err(method_arity_error(
+                &receiver,
+                name,
+                expected_args,
+                args,
+                span,
+            ));
+        }
+
+        let ty = match name {
+            "is_ok" | "is_err" => Type::Bool,
+            "unwrap" => ok_type.clone(),
+            "expect" => {
+                self.check_builtin_arg(&Type::String, &mut args[0])?;
+                ok_type.clone()
+            }
+            "unwrap_or" => {
+                self.check_builtin_arg(ok_type, &mut args[0])?;
+                ok_type.clone()
+            }
+            _ => {
+                // map(f): f 接收 ok 值，结果类型为 Result<f 的返回类型, E>
+                let arg_span = args[0].span.clone();
+                match self.infer(&mut args[0])? {
+                    Type::Function {
+                        param_types,
+                        return_type,
+                    } if param_types.len() == 1
+                        && super::is_compatible(&param_types[0], ok_type) =>
+                    {
+                        Type::Result {
+                            ok_type: return_type,
+                            err_type: Box::new(err_type.clone()),
+                        }
+                    }
+                    found => {
+                        return Err(SemanticError::TypeMismatch {
+                            expected: format!("a function taking '{}'", ok_type),
+                            found: found.to_string(),
+                            span: arg_span,
+                        })
+                    }
+                }
+            }
+        };
+        Ok(Some(ty))
+    }
+
+    /// 可空类型内置方法的类型检查：value_or(default) 返回 T，map(f) 返回 U?
+    pub(super) fn infer_nullable_method(
+        &mut self,
+        name: &str,
+        inner: &Type,
+        args: &mut [Expr],
+        span: &std::ops::Range<usize>,
+    ) -> Result<Type, SemanticError> {
+        if args.len() != 1 {
+            let receiver = Type::Nullable(Box::new(inner.clone()));
+            return Err(method_arity_error(&receiver, name, 1, args, span));
+        }
+        if name == "value_or" {
+            self.check_builtin_arg(inner, &mut args[0])?;
+            return Ok(inner.clone());
+        }
+
+        let arg_span = args[0].span.clone();
+        match self.infer(&mut args[0])? {
+            Type::Function {
+                param_types,
+                return_type,
+            } if param_types.len() == 1 && super::is_compatible(&param_types[0], inner) => {
+                Ok(match *return_type {
+                    ty @ Type::Nullable(_) => ty,
+                    ty => Type::Nullable(Box::new(ty)),
+                })
+            }
+            found => Err(SemanticError::TypeMismatch {
+                expected: format!("a function taking '{}'", inner),
+                found: found.to_string(),
+                span: arg_span,
+            }),
+        }
+    }
+
+    /// 内置方法的单个实参检查
+    pub(super) fn check_builtin_arg(
+        &mut self,
+        expected: &Type,
+        arg: &mut Expr,
+    ) -> Result<(), SemanticError> {
+        let found = self.infer(arg)?;
+        if super::is_compatible(expected, &found) {
+            Ok(())
+        } else {
+            Err(SemanticError::TypeMismatch {
+                expected: expected.to_string(),
+                found: found.to_string(),
+                span: arg.span.clone(),
+            })
+        }
+    }
+
+    /// Vec 内置方法的类型检查
+    pub(super) fn infer_vec_method(
+        &mut self,
+        inner: &Type,
+        name: &str,
+        args: &mut [Expr],
+        span: &std::ops::Range<usize>,
+    ) -> Result<Type, SemanticError> {
+        let (expected, ty) = match name {
+            "push" => (1, Type::Void),
+            "set" => (2, Type::Void),
+            "pop" => (0, inner.clone()),
+            "get" => (1, inner.clone()),
+            "len" => (0, Type::Int),
+            "sort_by" => (1, Type::Void),
+            _ => {
+                return Err(SemanticError::UndefinedMethod {
+                    class: "Vec".to_string(),
+                    method: name.to_string(),
+                    span: span.clone(),
+                })
+            }
+        };
+        if args.len() != expected {
+            let receiver = Type::Vec(Box::new(inner.clone()));
+            return Err(method_arity_error(&receiver, name, expected, args, span));
+        }
+        // 存入 Vec<dyn T> 的元素转换为 Trait 对象
+        let stored_arg = match name {
+            "push" => Some(0),
+            "set" => Some(1),
+            _ => None,
+        };
+        if let (Some(i), Some(_)) = (stored_arg, super::trait_object_name(inner)) {
+            super::wrap_trait_object(inner, &mut args[i]);
+            self.check_builtin_arg(inner, &mut args[i])?;
+        }
+        if name == "sort_by" {
+            // 比较器以函数指针形式传给运行时: int(T, T)
+            let comparator = Type::Function {
+                param_types: vec![inner.clone(), inner.clone()],
+                return_type: Box::new(Type::Int),
+            };
+            let arg_ty = self.infer(&mut args[0])?;
+            if !super::is_compatible(&comparator, &arg_ty) {
+                return Err(SemanticError::TypeMismatch {
+                    expected: comparator.to_string(),
+                    found: arg_ty.to_string(),
+                    span: args[0].span.clone(),
+                });
+            }
+        }
+        Ok(ty)
+    }
+}
@@ -8,13 +8,53 @@ impl<'a> TypeInferer<'a> {
     pub(crate) fn infer_call(
         &mut self,
         callee: &mut Expr,
-        _args: &mut [Expr],
+        args: &mut [Expr],
         span: &std::ops::Range<usize>,
     ) -> Result<Type, SemanticError> {
+        let callee_span = callee.span.clone();
+
+        // 未写 turbofish 的泛型函数调用：从实参推导类型参数，并改写为 f::<...>(...)
+        if let Some(ty) = self.infer_implicit_generic_call(callee, args, span)? {
+            return Ok(ty);
+        }
+
         match &mut callee.kind {
             ExprKind::Variable(name) => {
                 match self.lookup(name) {
-                    Some(Symbol::Function(func)) => Ok(func.return_type.clone()),
+                    Some(Symbol::Function(func)) => {
+                        let return_type = func.return_type.clone();
+                        let is_variadic = func.is_variadic;
+                        let param_types: Vec<Type> =
+                            func.params.iter().map(|(_, ty)| ty.clone()).collect();
+                        // 可变参数函数至少需要全部固定参数
+                        if args.len() < param_types.len()
+                            || (!is_variadic && args.len() > param_types.len())
+                        {
+                            return Err(SemanticError::ArgumentCountMismatch {
+                                name: name.clone(),
+                                expected: param_types.len(),
+                                found: args.len(),
+                                span: span.clone(),
+                            });
+                        }
+                        // 实参中的表达式（如结构体字面量）同样需要推导；dyn T 形参处的实参转换为 Trait 对象
+                        for (i, arg) in args.iter_mut().enumerate() {
+                            let Some(param_ty) = param_types.get(i) else {
+                                // 可变参数部分按 C 的默认实参提升传递，只接受基础类型
+                                let ty = self.infer(arg)?;
+                                if !is_variadic_arg_type(&ty) {
+                                    return Err(SemanticError::InvalidVariadicArgument {
+                                        ty: ty.to_string(),
+                                        span: arg.span.clone(),
+                                    });
+                                }
+                                continue;
+                            };
+                            super::wrap_trait_object(param_ty, arg);
+                            self.infer(arg)?;
+                        }
+                        Ok(return_type)
+                    }
                     Some(Symbol::Struct(s)) => {
                         // Constructor
                         Ok(Type::Struct(s.name.clone()))
@@ -53,40 +93,7 @@ impl<'a> TypeInferer<'a> {
             ExprKind::GenericInstantiation {
                 base,
                 args: type_args,
-            } => {
-                // 泛型函数调用
-                if let ExprKind::Variable(name) = &base.kind {
-                    match self.lookup(name) {
-                        Some(Symbol::Function(f)) => {
-                            // Check generic arg count
-                            if f.generic_params.len() != type_args.len() {
-                                return Err(SemanticError::ArgumentCountMismatch {
-                                    name: format!("{} generic args", name),
-                                    expected: f.generic_params.len(),
-                                    found: type_args.len(),
-                                    span: span.clone(),
-                                });
-                            }
-                            // Subst map
-                            let mut map = std::collections::HashMap::new();
-                            for (param, arg_ty) in f.generic_params.iter().zip(type_args.iter()) {
-                                map.insert(param.name.as_str().to_string(), arg_ty.clone());
-                            }
-                            // Substitute return type
-                            Ok(crate::type_infer::substitute_type(&f.return_type, &map))
-                        }
-                        _ => Err(SemanticError::NotCallable {
-                            ty: "generic instantiation".into(),
-                            span: span.clone(),
-                        }),
-                    }
-                } else {
-                    Err(SemanticError::NotCallable {
-                        ty: "complex generic instantiation".into(),
-                        span: span.clone(),
-                    })
-                }
-            }
+            } => self.infer_generic_instantiation_call(base, type_args, span),
             ExprKind::Get { object, name } => {
                 // 1. Check Enum Constructor: Enum.Variant(...)
                 let enum_access = match &object.kind {
@@ -101,7 +108,7 @@ impl<'a> TypeInferer<'a> {
                     _ => None,
                 };
 
-                if let Some((enum_name, args)) = enum_access {
+                if let Some((enum_name, generic_args)) = enum_access {
                     if let Some(Symbol::Enum(enum_sym)) = self.lookup(&enum_name) {
                         // Sprint 15: Special handling for Result.Ok and Result.Err
                         let is_result_builtin =
@@ -109,16 +116,16 @@ impl<'a> TypeInferer<'a> {
 
                         if enum_sym.get_variant(name).is_some() || is_result_builtin {
                             // Check Generic Arity
-                            if !args.is_empty() {
-                                if enum_sym.generic_params.len() != args.len() {
+                            if !generic_args.is_empty() {
+                                if enum_sym.generic_params.len() != generic_args.len() {
                                     return Err(SemanticError::GenericArityMismatch {
                                         name: enum_name.clone(),
                                         expected: enum_sym.generic_params.len(),
-                                        found: args.len(),
+                                        found: generic_args.len(),
                                         span: span.clone(),
                                     });
                                 }
-                                return Ok(Type::Generic(enum_name, args));
+                                return Ok(Type::Generic(enum_name, generic_args));
                             } else {
                                 if !enum_sym.generic_params.is_empty() {
                                     return Err(SemanticError::GenericArityMismatch {
@@ -131,11 +138,60 @@ impl<'a> TypeInferer<'a> {
                                 return Ok(Type::Struct(enum_name));
                             }
                         }
+
+                        // 内置静态方法: Enum.from_int(i) -> Enum?，仅限纯 unit 枚举
+                        if name == "from_int"
+                            && enum_sym.generic_params.is_empty()
+                            && enum_sym.is_unit_only()
+                        {
+                            if args.len() != 1 {
+                                let receiver = Type::Struct(enum_sym.name.clone());
+                                return Err(method_arity_error(
+                                    &receiver, "from_int", 1, args, span,
+                                ));
+                            }
+                            let arg_ty = self.infer(&mut args[0])?;
+                            if !super::is_compatible(&Type::Int, &arg_ty) {
+                                return Err(SemanticError::TypeMismatch {
+                                    expected: "int".to_string(),
+                                    found: arg_ty.to_string(),
+                                    span: args[0].span.clone(),
+                                });
+                            }
+                            return Ok(Type::Nullable(Box::new(Type::Struct(enum_name))));
+                        }
+
+                        return Err(SemanticError::UndefinedMethod {
+                            class: enum_name,
+                            method: name.clone(),
+                            span: span.clone(),
+                        });
                     }
                 }
 
                 // 2. Check Method Call: obj.method(...)
                 let obj_ty = self.infer(object)?;
+                // T? 内置方法: value_or, map
+                if let Type::Nullable(inner) = &obj_ty {
+                    if is_nullable_builtin(name) {
+                        return self.infer_nullable_method(name, inner, args, span);
+                    }
+                }
+                // Result<T,E> 内置方法: is_ok, is_err, unwrap, expect, unwrap_or, map
+                if let Some((ok_type, err_type)) = super::adt::result_parts(&obj_ty) {
+                    if let Some(ty) =
+                        self.infer_result_method(name, &ok_type, &err_type, args, span)?
+                    {
+                        return Ok(ty);
+                    }
+                }
+                // int / float / bool 内置 to_string()
+                if name == "to_string" && is_to_string_primitive(&obj_ty) {
+                    if !args.is_empty() {
+                        return Err(method_arity_error(&obj_ty, name, 0, args, span));
+                    }
+                    return Ok(Type::String);
+                }
                 // Check if it's a type that supports method lookup via name (Structs, Enums, or Primitives)
                 let type_name_opt = match &obj_ty {
                     Type::Struct(n) => Some(n.clone()),
@@ -143,16 +199,8 @@ impl<'a> TypeInferer<'a> {
                     Type::Bool => Some("bool".to_string()),
                     Type::String => Some("string".to_string()),
                     Type::Float => Some("float".to_string()),
-                    // Sprint 15: Support Result<T,E> method calls
-                    // 内置方法: is_ok, is_err, unwrap_or
-                    Type::Result { ok_type, .. } => {
-                        // 检查是否为内置方法
-                        match name.as_str() {
-                            "is_ok" | "is_err" => return Ok(Type::Bool),
-                            "unwrap" | "expect" | "unwrap_or" => return Ok((**ok_type).clone()),
-                            _ => Some("Result".to_string()),
-                        }
-                    }
+                    // Sprint 15: Support Result<T,E> method calls (内置方法已在上方处理)
+                    Type::Result { .. } => Some("Result".to_string()),
                     // Sprint 15: Support Option<T> method calls
                     // 内置方法: is_some, is_none, unwrap, unwrap_or
                     Type::Generic(base_name, args) if base_name == "Option" => {
@@ -168,20 +216,6 @@ impl<'a> TypeInferer<'a> {
                             _ => Some(base_name.clone()),
                         }
                     }
-                    // Sprint 15: Support Result<T, E> method calls (Generic)
-                    Type::Generic(base_name, args) if base_name == "Result" => {
-                        match name.as_str() {
-                            "is_ok" | "is_err" => return Ok(Type::Bool),
-                            "unwrap" | "expect" | "unwrap_or" => {
-                                if args.len() == 2 {
-                                    return Ok(args[0].clone()); // Return T
-                                } else {
-                                    return Ok(Type::Error);
-                                }
-                            }
-                            _ => Some(base_name.clone()),
-                        }
-                    }
                     // 泛型实例化类型：使用基础名称查找方法
                     Type::Generic(base_name, _) => Some(base_name.clone()),
                     _ => None,
@@ -192,6 +226,29 @@ impl<'a> TypeInferer<'a> {
                     match symbol {
                         Some(Symbol::Struct(struct_sym)) => {
                             if let Some(method) = struct_sym.get_method(name) {
+                                // 别名导入 (import x as m) 的包装结构体只暴露 pub 函数
+                                if type_name.ends_with("__Module") && !method.is_public {
+                                    return Err(SemanticError::PrivateSymbol {
+                                        name: name.clone(),
+                                        span: callee_span,
+                                    });
+                                }
+                                self.record_member_resolution(&type_name, name, &callee_span);
+                                if args.len() != method.params.len() {
+                                    return Err(SemanticError::MethodArgumentCountMismatch {
+                                        receiver: obj_ty.to_string(),
+                                        method: name.clone(),
+                                        expected: method.params.len(),
+                                        found: args.len(),
+                                        signature: Some(method.signature()),
+                                        span: span.clone(),
+                                    });
+                                }
+                                // dyn T 形参处的实参先转换为 Trait 对象，再统一推导一遍
+                                for (arg, (_, param_ty)) in args.iter_mut().zip(&method.params) {
+                                    super::wrap_trait_object(param_ty, arg);
+                                    self.infer(arg)?;
+                                }
                                 // 对于泛型实例化类型，替换返回类型中的泛型参数
                                 let return_type = if let Type::Generic(_, type_args) = &obj_ty {
                                     let mut map = std::collections::HashMap::new();
@@ -216,6 +273,7 @@ impl<'a> TypeInferer<'a> {
                         // Sprint 15: Support method calls on Enum types (e.g., Result<T,E>)
                         Some(Symbol::Enum(enum_sym)) => {
                             if let Some(method) = enum_sym.methods.get(name) {
+                                self.record_member_resolution(&type_name, name, &callee_span);
                                 // 对于泛型Result<T,E>，替换返回类型中的泛型参数
                                 let return_type = if let Type::Result { ok_type, err_type } =
                                     &obj_ty
@@ -236,6 +294,14 @@ impl<'a> TypeInferer<'a> {
                                     method.return_type.clone()
                                 };
                                 Ok(return_type)
+                            } else if name == "discriminant"
+                                || (name == "as_int" && enum_sym.is_unit_only())
+                            {
+                                // 内置方法: 读取变体的判别值 (tag)；as_int 仅限纯 unit 枚举
+                                if !args.is_empty() {
+                                    return Err(method_arity_error(&obj_ty, name, 0, args, span));
+                                }
+                                Ok(Type::Int)
                             } else {
                                 Err(SemanticError::UndefinedMethod {
                                     class: type_name.clone(),
@@ -269,56 +335,46 @@ impl<'a> TypeInferer<'a> {
                 } else {
                     match obj_ty {
                         Type::GenericParam(param_name) => {
-                            if let Some(Symbol::GenericParam(gp)) = self.lookup(&param_name) {
-                                if let Some(bound_ty) = &gp.bound {
-                                    if let Type::Struct(trait_name) = bound_ty {
-                                        if let Some(Symbol::Trait(trait_sym)) =
-                                            self.lookup(trait_name)
-                                        {
-                                            if let Some(method) = trait_sym.get_method(name) {
-                                                Ok(method.return_type.clone())
-                                            } else {
-                                                Err(SemanticError::UndefinedMethod {
-                                                    class: format!("Trait {}", trait_name),
-                                                    method: name.clone(),
-                                                    span: span.clone(),
-                                                })
-                                            }
-                                        } else {
-                                            Err(SemanticError::UndefinedTrait {
-                                                name: trait_name.clone(),
-                                                span: span.clone(),
-                                            })
+                            let Some(Symbol::GenericParam(gp)) = self.lookup(&param_name) else {
+                                return Err(SemanticError::UndefinedType {
+                                    name: param_name.clone(),
+                                    span: span.clone(),
+                                });
+                            };
+                            let traits: Vec<String> =
+                                gp.bound_traits().map(str::to_string).collect();
+                            if traits.is_empty() {
+                                return Err(SemanticError::NotCallable {
+                                    ty: format!("Generic {} has no bounds", param_name),
+                                    span: span.clone(),
+                                });
+                            }
+                            // 依次在每个约束 Trait 中查找方法
+                            for trait_name in &traits {
+                                match self.lookup(trait_name) {
+                                    Some(Symbol::Trait(trait_sym)) => {
+                                        if let Some(method) = trait_sym.get_method(name) {
+                                            return Ok(method.return_type.clone());
                                         }
-                                    } else {
-                                        Err(SemanticError::NotCallable {
-                                            ty: format!("{:?}", bound_ty),
+                                    }
+                                    _ => {
+                                        return Err(SemanticError::UndefinedTrait {
+                                            name: trait_name.clone(),
                                             span: span.clone(),
                                         })
                                     }
-                                } else {
-                                    Err(SemanticError::NotCallable {
-                                        ty: format!("Generic {} has no bounds", param_name),
-                                        span: span.clone(),
-                                    })
                                 }
-                            } else {
-                                Err(SemanticError::UndefinedType {
-                                    name: param_name.clone(),
-                                    span: span.clone(),
-                                })
                             }
-                        }
-                        Type::Vec(inner) => match name.as_str() {
-                            "push" | "set" => Ok(Type::Void),
-                            "pop" | "get" => Ok(*inner),
-                            "len" => Ok(Type::Int),
-                            _ => Err(SemanticError::UndefinedMethod {
-                                class: "Vec".to_string(),
+                            Err(SemanticError::UndefinedMethod {
+                                class: format!("Trait {}", traits.join(" + ")),
                                 method: name.clone(),
                                 span: span.clone(),
-                            }),
-                        },
+                            })
+                        }
+                        Type::Vec(inner) => self.infer_vec_method(&inner, name, args, span),
+                        Type::TraitObject(trait_name) => {
+                            self.infer_trait_object_call(&trait_name, name, args, span)
+                        }
                         _ => Err(SemanticError::NotCallable {
                             ty: obj_ty.to_string(),
                             span: span.clone(),
@@ -326,10 +382,145 @@ impl<'a> TypeInferer<'a> {
                     }
                 }
             }
+            ExprKind::Path { type_name, member } => {
+                self.infer_associated_call(type_name, member, args, &callee_span, span)
+            }
             _ => {
                 // 复杂调用表达式（如 (f())()），暂时返回 Error
                 Ok(Type::Error)
             }
         }
     }
+
+    /// Trait 对象上的方法调用：按 Trait 中声明的签名检查，运行时经虚表分发
+    fn infer_trait_object_call(
+        &mut self,
+        trait_name: &str,
+        name: &str,
+        args: &mut [Expr],
+        span: &std::ops::Range<usize>,
+    ) -> Result<Type, SemanticError> {
+        let Some(Symbol::Trait(trait_sym)) = self.lookup(trait_name) else {
+            return Err(SemanticError::UndefinedTrait {
+                name: trait_name.to_string(),
+                span: span.clone(),
+            });
+        };
+        let Some(method) = trait_sym.get_method(name).cloned() else {
+            return Err(SemanticError::UndefinedMethod {
+                class: format!("dyn {}", trait_name),
+                method: name.to_string(),
+                span: span.clone(),
+            });
+        };
+        if args.len() != method.params.len() {
+            let receiver = Type::TraitObject(trait_name.to_string());
+            return Err(method_arity_error(
+                &receiver,
+                name,
+                method.params.len(),
+                args,
+                span,
+            ));
+        }
+        for (arg, (_, param_ty)) in args.iter_mut().zip(&method.params) {
+            super::narrow_int_literal(param_ty, arg);
+            super::wrap_trait_object(param_ty, arg);
+            self.check_builtin_arg(param_ty, arg)?;
+        }
+        Ok(method.return_type)
+    }
+
+    /// 关联函数调用: Type::method(args)，方法体不能引用 this
+    fn infer_associated_call(
+        &mut self,
+        type_name: &str,
+        member: &str,
+        args: &mut [Expr],
+        callee_span: &std::ops::Range<usize>,
+        span: &std::ops::Range<usize>,
+    ) -> Result<Type, SemanticError> {
+        let method = match self.lookup(type_name) {
+            Some(Symbol::Struct(struct_sym)) => struct_sym.get_method(member),
+            Some(Symbol::Enum(enum_sym)) => enum_sym.methods.get(member),
+            _ => {
+                return Err(SemanticError::UndefinedType {
+                    name: type_name.to_string(),
+                    span: callee_span.clone(),
+                })
+            }
+        };
+        let Some(method) = method.cloned() else {
+            return Err(SemanticError::UndefinedMethod {
+                class: type_name.to_string(),
+                method: member.to_string(),
+                span: span.clone(),
+            });
+        };
+        if !method.is_static {
+            return Err(SemanticError::NotAnAssociatedFunction {
+                class: type_name.to_string(),
+                method: member.to_string(),
+                span: span.clone(),
+            });
+        }
+        if args.len() != method.params.len() {
+            return Err(SemanticError::ArgumentCountMismatch {
+                name: format!("{}::{}", type_name, member),
+                expected: method.params.len(),
+                found: args.len(),
+                span: span.clone(),
+            });
+        }
+        for (arg, (_, param_ty)) in args.iter_mut().zip(&method.params) {
+            super::narrow_int_literal(param_ty, arg);
+            super::wrap_trait_object(param_ty, arg);
+            let arg_ty = self.infer(arg)?;
+            if !super::is_compatible(param_ty, &arg_ty) {
+                return Err(SemanticError::TypeMismatch {
+                    expected: param_ty.to_string(),
+                    found: arg_ty.to_string(),
+                    span: arg.span.clone(),
+                });
+            }
+        }
+        self.record_member_resolution(type_name, member, callee_span);
+        Ok(method.return_type)
+    }
+}
+
+/// 可空类型 `T?` 上由编译器内置实现的方法（接收者为 null 时不报空访问）
+pub(crate) fn is_nullable_builtin(name: &str) -> bool {
+    matches!(name, "value_or" | "map")
+}
+
+/// 内置 `to_string()` 的接收者类型：int / float / bool
+pub(crate) fn is_to_string_primitive(ty: &Type) -> bool {
+    matches!(ty, Type::Int | Type::Float | Type::Bool)
+}
+
+/// 可变参数部分能传给 C 的类型：整数、浮点、bool 与 string（char*）
+fn is_variadic_arg_type(ty: &Type) -> bool {
+    match ty {
+        Type::Newtype(_, inner) => is_variadic_arg_type(inner),
+        _ => ty.int_bits().is_some() || matches!(ty, Type::Float | Type::Bool | Type::String),
+    }
+}
+
+/// 内置方法（没有声明签名）的参数数量错误，信息中带上接收者类型，如 `Vec<int>.push`
+pub(crate) fn method_arity_error(
+    receiver: &Type,
+    method: &str,
+    expected: usize,
+    args: &[Expr],
+    span: &std::ops::Range<usize>,
+) -> SemanticError {
+    SemanticError::MethodArgumentCountMismatch {
+        receiver: receiver.to_string(),
+        method: method.to_string(),
+        expected,
+        found: args.len(),
+        signature: None,
+        span: span.clone(),
+    }
 }
@@ -1,7 +1,7 @@
 use super::{is_compatible, TypeInferer};
 use crate::error::SemanticError;
 use crate::symbol::Symbol;
-use lency_syntax::ast::{Expr, MatchCase, MatchPattern, Type};
+use lency_syntax::ast::{Expr, Literal, MatchCase, MatchPattern, Type};
 
 impl<'a> TypeInferer<'a> {
     pub(crate) fn infer_match(
@@ -33,6 +33,15 @@ impl<'a> TypeInferer<'a> {
                 return Err(e);
             }
 
+            // 守卫在模式绑定的作用域内求值，必须是 bool
+            if let Some(guard) = &mut case.guard {
+                if let Err(e) = self.check_guard(guard) {
+                    self.scopes.exit_scope();
+                    self.current_scope = parent_scope;
+                    return Err(e);
+                }
+            }
+
             let body_ty = self.infer(case.body.as_mut());
             self.scopes.exit_scope(); // Exit scope after inferring body
             self.current_scope = parent_scope;
@@ -41,59 +50,65 @@ impl<'a> TypeInferer<'a> {
             if first {
                 ret_ty = body_ty;
                 first = false;
-            } else if !is_compatible(&ret_ty, &body_ty) {
-                return Err(SemanticError::TypeMismatch {
-                    expected: ret_ty.to_string(),
-                    found: body_ty.to_string(),
-                    span: case.body.span.clone(),
+            } else {
+                ret_ty = unify_branch_types(&ret_ty, &body_ty).ok_or_else(|| {
+                    SemanticError::TypeMismatch {
+                        expected: ret_ty.to_string(),
+                        found: body_ty.to_string(),
+                        span: case.body.span.clone(),
+                    }
+                })?;
+            }
+        }
+
+        // 被前面无守卫分支完全覆盖的分支永远不会执行
+        for (i, case) in cases.iter().enumerate() {
+            let covered = cases[..i]
+                .iter()
+                .any(|earlier| earlier.guard.is_none() && covers(&earlier.pattern, &case.pattern));
+            if covered {
+                return Err(SemanticError::UnreachablePattern {
+                    span: case.span.clone(),
                 });
             }
         }
 
         // Exhaustiveness check
+        // 带守卫的分支可能在运行时落空，不参与穷尽性判断
         // 1. Check for Wildcard/Variable (always exhaust)
-        let has_catch_all = cases.iter().any(|c| {
-            matches!(
-                c.pattern,
-                MatchPattern::Wildcard | MatchPattern::Variable(_)
-            )
-        });
+        let has_catch_all = cases
+            .iter()
+            .any(|c| c.guard.is_none() && is_catch_all(&c.pattern));
 
         if !has_catch_all {
-            // 2. If matching on an Enum, check all variants are covered
-            let enum_name = match &value_ty {
-                Type::Struct(n) => Some(n.clone()),
-                Type::Generic(n, _) => Some(n.clone()),
+            // 2. If matching on an Enum (or bool, whose two values act as variants),
+            //    check all variants are covered
+            let all_variants: Option<std::collections::HashSet<String>> = match &value_ty {
+                Type::Bool => Some(["true", "false"].map(String::from).into()),
+                Type::Struct(name) | Type::Generic(name, _) => match self.lookup(name) {
+                    Some(Symbol::Enum(e)) => Some(e.variants.keys().cloned().collect()),
+                    _ => None,
+                },
                 _ => None,
             };
 
-            if let Some(ref name) = enum_name {
-                if let Some(Symbol::Enum(e)) = self.lookup(name) {
-                    let all_variants: std::collections::HashSet<String> =
-                        e.variants.keys().cloned().collect();
+            if let Some(all_variants) = all_variants {
+                let mut matched_variants = std::collections::HashSet::new();
+                for c in cases.iter().filter(|c| c.guard.is_none()) {
+                    collect_covered_variants(&c.pattern, &mut matched_variants);
+                }
 
-                    let matched_variants: std::collections::HashSet<String> = cases
-                        .iter()
-                        .filter_map(|c| {
-                            if let MatchPattern::Variant { name, .. } = &c.pattern {
-                                Some(name.clone())
-                            } else {
-                                None
-                            }
-                        })
-                        .collect();
-
-                    let missing: Vec<String> = all_variants
-                        .difference(&matched_variants)
-                        .cloned()
-                        .collect();
-
-                    if !missing.is_empty() {
-                        return Err(SemanticError::PatternNotExhaustive {
-                            missing_variants: missing,
-                            span: span.clone(),
-                        });
-                    }
+                let mut missing: Vec<String> = all_variants
+                    .difference(&matched_variants)
+                    .cloned()
+                    .collect();
+                missing.sort();
+
+                if !missing.is_empty() {
+                    return Err(SemanticError::PatternNotExhaustive {
+                        missing_variants: missing,
+                        span: span.clone(),
+                    });
                 }
             }
         }
@@ -109,11 +124,62 @@ impl<'a> TypeInferer<'a> {
         Ok(ret_ty)
     }
 
+    /// 检查 match 守卫条件必须是 bool（`bool?` 需要先显式处理 null）
+    fn check_guard(&mut self, guard: &mut Expr) -> Result<(), SemanticError> {
+        match self.infer(guard)? {
+            Type::Bool => Ok(()),
+            Type::Nullable(inner) if *inner == Type::Bool => {
+                Err(SemanticError::NullableConditionRequiresCheck {
+                    ty: Type::Nullable(inner).to_string(),
+                    span: guard.span.clone(),
+                })
+            }
+            ty => Err(SemanticError::TypeMismatch {
+                expected: "bool".to_string(),
+                found: ty.to_string(),
+                span: guard.span.clone(),
+            }),
+        }
+    }
+
     fn check_pattern(
         &mut self,
         pattern: &MatchPattern,
         target_ty: &Type,
         span: &std::ops::Range<usize>,
+    ) -> Result<(), SemanticError> {
+        for (name, ty) in self.pattern_bindings(pattern, target_ty, span)? {
+            let var_sym = crate::symbol::VariableSymbol::new(
+                name,
+                ty,
+                false, // immutable binding
+                span.clone(),
+            );
+            self.scopes.define(Symbol::Variable(var_sym))?;
+        }
+        Ok(())
+    }
+
+    /// 检查模式与目标类型是否匹配，返回模式引入的绑定 (变量名, 类型)
+    ///
+    /// 不修改作用域，由调用者决定把绑定定义到哪里（match 分支 / if let 的 then 块）
+    pub(crate) fn pattern_bindings(
+        &mut self,
+        pattern: &MatchPattern,
+        target_ty: &Type,
+        span: &std::ops::Range<usize>,
+    ) -> Result<Vec<(String, Type)>, SemanticError> {
+        let mut bindings = Vec::new();
+        self.collect_pattern_bindings(pattern, target_ty, span, &mut bindings)?;
+        Ok(bindings)
+    }
+
+    fn collect_pattern_bindings(
+        &mut self,
+        pattern: &MatchPattern,
+        target_ty: &Type,
+        span: &std::ops::Range<usize>,
+        bindings: &mut Vec<(String, Type)>,
     ) -> Result<(), SemanticError> {
         match pattern {
             MatchPattern::Literal(lit) => {
@@ -128,17 +194,52 @@ impl<'a> TypeInferer<'a> {
                 Ok(())
             }
             MatchPattern::Wildcard => Ok(()),
+            MatchPattern::Range { start, end } => {
+                // 区间只支持整数（字符以码点整数表示）
+                let (Literal::Int(lo), Literal::Int(hi)) = (start, end) else {
+                    let found = match start {
+                        Literal::Int(_) => end,
+                        _ => start,
+                    };
+                    return Err(SemanticError::TypeMismatch {
+                        expected: "int".to_string(),
+                        found: self.infer_literal(found).to_string(),
+                        span: span.clone(),
+                    });
+                };
+                if *target_ty != Type::Int {
+                    return Err(SemanticError::TypeMismatch {
+                        expected: target_ty.to_string(),
+                        found: Type::Int.to_string(),
+                        span: span.clone(),
+                    });
+                }
+                if lo > hi {
+                    return Err(SemanticError::EmptyRangePattern {
+                        start: *lo,
+                        end: *hi,
+                        span: span.clone(),
+                    });
+                }
+                Ok(())
+            }
+            MatchPattern::Or(alternatives) => {
+                for alt in alternatives {
+                    let mut alt_bindings = Vec::new();
+                    self.collect_pattern_bindings(alt, target_ty, span, &mut alt_bindings)?;
+                    if let Some((name, _)) = alt_bindings.into_iter().next() {
+                        return Err(SemanticError::OrPatternBinding {
+                            name,
+                            span: span.clone(),
+                        });
+                    }
+                }
+                Ok(())
+            }
             MatchPattern::Variable(name) => {
                 // Bind variable 'name' with type 'target_ty'
                 // Check if shadowing? Shadowing allowed in new scope.
-                let var_sym = crate::symbol::VariableSymbol::new(
-                    name.clone(),
-                    target_ty.clone(),
-                    false, // immutable binding
-                    span.clone(),
-                );
-
-                self.scopes.define(Symbol::Variable(var_sym))?;
+                bindings.push((name.clone(), target_ty.clone()));
                 Ok(())
             }
             MatchPattern::Variant { name, sub_patterns } => {
@@ -217,7 +318,7 @@ impl<'a> TypeInferer<'a> {
                         field_ty.clone()
                     };
 
-                    self.check_pattern(sub_pat, &concrete_field_ty, span)?;
+                    self.collect_pattern_bindings(sub_pat, &concrete_field_ty, span, bindings)?;
                 }
 
                 Ok(())
@@ -272,3 +373,83 @@ impl<'a> TypeInferer<'a> {
         }
     }
 }
+
+/// 模式是否匹配任意值：通配符、变量绑定、覆盖整个 int 范围的区间，或包含这些的或模式
+fn is_catch_all(pattern: &MatchPattern) -> bool {
+    match pattern {
+        MatchPattern::Wildcard | MatchPattern::Variable(_) => true,
+        MatchPattern::Range {
+            start: Literal::Int(i64::MIN),
+            end: Literal::Int(i64::MAX),
+        } => true,
+        MatchPattern::Or(alternatives) => alternatives.iter().any(is_catch_all),
+        _ => false,
+    }
+}
+
+/// `earlier` 能匹配的值是否包含 `later` 能匹配的全部值
+///
+/// 只识别确定的情形：全匹配模式、相同的字面量、子模式全为全匹配的同名变体，以及或模式
+fn covers(earlier: &MatchPattern, later: &MatchPattern) -> bool {
+    if is_catch_all(earlier) {
+        return true;
+    }
+    match (earlier, later) {
+        (_, MatchPattern::Or(alternatives)) => alternatives.iter().all(|alt| covers(earlier, alt)),
+        (MatchPattern::Or(alternatives), _) => alternatives.iter().any(|alt| covers(alt, later)),
+        (MatchPattern::Literal(a), MatchPattern::Literal(b)) => a == b,
+        (
+            MatchPattern::Range {
+                start: Literal::Int(lo),
+                end: Literal::Int(hi),
+            },
+            MatchPattern::Literal(Literal::Int(n)),
+        ) => lo <= n && n <= hi,
+        (
+            MatchPattern::Variant { name, sub_patterns },
+            MatchPattern::Variant { name: other, .. },
+        ) => name == other && sub_patterns.iter().all(is_catch_all),
+        _ => false,
+    }
+}
+
+/// 合并两个分支的结果类型
+///
+/// 一侧为 null 字面量时提升为另一侧的可空类型（`5` 与 `null` 得到 `int?`），
+/// 否则取能容纳另一侧的类型（int 与 float 得到 float，`T` 与 `T?` 得到 `T?`）；不兼容时返回 None
+pub(crate) fn unify_branch_types(a: &Type, b: &Type) -> Option<Type> {
+    let is_null = |ty: &Type| matches!(ty, Type::Nullable(inner) if **inner == Type::Error);
+    let nullable = |ty: &Type| match ty {
+        Type::Nullable(_) => ty.clone(),
+        _ => Type::Nullable(Box::new(ty.clone())),
+    };
+    match (is_null(a), is_null(b)) {
+        (true, true) => Some(a.clone()),
+        (true, false) => Some(nullable(b)),
+        (false, true) => Some(nullable(a)),
+        (false, false) if is_compatible(a, b) => Some(a.clone()),
+        (false, false) if is_compatible(b, a) => Some(b.clone()),
+        _ => None,
+    }
+}
+
+/// 收集模式覆盖的枚举变体名（或模式展开到每个分支），bool 字面量记为 "true" / "false"
+fn collect_covered_variants(
+    pattern: &MatchPattern,
+    variants: &mut std::collections::HashSet<String>,
+) {
+    match pattern {
+        MatchPattern::Variant { name, .. } => {
+            variants.insert(name.clone());
+        }
+        MatchPattern::Literal(Literal::Bool(value)) => {
+            variants.insert(value.to_string());
+        }
+        MatchPattern::Or(alternatives) => {
+            for alt in alternatives {
+                collect_covered_variants(alt, variants);
+            }
+        }
+        _ => {}
+    }
+}
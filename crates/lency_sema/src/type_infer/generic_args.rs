@@ -0,0 +1,188 @@
+//! 泛型函数调用：从实参推导类型实参、检查 Trait 约束
+
+use super::adt::{substitute_type, unify_generic};
+use super::TypeInferer;
+use crate::error::SemanticError;
+use crate::symbol::{FunctionSymbol, Symbol};
+use lency_syntax::ast::{Expr, ExprKind, Type};
+use std::collections::HashMap;
+
+impl<'a> TypeInferer<'a> {
+    /// 未写 turbofish 的泛型函数调用：推导类型实参并把 callee 改写为 f::<...>
+    ///
+    /// callee 不是泛型函数时返回 None，交给普通调用处理。
+    pub(super) fn infer_implicit_generic_call(
+        &mut self,
+        callee: &mut Expr,
+        args: &mut [Expr],
+        span: &std::ops::Range<usize>,
+    ) -> Result<Option<Type>, SemanticError> {
+        let ExprKind::Variable(name) = &callee.kind else {
+            return Ok(None);
+        };
+        let func = match self.lookup(name) {
+            Some(Symbol::Function(func)) if !func.generic_params.is_empty() => func.clone(),
+            _ => return Ok(None),
+        };
+
+        let type_args = self.infer_type_args(&func, args, span)?;
+        self.check_trait_bounds(&func, &type_args, span)?;
+        let map = func
+            .generic_params
+            .iter()
+            .map(|p| p.name.clone())
+            .zip(type_args.iter().cloned())
+            .collect();
+        callee.kind = ExprKind::GenericInstantiation {
+            base: Box::new(Expr {
+                kind: ExprKind::Variable(func.name.clone()),
+                span: callee.span.clone(),
+            }),
+            args: type_args,
+        };
+        Ok(Some(substitute_type(&func.return_type, &map)))
+    }
+
+    /// 显式写出类型实参的泛型函数调用：f::<T>(...)
+    pub(super) fn infer_generic_instantiation_call(
+        &mut self,
+        base: &Expr,
+        type_args: &[Type],
+        span: &std::ops::Range<usize>,
+    ) -> Result<Type, SemanticError> {
+        let ExprKind::Variable(name) = &base.kind else {
+            return Err(SemanticError::NotCallable {
+                ty: "complex generic instantiation".into(),
+                span: span.clone(),
+            });
+        };
+        let Some(Symbol::Function(f)) = self.lookup(name) else {
+            return Err(SemanticError::NotCallable {
+                ty: "generic instantiation".into(),
+                span: span.clone(),
+            });
+        };
+        if f.generic_params.len() != type_args.len() {
+            return Err(SemanticError::ArgumentCountMismatch {
+                name: format!("{} generic args", name),
+                expected: f.generic_params.len(),
+                found: type_args.len(),
+                span: span.clone(),
+            });
+        }
+        let f = f.clone();
+        self.check_trait_bounds(&f, type_args, span)?;
+        let map: HashMap<String, Type> = f
+            .generic_params
+            .iter()
+            .map(|param| param.name.clone())
+            .zip(type_args.iter().cloned())
+            .collect();
+        Ok(substitute_type(&f.return_type, &map))
+    }
+
+    /// 检查泛型实参是否满足每个类型参数的全部 Trait 约束
+    fn check_trait_bounds(
+        &self,
+        func: &FunctionSymbol,
+        type_args: &[Type],
+        span: &std::ops::Range<usize>,
+    ) -> Result<(), SemanticError> {
+        for (gp, ty) in func.generic_params.iter().zip(type_args) {
+            for trait_name in gp.bound_traits() {
+                if self.implements_trait(ty, trait_name) == Some(false) {
+                    return Err(SemanticError::TraitBoundNotSatisfied {
+                        ty: ty.to_string(),
+                        trait_name: trait_name.to_string(),
+                        param: gp.name.clone(),
+                        span: span.clone(),
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// 类型是否实现了 Trait；无法判断时（如 Vec、枚举）返回 None，交给单态化阶段检查
+    fn implements_trait(&self, ty: &Type, trait_name: &str) -> Option<bool> {
+        let name = match ty {
+            Type::GenericParam(name) => {
+                return match self.lookup(name) {
+                    Some(Symbol::GenericParam(gp)) => {
+                        Some(gp.bound_traits().any(|t| t == trait_name))
+                    }
+                    _ => None,
+                };
+            }
+            Type::Int => "int",
+            Type::Bool => "bool",
+            Type::String => "string",
+            Type::Float => "float",
+            Type::Struct(name) | Type::Generic(name, _) => name.as_str(),
+            _ => return None,
+        };
+        match self.lookup(name) {
+            Some(Symbol::Struct(s)) => Some(s.implements(trait_name)),
+            _ => None,
+        }
+    }
+
+    /// 用实参类型求解泛型函数的类型参数
+    fn infer_type_args(
+        &mut self,
+        func: &FunctionSymbol,
+        args: &mut [Expr],
+        span: &std::ops::Range<usize>,
+    ) -> Result<Vec<Type>, SemanticError> {
+        if func.params.len() != args.len() {
+            return Err(SemanticError::ArgumentCountMismatch {
+                name: func.name.clone(),
+                expected: func.params.len(),
+                found: args.len(),
+                span: span.clone(),
+            });
+        }
+
+        let names: Vec<String> = func.generic_params.iter().map(|p| p.name.clone()).collect();
+        let mut map = HashMap::new();
+        let mut arg_types = Vec::with_capacity(args.len());
+        for ((_, param_ty), arg) in func.params.iter().zip(args.iter_mut()) {
+            super::wrap_trait_object(param_ty, arg);
+            let arg_ty = self.infer(arg)?;
+            if let Err((expected, found)) = unify_generic(param_ty, &arg_ty, &names, &mut map) {
+                return Err(SemanticError::TypeMismatch {
+                    expected: expected.to_string(),
+                    found: found.to_string(),
+                    span: arg.span.clone(),
+                });
+            }
+            arg_types.push(arg_ty);
+        }
+
+        let type_args = names
+            .iter()
+            .map(|name| {
+                map.get(name)
+                    .cloned()
+                    .ok_or_else(|| SemanticError::CannotInferType {
+                        name: format!("{} in call to '{}'", name, func.name),
+                        span: span.clone(),
+                    })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // 求解只看含泛型参数的部分；代入后再逐个检查实参（含 null 与非泛型形参）
+        for (((_, param_ty), arg), arg_ty) in func.params.iter().zip(args.iter()).zip(&arg_types) {
+            let expected = substitute_type(param_ty, &map);
+            if !super::is_compatible(&expected, arg_ty) {
+                return Err(SemanticError::TypeMismatch {
+                    expected: expected.to_string(),
+                    found: arg_ty.to_string(),
+                    span: arg.span.clone(),
+                });
+            }
+        }
+
+        Ok(type_args)
+    }
+}
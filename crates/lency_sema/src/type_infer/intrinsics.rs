@@ -1,5 +1,7 @@
 use crate::error::SemanticError;
-use crate::type_infer::TypeInferer;
+use crate::symbol::Symbol;
+use crate::type_infer::{trait_object_name, TypeInferer};
+use crate::types::TypeInfo;
 use lency_syntax::ast::{Expr, ExprKind, Type};
 
 impl<'a> TypeInferer<'a> {
@@ -167,6 +169,26 @@ impl<'a> TypeInferer<'a> {
                 }
                 Ok(Type::Void)
             }
+            ExprKind::Assert(cond, message) => {
+                // assert(bool, string) -> void
+                let cond_ty = self.infer(cond)?;
+                if cond_ty != Type::Bool {
+                    return Err(SemanticError::TypeMismatch {
+                        expected: "bool".to_string(),
+                        found: cond_ty.to_string(),
+                        span: cond.span.clone(),
+                    });
+                }
+                let message_ty = self.infer(message)?;
+                if message_ty != Type::String {
+                    return Err(SemanticError::TypeMismatch {
+                        expected: "string".to_string(),
+                        found: message_ty.to_string(),
+                        span: message.span.clone(),
+                    });
+                }
+                Ok(Type::Void)
+            }
             ExprKind::Format(template, args) => {
                 // format(string, Vec<string>) -> string
                 let template_ty = self.infer(template)?;
@@ -190,7 +212,76 @@ impl<'a> TypeInferer<'a> {
                 }
                 Ok(Type::String)
             }
+            ExprKind::Fmt { template, args } => {
+                // fmt!(literal, args...) -> string：占位符与实参一一对应
+                let placeholders = template.matches("{}").count();
+                if placeholders != args.len() {
+                    return Err(SemanticError::FormatArgMismatch {
+                        placeholders,
+                        found: args.len(),
+                        span: expr.span.clone(),
+                    });
+                }
+                for arg in args.iter_mut() {
+                    let arg_ty = self.infer(arg)?;
+                    if arg_ty != Type::String && !super::call::is_to_string_primitive(&arg_ty) {
+                        return Err(SemanticError::TypeMismatch {
+                            expected: "string, int, float or bool".to_string(),
+                            found: arg_ty.to_string(),
+                            span: arg.span.clone(),
+                        });
+                    }
+                }
+                Ok(Type::String)
+            }
+            ExprKind::Cast { ty, value } => {
+                // x as T / cast<T>(x)
+                let value_ty = self.infer(value)?;
+                if let Some(trait_name) = trait_object_name(ty) {
+                    // 转为 dyn T：值必须已经是 dyn T，或是实现了 T 的结构体；转为 dyn T? 时值还可以是它们的可空类型
+                    let source = match (&value_ty, &*ty) {
+                        (Type::Nullable(inner), Type::Nullable(_)) => inner.as_ref(),
+                        _ => &value_ty,
+                    };
+                    let implements = match source {
+                        Type::Struct(name) => matches!(
+                            self.lookup(name),
+                            Some(Symbol::Struct(s)) if s.implements(trait_name)
+                        ),
+                        Type::TraitObject(name) => name == trait_name,
+                        _ => false,
+                    };
+                    if !implements {
+                        return Err(SemanticError::InvalidTraitObject {
+                            ty: value_ty.to_string(),
+                            trait_name: trait_name.to_string(),
+                            span: value.span.clone(),
+                        });
+                    }
+                    return Ok(ty.clone());
+                }
+                if !is_valid_cast(&value_ty, ty) {
+                    return Err(SemanticError::InvalidCast {
+                        from: value_ty.to_string(),
+                        to: ty.to_string(),
+                        span: expr.span.clone(),
+                    });
+                }
+                Ok(ty.clone())
+            }
             _ => unreachable!("Not an intrinsic expression"),
         }
     }
 }
+
+/// 允许的显式转换：相同类型、newtype 与其底层类型、数值类型之间互转
+///
+/// 字符以码点 int 表示，字符与整数之间的转换即 int 到 int
+fn is_valid_cast(from: &Type, to: &Type) -> bool {
+    match (from, to) {
+        (a, b) if a == b => true,
+        (a, b) if a.is_numeric() && b.is_numeric() => true,
+        (Type::Newtype(_, inner), other) | (other, Type::Newtype(_, inner)) => **inner == *other,
+        _ => false,
+    }
+}
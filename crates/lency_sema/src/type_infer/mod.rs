@@ -7,8 +7,10 @@
 
 mod access;
 mod adt;
+mod builtin_methods;
 mod call;
 mod control;
+mod generic_args;
 mod intrinsics;
 mod literal;
 mod operators;
@@ -18,12 +20,13 @@ mod tests;
 
 // Re-export specific items for internal or external use
 pub(crate) use adt::substitute_type; // Re-export for other sema modules
+pub(crate) use call::{is_nullable_builtin, method_arity_error};
 
-use crate::error::SemanticError;
+use crate::error::{SemanticError, SemanticWarning};
 use crate::operators::{BinaryOpRegistry, UnaryOpRegistry};
 use crate::scope::{ScopeId, ScopeStack};
 use crate::symbol::Symbol;
-use lency_syntax::ast::{Expr, ExprKind, Type};
+use lency_syntax::ast::{Expr, ExprKind, Literal, Type};
 
 /// 类型推导器
 pub struct TypeInferer<'a> {
@@ -34,6 +37,12 @@ pub struct TypeInferer<'a> {
     pub(crate) binary_ops: BinaryOpRegistry,
     /// 一元运算符注册表
     pub(crate) unary_ops: UnaryOpRegistry,
+    /// 所在函数声明的返回类型（`?` 据此检查错误类型）
+    pub(crate) return_type: Option<Type>,
+    /// 不影响推导结果的错误（如结构体字面量缺字段），由调用者收集
+    pub(crate) errors: Vec<SemanticError>,
+    /// 推导过程中发现的警告（如浮点数相等比较），由调用者收集
+    pub(crate) warnings: Vec<SemanticWarning>,
 }
 
 impl<'a> TypeInferer<'a> {
@@ -43,6 +52,9 @@ impl<'a> TypeInferer<'a> {
             scopes,
             binary_ops: BinaryOpRegistry::new(),
             unary_ops: UnaryOpRegistry::new(),
+            return_type: None,
+            errors: Vec::new(),
+            warnings: Vec::new(),
         }
     }
 
@@ -53,6 +65,9 @@ impl<'a> TypeInferer<'a> {
             current_scope: scope_id,
             binary_ops: BinaryOpRegistry::new(),
             unary_ops: UnaryOpRegistry::new(),
+            return_type: None,
+            errors: Vec::new(),
+            warnings: Vec::new(),
         }
     }
 
@@ -75,18 +90,32 @@ impl<'a> TypeInferer<'a> {
 
             ExprKind::Variable(name) => self.infer_variable(name, &expr.span),
 
-            ExprKind::Binary(left, op, right) => self.infer_binary(left, op, right, &expr.span),
+            ExprKind::Binary(..) => self.infer_binary_expr(expr),
 
             ExprKind::Unary(op, operand) => self.infer_unary(op, operand, &expr.span),
 
             ExprKind::Call { callee, args } => self.infer_call(callee, args, &expr.span),
 
+            ExprKind::Path { type_name, member } => {
+                Err(SemanticError::AssociatedFunctionNotCalled {
+                    class: type_name.clone(),
+                    method: member.clone(),
+                    span: expr.span.clone(),
+                })
+            }
+
             ExprKind::Get { object, name } => self.infer_get(object, name, &expr.span),
 
             ExprKind::SafeGet { object, name } => self.infer_safe_get(object, name, &expr.span),
 
             ExprKind::Array(elements) => self.infer_array(elements, &expr.span),
 
+            ExprKind::Repeat {
+                value,
+                count,
+                is_vec,
+            } => self.infer_repeat(value, count, *is_vec),
+
             ExprKind::Index { array, index } => self.infer_index(array, index, &expr.span),
 
             ExprKind::Match {
@@ -102,6 +131,7 @@ impl<'a> TypeInferer<'a> {
 
             // ADT (Structs, Enums, Closures, Result, Vec) -> adt.rs
             ExprKind::StructLiteral { .. }
+            | ExprKind::RecordLiteral(_)
             | ExprKind::VecLiteral(_)
             | ExprKind::GenericInstantiation { .. }
             | ExprKind::Try(_)
@@ -119,8 +149,115 @@ impl<'a> TypeInferer<'a> {
             | ExprKind::Substr(_, _, _)
             | ExprKind::CharToString(_)
             | ExprKind::Panic(_)
-            | ExprKind::Format(_, _) => self.infer_intrinsic(expr),
+            | ExprKind::Assert(_, _)
+            | ExprKind::Format(_, _)
+            | ExprKind::Fmt { .. }
+            | ExprKind::Cast { .. } => self.infer_intrinsic(expr),
+        }
+    }
+}
+
+/// 整数字面量用在更窄的整数类型或 uint 处（如 `i32 x = 5`）时，原地改写为 `5 as i32`
+///
+/// 只改写值在目标类型范围内的字面量；超出范围的保持 int，交给兼容性检查报错
+pub(crate) fn narrow_int_literal(expected: &Type, expr: &mut Expr) {
+    let target = match expected {
+        Type::Nullable(inner) => inner.as_ref(),
+        ty => ty,
+    };
+    if !int_literal_fits(target, expr) {
+        return;
+    }
+
+    let span = expr.span.clone();
+    let literal = std::mem::replace(
+        expr,
+        Expr {
+            kind: ExprKind::Unit,
+            span: span.clone(),
+        },
+    );
+    *expr = Expr {
+        kind: ExprKind::Cast {
+            ty: target.clone(),
+            value: Box::new(literal),
+        },
+        span,
+    };
+}
+
+/// 值用在期望 Trait 对象 `dyn T` / `dyn T?` 的位置时，原地改写为 `value as dyn T` / `value as dyn T?`
+///
+/// 值的类型是否实现了该 Trait 由 Cast 的类型推导检查；已经是该转换的表达式保持不变。
+/// `null` 不需要转换；期望 `Vec<dyn T>`（可带 `?`）时逐个改写 `vec![...]` 字面量中的元素
+pub(crate) fn wrap_trait_object(expected: &Type, expr: &mut Expr) {
+    match expected {
+        Type::TraitObject(_) => {}
+        Type::Nullable(inner) => {
+            if matches!(expr.kind, ExprKind::Literal(Literal::Null)) {
+                return;
+            }
+            if !matches!(inner.as_ref(), Type::TraitObject(_)) {
+                wrap_trait_object(inner, expr);
+                return;
+            }
+        }
+        Type::Vec(inner) => {
+            if let ExprKind::VecLiteral(elements) = &mut expr.kind {
+                for element in elements {
+                    wrap_trait_object(inner, element);
+                }
+            }
+            return;
         }
+        _ => return,
+    }
+    if matches!(&expr.kind, ExprKind::Cast { ty, .. } if ty == expected) {
+        return;
+    }
+
+    let span = expr.span.clone();
+    let value = std::mem::replace(
+        expr,
+        Expr {
+            kind: ExprKind::Unit,
+            span: span.clone(),
+        },
+    );
+    *expr = Expr {
+        kind: ExprKind::Cast {
+            ty: expected.clone(),
+            value: Box::new(value),
+        },
+        span,
+    };
+}
+
+/// `dyn T` 或 `dyn T?` 中的 Trait 名
+pub(crate) fn trait_object_name(ty: &Type) -> Option<&str> {
+    match ty {
+        Type::TraitObject(name) => Some(name),
+        Type::Nullable(inner) => match inner.as_ref() {
+            Type::TraitObject(name) => Some(name),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// `expr` 是整数字面量（可带负号），且值落在窄整数类型或 uint `target` 的范围内
+pub(crate) fn int_literal_fits(target: &Type, expr: &Expr) -> bool {
+    let Some(n) = expr.const_int() else {
+        return false;
+    };
+    match target.int_bits() {
+        _ if target.is_unsigned() => n >= 0,
+        Some(bits) if bits < 64 => {
+            let min = -(1i64 << (bits - 1));
+            let max = (1i64 << (bits - 1)) - 1;
+            (min..=max).contains(&n)
+        }
+        _ => false,
     }
 }
 
@@ -130,8 +267,14 @@ pub fn is_compatible(expected: &Type, actual: &Type) -> bool {
         // 完全相同
         (a, b) if a == b => true,
 
-        // int 可以隐式转为 float
-        (Type::Float, Type::Int) => true,
+        // 有符号整数可以隐式转为 float
+        (Type::Float, actual) if actual.int_bits().is_some() && !actual.is_unsigned() => true,
+
+        // 有符号整数只能隐式拓宽（i32 -> int），收窄以及与 uint 之间的转换需要显式 `as`
+        (expected, actual) if expected.int_bits().is_some() && actual.int_bits().is_some() => {
+            expected.is_unsigned() == actual.is_unsigned()
+                && actual.int_bits() <= expected.int_bits()
+        }
 
         // null 字面量 (Type::Nullable(Type::Error)) 可以赋给任何可空类型
         (Type::Nullable(_), Type::Nullable(inner)) if matches!(**inner, Type::Error) => true,
@@ -139,6 +282,16 @@ pub fn is_compatible(expected: &Type, actual: &Type) -> bool {
         // 可空类型可以接受非空类型
         (Type::Nullable(inner), actual) => is_compatible(inner, actual),
 
+        // 记录类型按结构比较：字段名相同且字段类型完全一致
+        // （字段类型不做隐式转换，保证两边的内存布局相同）
+        (Type::Record(expected_fields), Type::Record(actual_fields)) => {
+            expected_fields.len() == actual_fields.len()
+                && expected_fields
+                    .iter()
+                    .zip(actual_fields)
+                    .all(|((n1, t1), (n2, t2))| n1 == n2 && t1 == t2)
+        }
+
         // Vec 兼容性
         (Type::Vec(t1), Type::Vec(t2)) => {
             if matches!(**t2, Type::Void) {
@@ -1,20 +1,102 @@
 use super::TypeInferer;
-use crate::error::SemanticError;
+use crate::error::{SemanticError, SemanticWarning};
 use crate::symbol::Symbol;
-use lency_syntax::ast::{Expr, Type, UnaryOp};
+use lency_syntax::ast::{BinaryOp, Expr, ExprKind, Literal, Type, UnaryOp};
 
 impl<'a> TypeInferer<'a> {
     /// 推导二元表达式类型
-    pub(crate) fn infer_binary(
+    ///
+    /// 结构体实现了运算符 Trait（如 `impl Add<Vector> for Vector`）时，
+    /// 将 `a + b` 原地降级为方法调用 `a.add(b)`，后续阶段按普通方法调用处理
+    pub(crate) fn infer_binary_expr(&mut self, expr: &mut Expr) -> Result<Type, SemanticError> {
+        let ExprKind::Binary(left, op, right) = &mut expr.kind else {
+            return Ok(Type::Error);
+        };
+
+        let mut left_ty = self.infer(left)?;
+        let mut right_ty = self.infer(right)?;
+
+        // 窄整数与整数字面量运算（如 `x + 1`，x 为 i32）时，字面量取窄整数的类型
+        if left_ty.int_bits().is_some() && right_ty == Type::Int {
+            super::narrow_int_literal(&left_ty, right);
+            right_ty = self.infer(right)?;
+        } else if right_ty.int_bits().is_some() && left_ty == Type::Int {
+            super::narrow_int_literal(&right_ty, left);
+            left_ty = self.infer(left)?;
+        }
+
+        let Some((method, result_ty)) =
+            self.operator_overload(op, &left_ty, &right_ty, &right.span)?
+        else {
+            return self
+                .infer_binary(op, left_ty, right, right_ty, &expr.span)
+                .map_err(|err| with_concat_fix(err, op, left, right));
+        };
+
+        let kind = std::mem::replace(&mut expr.kind, ExprKind::Unit);
+        if let ExprKind::Binary(left, _, right) = kind {
+            let callee_span = left.span.clone();
+            expr.kind = ExprKind::Call {
+                callee: Box::new(Expr {
+                    kind: ExprKind::Get {
+                        object: left,
+                        name: method.to_string(),
+                    },
+                    span: callee_span,
+                }),
+                args: vec![*right],
+            };
+        }
+        Ok(result_ty)
+    }
+
+    /// 查找结构体上的运算符重载
+    ///
+    /// 返回 (方法名, 结果类型)；左操作数不是实现了对应 Trait 的结构体时返回 None
+    fn operator_overload(
+        &self,
+        op: &BinaryOp,
+        left_ty: &Type,
+        right_ty: &Type,
+        right_span: &std::ops::Range<usize>,
+    ) -> Result<Option<(&'static str, Type)>, SemanticError> {
+        let Type::Struct(name) = left_ty else {
+            return Ok(None);
+        };
+        let Some((trait_name, method)) = self.binary_ops.operator_trait(op) else {
+            return Ok(None);
+        };
+        let Some(Symbol::Struct(struct_sym)) = self.lookup(name) else {
+            return Ok(None);
+        };
+        if !struct_sym.implements(trait_name) {
+            return Ok(None);
+        }
+        let Some(func) = struct_sym.get_method(method) else {
+            return Ok(None);
+        };
+
+        if let Some((_, param_ty)) = func.params.first() {
+            if !crate::type_infer::is_compatible(param_ty, right_ty) {
+                return Err(SemanticError::TypeMismatch {
+                    expected: param_ty.to_string(),
+                    found: right_ty.to_string(),
+                    span: right_span.clone(),
+                });
+            }
+        }
+        Ok(Some((method, func.return_type.clone())))
+    }
+
+    /// 根据左右操作数类型推导内置二元运算的结果类型
+    fn infer_binary(
         &mut self,
-        left: &mut Expr,
-        op: &lency_syntax::ast::BinaryOp,
-        right: &mut Expr,
+        op: &BinaryOp,
+        left_ty: Type,
+        right: &Expr,
+        right_ty: Type,
         span: &std::ops::Range<usize>,
     ) -> Result<Type, SemanticError> {
-        let left_ty = self.infer(left)?;
-        let right_ty = self.infer(right)?;
-
         // Special handling for Elvis Operator (??)
         if matches!(op, lency_syntax::ast::BinaryOp::Elvis) {
             match &left_ty {
@@ -44,34 +126,66 @@ impl<'a> TypeInferer<'a> {
         let result = self.binary_ops.lookup(op, &left_ty, &right_ty, span);
 
         if result.is_ok() {
+            self.check_float_equality(op, &left_ty, &right_ty, span);
             return result;
         }
 
+        // 逻辑运算的可空 bool 操作数：提示先解包，而不是笼统的运算符错误
+        if matches!(op, BinaryOp::And | BinaryOp::Or) {
+            for (ty, operand_span) in [(&left_ty, span), (&right_ty, &right.span)] {
+                if is_nullable_bool(ty) {
+                    return Err(SemanticError::NullableConditionRequiresCheck {
+                        ty: ty.to_string(),
+                        span: operand_span.clone(),
+                    });
+                }
+            }
+        }
+
+        // newtype：同一 newtype 之间按底层类型比较；算术运算需先 cast 回底层类型
+        if let Type::Newtype(_, inner) = &left_ty {
+            let is_comparison = matches!(
+                op,
+                BinaryOp::Eq
+                    | BinaryOp::Neq
+                    | BinaryOp::Lt
+                    | BinaryOp::Gt
+                    | BinaryOp::Leq
+                    | BinaryOp::Geq
+            );
+            if is_comparison && left_ty == right_ty {
+                return self.binary_ops.lookup(op, inner, inner, span);
+            }
+        }
+
+        // 枚举类型：只允许同类型之间的 == / !=
+        if self.is_enum_type(&left_ty) {
+            return self.binary_ops.lookup_enum(op, &left_ty, &right_ty, span);
+        }
+
+        // 结构体类型：同类型之间按字段比较 == / !=
+        if self.is_struct_type(&left_ty) {
+            return self.binary_ops.lookup_struct(op, &left_ty, &right_ty, span);
+        }
+
         // Fallback: Check for generic parameters with trait bounds (e.g. T: Comparable)
         if let Type::GenericParam(name) = &left_ty {
             if left_ty == right_ty {
                 // Currently restrict to T op T
                 if let Some(Symbol::GenericParam(gp)) = self.lookup(name) {
-                    if let Some(Type::Struct(tit_name)) = &gp.bound {
-                        // Check if trait supports the operator
-                        // For now, hardcode standard traits mapping since we don't have operator overloading fully generic mapped yet
-                        // Eq -> ==, !=
-                        // Comparable -> <, >, <=, >=
-                        use lency_syntax::ast::BinaryOp::*;
-                        match op {
-                            Eq | Neq => {
-                                if tit_name == "Eq" || tit_name == "Comparable" {
-                                    // Comparable usually implies Eq
-                                    return Ok(Type::Bool);
-                                }
-                            }
-                            Lt | Leq | Gt | Geq => {
-                                if tit_name == "Comparable" {
-                                    return Ok(Type::Bool);
-                                }
-                            }
-                            _ => {}
-                        }
+                    // Check if any bound trait supports the operator
+                    // For now, hardcode standard traits mapping since we don't have operator overloading fully generic mapped yet
+                    // Eq -> ==, !=
+                    // Comparable -> <, >, <=, >=
+                    use lency_syntax::ast::BinaryOp::*;
+                    let supported = gp.bound_traits().any(|tit_name| match op {
+                        // Comparable usually implies Eq
+                        Eq | Neq => tit_name == "Eq" || tit_name == "Comparable",
+                        Lt | Leq | Gt | Geq => tit_name == "Comparable",
+                        _ => false,
+                    });
+                    if supported {
+                        return Ok(Type::Bool);
                     }
                 }
             }
@@ -80,6 +194,45 @@ impl<'a> TypeInferer<'a> {
         result
     }
 
+    /// 浮点数之间的 == / != 记录警告，提示改用误差范围比较
+    fn check_float_equality(
+        &mut self,
+        op: &BinaryOp,
+        left_ty: &Type,
+        right_ty: &Type,
+        span: &std::ops::Range<usize>,
+    ) {
+        let op = match op {
+            BinaryOp::Eq => "==",
+            BinaryOp::Neq => "!=",
+            _ => return,
+        };
+        if *left_ty == Type::Float || *right_ty == Type::Float {
+            self.warnings.push(SemanticWarning::FloatEquality {
+                op,
+                span: span.clone(),
+            });
+        }
+    }
+
+    /// 判断类型是否为枚举（含泛型枚举实例）
+    fn is_enum_type(&self, ty: &Type) -> bool {
+        let name = match ty {
+            Type::Struct(name) | Type::Generic(name, _) => name,
+            _ => return false,
+        };
+        matches!(self.lookup(name), Some(Symbol::Enum(_)))
+    }
+
+    /// 判断类型是否为结构体（含泛型结构体实例）
+    fn is_struct_type(&self, ty: &Type) -> bool {
+        let name = match ty {
+            Type::Struct(name) | Type::Generic(name, _) => name,
+            _ => return false,
+        };
+        matches!(self.lookup(name), Some(Symbol::Struct(_)))
+    }
+
     /// 推导一元表达式类型
     pub(crate) fn infer_unary(
         &mut self,
@@ -89,7 +242,62 @@ impl<'a> TypeInferer<'a> {
     ) -> Result<Type, SemanticError> {
         let operand_ty = self.infer(operand)?;
 
+        if matches!(op, UnaryOp::Not) && is_nullable_bool(&operand_ty) {
+            return Err(SemanticError::NullableConditionRequiresCheck {
+                ty: operand_ty.to_string(),
+                span: operand.span.clone(),
+            });
+        }
+
         // 使用运算符表查找
         self.unary_ops.lookup(op, &operand_ty, span)
     }
 }
+
+fn is_nullable_bool(ty: &Type) -> bool {
+    matches!(ty, Type::Nullable(inner) if **inner == Type::Bool)
+}
+
+/// `string + int` 之类的拼接：为非字符串一侧生成 `int_to_string(x)` 形式的修复
+///
+/// 只处理变量与字面量操作数，其余表达式无法可靠还原源码，保留原错误
+fn with_concat_fix(err: SemanticError, op: &BinaryOp, left: &Expr, right: &Expr) -> SemanticError {
+    let SemanticError::InvalidBinaryOp {
+        op: op_name,
+        left: left_ty,
+        right: right_ty,
+        span,
+        fix: None,
+    } = err
+    else {
+        return err;
+    };
+
+    let operand = match (left_ty.as_str(), right_ty.as_str()) {
+        ("string", "int" | "float") => Some((right, right_ty.as_str())),
+        ("int" | "float", "string") => Some((left, left_ty.as_str())),
+        _ => None,
+    };
+    let fix = operand
+        .filter(|_| matches!(op, BinaryOp::Add))
+        .and_then(|(expr, ty)| {
+            let text = match &expr.kind {
+                ExprKind::Variable(name) => name.clone(),
+                ExprKind::Literal(Literal::Int(n)) => n.to_string(),
+                ExprKind::Literal(Literal::Float(f)) => format!("{:?}", f),
+                _ => return None,
+            };
+            Some(Box::new((
+                expr.span.clone(),
+                format!("{}_to_string({})", ty, text),
+            )))
+        });
+
+    SemanticError::InvalidBinaryOp {
+        op: op_name,
+        left: left_ty,
+        right: right_ty,
+        span,
+        fix,
+    }
+}
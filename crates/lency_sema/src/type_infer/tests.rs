@@ -5,7 +5,7 @@
 use super::TypeInferer;
 use crate::error::SemanticError;
 use crate::scope::ScopeStack;
-use lency_syntax::ast::{Expr, ExprKind, Literal, Type};
+use lency_syntax::ast::{BinaryOp, Expr, ExprKind, Literal, Type, UnaryOp};
 
 /// Helper: Create a test scope stack
 fn create_test_scopes() -> ScopeStack {
@@ -102,6 +102,58 @@ fn test_infer_array_mixed_types_error() {
     ));
 }
 
+#[test]
+fn test_infer_array_int_float_promotion() {
+    let mut scopes = create_test_scopes();
+    let mut inferer = TypeInferer::new(&mut scopes);
+
+    // [1, 2.5, 3] - int 元素提升为 float
+    let mut elements = vec![
+        make_expr(ExprKind::Literal(Literal::Int(1))),
+        make_expr(ExprKind::Literal(Literal::Float(2.5))),
+        make_expr(ExprKind::Literal(Literal::Int(3))),
+    ];
+
+    let result = inferer.infer_array(&mut elements, &(0..12));
+    assert_eq!(
+        result.unwrap(),
+        Type::Array {
+            element_type: Box::new(Type::Float),
+            size: 3,
+        }
+    );
+}
+
+#[test]
+fn test_infer_array_heterogeneous_error_span() {
+    let mut scopes = create_test_scopes();
+    let mut inferer = TypeInferer::new(&mut scopes);
+
+    // [1, 2.0, true] - 提升后仍无法统一，错误指向第一个不兼容的元素
+    let mut elements = vec![
+        make_expr(ExprKind::Literal(Literal::Int(1))),
+        make_expr(ExprKind::Literal(Literal::Float(2.0))),
+        Expr {
+            kind: ExprKind::Literal(Literal::Bool(true)),
+            span: 9..13,
+        },
+    ];
+
+    let result = inferer.infer_array(&mut elements, &(0..14));
+    match result.unwrap_err() {
+        SemanticError::TypeMismatch {
+            expected,
+            found,
+            span,
+        } => {
+            assert_eq!(expected, "float");
+            assert_eq!(found, "bool");
+            assert_eq!(span, 9..13);
+        }
+        other => panic!("Expected TypeMismatch, got {:?}", other),
+    }
+}
+
 #[test]
 fn test_infer_index_access_int_array() {
     let mut scopes = create_test_scopes();
@@ -176,6 +228,81 @@ fn test_infer_index_compile_time_bounds_check_overflow() {
     ));
 }
 
+#[test]
+fn test_infer_index_compile_time_bounds_check_unary_neg() {
+    let mut scopes = create_test_scopes();
+    let mut inferer = TypeInferer::new(&mut scopes);
+
+    // arr: [3]int
+    let mut array_expr = make_expr(ExprKind::Array(vec![
+        make_expr(ExprKind::Literal(Literal::Int(1))),
+        make_expr(ExprKind::Literal(Literal::Int(2))),
+        make_expr(ExprKind::Literal(Literal::Int(3))),
+    ]));
+
+    // arr[-1] 在源码中被解析为 Unary(Neg, 1)
+    let mut index_expr = make_expr(ExprKind::Unary(
+        UnaryOp::Neg,
+        Box::new(make_expr(ExprKind::Literal(Literal::Int(1)))),
+    ));
+
+    let result = inferer.infer_index(&mut array_expr, &mut index_expr, &(0..10));
+    assert!(matches!(
+        result.unwrap_err(),
+        SemanticError::ArrayIndexOutOfBounds {
+            index: -1,
+            size: 3,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn test_infer_index_constant_expression() {
+    let mut scopes = create_test_scopes();
+    let mut inferer = TypeInferer::new(&mut scopes);
+
+    let make_array = || {
+        make_expr(ExprKind::Array(vec![
+            make_expr(ExprKind::Literal(Literal::Int(1))),
+            make_expr(ExprKind::Literal(Literal::Int(2))),
+            make_expr(ExprKind::Literal(Literal::Int(3))),
+        ]))
+    };
+    let make_binary = |l: i64, op: BinaryOp, r: i64| {
+        make_expr(ExprKind::Binary(
+            Box::new(make_expr(ExprKind::Literal(Literal::Int(l)))),
+            op,
+            Box::new(make_expr(ExprKind::Literal(Literal::Int(r)))),
+        ))
+    };
+
+    // arr[1 + 1] - 常量索引在范围内
+    let mut array_expr = make_array();
+    let mut index_expr = make_binary(1, BinaryOp::Add, 1);
+    let result = inferer.infer_index(&mut array_expr, &mut index_expr, &(0..10));
+    assert_eq!(result.unwrap(), Type::Int);
+
+    // arr[2 * 2] - 常量索引越界
+    let mut array_expr = make_array();
+    let mut index_expr = make_binary(2, BinaryOp::Mul, 2);
+    let result = inferer.infer_index(&mut array_expr, &mut index_expr, &(0..10));
+    assert!(matches!(
+        result.unwrap_err(),
+        SemanticError::ArrayIndexOutOfBounds {
+            index: 4,
+            size: 3,
+            ..
+        }
+    ));
+
+    // arr[1 / 0] - 无法在编译期求值，留给运行时
+    let mut array_expr = make_array();
+    let mut index_expr = make_binary(1, BinaryOp::Div, 0);
+    let result = inferer.infer_index(&mut array_expr, &mut index_expr, &(0..10));
+    assert!(result.is_ok());
+}
+
 #[test]
 fn test_infer_index_non_int_index_error() {
     let mut scopes = create_test_scopes();
@@ -233,3 +360,116 @@ fn test_infer_array_invalid_property() {
         SemanticError::UndefinedField { .. }
     ));
 }
+
+#[test]
+fn test_infer_record_literal() {
+    let mut scopes = create_test_scopes();
+    let mut inferer = TypeInferer::new(&mut scopes);
+
+    // { y: "a", x: 1 }
+    let mut expr = make_expr(ExprKind::RecordLiteral(vec![
+        (
+            "y".to_string(),
+            make_expr(ExprKind::Literal(Literal::String("a".to_string()))),
+        ),
+        (
+            "x".to_string(),
+            make_expr(ExprKind::Literal(Literal::Int(1))),
+        ),
+    ]));
+
+    let ty = inferer.infer(&mut expr).unwrap();
+    assert_eq!(
+        ty,
+        Type::Record(vec![
+            ("x".to_string(), Type::Int),
+            ("y".to_string(), Type::String),
+        ])
+    );
+}
+
+#[test]
+fn test_infer_record_duplicate_field() {
+    let mut scopes = create_test_scopes();
+    let mut inferer = TypeInferer::new(&mut scopes);
+
+    // { x: 1, x: 2 }
+    let mut expr = make_expr(ExprKind::RecordLiteral(vec![
+        (
+            "x".to_string(),
+            make_expr(ExprKind::Literal(Literal::Int(1))),
+        ),
+        (
+            "x".to_string(),
+            make_expr(ExprKind::Literal(Literal::Int(2))),
+        ),
+    ]));
+
+    let result = inferer.infer(&mut expr);
+    assert!(matches!(
+        result,
+        Err(SemanticError::DuplicateDefinition { .. })
+    ));
+}
+
+#[test]
+fn test_record_structural_compatibility() {
+    use super::is_compatible;
+
+    let point = Type::record(vec![
+        ("y".to_string(), Type::Int),
+        ("x".to_string(), Type::Int),
+    ]);
+    let same = Type::record(vec![
+        ("x".to_string(), Type::Int),
+        ("y".to_string(), Type::Int),
+    ]);
+    let float_point = Type::record(vec![
+        ("x".to_string(), Type::Float),
+        ("y".to_string(), Type::Float),
+    ]);
+    let partial = Type::record(vec![("x".to_string(), Type::Int)]);
+
+    assert!(is_compatible(&point, &same));
+    // 字段类型不做隐式转换
+    assert!(!is_compatible(&float_point, &point));
+    assert!(!is_compatible(&point, &partial));
+}
+
+/// Helper: `match <bool> { case true => then_val, case false => else_val }`
+fn make_bool_match(then_val: Literal, else_val: Literal) -> Expr {
+    let case = |pattern: bool, body: Literal| lency_syntax::ast::MatchCase {
+        pattern: lency_syntax::ast::MatchPattern::Literal(Literal::Bool(pattern)),
+        guard: None,
+        body: Box::new(make_expr(ExprKind::Literal(body))),
+        span: 0..1,
+    };
+    make_expr(ExprKind::Match {
+        value: Box::new(make_expr(ExprKind::Literal(Literal::Bool(true)))),
+        cases: vec![case(true, then_val), case(false, else_val)],
+        default: None,
+    })
+}
+
+#[test]
+fn test_branch_types_unify_with_null_and_float() {
+    let mut scopes = create_test_scopes();
+    let mut inferer = TypeInferer::new(&mut scopes);
+
+    // cond ? 5 : null -> int?（null 在任一侧均可）
+    let nullable_int = Type::Nullable(Box::new(Type::Int));
+    let mut expr = make_bool_match(Literal::Int(5), Literal::Null);
+    assert_eq!(inferer.infer(&mut expr).unwrap(), nullable_int);
+    let mut expr = make_bool_match(Literal::Null, Literal::Int(5));
+    assert_eq!(inferer.infer(&mut expr).unwrap(), nullable_int);
+
+    // int 与 float 合并为 float
+    let mut expr = make_bool_match(Literal::Int(1), Literal::Float(2.5));
+    assert_eq!(inferer.infer(&mut expr).unwrap(), Type::Float);
+
+    let mut expr = make_bool_match(Literal::Int(1), Literal::String("a".to_string()));
+    assert!(matches!(
+        inferer.infer(&mut expr),
+        Err(SemanticError::TypeMismatch { .. })
+    ));
+}
@@ -10,7 +10,7 @@ use lency_syntax::ast::Type;
 /// - 添加新类型时，只需在此处更新实现
 /// - 避免在多处散落 match 语句
 pub trait TypeInfo {
-    /// 是否是数值类型（int, float）
+    /// 是否是数值类型（各宽度整数, uint, float）
     ///
     /// # Examples
     ///
@@ -40,7 +40,7 @@ pub trait TypeInfo {
 
     /// 是否是基础类型
     ///
-    /// 基础类型包括：int, float, bool, string, void
+    /// 基础类型包括：各宽度整数, uint, float, bool, string, void
     fn is_primitive(&self) -> bool;
 
     /// 是否是数组类型
@@ -75,7 +75,7 @@ pub trait TypeInfo {
 /// 为 Type 实现 TypeInfo
 impl TypeInfo for Type {
     fn is_numeric(&self) -> bool {
-        matches!(self, Type::Int | Type::Float)
+        self.int_bits().is_some() || matches!(self, Type::Float)
     }
 
     fn is_nullable(&self) -> bool {
@@ -85,7 +85,15 @@ impl TypeInfo for Type {
     fn is_primitive(&self) -> bool {
         matches!(
             self,
-            Type::Int | Type::Float | Type::Bool | Type::String | Type::Void
+            Type::Int
+                | Type::I8
+                | Type::I16
+                | Type::I32
+                | Type::UInt
+                | Type::Float
+                | Type::Bool
+                | Type::String
+                | Type::Void
         )
     }
 
@@ -7,6 +7,56 @@ pub struct Expr {
     pub span: Span,
 }
 
+impl Expr {
+    /// 编译期可确定的布尔值
+    ///
+    /// 只折叠字面量及其 `!`、`&&`、`||`、比较运算；短路的一侧可以是任意表达式，
+    /// 因为它本来就不会被求值
+    pub fn const_bool(&self) -> Option<bool> {
+        match &self.kind {
+            ExprKind::Literal(Literal::Bool(b)) => Some(*b),
+            ExprKind::Unary(UnaryOp::Not, inner) => inner.const_bool().map(|b| !b),
+            ExprKind::Binary(lhs, BinaryOp::And, rhs) => match lhs.const_bool()? {
+                false => Some(false),
+                true => rhs.const_bool(),
+            },
+            ExprKind::Binary(lhs, BinaryOp::Or, rhs) => match lhs.const_bool()? {
+                true => Some(true),
+                false => rhs.const_bool(),
+            },
+            ExprKind::Binary(lhs, op, rhs) => {
+                if let (Some(a), Some(b)) = (lhs.const_bool(), rhs.const_bool()) {
+                    return match op {
+                        BinaryOp::Eq => Some(a == b),
+                        BinaryOp::Neq => Some(a != b),
+                        _ => None,
+                    };
+                }
+                let (a, b) = (lhs.const_int()?, rhs.const_int()?);
+                match op {
+                    BinaryOp::Eq => Some(a == b),
+                    BinaryOp::Neq => Some(a != b),
+                    BinaryOp::Lt => Some(a < b),
+                    BinaryOp::Gt => Some(a > b),
+                    BinaryOp::Leq => Some(a <= b),
+                    BinaryOp::Geq => Some(a >= b),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// 整数字面量（可带负号）的值
+    pub fn const_int(&self) -> Option<i64> {
+        match &self.kind {
+            ExprKind::Literal(Literal::Int(n)) => Some(*n),
+            ExprKind::Unary(UnaryOp::Neg, inner) => inner.const_int()?.checked_neg(),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum ExprKind {
     // 字面量: 1, "hello", true, null
@@ -67,15 +117,31 @@ pub enum ExprKind {
         args: Vec<crate::ast::Type>,
     },
 
+    // 关联函数路径: Point::origin，作为被调用者使用
+    Path {
+        type_name: String,
+        member: String,
+    },
+
     // 结构体字面量: Point { x: 10, y: 20 }
     StructLiteral {
         type_: crate::ast::Type,
         fields: Vec<(String, Expr)>, // (field_name, value)
     },
 
+    // 匿名记录字面量: { x: 10, y: 20 }，类型为 Type::Record
+    RecordLiteral(Vec<(String, Expr)>),
+
     // Vec 字面量: vec![1, 2, 3]
     VecLiteral(Vec<Expr>),
 
+    // 重复初始化: [0; 10]（count 为编译期常量）或 vec![0; n]（count 可在运行时计算）
+    Repeat {
+        value: Box<Expr>,
+        count: Box<Expr>,
+        is_vec: bool,
+    },
+
     // Try 运算符: expr? (如果 err 则提前返回)
     Try(Box<Expr>),
 
@@ -112,13 +178,27 @@ pub enum ExprKind {
     CharToString(Box<Expr>),
     // panic("error message")
     Panic(Box<Expr>),
+    // assert(cond, "message")，cond 为 false 时以 message panic
+    Assert(Box<Expr>, Box<Expr>),
     // format("template {}", args_vec) -> string
     Format(Box<Expr>, Box<Expr>),
+    // fmt!("{} + {} = {}", a, b, c) -> string，模板为字面量，编译期检查占位符数量
+    Fmt {
+        template: String,
+        args: Vec<Expr>,
+    },
+    // 显式类型转换: x as int, cast<UserId>(42)
+    Cast {
+        ty: crate::ast::Type,
+        value: Box<Expr>,
+    },
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct MatchCase {
     pub pattern: MatchPattern,
+    /// `case n if n > 0 => ...` 中的守卫条件，模式匹配成功后才求值
+    pub guard: Option<Expr>,
     pub body: Box<Expr>,
     pub span: Span,
 }
@@ -132,6 +212,13 @@ pub enum MatchPattern {
     },
     Variable(String),
     Wildcard,
+    /// 闭区间 `200..=299`，两端都是整数字面量
+    Range {
+        start: Literal,
+        end: Literal,
+    },
+    /// `400 | 404`：任一分支匹配即成功，分支内不能绑定变量
+    Or(Vec<MatchPattern>),
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -1,10 +1,12 @@
 pub mod expr;
+pub mod pretty;
 pub mod stmt;
 pub mod types;
 pub mod visitor;
 
 // 重新导出核心类型，方便外部直接使用 lency_syntax::ast::Expr 等
 pub use expr::{BinaryOp, Expr, ExprKind, Literal, MatchCase, MatchPattern, Span, UnaryOp};
+pub use pretty::AstPrinter;
 pub use stmt::{Decl, EnumVariant, Field, GenericParam, ImportItem, Param, Stmt, TraitMethod};
 pub use types::Type;
 pub use visitor::Visitor;
@@ -0,0 +1,166 @@
+//! 声明节点的打印
+
+use super::{generics, param_list, type_suffix, AstPrinter};
+use crate::ast::stmt::{Decl, EnumVariant};
+use crate::ast::visitor::Visitor;
+
+impl AstPrinter {
+    pub(super) fn print_decl(&mut self, decl: &Decl) {
+        match decl {
+            Decl::Function {
+                name,
+                generic_params,
+                params,
+                return_type,
+                body,
+                ..
+            } => self.node(
+                format!(
+                    "Function {}{}({}) -> {}",
+                    name,
+                    generics(generic_params),
+                    param_list(params),
+                    return_type
+                ),
+                |p| p.stmts(body),
+            ),
+            Decl::ExternFunction {
+                abi,
+                name,
+                generic_params,
+                params,
+                is_variadic,
+                return_type,
+                ..
+            } => self.line(format!(
+                "ExternFunction {}{}{}({}{}) -> {}",
+                abi.as_ref()
+                    .map(|abi| format!("\"{}\" ", abi))
+                    .unwrap_or_default(),
+                name,
+                generics(generic_params),
+                param_list(params),
+                match (is_variadic, params.is_empty()) {
+                    (false, _) => "",
+                    (true, true) => "...",
+                    (true, false) => ", ...",
+                },
+                return_type
+            )),
+            Decl::Struct {
+                is_packed,
+                is_extern,
+                name,
+                generic_params,
+                fields,
+                ..
+            } => self.node(
+                format!(
+                    "{}Struct {}{}",
+                    match (is_packed, is_extern) {
+                        (true, _) => "Packed ",
+                        (_, true) => "Extern ",
+                        _ => "",
+                    },
+                    name,
+                    generics(generic_params)
+                ),
+                |p| {
+                    for field in fields {
+                        let text = format!("Field {} {}", field.ty, field.name);
+                        match &field.default {
+                            Some(default) => p.node(text, |p| p.visit_expr(default)),
+                            None => p.line(text),
+                        }
+                    }
+                },
+            ),
+            Decl::Impl {
+                trait_ref,
+                type_name,
+                generic_params,
+                methods,
+                ..
+            } => {
+                let header = match trait_ref {
+                    Some(tr) => {
+                        format!("Impl{} {} for {}", generics(generic_params), tr, type_name)
+                    }
+                    None => format!("Impl{} {}", generics(generic_params), type_name),
+                };
+                self.node(header, |p| {
+                    for method in methods {
+                        p.visit_decl(method);
+                    }
+                })
+            }
+            Decl::Trait {
+                name,
+                generic_params,
+                methods,
+                ..
+            } => self.node(format!("Trait {}{}", name, generics(generic_params)), |p| {
+                for method in methods {
+                    let header = format!(
+                        "Method {}({}) -> {}",
+                        method.name,
+                        param_list(&method.params),
+                        method.return_type
+                    );
+                    match &method.default_body {
+                        Some(body) => p.node(format!("{} (default)", header), |p| {
+                            for stmt in body {
+                                p.visit_stmt(stmt);
+                            }
+                        }),
+                        None => p.line(header),
+                    }
+                }
+            }),
+            Decl::Enum {
+                name,
+                generic_params,
+                variants,
+                ..
+            } => self.node(format!("Enum {}{}", name, generics(generic_params)), |p| {
+                for variant in variants {
+                    match variant {
+                        EnumVariant::Unit(v, None) => p.line(format!("Variant {}", v)),
+                        EnumVariant::Unit(v, Some(value)) => {
+                            p.line(format!("Variant {} = {}", v, value))
+                        }
+                        EnumVariant::Tuple(v, types) => {
+                            let types: Vec<_> = types.iter().map(|t| t.to_string()).collect();
+                            p.line(format!("Variant {}({})", v, types.join(", ")))
+                        }
+                    }
+                }
+            }),
+            Decl::Const {
+                name, ty, value, ..
+            } => self.node(format!("Const {}{}", name, type_suffix(ty.as_ref())), |p| {
+                p.visit_expr(value)
+            }),
+            Decl::Newtype { name, ty, .. } => self.line(format!("Newtype {} = {}", name, ty)),
+            Decl::Var {
+                name, ty, value, ..
+            } => self.node(format!("Var {}{}", name, type_suffix(ty.as_ref())), |p| {
+                p.visit_expr(value)
+            }),
+            Decl::Import {
+                is_public, items, ..
+            } => self.node(if *is_public { "PubImport" } else { "Import" }, |p| {
+                for item in items {
+                    match (&item.alias, &item.names) {
+                        (None, None) if item.glob => p.line(format!("{}.*", item.path.join("."))),
+                        (Some(alias), _) => p.line(format!("{} as {}", item.path.join("."), alias)),
+                        (None, Some(names)) => {
+                            p.line(format!("{}.{{{}}}", item.path.join("."), names.join(", ")))
+                        }
+                        (None, None) => p.line(item.path.join(".")),
+                    }
+                }
+            }),
+        }
+    }
+}
@@ -0,0 +1,155 @@
+//! 表达式节点的打印
+
+use super::{literal, param_list, AstPrinter};
+use crate::ast::expr::{BinaryOp, Expr, ExprKind, UnaryOp};
+use crate::ast::visitor::Visitor;
+
+impl AstPrinter {
+    pub(super) fn print_expr(&mut self, expr: &Expr) {
+        match &expr.kind {
+            ExprKind::Literal(lit) => self.line(format!("Literal {}", literal(lit))),
+            ExprKind::Unit => self.line("Unit"),
+            ExprKind::Variable(name) => self.line(format!("Variable {}", name)),
+            ExprKind::Binary(left, op, right) => {
+                self.node(format!("Binary {}", binary_op(op)), |p| {
+                    p.visit_expr(left);
+                    p.visit_expr(right);
+                })
+            }
+            ExprKind::Unary(op, operand) => {
+                let op = match op {
+                    UnaryOp::Neg => "-",
+                    UnaryOp::Not => "!",
+                };
+                self.node(format!("Unary {}", op), |p| p.visit_expr(operand))
+            }
+            ExprKind::Call { callee, args } => self.node("Call", |p| {
+                p.visit_expr(callee);
+                p.exprs(args);
+            }),
+            ExprKind::Get { object, name } => {
+                self.node(format!("Get .{}", name), |p| p.visit_expr(object))
+            }
+            ExprKind::SafeGet { object, name } => {
+                self.node(format!("SafeGet ?.{}", name), |p| p.visit_expr(object))
+            }
+            ExprKind::Array(elements) => self.node("Array", |p| p.exprs(elements)),
+            ExprKind::Match {
+                value,
+                cases,
+                default,
+            } => self.node("Match", |p| {
+                p.visit_expr(value);
+                for case in cases {
+                    p.node("Case", |p| {
+                        p.pattern(&case.pattern);
+                        if let Some(guard) = &case.guard {
+                            p.node("Guard", |p| p.visit_expr(guard));
+                        }
+                        p.visit_expr(&case.body);
+                    });
+                }
+                if let Some(default) = default {
+                    p.node("Default", |p| p.visit_expr(default));
+                }
+            }),
+            ExprKind::Print(arg) => self.node("Print", |p| p.visit_expr(arg)),
+            ExprKind::Index { array, index } => self.node("Index", |p| {
+                p.visit_expr(array);
+                p.visit_expr(index);
+            }),
+            ExprKind::GenericInstantiation { base, args } => {
+                let args: Vec<_> = args.iter().map(|t| t.to_string()).collect();
+                self.node(format!("GenericInstantiation <{}>", args.join(", ")), |p| {
+                    p.visit_expr(base)
+                })
+            }
+            ExprKind::Path { type_name, member } => {
+                self.line(format!("Path {}::{}", type_name, member))
+            }
+            ExprKind::StructLiteral { type_, fields } => {
+                self.node(format!("StructLiteral {}", type_), |p| {
+                    for (name, value) in fields {
+                        p.node(format!("{}:", name), |p| p.visit_expr(value));
+                    }
+                })
+            }
+            ExprKind::RecordLiteral(fields) => self.node("RecordLiteral", |p| {
+                for (name, value) in fields {
+                    p.node(format!("{}:", name), |p| p.visit_expr(value));
+                }
+            }),
+            ExprKind::VecLiteral(elements) => self.node("VecLiteral", |p| p.exprs(elements)),
+            ExprKind::Repeat {
+                value,
+                count,
+                is_vec,
+            } => self.node(if *is_vec { "VecRepeat" } else { "Repeat" }, |p| {
+                p.visit_expr(value);
+                p.visit_expr(count);
+            }),
+            ExprKind::Try(inner) => self.node("Try", |p| p.visit_expr(inner)),
+            ExprKind::Ok(inner) => self.node("Ok", |p| p.visit_expr(inner)),
+            ExprKind::Err(inner) => self.node("Err", |p| p.visit_expr(inner)),
+            ExprKind::Closure { params, body } => self
+                .node(format!("Closure |{}|", param_list(params)), |p| {
+                    p.visit_expr(body)
+                }),
+            ExprKind::ReadFile(path) => self.node("ReadFile", |p| p.visit_expr(path)),
+            ExprKind::WriteFile(path, content) => self.node("WriteFile", |p| {
+                p.visit_expr(path);
+                p.visit_expr(content);
+            }),
+            ExprKind::Len(arg) => self.node("Len", |p| p.visit_expr(arg)),
+            ExprKind::Trim(arg) => self.node("Trim", |p| p.visit_expr(arg)),
+            ExprKind::Split(s, sep) => self.node("Split", |p| {
+                p.visit_expr(s);
+                p.visit_expr(sep);
+            }),
+            ExprKind::Join(v, sep) => self.node("Join", |p| {
+                p.visit_expr(v);
+                p.visit_expr(sep);
+            }),
+            ExprKind::Substr(s, start, len) => self.node("Substr", |p| {
+                p.visit_expr(s);
+                p.visit_expr(start);
+                p.visit_expr(len);
+            }),
+            ExprKind::CharToString(arg) => self.node("CharToString", |p| p.visit_expr(arg)),
+            ExprKind::Panic(arg) => self.node("Panic", |p| p.visit_expr(arg)),
+            ExprKind::Assert(cond, message) => self.node("Assert", |p| {
+                p.visit_expr(cond);
+                p.visit_expr(message);
+            }),
+            ExprKind::Format(template, args) => self.node("Format", |p| {
+                p.visit_expr(template);
+                p.visit_expr(args);
+            }),
+            ExprKind::Fmt { template, args } => {
+                self.node(format!("Fmt {:?}", template), |p| p.exprs(args))
+            }
+            ExprKind::Cast { ty, value } => {
+                self.node(format!("Cast<{}>", ty), |p| p.visit_expr(value))
+            }
+        }
+    }
+}
+
+fn binary_op(op: &BinaryOp) -> &'static str {
+    match op {
+        BinaryOp::Add => "+",
+        BinaryOp::Sub => "-",
+        BinaryOp::Mul => "*",
+        BinaryOp::Div => "/",
+        BinaryOp::Mod => "%",
+        BinaryOp::Eq => "==",
+        BinaryOp::Neq => "!=",
+        BinaryOp::Lt => "<",
+        BinaryOp::Gt => ">",
+        BinaryOp::Leq => "<=",
+        BinaryOp::Geq => ">=",
+        BinaryOp::And => "&&",
+        BinaryOp::Or => "||",
+        BinaryOp::Elvis => "??",
+    }
+}
@@ -0,0 +1,174 @@
+//! AST 美化打印
+//!
+//! 将语法树打印为缩进的树形文本，用于 `lencyc --dump-ast` 调试输出。
+//! 输出不包含 span，相同的源码结构总是得到相同的文本，便于在 bug 报告中引用。
+
+use std::fmt;
+
+use crate::ast::expr::{Expr, Literal, MatchPattern};
+use crate::ast::stmt::{Decl, GenericParam, Param, Stmt};
+use crate::ast::visitor::Visitor;
+use crate::ast::Program;
+
+mod decl;
+mod expr;
+mod stmt;
+
+/// 树形打印器：每个节点占一行，子节点缩进两格
+pub struct AstPrinter {
+    out: String,
+    indent: usize,
+}
+
+impl AstPrinter {
+    pub fn new() -> Self {
+        Self {
+            out: String::new(),
+            indent: 0,
+        }
+    }
+
+    /// 取出打印结果
+    pub fn finish(self) -> String {
+        self.out
+    }
+
+    fn line(&mut self, text: impl AsRef<str>) {
+        for _ in 0..self.indent {
+            self.out.push_str("  ");
+        }
+        self.out.push_str(text.as_ref());
+        self.out.push('\n');
+    }
+
+    /// 打印一行标题，并在缩进一级后打印其子节点
+    fn node(&mut self, text: impl AsRef<str>, children: impl FnOnce(&mut Self)) {
+        self.line(text);
+        self.indent += 1;
+        children(self);
+        self.indent -= 1;
+    }
+
+    fn stmts(&mut self, stmts: &[Stmt]) {
+        for stmt in stmts {
+            self.visit_stmt(stmt);
+        }
+    }
+
+    fn exprs(&mut self, exprs: &[Expr]) {
+        for expr in exprs {
+            self.visit_expr(expr);
+        }
+    }
+
+    fn pattern(&mut self, pattern: &MatchPattern) {
+        match pattern {
+            MatchPattern::Literal(lit) => self.line(format!("Literal {}", literal(lit))),
+            MatchPattern::Variable(name) => self.line(format!("Bind {}", name)),
+            MatchPattern::Wildcard => self.line("Wildcard"),
+            MatchPattern::Range { start, end } => {
+                self.line(format!("Range {}..={}", literal(start), literal(end)))
+            }
+            MatchPattern::Or(alternatives) => self.node("Or", |p| {
+                for alt in alternatives {
+                    p.pattern(alt);
+                }
+            }),
+            MatchPattern::Variant { name, sub_patterns } => {
+                self.node(format!("Variant {}", name), |p| {
+                    for sub in sub_patterns {
+                        p.pattern(sub);
+                    }
+                })
+            }
+        }
+    }
+}
+
+impl Default for AstPrinter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Visitor<()> for AstPrinter {
+    fn visit_program(&mut self, program: &Program) {
+        self.node("Program", |p| {
+            for decl in &program.decls {
+                p.visit_decl(decl);
+            }
+        });
+    }
+
+    fn visit_decl(&mut self, decl: &Decl) {
+        self.print_decl(decl);
+    }
+
+    fn visit_stmt(&mut self, stmt: &Stmt) {
+        self.print_stmt(stmt);
+    }
+
+    fn visit_expr(&mut self, expr: &Expr) {
+        self.print_expr(expr);
+    }
+}
+
+fn literal(lit: &Literal) -> String {
+    match lit {
+        Literal::Int(v) => v.to_string(),
+        Literal::Float(v) => format!("{:?}", v),
+        Literal::Bool(v) => v.to_string(),
+        Literal::String(s) => format!("{:?}", s),
+        Literal::Null => "null".to_string(),
+    }
+}
+
+fn param_list(params: &[Param]) -> String {
+    let params: Vec<_> = params
+        .iter()
+        .map(|p| format!("{} {}", p.ty, p.name))
+        .collect();
+    params.join(", ")
+}
+
+fn generics(params: &[GenericParam]) -> String {
+    if params.is_empty() {
+        return String::new();
+    }
+    let params: Vec<_> = params
+        .iter()
+        .map(|gp| {
+            if gp.bounds.is_empty() {
+                return gp.name.clone();
+            }
+            let bounds: Vec<_> = gp.bounds.iter().map(|b| b.to_string()).collect();
+            format!("{}: {}", gp.name, bounds.join(" + "))
+        })
+        .collect();
+    format!("<{}>", params.join(", "))
+}
+
+fn type_suffix(ty: Option<&crate::ast::Type>) -> String {
+    ty.map(|t| format!(": {}", t)).unwrap_or_default()
+}
+
+/// 用 `AstPrinter` 打印单个节点，实现 `Display`
+macro_rules! display_via_printer {
+    ($ty:ty, $visit:ident) => {
+        impl fmt::Display for $ty {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                let mut printer = AstPrinter::new();
+                printer.$visit(self);
+                f.write_str(&printer.finish())
+            }
+        }
+    };
+}
+
+display_via_printer!(Program, visit_program);
+display_via_printer!(Decl, visit_decl);
+display_via_printer!(Stmt, visit_stmt);
+display_via_printer!(Expr, visit_expr);
+
+#[cfg(test)]
+mod tests;
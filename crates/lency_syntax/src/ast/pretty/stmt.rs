@@ -0,0 +1,124 @@
+//! 语句节点的打印
+
+use super::{type_suffix, AstPrinter};
+use crate::ast::stmt::Stmt;
+use crate::ast::visitor::Visitor;
+
+impl AstPrinter {
+    pub(super) fn print_stmt(&mut self, stmt: &Stmt) {
+        match stmt {
+            Stmt::VarDecl {
+                name,
+                mutable,
+                ty,
+                value,
+                ..
+            } => self.node(
+                format!(
+                    "{} {}{}",
+                    if *mutable { "VarDecl" } else { "LetDecl" },
+                    name,
+                    type_suffix(ty.as_ref())
+                ),
+                |p| p.visit_expr(value),
+            ),
+            Stmt::LoopVarDecl {
+                name,
+                mutable,
+                ty,
+                loop_stmt,
+                default,
+                ..
+            } => self.node(
+                format!(
+                    "{} {}{}",
+                    if *mutable {
+                        "LoopVarDecl"
+                    } else {
+                        "LoopLetDecl"
+                    },
+                    name,
+                    type_suffix(ty.as_ref())
+                ),
+                |p| {
+                    p.visit_stmt(loop_stmt);
+                    p.node("Default", |p| p.visit_expr(default));
+                },
+            ),
+            Stmt::Assignment { target, value, .. } => self.node("Assign", |p| {
+                p.visit_expr(target);
+                p.visit_expr(value);
+            }),
+            Stmt::Expression(expr) => self.node("ExprStmt", |p| p.visit_expr(expr)),
+            Stmt::Block(stmts) => self.node("Block", |p| p.stmts(stmts)),
+            Stmt::If {
+                condition,
+                then_block,
+                else_block,
+                ..
+            } => self.node("If", |p| {
+                p.visit_expr(condition);
+                p.node("Then", |p| p.stmts(then_block));
+                if let Some(else_block) = else_block {
+                    p.node("Else", |p| p.stmts(else_block));
+                }
+            }),
+            Stmt::IfLet {
+                pattern,
+                value,
+                then_block,
+                else_block,
+                ..
+            } => self.node("IfLet", |p| {
+                p.pattern(pattern);
+                p.visit_expr(value);
+                p.node("Then", |p| p.stmts(then_block));
+                if let Some(else_block) = else_block {
+                    p.node("Else", |p| p.stmts(else_block));
+                }
+            }),
+            Stmt::While {
+                condition, body, ..
+            } => self.node("While", |p| {
+                p.visit_expr(condition);
+                p.node("Body", |p| p.stmts(body));
+            }),
+            Stmt::For {
+                init,
+                condition,
+                update,
+                body,
+                ..
+            } => self.node("For", |p| {
+                if let Some(init) = init {
+                    p.node("Init", |p| p.visit_stmt(init));
+                }
+                if let Some(condition) = condition {
+                    p.node("Cond", |p| p.visit_expr(condition));
+                }
+                if let Some(update) = update {
+                    p.node("Update", |p| p.visit_stmt(update));
+                }
+                p.node("Body", |p| p.stmts(body));
+            }),
+            Stmt::ForIn {
+                iterator,
+                iterable,
+                body,
+                ..
+            } => self.node(format!("ForIn {}", iterator), |p| {
+                p.visit_expr(iterable);
+                p.node("Body", |p| p.stmts(body));
+            }),
+            Stmt::Return { value, .. } => match value {
+                Some(value) => self.node("Return", |p| p.visit_expr(value)),
+                None => self.line("Return"),
+            },
+            Stmt::Break { value, .. } => match value {
+                Some(value) => self.node("Break", |p| p.visit_expr(value)),
+                None => self.line("Break"),
+            },
+            Stmt::Continue { .. } => self.line("Continue"),
+        }
+    }
+}
@@ -0,0 +1,91 @@
+use crate::parser::parse;
+
+#[test]
+fn test_dump_small_program() {
+    let code = r#"
+            struct Point {
+                int x
+                int y
+            }
+
+            int main() {
+                var p = Point { x: 1, y: 2 }
+                if p.x < 2 {
+                    print(p.x + p.y * 3)
+                } else {
+                    return -1
+                }
+                for var i = 0; i < 3; i = i + 1 {
+                    p.x = i
+                }
+                return 0
+            }
+        "#;
+
+    let program = parse(code).expect("parse failed");
+    let expected = "\
+Program
+  Struct Point
+    Field int x
+    Field int y
+  Function main() -> int
+    VarDecl p
+      StructLiteral Point
+        x:
+          Literal 1
+        y:
+          Literal 2
+    If
+      Binary <
+        Get .x
+          Variable p
+        Literal 2
+      Then
+        ExprStmt
+          Print
+            Binary +
+              Get .x
+                Variable p
+              Binary *
+                Get .y
+                  Variable p
+                Literal 3
+      Else
+        Return
+          Literal -1
+    For
+      Init
+        VarDecl i
+          Literal 0
+      Cond
+        Binary <
+          Variable i
+          Literal 3
+      Update
+        Assign
+          Variable i
+          Binary +
+            Variable i
+            Literal 1
+      Body
+        Assign
+          Get .x
+            Variable p
+          Variable i
+    Return
+      Literal 0
+";
+    assert_eq!(program.to_string(), expected);
+}
+
+#[test]
+fn test_display_single_expr() {
+    let program = parse("int f(int a) { return a ?? 0 }").expect("parse failed");
+    let crate::ast::Decl::Function { body, .. } = &program.decls[0] else {
+        panic!("expected function");
+    };
+    assert_eq!(
+        body[0].to_string(),
+        "Return\n  Binary ??\n    Variable a\n    Literal 0\n"
+    );
+}
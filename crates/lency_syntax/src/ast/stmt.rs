@@ -6,28 +6,45 @@ use crate::ast::types::Type;
 pub enum Decl {
     // 函数定义: int add(int a, int b) { ... }
     // 泛型函数: T identity<T>(T x) { ... }
+    // 公开函数: pub int add(int a, int b) { ... }
     Function {
         span: Span,
+        doc: Option<String>, // 文档注释 (///)
+        is_public: bool,     // pub 修饰：可被其他模块引用
         name: String,
         generic_params: Vec<GenericParam>, // 泛型参数
         params: Vec<Param>,
         return_type: Type,
         body: Vec<Stmt>,
+        /// 导入模块中的函数记录其源文件路径（运行时 panic 按该文件报告位置），本文件中为 None
+        source_file: Option<String>,
     },
 
     // 外部函数声明: extern int print(int n);
+    // 指定调用约定: extern "C" int area(Rect r);
+    // 可变参数: extern int printf(string fmt, ...);
     ExternFunction {
         span: Span,
+        doc: Option<String>, // 文档注释 (///)
+        abi: Option<String>, // extern "C"：extern struct 参数/返回值按平台 C ABI 按值传递
         name: String,
         generic_params: Vec<GenericParam>,
         params: Vec<Param>,
+        is_variadic: bool, // 参数列表以 ... 结尾：固定参数之后可再传任意个基础类型实参
         return_type: Type,
     },
 
     // 结构体定义: struct Point { int x int y }
     // 泛型结构体: struct Box<T> { T value }
+    // 紧凑布局: packed struct Header { i8 tag int len }
+    // C 互操作: extern struct Rect { int w int h }
+    // 公开结构体: pub struct Point { ... }
     Struct {
         span: Span,
+        doc: Option<String>, // 文档注释 (///)
+        is_public: bool,     // pub 修饰：可被其他模块引用
+        is_packed: bool,     // packed 修饰：字段间不插入对齐填充
+        is_extern: bool,     // extern 修饰：可按值传给 extern "C" 函数
         name: String,
         generic_params: Vec<GenericParam>,
         fields: Vec<Field>,
@@ -55,6 +72,8 @@ pub enum Decl {
     // 泛型Trait: trait Comparable<T> { bool equals(T other); }
     Trait {
         span: Span,
+        doc: Option<String>, // 文档注释 (///)
+        is_public: bool,     // pub 修饰：可被其他模块引用
         name: String,
         generic_params: Vec<GenericParam>,
         methods: Vec<TraitMethod>,
@@ -66,80 +85,184 @@ pub enum Decl {
     // enum Option<T> { Some(T), None }
     Enum {
         span: Span,
+        doc: Option<String>, // 文档注释 (///)
+        is_public: bool,     // pub 修饰：可被其他模块引用
         name: String,
         generic_params: Vec<GenericParam>,
         variants: Vec<EnumVariant>,
     },
 
+    // 常量定义: const int SIZE = 4
+    Const {
+        span: Span,
+        doc: Option<String>, // 文档注释 (///)
+        is_public: bool,     // pub 修饰：可被其他模块引用
+        name: String,
+        ty: Option<Type>,
+        value: Expr,
+    },
+
+    // 新类型: newtype UserId = int
+    // 与底层类型表示相同，但不能与之互相赋值，需用 cast 显式转换
+    Newtype {
+        span: Span,
+        doc: Option<String>, // 文档注释 (///)
+        is_public: bool,     // pub 修饰：可被其他模块引用
+        name: String,
+        ty: Type,
+    },
+
     // Global Variable: var x = 1;
     Var {
         span: Span,
+        is_public: bool, // pub 修饰：可被其他模块引用
         name: String,
         ty: Option<Type>,
         value: Expr,
     },
 
     // Sprint 13: 模块系统
-    // import std.io;
+    // import std.io;  pub import foo.*;（pub 表示再导出）
     Import {
         span: Span,
+        is_public: bool,
         items: Vec<ImportItem>,
     },
 }
 
+impl Decl {
+    /// 获取声明前的文档注释（多行以换行符连接）
+    pub fn doc(&self) -> Option<&str> {
+        match self {
+            Decl::Function { doc, .. }
+            | Decl::ExternFunction { doc, .. }
+            | Decl::Struct { doc, .. }
+            | Decl::Trait { doc, .. }
+            | Decl::Enum { doc, .. }
+            | Decl::Const { doc, .. }
+            | Decl::Newtype { doc, .. } => doc.as_deref(),
+            Decl::Impl { .. } | Decl::Var { .. } | Decl::Import { .. } => None,
+        }
+    }
+
+    /// 是否带 pub 修饰；extern 函数始终可见，impl 块没有可见性
+    pub fn is_public(&self) -> bool {
+        match self {
+            Decl::Function { is_public, .. }
+            | Decl::Struct { is_public, .. }
+            | Decl::Trait { is_public, .. }
+            | Decl::Enum { is_public, .. }
+            | Decl::Const { is_public, .. }
+            | Decl::Newtype { is_public, .. }
+            | Decl::Var { is_public, .. }
+            | Decl::Import { is_public, .. } => *is_public,
+            Decl::ExternFunction { .. } | Decl::Impl { .. } => true,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct ImportItem {
     pub path: Vec<String>,
     pub alias: Option<String>,
+    /// 选择性导入的符号名：import foo.{a, b}；None 表示导入全部
+    pub names: Option<Vec<String>>,
+    /// 显式通配导入：import foo.*（语义与 import foo 相同）
+    pub glob: bool,
 }
 
 #[derive(Debug, Clone)]
 pub struct GenericParam {
     pub span: Span,
     pub name: String,
-    pub bound: Option<Type>, // 约束类型，如 T: Display
+    pub bounds: Vec<Type>, // 约束类型，如 T: Display + Comparable
 }
 
 #[derive(Debug, Clone)]
 pub enum EnumVariant {
-    // Unit Variant: None
-    Unit(String),
+    // Unit Variant: None, 可带显式判别值: Red = 1
+    Unit(String, Option<i64>),
     // Tuple Variant: Some(T)
     Tuple(String, Vec<Type>),
 }
 
+impl EnumVariant {
+    /// 变体名称
+    pub fn name(&self) -> &str {
+        match self {
+            EnumVariant::Unit(name, _) | EnumVariant::Tuple(name, _) => name,
+        }
+    }
+
+    /// 计算每个变体的判别值（tag）
+    ///
+    /// 显式指定的值直接使用；未指定的取前一个变体的值加一（第一个为 0），与 C 枚举一致
+    pub fn discriminants(variants: &[EnumVariant]) -> Vec<i64> {
+        let mut next = 0i64;
+        variants
+            .iter()
+            .map(|variant| {
+                let value = match variant {
+                    EnumVariant::Unit(_, Some(value)) => *value,
+                    _ => next,
+                };
+                next = value.wrapping_add(1);
+                value
+            })
+            .collect()
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct Param {
     pub name: String,
     pub ty: Type,
 }
 
+/// 结构体字段，可带默认值: int retries = 3
 #[derive(Debug, Clone)]
 pub struct Field {
     pub name: String,
     pub ty: Type,
+    /// 默认值；结构体字面量省略该字段时使用
+    pub default: Option<Expr>,
 }
 
-/// Trait 方法签名（无函数体）
-/// 例如: void greet(); 或 bool equals(T other);
+/// Trait 方法签名，可带默认实现
+/// 例如: void greet(); 或 bool equals(T other); 或 void hello() { this.greet() }
 #[derive(Debug, Clone)]
 pub struct TraitMethod {
+    pub span: Span,
     pub name: String,
     pub params: Vec<Param>,
     pub return_type: Type,
+    pub default_body: Option<Vec<Stmt>>, // 默认实现：实现者未提供该方法时使用
 }
 
 // 语句：出现在函数体内部
 #[derive(Debug, Clone)]
 pub enum Stmt {
     // 变量声明: var x: int = 1; 或 var x = 1;
+    // 不可变绑定: let x = 1;（mutable 为 false）
     VarDecl {
         span: Span,
         name: String,
+        mutable: bool,
         ty: Option<Type>, // None 表示需要类型推导
         value: Expr,
     },
 
+    // 以循环的值初始化变量: var found = while cond { ... break value ... } else default
+    // 循环经 `break value` 退出时取 value，正常结束时取 default
+    LoopVarDecl {
+        span: Span,
+        name: String,
+        mutable: bool,
+        ty: Option<Type>,
+        loop_stmt: Box<Stmt>, // While / For / ForIn
+        default: Expr,
+    },
+
     // 赋值: x = x + 1; (注意赋值在 Lency 里是语句，不是表达式)
     Assignment {
         span: Span,
@@ -161,6 +284,16 @@ pub enum Stmt {
         else_block: Option<Vec<Stmt>>,
     },
 
+    // 解构条件: if let x = maybe { ... } else { ... }
+    // 可空值非 null、或枚举值匹配模式时执行 then 分支，并绑定解包后的值
+    IfLet {
+        span: Span,
+        pattern: crate::ast::expr::MatchPattern,
+        value: Expr,
+        then_block: Vec<Stmt>,
+        else_block: Option<Vec<Stmt>>,
+    },
+
     // 循环: while (expr) { ... }
     While {
         span: Span,
@@ -190,8 +323,10 @@ pub enum Stmt {
         span: Span,
         value: Option<Expr>,
     },
+    // break 或 break value（仅用于作为值的循环）
     Break {
         span: Span,
+        value: Option<Expr>,
     },
     Continue {
         span: Span,
@@ -4,6 +4,10 @@ use std::fmt::{self, Display};
 pub enum Type {
     // --- 基础类型 ---
     Int,    // i64
+    I8,     // i8
+    I16,    // i16
+    I32,    // i32
+    UInt,   // u64
     Float,  // f64
     Bool,   // bool
     String, // string
@@ -29,6 +33,13 @@ pub enum Type {
         size: usize,
     },
 
+    // 以常量为长度的数组类型: [N]int
+    // 只在语法树中出现，Resolver 会把它替换为 Array
+    ConstSizedArray {
+        element_type: Box<Type>,
+        size_name: String,
+    },
+
     // 结构体类型: Point
     Struct(String),
 
@@ -49,15 +60,59 @@ pub enum Type {
         return_type: Box<Type>,
     },
 
+    // 匿名记录类型: { int x, int y }
+    // 结构化类型：字段按名称排序存放，字段集合相同即为同一类型
+    Record(Vec<(String, Type)>),
+
+    // 新类型: newtype UserId = int
+    // Resolver 把指向 newtype 的 Struct(name) 替换为它，携带底层类型
+    Newtype(String, Box<Type>),
+
+    // Trait 对象: dyn Printable
+    // 运行时为 (数据指针, 虚表指针) 胖指针，方法调用经虚表动态分发
+    TraitObject(String),
+
+    // 自身类型: This，只能出现在 impl 块中
+    // Resolver 会把它替换为 impl 的目标类型
+    SelfType,
+
     // 错误占位符 (当用户写错类型时，编译器用这个占位，防止崩溃)
     Error,
 }
 
+impl Type {
+    /// 构造记录类型，字段按名称排序，保证结构相同的记录类型相等
+    pub fn record(mut fields: Vec<(String, Type)>) -> Type {
+        fields.sort_by(|a, b| a.0.cmp(&b.0));
+        Type::Record(fields)
+    }
+
+    /// 整数类型的位宽（int 与 uint 为 64 位），非整数类型返回 None
+    pub fn int_bits(&self) -> Option<u32> {
+        match self {
+            Type::I8 => Some(8),
+            Type::I16 => Some(16),
+            Type::I32 => Some(32),
+            Type::Int | Type::UInt => Some(64),
+            _ => None,
+        }
+    }
+
+    /// 是否为无符号整数类型
+    pub fn is_unsigned(&self) -> bool {
+        matches!(self, Type::UInt)
+    }
+}
+
 // 让类型能打印成好看的字符串: "List<int>", "string?"
 impl Display for Type {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Type::Int => write!(f, "int"),
+            Type::I8 => write!(f, "i8"),
+            Type::I16 => write!(f, "i16"),
+            Type::I32 => write!(f, "i32"),
+            Type::UInt => write!(f, "uint"),
             Type::Float => write!(f, "float"),
             Type::Bool => write!(f, "bool"),
             Type::String => write!(f, "string"),
@@ -76,6 +131,10 @@ impl Display for Type {
             }
             Type::Nullable(inner) => write!(f, "{}?", inner),
             Type::Array { element_type, size } => write!(f, "[{}]{}", size, element_type),
+            Type::ConstSizedArray {
+                element_type,
+                size_name,
+            } => write!(f, "[{}]{}", size_name, element_type),
             Type::Struct(name) => write!(f, "{}", name),
             Type::Vec(inner) => write!(f, "Vec<{}>", inner),
             Type::Result { ok_type, err_type } => write!(f, "Result<{}, {}>", ok_type, err_type),
@@ -92,6 +151,19 @@ impl Display for Type {
                 }
                 write!(f, ")")
             }
+            Type::Record(fields) => {
+                write!(f, "{{")?;
+                for (i, (name, ty)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, " {} {}", ty, name)?;
+                }
+                write!(f, " }}")
+            }
+            Type::Newtype(name, _) => write!(f, "{}", name),
+            Type::TraitObject(name) => write!(f, "dyn {}", name),
+            Type::SelfType => write!(f, "This"),
             Type::Error => write!(f, "<?>"),
         }
     }
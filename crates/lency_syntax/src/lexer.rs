@@ -1,8 +1,109 @@
-use logos::Logos;
+use logos::{FilterResult, Lexer, Logos};
 use std::fmt;
+use std::ops::Range;
+
+/// 词法错误
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub enum LexError {
+    /// 无法识别的字符或非法字面量
+    #[default]
+    InvalidToken,
+    /// 块注释未闭合，span 指向最外层的 `/*`
+    UnterminatedBlockComment { span: Range<usize> },
+    /// 整数字面量超出 int (i64) 范围
+    IntegerLiteralOverflow { literal: String },
+}
+
+impl fmt::Display for LexError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LexError::InvalidToken => write!(f, "invalid token"),
+            LexError::UnterminatedBlockComment { .. } => write!(f, "unterminated block comment"),
+            LexError::IntegerLiteralOverflow { literal } => write!(
+                f,
+                "integer literal '{}' overflows int (max {})",
+                literal,
+                i64::MAX
+            ),
+        }
+    }
+}
+
+/// 对整个源文件做词法分析，返回每个 token（或词法错误）及其 span
+///
+/// 遇到错误不会停止：logos 会从出错位置之后继续切分，后续 token 照常产出。
+pub fn lex(source: &str) -> Vec<(Result<Token, LexError>, Range<usize>)> {
+    Token::lexer(source).spanned().collect()
+}
+
+/// 将 token 流格式化为逐行文本（`lencyc --dump-tokens`）
+///
+/// 每行形如 `4..5 Ident("a")`；错误行形如 `6..7 error: invalid token`。
+pub fn dump_tokens(source: &str) -> String {
+    let mut out = String::new();
+    for (tok, span) in lex(source) {
+        let span = format!("{}..{}", span.start, span.end);
+        match tok {
+            Ok(tok) => out.push_str(&format!("{:<10} {:?}\n", span, tok)),
+            Err(e) => out.push_str(&format!("{:<10} error: {}\n", span, e)),
+        }
+    }
+    out
+}
+
+/// 跳过块注释 `/* ... */`，支持嵌套
+///
+/// logos 已经消费了开头的 `/*`，这里手动扫描剩余输入并跟踪嵌套深度。
+fn block_comment(lex: &mut Lexer<Token>) -> FilterResult<(), LexError> {
+    let open = lex.span().start..lex.span().end;
+    let bytes = lex.remainder().as_bytes();
+    let mut depth = 1usize;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match (bytes[i], bytes.get(i + 1)) {
+            (b'/', Some(b'*')) => {
+                depth += 1;
+                i += 2;
+            }
+            (b'*', Some(b'/')) => {
+                depth -= 1;
+                i += 2;
+                if depth == 0 {
+                    lex.bump(i);
+                    return FilterResult::Skip;
+                }
+            }
+            _ => i += 1,
+        }
+    }
+
+    // 未闭合：吞掉剩余输入，避免后续产生一连串无意义的 token
+    lex.bump(bytes.len());
+    FilterResult::Error(LexError::UnterminatedBlockComment { span: open })
+}
+
+/// 解析整数字面量，超出 i64 范围时报告溢出而不是普通的非法 token
+fn int_literal(lex: &mut Lexer<Token>) -> Result<i64, LexError> {
+    lex.slice()
+        .parse()
+        .map_err(|_| LexError::IntegerLiteralOverflow {
+            literal: lex.slice().to_string(),
+        })
+}
+
+/// 提取文档注释正文：去掉 `///` 前缀和紧随其后的一个空格
+fn doc_comment(lex: &mut Lexer<Token>) -> String {
+    let text = &lex.slice()[3..];
+    text.strip_prefix(' ')
+        .unwrap_or(text)
+        .trim_end()
+        .to_string()
+}
 
 #[derive(Logos, Debug, PartialEq, Eq, Hash, Clone)] // 关键：加上 Eq 和 Hash
 #[logos(skip r"[ \t\r\n\f]+")]
+#[logos(error = LexError)]
 pub enum Token {
     // --- 关键字 (Keywords) ---
     #[token("var")]
@@ -11,6 +112,8 @@ pub enum Token {
     Const,
     #[token("if")]
     If,
+    #[token("let")]
+    Let,
     #[token("else")]
     Else,
     #[token("while")]
@@ -29,6 +132,8 @@ pub enum Token {
     Import,
     #[token("extern")]
     Extern,
+    #[token("pub")]
+    Pub,
     #[token("print")]
     Print,
     #[token("read_file")]
@@ -50,14 +155,24 @@ pub enum Token {
     CharToString,
     #[token("panic")]
     Panic,
+    #[token("assert")]
+    Assert,
     #[token("format")]
     Format,
+    #[token("fmt")]
+    Fmt,
+    #[token("cast")]
+    Cast,
     #[token("struct")]
     Struct,
+    #[token("packed")]
+    Packed,
     #[token("impl")]
     Impl,
     #[token("trait")]
     Trait,
+    #[token("dyn")]
+    Dyn,
     #[token("vec")]
     Vec,
     #[token("Ok")]
@@ -67,6 +182,8 @@ pub enum Token {
     #[token("as")]
     As,
 
+    #[token("newtype")]
+    Newtype,
     #[token("enum")]
     Enum,
 
@@ -81,6 +198,17 @@ pub enum Token {
     // --- 基础类型关键字 ---
     #[token("int")]
     TypeInt,
+    #[token("i8")]
+    TypeI8,
+    #[token("i16")]
+    TypeI16,
+    #[token("i32")]
+    TypeI32,
+    #[token("i64")]
+    TypeI64,
+    #[token("uint")]
+    #[token("u64")]
+    TypeUInt,
     #[token("float")]
     TypeFloat,
     #[token("bool")]
@@ -143,6 +271,10 @@ pub enum Token {
     Comma,
     #[token(".")]
     Dot,
+    #[token("..=")]
+    DotDotEq,
+    #[token("...")]
+    Ellipsis,
     #[token(":")]
     Colon,
     #[token(";")]
@@ -167,7 +299,7 @@ pub enum Token {
     #[regex(r"[a-zA-Z_][a-zA-Z0-9_]*", |lex| lex.slice().to_string())]
     Ident(String),
 
-    #[regex(r"-?[0-9]+", |lex| lex.slice().parse().ok())]
+    #[regex(r"-?[0-9]+", int_literal)]
     Int(i64),
 
     // 关键修正：为了 Hash 实现，Float 这里先存 String，AST 阶段再转 f64
@@ -202,15 +334,49 @@ pub enum Token {
     #[regex(r"//[^\n]*", logos::skip)]
     Comment,
 
+    // 文档注释: /// text，保留内容供声明解析器挂到 Decl 上
+    #[regex(r"///[^\n]*", doc_comment)]
+    DocComment(String),
+
+    // 块注释: /* ... */，允许嵌套
+    #[token("/*", block_comment)]
+    BlockComment,
+
     Error,
 }
 
+impl Token {
+    /// 上下文关键字：内置函数名只在调用位置 `name(...)`（`vec` 为 `vec![...]` / `vec<T>`）
+    /// 被识别为内置函数，其余位置可以当作普通标识符使用（如 `var len = 5`）
+    pub fn contextual_keyword(&self) -> Option<&'static str> {
+        let name = match self {
+            Token::Print => "print",
+            Token::ReadFile => "read_file",
+            Token::WriteFile => "write_file",
+            Token::Len => "len",
+            Token::Trim => "trim",
+            Token::Split => "split",
+            Token::Join => "join",
+            Token::Substr => "substr",
+            Token::CharToString => "char_to_string",
+            Token::Panic => "panic",
+            Token::Assert => "assert",
+            Token::Format => "format",
+            Token::Fmt => "fmt",
+            Token::Vec => "vec",
+            _ => return None,
+        };
+        Some(name)
+    }
+}
+
 impl fmt::Display for Token {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Token::Var => write!(f, "var"),
             Token::Const => write!(f, "const"),
             Token::If => write!(f, "if"),
+            Token::Let => write!(f, "let"),
             Token::Else => write!(f, "else"),
             Token::While => write!(f, "while"),
             Token::For => write!(f, "for"),
@@ -220,6 +386,7 @@ impl fmt::Display for Token {
             Token::Return => write!(f, "return"),
             Token::Import => write!(f, "import"),
             Token::Extern => write!(f, "extern"),
+            Token::Pub => write!(f, "pub"),
             Token::Print => write!(f, "print"),
             Token::ReadFile => write!(f, "read_file"),
             Token::WriteFile => write!(f, "write_file"),
@@ -230,19 +397,30 @@ impl fmt::Display for Token {
             Token::Substr => write!(f, "substr"),
             Token::CharToString => write!(f, "char_to_string"),
             Token::Panic => write!(f, "panic"),
+            Token::Assert => write!(f, "assert"),
             Token::Format => write!(f, "format"),
+            Token::Fmt => write!(f, "fmt"),
+            Token::Cast => write!(f, "cast"),
             Token::Struct => write!(f, "struct"),
+            Token::Packed => write!(f, "packed"),
             Token::Impl => write!(f, "impl"),
             Token::Trait => write!(f, "trait"),
+            Token::Dyn => write!(f, "dyn"),
             Token::Vec => write!(f, "vec"),
             Token::Ok => write!(f, "Ok"),
             Token::Err => write!(f, "Err"),
             Token::As => write!(f, "as"),
+            Token::Newtype => write!(f, "newtype"),
             Token::Enum => write!(f, "enum"),
             Token::Null => write!(f, "null"),
             Token::True => write!(f, "true"),
             Token::False => write!(f, "false"),
             Token::TypeInt => write!(f, "int"),
+            Token::TypeI8 => write!(f, "i8"),
+            Token::TypeI16 => write!(f, "i16"),
+            Token::TypeI32 => write!(f, "i32"),
+            Token::TypeI64 => write!(f, "i64"),
+            Token::TypeUInt => write!(f, "uint"),
             Token::TypeFloat => write!(f, "float"),
             Token::TypeBool => write!(f, "bool"),
             Token::TypeString => write!(f, "string"),
@@ -271,6 +449,8 @@ impl fmt::Display for Token {
             Token::RBracket => write!(f, "]"),
             Token::Comma => write!(f, ","),
             Token::Dot => write!(f, "."),
+            Token::DotDotEq => write!(f, "..="),
+            Token::Ellipsis => write!(f, "..."),
             Token::Colon => write!(f, ":"),
             Token::Semicolon => write!(f, ";"),
             Token::Question => write!(f, "?"),
@@ -285,6 +465,8 @@ impl fmt::Display for Token {
             Token::Float(s) => write!(f, "{}", s),
             Token::String(s) => write!(f, "\"{}\"", s), // Quote string
             Token::Comment => write!(f, "<comment>"),
+            Token::DocComment(s) => write!(f, "///{}", s),
+            Token::BlockComment => write!(f, "<block comment>"),
             Token::Error => write!(f, "<error>"),
         }
     }
@@ -5,7 +5,7 @@ pub mod parser; // Now points to parser/mod.rs
 #[cfg(test)]
 mod tests {
     // use super::*;
-    use crate::lexer::Token;
+    use crate::lexer::{LexError, Token};
     // use chumsky::Parser;
     use logos::Logos; // 修复: 引入 Parser trait
 
@@ -32,6 +32,744 @@ mod tests {
         assert!(tokens.contains(&Token::Ident("b".to_string())));
     }
 
+    #[test]
+    fn test_lexer_block_comment() {
+        let code = "var /* skipped */ a";
+        let tokens: Vec<_> = Token::lexer(code).collect();
+
+        assert_eq!(
+            tokens,
+            vec![Ok(Token::Var), Ok(Token::Ident("a".to_string()))]
+        );
+    }
+
+    #[test]
+    fn test_lexer_nested_block_comment() {
+        let code = "var /* outer /* inner */ still outer */ a";
+        let tokens: Vec<_> = Token::lexer(code).collect();
+
+        assert_eq!(
+            tokens,
+            vec![Ok(Token::Var), Ok(Token::Ident("a".to_string()))]
+        );
+    }
+
+    #[test]
+    fn test_lexer_unterminated_block_comment() {
+        let code = "var a /* open /* nested */";
+        let mut lexer = Token::lexer(code);
+
+        assert_eq!(lexer.next(), Some(Ok(Token::Var)));
+        assert_eq!(lexer.next(), Some(Ok(Token::Ident("a".to_string()))));
+        assert_eq!(
+            lexer.next(),
+            Some(Err(LexError::UnterminatedBlockComment { span: 6..8 }))
+        );
+        assert_eq!(lexer.next(), None);
+    }
+
+    #[test]
+    fn test_dump_tokens() {
+        let dump = crate::lexer::dump_tokens("var a = 1;");
+        assert_eq!(
+            dump,
+            "\
+0..3       Var
+4..5       Ident(\"a\")
+6..7       Eq
+8..9       Int(1)
+9..10      Semicolon
+"
+        );
+    }
+
+    #[test]
+    fn test_dump_tokens_continues_after_error() {
+        let dump = crate::lexer::dump_tokens("var $ b");
+        assert_eq!(
+            dump,
+            "\
+0..3       Var
+4..5       error: invalid token
+6..7       Ident(\"b\")
+"
+        );
+    }
+
+    #[test]
+    fn test_parser_function_doc_comment() {
+        let code = r#"
+            /// Adds two numbers.
+            /// Returns their sum.
+            int add(int a, int b) {
+                // plain comment, not a doc
+                return a + b;
+            }
+
+            int main() {
+                return add(1, 2);
+            }
+        "#;
+
+        let program = crate::parser::parse(code).expect("parse failed");
+        assert_eq!(program.decls.len(), 2);
+        assert_eq!(
+            program.decls[0].doc(),
+            Some("Adds two numbers.\nReturns their sum.")
+        );
+        assert_eq!(program.decls[1].doc(), None);
+    }
+
+    #[test]
+    fn test_parser_orphan_doc_comment() {
+        let code = r#"
+            struct Point {
+                int x
+                /// trailing field doc
+            }
+
+            impl Point {
+                int get_x() {
+                    /// trailing statement doc
+                    return this.x;
+                }
+                /// trailing method doc
+            }
+
+            int main() {
+                return 0;
+            }
+            /// trailing doc at end of file"#;
+
+        let program = crate::parser::parse(code).expect("parse failed");
+        assert_eq!(program.decls.len(), 3);
+        assert!(program.decls.iter().all(|decl| decl.doc().is_none()));
+    }
+
+    #[test]
+    fn test_parser_pub_function() {
+        let code = r#"
+            /// Exported helper.
+            pub int add(int a, int b) {
+                return a + b;
+            }
+
+            int helper() {
+                return 1;
+            }
+        "#;
+
+        let program = crate::parser::parse(code).expect("parse failed");
+        assert_eq!(program.decls.len(), 2);
+        assert_eq!(program.decls[0].doc(), Some("Exported helper."));
+        let publics: Vec<bool> = program
+            .decls
+            .iter()
+            .map(|d| match d {
+                crate::ast::Decl::Function { is_public, .. } => *is_public,
+                _ => panic!("Expected function decl"),
+            })
+            .collect();
+        assert_eq!(publics, vec![true, false]);
+    }
+
+    #[test]
+    fn test_parser_pub_on_every_declaration() {
+        let code = r#"
+            pub struct Point { int x }
+            struct Hidden { int x }
+            pub enum Color { Red, Green }
+            pub trait Shape { int area(); }
+            pub const int LIMIT = 10
+            pub newtype UserId = int
+            pub var counter = 0
+            enum Private { A }
+        "#;
+
+        let program = crate::parser::parse(code).expect("parse failed");
+        let publics: Vec<bool> = program.decls.iter().map(|d| d.is_public()).collect();
+        assert_eq!(
+            publics,
+            vec![true, false, true, true, true, true, true, false]
+        );
+    }
+
+    #[test]
+    fn test_parser_selective_import() {
+        let code = "import std.io.{println, eprintln}\nimport foo.bar as fb";
+        let program = crate::parser::parse(code).expect("parse failed");
+        let crate::ast::Decl::Import { items, .. } = &program.decls[0] else {
+            panic!("Expected import decl");
+        };
+        assert_eq!(items[0].path, vec!["std", "io"]);
+        assert_eq!(
+            items[0].names,
+            Some(vec!["println".to_string(), "eprintln".to_string()])
+        );
+        let crate::ast::Decl::Import { items, .. } = &program.decls[1] else {
+            panic!("Expected import decl");
+        };
+        assert_eq!(items[0].alias.as_deref(), Some("fb"));
+        assert_eq!(items[0].names, None);
+    }
+
+    #[test]
+    fn test_parser_glob_and_pub_import() {
+        let code = "pub import foo.bar.*\nimport foo.baz";
+        let program = crate::parser::parse(code).expect("parse failed");
+        let crate::ast::Decl::Import {
+            is_public, items, ..
+        } = &program.decls[0]
+        else {
+            panic!("Expected import decl");
+        };
+        assert!(*is_public);
+        assert_eq!(items[0].path, vec!["foo", "bar"]);
+        assert!(items[0].glob);
+        let crate::ast::Decl::Import {
+            is_public, items, ..
+        } = &program.decls[1]
+        else {
+            panic!("Expected import decl");
+        };
+        assert!(!*is_public);
+        assert!(!items[0].glob);
+    }
+
+    #[test]
+    fn test_parser_multiple_trait_bounds() {
+        let code = "int f<T: Printable + Comparable>(T x) { return 0 }";
+        let program = crate::parser::parse(code).expect("parse failed");
+        let crate::ast::Decl::Function { generic_params, .. } = &program.decls[0] else {
+            panic!("Expected function decl");
+        };
+        assert_eq!(generic_params[0].name, "T");
+        assert_eq!(generic_params[0].bounds.len(), 2);
+    }
+
+    #[test]
+    fn test_parser_self_type() {
+        let code = "impl Point { This copy() { return this } }";
+        let program = crate::parser::parse(code).expect("parse failed");
+        let crate::ast::Decl::Impl { methods, .. } = &program.decls[0] else {
+            panic!("Expected impl decl");
+        };
+        let crate::ast::Decl::Function { return_type, .. } = &methods[0] else {
+            panic!("Expected method");
+        };
+        assert_eq!(*return_type, crate::ast::Type::SelfType);
+    }
+
+    #[test]
+    fn test_parser_trait_default_method() {
+        let code = "trait Greeter { string name(); string greet() { return this.name() } }";
+        let program = crate::parser::parse(code).expect("parse failed");
+        let crate::ast::Decl::Trait { methods, .. } = &program.decls[0] else {
+            panic!("Expected trait decl");
+        };
+        assert!(methods[0].default_body.is_none());
+        assert_eq!(methods[1].default_body.as_ref().map(Vec::len), Some(1));
+    }
+
+    #[test]
+    fn test_parser_trait_object_type() {
+        let code = "void show(Vec<dyn Printable> items, dyn Printable? extra) { }";
+        let program = crate::parser::parse(code).expect("parse failed");
+        let crate::ast::Decl::Function { params, .. } = &program.decls[0] else {
+            panic!("Expected function decl");
+        };
+        let object = crate::ast::Type::TraitObject("Printable".to_string());
+        assert_eq!(
+            params[0].ty,
+            crate::ast::Type::Generic("Vec".to_string(), vec![object.clone()])
+        );
+        assert_eq!(params[1].ty, crate::ast::Type::Nullable(Box::new(object)));
+        assert_eq!(params[1].ty.to_string(), "dyn Printable?");
+    }
+
+    #[test]
+    fn test_parser_packed_struct() {
+        let code = "packed struct Header { i8 tag int len } struct Loose { i8 tag }";
+        let program = crate::parser::parse(code).expect("parse failed");
+        let packed: Vec<bool> = program
+            .decls
+            .iter()
+            .map(|decl| match decl {
+                crate::ast::Decl::Struct { is_packed, .. } => *is_packed,
+                _ => panic!("Expected struct decl"),
+            })
+            .collect();
+        assert_eq!(packed, vec![true, false]);
+        assert!(crate::parser::parse("packed int f() { return 0 }").is_err());
+    }
+
+    #[test]
+    fn test_parser_extern_c_declarations() {
+        let code = r#"extern struct Rect { int w int h } extern "C" int area(Rect r); extern int puts(string s);"#;
+        let program = crate::parser::parse(code).expect("parse failed");
+        assert!(matches!(
+            &program.decls[0],
+            crate::ast::Decl::Struct {
+                is_extern: true,
+                is_packed: false,
+                ..
+            }
+        ));
+        let abis: Vec<Option<&str>> = program.decls[1..]
+            .iter()
+            .map(|decl| match decl {
+                crate::ast::Decl::ExternFunction { abi, .. } => abi.as_deref(),
+                _ => panic!("Expected extern function"),
+            })
+            .collect();
+        assert_eq!(abis, vec![Some("C"), None]);
+    }
+
+    #[test]
+    fn test_parser_variadic_extern() {
+        let code = "extern int printf(string, ...); extern void log(int level, string fmt, ...);";
+        let program = crate::parser::parse(code).expect("parse failed");
+        let shapes: Vec<(Vec<&str>, bool)> = program
+            .decls
+            .iter()
+            .map(|decl| match decl {
+                crate::ast::Decl::ExternFunction {
+                    params,
+                    is_variadic,
+                    ..
+                } => (
+                    params.iter().map(|p| p.name.as_str()).collect(),
+                    *is_variadic,
+                ),
+                _ => panic!("Expected extern function"),
+            })
+            .collect();
+        assert_eq!(
+            shapes,
+            vec![(vec!["_"], true), (vec!["level", "fmt"], true)]
+        );
+
+        // `...` 只能出现在参数列表末尾
+        assert!(crate::parser::parse("extern int bad(..., int x);").is_err());
+    }
+
+    #[test]
+    fn test_parser_associated_function_path() {
+        let code = "int main() { var p = Point::origin() return id::<int>(1) }";
+        let program = crate::parser::parse(code).expect("parse failed");
+        let crate::ast::Decl::Function { body, .. } = &program.decls[0] else {
+            panic!("Expected function decl");
+        };
+        let crate::ast::Stmt::VarDecl { value, .. } = &body[0] else {
+            panic!("Expected var decl");
+        };
+        let crate::ast::ExprKind::Call { callee, .. } = &value.kind else {
+            panic!("Expected call");
+        };
+        assert!(matches!(
+            &callee.kind,
+            crate::ast::ExprKind::Path { type_name, member }
+                if type_name == "Point" && member == "origin"
+        ));
+    }
+
+    #[test]
+    fn test_parser_enum_discriminants() {
+        let code = "enum Flag { Read = 1, Write = 2, Neg = -1, Next, Data(int) }";
+
+        let program = crate::parser::parse(code).expect("parse failed");
+        let crate::ast::Decl::Enum { variants, .. } = &program.decls[0] else {
+            panic!("Expected enum decl");
+        };
+        assert!(matches!(&variants[0], crate::ast::EnumVariant::Unit(n, Some(1)) if n == "Read"));
+        assert!(matches!(
+            &variants[2],
+            crate::ast::EnumVariant::Unit(_, Some(-1))
+        ));
+        assert!(matches!(
+            &variants[3],
+            crate::ast::EnumVariant::Unit(_, None)
+        ));
+        assert!(matches!(&variants[4], crate::ast::EnumVariant::Tuple(n, _) if n == "Data"));
+        assert_eq!(
+            crate::ast::EnumVariant::discriminants(variants),
+            vec![1, 2, -1, 0, 1]
+        );
+    }
+
+    #[test]
+    fn test_parser_if_let() {
+        let code = r#"
+            void main() {
+                if let Shape.Rect(w, h) = s {
+                    print(w)
+                } else {
+                    print(0)
+                }
+            }
+        "#;
+
+        let program = crate::parser::parse(code).expect("parse failed");
+        let crate::ast::Decl::Function { body, .. } = &program.decls[0] else {
+            panic!("Expected function decl");
+        };
+        match &body[0] {
+            crate::ast::Stmt::IfLet {
+                pattern,
+                else_block,
+                ..
+            } => {
+                assert!(matches!(
+                    pattern,
+                    crate::ast::MatchPattern::Variant { name, sub_patterns }
+                        if name == "Rect" && sub_patterns.len() == 2
+                ));
+                assert!(else_block.is_some());
+            }
+            other => panic!("Expected if let, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parser_match_range_and_or_patterns() {
+        let code = r#"
+            string status(int code) {
+                return match code {
+                    case 200..=299 => "ok",
+                    case 400 | 404 => "client error",
+                    case _ => "other"
+                }
+            }
+        "#;
+
+        let program = crate::parser::parse(code).expect("parse failed");
+        let crate::ast::Decl::Function { body, .. } = &program.decls[0] else {
+            panic!("Expected function decl");
+        };
+        let crate::ast::Stmt::Return {
+            value: Some(value), ..
+        } = &body[0]
+        else {
+            panic!("Expected return, got {:?}", body[0]);
+        };
+        let crate::ast::ExprKind::Match { cases, .. } = &value.kind else {
+            panic!("Expected match, got {:?}", value.kind);
+        };
+        use crate::ast::{Literal, MatchPattern};
+        assert_eq!(
+            cases[0].pattern,
+            MatchPattern::Range {
+                start: Literal::Int(200),
+                end: Literal::Int(299)
+            }
+        );
+        assert_eq!(
+            cases[1].pattern,
+            MatchPattern::Or(vec![
+                MatchPattern::Literal(Literal::Int(400)),
+                MatchPattern::Literal(Literal::Int(404))
+            ])
+        );
+        assert_eq!(cases[2].pattern, MatchPattern::Wildcard);
+    }
+
+    #[test]
+    fn test_parser_newtype_and_cast() {
+        let code = r#"
+            newtype UserId = int;
+
+            UserId make() {
+                return cast<UserId>(42)
+            }
+        "#;
+
+        let program = crate::parser::parse(code).expect("parse failed");
+        assert!(matches!(
+            &program.decls[0],
+            crate::ast::Decl::Newtype { name, ty: crate::ast::Type::Int, .. } if name == "UserId"
+        ));
+        let crate::ast::Decl::Function { body, .. } = &program.decls[1] else {
+            panic!("Expected function decl");
+        };
+        let crate::ast::Stmt::Return {
+            value: Some(value), ..
+        } = &body[0]
+        else {
+            panic!("Expected return, got {:?}", body[0]);
+        };
+        assert!(matches!(
+            &value.kind,
+            crate::ast::ExprKind::Cast { ty: crate::ast::Type::Struct(name), .. } if name == "UserId"
+        ));
+    }
+
+    #[test]
+    fn test_parser_sized_int_types() {
+        let code = "i64 widen(i8 a, i16 b, i32 c) { return c }";
+
+        let program = crate::parser::parse(code).expect("parse failed");
+        let crate::ast::Decl::Function {
+            params,
+            return_type,
+            ..
+        } = &program.decls[0]
+        else {
+            panic!("Expected function decl");
+        };
+        // i64 就是 int
+        assert_eq!(*return_type, crate::ast::Type::Int);
+        let param_types: Vec<_> = params.iter().map(|p| p.ty.clone()).collect();
+        assert_eq!(
+            param_types,
+            vec![
+                crate::ast::Type::I8,
+                crate::ast::Type::I16,
+                crate::ast::Type::I32
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parser_contextual_keywords_as_identifiers() {
+        let code = r#"
+            struct Span { int len }
+            void main() {
+                var len = 5
+                var print = len + 1
+                var vec = [1, 2]
+                var s = Span { len: vec[0] }
+                print(len(trim(" a ")) + s.len)
+            }
+        "#;
+
+        let program = crate::parser::parse(code).expect("parse failed");
+        let crate::ast::Decl::Function { body, .. } = &program.decls[1] else {
+            panic!("Expected function decl");
+        };
+        let crate::ast::Stmt::VarDecl { name, .. } = &body[0] else {
+            panic!("Expected var decl");
+        };
+        assert_eq!(name, "len");
+        // 调用位置仍是内置函数
+        let crate::ast::Stmt::Expression(expr) = &body[4] else {
+            panic!("Expected expression statement");
+        };
+        let crate::ast::ExprKind::Print(arg) = &expr.kind else {
+            panic!("Expected print intrinsic, got {:?}", expr.kind);
+        };
+        let crate::ast::ExprKind::Binary(lhs, _, _) = &arg.kind else {
+            panic!("Expected binary expr");
+        };
+        assert!(matches!(lhs.kind, crate::ast::ExprKind::Len(_)));
+    }
+
+    #[test]
+    fn test_parser_fmt_macro() {
+        let code = r#"void main() { var fmt = 1 print(fmt!("{} + {}", fmt, 2,)) }"#;
+        let program = crate::parser::parse(code).expect("parse failed");
+        let crate::ast::Decl::Function { body, .. } = &program.decls[0] else {
+            panic!("Expected function decl");
+        };
+        // fmt 在调用位置之外仍是普通标识符
+        assert!(matches!(&body[0], crate::ast::Stmt::VarDecl { name, .. } if name == "fmt"));
+        let crate::ast::Stmt::Expression(expr) = &body[1] else {
+            panic!("Expected expression statement");
+        };
+        let crate::ast::ExprKind::Print(arg) = &expr.kind else {
+            panic!("Expected print intrinsic");
+        };
+        let crate::ast::ExprKind::Fmt { template, args } = &arg.kind else {
+            panic!("Expected fmt! macro, got {:?}", arg.kind);
+        };
+        assert_eq!(template, "{} + {}");
+        assert_eq!(args.len(), 2);
+
+        // 模板必须是字符串字面量
+        assert!(crate::parser::parse("void main() { var t = \"{}\" print(fmt!(t, 1)) }").is_err());
+    }
+
+    #[test]
+    fn test_parser_spans_are_byte_offsets() {
+        let code = "int main() {\n    return  40 + 2\n}";
+        let program = crate::parser::parse(code).expect("parse failed");
+        let crate::ast::Decl::Function { body, .. } = &program.decls[0] else {
+            panic!("Expected function decl");
+        };
+        let crate::ast::Stmt::Return {
+            value: Some(value), ..
+        } = &body[0]
+        else {
+            panic!("Expected return statement");
+        };
+        assert_eq!(&code[value.span.clone()], "40 + 2");
+
+        // 语法错误同样指向出错 token 的字节位置
+        let errors = crate::parser::parse("int main() { return 1 + }").unwrap_err();
+        assert_eq!(errors[0].span(), 24..25);
+    }
+
+    #[test]
+    fn test_integer_literal_overflow() {
+        let mut lexer = Token::lexer("99999999999999999999");
+        assert_eq!(
+            lexer.next(),
+            Some(Err(LexError::IntegerLiteralOverflow {
+                literal: "99999999999999999999".to_string()
+            }))
+        );
+
+        // 边界值本身仍可解析
+        let mut lexer = Token::lexer("9223372036854775807");
+        assert_eq!(lexer.next(), Some(Ok(Token::Int(i64::MAX))));
+
+        let errors =
+            crate::parser::parse("int main() { return 99999999999999999999 }").unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].span(), 20..40);
+        assert_eq!(
+            errors[0].reason(),
+            &chumsky::error::SimpleReason::Custom(
+                "integer literal '99999999999999999999' overflows int (max 9223372036854775807)"
+                    .to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn test_parser_trailing_commas() {
+        let code = r#"
+            struct Pair<T,> { T left, T right, }
+            enum Shape { Circle(float,), Rect(float, float,), }
+            int add(int a, int b,) { return a + b }
+            T first<T,>(Pair<T,> p,) { return p.left }
+            void main() {
+                var p = Pair<int,> { left: 1, right: 2, }
+                var sum = add(p.left, p.right,)
+                var head = first::<int,>(p,)
+                var parts = split("a,b", ",",)
+                var f = |int x, int y,| => x + y
+            }
+        "#;
+
+        let program = crate::parser::parse(code).expect("parse failed");
+        let crate::ast::Decl::Struct { fields, .. } = &program.decls[0] else {
+            panic!("Expected struct decl");
+        };
+        assert_eq!(fields.len(), 2);
+        let crate::ast::Decl::Function { params, .. } = &program.decls[2] else {
+            panic!("Expected function decl");
+        };
+        assert_eq!(params.len(), 2);
+    }
+
+    #[test]
+    fn test_parser_global_var_decl() {
+        let code = r#"
+            var answer = compute()
+            var count: int = 3
+            int compute() { return 42 }
+        "#;
+
+        let program = crate::parser::parse(code).expect("parse failed");
+        let crate::ast::Decl::Var { name, ty, .. } = &program.decls[0] else {
+            panic!("Expected global var decl");
+        };
+        assert_eq!(name, "answer");
+        assert!(ty.is_none());
+        let crate::ast::Decl::Var { ty, .. } = &program.decls[1] else {
+            panic!("Expected global var decl");
+        };
+        assert_eq!(ty, &Some(crate::ast::Type::Int));
+    }
+
+    #[test]
+    fn test_parser_repeat_literals() {
+        let code = r#"
+            void main() {
+                var zeros = [0; 10]
+                var list = vec![0; n + 1]
+                var plain = [1, 2]
+            }
+        "#;
+
+        let program = crate::parser::parse(code).expect("parse failed");
+        let crate::ast::Decl::Function { body, .. } = &program.decls[0] else {
+            panic!("Expected function decl");
+        };
+        let values: Vec<_> = body
+            .iter()
+            .map(|stmt| match stmt {
+                crate::ast::Stmt::VarDecl { value, .. } => &value.kind,
+                _ => panic!("Expected var decl"),
+            })
+            .collect();
+        assert!(matches!(
+            values[0],
+            crate::ast::ExprKind::Repeat { is_vec: false, .. }
+        ));
+        assert!(matches!(
+            values[1],
+            crate::ast::ExprKind::Repeat { is_vec: true, .. }
+        ));
+        assert!(matches!(values[2], crate::ast::ExprKind::Array(_)));
+    }
+
+    #[test]
+    fn test_parser_as_cast_binds_tighter_than_binary() {
+        let code = r#"
+            float mix(int a, int b) {
+                return a + b as float
+            }
+        "#;
+
+        let program = crate::parser::parse(code).expect("parse failed");
+        let crate::ast::Decl::Function { body, .. } = &program.decls[0] else {
+            panic!("Expected function decl");
+        };
+        let crate::ast::Stmt::Return {
+            value: Some(value), ..
+        } = &body[0]
+        else {
+            panic!("Expected return, got {:?}", body[0]);
+        };
+        let crate::ast::ExprKind::Binary(_, crate::ast::BinaryOp::Add, rhs) = &value.kind else {
+            panic!("Expected addition, got {:?}", value.kind);
+        };
+        assert!(matches!(
+            &rhs.kind,
+            crate::ast::ExprKind::Cast {
+                ty: crate::ast::Type::Float,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_parser_const_and_const_sized_array() {
+        let code = r#"
+            const int SIZE = 4
+            const SCALE = 10
+
+            struct Buf {
+                [SIZE]int data
+            }
+        "#;
+
+        let program = crate::parser::parse(code).expect("parse failed");
+        assert!(matches!(
+            &program.decls[0],
+            crate::ast::Decl::Const { name, ty: Some(crate::ast::Type::Int), .. } if name == "SIZE"
+        ));
+        assert!(matches!(
+            &program.decls[1],
+            crate::ast::Decl::Const { name, ty: None, .. } if name == "SCALE"
+        ));
+        let crate::ast::Decl::Struct { fields, .. } = &program.decls[2] else {
+            panic!("Expected struct decl");
+        };
+        assert_eq!(fields[0].ty.to_string(), "[SIZE]int");
+    }
+
     // 注意：此测试在某些配置下可能栈溢出
     // 功能已通过 examples/test_parser.rs 验证
     // 如需运行，请确保 .cargo/config.toml 中设置了足够的栈大小
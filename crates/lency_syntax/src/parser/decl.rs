@@ -2,8 +2,11 @@
 //!
 //! 声明解析：函数、类
 
-use super::helpers::{field_parser, generic_params_parser, ident_parser, type_parser};
+use super::helpers::{
+    doc_comment_parser, field_parser, generic_params_parser, ident_parser, type_parser,
+};
 
+use super::expr::expr_parser;
 use super::stmt::stmt_parser;
 use crate::ast::stmt::ImportItem;
 use crate::ast::*;
@@ -37,54 +40,98 @@ pub fn decl_parser() -> impl Parser<Token, Decl, Error = ParserError> {
             .map_with_span(
                 |((((return_type, name), generic_params), params), body), span| Decl::Function {
                     span,
+                    doc: None,
+                    is_public: false,
                     name,
                     generic_params,
                     params,
                     return_type,
                     body,
+                    source_file: None,
                 },
             );
 
         // 外部函数声明: extern int print(int n);
-        let extern_decl = just(Token::Extern)
-            .ignore_then(type_parser())
-            .then(ident_parser())
-            .then(generic_params_parser()) // 解析 <T>
+        // 指定调用约定: extern "C" int area(Rect r);
+        // 可变参数: extern int printf(string fmt, ...);
+        #[allow(clippy::result_large_err)] // Macro-generated code, unavoidable
+        let abi = select! { Token::String(abi) => abi };
+        // 外部函数的参数名可省略（与 C 原型一致）: extern int printf(string, ...);
+        let extern_param = type_parser()
+            .then(ident_parser().or_not())
+            .map(|(ty, name)| Param {
+                name: name.unwrap_or_else(|| "_".to_string()),
+                ty,
+            });
+        // 末尾的 ... 只能出现在最后
+        let extern_params = extern_param
+            .clone()
+            .then_ignore(just(Token::Comma))
+            .repeated()
             .then(
-                type_parser()
-                    .then(ident_parser())
-                    .map(|(ty, name)| Param { name, ty })
-                    .separated_by(just(Token::Comma))
-                    .allow_trailing()
-                    .delimited_by(just(Token::LParen), just(Token::RParen)),
+                just(Token::Ellipsis)
+                    .to(None)
+                    .or(extern_param.map(Some))
+                    .or_not(),
             )
-            .then_ignore(just(Token::Semicolon))
-            .map_with_span(|(((return_type, name), generic_params), params), span| {
-                Decl::ExternFunction {
-                    span,
-                    name,
-                    generic_params,
-                    params,
-                    return_type,
+            .delimited_by(just(Token::LParen), just(Token::RParen))
+            .map(|(mut params, last)| match last {
+                Some(None) => (params, true),
+                Some(Some(param)) => {
+                    params.push(param);
+                    (params, false)
                 }
+                None => (params, false),
             });
+        let extern_decl = just(Token::Extern)
+            .ignore_then(abi.or_not())
+            .then(type_parser())
+            .then(ident_parser())
+            .then(generic_params_parser()) // 解析 <T>
+            .then(extern_params)
+            .then_ignore(just(Token::Semicolon))
+            .map_with_span(
+                |((((abi, return_type), name), generic_params), (params, is_variadic)), span| {
+                    Decl::ExternFunction {
+                        span,
+                        doc: None,
+                        abi,
+                        name,
+                        generic_params,
+                        params,
+                        is_variadic,
+                        return_type,
+                    }
+                },
+            );
 
         // 结构体声明: struct Point { int x int y }
         // 泛型结构体: struct Box<T> { T value }
-        let struct_decl = just(Token::Struct)
-            .ignore_then(ident_parser())
+        // 紧凑布局: packed struct Header { i8 tag int len }
+        // C 互操作: extern struct Rect { int w int h }
+        let struct_decl = just(Token::Packed)
+            .or(just(Token::Extern))
+            .or_not()
+            .then_ignore(just(Token::Struct))
+            .then(ident_parser())
             .then(generic_params_parser()) // 解析 <T, U>
             .then(
                 field_parser()
                     .repeated()
                     .delimited_by(just(Token::LBrace), just(Token::RBrace)),
             )
-            .map_with_span(|((name, generic_params), fields), span| Decl::Struct {
-                span,
-                name,
-                generic_params,
-                fields,
-            });
+            .map_with_span(
+                |(((modifier, name), generic_params), fields), span| Decl::Struct {
+                    span,
+                    doc: None,
+                    is_public: false,
+                    is_packed: modifier == Some(Token::Packed),
+                    is_extern: modifier == Some(Token::Extern),
+                    name,
+                    generic_params,
+                    fields,
+                },
+            );
 
         // impl 块: impl Point { ... }
         // 泛型impl: impl<T> Box<T> { ... }
@@ -102,7 +149,9 @@ pub fn decl_parser() -> impl Parser<Token, Decl, Error = ParserError> {
             .then(type_parser()) // 第一个标识符（可能是 Trait 类型 或 Type 类型）
             .then(just(Token::For).ignore_then(type_parser()).or_not()) // 可选的 "for TypeName"
             .then(
-                func.clone()
+                doc_comment_parser()
+                    .then(func.clone())
+                    .map(|(doc, method)| attach_doc(method, doc))
                     .repeated()
                     .delimited_by(just(Token::LBrace), just(Token::RBrace)),
             )
@@ -129,7 +178,9 @@ pub fn decl_parser() -> impl Parser<Token, Decl, Error = ParserError> {
             });
 
         // Trait 方法签名: void greet(); 或 bool equals(T other);
-        let trait_method = type_parser()
+        // 默认实现: void hello() { ... }
+        let trait_method = doc_comment_parser()
+            .ignore_then(type_parser())
             .then(ident_parser())
             .then(
                 type_parser()
@@ -139,12 +190,22 @@ pub fn decl_parser() -> impl Parser<Token, Decl, Error = ParserError> {
                     .allow_trailing()
                     .delimited_by(just(Token::LParen), just(Token::RParen)),
             )
-            .then_ignore(just(Token::Semicolon))
-            .map(|((return_type, name), params)| TraitMethod {
-                name,
-                params,
-                return_type,
-            });
+            .then(
+                just(Token::Semicolon).to(None).or(stmt
+                    .clone()
+                    .repeated()
+                    .delimited_by(just(Token::LBrace), just(Token::RBrace))
+                    .map(Some)),
+            )
+            .map_with_span(
+                |(((return_type, name), params), default_body), span| TraitMethod {
+                    span,
+                    name,
+                    params,
+                    return_type,
+                    default_body,
+                },
+            );
 
         // Trait 定义: trait Greeter { void greet(); }
         // 泛型Trait: trait Comparable<T> { bool equals(T other); }
@@ -158,35 +219,68 @@ pub fn decl_parser() -> impl Parser<Token, Decl, Error = ParserError> {
             )
             .map_with_span(|((name, generic_params), methods), span| Decl::Trait {
                 span,
+                doc: None,
+                is_public: false,
                 name,
                 generic_params,
                 methods,
             });
 
-        // Enum Variant: Idle 或 Some(T)
-        let enum_variant = ident_parser() // Variant Name
+        // 显式判别值: = 4 或 = -1
+        #[allow(clippy::result_large_err)] // Macro-generated code, unavoidable
+        let int_literal = select! { Token::Int(v) => v };
+        let discriminant = just(Token::Eq).ignore_then(
+            just(Token::Minus)
+                .or_not()
+                .then(int_literal)
+                .map(|(neg, v)| if neg.is_some() { -v } else { v }),
+        );
+
+        // Enum Variant: Idle、Red = 1 或 Some(T)
+        let tuple_variant = ident_parser()
             .then(
                 type_parser()
                     .separated_by(just(Token::Comma))
                     .allow_trailing()
-                    .delimited_by(just(Token::LParen), just(Token::RParen))
-                    .or_not(),
+                    .delimited_by(just(Token::LParen), just(Token::RParen)),
             )
-            .then_ignore(just(Token::Comma).or_not()) // Optional trailing comma
-            .map(|(name, args)| {
-                if let Some(types) = args {
-                    EnumVariant::Tuple(name, types)
-                } else {
-                    EnumVariant::Unit(name)
-                }
-            });
+            .map(|(name, types)| EnumVariant::Tuple(name, types));
+        let unit_variant = ident_parser()
+            .then(discriminant.or_not())
+            .map(|(name, value)| EnumVariant::Unit(name, value));
+        let enum_variant = doc_comment_parser()
+            .ignore_then(tuple_variant.or(unit_variant))
+            .then_ignore(just(Token::Comma).or_not()); // Optional trailing comma
 
-        // Import: import std.io [as alias]; OR import { ... }
-        let import_item = ident_parser()
-            .separated_by(just(Token::Dot))
-            .at_least(1)
-            .then(just(Token::As).ignore_then(ident_parser()).or_not())
-            .map(|(path, alias)| ImportItem { path, alias });
+        // Import: import std.io [as alias]; import foo.{a, b}; OR import { ... }
+        // 模块路径段可以是上下文关键字（std.assert）
+        let import_segment = ident_parser();
+        let import_path = import_segment
+            .clone()
+            .chain(just(Token::Dot).ignore_then(import_segment).repeated());
+        let import_names = just(Token::Dot).ignore_then(
+            ident_parser()
+                .separated_by(just(Token::Comma))
+                .allow_trailing()
+                .at_least(1)
+                .delimited_by(just(Token::LBrace), just(Token::RBrace)),
+        );
+        let import_glob = just(Token::Dot).then(just(Token::Star));
+        let import_tail = import_names
+            .map(|names| (None, Some(names), false))
+            .or(import_glob.to((None, None, true)))
+            .or(just(Token::As)
+                .ignore_then(ident_parser())
+                .or_not()
+                .map(|alias| (alias, None, false)));
+        let import_item = import_path
+            .then(import_tail)
+            .map(|(path, (alias, names, glob))| ImportItem {
+                path,
+                alias,
+                names,
+                glob,
+            });
 
         let import_block = import_item
             .clone()
@@ -199,7 +293,11 @@ pub fn decl_parser() -> impl Parser<Token, Decl, Error = ParserError> {
         let import_decl = just(Token::Import)
             .ignore_then(import_block.or(import_single))
             .then_ignore(just(Token::Semicolon).or_not())
-            .map_with_span(|items, span| Decl::Import { span, items }); // Enum 定义: enum Option<T> { Some(T), None }
+            .map_with_span(|items, span| Decl::Import {
+                span,
+                is_public: false,
+                items,
+            }); // Enum 定义: enum Option<T> { Some(T), None }
         let enum_decl = just(Token::Enum)
             .ignore_then(ident_parser())
             .then(generic_params_parser())
@@ -210,20 +308,125 @@ pub fn decl_parser() -> impl Parser<Token, Decl, Error = ParserError> {
             )
             .map_with_span(|((name, generic_params), variants), span| Decl::Enum {
                 span,
+                doc: None,
+                is_public: false,
                 name,
                 generic_params,
                 variants,
             });
 
-        choice((
-            enum_decl,
-            import_decl,
-            trait_decl,
-            struct_decl,
-            impl_decl,
-            extern_decl,
-            func,
-        ))
-        .boxed()
+        // 常量定义: const int SIZE = 4 或 const SIZE = 4
+        let const_decl = just(Token::Const)
+            .ignore_then(
+                type_parser()
+                    .then(ident_parser())
+                    .map(|(ty, name)| (Some(ty), name))
+                    .or(ident_parser().map(|name| (None, name))),
+            )
+            .then_ignore(just(Token::Eq))
+            .then(expr_parser())
+            .then_ignore(just(Token::Semicolon).or_not())
+            .map_with_span(|((ty, name), value), span| Decl::Const {
+                span,
+                doc: None,
+                is_public: false,
+                name,
+                ty,
+                value,
+            });
+
+        // 全局变量: var counter: int = 0 或 var table = build()（非字面量初始值在 __lency_init 中计算）
+        let global_var_decl = just(Token::Var)
+            .ignore_then(ident_parser())
+            .then(just(Token::Colon).ignore_then(type_parser()).or_not())
+            .then_ignore(just(Token::Eq))
+            .then(expr_parser())
+            .then_ignore(just(Token::Semicolon).or_not())
+            .map_with_span(|((name, ty), value), span| Decl::Var {
+                span,
+                is_public: false,
+                name,
+                ty,
+                value,
+            });
+
+        // 新类型: newtype UserId = int
+        let newtype_decl = just(Token::Newtype)
+            .ignore_then(ident_parser())
+            .then_ignore(just(Token::Eq))
+            .then(type_parser())
+            .then_ignore(just(Token::Semicolon).or_not())
+            .map_with_span(|(name, ty), span| Decl::Newtype {
+                span,
+                doc: None,
+                is_public: false,
+                name,
+                ty,
+            });
+
+        // 公开声明: pub int add(...)、pub struct Point { ... }、pub import（再导出）
+        // impl 块没有可见性；extern 函数始终可见，pub 对其不起作用
+        let pub_decl = just(Token::Pub)
+            .ignore_then(choice((
+                enum_decl.clone(),
+                import_decl.clone(),
+                const_decl.clone(),
+                global_var_decl.clone(),
+                newtype_decl.clone(),
+                trait_decl.clone(),
+                struct_decl.clone(),
+                extern_decl.clone(),
+                func.clone(),
+            )))
+            .map(mark_public);
+
+        // 声明前的文档注释挂到对应 Decl 上
+        doc_comment_parser()
+            .then(choice((
+                enum_decl,
+                import_decl,
+                const_decl,
+                global_var_decl,
+                newtype_decl,
+                trait_decl,
+                struct_decl,
+                impl_decl,
+                extern_decl,
+                pub_decl,
+                func,
+            )))
+            .map(|(doc, decl)| attach_doc(decl, doc))
+            .boxed()
     })
 }
+
+/// 将声明标记为公开（导入标记为再导出）
+fn mark_public(mut decl: Decl) -> Decl {
+    match &mut decl {
+        Decl::Function { is_public, .. }
+        | Decl::Struct { is_public, .. }
+        | Decl::Trait { is_public, .. }
+        | Decl::Enum { is_public, .. }
+        | Decl::Const { is_public, .. }
+        | Decl::Newtype { is_public, .. }
+        | Decl::Var { is_public, .. }
+        | Decl::Import { is_public, .. } => *is_public = true,
+        Decl::ExternFunction { .. } | Decl::Impl { .. } => {}
+    }
+    decl
+}
+
+/// 将文档注释写入声明（不支持文档注释的声明直接忽略）
+fn attach_doc(mut decl: Decl, text: Option<String>) -> Decl {
+    match &mut decl {
+        Decl::Function { doc, .. }
+        | Decl::ExternFunction { doc, .. }
+        | Decl::Struct { doc, .. }
+        | Decl::Trait { doc, .. }
+        | Decl::Enum { doc, .. }
+        | Decl::Const { doc, .. }
+        | Decl::Newtype { doc, .. } => *doc = text,
+        Decl::Impl { .. } | Decl::Var { .. } | Decl::Import { .. } => {}
+    }
+    decl
+}
@@ -18,6 +18,15 @@ pub fn parser(
         span,
     });
 
+    // 关联函数路径: Point::origin
+    let path = ident_parser()
+        .then_ignore(just(Token::Colon).then(just(Token::Colon)))
+        .then(ident_parser())
+        .map_with_span(|(type_name, member), span| Expr {
+            kind: ExprKind::Path { type_name, member },
+            span,
+        });
+
     let paren = expr
         .clone()
         .delimited_by(just(Token::LParen), just(Token::RParen));
@@ -27,10 +36,12 @@ pub fn parser(
         .then(
             just(Token::Case)
                 .ignore_then(crate::parser::pattern::pattern_parser())
+                .then(just(Token::If).ignore_then(expr.clone()).or_not())
                 .then_ignore(just(Token::Arrow))
                 .then(expr.clone())
-                .map_with_span(|(pattern, body), span| MatchCase {
+                .map_with_span(|((pattern, guard), body), span| MatchCase {
                     pattern,
+                    guard,
                     body: Box::new(body),
                     span,
                 })
@@ -50,30 +61,55 @@ pub fn parser(
     // 内置函数
     let intrinsic_expr = intrinsics::intrinsic_parsers(expr.clone());
 
-    // Array literal: [1, 2, 3]
-    let array_literal = expr
+    // 重复初始化的方括号部分: [value; count]
+    let repeat_body = expr
         .clone()
-        .separated_by(just(Token::Comma))
-        .allow_trailing()
-        .delimited_by(just(Token::LBracket), just(Token::RBracket))
-        .map_with_span(|elements, span| Expr {
-            kind: ExprKind::Array(elements),
+        .then_ignore(just(Token::Semicolon))
+        .then(expr.clone())
+        .delimited_by(just(Token::LBracket), just(Token::RBracket));
+
+    // Array literal: [1, 2, 3] 或 [0; 10]
+    let array_literal = repeat_body
+        .clone()
+        .map_with_span(|(value, count), span| Expr {
+            kind: ExprKind::Repeat {
+                value: Box::new(value),
+                count: Box::new(count),
+                is_vec: false,
+            },
             span,
-        });
+        })
+        .or(expr
+            .clone()
+            .separated_by(just(Token::Comma))
+            .allow_trailing()
+            .delimited_by(just(Token::LBracket), just(Token::RBracket))
+            .map_with_span(|elements, span| Expr {
+                kind: ExprKind::Array(elements),
+                span,
+            }));
 
-    // Vec 字面量: vec![1, 2, 3]
-    let vec_literal = just(Token::Vec)
-        .ignore_then(just(Token::Bang))
-        .ignore_then(
-            expr.clone()
+    // Vec 字面量: vec![1, 2, 3] 或 vec![0; n]
+    let vec_literal = just(Token::Vec).ignore_then(just(Token::Bang)).ignore_then(
+        repeat_body
+            .map_with_span(|(value, count), span| Expr {
+                kind: ExprKind::Repeat {
+                    value: Box::new(value),
+                    count: Box::new(count),
+                    is_vec: true,
+                },
+                span,
+            })
+            .or(expr
+                .clone()
                 .separated_by(just(Token::Comma))
                 .allow_trailing()
-                .delimited_by(just(Token::LBracket), just(Token::RBracket)),
-        )
-        .map_with_span(|elements, span| Expr {
-            kind: ExprKind::VecLiteral(elements),
-            span,
-        });
+                .delimited_by(just(Token::LBracket), just(Token::RBracket))
+                .map_with_span(|elements, span| Expr {
+                    kind: ExprKind::VecLiteral(elements),
+                    span,
+                })),
+    );
 
     // Struct literal: Point { x: 10, y: 20 } or Box<int> { value: 10 }
     let struct_literal = type_parser()
@@ -90,6 +126,19 @@ pub fn parser(
             span,
         });
 
+    // 匿名记录字面量: { x: 10, y: 20 }（至少一个字段，避免与空代码块混淆）
+    let record_literal = ident_parser()
+        .then_ignore(just(Token::Colon))
+        .then(expr.clone())
+        .separated_by(just(Token::Comma))
+        .at_least(1)
+        .allow_trailing()
+        .delimited_by(just(Token::LBrace), just(Token::RBrace))
+        .map_with_span(|fields, span| Expr {
+            kind: ExprKind::RecordLiteral(fields),
+            span,
+        });
+
     // Ok 构造器: Ok(value)
     let ok_expr = just(Token::Ok)
         .ignore_then(
@@ -153,8 +202,10 @@ pub fn parser(
         .or(ok_expr)
         .or(err_expr)
         .or(struct_literal)
+        .or(record_literal)
         .or(unit) // Check unit () before paren (expr)
         .or(val)
+        .or(path) // Point::origin 先于普通标识符
         .or(ident)
         .or(paren)
 }
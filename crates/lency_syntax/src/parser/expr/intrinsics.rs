@@ -1,7 +1,8 @@
 //! Intrinsic Function Parsers
 //!
-//! 内置函数解析器：print, read_file, write_file, len, trim, split, join, substr
+//! 内置函数解析器：print, read_file, write_file, len, trim, split, join, substr, fmt!, cast
 
+use super::super::helpers::type_parser;
 use crate::ast::*;
 use crate::lexer::Token;
 use chumsky::prelude::*;
@@ -9,7 +10,8 @@ use chumsky::prelude::*;
 use super::ParserError;
 
 /// 创建所有内置函数解析器的组合
-/// 返回一个能解析任何内置函数调用的 Parser
+/// 返回一个能解析任何内置函数调用的 Parser；参数列表允许末尾逗号
+#[allow(clippy::result_large_err)]
 pub fn intrinsic_parsers<P>(expr: P) -> impl Parser<Token, Expr, Error = ParserError> + Clone
 where
     P: Parser<Token, Expr, Error = ParserError> + Clone,
@@ -18,6 +20,7 @@ where
     let print_expr = just(Token::Print)
         .ignore_then(
             expr.clone()
+                .then_ignore(just(Token::Comma).or_not())
                 .delimited_by(just(Token::LParen), just(Token::RParen)),
         )
         .map_with_span(|arg, span| Expr {
@@ -29,6 +32,7 @@ where
     let read_file_expr = just(Token::ReadFile)
         .ignore_then(
             expr.clone()
+                .then_ignore(just(Token::Comma).or_not())
                 .delimited_by(just(Token::LParen), just(Token::RParen)),
         )
         .map_with_span(|path, span| Expr {
@@ -42,6 +46,7 @@ where
             expr.clone()
                 .then_ignore(just(Token::Comma))
                 .then(expr.clone())
+                .then_ignore(just(Token::Comma).or_not())
                 .delimited_by(just(Token::LParen), just(Token::RParen)),
         )
         .map_with_span(|(path, content), span| Expr {
@@ -53,6 +58,7 @@ where
     let len_expr = just(Token::Len)
         .ignore_then(
             expr.clone()
+                .then_ignore(just(Token::Comma).or_not())
                 .delimited_by(just(Token::LParen), just(Token::RParen)),
         )
         .map_with_span(|arg, span| Expr {
@@ -64,6 +70,7 @@ where
     let trim_expr = just(Token::Trim)
         .ignore_then(
             expr.clone()
+                .then_ignore(just(Token::Comma).or_not())
                 .delimited_by(just(Token::LParen), just(Token::RParen)),
         )
         .map_with_span(|arg, span| Expr {
@@ -77,6 +84,7 @@ where
             expr.clone()
                 .then_ignore(just(Token::Comma))
                 .then(expr.clone())
+                .then_ignore(just(Token::Comma).or_not())
                 .delimited_by(just(Token::LParen), just(Token::RParen)),
         )
         .map_with_span(|(str_arg, delim), span| Expr {
@@ -90,6 +98,7 @@ where
             expr.clone()
                 .then_ignore(just(Token::Comma))
                 .then(expr.clone())
+                .then_ignore(just(Token::Comma).or_not())
                 .delimited_by(just(Token::LParen), just(Token::RParen)),
         )
         .map_with_span(|(vec_arg, sep), span| Expr {
@@ -105,6 +114,7 @@ where
                 .then(expr.clone())
                 .then_ignore(just(Token::Comma))
                 .then(expr.clone())
+                .then_ignore(just(Token::Comma).or_not())
                 .delimited_by(just(Token::LParen), just(Token::RParen)),
         )
         .map_with_span(|((str_arg, start), len), span| Expr {
@@ -116,6 +126,7 @@ where
     let char_to_string_expr = just(Token::CharToString)
         .ignore_then(
             expr.clone()
+                .then_ignore(just(Token::Comma).or_not())
                 .delimited_by(just(Token::LParen), just(Token::RParen)),
         )
         .map_with_span(|arg, span| Expr {
@@ -127,6 +138,7 @@ where
     let panic_expr = just(Token::Panic)
         .ignore_then(
             expr.clone()
+                .then_ignore(just(Token::Comma).or_not())
                 .delimited_by(just(Token::LParen), just(Token::RParen)),
         )
         .map_with_span(|arg, span| Expr {
@@ -134,12 +146,27 @@ where
             span,
         });
 
+    // assert(cond, "message")
+    let assert_expr = just(Token::Assert)
+        .ignore_then(
+            expr.clone()
+                .then_ignore(just(Token::Comma))
+                .then(expr.clone())
+                .then_ignore(just(Token::Comma).or_not())
+                .delimited_by(just(Token::LParen), just(Token::RParen)),
+        )
+        .map_with_span(|(cond, message), span| Expr {
+            kind: ExprKind::Assert(Box::new(cond), Box::new(message)),
+            span,
+        });
+
     // format("template {}", args_vec) -> string
     let format_expr = just(Token::Format)
         .ignore_then(
             expr.clone()
                 .then_ignore(just(Token::Comma))
                 .then(expr.clone())
+                .then_ignore(just(Token::Comma).or_not())
                 .delimited_by(just(Token::LParen), just(Token::RParen)),
         )
         .map_with_span(|(template, args), span| Expr {
@@ -147,6 +174,40 @@ where
             span,
         });
 
+    // fmt!("{} + {}", a, b) -> string
+    let fmt_expr = just(Token::Fmt)
+        .ignore_then(just(Token::Bang))
+        .ignore_then(
+            select! { Token::String(template) => template }
+                .then(
+                    just(Token::Comma)
+                        .ignore_then(expr.clone())
+                        .repeated()
+                        .then_ignore(just(Token::Comma).or_not()),
+                )
+                .delimited_by(just(Token::LParen), just(Token::RParen)),
+        )
+        .map_with_span(|(template, args), span| Expr {
+            kind: ExprKind::Fmt { template, args },
+            span,
+        });
+
+    // cast<UserId>(42) -> UserId
+    let cast_expr = just(Token::Cast)
+        .ignore_then(type_parser().delimited_by(just(Token::Lt), just(Token::Gt)))
+        .then(
+            expr.clone()
+                .then_ignore(just(Token::Comma).or_not())
+                .delimited_by(just(Token::LParen), just(Token::RParen)),
+        )
+        .map_with_span(|(ty, value), span| Expr {
+            kind: ExprKind::Cast {
+                ty,
+                value: Box::new(value),
+            },
+            span,
+        });
+
     // 组合所有内置函数解析器
     print_expr
         .or(read_file_expr)
@@ -158,5 +219,8 @@ where
         .or(substr_expr)
         .or(char_to_string_expr)
         .or(panic_expr)
+        .or(assert_expr)
         .or(format_expr)
+        .or(fmt_expr)
+        .or(cast_expr)
 }
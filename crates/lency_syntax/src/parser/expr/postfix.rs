@@ -13,6 +13,7 @@ enum PostfixOp {
     Call(Vec<Expr>, Span),
     GenericInstantiation(Vec<Type>, Span),
     Try(Span),
+    Cast(Type, Span),
 }
 
 pub fn parser(
@@ -24,20 +25,15 @@ pub fn parser(
             .delimited_by(just(Token::LBracket), just(Token::RBracket))
             .map(PostfixOp::Index)
             .or(just(Token::Dot)
-                .ignore_then(
-                    ident_parser()
-                        .or(just(Token::Len).to("len".to_string()))
-                        .map_with_span(|n, s| (n, s)),
-                )
+                .ignore_then(ident_parser().map_with_span(|n, s| (n, s)))
                 .map(|(n, s)| PostfixOp::Member(n, s)))
             .or(just(Token::QuestionDot)
-                .ignore_then(
-                    ident_parser()
-                        .or(just(Token::Len).to("len".to_string()))
-                        .map_with_span(|n, s| (n, s)),
-                )
+                .ignore_then(ident_parser().map_with_span(|n, s| (n, s)))
                 .map(|(n, s)| PostfixOp::SafeMember(n, s)))
             .or(just(Token::Question).map_with_span(|_, s| PostfixOp::Try(s))) // Try Operator
+            .or(just(Token::As)
+                .ignore_then(type_parser())
+                .map_with_span(PostfixOp::Cast))
             .or(just(Token::Colon)
                 .then(just(Token::Colon))
                 .ignore_then(just(Token::Lt))
@@ -114,5 +110,15 @@ pub fn parser(
                 span,
             }
         }
+        PostfixOp::Cast(ty, cast_span) => {
+            let span = lhs.span.start..cast_span.end;
+            Expr {
+                kind: ExprKind::Cast {
+                    ty,
+                    value: Box::new(lhs),
+                },
+                span,
+            }
+        }
     })
 }
@@ -8,10 +8,31 @@ use chumsky::prelude::*;
 
 pub type ParserError = Simple<Token>;
 
-/// 解析标识符
+/// 解析标识符；内置函数名等上下文关键字也可以作为标识符
 #[allow(clippy::result_large_err)]
 pub fn ident_parser() -> impl Parser<Token, String, Error = ParserError> + Clone {
-    select! { Token::Ident(ident) => ident }
+    filter_map(|span, tok: Token| match tok {
+        Token::Ident(ident) => Ok(ident),
+        tok => match tok.contextual_keyword() {
+            Some(name) => Ok(name.to_string()),
+            None => Err(Simple::expected_input_found(span, Vec::new(), Some(tok))),
+        },
+    })
+}
+
+/// 解析连续的文档注释 (///)，多行以换行符连接
+/// 没有文档注释时返回 None
+#[allow(clippy::result_large_err)]
+pub fn doc_comment_parser() -> impl Parser<Token, Option<String>, Error = ParserError> + Clone {
+    select! { Token::DocComment(text) => text }
+        .repeated()
+        .map(|lines| {
+            if lines.is_empty() {
+                None
+            } else {
+                Some(lines.join("\n"))
+            }
+        })
 }
 
 /// 解析类型
@@ -21,13 +42,19 @@ pub fn type_parser() -> impl Parser<Token, Type, Error = ParserError> + Clone {
         // 基础类型
         let basic = select! {
             Token::TypeInt => Type::Int,
+            Token::TypeI8 => Type::I8,
+            Token::TypeI16 => Type::I16,
+            Token::TypeI32 => Type::I32,
+            // int 本身就是 64 位整数
+            Token::TypeI64 => Type::Int,
+            Token::TypeUInt => Type::UInt,
             Token::TypeFloat => Type::Float,
             Token::TypeString => Type::String,
             Token::TypeBool => Type::Bool,
             Token::TypeVoid => Type::Void,
         };
 
-        // 泛型/结构体: Ident 或 Ident<Type, ...>
+        // 泛型/结构体: Ident 或 Ident<Type, ...>；`This` 表示 impl 的目标类型
         let ident_or_generic = ident_parser()
             .then(
                 ty.clone()
@@ -36,12 +63,10 @@ pub fn type_parser() -> impl Parser<Token, Type, Error = ParserError> + Clone {
                     .delimited_by(just(Token::Lt), just(Token::Gt))
                     .or_not(),
             )
-            .map(|(name, args)| {
-                if let Some(params) = args {
-                    Type::Generic(name, params)
-                } else {
-                    Type::Struct(name)
-                }
+            .map(|(name, args)| match args {
+                Some(params) => Type::Generic(name, params),
+                None if name == "This" => Type::SelfType,
+                None => Type::Struct(name),
             });
 
         // Vec<Type>
@@ -51,18 +76,50 @@ pub fn type_parser() -> impl Parser<Token, Type, Error = ParserError> + Clone {
             .then_ignore(just(Token::Gt))
             .map(|inner| Type::Vec(Box::new(inner)));
 
-        // [N]Type
+        // [N]Type，N 为整数字面量或常量名
         let array_type = just(Token::LBracket)
-            .ignore_then(select! { Token::Int(n) => n as usize })
+            .ignore_then(select! {
+                Token::Int(n) => Ok(n as usize),
+                Token::Ident(name) => Err(name),
+            })
             .then_ignore(just(Token::RBracket))
             .then(ty.clone())
-            .map(|(size, element_type)| Type::Array {
-                element_type: Box::new(element_type),
-                size,
+            .map(|(size, element_type)| match size {
+                Ok(size) => Type::Array {
+                    element_type: Box::new(element_type),
+                    size,
+                },
+                Err(size_name) => Type::ConstSizedArray {
+                    element_type: Box::new(element_type),
+                    size_name,
+                },
             });
 
+        // Trait 对象: dyn Printable
+        let trait_object = just(Token::Dyn)
+            .ignore_then(ident_parser())
+            .map(Type::TraitObject);
+
+        // 匿名记录类型: { int x, int y }
+        let record_type = ty
+            .clone()
+            .then(ident_parser())
+            .map(|(ty, name)| (name, ty))
+            .separated_by(just(Token::Comma))
+            .at_least(1)
+            .allow_trailing()
+            .delimited_by(just(Token::LBrace), just(Token::RBrace))
+            .map(Type::record);
+
         // 组合
-        let type_without_suffix = choice((vec_type, array_type, basic, ident_or_generic));
+        let type_without_suffix = choice((
+            vec_type,
+            array_type,
+            basic,
+            trait_object,
+            ident_or_generic,
+            record_type,
+        ));
 
         // 后缀类型修饰符: T? (可空) 或 T! (Result)
         type_without_suffix
@@ -99,24 +156,33 @@ pub fn type_parser() -> impl Parser<Token, Type, Error = ParserError> + Clone {
     })
 }
 
-/// 解析字段
+/// 解析字段: int x 或带默认值的 int retries = 3，字段之间可用 `;` 或 `,` 分隔
 pub fn field_parser() -> impl Parser<Token, Field, Error = ParserError> + Clone {
-    type_parser()
+    doc_comment_parser()
+        .ignore_then(type_parser())
         .then(ident_parser())
-        .then_ignore(just(Token::Semicolon).or_not())
-        .map(|(ty, name)| Field { name, ty })
+        .then(
+            just(Token::Eq)
+                .ignore_then(super::expr::expr_parser())
+                .or_not(),
+        )
+        .then_ignore(just(Token::Semicolon).or(just(Token::Comma)).or_not())
+        .map(|((ty, name), default)| Field { name, ty, default })
 }
 
-/// 解析泛型参数列表: <T, U> 或 <T: Bound, U: Bound>
-/// 返回空Vec如果没有泛型参数
-/// 解析泛型参数列表: <T, U> 或 <T: Bound, U: Bound>
+/// 解析泛型参数列表: <T, U> 或 <T: Bound, U: A + B>
 /// 返回空Vec如果没有泛型参数
 pub fn generic_params_parser(
 ) -> impl Parser<Token, Vec<crate::ast::GenericParam>, Error = ParserError> + Clone {
-    // 单个泛型参数: T 或 T: Bound
+    // 单个泛型参数: T、T: Bound 或 T: A + B
+    let bounds = type_parser().separated_by(just(Token::Plus)).at_least(1);
     let single_param = ident_parser()
-        .then(just(Token::Colon).ignore_then(type_parser()).or_not())
-        .map_with_span(|(name, bound), span| crate::ast::GenericParam { span, name, bound });
+        .then(just(Token::Colon).ignore_then(bounds).or_not())
+        .map_with_span(|(name, bounds), span| crate::ast::GenericParam {
+            span,
+            name,
+            bounds: bounds.unwrap_or_default(),
+        });
 
     single_param
         .separated_by(just(Token::Comma))
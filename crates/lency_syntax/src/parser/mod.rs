@@ -11,6 +11,7 @@ pub mod stmt;
 use crate::ast::Program;
 use crate::lexer::Token;
 use chumsky::prelude::*;
+use chumsky::Stream;
 
 pub type ParserError = Simple<Token>;
 
@@ -23,12 +24,57 @@ pub fn program_parser() -> impl Parser<Token, Program, Error = ParserError> {
 }
 
 /// 辅助函数：解析源码字符串
+///
+/// 以 `(Token, Span)` 流喂给 chumsky，AST 节点与语法错误的 Span 均为源码字节偏移。
+/// 词法错误以 [`Simple::custom`] 的形式排在语法错误之前报告。
 pub fn parse(code: &str) -> Result<Program, Vec<ParserError>> {
     use logos::Logos;
-    let tokens: Vec<Token> = Token::lexer(code)
+    let mut lex_errors = Vec::new();
+    let tokens: Vec<(Token, std::ops::Range<usize>)> = Token::lexer(code)
         .spanned()
-        .map(|(tok, _span)| tok.unwrap_or(Token::Error))
+        .map(|(tok, span)| match tok {
+            Ok(tok) => (tok, span),
+            Err(e) => {
+                lex_errors.push(Simple::custom(span.clone(), e.to_string()));
+                (Token::Error, span)
+            }
+        })
         .collect();
+    let tokens = drop_orphan_doc_comments(tokens);
+    let len = code.len();
 
-    program_parser().parse(tokens)
+    let result = program_parser().parse(Stream::from_iter(len..len, tokens.into_iter()));
+    if lex_errors.is_empty() {
+        return result;
+    }
+    // 由 Token::Error 引起的语法错误只是词法错误的连带结果，不再重复报告
+    if let Err(parse_errors) = result {
+        lex_errors.extend(
+            parse_errors
+                .into_iter()
+                .filter(|e| e.found() != Some(&Token::Error)),
+        );
+    }
+    Err(lex_errors)
+}
+
+/// 文档注释之后没有可挂载的声明（位于文件末尾或 `}` 之前）时按普通注释处理
+fn drop_orphan_doc_comments(
+    tokens: Vec<(Token, std::ops::Range<usize>)>,
+) -> Vec<(Token, std::ops::Range<usize>)> {
+    // 从后往前扫描，记录下一个非文档注释 token 是否为 `}` 或输入结束
+    let mut orphan = true;
+    let mut kept: Vec<_> = tokens
+        .into_iter()
+        .rev()
+        .filter(|(tok, _)| match tok {
+            Token::DocComment(_) => !orphan,
+            tok => {
+                orphan = *tok == Token::RBrace;
+                true
+            }
+        })
+        .collect();
+    kept.reverse();
+    kept
 }
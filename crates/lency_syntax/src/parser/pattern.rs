@@ -8,8 +8,17 @@ pub type ParserError = Simple<Token>;
 
 pub fn pattern_parser() -> impl Parser<Token, MatchPattern, Error = ParserError> + Clone {
     recursive(|pat| {
-        // Literal Pattern
-        let literal = literal_value_parser().map(MatchPattern::Literal);
+        // Literal Pattern, or Range Pattern when followed by `..=`
+        let literal = literal_value_parser()
+            .then(
+                just(Token::DotDotEq)
+                    .ignore_then(literal_value_parser())
+                    .or_not(),
+            )
+            .map(|(start, end)| match end {
+                Some(end) => MatchPattern::Range { start, end },
+                None => MatchPattern::Literal(start),
+            });
 
         // Wildcard Pattern
         let wildcard = just(Token::Underscore).to(MatchPattern::Wildcard);
@@ -84,6 +93,17 @@ pub fn pattern_parser() -> impl Parser<Token, MatchPattern, Error = ParserError>
                 }
             });
 
-        choice((wildcard, literal, ident_pat)).boxed()
+        // Or Pattern: `400 | 404`
+        choice((wildcard, literal, ident_pat))
+            .separated_by(just(Token::Pipe))
+            .at_least(1)
+            .map(|mut alternatives| {
+                if alternatives.len() == 1 {
+                    alternatives.remove(0)
+                } else {
+                    MatchPattern::Or(alternatives)
+                }
+            })
+            .boxed()
     })
 }
@@ -3,7 +3,8 @@
 //! 语句解析：变量声明、赋值、return、if、while、block等
 
 use super::expr::expr_parser;
-use super::helpers::{ident_parser, type_parser};
+use super::helpers::{doc_comment_parser, ident_parser, type_parser};
+use super::pattern::pattern_parser;
 use crate::ast::*;
 use crate::lexer::Token;
 use chumsky::prelude::*;
@@ -24,15 +25,21 @@ pub fn stmt_parser() -> impl Parser<Token, Stmt, Error = ParserError> + Clone {
             .delimited_by(just(Token::LBrace), just(Token::RBrace));
 
         // 变量声明: var x: int = 1;
-        let var_decl = just(Token::Var)
-            .ignore_then(ident.clone())
+        // 变量声明: var x = 1（可变）或 let x = 1（不可变）
+        let var_head = just(Token::Var)
+            .to(true)
+            .or(just(Token::Let).to(false))
+            .then(ident.clone())
             .then(just(Token::Colon).ignore_then(type_p.clone()).or_not())
-            .then_ignore(just(Token::Eq))
+            .then_ignore(just(Token::Eq));
+        let var_decl = var_head
+            .clone()
             .then(expr.clone())
             .then_ignore(just(Token::Semicolon).or_not())
-            .map_with_span(|((name, ty), value), span| Stmt::VarDecl {
+            .map_with_span(|(((mutable, name), ty), value), span| Stmt::VarDecl {
                 span,
                 name,
+                mutable,
                 ty,
                 value,
             });
@@ -67,6 +74,24 @@ pub fn stmt_parser() -> impl Parser<Token, Stmt, Error = ParserError> + Clone {
             .then_ignore(just(Token::Semicolon).or_not())
             .map_with_span(|value, span| Stmt::Return { span, value });
 
+        // If let: if let pattern = value { ... } else { ... }
+        let if_let_stmt = just(Token::If)
+            .ignore_then(just(Token::Let))
+            .ignore_then(pattern_parser())
+            .then_ignore(just(Token::Eq))
+            .then(expr.clone())
+            .then(raw_block.clone())
+            .then(just(Token::Else).ignore_then(raw_block.clone()).or_not())
+            .map_with_span(
+                |(((pattern, value), then_block), else_block), span| Stmt::IfLet {
+                    span,
+                    pattern,
+                    value,
+                    then_block,
+                    else_block,
+                },
+            );
+
         // If
         let if_stmt = just(Token::If)
             .ignore_then(expr.clone())
@@ -114,6 +139,7 @@ pub fn stmt_parser() -> impl Parser<Token, Stmt, Error = ParserError> + Clone {
                         Some(Box::new(Stmt::VarDecl {
                             span,
                             name,
+                            mutable: true,
                             ty,
                             value,
                         }))
@@ -154,10 +180,30 @@ pub fn stmt_parser() -> impl Parser<Token, Stmt, Error = ParserError> + Clone {
                     })),
         );
 
-        // Break
+        // 以循环的值初始化: var found = while cond { ... } else default
+        // 需要在 var_decl 之前尝试
+        let loop_stmt = while_stmt.clone().or(for_stmt.clone());
+        let loop_var_decl = var_head
+            .then(loop_stmt)
+            .then_ignore(just(Token::Else))
+            .then(expr.clone())
+            .then_ignore(just(Token::Semicolon).or_not())
+            .map_with_span(|((((mutable, name), ty), loop_stmt), default), span| {
+                Stmt::LoopVarDecl {
+                    span,
+                    name,
+                    mutable,
+                    ty,
+                    loop_stmt: Box::new(loop_stmt),
+                    default,
+                }
+            });
+
+        // Break: break 或 break value
         let break_stmt = just(Token::Break)
+            .ignore_then(expr.clone().or_not())
             .then_ignore(just(Token::Semicolon).or_not())
-            .map_with_span(|_, span| Stmt::Break { span });
+            .map_with_span(|value, span| Stmt::Break { span, value });
 
         // Continue
         let continue_stmt = just(Token::Continue)
@@ -166,16 +212,22 @@ pub fn stmt_parser() -> impl Parser<Token, Stmt, Error = ParserError> + Clone {
 
         // 表达式语句
 
-        var_decl
-            .or(block_stmt)
-            .or(ret)
-            .or(if_stmt)
-            .or(while_stmt)
-            .or(for_stmt)
-            .or(break_stmt)
-            .or(continue_stmt)
-            // 必须放在最后，作为兜底
-            .or(expr_based_stmt)
+        // 函数体内的文档注释没有挂载目标，直接跳过
+        doc_comment_parser()
+            .ignore_then(
+                loop_var_decl
+                    .or(var_decl)
+                    .or(block_stmt)
+                    .or(ret)
+                    .or(if_let_stmt)
+                    .or(if_stmt)
+                    .or(while_stmt)
+                    .or(for_stmt)
+                    .or(break_stmt)
+                    .or(continue_stmt)
+                    // 必须放在最后，作为兜底
+                    .or(expr_based_stmt),
+            )
             .boxed()
     })
 }
@@ -1,9 +1,9 @@
 import { BuiltinSpec } from './types';
 
 export const KEYWORDS = new Set([
-    'var', 'const', 'struct', 'impl', 'trait', 'enum', 'if', 'else', 'while', 'for', 'in',
+    'var', 'const', 'struct', 'packed', 'impl', 'trait', 'enum', 'newtype', 'if', 'else', 'while', 'for', 'in',
     'break', 'continue', 'return', 'import', 'extern', 'match', 'case', 'as', 'null',
-    'true', 'false', 'void', 'int', 'float', 'bool', 'string',
+    'true', 'false', 'void', 'int', 'i8', 'i16', 'i32', 'i64', 'uint', 'u64', 'float', 'bool', 'string',
     // vec / Result 类型关键字（lexer.rs 中的独立 token）
     'vec', 'Ok', 'Err'
 ]);
@@ -63,6 +63,11 @@ export const BUILTIN_SPECS: Record<string, BuiltinSpec> = {
         signatureLabel: 'format(template, args)',
         markdown: '`string format(string template, vec<string> args)`\n\n将占位符 `{}` 依次替换为 args 中的元素。',
         parameters: ['template', 'args']
+    },
+    cast: {
+        signatureLabel: 'cast<T>(value)',
+        markdown: '`T cast<T>(value)`\n\n在 newtype 与其底层类型之间显式转换。',
+        parameters: ['value']
     }
 };
 
@@ -12,7 +12,7 @@ export function collectSymbols(document: vscode.TextDocument): SimpleSymbol[] {
             { regex: /^\s*(?:struct|enum)\s+([a-zA-Z_][a-zA-Z0-9_]*)\b/, kind: vscode.SymbolKind.Struct },
             { regex: /^\s*trait\s+([a-zA-Z_][a-zA-Z0-9_]*)\b/, kind: vscode.SymbolKind.Interface },
             { regex: /^\s*impl\s+([a-zA-Z_][a-zA-Z0-9_]*)\b/, kind: vscode.SymbolKind.Interface },
-            { regex: /^\s*(?:void|int|float|bool|string|[A-Z][a-zA-Z0-9_]*)\s+([a-zA-Z_][a-zA-Z0-9_]*)\s*\(/, kind: vscode.SymbolKind.Function },
+            { regex: /^\s*(?:void|int|i8|i16|i32|i64|uint|u64|float|bool|string|[A-Z][a-zA-Z0-9_]*)\s+([a-zA-Z_][a-zA-Z0-9_]*)\s*\(/, kind: vscode.SymbolKind.Function },
             { regex: /^\s*(?:var|const)\s+([a-zA-Z_][a-zA-Z0-9_]*)\b/, kind: vscode.SymbolKind.Variable }
         ];
 
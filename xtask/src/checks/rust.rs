@@ -41,6 +41,16 @@ pub(crate) fn check_rust() -> Result<()> {
         )
     })?;
 
+    step("Running execution tests (lli)", || {
+        run_cmd(
+            "cargo",
+            &["test", "-p", "lency_driver", "--features", "lli-tests"],
+            false,
+            &[("RUST_MIN_STACK", "8388608")],
+            &[0],
+        )
+    })?;
+
     step(
         "Running .lcy integration tests (Rust compiler path)",
         || {